@@ -0,0 +1,45 @@
+use std::{env, process};
+
+mod radclient;
+mod radperf;
+mod radtest;
+mod replay;
+mod sniff;
+
+fn print_usage(program: &str) {
+    println!(
+        "Usage: {program} <subcommand> [options]
+
+Subcommands:
+    test    send an Access-Request to check the authentication (like radtest)
+    send    send a request of the given attributes and print the response (like radclient)
+    perf    send the requests at a configured rate and report the latency (like radperf)
+    replay  re-send the captured requests to a server and compare the responses
+    sniff   print the captured RADIUS packets (like radsniff)
+
+Run `{program} <subcommand> --help` to see the options of each subcommand."
+    );
+}
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = env::args().collect();
+    let program = args[0].clone();
+
+    let exit_code = match args.get(1).map(|s| s.as_str()) {
+        Some("test") => radtest::run(&format!("{program} test"), &args[2..]).await,
+        Some("send") => radclient::run(&format!("{program} send"), &args[2..]).await,
+        Some("perf") => radperf::run(&format!("{program} perf"), &args[2..]).await,
+        Some("replay") => replay::run(&format!("{program} replay"), &args[2..]).await,
+        Some("sniff") => sniff::run(&format!("{program} sniff"), &args[2..]).await,
+        Some("-h") | Some("--help") => {
+            print_usage(&program);
+            0
+        }
+        _ => {
+            print_usage(&program);
+            2
+        }
+    };
+    process::exit(exit_code);
+}
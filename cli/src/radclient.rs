@@ -0,0 +1,256 @@
+//! `send` subcommand: a radclient equivalent.
+//!
+//! This reads the attributes of a request as the `Name = value` pairs (one per line, or separated by commas) from
+//! the standard input or a file, sends the request of the given type, and prints the response with its attributes.
+//! The names and the values are resolved by the built-in dictionary, and by the FreeRADIUS dictionary files if they
+//! are given. It exits with `0` on Access-Accept and the ACKs, `1` on Access-Reject and the NAKs, and `2` on the
+//! other failures (e.g. timeout).
+
+use std::fs;
+use std::io::{self, Read};
+use std::time::Duration;
+
+use getopts::Options;
+use radius::client::Client;
+use radius::core::code::Code;
+use radius::core::dictionary::Dictionary;
+use radius::core::packet::Packet;
+
+use crate::radtest::{resolve_server, EXIT_ACCEPT, EXIT_FAILURE, EXIT_REJECT};
+
+const DEFAULT_TIMEOUT_SECS: u64 = 3;
+const DEFAULT_RETRIES: u32 = 2;
+
+fn print_usage(program: &str, opts: &Options) {
+    let brief = format!(
+        "Usage: {program} [options] <server[:port]> <auth|acct|status|coa|disconnect> <secret>"
+    );
+    print!("{}", opts.usage(&brief));
+}
+
+pub async fn run(program: &str, args: &[String]) -> i32 {
+    let mut opts = Options::new();
+    opts.optflag("h", "help", "print this help menu");
+    opts.optopt(
+        "f",
+        "file",
+        "read the attributes from the file instead of the standard input",
+        "FILE",
+    );
+    opts.optmulti(
+        "D",
+        "dictionary",
+        "load a FreeRADIUS dictionary file on top of the built-in dictionary",
+        "FILE",
+    );
+    opts.optopt(
+        "",
+        "timeout",
+        "seconds to wait for each response (default: 3)",
+        "SECS",
+    );
+    opts.optopt(
+        "r",
+        "retries",
+        "number of retransmissions on timeout (default: 2)",
+        "NUM",
+    );
+    opts.optopt(
+        "",
+        "deadline",
+        "seconds to wait for the response including all the retransmissions (default: none)",
+        "SECS",
+    );
+    let matches = match opts.parse(args) {
+        Ok(matches) => matches,
+        Err(e) => {
+            eprintln!("{e}");
+            print_usage(program, &opts);
+            return EXIT_FAILURE;
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, &opts);
+        return EXIT_ACCEPT;
+    }
+    if matches.free.len() != 3 {
+        print_usage(program, &opts);
+        return EXIT_FAILURE;
+    }
+
+    match send_request(&matches).await {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            eprintln!("{e}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// Returns the code of the request and the default port of the server for a request type.
+fn parse_request_type(request_type: &str) -> Option<(Code, u16)> {
+    match request_type {
+        "auth" => Some((Code::AccessRequest, 1812)),
+        "acct" => Some((Code::AccountingRequest, 1813)),
+        "status" => Some((Code::StatusServer, 1812)),
+        "coa" => Some((Code::CoARequest, 3799)),
+        "disconnect" => Some((Code::DisconnectRequest, 3799)),
+        _ => None,
+    }
+}
+
+/// Split the input into the `(name, value)` pairs; a value can be quoted by `"`, and the lines that start with `#`
+/// are ignored.
+fn parse_pairs(input: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = vec![];
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        for pair in line.split(',') {
+            let pair = pair.trim();
+            if pair.is_empty() {
+                continue;
+            }
+            let (name, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("invalid attribute: {pair}"))?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            pairs.push((name.trim().to_owned(), value.to_owned()));
+        }
+    }
+    Ok(pairs)
+}
+
+async fn send_request(matches: &getopts::Matches) -> Result<i32, String> {
+    let (server, request_type, secret) = (&matches.free[0], &matches.free[1], &matches.free[2]);
+
+    let (code, default_port) = parse_request_type(request_type)
+        .ok_or_else(|| format!("unsupported request type: {request_type}"))?;
+    let remote_addr = resolve_server(server, default_port).await?;
+    let timeout = match matches.opt_str("timeout") {
+        Some(timeout) => timeout
+            .parse()
+            .map_err(|_| format!("invalid timeout: {timeout}"))?,
+        None => DEFAULT_TIMEOUT_SECS,
+    };
+    let retries = match matches.opt_str("r") {
+        Some(retries) => retries
+            .parse()
+            .map_err(|_| format!("invalid retries: {retries}"))?,
+        None => DEFAULT_RETRIES,
+    };
+    let deadline = match matches.opt_str("deadline") {
+        Some(deadline) => Some(
+            deadline
+                .parse()
+                .map_err(|_| format!("invalid deadline: {deadline}"))?,
+        ),
+        None => None,
+    };
+
+    let mut dictionary = Dictionary::built_in();
+    for path in matches.opt_strs("D") {
+        dictionary
+            .load_file(&path)
+            .map_err(|e| format!("failed to load the dictionary: {e}"))?;
+    }
+    let input = match matches.opt_str("f") {
+        Some(path) => {
+            fs::read_to_string(&path).map_err(|e| format!("failed to read {path}: {e}"))?
+        }
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("failed to read the standard input: {e}"))?;
+            input
+        }
+    };
+
+    let mut packet = Packet::new(code, secret.as_bytes());
+    for (name, value) in parse_pairs(&input)? {
+        dictionary
+            .add(&mut packet, &name, &value)
+            .map_err(|e| e.to_string())?;
+    }
+    if code == Code::StatusServer {
+        // RFC 5997 requires Message-Authenticator
+        packet.add_message_authenticator();
+    }
+
+    let mut client = Client::new(
+        Some(Duration::from_secs(timeout)),
+        Some(Duration::from_secs(timeout)),
+    );
+    client.set_max_retransmissions(retries);
+    client.set_total_timeout(deadline.map(Duration::from_secs));
+
+    println!(
+        "Sent {} Id {} to {}",
+        packet.get_code().string(),
+        packet.get_identifier(),
+        remote_addr
+    );
+    for line in dictionary.format_packet(&packet).lines() {
+        println!("\t{line}");
+    }
+    let response = client
+        .send_packet(&remote_addr, &packet)
+        .await
+        .map_err(|e| e.to_string())?;
+    println!(
+        "Received {} Id {} from {}",
+        response.get_code().string(),
+        response.get_identifier(),
+        remote_addr
+    );
+    for line in dictionary.format_packet(&response).lines() {
+        println!("\t{line}");
+    }
+
+    match response.get_code() {
+        Code::AccessAccept | Code::AccountingResponse | Code::CoAACK | Code::DisconnectACK => {
+            Ok(EXIT_ACCEPT)
+        }
+        Code::AccessReject | Code::CoANAK | Code::DisconnectNAK => Ok(EXIT_REJECT),
+        code => Err(format!("unexpected response: {}", code.string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use radius::core::code::Code;
+
+    use crate::radclient::{parse_pairs, parse_request_type};
+
+    #[test]
+    fn test_parse_pairs() {
+        let pairs = parse_pairs(
+            "# comment\nUser-Name = \"bob\", User-Password = p@ssw0rd\n\nService-Type=Framed-User\n",
+        )
+        .unwrap();
+        let pairs: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("User-Name", "bob"),
+                ("User-Password", "p@ssw0rd"),
+                ("Service-Type", "Framed-User"),
+            ]
+        );
+        assert!(parse_pairs("User-Name").is_err());
+
+        assert_eq!(parse_request_type("coa"), Some((Code::CoARequest, 3799)));
+        assert_eq!(parse_request_type("unknown"), None);
+    }
+}
@@ -0,0 +1,411 @@
+//! `perf` subcommand: a radperf equivalent.
+//!
+//! This sends Access-Requests (or Accounting-Requests) at a configured rate with a limited number of
+//! outstanding requests, and reports the latency percentiles and the breakdown of the responses.
+//! User-Name, User-Password and Acct-Session-Id are generated from templates:
+//! `{n}` is replaced by a random number in `0..users`, and `{seq}` by the sequence number of the request.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use getopts::Options;
+use radius::client::{Client, ClientError};
+use radius::core::code::Code;
+use radius::core::packet::Packet;
+use radius::core::{rfc2865, rfc2866};
+use radius::error::ErrorKind;
+use rand::Rng;
+use tokio::sync::Semaphore;
+use tokio::time::{interval, MissedTickBehavior};
+
+use crate::radtest::resolve_server;
+
+const DEFAULT_AUTH_PORT: u16 = 1812;
+const DEFAULT_ACCT_PORT: u16 = 1813;
+const DEFAULT_RATE: u32 = 100;
+const DEFAULT_CONCURRENCY: usize = 10;
+const DEFAULT_DURATION_SECS: u64 = 10;
+const DEFAULT_TIMEOUT_SECS: u64 = 3;
+const DEFAULT_USERS: u32 = 1000;
+
+const EXIT_SUCCESS: i32 = 0;
+const EXIT_FAILURE: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RequestType {
+    Auth,
+    Acct,
+}
+
+struct Templates {
+    user_name: String,
+    password: String,
+    session_id: String,
+    users: u32,
+}
+
+impl Templates {
+    fn make_packet(
+        &self,
+        request_type: RequestType,
+        secret: &[u8],
+        seq: u64,
+    ) -> Result<Packet, String> {
+        let n = rand::thread_rng().gen_range(0..self.users);
+        let expand = |template: &str| {
+            template
+                .replace("{n}", &n.to_string())
+                .replace("{seq}", &seq.to_string())
+        };
+
+        match request_type {
+            RequestType::Auth => {
+                let mut packet = Packet::new(Code::AccessRequest, secret);
+                rfc2865::add_user_name(&mut packet, &expand(&self.user_name));
+                rfc2865::add_user_password(&mut packet, expand(&self.password).as_bytes())
+                    .map_err(|e| e.to_string())?;
+                Ok(packet)
+            }
+            RequestType::Acct => {
+                let mut packet = Packet::new(Code::AccountingRequest, secret);
+                rfc2866::add_acct_status_type(&mut packet, rfc2866::ACCT_STATUS_TYPE_START);
+                rfc2866::add_acct_session_id(&mut packet, &expand(&self.session_id));
+                rfc2865::add_user_name(&mut packet, &expand(&self.user_name));
+                Ok(packet)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    latencies: Vec<Duration>,
+    accepted: u64,
+    rejected: u64,
+    accounting_responses: u64,
+    other_responses: u64,
+    timeouts: u64,
+    errors: u64,
+}
+
+impl Stats {
+    fn record(&mut self, result: Result<Code, ClientError>, latency: Duration) {
+        match result {
+            Ok(code) => {
+                self.latencies.push(latency);
+                match code {
+                    Code::AccessAccept => self.accepted += 1,
+                    Code::AccessReject => self.rejected += 1,
+                    Code::AccountingResponse => self.accounting_responses += 1,
+                    _ => self.other_responses += 1,
+                }
+            }
+            Err(e) if e.get_kind() == ErrorKind::Timeout => self.timeouts += 1,
+            Err(_) => self.errors += 1,
+        }
+    }
+
+    fn sent(&self) -> u64 {
+        self.latencies.len() as u64 + self.timeouts + self.errors
+    }
+
+    /// Returns the latency at the given percentile (nearest-rank) of the responses.
+    fn percentile(&mut self, percentile: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        self.latencies.sort_unstable();
+        let rank = (percentile / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        Some(self.latencies[rank.clamp(1, self.latencies.len()) - 1])
+    }
+}
+
+fn print_usage(program: &str, opts: &Options) {
+    let brief = format!("Usage: {program} [options] <server[:port]> <secret>");
+    print!("{}", opts.usage(&brief));
+}
+
+pub async fn run(program: &str, args: &[String]) -> i32 {
+    let mut opts = Options::new();
+    opts.optflag("h", "help", "print this help menu");
+    opts.optopt("t", "type", "request type: auth (default) or acct", "TYPE");
+    opts.optopt("r", "rate", "requests per second (default: 100)", "NUM");
+    opts.optopt(
+        "c",
+        "concurrency",
+        "maximum number of outstanding requests (default: 10)",
+        "NUM",
+    );
+    opts.optopt(
+        "d",
+        "duration",
+        "seconds to send the requests (default: 10)",
+        "SECS",
+    );
+    opts.optopt(
+        "n",
+        "requests",
+        "total number of requests; it overrides the duration",
+        "NUM",
+    );
+    opts.optopt(
+        "",
+        "timeout",
+        "seconds to wait for each response (default: 3)",
+        "SECS",
+    );
+    opts.optopt(
+        "u",
+        "user-name",
+        "User-Name template (default: user{n})",
+        "TEMPLATE",
+    );
+    opts.optopt(
+        "p",
+        "password",
+        "User-Password template (default: password)",
+        "TEMPLATE",
+    );
+    opts.optopt(
+        "",
+        "session-id",
+        "Acct-Session-Id template (default: session-{seq})",
+        "TEMPLATE",
+    );
+    opts.optopt(
+        "",
+        "users",
+        "the range of {n} in the templates (default: 1000)",
+        "NUM",
+    );
+    let matches = match opts.parse(args) {
+        Ok(matches) => matches,
+        Err(e) => {
+            eprintln!("{e}");
+            print_usage(program, &opts);
+            return EXIT_FAILURE;
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, &opts);
+        return EXIT_SUCCESS;
+    }
+    if matches.free.len() != 2 {
+        print_usage(program, &opts);
+        return EXIT_FAILURE;
+    }
+
+    match perf(&matches).await {
+        Ok(()) => EXIT_SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+fn parse_opt<T: std::str::FromStr>(
+    matches: &getopts::Matches,
+    name: &str,
+    default: T,
+) -> Result<T, String> {
+    match matches.opt_str(name) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("invalid value of --{name}: {value}")),
+        None => Ok(default),
+    }
+}
+
+async fn perf(matches: &getopts::Matches) -> Result<(), String> {
+    let (server, secret) = (&matches.free[0], matches.free[1].as_bytes());
+
+    let request_type = match matches.opt_str("type").as_deref() {
+        None | Some("auth") => RequestType::Auth,
+        Some("acct") => RequestType::Acct,
+        Some(request_type) => return Err(format!("unsupported request type: {request_type}")),
+    };
+    let default_port = match request_type {
+        RequestType::Auth => DEFAULT_AUTH_PORT,
+        RequestType::Acct => DEFAULT_ACCT_PORT,
+    };
+    let remote_addr = resolve_server(server, default_port).await?;
+    let rate: u32 = parse_opt(matches, "rate", DEFAULT_RATE)?;
+    let concurrency: usize = parse_opt(matches, "concurrency", DEFAULT_CONCURRENCY)?;
+    let duration = Duration::from_secs(parse_opt(matches, "duration", DEFAULT_DURATION_SECS)?);
+    let total_requests: Option<u64> = match matches.opt_str("requests") {
+        Some(_) => Some(parse_opt(matches, "requests", 0)?),
+        None => None,
+    };
+    let timeout = Duration::from_secs(parse_opt(matches, "timeout", DEFAULT_TIMEOUT_SECS)?);
+    let templates = Templates {
+        user_name: matches
+            .opt_str("user-name")
+            .unwrap_or_else(|| "user{n}".to_owned()),
+        password: matches
+            .opt_str("password")
+            .unwrap_or_else(|| "password".to_owned()),
+        session_id: matches
+            .opt_str("session-id")
+            .unwrap_or_else(|| "session-{seq}".to_owned()),
+        users: parse_opt(matches, "users", DEFAULT_USERS)?,
+    };
+    if rate == 0 || concurrency == 0 || templates.users == 0 {
+        return Err("rate, concurrency and users must be positive".to_owned());
+    }
+
+    println!(
+        "Sending {} requests to {} at {} req/s with concurrency {}",
+        match request_type {
+            RequestType::Auth => "Access",
+            RequestType::Acct => "Accounting",
+        },
+        remote_addr,
+        rate,
+        concurrency
+    );
+
+    let client = Arc::new(Client::new(Some(timeout), Some(timeout)));
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let stats = Arc::new(Mutex::new(Stats::default()));
+    let mut ticker = interval(Duration::from_secs_f64(1.0 / f64::from(rate)));
+    // when the concurrency limit holds the requests back, the lost ticks are not sent in a burst
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let started_at = Instant::now();
+    let mut seq = 0;
+    loop {
+        match total_requests {
+            Some(total_requests) if seq >= total_requests => break,
+            None if started_at.elapsed() >= duration => break,
+            _ => {}
+        }
+        ticker.tick().await;
+        let permit = semaphore.clone().acquire_owned().await.unwrap();
+
+        let packet = templates.make_packet(request_type, secret, seq)?;
+        seq += 1;
+        let (client, stats) = (client.clone(), stats.clone());
+        tokio::spawn(async move {
+            let result = send(&client, remote_addr, &packet).await;
+            drop(permit);
+            let (result, latency) = result;
+            stats.lock().unwrap().record(result, latency);
+        });
+    }
+    let sending_time = started_at.elapsed();
+    // wait for all of the outstanding requests
+    let _ = semaphore.acquire_many(concurrency as u32).await.unwrap();
+
+    let mut stats = stats.lock().unwrap();
+    print_report(&mut stats, sending_time);
+    Ok(())
+}
+
+async fn send(
+    client: &Client,
+    remote_addr: SocketAddr,
+    packet: &Packet,
+) -> (Result<Code, ClientError>, Duration) {
+    let sent_at = Instant::now();
+    let result = client
+        .send_packet(&remote_addr, packet)
+        .await
+        .map(|response| response.get_code());
+    (result, sent_at.elapsed())
+}
+
+fn print_report(stats: &mut Stats, sending_time: Duration) {
+    let sent = stats.sent();
+    println!();
+    println!(
+        "Sent {} requests in {:.2}s ({:.1} req/s)",
+        sent,
+        sending_time.as_secs_f64(),
+        sent as f64 / sending_time.as_secs_f64()
+    );
+    println!("    Access-Accept        {}", stats.accepted);
+    println!("    Access-Reject        {}", stats.rejected);
+    println!("    Accounting-Response  {}", stats.accounting_responses);
+    println!("    Other responses      {}", stats.other_responses);
+    println!("    Timeouts             {}", stats.timeouts);
+    println!("    Errors               {}", stats.errors);
+
+    let format_latency = |latency: Option<Duration>| match latency {
+        Some(latency) => format!("{:.3}ms", latency.as_secs_f64() * 1000.0),
+        None => "-".to_owned(),
+    };
+    println!("Latency");
+    for (label, percentile) in [
+        ("min", 0.0),
+        ("p50", 50.0),
+        ("p90", 90.0),
+        ("p99", 99.0),
+        ("max", 100.0),
+    ] {
+        println!(
+            "    {:<5}{}",
+            label,
+            format_latency(stats.percentile(percentile))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use radius::client::{ClientError, TimeoutDetails};
+    use radius::core::code::Code;
+    use radius::core::rfc2865;
+
+    use crate::radperf::{RequestType, Stats, Templates};
+
+    #[test]
+    fn test_make_packet() {
+        let templates = Templates {
+            user_name: "user{n}".to_owned(),
+            password: "pass-{seq}".to_owned(),
+            session_id: "session-{seq}".to_owned(),
+            users: 1,
+        };
+        let packet = templates
+            .make_packet(RequestType::Auth, b"secret", 42)
+            .unwrap();
+        assert_eq!(
+            rfc2865::lookup_user_name(&packet).unwrap().unwrap(),
+            "user0"
+        );
+        assert_eq!(
+            rfc2865::lookup_user_password(&packet).unwrap().unwrap(),
+            b"pass-42"
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.percentile(50.0), None);
+        for millis in (1..=10).rev() {
+            stats.record(Ok(Code::AccessAccept), Duration::from_millis(millis));
+        }
+        stats.record(
+            Err(ClientError::SocketTimeoutError(TimeoutDetails::new(
+                Duration::from_secs(3),
+                1,
+                Duration::from_secs(3),
+            ))),
+            Duration::from_secs(3),
+        );
+
+        assert_eq!(stats.sent(), 11);
+        assert_eq!(stats.accepted, 10);
+        assert_eq!(stats.timeouts, 1);
+        assert_eq!(stats.percentile(0.0), Some(Duration::from_millis(1)));
+        assert_eq!(stats.percentile(50.0), Some(Duration::from_millis(5)));
+        assert_eq!(stats.percentile(90.0), Some(Duration::from_millis(9)));
+        assert_eq!(stats.percentile(100.0), Some(Duration::from_millis(10)));
+    }
+}
@@ -0,0 +1,331 @@
+//! `test` subcommand: a radtest equivalent.
+//!
+//! This sends an Access-Request with the given credentials and exits with `0` on Access-Accept,
+//! `1` on Access-Reject and `2` on the other failures (e.g. timeout), so it can be used by monitoring scripts.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use getopts::Options;
+use radius::client::Client;
+use radius::core::code::Code;
+use radius::core::packet::Packet;
+use radius::core::rfc2865;
+
+const DEFAULT_AUTH_PORT: u16 = 1812;
+const DEFAULT_TIMEOUT_SECS: u64 = 3;
+const DEFAULT_RETRIES: u32 = 2;
+
+pub const EXIT_ACCEPT: i32 = 0;
+pub const EXIT_REJECT: i32 = 1;
+pub const EXIT_FAILURE: i32 = 2;
+
+fn print_usage(program: &str, opts: &Options) {
+    let brief =
+        format!("Usage: {program} [options] <user> <password> <server[:port]> <nas-port> <secret>");
+    print!("{}", opts.usage(&brief));
+}
+
+pub async fn run(program: &str, args: &[String]) -> i32 {
+    let mut opts = Options::new();
+    opts.optflag("h", "help", "print this help menu");
+    opts.optopt(
+        "t",
+        "auth-type",
+        "authentication type: pap (default) or chap",
+        "TYPE",
+    );
+    opts.optopt(
+        "",
+        "timeout",
+        "seconds to wait for each response (default: 3)",
+        "SECS",
+    );
+    opts.optopt(
+        "r",
+        "retries",
+        "number of retransmissions on timeout (default: 2)",
+        "NUM",
+    );
+    opts.optopt(
+        "",
+        "deadline",
+        "seconds to wait for the response including all the retransmissions (default: none)",
+        "SECS",
+    );
+    opts.optopt("", "nas-ip", "NAS-IP-Address to send", "ADDRESS");
+    let matches = match opts.parse(args) {
+        Ok(matches) => matches,
+        Err(e) => {
+            eprintln!("{e}");
+            print_usage(program, &opts);
+            return EXIT_FAILURE;
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, &opts);
+        return EXIT_ACCEPT;
+    }
+    if matches.free.len() != 5 {
+        print_usage(program, &opts);
+        return EXIT_FAILURE;
+    }
+
+    match send_request(&matches).await {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            eprintln!("{e}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+async fn send_request(matches: &getopts::Matches) -> Result<i32, String> {
+    let (user, password, server, nas_port, secret) = (
+        &matches.free[0],
+        &matches.free[1],
+        &matches.free[2],
+        &matches.free[3],
+        &matches.free[4],
+    );
+
+    let remote_addr = resolve_server(server, DEFAULT_AUTH_PORT).await?;
+    let nas_port: u32 = nas_port
+        .parse()
+        .map_err(|_| format!("invalid NAS port: {nas_port}"))?;
+    let timeout = match matches.opt_str("timeout") {
+        Some(timeout) => timeout
+            .parse()
+            .map_err(|_| format!("invalid timeout: {timeout}"))?,
+        None => DEFAULT_TIMEOUT_SECS,
+    };
+    let retries = match matches.opt_str("r") {
+        Some(retries) => retries
+            .parse()
+            .map_err(|_| format!("invalid retries: {retries}"))?,
+        None => DEFAULT_RETRIES,
+    };
+    let deadline = match matches.opt_str("deadline") {
+        Some(deadline) => Some(
+            deadline
+                .parse()
+                .map_err(|_| format!("invalid deadline: {deadline}"))?,
+        ),
+        None => None,
+    };
+
+    let mut packet = Packet::new(Code::AccessRequest, secret.as_bytes());
+    rfc2865::add_user_name(&mut packet, user);
+    add_credentials(&mut packet, password, matches.opt_str("t").as_deref())?;
+    rfc2865::add_nas_port(&mut packet, nas_port);
+    if let Some(nas_ip) = matches.opt_str("nas-ip") {
+        let nas_ip = nas_ip
+            .parse()
+            .map_err(|_| format!("invalid NAS-IP-Address: {nas_ip}"))?;
+        rfc2865::add_nas_ip_address(&mut packet, &nas_ip);
+    }
+
+    let mut client = Client::new(
+        Some(Duration::from_secs(timeout)),
+        Some(Duration::from_secs(timeout)),
+    );
+    client.set_max_retransmissions(retries);
+    client.set_total_timeout(deadline.map(Duration::from_secs));
+
+    println!(
+        "Sent {} Id {} to {}",
+        packet.get_code().string(),
+        packet.get_identifier(),
+        remote_addr
+    );
+    let response = client
+        .send_packet(&remote_addr, &packet)
+        .await
+        .map_err(|e| e.to_string())?;
+    println!(
+        "Received {} Id {} from {}",
+        response.get_code().string(),
+        response.get_identifier(),
+        remote_addr
+    );
+    for reply_message in rfc2865::lookup_all_reply_message(&response).unwrap_or_default() {
+        println!("\tReply-Message = \"{reply_message}\"");
+    }
+
+    exit_code(response.get_code())
+}
+
+/// Returns the exit code of the code of a response.
+fn exit_code(code: Code) -> Result<i32, String> {
+    match code {
+        Code::AccessAccept => Ok(EXIT_ACCEPT),
+        Code::AccessReject => Ok(EXIT_REJECT),
+        code => Err(format!("unexpected response: {}", code.string())),
+    }
+}
+
+/// Add User-Password (`pap`, the default) or CHAP-Password (`chap`) of the password to a request.
+fn add_credentials(
+    packet: &mut Packet,
+    password: &str,
+    auth_type: Option<&str>,
+) -> Result<(), String> {
+    match auth_type {
+        None | Some("pap") => {
+            rfc2865::add_user_password(packet, password.as_bytes()).map_err(|e| e.to_string())?
+        }
+        Some("chap") => {
+            // the Request Authenticator is used as the CHAP challenge
+            let chap_id = packet.get_identifier();
+            let mut chap_password = vec![chap_id];
+            chap_password.extend(
+                md5::compute(
+                    [
+                        &[chap_id],
+                        password.as_bytes(),
+                        packet.get_authenticator().as_slice(),
+                    ]
+                    .concat(),
+                )
+                .0,
+            );
+            rfc2865::add_chap_password(packet, &chap_password);
+        }
+        Some(auth_type) => return Err(format!("unsupported authentication type: {auth_type}")),
+    }
+    Ok(())
+}
+
+/// Resolve `host[:port]` to an address; the port defaults to `default_port`.
+pub async fn resolve_server(server: &str, default_port: u16) -> Result<SocketAddr, String> {
+    if let Ok(addr) = server.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    let server = match server.parse::<std::net::IpAddr>() {
+        Ok(ip) => SocketAddr::new(ip, default_port).to_string(),
+        Err(_) if server.contains(':') => server.to_owned(),
+        Err(_) => format!("{server}:{default_port}"),
+    };
+
+    let resolved = tokio::net::lookup_host(&server).await;
+    match resolved {
+        Ok(mut addrs) => addrs
+            .next()
+            .ok_or_else(|| format!("failed to resolve the server: {server}")),
+        Err(e) => Err(format!("failed to resolve the server {server}: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use radius::core::chap;
+    use radius::core::code::Code;
+    use radius::core::packet::Packet;
+    use radius::core::rfc2865;
+    use radius::testing::{MockResponder, MockRule, MockServer};
+
+    use crate::radtest::{
+        add_credentials, exit_code, resolve_server, run, EXIT_ACCEPT, EXIT_FAILURE, EXIT_REJECT,
+    };
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(exit_code(Code::AccessAccept), Ok(EXIT_ACCEPT));
+        assert_eq!(exit_code(Code::AccessReject), Ok(EXIT_REJECT));
+        assert!(exit_code(Code::AccessChallenge).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_server() {
+        let addr = |s: &str| s.parse::<SocketAddr>().unwrap();
+        assert_eq!(
+            resolve_server("127.0.0.1", 1812).await,
+            Ok(addr("127.0.0.1:1812"))
+        );
+        assert_eq!(
+            resolve_server("127.0.0.1:1645", 1812).await,
+            Ok(addr("127.0.0.1:1645"))
+        );
+        assert_eq!(resolve_server("::1", 1812).await, Ok(addr("[::1]:1812")));
+        assert_eq!(
+            resolve_server("[::1]:1645", 1812).await,
+            Ok(addr("[::1]:1645"))
+        );
+        assert_eq!(
+            resolve_server("localhost", 1812)
+                .await
+                .map(|addr| addr.port()),
+            Ok(1812)
+        );
+        assert!(resolve_server("localhost:port", 1812).await.is_err());
+    }
+
+    #[test]
+    fn test_add_credentials() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        add_credentials(&mut packet, "p@ssw0rd", Some("chap")).unwrap();
+        assert_eq!(
+            chap::verify_chap_password(&packet, b"p@ssw0rd"),
+            Some(Ok(true))
+        );
+        assert_eq!(
+            chap::verify_chap_password(&packet, b"wrong"),
+            Some(Ok(false))
+        );
+        assert_eq!(
+            rfc2865::lookup_chap_password(&packet).unwrap()[0],
+            packet.get_identifier()
+        );
+
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        add_credentials(&mut packet, "p@ssw0rd", None).unwrap();
+        assert_eq!(
+            rfc2865::lookup_user_password(&packet).unwrap().unwrap(),
+            b"p@ssw0rd"
+        );
+
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        assert!(add_credentials(&mut packet, "p@ssw0rd", Some("mschap")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        let responder = MockResponder::new();
+        responder.add_rule(MockRule::respond(Code::AccessAccept).when_chap_password(b"p@ssw0rd"));
+        responder.add_rule(MockRule::respond(Code::AccessReject));
+        let server = MockServer::start(responder.clone(), b"secret")
+            .await
+            .unwrap();
+
+        let args = |password: &str| -> Vec<String> {
+            [
+                "--auth-type",
+                "chap",
+                "bob",
+                password,
+                &server.get_address().to_string(),
+                "1",
+                "secret",
+            ]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect()
+        };
+        assert_eq!(run("radtest", &args("p@ssw0rd")).await, EXIT_ACCEPT);
+        assert_eq!(run("radtest", &args("wrong")).await, EXIT_REJECT);
+        assert_eq!(run("radtest", &args("wrong")[..4]).await, EXIT_FAILURE);
+
+        let requests = responder.get_received_requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            rfc2865::lookup_user_name(&requests[0]).unwrap().unwrap(),
+            "bob"
+        );
+        assert_eq!(rfc2865::lookup_nas_port(&requests[0]).unwrap().unwrap(), 1);
+
+        server.shutdown().await;
+    }
+}
@@ -0,0 +1,205 @@
+//! `replay` subcommand: re-send the captured requests to a server.
+//!
+//! This replays the Access-Requests and Accounting-Requests in a pcap/pcapng file against the target server
+//! and prints whether each response matches the captured one. It exits with `1` if any response doesn't match,
+//! so it can be used for regression and migration testing.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use getopts::Options;
+use radius::capture::{PcapReader, ReplayPacing, Replayer, DEFAULT_RADIUS_PORTS};
+use radius::client::Client;
+use radius::core::rfc2865;
+use radius::server::{SecretProvider, SecretProviderError};
+
+use crate::radtest::resolve_server;
+use crate::sniff::parse_ports;
+
+const DEFAULT_PORT: u16 = 1812;
+const DEFAULT_TIMEOUT_SECS: u64 = 3;
+
+const EXIT_SUCCESS: i32 = 0;
+const EXIT_MISMATCH: i32 = 1;
+const EXIT_FAILURE: i32 = 2;
+
+struct FixedSecretProvider {
+    secret: Vec<u8>,
+}
+
+impl SecretProvider for FixedSecretProvider {
+    fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        Ok(self.secret.clone())
+    }
+}
+
+fn print_usage(program: &str, opts: &Options) {
+    let brief = format!("Usage: {program} [options] <capture-file> <server[:port]> <secret>");
+    print!("{}", opts.usage(&brief));
+}
+
+pub async fn run(program: &str, args: &[String]) -> i32 {
+    let mut opts = Options::new();
+    opts.optflag("h", "help", "print this help menu");
+    opts.optopt(
+        "s",
+        "source-secret",
+        "shared-secret of the captured requests (default: the secret of the server)",
+        "SECRET",
+    );
+    opts.optopt(
+        "",
+        "speed",
+        "speed factor of the replay; 0 sends the requests one by one without waiting (default: 1)",
+        "FACTOR",
+    );
+    opts.optopt(
+        "p",
+        "ports",
+        "comma separated server ports in the capture (default: 1812,1813,1645,1646,3799)",
+        "PORTS",
+    );
+    opts.optopt(
+        "",
+        "timeout",
+        "seconds to wait for each response (default: 3)",
+        "SECS",
+    );
+    opts.optopt(
+        "",
+        "nas-ip",
+        "rewrite NAS-IP-Address of the requests",
+        "ADDRESS",
+    );
+    opts.optopt(
+        "",
+        "nas-identifier",
+        "rewrite NAS-Identifier of the requests",
+        "NAME",
+    );
+    let matches = match opts.parse(args) {
+        Ok(matches) => matches,
+        Err(e) => {
+            eprintln!("{e}");
+            print_usage(program, &opts);
+            return EXIT_FAILURE;
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, &opts);
+        return EXIT_SUCCESS;
+    }
+    if matches.free.len() != 3 {
+        print_usage(program, &opts);
+        return EXIT_FAILURE;
+    }
+
+    match replay(&matches).await {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            eprintln!("{e}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+async fn replay(matches: &getopts::Matches) -> Result<i32, String> {
+    let (path, server, secret) = (&matches.free[0], &matches.free[1], &matches.free[2]);
+
+    let target_addr = resolve_server(server, DEFAULT_PORT).await?;
+    let source_secret = matches.opt_str("s").unwrap_or_else(|| secret.to_owned());
+    let pacing = match matches.opt_str("speed") {
+        Some(speed) => match speed.parse::<f64>() {
+            Ok(0.0) => ReplayPacing::AsFastAsPossible,
+            Ok(speed) if speed > 0.0 => ReplayPacing::Accelerated(speed),
+            _ => return Err(format!("invalid speed: {speed}")),
+        },
+        None => ReplayPacing::Original,
+    };
+    let ports = match matches.opt_str("p") {
+        Some(ports) => parse_ports(&ports)?,
+        None => DEFAULT_RADIUS_PORTS.to_vec(),
+    };
+    let timeout = match matches.opt_str("timeout") {
+        Some(timeout) => timeout
+            .parse()
+            .map_err(|_| format!("invalid timeout: {timeout}"))?,
+        None => DEFAULT_TIMEOUT_SECS,
+    };
+
+    let client = Client::new(
+        Some(Duration::from_secs(timeout)),
+        Some(Duration::from_secs(timeout)),
+    );
+    let mut replayer = Replayer::new(client, target_addr, secret.as_bytes());
+    replayer.set_pacing(pacing);
+    replayer.set_server_ports(&ports);
+    if let Some(nas_ip) = matches.opt_str("nas-ip") {
+        let nas_ip = nas_ip
+            .parse()
+            .map_err(|_| format!("invalid NAS-IP-Address: {nas_ip}"))?;
+        replayer.add_rewrite(move |packet| {
+            packet.delete(rfc2865::NAS_IP_ADDRESS_TYPE);
+            rfc2865::add_nas_ip_address(packet, &nas_ip);
+        });
+    }
+    if let Some(nas_identifier) = matches.opt_str("nas-identifier") {
+        replayer.add_rewrite(move |packet| {
+            packet.delete(rfc2865::NAS_IDENTIFIER_TYPE);
+            rfc2865::add_nas_identifier(packet, &nas_identifier);
+        });
+    }
+
+    let file = File::open(path).map_err(|e| format!("failed to open {path}: {e}"))?;
+    let mut reader = PcapReader::new(BufReader::new(file)).map_err(|e| e.to_string())?;
+    reader.set_ports(&ports);
+    let outcomes = replayer
+        .replay(
+            reader,
+            &FixedSecretProvider {
+                secret: source_secret.into_bytes(),
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut mismatches = 0;
+    for outcome in &outcomes {
+        let original = outcome
+            .get_original_response_code()
+            .map_or("(no response)", |code| code.string());
+        let result = match outcome.get_result() {
+            Ok(code) => code.string().to_owned(),
+            Err(e) => format!("error: {e}"),
+        };
+        let status = if outcome.is_mismatch() {
+            mismatches += 1;
+            "MISMATCH"
+        } else {
+            "OK"
+        };
+        println!(
+            "{} {} {}: captured {}, replayed {} [{}]",
+            outcome.get_captured_at().format("%Y-%m-%d %H:%M:%S%.6f"),
+            outcome.get_client_addr(),
+            outcome.get_request_code().string(),
+            original,
+            result,
+            status
+        );
+    }
+    println!(
+        "{} requests replayed, {} mismatches",
+        outcomes.len(),
+        mismatches
+    );
+
+    Ok(if mismatches > 0 {
+        EXIT_MISMATCH
+    } else {
+        EXIT_SUCCESS
+    })
+}
@@ -0,0 +1,175 @@
+//! `sniff` subcommand: a focused radsniff equivalent.
+//!
+//! This prints the RADIUS packets on the wire (or in a pcap/pcapng file) with their dictionary-resolved attributes.
+//! If the shared-secret is given, User-Password of Access-Request is decrypted and printed as well.
+
+use std::fs::File;
+use std::io::BufReader;
+
+use getopts::Options;
+use radius::capture::{CapturedDatagram, PcapReader, DEFAULT_RADIUS_PORTS};
+use radius::core::code::Code;
+use radius::core::rfc2865;
+use radius::export::AccountingRecord;
+
+const EXIT_SUCCESS: i32 = 0;
+const EXIT_FAILURE: i32 = 2;
+
+fn print_usage(program: &str, opts: &Options) {
+    let brief = format!("Usage: {program} [options]");
+    print!("{}", opts.usage(&brief));
+}
+
+pub async fn run(program: &str, args: &[String]) -> i32 {
+    let mut opts = Options::new();
+    opts.optflag("h", "help", "print this help menu");
+    opts.optopt(
+        "i",
+        "interface",
+        "interface to capture on (default: all of the interfaces)",
+        "NAME",
+    );
+    opts.optopt(
+        "r",
+        "read",
+        "read the packets from a pcap/pcapng file instead of the interfaces",
+        "FILE",
+    );
+    opts.optopt(
+        "s",
+        "secret",
+        "shared-secret to decrypt User-Password",
+        "SECRET",
+    );
+    opts.optopt(
+        "p",
+        "ports",
+        "comma separated UDP ports to capture (default: 1812,1813,1645,1646,3799)",
+        "PORTS",
+    );
+    let matches = match opts.parse(args) {
+        Ok(matches) => matches,
+        Err(e) => {
+            eprintln!("{e}");
+            print_usage(program, &opts);
+            return EXIT_FAILURE;
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, &opts);
+        return EXIT_SUCCESS;
+    }
+    if !matches.free.is_empty() {
+        print_usage(program, &opts);
+        return EXIT_FAILURE;
+    }
+
+    let ports = match matches.opt_str("p") {
+        Some(ports) => match parse_ports(&ports) {
+            Ok(ports) => ports,
+            Err(e) => {
+                eprintln!("{e}");
+                return EXIT_FAILURE;
+            }
+        },
+        None => DEFAULT_RADIUS_PORTS.to_vec(),
+    };
+    let secret = matches.opt_str("s");
+    let secret = secret.as_ref().map(|s| s.as_bytes());
+
+    let result = match matches.opt_str("r") {
+        Some(path) => read_file(&path, &ports, secret),
+        None => capture(matches.opt_str("i").as_deref(), &ports, secret).await,
+    };
+    match result {
+        Ok(()) => EXIT_SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+pub fn parse_ports(ports: &str) -> Result<Vec<u16>, String> {
+    ports
+        .split(',')
+        .map(|port| {
+            port.trim()
+                .parse()
+                .map_err(|_| format!("invalid port: {port}"))
+        })
+        .collect()
+}
+
+fn read_file(path: &str, ports: &[u16], secret: Option<&[u8]>) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| format!("failed to open {path}: {e}"))?;
+    let mut reader = PcapReader::new(BufReader::new(file)).map_err(|e| e.to_string())?;
+    reader.set_ports(ports);
+    for datagram in reader {
+        print_datagram(&datagram.map_err(|e| e.to_string())?, ports, secret);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+async fn capture(
+    interface: Option<&str>,
+    ports: &[u16],
+    secret: Option<&[u8]>,
+) -> Result<(), String> {
+    let mut capture = radius::capture::LiveCapture::open(interface).map_err(|e| e.to_string())?;
+    capture.set_ports(ports);
+    loop {
+        let datagram = capture.next_datagram().await.map_err(|e| e.to_string())?;
+        print_datagram(&datagram, ports, secret);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn capture(
+    _interface: Option<&str>,
+    _ports: &[u16],
+    _secret: Option<&[u8]>,
+) -> Result<(), String> {
+    Err("live capture is only supported on Linux; use `--read` with a capture file".to_owned())
+}
+
+fn print_datagram(datagram: &CapturedDatagram, ports: &[u16], secret: Option<&[u8]>) {
+    let timestamp = datagram.get_timestamp().format("%Y-%m-%d %H:%M:%S%.6f");
+    let (source, destination) = (datagram.get_source(), datagram.get_destination());
+
+    // the packet is decoded without the secret unless it is given; only the password decryption needs that
+    let packet = match datagram.decode(secret.unwrap_or_default()) {
+        Ok(packet) => packet,
+        Err(e) => {
+            println!("{timestamp} {source} -> {destination} malformed RADIUS packet: {e}\n");
+            return;
+        }
+    };
+    println!(
+        "{timestamp} {source} -> {destination} {} Id {} Length {}",
+        packet.get_code().string(),
+        packet.get_identifier(),
+        datagram.get_payload().len(),
+    );
+
+    let record = AccountingRecord::from_packet(
+        &packet,
+        datagram.get_client_addr(ports),
+        datagram.get_timestamp(),
+    );
+    for (name, value) in record.get_attributes() {
+        println!("    {name} = {value}");
+    }
+    if secret.is_some() && packet.get_code() == Code::AccessRequest {
+        match rfc2865::lookup_user_password(&packet) {
+            Some(Ok(password)) => {
+                println!("    User-Password = {}", String::from_utf8_lossy(&password))
+            }
+            Some(Err(e)) => println!("    User-Password = <failed to decrypt: {e}>"),
+            None => {}
+        }
+    }
+    println!();
+}
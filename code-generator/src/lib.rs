@@ -0,0 +1,1634 @@
+//! The code generator of the attribute modules (e.g. `radius::core::rfc2865`) from the dictionary files of the
+//! FreeRADIUS format.
+//!
+//! `Generator` is also for the other crates: a build script (`build.rs`) can generate the typed functions of their own
+//! vendor dictionaries, just like the bundled `radius::core::cisco` module.
+//!
+//! ```no_run
+//! // build.rs
+//! println!("cargo:rerun-if-changed=dicts/dictionary.acme");
+//! radius_code_generator::Generator::new()
+//!     .dictionary("dicts/dictionary.acme")
+//!     .generate()
+//!     .unwrap();
+//! ```
+//!
+//! Then the crate includes `<name>.rs` of `OUT_DIR` for each `dictionary.<name>`:
+//!
+//! ```ignore
+//! pub mod acme {
+//!     include!(concat!(env!("OUT_DIR"), "/acme.rs"));
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::{env, fs, io};
+
+use inflector::Inflector;
+use regex::Regex;
+use thiserror::Error;
+
+const ATTRIBUTE_KIND: &str = "ATTRIBUTE";
+const VALUE_KIND: &str = "VALUE";
+const VENDOR_KIND: &str = "VENDOR";
+const BEGIN_VENDOR_KIND: &str = "BEGIN-VENDOR";
+const END_VENDOR_KIND: &str = "END-VENDOR";
+
+const RADIUS_VALUE_TYPE: &str = "u32";
+
+const USER_PASSWORD_TYPE_OPT: &str = "encrypt=1";
+const TUNNEL_PASSWORD_TYPE_OPT: &str = "encrypt=2";
+const HAS_TAG_TYPE_OPT: &str = "has_tag";
+const CONCAT_TYPE_OPT: &str = "concat";
+
+#[derive(Error, Debug)]
+pub enum GeneratorError {
+    /// An error that is raised when the out directory is neither given nor in `OUT_DIR`.
+    #[error("out directory is missing; set it or run in a build script")]
+    OutDirMissingError(),
+    /// An error that is raised when the file name of a dictionary isn't `dictionary.<name>`.
+    #[error("invalid dictionary file name: {0}")]
+    InvalidFileNameError(String),
+    /// An error that is raised when reading a dictionary or writing a generated module has been failed.
+    #[error("failed to access {0}: {1}")]
+    IOError(String, io::Error),
+    /// An error that is raised when a dictionary is malformed.
+    #[error("invalid dictionary {0}: {1}")]
+    InvalidDictionaryError(String, String),
+}
+
+/// A builder of the generation of the attribute modules.
+///
+/// The generated code refers to `radius` by `::radius`, to the allocating types by `std` and to the modules of the
+/// other dictionaries by `super::<name>` by default, which fit the modules that a crate includes side by side.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    dictionaries: Vec<PathBuf>,
+    out_dir: Option<PathBuf>,
+    radius_path: String,
+    alloc_path: String,
+    module_path: String,
+    module_docs: bool,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator {
+            dictionaries: Vec::new(),
+            out_dir: None,
+            radius_path: "::radius".to_owned(),
+            alloc_path: "std".to_owned(),
+            module_path: "super".to_owned(),
+            module_docs: false,
+        }
+    }
+}
+
+impl Generator {
+    pub fn new() -> Self {
+        Generator::default()
+    }
+
+    /// Add a dictionary file, i.e. `dictionary.<name>`, whose module is `<name>.rs`.
+    ///
+    /// The dictionaries are generated in the added order; the `VALUE`s of a dictionary can be for the attributes of
+    /// the former ones.
+    pub fn dictionary<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.dictionaries.push(path.into());
+        self
+    }
+
+    /// Set the directory of the generated modules; it's `OUT_DIR` of the build script by default.
+    pub fn out_dir<P: Into<PathBuf>>(mut self, out_dir: P) -> Self {
+        self.out_dir = Some(out_dir.into());
+        self
+    }
+
+    /// Set the path of the `radius` crate in the generated code (`::radius` by default, `crate` in itself).
+    pub fn radius_path(mut self, path: &str) -> Self {
+        self.radius_path = path.to_owned();
+        self
+    }
+
+    /// Set the path of `String`, `Vec` and `ToOwned` in the generated code (`std` by default); a `no_std` crate
+    /// sets `alloc`.
+    pub fn alloc_path(mut self, path: &str) -> Self {
+        self.alloc_path = path.to_owned();
+        self
+    }
+
+    /// Set the path of the parent module of the generated modules (`super` by default).
+    pub fn module_path(mut self, path: &str) -> Self {
+        self.module_path = path.to_owned();
+        self
+    }
+
+    /// Set whether the generated modules have the module docs (`//!`); `include!()` doesn't accept them, so they are
+    /// plain comments by default.
+    pub fn module_docs(mut self, module_docs: bool) -> Self {
+        self.module_docs = module_docs;
+        self
+    }
+
+    /// Generate the modules, and returns the paths of them.
+    pub fn generate(self) -> Result<Vec<PathBuf>, GeneratorError> {
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or(GeneratorError::OutDirMissingError())?,
+        };
+
+        let mut dictionaries = Vec::new();
+        for dict_file_path in &self.dictionaries {
+            let display_path = dict_file_path.display().to_string();
+            let rfc_name = dict_file_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("dictionary."))
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| GeneratorError::InvalidFileNameError(display_path.clone()))?;
+            let dict_file_contents = fs::read_to_string(dict_file_path)
+                .map_err(|e| GeneratorError::IOError(display_path.clone(), e))?;
+            let parsed = parse_dict_file(&dict_file_contents)
+                .map_err(|e| GeneratorError::InvalidDictionaryError(display_path.clone(), e))?;
+            dictionaries.push((rfc_name.to_owned(), dict_file_contents, parsed));
+        }
+        let extra_values = collect_extra_values(&dictionaries);
+
+        let mut generated = Vec::new();
+        let mut rfc_names: Vec<String> = Vec::new();
+        let mut attribute_name_to_rfc_name: HashMap<String, String> = HashMap::new();
+
+        for (rfc_name, dict_file_contents, parsed) in &dictionaries {
+            let rfc_name = rfc_name.as_str();
+            let (radius_attributes, radius_attribute_to_values_map, radius_vendor) = parsed;
+
+            let value_defined_attributes_set = radius_attribute_to_values_map
+                .keys()
+                .collect::<HashSet<&String>>();
+
+            let mut w = Vec::new();
+            self.generate_header(
+                &mut w,
+                &rfc_names,
+                rfc_name,
+                radius_vendor.as_ref(),
+                radius_attributes,
+                dict_file_contents,
+            );
+            generate_attributes_code(
+                &mut w,
+                radius_attributes,
+                &value_defined_attributes_set,
+                radius_vendor.as_ref(),
+                self.system_time_cfg(),
+            );
+            generate_values_code(
+                &mut w,
+                radius_attribute_to_values_map,
+                &attribute_name_to_rfc_name,
+                extra_values.get(rfc_name),
+            );
+            generate_dictionary_code(&mut w, radius_attributes, radius_attribute_to_values_map);
+
+            let out_path = out_dir.join(format!("{rfc_name}.rs"));
+            fs::write(&out_path, w)
+                .map_err(|e| GeneratorError::IOError(out_path.display().to_string(), e))?;
+            generated.push(out_path);
+
+            // a vendor's attributes live in the namespace of the vendor, so the other dictionaries don't refer to them
+            if radius_vendor.is_some() {
+                continue;
+            }
+            for attr in radius_attributes {
+                attribute_name_to_rfc_name.insert(attr.name.clone(), rfc_name.to_owned());
+            }
+            rfc_names.push(rfc_name.to_owned());
+        }
+
+        Ok(generated)
+    }
+
+    fn generate_header(
+        &self,
+        w: &mut impl Write,
+        rfc_names: &[String],
+        rfc_name: &str,
+        vendor: Option<&RadiusVendor>,
+        attrs: &[RadiusAttribute],
+        dict_file_contents: &str,
+    ) {
+        let summary = match vendor {
+            Some(vendor) => format!(
+                "the Vendor-Specific attributes of {name} (Vendor-Id {id})",
+                name = vendor.name,
+                id = vendor.id,
+            ),
+            None => format!("{rfc_name} packet"),
+        };
+        let doc = if self.module_docs { "//!" } else { "//" };
+        let imports = [
+            format!("use {}::borrow::ToOwned;", self.alloc_path),
+            format!("use {}::string::String;", self.alloc_path),
+            format!("use {}::vec::Vec;", self.alloc_path),
+            "use core::net::{Ipv4Addr, Ipv6Addr};".to_owned(),
+        ];
+        let radius = &self.radius_path;
+        let mut radius_imports = vec![
+            format!("use {radius}::core::avp::{{AVP, AVPType, AVPError}};"),
+            format!("use {radius}::core::dictionary::{{AttributeDefinition, ValueDefinition}};"),
+            format!("use {radius}::core::ipv6::{{InterfaceId, Ipv6Prefix}};"),
+            format!("use {radius}::core::packet::Packet;"),
+            format!("use {radius}::core::tag::Tag;"),
+        ];
+        if vendor.is_some() {
+            radius_imports.push(format!("use {radius}::core::vsa::{{self, VendorId}};"));
+        }
+        // the crates that use the generator may not depend on chrono
+        let chrono_imports = match attrs
+            .iter()
+            .any(|attr| attr.value_type == RadiusAttributeValueType::Date)
+        {
+            true => "use chrono::{DateTime, Utc};\n\n",
+            false => "",
+        };
+        let code = format!(
+            "// Code generated by machine generator; DO NOT EDIT.
+
+{doc} Utility for {summary}.
+{doc}
+{doc} This module handles the packet according to the following definition:
+{doc} ```text
+{doc} {dict_file_contents}
+{doc} ```
+
+{imports}
+
+{chrono_imports}{radius_imports}
+
+",
+            imports = self.allow_unused_imports(&imports),
+            radius_imports = self.allow_unused_imports(&radius_imports),
+            dict_file_contents = dict_file_contents
+                .lines()
+                .map(|line| format!("{doc} {line}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+
+        w.write_all(code.as_bytes()).unwrap();
+
+        if let Some(vendor) = vendor {
+            w.write_all(
+                format!(
+                    "/// The Vendor-Id of {name}.\npub const {identifier}: VendorId = {id};\n",
+                    name = vendor.name,
+                    identifier = vendor.const_identifier(),
+                    id = vendor.id,
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        }
+
+        let rfc_imports: Vec<String> = rfc_names
+            .iter()
+            .map(|rfc_name| format!("use {}::{rfc_name};", self.module_path))
+            .collect();
+        if !rfc_imports.is_empty() {
+            w.write_all(format!("{}\n", self.allow_unused_imports(&rfc_imports)).as_bytes())
+                .unwrap();
+        }
+    }
+
+    /// Returns the `cfg` attribute of the functions of `std::time::SystemTime`, or `None` if the generated code doesn't
+    /// have `std`; the bundled modules have them by the `std` feature.
+    fn system_time_cfg(&self) -> Option<&'static str> {
+        match (self.radius_path.as_str(), self.alloc_path.as_str()) {
+            ("crate", _) => Some("#[cfg(feature = \"std\")]\n"),
+            (_, "std") => Some(""),
+            _ => None,
+        }
+    }
+
+    /// Join the imports; the ones of the other crates are allowed to be unused, since they can't be cleaned up by
+    /// `cargo fix` like the bundled modules are.
+    fn allow_unused_imports(&self, imports: &[String]) -> String {
+        match self.radius_path.as_str() {
+            "crate" => imports.join("\n"),
+            _ => imports
+                .iter()
+                .map(|import| format!("#[allow(unused_imports)]\n{import}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum EncryptionType {
+    UserPassword,
+    TunnelPassword,
+}
+
+#[derive(Debug)]
+struct RadiusAttribute {
+    name: String,
+    typ: u8,
+    value_type: RadiusAttributeValueType,
+    fixed_octets_length: Option<usize>,
+    concat_octets: bool,
+    has_tag: bool,
+}
+
+/// The vendor of a dictionary; its attributes are the sub-attributes of Vendor-Specific.
+#[derive(Debug)]
+struct RadiusVendor {
+    name: String,
+    id: u32,
+}
+
+impl RadiusVendor {
+    fn const_identifier(&self) -> String {
+        format!("VENDOR_{}", self.name.to_screaming_snake_case())
+    }
+}
+
+#[derive(Debug)]
+struct RadiusValue {
+    name: String,
+    value: u16,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq)]
+enum RadiusAttributeValueType {
+    String,
+    UserPassword,
+    TunnelPassword,
+    Octets,
+    IpAddr,
+    Ipv4Prefix,
+    Ipv6Addr,
+    Ipv6Prefix,
+    IfId,
+    Date,
+    Integer,
+    Short,
+    Integer64,
+    VSA,
+}
+
+impl RadiusAttributeValueType {
+    fn dictionary_data_type(&self) -> &'static str {
+        match self {
+            RadiusAttributeValueType::String
+            | RadiusAttributeValueType::UserPassword
+            | RadiusAttributeValueType::TunnelPassword => "string",
+            RadiusAttributeValueType::Octets => "octets",
+            RadiusAttributeValueType::IpAddr => "ipaddr",
+            RadiusAttributeValueType::Ipv4Prefix => "ipv4prefix",
+            RadiusAttributeValueType::Ipv6Addr => "ipv6addr",
+            RadiusAttributeValueType::Ipv6Prefix => "ipv6prefix",
+            RadiusAttributeValueType::IfId => "ifid",
+            RadiusAttributeValueType::Date => "date",
+            RadiusAttributeValueType::Integer => "integer",
+            RadiusAttributeValueType::Short => "short",
+            RadiusAttributeValueType::Integer64 => "integer64",
+            RadiusAttributeValueType::VSA => "vsa",
+        }
+    }
+}
+
+impl FromStr for RadiusAttributeValueType {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "string" => Ok(RadiusAttributeValueType::String),
+            "octets" => Ok(RadiusAttributeValueType::Octets),
+            "ipaddr" => Ok(RadiusAttributeValueType::IpAddr),
+            "ipv4prefix" => Ok(RadiusAttributeValueType::Ipv4Prefix),
+            "ipv6addr" => Ok(RadiusAttributeValueType::Ipv6Addr),
+            "ipv6prefix" => Ok(RadiusAttributeValueType::Ipv6Prefix),
+            "ifid" => Ok(RadiusAttributeValueType::IfId),
+            "date" => Ok(RadiusAttributeValueType::Date),
+            "integer" => Ok(RadiusAttributeValueType::Integer),
+            "short" => Ok(RadiusAttributeValueType::Short),
+            "integer64" => Ok(RadiusAttributeValueType::Integer64),
+            "vsa" => Ok(RadiusAttributeValueType::VSA),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The `VALUE`s of the latter dictionaries for the attributes of the former ones, by the module names and the
+/// attribute names of the former ones; the enums of the values cover them.
+type ExtraValues<'a> = HashMap<String, HashMap<&'a str, Vec<&'a RadiusValue>>>;
+
+fn collect_extra_values(dictionaries: &[(String, String, DictParsed)]) -> ExtraValues<'_> {
+    let mut extra_values: ExtraValues = HashMap::new();
+    let mut attribute_name_to_rfc_name: HashMap<&str, &str> = HashMap::new();
+    for (rfc_name, _, (attributes, attr_to_values_map, vendor)) in dictionaries {
+        for (attr, values) in attr_to_values_map {
+            if let Some(owner_rfc_name) = attribute_name_to_rfc_name.get(attr.as_str()) {
+                extra_values
+                    .entry(owner_rfc_name.to_string())
+                    .or_default()
+                    .entry(attr.as_str())
+                    .or_default()
+                    .extend(values);
+            }
+        }
+        if vendor.is_some() {
+            continue;
+        }
+        for attr in attributes {
+            attribute_name_to_rfc_name.insert(&attr.name, rfc_name);
+        }
+    }
+    extra_values
+}
+
+fn generate_values_code(
+    w: &mut impl Write,
+    attr_to_values_map: &BTreeMap<String, Vec<RadiusValue>>,
+    attr_name_to_rfc_name: &HashMap<String, String>,
+    extra_values: Option<&HashMap<&str, Vec<&RadiusValue>>>,
+) {
+    for (attr, values) in attr_to_values_map {
+        let maybe_rfc_name = attr_name_to_rfc_name.get(attr);
+        generate_values_for_attribute_code(w, attr, values, maybe_rfc_name);
+        if maybe_rfc_name.is_none() {
+            let mut all_values: Vec<&RadiusValue> = values.iter().collect();
+            if let Some(extra_values) = extra_values.and_then(|m| m.get(attr.as_str())) {
+                all_values.extend(extra_values);
+            }
+            generate_value_enum_code(w, attr, &all_values);
+        }
+    }
+}
+
+fn generate_values_for_attribute_code(
+    w: &mut impl Write,
+    attr: &str,
+    values: &[RadiusValue],
+    maybe_rfc_name: Option<&String>,
+) {
+    let type_name = attr.to_pascal_case();
+
+    if maybe_rfc_name.is_none() {
+        w.write_all(format!("\npub type {type_name} = {RADIUS_VALUE_TYPE};\n").as_bytes())
+            .unwrap();
+    }
+
+    for v in values {
+        if let Some(rfc_name) = maybe_rfc_name {
+            w.write_all(
+                format!(
+                "pub const {type_name_prefix}_{value_name}: {rfc_name}::{type_name} = {value};\n",
+                type_name_prefix = type_name.to_screaming_snake_case(),
+                value_name = v.name.to_screaming_snake_case(),
+                rfc_name = rfc_name,
+                type_name = type_name,
+                value = v.value,
+            )
+                .as_bytes(),
+            )
+            .unwrap()
+        } else {
+            w.write_all(
+                format!(
+                    "pub const {type_name_prefix}_{value_name}: {type_name} = {value};\n",
+                    type_name_prefix = type_name.to_screaming_snake_case(),
+                    value_name = v.name.to_screaming_snake_case(),
+                    type_name = type_name,
+                    value = v.value,
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        }
+    }
+    w.write_all(b"\n").unwrap();
+}
+
+fn generate_value_enum_code(w: &mut impl Write, attr: &str, values: &[&RadiusValue]) {
+    let type_name = attr.to_pascal_case();
+    let enum_name = format!("{type_name}Value");
+
+    // the aliases (i.e. the other names of the same value) aren't the variants
+    let mut seen_values = HashSet::new();
+    let mut seen_variants = HashSet::new();
+    let mut variants = Vec::new();
+    for v in values {
+        let variant = match v.name.to_pascal_case() {
+            variant if variant.starts_with(|c: char| c.is_ascii_alphabetic()) => variant,
+            variant => format!("V{variant}"),
+        };
+        if seen_values.insert(v.value) && seen_variants.insert(variant.clone()) {
+            variants.push((variant, v));
+        }
+    }
+
+    let mut code = format!(
+        "/// The values of `{attr}` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `{enum_name}::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr({RADIUS_VALUE_TYPE})]
+pub enum {enum_name} {{
+"
+    );
+    for (variant, v) in &variants {
+        code.push_str(&format!(
+            "    /// `{name}`\n    {variant} = {value},\n",
+            name = v.name,
+            value = v.value,
+        ));
+    }
+    code.push_str(&format!(
+        "}}
+
+impl From<{enum_name}> for {RADIUS_VALUE_TYPE} {{
+    fn from(value: {enum_name}) -> Self {{
+        value as {RADIUS_VALUE_TYPE}
+    }}
+}}
+
+impl core::convert::TryFrom<{RADIUS_VALUE_TYPE}> for {enum_name} {{
+    type Error = AVPError;
+
+    fn try_from(value: {RADIUS_VALUE_TYPE}) -> Result<Self, Self::Error> {{
+        match value {{
+"
+    ));
+    for (variant, v) in &variants {
+        code.push_str(&format!(
+            "            {value} => Ok({enum_name}::{variant}),\n",
+            value = v.value,
+        ));
+    }
+    code.push_str(&format!(
+        "            _ => Err(AVPError::UnknownValueError(\"{attr}\".to_owned(), value)),
+        }}
+    }}
+}}
+
+"
+    ));
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_dictionary_code(
+    w: &mut impl Write,
+    attrs: &[RadiusAttribute],
+    attr_to_values_map: &BTreeMap<String, Vec<RadiusValue>>,
+) {
+    w.write_all(
+        b"/// The attributes that are defined by this dictionary.\npub const ATTRIBUTES: &[AttributeDefinition] = &[\n",
+    )
+    .unwrap();
+    for attr in attrs {
+        w.write_all(
+            format!(
+                "    AttributeDefinition::new(\"{name}\", {type_identifier}_TYPE, \"{data_type}\"),\n",
+                name = attr.name,
+                type_identifier = attr.name.to_screaming_snake_case(),
+                data_type = attr.value_type.dictionary_data_type(),
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+    }
+    w.write_all(b"];\n\n").unwrap();
+
+    w.write_all(
+        b"/// The named values of the attributes that are defined by this dictionary.\npub const VALUES: &[ValueDefinition] = &[\n",
+    )
+    .unwrap();
+    for (attr, values) in attr_to_values_map {
+        for v in values {
+            w.write_all(
+                format!(
+                    "    ValueDefinition::new(\"{attr}\", \"{name}\", {value}),\n",
+                    name = v.name,
+                    value = v.value,
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        }
+    }
+    w.write_all(b"];\n").unwrap();
+}
+
+fn generate_attributes_code(
+    w: &mut impl Write,
+    attrs: &[RadiusAttribute],
+    value_defined_attributes_set: &HashSet<&String>,
+    vendor: Option<&RadiusVendor>,
+    system_time_cfg: Option<&str>,
+) {
+    for attr in attrs {
+        match vendor {
+            Some(vendor) => generate_vendor_attribute_code(
+                w,
+                attr,
+                value_defined_attributes_set,
+                vendor,
+                system_time_cfg,
+            ),
+            None => generate_attribute_code(w, attr, value_defined_attributes_set, system_time_cfg),
+        }
+    }
+}
+
+/// Generate the code of a vendor's attribute, i.e. the code of an attribute whose access to the packet goes through
+/// the sub-attributes of Vendor-Specific (see `radius::core::vsa`).
+fn generate_vendor_attribute_code(
+    w: &mut impl Write,
+    attr: &RadiusAttribute,
+    value_defined_attributes_set: &HashSet<&String>,
+    vendor: &RadiusVendor,
+    system_time_cfg: Option<&str>,
+) {
+    match attr.value_type {
+        RadiusAttributeValueType::UserPassword | RadiusAttributeValueType::TunnelPassword => {
+            unimplemented!("vendor-password")
+        }
+        RadiusAttributeValueType::Octets if attr.concat_octets => {
+            unimplemented!("vendor-concat-octets")
+        }
+        _ => {}
+    }
+
+    let mut code = Vec::new();
+    generate_attribute_code(
+        &mut code,
+        attr,
+        value_defined_attributes_set,
+        system_time_cfg,
+    );
+    let vendor_identifier = vendor.const_identifier();
+    let code = String::from_utf8(code)
+        .unwrap()
+        .replace(
+            "packet.delete(",
+            &format!("vsa::delete_vendor_attribute(packet, {vendor_identifier}, "),
+        )
+        .replace(
+            "packet.add(",
+            &format!("vsa::add_vendor_attribute(packet, {vendor_identifier}, "),
+        )
+        .replace(
+            "packet.lookup_all(",
+            &format!("vsa::lookup_all_vendor_attributes(packet, {vendor_identifier}, "),
+        )
+        .replace(
+            "packet.lookup(",
+            &format!("vsa::lookup_vendor_attribute(packet, {vendor_identifier}, "),
+        );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_attribute_code(
+    w: &mut impl Write,
+    attr: &RadiusAttribute,
+    value_defined_attributes_set: &HashSet<&String>,
+    system_time_cfg: Option<&str>,
+) {
+    let attr_name = attr.name.clone();
+    let type_identifier = format!("{}_TYPE", attr_name.to_screaming_snake_case());
+    let type_value = attr.typ;
+    let method_identifier = attr_name.to_snake_case();
+
+    generate_common_attribute_code(w, &attr_name, &type_identifier, type_value);
+    match attr.value_type {
+        RadiusAttributeValueType::String => match attr.has_tag {
+            true => generate_tagged_string_attribute_code(w, &method_identifier, &type_identifier),
+            false => generate_string_attribute_code(w, &method_identifier, &type_identifier),
+        },
+        RadiusAttributeValueType::UserPassword => match attr.has_tag {
+            true => unimplemented!("tagged-user-password"),
+            false => generate_user_password_attribute_code(w, &method_identifier, &type_identifier),
+        },
+        RadiusAttributeValueType::TunnelPassword => match attr.has_tag {
+            true => {
+                generate_tunnel_password_attribute_code(w, &method_identifier, &type_identifier)
+            }
+            false => unimplemented!("tunnel-password"),
+        },
+        RadiusAttributeValueType::Octets => match attr.has_tag {
+            true => unimplemented!("tagged-octets"),
+            false => match attr.fixed_octets_length {
+                Some(fixed_octets_length) => generate_fixed_length_octets_attribute_code(
+                    w,
+                    &method_identifier,
+                    &type_identifier,
+                    fixed_octets_length,
+                ),
+                None => match attr.concat_octets {
+                    true => generate_concat_octets_attribute_code(
+                        w,
+                        &method_identifier,
+                        &type_identifier,
+                    ),
+                    false => {
+                        generate_octets_attribute_code(w, &method_identifier, &type_identifier)
+                    }
+                },
+            },
+        },
+        RadiusAttributeValueType::IpAddr => match attr.has_tag {
+            true => unimplemented!("tagged-ip-addr"),
+            false => generate_ipaddr_attribute_code(w, &method_identifier, &type_identifier),
+        },
+        RadiusAttributeValueType::Ipv4Prefix => match attr.has_tag {
+            true => unimplemented!("tagged-ip-addr"),
+            false => generate_ipv4_prefix_attribute_code(w, &method_identifier, &type_identifier),
+        },
+        RadiusAttributeValueType::Ipv6Addr => match attr.has_tag {
+            true => unimplemented!("tagged-ip-v6-addr"),
+            false => generate_ipv6addr_attribute_code(w, &method_identifier, &type_identifier),
+        },
+        RadiusAttributeValueType::Ipv6Prefix => match attr.has_tag {
+            true => unimplemented!("tagged-ipv6-prefix"),
+            false => generate_ipv6_prefix_attribute_code(w, &method_identifier, &type_identifier),
+        },
+        RadiusAttributeValueType::IfId => match attr.has_tag {
+            true => unimplemented!("tagged-ifid"),
+            false => generate_ifid_attribute_code(w, &method_identifier, &type_identifier),
+        },
+        RadiusAttributeValueType::Date => match attr.has_tag {
+            true => unimplemented!("tagged-date"),
+            false => generate_date_attribute_code(
+                w,
+                &method_identifier,
+                &type_identifier,
+                system_time_cfg,
+            ),
+        },
+        RadiusAttributeValueType::Integer => {
+            match value_defined_attributes_set.contains(&attr_name) {
+                true => match attr.has_tag {
+                    true => generate_tagged_value_defined_integer_attribute_code(
+                        w,
+                        &method_identifier,
+                        &type_identifier,
+                        &attr_name.to_pascal_case(),
+                    ),
+                    false => generate_value_defined_integer_attribute_code(
+                        w,
+                        &method_identifier,
+                        &type_identifier,
+                        &attr_name.to_pascal_case(),
+                    ),
+                },
+                false => match attr.has_tag {
+                    true => generate_tagged_integer_attribute_code(
+                        w,
+                        &method_identifier,
+                        &type_identifier,
+                    ),
+                    false => {
+                        generate_integer_attribute_code(w, &method_identifier, &type_identifier)
+                    }
+                },
+            }
+        }
+        RadiusAttributeValueType::Short => match attr.has_tag {
+            true => unimplemented!("tagged-short"),
+            false => generate_short_attribute_code(w, &method_identifier, &type_identifier),
+        },
+        RadiusAttributeValueType::Integer64 => match attr.has_tag {
+            true => unimplemented!("tagged-integer64"),
+            false => generate_integer64_attribute_code(w, &method_identifier, &type_identifier),
+        },
+        RadiusAttributeValueType::VSA => generate_vsa_attribute_code(),
+    }
+}
+
+fn generate_common_attribute_code(
+    w: &mut impl Write,
+    attr_name: &str,
+    type_identifier: &str,
+    type_value: u8,
+) {
+    let code = format!(
+        "
+pub const {type_identifier}: AVPType = {type_value};
+/// Delete all of `{method_identifier}` values from a packet.
+pub fn delete_{method_identifier}(packet: &mut Packet) {{
+    packet.delete({type_identifier});
+}}
+",
+        method_identifier = attr_name.to_snake_case(),
+        type_identifier = type_identifier,
+        type_value = type_value,
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_string_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` string value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: &str) {{
+    packet.add(AVP::from_string({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<String, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_string())
+}}
+/// Lookup all of the `{method_identifier}` string value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<String>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_string()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_tagged_string_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` tagged string value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, tag: Option<&Tag>, value: &str) {{
+    packet.add(AVP::from_tagged_string({type_identifier}, tag, value));
+}}
+/// Lookup a `{method_identifier}` tagged string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<(String, Option<Tag>), AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_tagged_string())
+}}
+/// Lookup all of the `{method_identifier}` tagged string value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<(String, Option<Tag>)>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_tagged_string()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_user_password_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` user-password value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: &[u8]) -> Result<(), AVPError> {{
+    packet.add(AVP::from_user_password({type_identifier}, value, packet.get_secret(), packet.get_authenticator())?);
+    Ok(())
+}}
+/// Lookup a `{method_identifier}` user-password value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<Vec<u8>, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_user_password(packet.get_secret(), packet.get_authenticator()))
+}}
+/// Lookup all of the `{method_identifier}` user-password value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<Vec<u8>>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_user_password(packet.get_secret(), packet.get_authenticator())?)
+    }}
+    Ok(vec)
+}}
+");
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_tunnel_password_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` tunnel-password value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, tag: Option<&Tag>, value: &[u8]) -> Result<(), AVPError> {{
+    packet.add(AVP::from_tunnel_password({type_identifier}, tag, value, packet.get_secret(), packet.get_authenticator())?);
+    Ok(())
+}}
+/// Lookup a `{method_identifier}` tunnel-password value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<(Vec<u8>, Tag), AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_tunnel_password(packet.get_secret(), packet.get_authenticator()))
+}}
+/// Lookup all of the `{method_identifier}` tunnel-password value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<(Vec<u8>, Tag)>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_tunnel_password(packet.get_secret(), packet.get_authenticator())?)
+    }}
+    Ok(vec)
+}}
+");
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_octets_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` octets value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: &[u8]) {{
+    packet.add(AVP::from_bytes({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` octets value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Vec<u8>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_bytes())
+}}
+/// Lookup all of the `{method_identifier}` octets value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Vec<Vec<u8>> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_bytes())
+    }}
+    vec
+}}
+");
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_concat_octets_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "pub fn add_{method_identifier}(packet: &mut Packet, value: &[u8]) {{
+    packet.extend(
+        value
+            .chunks(253)
+            .map(|chunk| AVP::from_bytes({type_identifier}, chunk))
+            .collect(),
+    );
+}}
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Vec<u8>> {{
+    let avps = packet.lookup_all({type_identifier});
+    match avps.is_empty() {{
+        true => None,
+        false => Some(avps.into_iter().fold(Vec::new(), |mut acc, v| {{
+            acc.extend(v.encode_bytes());
+            acc
+        }})),
+    }}
+}}
+"
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_fixed_length_octets_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+    fixed_octets_length: usize,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` fixed-length octets value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: &[u8]) -> Result<(), AVPError> {{
+    if value.len() != {fixed_octets_length} {{
+        return Err(AVPError::InvalidAttributeLengthError(\"{fixed_octets_length} bytes\".to_owned(), value.len()));
+    }}
+    packet.add(AVP::from_bytes({type_identifier}, value));
+    Ok(())
+}}
+/// Lookup a `{method_identifier}` fixed-length octets value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Vec<u8>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_bytes())
+}}
+/// Lookup all of the `{method_identifier}` fixed-length octets value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Vec<Vec<u8>> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_bytes())
+    }}
+    vec
+}}
+"
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_ipaddr_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` ipaddr value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: &Ipv4Addr) {{
+    packet.add(AVP::from_ipv4({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` ipaddr value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<Ipv4Addr, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_ipv4())
+}}
+/// Lookup all of the `{method_identifier}` ipaddr value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<Ipv4Addr>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_ipv4()?)
+    }}
+    Ok(vec)
+}}
+"
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_ipv4_prefix_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` ipv4 prefix value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: &[u8]) -> Result<(), AVPError> {{
+    packet.add(AVP::from_ipv4_prefix({type_identifier}, value)?);
+    Ok(())
+}}
+/// Lookup a `{method_identifier}` ipv4 prefix value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<Vec<u8>, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_ipv4_prefix())
+}}
+/// Lookup all of the `{method_identifier}` ipv4 prefix value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<Vec<u8>>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_ipv4_prefix()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_ipv6addr_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` ipv6addr value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: &Ipv6Addr) {{
+    packet.add(AVP::from_ipv6({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` ipv6addr value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<Ipv6Addr, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_ipv6())
+}}
+/// Lookup all of the `{method_identifier}` ipv6addr value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<Ipv6Addr>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_ipv6()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_ipv6_prefix_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` ipv6 prefix value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: &[u8]) -> Result<(), AVPError> {{
+    packet.add(AVP::from_ipv6_prefix({type_identifier}, value)?);
+    Ok(())
+}}
+/// Lookup a `{method_identifier}` ipv6 prefix value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<Vec<u8>, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_ipv6_prefix())
+}}
+/// Lookup all of the `{method_identifier}` ipv6 prefix value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<Vec<u8>>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_ipv6_prefix()?)
+    }}
+    Ok(vec)
+}}
+/// Add `{method_identifier}` ipv6 prefix value with the prefix length to a packet.
+pub fn add_{method_identifier}_value(packet: &mut Packet, value: &Ipv6Prefix) {{
+    packet.add(AVP::from_ipv6_prefix_value({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` ipv6 prefix value with the prefix length from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}_value(packet: &Packet) -> Option<Result<Ipv6Prefix, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_ipv6_prefix_value())
+}}
+/// Lookup all of the `{method_identifier}` ipv6 prefix value with the prefix length from a packet.
+pub fn lookup_all_{method_identifier}_value(packet: &Packet) -> Result<Vec<Ipv6Prefix>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_ipv6_prefix_value()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_ifid_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    generate_fixed_length_octets_attribute_code(w, method_identifier, type_identifier, 8);
+    let code = format!(
+        "/// Add `{method_identifier}` interface id value to a packet.
+pub fn add_{method_identifier}_value(packet: &mut Packet, value: &InterfaceId) {{
+    packet.add(AVP::from_interface_id({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` interface id value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}_value(packet: &Packet) -> Option<Result<InterfaceId, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_interface_id())
+}}
+/// Lookup all of the `{method_identifier}` interface id value from a packet.
+pub fn lookup_all_{method_identifier}_value(packet: &Packet) -> Result<Vec<InterfaceId>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_interface_id()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_date_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+    system_time_cfg: Option<&str>,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` date value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: &DateTime<Utc>) {{
+    packet.add(AVP::from_date({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` date value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<DateTime<Utc>, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_date())
+}}
+/// Lookup all of the `{method_identifier}` date value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<DateTime<Utc>>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_date()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+
+    let cfg = match system_time_cfg {
+        Some(cfg) => cfg,
+        None => return,
+    };
+    let code = format!(
+        "/// Add `{method_identifier}` date value of `SystemTime` to a packet.
+///
+/// It fails if the value isn't in the range of the date (i.e. from 1970 to 2106).
+{cfg}pub fn add_{method_identifier}_system_time(packet: &mut Packet, value: &std::time::SystemTime) -> Result<(), AVPError> {{
+    packet.add(AVP::from_system_time({type_identifier}, value)?);
+    Ok(())
+}}
+/// Lookup a `{method_identifier}` date value of `SystemTime` from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+{cfg}pub fn lookup_{method_identifier}_system_time(packet: &Packet) -> Option<Result<std::time::SystemTime, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_system_time())
+}}
+/// Lookup all of the `{method_identifier}` date value of `SystemTime` from a packet.
+{cfg}pub fn lookup_all_{method_identifier}_system_time(packet: &Packet) -> Result<Vec<std::time::SystemTime>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_system_time()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_integer_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` integer value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: u32) {{
+    packet.add(AVP::from_u32({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<u32, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_u32())
+}}
+/// Lookup all of the `{method_identifier}` integer value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<u32>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_u32()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_tagged_integer_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` tagged integer value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, tag: Option<&Tag>, value: u32) {{
+    packet.add(AVP::from_tagged_u32({type_identifier}, tag, value));
+}}
+/// Lookup a `{method_identifier}` tagged integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<(u32, Tag), AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_tagged_u32())
+}}
+/// Lookup all of the `{method_identifier}` tagged integer value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<(u32, Tag)>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_tagged_u32()?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_value_defined_integer_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+    value_type: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` value-defined integer value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: {value_type}) {{
+    packet.add(AVP::from_u32({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<{value_type}, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| Ok(v.encode_u32()? as {value_type}))
+}}
+/// Lookup all of the `{method_identifier}` value-defined integer value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<{value_type}>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_u32()? as {value_type})
+    }}
+    Ok(vec)
+}}
+/// Lookup a `{method_identifier}` value from a packet as `{value_type}Value`.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}_value(packet: &Packet) -> Option<Result<{value_type}Value, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}}
+/// Lookup all of the `{method_identifier}` values from a packet as `{value_type}Value`.
+pub fn lookup_all_{method_identifier}_value(packet: &Packet) -> Result<Vec<{value_type}Value>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }}
+    Ok(vec)
+}}
+",
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_tagged_value_defined_integer_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+    value_type: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` tagged value-defined integer value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, tag: Option<&Tag>, value: {value_type}) {{
+    packet.add(AVP::from_tagged_u32({type_identifier}, tag, value));
+}}
+/// Lookup a `{method_identifier}` tagged value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<({value_type}, Tag), AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| {{
+        let (v, t) = v.encode_tagged_u32()?;
+        Ok((v as {value_type}, t))
+    }})
+}}
+/// Lookup all of the `{method_identifier}` tagged value-defined integer value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<({value_type}, Tag)>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        let (v, t) = avp.encode_tagged_u32()?;
+        vec.push((v as {value_type}, t))
+    }}
+    Ok(vec)
+}}
+/// Lookup a `{method_identifier}` tagged value from a packet as `{value_type}Value`.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}_value(packet: &Packet) -> Option<Result<({value_type}Value, Tag), AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| {{
+        let (v, t) = v.encode_tagged_u32()?;
+        Ok((core::convert::TryFrom::try_from(v)?, t))
+    }})
+}}
+/// Lookup all of the `{method_identifier}` tagged values from a packet as `{value_type}Value`.
+pub fn lookup_all_{method_identifier}_value(packet: &Packet) -> Result<Vec<({value_type}Value, Tag)>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        let (v, t) = avp.encode_tagged_u32()?;
+        vec.push((core::convert::TryFrom::try_from(v)?, t))
+    }}
+    Ok(vec)
+}}
+"
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_short_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` short integer value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: u16) {{
+    packet.add(AVP::from_u16({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` short integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<u16, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_u16())
+}}
+/// Lookup all of the `{method_identifier}` short integer value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<u16>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_u16()?)
+    }}
+    Ok(vec)
+}}
+"
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_integer64_attribute_code(
+    w: &mut impl Write,
+    method_identifier: &str,
+    type_identifier: &str,
+) {
+    let code = format!(
+        "/// Add `{method_identifier}` 64-bit integer value to a packet.
+pub fn add_{method_identifier}(packet: &mut Packet, value: u64) {{
+    packet.add(AVP::from_u64({type_identifier}, value));
+}}
+/// Lookup a `{method_identifier}` 64-bit integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `{method_identifier}`, it returns `None`.
+pub fn lookup_{method_identifier}(packet: &Packet) -> Option<Result<u64, AVPError>> {{
+    packet.lookup({type_identifier}).map(|v| v.encode_u64())
+}}
+/// Lookup all of the `{method_identifier}` 64-bit integer value from a packet.
+pub fn lookup_all_{method_identifier}(packet: &Packet) -> Result<Vec<u64>, AVPError> {{
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all({type_identifier}) {{
+        vec.push(avp.encode_u64()?)
+    }}
+    Ok(vec)
+}}
+"
+    );
+    w.write_all(code.as_bytes()).unwrap();
+}
+
+fn generate_vsa_attribute_code() {
+    // NOP
+}
+
+type DictParsed = (
+    Vec<RadiusAttribute>,
+    BTreeMap<String, Vec<RadiusValue>>,
+    Option<RadiusVendor>,
+);
+
+fn parse_dict_file(dict_file_contents: &str) -> Result<DictParsed, String> {
+    let line_filter_re = Regex::new(r"^(?:#.*|)$").unwrap();
+    let ws_re = Regex::new(r"\s+").unwrap();
+    let trailing_comment_re = Regex::new(r"\s*?#.+?$").unwrap();
+    let fixed_length_octets_re = Regex::new(r"^octets\[(\d+)]$").unwrap();
+
+    let mut radius_attributes: Vec<RadiusAttribute> = Vec::new();
+    let mut radius_attribute_to_values: BTreeMap<String, Vec<RadiusValue>> = BTreeMap::new();
+    let mut radius_vendor: Option<RadiusVendor> = None;
+
+    for line in dict_file_contents.lines() {
+        if line_filter_re.is_match(line) {
+            continue;
+        }
+
+        let items = ws_re.split(line).collect::<Vec<&str>>();
+
+        let kind = items[0];
+        let required_items = match kind {
+            VENDOR_KIND => 3,
+            BEGIN_VENDOR_KIND | END_VENDOR_KIND => 2,
+            _ => 4,
+        };
+        if items.len() < required_items {
+            return Err("the number of items is lacked in a line".to_owned());
+        }
+
+        match kind {
+            VENDOR_KIND => {
+                if radius_vendor.is_some() {
+                    return Err("a dictionary can have only one vendor".to_owned());
+                }
+                radius_vendor = Some(RadiusVendor {
+                    name: items[1].to_string(),
+                    id: items[2]
+                        .parse()
+                        .map_err(|_| format!("invalid vendor id has come => {}", items[2]))?,
+                });
+            }
+            BEGIN_VENDOR_KIND | END_VENDOR_KIND => {
+                // the attributes of a vendor's dictionary are always of the vendor
+                match &radius_vendor {
+                    Some(vendor) if vendor.name == items[1] => {}
+                    _ => return Err(format!("unknown vendor has come => {}", items[1])),
+                }
+            }
+            ATTRIBUTE_KIND => {
+                let mut encryption_type: Option<EncryptionType> = None;
+                let mut has_tag = false;
+                let mut concat_octets = false;
+                if items.len() >= 5 {
+                    // TODO consider to extract to a method
+                    for type_opt in items[4].split(',') {
+                        if type_opt == USER_PASSWORD_TYPE_OPT {
+                            encryption_type = Some(EncryptionType::UserPassword);
+                            continue;
+                        }
+                        if type_opt == TUNNEL_PASSWORD_TYPE_OPT {
+                            encryption_type = Some(EncryptionType::TunnelPassword);
+                            continue;
+                        }
+                        if type_opt == HAS_TAG_TYPE_OPT {
+                            has_tag = true;
+                            continue;
+                        }
+                        if type_opt == CONCAT_TYPE_OPT {
+                            concat_octets = true;
+                            continue;
+                        }
+                    }
+                }
+
+                let (typ, fixed_octets_length) = match RadiusAttributeValueType::from_str(items[3])
+                {
+                    Ok(t) => {
+                        if t == RadiusAttributeValueType::String {
+                            match encryption_type {
+                                Some(EncryptionType::UserPassword) => {
+                                    (RadiusAttributeValueType::UserPassword, None)
+                                }
+                                Some(EncryptionType::TunnelPassword) => {
+                                    (RadiusAttributeValueType::TunnelPassword, None)
+                                }
+                                None => (t, None),
+                            }
+                        } else {
+                            (t, None)
+                        }
+                    }
+                    Err(_) => {
+                        // XXX ad-hoc
+                        let maybe_cap = fixed_length_octets_re.captures(items[3]);
+                        if let Some(cap) = maybe_cap {
+                            (
+                                RadiusAttributeValueType::Octets,
+                                Some(cap.get(1).unwrap().as_str().parse::<usize>().map_err(
+                                    |_| format!("invalid octets length has come => {}", items[3]),
+                                )?),
+                            )
+                        } else {
+                            return Err(format!("invalid type has come => {}", items[3]));
+                        }
+                    }
+                };
+
+                radius_attributes.push(RadiusAttribute {
+                    name: items[1].to_string(),
+                    typ: items[2]
+                        .parse()
+                        .map_err(|_| format!("invalid attribute type has come => {}", items[2]))?,
+                    value_type: typ,
+                    fixed_octets_length,
+                    concat_octets,
+                    has_tag,
+                });
+            }
+            VALUE_KIND => {
+                let attribute_name = items[1].to_string();
+                let name = items[2].to_string();
+
+                let value = trailing_comment_re.replace(items[3], "").to_string();
+                let radius_value = RadiusValue {
+                    name,
+                    value: value
+                        .parse()
+                        .map_err(|_| format!("invalid value has come => {value}"))?,
+                };
+
+                match radius_attribute_to_values.get_mut(&attribute_name) {
+                    None => {
+                        radius_attribute_to_values
+                            .insert(attribute_name.clone(), vec![radius_value]);
+                    }
+                    Some(vec) => {
+                        vec.push(radius_value);
+                    }
+                };
+            }
+            _ => return Err(format!("unexpected kind has come => {kind}")),
+        }
+    }
+
+    Ok((radius_attributes, radius_attribute_to_values, radius_vendor))
+}
@@ -0,0 +1,261 @@
+//! The derive macro of `radius::core::attribute_set::RadiusAttributes`.
+//!
+//! Use this through the `derive` feature of `radius` (see `radius::core::attribute_set` for the field attributes);
+//! the generated code refers to the items of `::radius`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Field, Fields, GenericArgument, LitInt, LitStr,
+    PathArguments, Type,
+};
+
+/// Implements `RadiusAttributes` by the named fields of a struct.
+#[proc_macro_derive(RadiusAttributes, attributes(radius))]
+pub fn derive_radius_attributes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How a field holds the values of the attribute.
+#[derive(Debug, PartialEq)]
+enum Multiplicity {
+    /// `T`: the first value, which is required.
+    Required,
+    /// `Option<T>`: the first value, if any.
+    Optional,
+    /// `Vec<T>` (except `Vec<u8>`, which is a value): all of the values.
+    Multiple,
+}
+
+/// The `#[radius(...)]` of a field.
+#[derive(Debug, Default, PartialEq)]
+struct FieldOptions {
+    name: Option<String>,
+    vendor: Option<u32>,
+    typ: Option<u8>,
+    skip: bool,
+}
+
+fn parse_field_options(field: &Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("radius"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("vendor") {
+                options.vendor = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("type") {
+                options.typ = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else {
+                return Err(meta.error("expected `name`, `vendor`, `type` or `skip`"));
+            }
+            Ok(())
+        })?;
+    }
+    if options.name.is_some() && options.typ.is_some() {
+        return Err(syn::Error::new_spanned(
+            field,
+            "`name` and `type` are exclusive",
+        ));
+    }
+    if options.vendor.is_some() && options.typ.is_none() {
+        return Err(syn::Error::new_spanned(field, "`vendor` requires `type`"));
+    }
+    Ok(options)
+}
+
+/// Returns the type argument of `Option<T>` or `Vec<T>`.
+fn type_argument<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn multiplicity(ty: &Type) -> Multiplicity {
+    if type_argument(ty, "Option").is_some() {
+        return Multiplicity::Optional;
+    }
+    match type_argument(ty, "Vec") {
+        Some(Type::Path(path)) if path.path.is_ident("u8") => Multiplicity::Required,
+        Some(_) => Multiplicity::Multiple,
+        None => Multiplicity::Required,
+    }
+}
+
+/// Returns the name of the attribute of a field without `name`, e.g. `User-Name` of `user_name`; the names of the
+/// dictionary are compared case-insensitively.
+fn default_attribute_name(field_name: &str) -> String {
+    field_name.trim_start_matches("r#").replace('_', "-")
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "RadiusAttributes requires the named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "RadiusAttributes can be derived only for the structs",
+            ))
+        }
+    };
+
+    let mut lookups = vec![];
+    let mut replacements = vec![];
+    for field in fields {
+        let ident = field.ident.as_ref().expect("a named field");
+        let options = parse_field_options(field)?;
+        if options.skip {
+            lookups.push(quote! { #ident: ::core::default::Default::default() });
+            continue;
+        }
+
+        let name = options
+            .name
+            .clone()
+            .unwrap_or_else(|| default_attribute_name(&ident.to_string()));
+        let attribute_id = match (options.vendor, options.typ) {
+            (Some(vendor), Some(typ)) => {
+                quote! { ::radius::core::attribute_set::AttributeId::Vendor(#vendor, #typ) }
+            }
+            (None, Some(typ)) => {
+                quote! { ::radius::core::attribute_set::AttributeId::Standard(#typ) }
+            }
+            _ => quote! { ::radius::core::attribute_set::AttributeId::resolve(#name)? },
+        };
+        let (lookup, values) = match multiplicity(&field.ty) {
+            Multiplicity::Required => (
+                quote! { lookup_required },
+                quote! { ::core::slice::from_ref(&self.#ident) },
+            ),
+            Multiplicity::Optional => (
+                quote! { lookup_optional },
+                quote! { self.#ident.as_slice() },
+            ),
+            Multiplicity::Multiple => (quote! { lookup_values }, quote! { self.#ident.as_slice() }),
+        };
+        lookups.push(quote! { #ident: #attribute_id.#lookup(packet, #name)? });
+        replacements.push(quote! { #attribute_id.replace_values(packet, #name, #values)?; });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::radius::core::attribute_set::RadiusAttributes for #ident #ty_generics #where_clause {
+            fn from_packet(
+                packet: &::radius::core::packet::Packet,
+            ) -> ::core::result::Result<Self, ::radius::core::attribute_set::AttributeSetError> {
+                ::core::result::Result::Ok(#ident { #(#lookups,)* })
+            }
+
+            fn apply_to_packet(
+                &self,
+                packet: &mut ::radius::core::packet::Packet,
+            ) -> ::core::result::Result<(), ::radius::core::attribute_set::AttributeSetError> {
+                #(#replacements)*
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{DeriveInput, Field, Type};
+
+    use crate::{
+        default_attribute_name, expand, multiplicity, parse_field_options, FieldOptions,
+        Multiplicity,
+    };
+
+    fn parse_field(field: &str) -> Field {
+        let input: DeriveInput = syn::parse_str(&format!("struct S {{ {field} }}")).unwrap();
+        match input.data {
+            syn::Data::Struct(data) => data.fields.into_iter().next().unwrap(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_field_options() {
+        assert_eq!(
+            parse_field_options(&parse_field("#[radius(vendor = 9, type = 1)] f: String")).unwrap(),
+            FieldOptions {
+                vendor: Some(9),
+                typ: Some(1),
+                ..FieldOptions::default()
+            }
+        );
+        assert_eq!(
+            parse_field_options(&parse_field("#[radius(name = \"Class\")] f: Vec<Vec<u8>>"))
+                .unwrap()
+                .name,
+            Some("Class".to_owned())
+        );
+        assert!(
+            parse_field_options(&parse_field("#[radius(skip)] f: u32"))
+                .unwrap()
+                .skip
+        );
+        assert!(parse_field_options(&parse_field("#[radius(vendor = 9)] f: u32")).is_err());
+        assert!(parse_field_options(&parse_field(
+            "#[radius(name = \"Class\", type = 25)] f: u32"
+        ))
+        .is_err());
+        assert!(parse_field_options(&parse_field("#[radius(unknown)] f: u32")).is_err());
+
+        assert_eq!(default_attribute_name("nas_ip_address"), "nas-ip-address");
+        assert_eq!(default_attribute_name("r#class"), "class");
+    }
+
+    #[test]
+    fn test_multiplicity() {
+        let ty = |ty: &str| syn::parse_str::<Type>(ty).unwrap();
+        assert_eq!(multiplicity(&ty("String")), Multiplicity::Required);
+        assert_eq!(multiplicity(&ty("Vec<u8>")), Multiplicity::Required);
+        assert_eq!(multiplicity(&ty("Option<u32>")), Multiplicity::Optional);
+        assert_eq!(multiplicity(&ty("Vec<Vec<u8>>")), Multiplicity::Multiple);
+        assert_eq!(
+            multiplicity(&ty("std::vec::Vec<String>")),
+            Multiplicity::Multiple
+        );
+    }
+
+    #[test]
+    fn test_expand() {
+        let input = syn::parse_str("struct S { user_name: String }").unwrap();
+        assert!(expand(&input).is_ok());
+        let input = syn::parse_str("struct S(String);").unwrap();
+        assert!(expand(&input).is_err());
+        let input = syn::parse_str("enum E { A }").unwrap();
+        assert!(expand(&input).is_err());
+    }
+}
@@ -0,0 +1,448 @@
+//! Interoperability tests against FreeRADIUS.
+//!
+//! These tests run FreeRADIUS and `radclient` in docker containers, so they are ignored by default.
+//! Run them with a docker daemon that is available for the current user:
+//!
+//! ```shell
+//! $ cargo test -p e2e-test -- --ignored interop
+//! ```
+//!
+//! The image can be overridden by `RADIUS_INTEROP_IMAGE` (default: `freeradius/freeradius-server:latest-3.2`).
+//!
+//! NOTE: EAP (i.e. eapol_test) is not covered, since the server of this library has no EAP method.
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+    use std::net::SocketAddr;
+    use std::path::Path;
+    use std::process::{Command, Stdio};
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+    use tokio::sync::oneshot;
+    use tokio::time::{sleep, timeout};
+
+    use radius::client::Client;
+    use radius::core::avp::AVP;
+    use radius::core::code::Code;
+    use radius::core::packet::Packet;
+    use radius::core::request::Request;
+    use radius::core::{rfc2865, rfc2866, rfc2869};
+    use radius::server::{RequestHandler, SecretProvider, SecretProviderError, Server};
+
+    const DEFAULT_IMAGE: &str = "freeradius/freeradius-server:latest-3.2";
+    const SECRET: &[u8] = b"interop-secret";
+    // the ports on the host that are mapped to the FreeRADIUS container
+    const FREERADIUS_AUTH_PORT: u16 = 21812;
+    const FREERADIUS_ACCT_PORT: u16 = 21813;
+    // the ports of the server of this library that radclient sends the requests to
+    const SERVER_AUTH_PORT: u16 = 31812;
+    const SERVER_ACCT_PORT: u16 = 31813;
+    const SERVER_COA_PORT: u16 = 33799;
+
+    fn image() -> String {
+        std::env::var("RADIUS_INTEROP_IMAGE").unwrap_or_else(|_| DEFAULT_IMAGE.to_owned())
+    }
+
+    /// A FreeRADIUS container that is removed on drop.
+    struct FreeRadius {
+        container_id: String,
+    }
+
+    impl FreeRadius {
+        fn start() -> Self {
+            let config_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("interop");
+            let output = Command::new("docker")
+                .args(["run", "--rm", "-d"])
+                .args(["-p", &format!("{FREERADIUS_AUTH_PORT}:1812/udp")])
+                .args(["-p", &format!("{FREERADIUS_ACCT_PORT}:1813/udp")])
+                .args([
+                    "-v",
+                    &format!(
+                        "{}:/etc/freeradius/clients.conf:ro",
+                        config_dir.join("clients.conf").display()
+                    ),
+                ])
+                .args([
+                    "-v",
+                    &format!(
+                        "{}:/etc/freeradius/mods-config/files/authorize:ro",
+                        config_dir.join("authorize").display()
+                    ),
+                ])
+                .arg(image())
+                .arg("-X")
+                .output()
+                .expect("docker is required for the interoperability tests");
+            assert!(
+                output.status.success(),
+                "failed to start FreeRADIUS: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            FreeRadius {
+                container_id: String::from_utf8_lossy(&output.stdout).trim().to_owned(),
+            }
+        }
+
+        fn auth_addr(&self) -> SocketAddr {
+            format!("127.0.0.1:{FREERADIUS_AUTH_PORT}").parse().unwrap()
+        }
+
+        fn acct_addr(&self) -> SocketAddr {
+            format!("127.0.0.1:{FREERADIUS_ACCT_PORT}").parse().unwrap()
+        }
+
+        /// Wait until FreeRADIUS responds to an Access-Request.
+        async fn wait_until_ready(&self) {
+            let client = Client::new(None, Some(Duration::from_millis(500)));
+            for _ in 0..60 {
+                let packet = make_access_request("alice", b"alice-password");
+                if client.send_packet(&self.auth_addr(), &packet).await.is_ok() {
+                    return;
+                }
+                sleep(Duration::from_millis(500)).await;
+            }
+            panic!("FreeRADIUS is not ready");
+        }
+    }
+
+    impl Drop for FreeRadius {
+        fn drop(&mut self) {
+            let _ = Command::new("docker")
+                .args(["rm", "-f", &self.container_id])
+                .output();
+        }
+    }
+
+    /// Run radclient in a container on the host network, and return its output.
+    fn radclient(server: &str, command: &str, attributes: &str) -> String {
+        let mut child = Command::new("docker")
+            .args(["run", "--rm", "-i", "--network", "host"])
+            .args(["--entrypoint", "radclient"])
+            .arg(image())
+            .args(["-x", "-r", "1", "-t", "3", server, command])
+            .arg(String::from_utf8_lossy(SECRET).as_ref())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("docker is required for the interoperability tests");
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(attributes.as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+    }
+
+    fn make_access_request(user_name: &str, password: &[u8]) -> Packet {
+        let mut packet = Packet::new(Code::AccessRequest, SECRET);
+        rfc2865::add_user_name(&mut packet, user_name);
+        rfc2865::add_user_password(&mut packet, password).unwrap();
+        packet
+    }
+
+    fn hmac_md5(key: &[u8], data: &[u8]) -> [u8; 16] {
+        let mut padded_key = [0u8; 64];
+        padded_key[..key.len()].copy_from_slice(key);
+        let inner_pad: Vec<u8> = padded_key.iter().map(|b| b ^ 0x36).collect();
+        let outer_pad: Vec<u8> = padded_key.iter().map(|b| b ^ 0x5c).collect();
+        let inner = md5::compute([inner_pad.as_slice(), data].concat());
+        md5::compute([outer_pad.as_slice(), &inner.0].concat()).0
+    }
+
+    /// Returns the offset of the value of Message-Authenticator in an encoded packet.
+    fn find_message_authenticator(encoded: &[u8]) -> Option<usize> {
+        let mut offset = 20;
+        while offset + 2 <= encoded.len() {
+            let (typ, len) = (encoded[offset], usize::from(encoded[offset + 1]));
+            if len < 2 {
+                return None;
+            }
+            if typ == rfc2869::MESSAGE_AUTHENTICATOR_TYPE && len == 18 {
+                return Some(offset + 2);
+            }
+            offset += len;
+        }
+        None
+    }
+
+    /// Encode an Access-Request with Message-Authenticator (RFC 3579 section 3.2).
+    fn encode_with_message_authenticator(packet: &Packet) -> Vec<u8> {
+        let mut packet = packet.clone();
+        packet.add(AVP::from_bytes(
+            rfc2869::MESSAGE_AUTHENTICATOR_TYPE,
+            &[0; 16],
+        ));
+        let mut encoded = packet.encode().unwrap();
+        let offset = find_message_authenticator(&encoded).unwrap();
+        let mac = hmac_md5(SECRET, &encoded);
+        encoded[offset..offset + 16].copy_from_slice(&mac);
+        encoded
+    }
+
+    async fn exchange(addr: SocketAddr, request: &[u8]) -> io::Result<Vec<u8>> {
+        let conn = UdpSocket::bind("0.0.0.0:0").await?;
+        conn.send_to(request, addr).await?;
+        let mut buf = vec![0; 4096];
+        let len = timeout(Duration::from_secs(2), conn.recv(&mut buf))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "no response"))??;
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_interop_client_against_freeradius() {
+        let freeradius = FreeRadius::start();
+        freeradius.wait_until_ready().await;
+        let client = Client::new(None, Some(Duration::from_secs(2)));
+
+        // PAP
+        let response = client
+            .send_packet(
+                &freeradius.auth_addr(),
+                &make_access_request("alice", b"alice-password"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(
+            rfc2865::lookup_reply_message(&response).unwrap().unwrap(),
+            "Hello, alice"
+        );
+        assert_eq!(
+            rfc2865::lookup_session_timeout(&response).unwrap().unwrap(),
+            3600
+        );
+        let response = client
+            .send_packet(
+                &freeradius.auth_addr(),
+                &make_access_request("alice", b"wrong-password"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+
+        // CHAP (the Request Authenticator is the challenge)
+        let mut packet = Packet::new(Code::AccessRequest, SECRET);
+        rfc2865::add_user_name(&mut packet, "alice");
+        let chap_id = 1u8;
+        let hash = md5::compute(
+            [
+                &[chap_id],
+                b"alice-password".as_slice(),
+                packet.get_authenticator().as_slice(),
+            ]
+            .concat(),
+        );
+        rfc2865::add_chap_password(&mut packet, &[&[chap_id], hash.0.as_slice()].concat());
+        let response = client
+            .send_packet(&freeradius.auth_addr(), &packet)
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+
+        // accounting
+        let mut packet = Packet::new(Code::AccountingRequest, SECRET);
+        rfc2865::add_user_name(&mut packet, "alice");
+        rfc2866::add_acct_status_type(&mut packet, rfc2866::ACCT_STATUS_TYPE_START);
+        rfc2866::add_acct_session_id(&mut packet, "interop-session-1");
+        rfc2865::add_nas_ip_address(&mut packet, &"192.0.2.1".parse().unwrap());
+        let response = client
+            .send_packet(&freeradius.acct_addr(), &packet)
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccountingResponse);
+
+        // Message-Authenticator: a valid one is accepted and an invalid one is silently discarded
+        let request = make_access_request("alice", b"alice-password");
+        let encoded = encode_with_message_authenticator(&request);
+        let response = exchange(freeradius.auth_addr(), &encoded).await.unwrap();
+        assert!(Packet::is_authentic_response(&response, &encoded, SECRET));
+        assert_eq!(
+            Packet::decode(&response, SECRET).unwrap().get_code(),
+            Code::AccessAccept
+        );
+        if let Some(offset) = find_message_authenticator(&response) {
+            // the Message-Authenticator of a response is computed with the Request Authenticator
+            let mut signed = response.clone();
+            signed[4..20].copy_from_slice(&encoded[4..20]);
+            signed[offset..offset + 16].copy_from_slice(&[0; 16]);
+            assert_eq!(
+                hmac_md5(SECRET, &signed).as_slice(),
+                &response[offset..offset + 16]
+            );
+        }
+
+        let mut broken = encode_with_message_authenticator(&request);
+        let offset = find_message_authenticator(&broken).unwrap();
+        broken[offset] ^= 0xff;
+        assert!(exchange(freeradius.auth_addr(), &broken).await.is_err());
+    }
+
+    struct InteropHandler {}
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for InteropHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let req_packet = req.get_packet();
+            let code = match req_packet.get_code() {
+                Code::AccessRequest => {
+                    let user_name = rfc2865::lookup_user_name(req_packet).and_then(|v| v.ok());
+                    let password = match rfc2865::lookup_user_password(req_packet) {
+                        Some(Ok(password)) => Some(password),
+                        _ => None,
+                    };
+                    let chap_password = rfc2865::lookup_chap_password(req_packet);
+                    let is_chap_authentic = match chap_password {
+                        Some(chap_password) if chap_password.len() == 17 => {
+                            let challenge = rfc2865::lookup_chap_challenge(req_packet)
+                                .unwrap_or_else(|| req_packet.get_authenticator().to_vec());
+                            let hash = md5::compute(
+                                [&chap_password[..1], b"bob-password".as_slice(), &challenge]
+                                    .concat(),
+                            );
+                            chap_password[1..] == hash.0
+                        }
+                        _ => false,
+                    };
+                    if user_name.as_deref() == Some("bob")
+                        && (password.as_deref() == Some(b"bob-password".as_slice())
+                            || is_chap_authentic)
+                    {
+                        Code::AccessAccept
+                    } else {
+                        Code::AccessReject
+                    }
+                }
+                Code::AccountingRequest => Code::AccountingResponse,
+                Code::CoARequest => Code::CoAACK,
+                Code::DisconnectRequest => Code::DisconnectACK,
+                _ => return Ok(()),
+            };
+            let resp_packet = req_packet.make_response_packet(code);
+            conn.send_to(&resp_packet.encode().unwrap(), req.get_remote_addr())
+                .await?;
+            Ok(())
+        }
+    }
+
+    struct InteropSecretProvider {}
+
+    impl SecretProvider for InteropSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(SECRET.to_vec())
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_interop_server_against_radclient() {
+        let mut servers = vec![];
+        for port in [SERVER_AUTH_PORT, SERVER_ACCT_PORT, SERVER_COA_PORT] {
+            let (sender, receiver) = oneshot::channel::<()>();
+            let mut server =
+                Server::listen("0.0.0.0", port, InteropHandler {}, InteropSecretProvider {})
+                    .await
+                    .unwrap();
+            let server_proc = tokio::spawn(async move {
+                server.run(receiver).await.unwrap();
+            });
+            servers.push((sender, server_proc));
+        }
+
+        let run = |port: u16, command: &'static str, attributes: &'static str| {
+            tokio::task::spawn_blocking(move || {
+                radclient(&format!("127.0.0.1:{port}"), command, attributes)
+            })
+        };
+
+        let output = run(
+            SERVER_AUTH_PORT,
+            "auth",
+            "User-Name = bob, User-Password = bob-password\n",
+        )
+        .await
+        .unwrap();
+        assert!(output.contains("Received Access-Accept"), "{}", output);
+
+        let output = run(
+            SERVER_AUTH_PORT,
+            "auth",
+            "User-Name = bob, User-Password = wrong-password\n",
+        )
+        .await
+        .unwrap();
+        assert!(output.contains("Received Access-Reject"), "{}", output);
+
+        // radclient computes CHAP-Password from the given Cleartext-Password
+        let output = run(
+            SERVER_AUTH_PORT,
+            "auth",
+            "User-Name = bob, CHAP-Password = bob-password\n",
+        )
+        .await
+        .unwrap();
+        assert!(output.contains("Received Access-Accept"), "{}", output);
+
+        // radclient adds Message-Authenticator with a valid signature
+        let output = run(
+            SERVER_AUTH_PORT,
+            "auth",
+            "User-Name = bob, User-Password = bob-password, Message-Authenticator = 0x00\n",
+        )
+        .await
+        .unwrap();
+        assert!(output.contains("Received Access-Accept"), "{}", output);
+
+        let output = run(
+            SERVER_ACCT_PORT,
+            "acct",
+            "User-Name = bob, Acct-Status-Type = Start, Acct-Session-Id = interop-session-1\n",
+        )
+        .await
+        .unwrap();
+        assert!(
+            output.contains("Received Accounting-Response"),
+            "{}",
+            output
+        );
+
+        let output = run(
+            SERVER_COA_PORT,
+            "coa",
+            "User-Name = bob, Acct-Session-Id = interop-session-1\n",
+        )
+        .await
+        .unwrap();
+        assert!(output.contains("Received CoA-ACK"), "{}", output);
+
+        let output = run(
+            SERVER_COA_PORT,
+            "disconnect",
+            "User-Name = bob, Acct-Session-Id = interop-session-1\n",
+        )
+        .await
+        .unwrap();
+        assert!(output.contains("Received Disconnect-ACK"), "{}", output);
+
+        for (sender, server_proc) in servers {
+            sender.send(()).unwrap();
+            server_proc.await.unwrap();
+        }
+    }
+}
@@ -1 +1,4 @@
+#[cfg(test)]
+mod interop;
+#[cfg(test)]
 mod test;
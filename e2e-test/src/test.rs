@@ -9,6 +9,7 @@ use tokio::time::sleep;
 use radius::core::code::Code;
 use radius::core::request::Request;
 use radius::core::rfc2865;
+use radius::core::rfc2866;
 use radius::server::{RequestHandler, SecretProvider, SecretProviderError};
 
 struct MyRequestHandler {}
@@ -58,6 +59,32 @@ impl RequestHandler<(), io::Error> for LongTimeTakingHandler {
     }
 }
 
+struct DelayedAccountingHandler {}
+
+#[async_trait]
+impl RequestHandler<(), io::Error> for DelayedAccountingHandler {
+    async fn handle_radius_request(
+        &self,
+        conn: &UdpSocket,
+        req: &Request,
+    ) -> Result<(), io::Error> {
+        let req_packet = req.get_packet();
+        let acct_delay_time = rfc2866::lookup_acct_delay_time(req_packet)
+            .map(|v| v.unwrap())
+            .unwrap_or(0);
+        if acct_delay_time == 0 {
+            // pretend the first transmission has been lost
+            return Ok(());
+        }
+
+        let mut resp_packet = req_packet.make_response_packet(Code::AccountingResponse);
+        rfc2866::add_acct_delay_time(&mut resp_packet, acct_delay_time);
+        conn.send_to(&resp_packet.encode().unwrap(), req.get_remote_addr())
+            .await?;
+        Ok(())
+    }
+}
+
 struct MySecretProvider {}
 
 impl SecretProvider for MySecretProvider {
@@ -78,14 +105,20 @@ mod tests {
     use radius::core::code::Code;
     use radius::core::packet::Packet;
     use radius::core::rfc2865;
+    use radius::core::rfc2866;
 
-    use crate::test::{LongTimeTakingHandler, MyRequestHandler, MySecretProvider};
+    use crate::test::{
+        DelayedAccountingHandler, LongTimeTakingHandler, MyRequestHandler, MySecretProvider,
+    };
     use radius::server::Server;
+    use radius::testing::{MockResponder, MockRule, MockServer};
 
     #[tokio::test]
     async fn test_runner() {
         test_access_request().await;
         test_socket_timeout().await;
+        test_accounting_retransmission().await;
+        test_access_challenge_with_mock_server().await;
     }
 
     async fn test_access_request() {
@@ -104,18 +137,18 @@ mod tests {
         let remote_addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
         let client = Client::new(None, None);
 
-        let mut req_packet = Packet::new(Code::AccessRequest, &b"secret".to_vec());
+        let mut req_packet = Packet::new(Code::AccessRequest, b"secret");
         rfc2865::add_user_name(&mut req_packet, "admin");
         rfc2865::add_user_password(&mut req_packet, b"p@ssw0rd").unwrap();
         let res = client.send_packet(&remote_addr, &req_packet).await.unwrap();
         let maybe_user_name = rfc2865::lookup_user_name(&res);
         let maybe_user_pass = rfc2865::lookup_user_password(&res);
         assert_eq!(res.get_code(), Code::AccessAccept);
-        assert_eq!(maybe_user_name.is_some(), true);
+        assert!(maybe_user_name.is_some());
         assert_eq!(maybe_user_name.unwrap().unwrap(), "admin");
-        assert_eq!(maybe_user_pass.is_none(), true);
+        assert!(maybe_user_pass.is_none());
 
-        let mut req_packet = Packet::new(Code::AccessRequest, &b"secret".to_vec());
+        let mut req_packet = Packet::new(Code::AccessRequest, b"secret");
         rfc2865::add_user_name(&mut req_packet, "admin");
         rfc2865::add_user_password(&mut req_packet, b"INVALID-PASS").unwrap();
         let res = client.send_packet(&remote_addr, &req_packet).await.unwrap();
@@ -146,18 +179,92 @@ mod tests {
         let remote_addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
         let client = Client::new(None, Some(Duration::from_secs(0)));
 
-        let mut req_packet = Packet::new(Code::AccessRequest, &b"secret".to_vec());
+        let mut req_packet = Packet::new(Code::AccessRequest, b"secret");
         rfc2865::add_user_name(&mut req_packet, "admin");
         rfc2865::add_user_password(&mut req_packet, b"p@ssw0rd").unwrap();
         let res = client.send_packet(&remote_addr, &req_packet).await;
 
         let err = res.unwrap_err();
         match err {
-            ClientError::SocketTimeoutError() => {}
+            ClientError::SocketTimeoutError(_) => {}
             _ => panic!("unexpected error: {}", err),
         }
 
         sender.send(()).unwrap();
         server_proc.await.unwrap();
     }
+
+    async fn test_accounting_retransmission() {
+        let (sender, receiver) = oneshot::channel::<()>();
+
+        let port = 1813;
+
+        let mut server = Server::listen(
+            "0.0.0.0",
+            port,
+            DelayedAccountingHandler {},
+            MySecretProvider {},
+        )
+        .await
+        .unwrap();
+
+        let server_proc = tokio::spawn(async move {
+            server.run(receiver).await.unwrap();
+        });
+
+        let remote_addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+        let mut client = Client::new(None, Some(Duration::from_millis(1100)));
+        client.set_max_retransmissions(1);
+
+        let mut req_packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut req_packet, rfc2866::ACCT_STATUS_TYPE_START);
+        rfc2866::add_acct_session_id(&mut req_packet, "session-1");
+        let res = client.send_packet(&remote_addr, &req_packet).await.unwrap();
+        assert_eq!(res.get_code(), Code::AccountingResponse);
+        assert_eq!(rfc2866::lookup_acct_delay_time(&res).unwrap().unwrap(), 1);
+
+        sender.send(()).unwrap();
+        server_proc.await.unwrap();
+    }
+
+    async fn test_access_challenge_with_mock_server() {
+        let responder = MockResponder::new();
+        responder.add_rule(
+            MockRule::respond(Code::AccessAccept)
+                .when_attribute(rfc2865::STATE_TYPE, b"challenge-1")
+                .when_user_password(b"123456"),
+        );
+        responder.add_rule(
+            MockRule::respond(Code::AccessChallenge)
+                .when_user_password(b"p@ssw0rd")
+                .with_template(|_req, res| rfc2865::add_state(res, b"challenge-1")),
+        );
+        responder.add_rule(MockRule::respond(Code::AccessReject));
+        let server = MockServer::start(responder.clone(), b"secret")
+            .await
+            .unwrap();
+
+        let client = Client::new(None, Some(Duration::from_secs(1)));
+        let mut req_packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut req_packet, "admin");
+        rfc2865::add_user_password(&mut req_packet, b"p@ssw0rd").unwrap();
+        let res = client
+            .send_packet(&server.get_address(), &req_packet)
+            .await
+            .unwrap();
+        assert_eq!(res.get_code(), Code::AccessChallenge);
+
+        let mut req_packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut req_packet, "admin");
+        rfc2865::add_user_password(&mut req_packet, b"123456").unwrap();
+        rfc2865::add_state(&mut req_packet, &rfc2865::lookup_state(&res).unwrap());
+        let res = client
+            .send_packet(&server.get_address(), &req_packet)
+            .await
+            .unwrap();
+        assert_eq!(res.get_code(), Code::AccessAccept);
+        assert_eq!(responder.get_received_requests().len(), 2);
+
+        server.shutdown().await;
+    }
 }
@@ -0,0 +1,96 @@
+use std::net::Ipv4Addr;
+
+use radius::core::attribute_set::RadiusAttributes;
+use radius::core::code::Code;
+use radius::core::packet::Packet;
+
+/// The authorization of a user, which is mapped to the attributes of Access-Accept.
+#[derive(RadiusAttributes, Debug, Default, PartialEq)]
+struct Authorization {
+    user_name: String,
+    #[radius(name = "Framed-IP-Address")]
+    address: Option<Ipv4Addr>,
+    session_timeout: Option<u32>,
+    #[radius(type = 25)]
+    classes: Vec<Vec<u8>>,
+    #[radius(name = "Cisco-AVPair")]
+    av_pairs: Vec<String>,
+    #[radius(vendor = 32473, type = 3)]
+    gateway: Option<Ipv4Addr>,
+    #[radius(skip)]
+    cached: bool,
+}
+
+fn main() {
+    let authorization = Authorization {
+        user_name: "bob".to_owned(),
+        address: Some(Ipv4Addr::new(192, 0, 2, 10)),
+        session_timeout: Some(3600),
+        classes: vec![b"gold".to_vec()],
+        av_pairs: vec!["shell:priv-lvl=15".to_owned()],
+        gateway: None,
+        cached: false,
+    };
+    let mut packet = Packet::new(Code::AccessAccept, b"secret");
+    authorization.apply_to_packet(&mut packet).unwrap();
+
+    let decoded = Packet::decode(&packet.encode().unwrap(), b"secret").unwrap();
+    println!("{:?}", Authorization::from_packet(&decoded).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use radius::core::attribute_set::{AttributeSetError, RadiusAttributes};
+    use radius::core::code::Code;
+    use radius::core::packet::Packet;
+    use radius::core::{rfc2865, vsa};
+
+    use crate::Authorization;
+
+    #[test]
+    fn test_derived_attributes() {
+        let authorization = Authorization {
+            user_name: "bob".to_owned(),
+            address: Some(Ipv4Addr::new(192, 0, 2, 10)),
+            session_timeout: None,
+            classes: vec![b"gold".to_vec(), b"silver".to_vec()],
+            av_pairs: vec!["shell:priv-lvl=15".to_owned()],
+            gateway: Some(Ipv4Addr::new(192, 0, 2, 1)),
+            cached: true,
+        };
+        let mut packet = Packet::new(Code::AccessAccept, b"secret");
+        rfc2865::add_user_name(&mut packet, "former");
+        rfc2865::add_reply_message(&mut packet, "welcome");
+        authorization.apply_to_packet(&mut packet).unwrap();
+
+        let decoded = Packet::decode(&packet.encode().unwrap(), b"secret").unwrap();
+        assert_eq!(rfc2865::lookup_all_user_name(&decoded).unwrap().len(), 1);
+        assert_eq!(rfc2865::lookup_all_class(&decoded).len(), 2);
+        assert_eq!(
+            rfc2865::lookup_reply_message(&decoded).unwrap().unwrap(),
+            "welcome"
+        );
+        assert_eq!(
+            vsa::lookup_vendor_attribute(&decoded, 32473, 3)
+                .unwrap()
+                .encode_ipv4()
+                .unwrap(),
+            Ipv4Addr::new(192, 0, 2, 1)
+        );
+        assert_eq!(
+            Authorization::from_packet(&decoded).unwrap(),
+            Authorization {
+                cached: false,
+                ..authorization
+            }
+        );
+
+        let packet = Packet::new(Code::AccessAccept, b"secret");
+        assert_eq!(
+            Authorization::from_packet(&packet).unwrap_err(),
+            AttributeSetError::MissingAttributeError("user-name".to_owned())
+        );
+    }
+}
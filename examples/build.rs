@@ -0,0 +1,9 @@
+// Generate the module of the vendor dictionary (`dicts/dictionary.acme`) into `OUT_DIR`; `vendor_dictionary.rs`
+// includes it.
+fn main() {
+    println!("cargo:rerun-if-changed=dicts/dictionary.acme");
+    radius_code_generator::Generator::new()
+        .dictionary("dicts/dictionary.acme")
+        .generate()
+        .unwrap();
+}
@@ -0,0 +1,72 @@
+#[macro_use]
+extern crate log;
+
+use std::{env, io, process};
+
+use async_trait::async_trait;
+use tokio::net::UdpSocket;
+use tokio::signal;
+
+use radius::core::code::Code;
+use radius::core::request::Request;
+use radius::core::rfc2865;
+use radius::server::{RequestHandler, Server};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    // start UDP listening on the all listeners of the configuration file
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/radius.toml".to_owned());
+    let servers = Server::from_config(&path, MyRequestHandler {})
+        .await
+        .unwrap();
+
+    for server in &servers {
+        info!(
+            "serve is now ready: {}",
+            server.get_listen_address().unwrap()
+        );
+    }
+
+    // start the loops to handle the RADIUS requests
+    let result = Server::run_all(&servers, signal::ctrl_c()).await;
+    info!("{:?}", result);
+    if result.is_err() {
+        process::exit(1);
+    }
+}
+
+struct MyRequestHandler {}
+
+#[async_trait]
+impl RequestHandler<(), io::Error> for MyRequestHandler {
+    async fn handle_radius_request(
+        &self,
+        conn: &UdpSocket,
+        req: &Request,
+    ) -> Result<(), io::Error> {
+        let req_packet = req.get_packet();
+        let user_name = rfc2865::lookup_user_name(req_packet);
+        let user_password = rfc2865::lookup_user_password(req_packet);
+
+        let code = match (user_name, user_password) {
+            (Some(Ok(user_name)), Some(Ok(user_password)))
+                if user_name == "admin" && user_password == b"p@ssw0rd" =>
+            {
+                Code::AccessAccept
+            }
+            _ => Code::AccessReject,
+        };
+        info!("response => {:?} to {}", code, req.get_remote_addr());
+
+        conn.send_to(
+            &req_packet.make_response_packet(code).encode().unwrap(),
+            req.get_remote_addr(),
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,412 @@
+//! A minimal RADIUS server for the enterprise Wi-Fi (802.1X).
+//!
+//! This combines the server bootstrap by a configuration file, a users file as the backend of the credentials,
+//! and an accounting sink that writes the sessions as JSON Lines.
+//!
+//! The supplicants are authenticated by EAP-MSCHAPv2 with the passwords of the users file, or by EAP-TLS with
+//! their certificates if the certificate chain and the key of the server and the CA of the clients are given
+//! (as PEM files); the Access-Accept carries the MPPE keys to the access point. The password authentication (PAP)
+//! is available as well for the testing of the access points.
+//!
+//! ```sh
+//! cargo run --example dot1x_server -- examples/dot1x.toml examples/dot1x_users accounting.jsonl \
+//!     [server.pem server.key ca.pem]
+//! ```
+
+#[macro_use]
+extern crate log;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+use std::{env, fs, io, process};
+
+use async_trait::async_trait;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::RootCertStore;
+use tokio::net::UdpSocket;
+use tokio::signal;
+
+use radius::core::code::Code;
+use radius::core::packet::Packet;
+use radius::core::request::Request;
+use radius::core::{rfc2865, rfc2869};
+use radius::eap::{
+    EapAuthenticator, EapCredentialProvider, EapError, EapRequestHandler, MsChapV2Method, TlsMethod,
+};
+use radius::export::{AccountingExporter, AccountingRecord, AccountingSink, ExportFormat};
+use radius::server::{RequestHandler, Server};
+
+const SESSION_TIMEOUT_SECS: u32 = 8 * 60 * 60;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().collect();
+    let config_path = args.get(1).map_or("examples/dot1x.toml", |s| s);
+    let users_path = args.get(2).map_or("examples/dot1x_users", |s| s);
+    let accounting_path = args.get(3).map_or("accounting.jsonl", |s| s);
+
+    let users = Users::load(users_path).unwrap();
+    let mut eap_authenticator = EapAuthenticator::new();
+    eap_authenticator.add_method(MsChapV2Method::new(users.clone()));
+    if let (Some(certificate_path), Some(key_path), Some(ca_path)) =
+        (args.get(4), args.get(5), args.get(6))
+    {
+        let tls_config = load_tls_config(certificate_path, key_path, ca_path).unwrap();
+        eap_authenticator.add_method(TlsMethod::new(Arc::new(tls_config)));
+    }
+
+    let handler = Dot1xRequestHandler::new(
+        users,
+        eap_authenticator,
+        Box::new(AccountingExporter::new(
+            accounting_path,
+            ExportFormat::JsonLines,
+        )),
+    );
+    let servers = Server::from_config(config_path, handler).await.unwrap();
+    for server in &servers {
+        info!(
+            "serve is now ready: {}",
+            server.get_listen_address().unwrap()
+        );
+    }
+
+    let result = Server::run_all(&servers, signal::ctrl_c()).await;
+    info!("{:?}", result);
+    if result.is_err() {
+        process::exit(1);
+    }
+}
+
+/// Returns the TLS configuration of EAP-TLS, which requires the certificates of the clients that the CA has issued.
+fn load_tls_config(
+    certificate_path: &str,
+    key_path: &str,
+    ca_path: &str,
+) -> Result<rustls::ServerConfig, Box<dyn Error>> {
+    let certificates =
+        CertificateDer::pem_file_iter(certificate_path)?.collect::<Result<_, _>>()?;
+    let key = PrivateKeyDer::from_pem_file(key_path)?;
+    let mut roots = RootCertStore::empty();
+    for ca in CertificateDer::pem_file_iter(ca_path)? {
+        roots.add(ca?)?;
+    }
+    let config = rustls::ServerConfig::builder()
+        .with_client_cert_verifier(WebPkiClientVerifier::builder(Arc::new(roots)).build()?)
+        .with_single_cert(certificates, key)?;
+    Ok(config)
+}
+
+/// The credentials of the users; each line of the file is `<User-Name> <password>`.
+#[derive(Clone)]
+struct Users {
+    passwords: Arc<HashMap<String, Vec<u8>>>,
+}
+
+impl Users {
+    fn load(path: &str) -> io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    fn parse(content: &str) -> Self {
+        let passwords = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once(char::is_whitespace))
+            .map(|(user, password)| (user.to_owned(), password.trim().as_bytes().to_vec()))
+            .collect();
+        Users {
+            passwords: Arc::new(passwords),
+        }
+    }
+
+    fn authenticate(&self, user_name: &str, password: &[u8]) -> bool {
+        self.passwords
+            .get(user_name)
+            .is_some_and(|expected| expected == password)
+    }
+}
+
+#[async_trait]
+impl EapCredentialProvider for Users {
+    async fn fetch_password(&self, identity: &str) -> Result<Option<String>, EapError> {
+        Ok(self
+            .passwords
+            .get(identity)
+            .map(|password| String::from_utf8_lossy(password).into_owned()))
+    }
+}
+
+struct Dot1xRequestHandler {
+    users: Users,
+    eap_handler: EapRequestHandler,
+    accounting_sink: Box<dyn AccountingSink>,
+}
+
+impl Dot1xRequestHandler {
+    fn new(
+        users: Users,
+        eap_authenticator: EapAuthenticator,
+        accounting_sink: Box<dyn AccountingSink>,
+    ) -> Self {
+        Dot1xRequestHandler {
+            users,
+            eap_handler: EapRequestHandler::new(eap_authenticator),
+            accounting_sink,
+        }
+    }
+
+    fn authenticate(&self, req_packet: &Packet) -> Packet {
+        let user_name = rfc2865::lookup_user_name(req_packet).and_then(Result::ok);
+        let password = rfc2865::lookup_user_password(req_packet).and_then(Result::ok);
+        match (user_name, password) {
+            (Some(user_name), Some(password)) if self.users.authenticate(&user_name, &password) => {
+                info!("accepted {}", user_name);
+                let mut response = req_packet.make_response_packet(Code::AccessAccept);
+                rfc2865::add_session_timeout(&mut response, SESSION_TIMEOUT_SECS);
+                response
+            }
+            (user_name, _) => {
+                info!("rejected {:?}", user_name);
+                req_packet.make_response_packet(Code::AccessReject)
+            }
+        }
+    }
+
+    async fn account(&self, req: &Request) -> Option<Packet> {
+        match self
+            .accounting_sink
+            .write_record(&AccountingRecord::from_request(req))
+            .await
+        {
+            Ok(()) => Some(
+                req.get_packet()
+                    .make_response_packet(Code::AccountingResponse),
+            ),
+            Err(e) => {
+                // not responding makes the NAS retransmit the request later
+                error!("failed to write an accounting record; {}", e);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl RequestHandler<(), io::Error> for Dot1xRequestHandler {
+    async fn handle_radius_request(
+        &self,
+        conn: &UdpSocket,
+        req: &Request,
+    ) -> Result<(), io::Error> {
+        let req_packet = req.get_packet();
+        if req_packet.get_code() == Code::AccessRequest
+            && rfc2869::lookup_eap_message(req_packet).is_some()
+        {
+            return self.eap_handler.handle_radius_request(conn, req).await;
+        }
+
+        let response = match req_packet.get_code() {
+            Code::AccessRequest => Some(self.authenticate(req_packet)),
+            Code::AccountingRequest => self.account(req).await,
+            code => {
+                info!("ignoring {} from {}", code.string(), req.get_remote_addr());
+                None
+            }
+        };
+
+        if let Some(response) = response {
+            let response = response
+                .encode()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            conn.send_to(&response, req.get_remote_addr()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use radius::client::Client;
+    use radius::config::ServerConfig;
+    use radius::core::code::Code;
+    use radius::core::mschap::{generate_mppe_keys, generate_nt_response};
+    use radius::core::packet::Packet;
+    use radius::core::{rfc2548, rfc2865, rfc2866};
+    use radius::eap::{
+        EapAuthenticator, EapCode, EapPacket, MsChapV2Method, EAP_TYPE_IDENTITY, EAP_TYPE_MSCHAPV2,
+    };
+    use radius::export::{AccountingExporter, ExportFormat};
+    use radius::server::Server;
+
+    use crate::{Dot1xRequestHandler, Users};
+
+    fn make_eap_request(eap_response: &EapPacket, state: Option<Vec<u8>>) -> Packet {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        if let Some(state) = state {
+            rfc2865::add_state(&mut request, &state);
+        }
+        request.add_eap_message(&eap_response.encode());
+        request
+    }
+
+    #[tokio::test]
+    async fn test_dot1x_server() {
+        let dir = std::env::temp_dir().join(format!("radius-dot1x-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let accounting_path = dir.join("accounting.jsonl");
+
+        let config = ServerConfig::from_toml_str(
+            r#"
+[[listeners]]
+host = "127.0.0.1"
+port = 0
+
+[[listeners]]
+host = "127.0.0.1"
+port = 0
+
+[[clients]]
+address = "127.0.0.1"
+secret = "secret"
+"#,
+        )
+        .unwrap();
+        let users = Users::parse("# comment\nalice wonderland\n");
+        let mut eap_authenticator = EapAuthenticator::new();
+        eap_authenticator.add_method(MsChapV2Method::new(users.clone()));
+        let handler = Dot1xRequestHandler::new(
+            users,
+            eap_authenticator,
+            Box::new(AccountingExporter::new(
+                &accounting_path,
+                ExportFormat::JsonLines,
+            )),
+        );
+        let servers = Server::listen_with_config(&config, handler).await.unwrap();
+        let auth_addr = servers[0].get_listen_address().unwrap();
+        let acct_addr = servers[1].get_listen_address().unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server_task = tokio::spawn(async move { Server::run_all(&servers, shutdown_rx).await });
+
+        let client = Client::new(None, Some(Duration::from_secs(3)));
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        rfc2865::add_user_password(&mut request, b"wonderland").unwrap();
+        let response = client.send_packet(&auth_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert!(rfc2865::lookup_session_timeout(&response).is_some());
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        rfc2865::add_user_password(&mut request, b"wrong").unwrap();
+        let response = client.send_packet(&auth_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+
+        // EAP-MSCHAPv2
+        let identity_response = EapPacket::new_response(1, EAP_TYPE_IDENTITY, b"alice");
+        let request = make_eap_request(&identity_response, None);
+        let response = client.send_packet(&auth_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessChallenge);
+        let challenge_request = EapPacket::decode(&response.get_eap_message()).unwrap();
+        assert_eq!(challenge_request.get_type(), Some(EAP_TYPE_MSCHAPV2));
+        let type_data = challenge_request.get_type_data();
+        let ms_chapv2_id = type_data[1];
+        let authenticator_challenge = type_data[5..21].to_vec();
+
+        let peer_challenge = [0x21; 16];
+        let nt_response = generate_nt_response(
+            &authenticator_challenge,
+            &peer_challenge,
+            "alice",
+            "wonderland",
+        );
+        let value = [&peer_challenge[..], &[0; 8], &nt_response[..], &[0]].concat();
+        let body = [&[49], &value[..], b"alice"].concat();
+        let len = ((4 + body.len()) as u16).to_be_bytes();
+        let challenge_response = EapPacket::new_response(
+            challenge_request.get_identifier(),
+            EAP_TYPE_MSCHAPV2,
+            &[&[2, ms_chapv2_id, len[0], len[1]], &body[..]].concat(),
+        );
+        let request = make_eap_request(&challenge_response, rfc2865::lookup_state(&response));
+        let response = client.send_packet(&auth_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessChallenge);
+        let success_request = EapPacket::decode(&response.get_eap_message()).unwrap();
+        assert_eq!(success_request.get_type_data()[0], 3);
+
+        let success_response =
+            EapPacket::new_response(success_request.get_identifier(), EAP_TYPE_MSCHAPV2, &[3]);
+        let request = make_eap_request(&success_response, rfc2865::lookup_state(&response));
+        let response = client.send_packet(&auth_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(
+            EapPacket::decode(&response.get_eap_message())
+                .unwrap()
+                .get_code(),
+            EapCode::Success
+        );
+        let (send_key, recv_key) = generate_mppe_keys("wonderland", &nt_response);
+        assert_eq!(
+            rfc2548::lookup_ms_mppe_send_key(&response, request.get_authenticator())
+                .unwrap()
+                .unwrap(),
+            send_key
+        );
+        assert_eq!(
+            rfc2548::lookup_ms_mppe_recv_key(&response, request.get_authenticator())
+                .unwrap()
+                .unwrap(),
+            recv_key
+        );
+
+        let identity_response = EapPacket::new_response(1, EAP_TYPE_IDENTITY, b"bob");
+        let request = make_eap_request(&identity_response, None);
+        let response = client.send_packet(&auth_addr, &request).await.unwrap();
+        let challenge_request = EapPacket::decode(&response.get_eap_message()).unwrap();
+        let challenge_response = EapPacket::new_response(
+            challenge_request.get_identifier(),
+            EAP_TYPE_MSCHAPV2,
+            &[
+                &[2, challenge_request.get_type_data()[1], len[0], len[1]],
+                &body[..],
+            ]
+            .concat(),
+        );
+        let request = make_eap_request(&challenge_response, rfc2865::lookup_state(&response));
+        let response = client.send_packet(&auth_addr, &request).await.unwrap();
+        let failure_request = EapPacket::decode(&response.get_eap_message()).unwrap();
+        assert_eq!(failure_request.get_type_data()[0], 4);
+        let failure_response =
+            EapPacket::new_response(failure_request.get_identifier(), EAP_TYPE_MSCHAPV2, &[4]);
+        let request = make_eap_request(&failure_response, rfc2865::lookup_state(&response));
+        let response = client.send_packet(&auth_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+
+        let mut request = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        rfc2866::add_acct_status_type(&mut request, rfc2866::ACCT_STATUS_TYPE_START);
+        rfc2866::add_acct_session_id(&mut request, "session-1");
+        let response = client.send_packet(&acct_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccountingResponse);
+
+        shutdown_tx.send(()).unwrap();
+        server_task.await.unwrap().unwrap();
+
+        let records = fs::read_to_string(&accounting_path).unwrap();
+        assert_eq!(records.lines().count(), 1);
+        assert!(records.contains("session-1"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
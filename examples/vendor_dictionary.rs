@@ -0,0 +1,70 @@
+use std::net::Ipv4Addr;
+
+use radius::core::code::Code;
+use radius::core::packet::Packet;
+
+/// The module that `build.rs` generates from `dicts/dictionary.acme`.
+pub mod acme {
+    include!(concat!(env!("OUT_DIR"), "/acme.rs"));
+}
+
+fn main() {
+    let mut packet = Packet::new(Code::AccessAccept, b"secret");
+    acme::add_acme_group(&mut packet, "operators");
+    acme::add_acme_tier(&mut packet, acme::ACME_TIER_GOLD);
+    acme::add_acme_gateway(&mut packet, &Ipv4Addr::new(192, 0, 2, 1));
+
+    let decoded = Packet::decode(&packet.encode().unwrap(), b"secret").unwrap();
+    println!(
+        "Acme-Group = {}, Acme-Tier = {}, Acme-Gateway = {}",
+        acme::lookup_acme_group(&decoded).unwrap().unwrap(),
+        acme::lookup_acme_tier(&decoded).unwrap().unwrap(),
+        acme::lookup_acme_gateway(&decoded).unwrap().unwrap(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use radius::core::code::Code;
+    use radius::core::packet::Packet;
+    use radius::core::rfc2865;
+
+    use crate::acme;
+
+    #[test]
+    fn test_generated_module() {
+        let mut packet = Packet::new(Code::AccessAccept, b"secret");
+        acme::add_acme_group(&mut packet, "operators");
+        acme::add_acme_tier(&mut packet, acme::ACME_TIER_SILVER);
+        acme::add_acme_gateway(&mut packet, &Ipv4Addr::new(192, 0, 2, 1));
+        acme::add_acme_token(&mut packet, &[0xde, 0xad]);
+        acme::add_acme_octets_total(&mut packet, 5 << 32);
+
+        let decoded = Packet::decode(&packet.encode().unwrap(), b"secret").unwrap();
+        assert_eq!(decoded.lookup_all(rfc2865::VENDOR_SPECIFIC_TYPE).len(), 5);
+        assert_eq!(
+            acme::lookup_acme_group(&decoded).unwrap().unwrap(),
+            "operators"
+        );
+        assert_eq!(
+            acme::lookup_acme_tier(&decoded).unwrap().unwrap(),
+            acme::ACME_TIER_SILVER
+        );
+        assert_eq!(
+            acme::lookup_acme_gateway(&decoded).unwrap().unwrap(),
+            Ipv4Addr::new(192, 0, 2, 1)
+        );
+        assert_eq!(acme::lookup_acme_token(&decoded).unwrap(), vec![0xde, 0xad]);
+        assert_eq!(
+            acme::lookup_acme_octets_total(&decoded).unwrap().unwrap(),
+            5 << 32
+        );
+        assert_eq!(acme::VENDOR_ACME, 32473);
+        assert_eq!(acme::ATTRIBUTES.len(), 5);
+
+        acme::delete_acme_group(&mut packet);
+        assert!(acme::lookup_acme_group(&packet).is_none());
+    }
+}
@@ -0,0 +1,74 @@
+/*
+ * C API of the RADIUS packet codec of radius-rs.
+ *
+ * Link with libradius_ffi (cdylib or staticlib) that is built by `cargo build -p radius-ffi --release`.
+ * A packet is an opaque handle that has to be released by radius_packet_free().
+ * The functions that can fail return RADIUS_OK or a negative error code (see radius_strerror()).
+ * The functions that write the bytes into a caller's buffer always set the required length to out_len,
+ * and return RADIUS_ERR_BUFFER_TOO_SMALL if the buffer is insufficient.
+ */
+
+#ifndef RADIUS_FFI_H
+#define RADIUS_FFI_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define RADIUS_FFI_API_VERSION 1
+
+#define RADIUS_OK 0
+#define RADIUS_ERR_NULL_POINTER (-1)
+#define RADIUS_ERR_DECODING (-2)
+#define RADIUS_ERR_ENCODING (-3)
+#define RADIUS_ERR_BUFFER_TOO_SMALL (-4)
+#define RADIUS_ERR_NOT_FOUND (-5)
+#define RADIUS_ERR_INVALID_ATTRIBUTE (-6)
+
+typedef struct radius_packet radius_packet;
+
+uint32_t radius_ffi_api_version(void);
+const char *radius_strerror(int code);
+
+/* Make a new packet with a random identifier and a random authenticator. */
+radius_packet *radius_packet_new(uint8_t code, const uint8_t *secret, size_t secret_len);
+/* Decode a datagram into a new packet. */
+int radius_packet_decode(const uint8_t *data, size_t data_len, const uint8_t *secret, size_t secret_len,
+                         radius_packet **out_packet);
+/* Make a new response packet for a request packet. */
+radius_packet *radius_packet_make_response(const radius_packet *request, uint8_t code);
+void radius_packet_free(radius_packet *packet);
+
+uint8_t radius_packet_get_code(const radius_packet *packet);
+uint8_t radius_packet_get_identifier(const radius_packet *packet);
+void radius_packet_set_identifier(radius_packet *packet, uint8_t identifier);
+int radius_packet_get_authenticator(const radius_packet *packet, uint8_t *buf, size_t buf_len, size_t *out_len);
+int radius_packet_encode(const radius_packet *packet, uint8_t *buf, size_t buf_len, size_t *out_len);
+
+/* The value has to be 253 bytes or less. */
+int radius_packet_add_attribute(radius_packet *packet, uint8_t type, const uint8_t *value, size_t value_len);
+void radius_packet_delete_attribute(radius_packet *packet, uint8_t type);
+size_t radius_packet_get_attribute_count(const radius_packet *packet);
+/* The values point to the memory of the packet; they are valid until the packet is modified or released. */
+int radius_packet_get_attribute(const radius_packet *packet, size_t index, uint8_t *out_type,
+                                const uint8_t **out_value, size_t *out_len);
+int radius_packet_lookup_attribute(const radius_packet *packet, uint8_t type, const uint8_t **out_value,
+                                   size_t *out_len);
+
+int radius_packet_add_user_password(radius_packet *packet, const uint8_t *password, size_t password_len);
+int radius_packet_lookup_user_password(const radius_packet *packet, uint8_t *buf, size_t buf_len, size_t *out_len);
+
+/* Return 1 if the authenticator is valid, otherwise 0. */
+int radius_is_authentic_request(const uint8_t *request, size_t request_len, const uint8_t *secret,
+                                size_t secret_len);
+int radius_is_authentic_response(const uint8_t *response, size_t response_len, const uint8_t *request,
+                                 size_t request_len, const uint8_t *secret, size_t secret_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RADIUS_FFI_H */
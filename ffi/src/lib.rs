@@ -0,0 +1,585 @@
+//! C API of the RADIUS packet codec.
+//!
+//! This crate is built as a `cdylib` and a `staticlib` (`libradius_ffi`); the declarations are in `include/radius.h`.
+//! A packet is an opaque handle that is allocated by `radius_packet_new()`, `radius_packet_decode()` or
+//! `radius_packet_make_response()`, and it has to be released by `radius_packet_free()`.
+//!
+//! The functions that can fail return `RADIUS_OK` or a negative error code (see `radius_strerror()`).
+//! The functions that write the bytes into a caller's buffer always set the required length to `out_len`,
+//! and return `RADIUS_ERR_BUFFER_TOO_SMALL` if the buffer is insufficient.
+
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use std::slice;
+
+use radius::core::avp::AVP;
+use radius::core::code::Code;
+use radius::core::packet::Packet;
+use radius::core::rfc2865;
+
+/// The version of this API; it is incremented when the API is changed incompatibly.
+pub const RADIUS_FFI_API_VERSION: u32 = 1;
+
+pub const RADIUS_OK: c_int = 0;
+pub const RADIUS_ERR_NULL_POINTER: c_int = -1;
+pub const RADIUS_ERR_DECODING: c_int = -2;
+pub const RADIUS_ERR_ENCODING: c_int = -3;
+pub const RADIUS_ERR_BUFFER_TOO_SMALL: c_int = -4;
+pub const RADIUS_ERR_NOT_FOUND: c_int = -5;
+pub const RADIUS_ERR_INVALID_ATTRIBUTE: c_int = -6;
+
+/// Returns `RADIUS_FFI_API_VERSION` of the library.
+#[no_mangle]
+pub extern "C" fn radius_ffi_api_version() -> u32 {
+    RADIUS_FFI_API_VERSION
+}
+
+/// Returns a static, NUL-terminated description of an error code.
+#[no_mangle]
+pub extern "C" fn radius_strerror(code: c_int) -> *const c_char {
+    let message: &'static [u8] = match code {
+        RADIUS_OK => b"ok\0",
+        RADIUS_ERR_NULL_POINTER => b"null pointer\0",
+        RADIUS_ERR_DECODING => b"failed to decode a RADIUS packet\0",
+        RADIUS_ERR_ENCODING => b"failed to encode a RADIUS packet\0",
+        RADIUS_ERR_BUFFER_TOO_SMALL => b"buffer too small\0",
+        RADIUS_ERR_NOT_FOUND => b"attribute not found\0",
+        RADIUS_ERR_INVALID_ATTRIBUTE => b"invalid attribute\0",
+        _ => b"unknown error\0",
+    };
+    message.as_ptr() as *const c_char
+}
+
+/// Make a new packet with a random identifier and a random authenticator.
+///
+/// # Safety
+///
+/// `secret` must point to `secret_len` readable bytes (or may be null if `secret_len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_new(
+    code: u8,
+    secret: *const u8,
+    secret_len: usize,
+) -> *mut Packet {
+    match as_slice(secret, secret_len) {
+        Some(secret) => Box::into_raw(Box::new(Packet::new(Code::from(code), secret))),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Decode a datagram into a new packet, and set that to `out_packet`.
+///
+/// # Safety
+///
+/// `data` and `secret` must point to the readable bytes of the lengths, and `out_packet` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_decode(
+    data: *const u8,
+    data_len: usize,
+    secret: *const u8,
+    secret_len: usize,
+    out_packet: *mut *mut Packet,
+) -> c_int {
+    let (data, secret) = match (as_slice(data, data_len), as_slice(secret, secret_len)) {
+        (Some(data), Some(secret)) if !out_packet.is_null() => (data, secret),
+        _ => return RADIUS_ERR_NULL_POINTER,
+    };
+    match Packet::decode(data, secret) {
+        Ok(packet) => {
+            *out_packet = Box::into_raw(Box::new(packet));
+            RADIUS_OK
+        }
+        Err(_) => RADIUS_ERR_DECODING,
+    }
+}
+
+/// Make a new response packet for a request packet (the identifier, the authenticator and the secret are inherited).
+///
+/// # Safety
+///
+/// `request` must be a packet of this library.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_make_response(
+    request: *const Packet,
+    code: u8,
+) -> *mut Packet {
+    match request.as_ref() {
+        Some(request) => Box::into_raw(Box::new(request.make_response_packet(Code::from(code)))),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Release a packet; null is ignored.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library that has not been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_free(packet: *mut Packet) {
+    if !packet.is_null() {
+        drop(Box::from_raw(packet));
+    }
+}
+
+/// Returns the code of a packet (0 if `packet` is null).
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_get_code(packet: *const Packet) -> u8 {
+    packet.as_ref().map_or(0, |packet| packet.get_code() as u8)
+}
+
+/// Returns the identifier of a packet (0 if `packet` is null).
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_get_identifier(packet: *const Packet) -> u8 {
+    packet.as_ref().map_or(0, |packet| packet.get_identifier())
+}
+
+/// Set the identifier of a packet.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_set_identifier(packet: *mut Packet, identifier: u8) {
+    if let Some(packet) = packet.as_mut() {
+        packet.set_identifier(identifier);
+    }
+}
+
+/// Copy the authenticator of a packet (16 bytes) into `buf`.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library, `buf` must point to `buf_len` writable bytes and `out_len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_get_authenticator(
+    packet: *const Packet,
+    buf: *mut u8,
+    buf_len: usize,
+    out_len: *mut usize,
+) -> c_int {
+    match packet.as_ref() {
+        Some(packet) => write_output(packet.get_authenticator(), buf, buf_len, out_len),
+        None => RADIUS_ERR_NULL_POINTER,
+    }
+}
+
+/// Encode a packet into `buf`.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library, `buf` must point to `buf_len` writable bytes and `out_len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_encode(
+    packet: *const Packet,
+    buf: *mut u8,
+    buf_len: usize,
+    out_len: *mut usize,
+) -> c_int {
+    let packet = match packet.as_ref() {
+        Some(packet) => packet,
+        None => return RADIUS_ERR_NULL_POINTER,
+    };
+    match packet.encode() {
+        Ok(encoded) => write_output(&encoded, buf, buf_len, out_len),
+        Err(_) => RADIUS_ERR_ENCODING,
+    }
+}
+
+/// Add an attribute with the raw value to a packet; the value has to be 253 bytes or less.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library, and `value` must point to `value_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_add_attribute(
+    packet: *mut Packet,
+    typ: u8,
+    value: *const u8,
+    value_len: usize,
+) -> c_int {
+    let (packet, value) = match (packet.as_mut(), as_slice(value, value_len)) {
+        (Some(packet), Some(value)) => (packet, value),
+        _ => return RADIUS_ERR_NULL_POINTER,
+    };
+    if value.len() > 253 {
+        return RADIUS_ERR_INVALID_ATTRIBUTE;
+    }
+    packet.add(AVP::from_bytes(typ, value));
+    RADIUS_OK
+}
+
+/// Delete all of the attributes of the type from a packet.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_delete_attribute(packet: *mut Packet, typ: u8) {
+    if let Some(packet) = packet.as_mut() {
+        packet.delete(typ);
+    }
+}
+
+/// Returns the number of the attributes of a packet.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_get_attribute_count(packet: *const Packet) -> usize {
+    packet
+        .as_ref()
+        .map_or(0, |packet| packet.get_attributes().len())
+}
+
+/// Get the attribute at the index (in the order of the packet).
+///
+/// The value points to the memory of the packet; it is valid until the packet is modified or released.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library, and `out_type`, `out_value` and `out_len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_get_attribute(
+    packet: *const Packet,
+    index: usize,
+    out_type: *mut u8,
+    out_value: *mut *const u8,
+    out_len: *mut usize,
+) -> c_int {
+    let packet = match packet.as_ref() {
+        Some(packet) if !out_type.is_null() => packet,
+        _ => return RADIUS_ERR_NULL_POINTER,
+    };
+    match packet.get_attributes().get(index) {
+        Some(avp) => {
+            *out_type = avp.get_type();
+            borrow_output(avp.get_value(), out_value, out_len)
+        }
+        None => RADIUS_ERR_NOT_FOUND,
+    }
+}
+
+/// Lookup the first attribute of the type.
+///
+/// The value points to the memory of the packet; it is valid until the packet is modified or released.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library, and `out_value` and `out_len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_lookup_attribute(
+    packet: *const Packet,
+    typ: u8,
+    out_value: *mut *const u8,
+    out_len: *mut usize,
+) -> c_int {
+    match packet.as_ref() {
+        Some(packet) => match packet.lookup(typ) {
+            Some(avp) => borrow_output(avp.get_value(), out_value, out_len),
+            None => RADIUS_ERR_NOT_FOUND,
+        },
+        None => RADIUS_ERR_NULL_POINTER,
+    }
+}
+
+/// Add a User-Password attribute that is encrypted with the secret and the authenticator of the packet.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library, and `password` must point to `password_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_add_user_password(
+    packet: *mut Packet,
+    password: *const u8,
+    password_len: usize,
+) -> c_int {
+    match (packet.as_mut(), as_slice(password, password_len)) {
+        (Some(packet), Some(password)) => match rfc2865::add_user_password(packet, password) {
+            Ok(()) => RADIUS_OK,
+            Err(_) => RADIUS_ERR_INVALID_ATTRIBUTE,
+        },
+        _ => RADIUS_ERR_NULL_POINTER,
+    }
+}
+
+/// Decrypt the User-Password attribute of a packet into `buf`.
+///
+/// # Safety
+///
+/// `packet` must be a packet of this library, `buf` must point to `buf_len` writable bytes and `out_len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn radius_packet_lookup_user_password(
+    packet: *const Packet,
+    buf: *mut u8,
+    buf_len: usize,
+    out_len: *mut usize,
+) -> c_int {
+    match packet.as_ref().map(rfc2865::lookup_user_password) {
+        Some(Some(Ok(password))) => write_output(&password, buf, buf_len, out_len),
+        Some(Some(Err(_))) => RADIUS_ERR_INVALID_ATTRIBUTE,
+        Some(None) => RADIUS_ERR_NOT_FOUND,
+        None => RADIUS_ERR_NULL_POINTER,
+    }
+}
+
+/// Returns 1 if the Request Authenticator of a request datagram is valid for the secret, otherwise 0.
+///
+/// # Safety
+///
+/// `request` and `secret` must point to the readable bytes of the lengths.
+#[no_mangle]
+pub unsafe extern "C" fn radius_is_authentic_request(
+    request: *const u8,
+    request_len: usize,
+    secret: *const u8,
+    secret_len: usize,
+) -> c_int {
+    match (as_slice(request, request_len), as_slice(secret, secret_len)) {
+        (Some(request), Some(secret)) => Packet::is_authentic_request(request, secret) as c_int,
+        _ => 0,
+    }
+}
+
+/// Returns 1 if the Response Authenticator of a response datagram is valid for the request datagram and the secret,
+/// otherwise 0.
+///
+/// # Safety
+///
+/// `response`, `request` and `secret` must point to the readable bytes of the lengths.
+#[no_mangle]
+pub unsafe extern "C" fn radius_is_authentic_response(
+    response: *const u8,
+    response_len: usize,
+    request: *const u8,
+    request_len: usize,
+    secret: *const u8,
+    secret_len: usize,
+) -> c_int {
+    match (
+        as_slice(response, response_len),
+        as_slice(request, request_len),
+        as_slice(secret, secret_len),
+    ) {
+        (Some(response), Some(request), Some(secret)) => {
+            Packet::is_authentic_response(response, request, secret) as c_int
+        }
+        _ => 0,
+    }
+}
+
+unsafe fn as_slice<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(data, len))
+    }
+}
+
+unsafe fn write_output(data: &[u8], buf: *mut u8, buf_len: usize, out_len: *mut usize) -> c_int {
+    if out_len.is_null() || (buf.is_null() && buf_len > 0) {
+        return RADIUS_ERR_NULL_POINTER;
+    }
+    *out_len = data.len();
+    if buf_len < data.len() {
+        return RADIUS_ERR_BUFFER_TOO_SMALL;
+    }
+    if !data.is_empty() {
+        ptr::copy_nonoverlapping(data.as_ptr(), buf, data.len());
+    }
+    RADIUS_OK
+}
+
+unsafe fn borrow_output(data: &[u8], out_value: *mut *const u8, out_len: *mut usize) -> c_int {
+    if out_value.is_null() || out_len.is_null() {
+        return RADIUS_ERR_NULL_POINTER;
+    }
+    *out_value = data.as_ptr();
+    *out_len = data.len();
+    RADIUS_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+    use std::ptr;
+
+    use crate::*;
+
+    const SECRET: &[u8] = b"secret";
+
+    #[test]
+    fn test_encode_and_decode() {
+        unsafe {
+            let request = radius_packet_new(1, SECRET.as_ptr(), SECRET.len());
+            radius_packet_set_identifier(request, 42);
+            assert_eq!(
+                radius_packet_add_attribute(request, 1, b"admin".as_ptr(), 5),
+                RADIUS_OK
+            );
+            assert_eq!(
+                radius_packet_add_user_password(request, b"p@ssw0rd".as_ptr(), 8),
+                RADIUS_OK
+            );
+
+            let mut len = 0;
+            assert_eq!(
+                radius_packet_encode(request, ptr::null_mut(), 0, &mut len),
+                RADIUS_ERR_BUFFER_TOO_SMALL
+            );
+            let mut buf = vec![0u8; len];
+            assert_eq!(
+                radius_packet_encode(request, buf.as_mut_ptr(), buf.len(), &mut len),
+                RADIUS_OK
+            );
+
+            let mut decoded = ptr::null_mut();
+            assert_eq!(
+                radius_packet_decode(
+                    buf.as_ptr(),
+                    len,
+                    SECRET.as_ptr(),
+                    SECRET.len(),
+                    &mut decoded
+                ),
+                RADIUS_OK
+            );
+            assert_eq!(radius_packet_get_code(decoded), 1);
+            assert_eq!(radius_packet_get_identifier(decoded), 42);
+            assert_eq!(radius_packet_get_attribute_count(decoded), 2);
+
+            let (mut typ, mut value, mut value_len) = (0u8, ptr::null(), 0usize);
+            assert_eq!(
+                radius_packet_get_attribute(decoded, 0, &mut typ, &mut value, &mut value_len),
+                RADIUS_OK
+            );
+            assert_eq!(typ, 1);
+            assert_eq!(std::slice::from_raw_parts(value, value_len), b"admin");
+            assert_eq!(
+                radius_packet_get_attribute(decoded, 2, &mut typ, &mut value, &mut value_len),
+                RADIUS_ERR_NOT_FOUND
+            );
+
+            let mut password = [0u8; 128];
+            assert_eq!(
+                radius_packet_lookup_user_password(
+                    decoded,
+                    password.as_mut_ptr(),
+                    password.len(),
+                    &mut len
+                ),
+                RADIUS_OK
+            );
+            assert_eq!(&password[..len], b"p@ssw0rd");
+
+            radius_packet_delete_attribute(decoded, 1);
+            assert_eq!(
+                radius_packet_lookup_attribute(decoded, 1, &mut value, &mut value_len),
+                RADIUS_ERR_NOT_FOUND
+            );
+
+            radius_packet_free(decoded);
+            radius_packet_free(request);
+        }
+    }
+
+    #[test]
+    fn test_authenticity() {
+        unsafe {
+            let request = radius_packet_new(4, SECRET.as_ptr(), SECRET.len());
+            let mut request_buf = [0u8; 4096];
+            let mut request_len = 0;
+            radius_packet_encode(
+                request,
+                request_buf.as_mut_ptr(),
+                request_buf.len(),
+                &mut request_len,
+            );
+            assert_eq!(
+                radius_is_authentic_request(
+                    request_buf.as_ptr(),
+                    request_len,
+                    SECRET.as_ptr(),
+                    SECRET.len()
+                ),
+                1
+            );
+            assert_eq!(
+                radius_is_authentic_request(request_buf.as_ptr(), request_len, b"bad".as_ptr(), 3),
+                0
+            );
+
+            let mut decoded_request = ptr::null_mut();
+            radius_packet_decode(
+                request_buf.as_ptr(),
+                request_len,
+                SECRET.as_ptr(),
+                SECRET.len(),
+                &mut decoded_request,
+            );
+            let response = radius_packet_make_response(decoded_request, 5);
+            let mut response_buf = [0u8; 4096];
+            let mut response_len = 0;
+            radius_packet_encode(
+                response,
+                response_buf.as_mut_ptr(),
+                response_buf.len(),
+                &mut response_len,
+            );
+            assert_eq!(
+                radius_is_authentic_response(
+                    response_buf.as_ptr(),
+                    response_len,
+                    request_buf.as_ptr(),
+                    request_len,
+                    SECRET.as_ptr(),
+                    SECRET.len()
+                ),
+                1
+            );
+
+            radius_packet_free(response);
+            radius_packet_free(decoded_request);
+            radius_packet_free(request);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        unsafe {
+            let mut packet = ptr::null_mut();
+            assert_eq!(
+                radius_packet_decode(
+                    [1u8, 2].as_ptr(),
+                    2,
+                    SECRET.as_ptr(),
+                    SECRET.len(),
+                    &mut packet
+                ),
+                RADIUS_ERR_DECODING
+            );
+            assert!(packet.is_null());
+            assert_eq!(
+                radius_packet_decode(ptr::null(), 20, SECRET.as_ptr(), SECRET.len(), &mut packet),
+                RADIUS_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                CStr::from_ptr(radius_strerror(RADIUS_ERR_BUFFER_TOO_SMALL))
+                    .to_str()
+                    .unwrap(),
+                "buffer too small"
+            );
+            radius_packet_free(ptr::null_mut());
+        }
+    }
+}
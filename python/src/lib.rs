@@ -0,0 +1,453 @@
+//! Python bindings of radius-rs.
+//!
+//! This builds the `radius` extension module for CPython by [PyO3](https://pyo3.rs);
+//! it exposes the packet codec, the lookups of the built-in dictionary and a blocking client.
+//! Build and install that by `maturin develop --release` in this directory (see `pyproject.toml`).
+//!
+//! ```python
+//! import radius
+//!
+//! request = radius.Packet(radius.ACCESS_REQUEST, b"secret")
+//! request.add_attribute("User-Name", "admin")
+//! request.add_user_password(b"12345")
+//!
+//! client = radius.Client(timeout=3.0, retries=1)
+//! response = client.send("127.0.0.1:1812", request)
+//! print(radius.code_name(response.code))
+//! ```
+
+use std::net::{Ipv4Addr, Ipv6Addr, ToSocketAddrs};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyTimeoutError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use radius::client::blocking::Client;
+use radius::client::ClientError;
+use radius::core::avp::{AVPType, AVP};
+use radius::core::code::Code;
+use radius::core::dictionary;
+use radius::core::packet::Packet;
+use radius::core::rfc2865;
+use radius::error::ErrorKind;
+
+create_exception!(
+    radius,
+    RadiusError,
+    PyException,
+    "An error that is raised by the RADIUS codec or the client."
+);
+
+/// Resolve an attribute that is specified by the name (e.g. `"User-Name"`) or the type (e.g. `1`).
+fn resolve_attribute(attribute: &Bound<'_, PyAny>) -> PyResult<(AVPType, Option<&'static str>)> {
+    if let Ok(typ) = attribute.extract::<AVPType>() {
+        let data_type = dictionary::lookup_attribute_by_type(typ).map(|a| a.get_data_type());
+        return Ok((typ, data_type));
+    }
+    let name = attribute.extract::<String>()?;
+    match dictionary::lookup_attribute_by_name(&name) {
+        Some(definition) => Ok((definition.get_type(), Some(definition.get_data_type()))),
+        None => Err(PyValueError::new_err(format!("unknown attribute: {name}"))),
+    }
+}
+
+fn attribute_name(typ: AVPType) -> String {
+    dictionary::lookup_attribute_by_type(typ)
+        .map(|a| a.get_name().to_owned())
+        .unwrap_or_else(|| format!("Attr-{typ}"))
+}
+
+/// Make an AVP from a Python value according to the data type of the dictionary.
+fn to_avp(typ: AVPType, data_type: Option<&str>, value: &Bound<'_, PyAny>) -> PyResult<AVP> {
+    if let Ok(bytes) = value.cast::<PyBytes>() {
+        return Ok(AVP::from_bytes(typ, bytes.as_bytes()));
+    }
+    let avp = match data_type {
+        Some("integer") => match value.extract::<u32>() {
+            Ok(v) => AVP::from_u32(typ, v),
+            Err(_) => {
+                let name = value.extract::<String>()?;
+                let definition = dictionary::lookup_value_by_name(&attribute_name(typ), &name)
+                    .ok_or_else(|| PyValueError::new_err(format!("unknown value: {name}")))?;
+                AVP::from_u32(typ, definition.get_value())
+            }
+        },
+        Some("short") => AVP::from_u16(typ, value.extract()?),
+        Some("date") => {
+            let timestamp = value.extract::<i64>()?;
+            let dt = DateTime::<Utc>::from_timestamp(timestamp, 0)
+                .ok_or_else(|| PyValueError::new_err("invalid timestamp"))?;
+            AVP::from_date(typ, &dt)
+        }
+        Some("ipaddr") => {
+            let addr = value.str()?.to_str()?.parse::<Ipv4Addr>();
+            AVP::from_ipv4(
+                typ,
+                &addr.map_err(|e| PyValueError::new_err(e.to_string()))?,
+            )
+        }
+        Some("ipv6addr") => {
+            let addr = value.str()?.to_str()?.parse::<Ipv6Addr>();
+            AVP::from_ipv6(
+                typ,
+                &addr.map_err(|e| PyValueError::new_err(e.to_string()))?,
+            )
+        }
+        _ => AVP::from_string(typ, &value.extract::<String>()?),
+    };
+    Ok(avp)
+}
+
+/// Convert an AVP to a Python value according to the data type of the dictionary;
+/// the value is returned as `bytes` if that cannot be interpreted as the data type.
+fn from_avp<'py>(
+    py: Python<'py>,
+    data_type: Option<&str>,
+    avp: &AVP,
+) -> PyResult<Bound<'py, PyAny>> {
+    let value = match data_type {
+        Some("string") => avp
+            .encode_string()
+            .ok()
+            .map(|v| v.into_pyobject(py))
+            .transpose()?
+            .map(Bound::into_any),
+        Some("integer") => avp
+            .encode_u32()
+            .ok()
+            .map(|v| v.into_pyobject(py))
+            .transpose()?
+            .map(Bound::into_any),
+        Some("short") => avp
+            .encode_u16()
+            .ok()
+            .map(|v| v.into_pyobject(py))
+            .transpose()?
+            .map(Bound::into_any),
+        Some("date") => avp
+            .encode_date()
+            .ok()
+            .map(|v| v.timestamp().into_pyobject(py))
+            .transpose()?
+            .map(Bound::into_any),
+        Some("ipaddr") => avp
+            .encode_ipv4()
+            .ok()
+            .map(|v| v.to_string().into_pyobject(py))
+            .transpose()?
+            .map(Bound::into_any),
+        Some("ipv6addr") => avp
+            .encode_ipv6()
+            .ok()
+            .map(|v| v.to_string().into_pyobject(py))
+            .transpose()?
+            .map(Bound::into_any),
+        _ => None,
+    };
+    Ok(value.unwrap_or_else(|| PyBytes::new(py, avp.get_value()).into_any()))
+}
+
+fn client_error(e: ClientError) -> PyErr {
+    match e.get_kind() {
+        ErrorKind::Timeout => PyTimeoutError::new_err(e.to_string()),
+        _ => RadiusError::new_err(e.to_string()),
+    }
+}
+
+/// A RADIUS packet.
+#[pyclass(name = "Packet", module = "radius")]
+struct PyPacket {
+    packet: Packet,
+}
+
+#[pymethods]
+impl PyPacket {
+    /// Make a new packet; the identifier is random if that is not given.
+    #[new]
+    #[pyo3(signature = (code, secret, identifier=None))]
+    fn new(code: u8, secret: &[u8], identifier: Option<u8>) -> Self {
+        let code = Code::from(code);
+        let packet = match identifier {
+            Some(identifier) => Packet::new_with_identifier(code, secret, identifier),
+            None => Packet::new(code, secret),
+        };
+        PyPacket { packet }
+    }
+
+    /// Decode a datagram into a packet.
+    #[staticmethod]
+    fn decode(data: &[u8], secret: &[u8]) -> PyResult<Self> {
+        Packet::decode(data, secret)
+            .map(|packet| PyPacket { packet })
+            .map_err(|e| RadiusError::new_err(e.to_string()))
+    }
+
+    /// Encode the packet into a datagram.
+    fn encode<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        self.packet
+            .encode()
+            .map(|data| PyBytes::new(py, &data))
+            .map_err(|e| RadiusError::new_err(e.to_string()))
+    }
+
+    /// Make a response packet for this request.
+    fn make_response(&self, code: u8) -> Self {
+        PyPacket {
+            packet: self.packet.make_response_packet(Code::from(code)),
+        }
+    }
+
+    #[getter]
+    fn code(&self) -> u8 {
+        self.packet.get_code() as u8
+    }
+
+    #[getter]
+    fn identifier(&self) -> u8 {
+        self.packet.get_identifier()
+    }
+
+    #[setter]
+    fn set_identifier(&mut self, identifier: u8) {
+        self.packet.set_identifier(identifier);
+    }
+
+    #[getter]
+    fn authenticator<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, self.packet.get_authenticator())
+    }
+
+    #[getter]
+    fn secret<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, self.packet.get_secret())
+    }
+
+    /// Add an attribute that is specified by the name or the type.
+    ///
+    /// `bytes` is added as it is; the other values are converted by the data type of the dictionary
+    /// (e.g. `int` or the name of the value for `integer`, `str` for `ipaddr`).
+    /// User-Password is encrypted by the secret.
+    fn add_attribute(
+        &mut self,
+        attribute: &Bound<'_, PyAny>,
+        value: &Bound<'_, PyAny>,
+    ) -> PyResult<()> {
+        let (typ, data_type) = resolve_attribute(attribute)?;
+        if typ == rfc2865::USER_PASSWORD_TYPE {
+            return self.add_user_password(
+                &value
+                    .extract::<Vec<u8>>()
+                    .or_else(|_| value.extract::<String>().map(String::into_bytes))?,
+            );
+        }
+        let avp = to_avp(typ, data_type, value)?;
+        self.packet.add(avp);
+        Ok(())
+    }
+
+    /// Delete the all attributes that are specified by the name or the type.
+    fn delete_attribute(&mut self, attribute: &Bound<'_, PyAny>) -> PyResult<()> {
+        let (typ, _) = resolve_attribute(attribute)?;
+        self.packet.delete(typ);
+        Ok(())
+    }
+
+    /// Returns the first value of the attribute, or `None` if the packet doesn't have that.
+    fn get_attribute<'py>(
+        &self,
+        py: Python<'py>,
+        attribute: &Bound<'py, PyAny>,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let (typ, data_type) = resolve_attribute(attribute)?;
+        if typ == rfc2865::USER_PASSWORD_TYPE {
+            return Ok(self.get_user_password(py)?.map(Bound::into_any));
+        }
+        self.packet
+            .lookup(typ)
+            .map(|avp| from_avp(py, data_type, avp))
+            .transpose()
+    }
+
+    /// Returns the all values of the attribute.
+    fn get_all_attributes<'py>(
+        &self,
+        py: Python<'py>,
+        attribute: &Bound<'py, PyAny>,
+    ) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        let (typ, data_type) = resolve_attribute(attribute)?;
+        self.packet
+            .lookup_all(typ)
+            .into_iter()
+            .map(|avp| from_avp(py, data_type, avp))
+            .collect()
+    }
+
+    /// Returns the all attributes as the list of `(type, raw value)` in the order of the packet.
+    fn get_attributes<'py>(&self, py: Python<'py>) -> Vec<(AVPType, Bound<'py, PyBytes>)> {
+        self.packet
+            .get_attributes()
+            .iter()
+            .map(|avp| (avp.get_type(), PyBytes::new(py, avp.get_value())))
+            .collect()
+    }
+
+    /// Add User-Password that is encrypted by the secret.
+    fn add_user_password(&mut self, password: &[u8]) -> PyResult<()> {
+        rfc2865::add_user_password(&mut self.packet, password)
+            .map_err(|e| RadiusError::new_err(e.to_string()))
+    }
+
+    /// Returns the decrypted User-Password, or `None` if the packet doesn't have that.
+    fn get_user_password<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyBytes>>> {
+        match rfc2865::lookup_user_password(&self.packet) {
+            Some(Ok(password)) => Ok(Some(PyBytes::new(py, &password))),
+            Some(Err(e)) => Err(RadiusError::new_err(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Packet(code={}, identifier={}, attributes={})",
+            self.packet.get_code().string(),
+            self.packet.get_identifier(),
+            self.packet.get_attributes().len()
+        )
+    }
+}
+
+/// A blocking RADIUS client.
+#[pyclass(name = "Client", module = "radius")]
+struct PyClient {
+    client: Client,
+}
+
+#[pymethods]
+impl PyClient {
+    /// Make a client; `timeout` is the seconds to wait for each response (`None` waits forever),
+    /// `retries` is the maximum number of the retransmissions on timeout, and `deadline` is the seconds to wait
+    /// for the response including all the retransmissions (`None` is no deadline).
+    #[new]
+    #[pyo3(signature = (timeout=Some(3.0), retries=0, add_event_timestamp=true, deadline=None))]
+    fn new(
+        timeout: Option<f64>,
+        retries: u32,
+        add_event_timestamp: bool,
+        deadline: Option<f64>,
+    ) -> PyResult<Self> {
+        let to_duration = |secs: Option<f64>| {
+            secs.map(Duration::try_from_secs_f64)
+                .transpose()
+                .map_err(|e| PyValueError::new_err(e.to_string()))
+        };
+        let deadline = to_duration(deadline)?;
+        if deadline.is_some_and(|deadline| deadline.is_zero()) {
+            return Err(PyValueError::new_err("deadline must be positive"));
+        }
+        let mut client = Client::new(to_duration(timeout)?);
+        client.set_max_retransmissions(retries);
+        client.set_total_timeout(deadline);
+        client.set_add_event_timestamp(add_event_timestamp);
+        Ok(PyClient { client })
+    }
+
+    /// Send a request to the address (e.g. `"127.0.0.1:1812"`) and return the response.
+    ///
+    /// This raises `TimeoutError` if the response doesn't arrive in time.
+    fn send(&self, py: Python<'_>, address: &str, packet: &PyPacket) -> PyResult<PyPacket> {
+        let remote_addr = address
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| PyValueError::new_err(format!("invalid address: {address}")))?;
+        let request = packet.packet.clone();
+        py.detach(|| self.client.send_packet(&remote_addr, &request))
+            .map(|packet| PyPacket { packet })
+            .map_err(client_error)
+    }
+}
+
+/// Returns `(name, type, data type)` of the attribute that is specified by the name or the type,
+/// or `None` if the built-in dictionary doesn't have that.
+#[pyfunction]
+fn lookup_attribute(attribute: &Bound<'_, PyAny>) -> PyResult<Option<(String, AVPType, String)>> {
+    let definition = match attribute.extract::<AVPType>() {
+        Ok(typ) => dictionary::lookup_attribute_by_type(typ),
+        Err(_) => dictionary::lookup_attribute_by_name(&attribute.extract::<String>()?),
+    };
+    Ok(definition.map(|d| {
+        (
+            d.get_name().to_owned(),
+            d.get_type(),
+            d.get_data_type().to_owned(),
+        )
+    }))
+}
+
+/// Returns the number of the named value (e.g. `("Service-Type", "Framed-User")`),
+/// or `None` if the built-in dictionary doesn't have that.
+#[pyfunction]
+fn lookup_value(attribute_name: &str, value_name: &str) -> Option<u32> {
+    dictionary::lookup_value_by_name(attribute_name, value_name).map(|v| v.get_value())
+}
+
+/// Returns the name of the value (e.g. `("Service-Type", 2)`),
+/// or `None` if the built-in dictionary doesn't have that.
+#[pyfunction]
+fn lookup_value_name(attribute_name: &str, value: u32) -> Option<&'static str> {
+    dictionary::lookup_value(attribute_name, value).map(|v| v.get_name())
+}
+
+/// Returns the name of the packet code (e.g. `"Access-Accept"` for `2`).
+#[pyfunction]
+fn code_name(code: u8) -> &'static str {
+    Code::from(code).string()
+}
+
+#[pyfunction]
+fn is_authentic_request(request: &[u8], secret: &[u8]) -> bool {
+    Packet::is_authentic_request(request, secret)
+}
+
+#[pyfunction]
+fn is_authentic_response(response: &[u8], request: &[u8], secret: &[u8]) -> bool {
+    Packet::is_authentic_response(response, request, secret)
+}
+
+#[pymodule]
+#[pyo3(name = "radius")]
+fn radius_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPacket>()?;
+    m.add_class::<PyClient>()?;
+    m.add("RadiusError", m.py().get_type::<RadiusError>())?;
+    m.add_function(wrap_pyfunction!(lookup_attribute, m)?)?;
+    m.add_function(wrap_pyfunction!(lookup_value, m)?)?;
+    m.add_function(wrap_pyfunction!(lookup_value_name, m)?)?;
+    m.add_function(wrap_pyfunction!(code_name, m)?)?;
+    m.add_function(wrap_pyfunction!(is_authentic_request, m)?)?;
+    m.add_function(wrap_pyfunction!(is_authentic_response, m)?)?;
+
+    for code in [
+        Code::AccessRequest,
+        Code::AccessAccept,
+        Code::AccessReject,
+        Code::AccountingRequest,
+        Code::AccountingResponse,
+        Code::AccessChallenge,
+        Code::StatusServer,
+        Code::StatusClient,
+        Code::DisconnectRequest,
+        Code::DisconnectACK,
+        Code::DisconnectNAK,
+        Code::CoARequest,
+        Code::CoAACK,
+        Code::CoANAK,
+    ] {
+        let name = code.string().to_uppercase().replace('-', "_");
+        m.add(name.as_str(), code as u8)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,100 @@
+import socket
+import threading
+import time
+
+import pytest
+
+import radius
+
+
+def test_packet_roundtrip():
+    request = radius.Packet(radius.ACCESS_REQUEST, b"secret", identifier=7)
+    request.add_attribute("User-Name", "admin")
+    request.add_attribute("Service-Type", "Framed-User")
+    request.add_attribute(4, "192.0.2.1")
+    request.add_user_password(b"12345")
+
+    datagram = request.encode()
+    assert radius.is_authentic_request(datagram, b"secret")
+
+    decoded = radius.Packet.decode(datagram, b"secret")
+    assert decoded.code == radius.ACCESS_REQUEST
+    assert decoded.identifier == 7
+    assert decoded.get_attribute("user-name") == "admin"
+    assert decoded.get_attribute("Service-Type") == 2
+    assert decoded.get_attribute("NAS-IP-Address") == "192.0.2.1"
+    assert decoded.get_user_password() == b"12345"
+    assert decoded.get_attribute("Reply-Message") is None
+    assert [typ for typ, _ in decoded.get_attributes()] == [1, 6, 4, 2]
+
+    decoded.delete_attribute("User-Name")
+    assert decoded.get_all_attributes("User-Name") == []
+
+    with pytest.raises(ValueError):
+        request.add_attribute("No-Such-Attribute", "value")
+    with pytest.raises(radius.RadiusError):
+        radius.Packet.decode(b"\x01", b"secret")
+
+
+def test_dictionary():
+    assert radius.lookup_attribute("User-Name") == ("User-Name", 1, "string")
+    assert radius.lookup_attribute(55) == ("Event-Timestamp", 55, "date")
+    assert radius.lookup_attribute("No-Such-Attribute") is None
+    assert radius.lookup_value("Service-Type", "Framed-User") == 2
+    assert radius.lookup_value_name("Acct-Status-Type", 2) == "Stop"
+    assert radius.code_name(radius.ACCESS_ACCEPT) == "Access-Accept"
+
+
+def test_client():
+    server = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    server.bind(("127.0.0.1", 0))
+
+    def serve():
+        data, addr = server.recvfrom(4096)
+        request = radius.Packet.decode(data, b"secret")
+        response = request.make_response(radius.ACCESS_ACCEPT)
+        response.add_attribute("Reply-Message", "welcome")
+        server.sendto(response.encode(), addr)
+
+    thread = threading.Thread(target=serve)
+    thread.start()
+
+    request = radius.Packet(radius.ACCESS_REQUEST, b"secret")
+    request.add_attribute("User-Name", "admin")
+    client = radius.Client(timeout=3.0)
+    response = client.send("127.0.0.1:%d" % server.getsockname()[1], request)
+    thread.join()
+    server.close()
+
+    assert response.code == radius.ACCESS_ACCEPT
+    assert response.get_attribute("Reply-Message") == "welcome"
+
+
+def test_client_timeout():
+    server = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    server.bind(("127.0.0.1", 0))
+
+    client = radius.Client(timeout=0.1, retries=1)
+    with pytest.raises(TimeoutError):
+        client.send("127.0.0.1:%d" % server.getsockname()[1], radius.Packet(radius.ACCESS_REQUEST, b"secret"))
+
+    # the request has been retransmitted once
+    server.settimeout(1.0)
+    assert server.recv(4096) == server.recv(4096)
+    server.close()
+
+
+def test_client_deadline():
+    server = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    server.bind(("127.0.0.1", 0))
+
+    client = radius.Client(timeout=0.2, retries=10, deadline=0.3)
+    started_at = time.monotonic()
+    with pytest.raises(TimeoutError):
+        client.send("127.0.0.1:%d" % server.getsockname()[1], radius.Packet(radius.ACCESS_REQUEST, b"secret"))
+    # the deadline cuts the retransmissions short
+    assert time.monotonic() - started_at < 1.0
+    server.close()
+
+    with pytest.raises(ValueError):
+        radius.Client(deadline=0.0)
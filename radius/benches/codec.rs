@@ -0,0 +1,146 @@
+//! Benchmarks of the packet codec.
+//!
+//! Run with `cargo bench -p radius --bench codec`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use radius::core::avp::AVP;
+use radius::core::code::Code;
+use radius::core::packet::Packet;
+use radius::core::{rfc2865, rfc2869};
+
+const SECRET: &[u8] = b"secret";
+const ATTRIBUTE_COUNTS: &[usize] = &[0, 8, 32, 128];
+const EAP_MESSAGE_LENGTHS: &[usize] = &[64, 1024, 3000];
+
+fn access_request(attribute_count: usize) -> Packet {
+    let mut packet = Packet::new(Code::AccessRequest, SECRET);
+    rfc2865::add_user_name(&mut packet, "admin");
+    rfc2865::add_user_password(&mut packet, b"p@ssw0rd").unwrap();
+    for i in 0..attribute_count {
+        rfc2865::add_reply_message(&mut packet, &format!("reply message number {i}"));
+    }
+    packet
+}
+
+fn vendor_specific_request(attribute_count: usize) -> Packet {
+    let mut packet = Packet::new(Code::AccessRequest, SECRET);
+    for i in 0..attribute_count {
+        // Vendor-Id (Cisco: 9), Vendor-Type (Cisco-AVPair: 1), Vendor-Length, and the value
+        let value = format!("shell:priv-lvl={i}");
+        let mut vsa = 9u32.to_be_bytes().to_vec();
+        vsa.extend([1, value.len() as u8 + 2]);
+        vsa.extend(value.as_bytes());
+        packet.add(AVP::from_bytes(rfc2865::VENDOR_SPECIFIC_TYPE, &vsa));
+    }
+    packet
+}
+
+fn eap_request(eap_message_length: usize) -> Packet {
+    let mut packet = Packet::new(Code::AccessRequest, SECRET);
+    rfc2865::add_user_name(&mut packet, "admin");
+    rfc2869::add_eap_message(&mut packet, &vec![0x5a; eap_message_length]);
+    packet
+}
+
+fn bench_codec(c: &mut Criterion, name: &str, packets: Vec<(usize, Packet)>) {
+    let mut group = c.benchmark_group(name);
+    for (parameter, packet) in packets {
+        let datagram = packet.encode().unwrap();
+        group.throughput(Throughput::Bytes(datagram.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("encode", parameter),
+            &packet,
+            |b, packet| b.iter(|| black_box(packet).encode().unwrap()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("decode", parameter),
+            &datagram,
+            |b, datagram| b.iter(|| Packet::decode(black_box(datagram), SECRET).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+fn attributes(c: &mut Criterion) {
+    let packets = ATTRIBUTE_COUNTS
+        .iter()
+        .map(|&count| (count, access_request(count)))
+        .collect();
+    bench_codec(c, "attributes", packets);
+}
+
+fn vendor_specific_attributes(c: &mut Criterion) {
+    let packets = ATTRIBUTE_COUNTS
+        .iter()
+        .map(|&count| (count, vendor_specific_request(count)))
+        .collect();
+    bench_codec(c, "vendor_specific_attributes", packets);
+}
+
+fn eap_messages(c: &mut Criterion) {
+    let packets: Vec<_> = EAP_MESSAGE_LENGTHS
+        .iter()
+        .map(|&length| (length, eap_request(length)))
+        .collect();
+    bench_codec(c, "eap_messages", packets.clone());
+
+    let mut group = c.benchmark_group("eap_messages");
+    for (length, packet) in packets {
+        group.throughput(Throughput::Bytes(length as u64));
+        group.bench_with_input(
+            BenchmarkId::new("fragment", length),
+            &length,
+            |b, &length| {
+                let value = vec![0x5a; length];
+                b.iter(|| {
+                    let mut packet = Packet::new(Code::AccessRequest, SECRET);
+                    rfc2869::add_eap_message(&mut packet, black_box(&value));
+                    packet
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("reassemble", length),
+            &packet,
+            |b, packet| b.iter(|| rfc2869::lookup_eap_message(black_box(packet)).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+fn authenticity(c: &mut Criterion) {
+    // the Request Authenticator of Access-Request is random and has nothing to verify, so it uses Accounting-Request
+    let mut request_packet = Packet::new(Code::AccountingRequest, SECRET);
+    rfc2865::add_user_name(&mut request_packet, "admin");
+    let request = request_packet.encode().unwrap();
+    let response = request_packet
+        .make_response_packet(Code::AccountingResponse)
+        .encode()
+        .unwrap();
+    let mut group = c.benchmark_group("authenticity");
+    group.bench_function("request", |b| {
+        b.iter(|| Packet::is_authentic_request(black_box(&request), SECRET))
+    });
+    group.bench_function("response", |b| {
+        b.iter(|| Packet::is_authentic_response(black_box(&response), &request, SECRET))
+    });
+    group.bench_function("user_password", |b| {
+        let packet = access_request(0);
+        b.iter(|| {
+            rfc2865::lookup_user_password(black_box(&packet))
+                .unwrap()
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    attributes,
+    vendor_specific_attributes,
+    eap_messages,
+    authenticity
+);
+criterion_main!(benches);
@@ -0,0 +1,146 @@
+//! Benchmarks of the client↔server round trips over the loopback interface.
+//!
+//! Run with `cargo bench -p radius --bench round_trip`.
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tokio::net::UdpSocket;
+use tokio::runtime::Runtime;
+use tokio::sync::oneshot;
+use tokio::task::JoinSet;
+
+use radius::client::multiplexed::MultiplexedClient;
+use radius::client::Client;
+use radius::core::code::Code;
+use radius::core::packet::Packet;
+use radius::core::request::Request;
+use radius::core::{rfc2865, rfc2866};
+use radius::server::{RequestHandler, SecretProvider, SecretProviderError, Server};
+
+const SECRET: &[u8] = b"secret";
+const CONCURRENCIES: &[usize] = &[1, 16, 64];
+
+struct AcceptingHandler {}
+
+#[async_trait]
+impl RequestHandler<(), io::Error> for AcceptingHandler {
+    async fn handle_radius_request(
+        &self,
+        conn: &UdpSocket,
+        req: &Request,
+    ) -> Result<(), io::Error> {
+        let req_packet = req.get_packet();
+        let code = match req_packet.get_code() {
+            Code::AccountingRequest => Code::AccountingResponse,
+            _ => Code::AccessAccept,
+        };
+        conn.send_to(
+            &req_packet.make_response_packet(code).encode().unwrap(),
+            req.get_remote_addr(),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+struct StaticSecretProvider {}
+
+impl SecretProvider for StaticSecretProvider {
+    fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        Ok(SECRET.to_vec())
+    }
+}
+
+fn access_request() -> Packet {
+    let mut packet = Packet::new(Code::AccessRequest, SECRET);
+    rfc2865::add_user_name(&mut packet, "admin");
+    rfc2865::add_user_password(&mut packet, b"p@ssw0rd").unwrap();
+    packet
+}
+
+fn accounting_request() -> Packet {
+    let mut packet = Packet::new(Code::AccountingRequest, SECRET);
+    rfc2865::add_user_name(&mut packet, "admin");
+    rfc2866::add_acct_status_type(&mut packet, rfc2866::ACCT_STATUS_TYPE_START);
+    rfc2866::add_acct_session_id(&mut packet, "0123456789abcdef");
+    packet
+}
+
+fn round_trips(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let addr = runtime.block_on(async {
+        let mut server =
+            Server::listen("127.0.0.1", 0, AcceptingHandler {}, StaticSecretProvider {})
+                .await
+                .unwrap();
+        let addr = server.get_listen_address().unwrap();
+        tokio::spawn(async move { server.run(shutdown_rx).await });
+        addr
+    });
+
+    let client = Arc::new(Client::new(
+        Some(Duration::from_secs(1)),
+        Some(Duration::from_secs(1)),
+    ));
+    let mut group = c.benchmark_group("round_trip");
+    for (name, packet) in [
+        ("access_request", access_request()),
+        ("accounting_request", accounting_request()),
+    ] {
+        for &concurrency in CONCURRENCIES {
+            group.throughput(Throughput::Elements(concurrency as u64));
+            group.bench_with_input(BenchmarkId::new(name, concurrency), &packet, |b, packet| {
+                b.to_async(&runtime).iter(|| async {
+                    let mut requests = JoinSet::new();
+                    for _ in 0..concurrency {
+                        let client = client.clone();
+                        let packet = packet.clone();
+                        requests.spawn(async move { client.send_packet(&addr, &packet).await });
+                    }
+                    while let Some(response) = requests.join_next().await {
+                        response.unwrap().unwrap();
+                    }
+                })
+            });
+        }
+    }
+    group.finish();
+
+    // the concurrent requests share the sockets of the multiplexed client
+    let client = runtime
+        .block_on(async { Arc::new(MultiplexedClient::new(addr, Some(Duration::from_secs(1)))) });
+    let mut group = c.benchmark_group("multiplexed_round_trip");
+    for (name, packet) in [
+        ("access_request", access_request()),
+        ("accounting_request", accounting_request()),
+    ] {
+        for &concurrency in CONCURRENCIES {
+            group.throughput(Throughput::Elements(concurrency as u64));
+            group.bench_with_input(BenchmarkId::new(name, concurrency), &packet, |b, packet| {
+                b.to_async(&runtime).iter(|| async {
+                    let mut requests = JoinSet::new();
+                    for _ in 0..concurrency {
+                        let client = client.clone();
+                        let packet = packet.clone();
+                        requests.spawn(async move { client.send_packet(&packet).await });
+                    }
+                    while let Some(response) = requests.join_next().await {
+                        response.unwrap().unwrap();
+                    }
+                })
+            });
+        }
+    }
+    group.finish();
+
+    shutdown_tx.send(()).unwrap();
+}
+
+criterion_group!(benches, round_trips);
+criterion_main!(benches);
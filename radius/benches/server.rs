@@ -0,0 +1,105 @@
+//! Benchmarks of the throughput of a server over the loopback interface.
+//!
+//! A raw socket sends the pre-encoded requests in the windows of `WINDOW`, so this measures the server rather than a
+//! client; compare the default server with the fast path (`Server::set_fast_path()` and `Concurrency::Inline`).
+//!
+//! Run with `cargo bench -p radius --bench server`.
+
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use tokio::net::UdpSocket;
+use tokio::runtime::Runtime;
+
+use radius::core::code::Code;
+use radius::core::packet::Packet;
+use radius::core::request::Request;
+use radius::core::rfc2865;
+use radius::server::{Concurrency, RequestHandler, SecretProvider, SecretProviderError, Server};
+
+const SECRET: &[u8] = b"secret";
+/// The number of the requests in flight; small enough not to overflow the receive buffer of the sockets.
+const WINDOW: usize = 32;
+
+struct AcceptingHandler {}
+
+#[async_trait]
+impl RequestHandler<(), io::Error> for AcceptingHandler {
+    async fn handle_radius_request(
+        &self,
+        conn: &UdpSocket,
+        req: &Request,
+    ) -> Result<(), io::Error> {
+        let response = req.get_packet().make_response_packet(Code::AccessAccept);
+        conn.send_to(&response.encode().unwrap(), req.get_remote_addr())
+            .await?;
+        Ok(())
+    }
+}
+
+struct StaticSecretProvider {}
+
+impl SecretProvider for StaticSecretProvider {
+    fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        Ok(SECRET.to_vec())
+    }
+}
+
+/// Returns the datagrams of the requests of the distinct identifiers, so the server doesn't take them as the
+/// retransmissions of each other.
+fn request_datagrams() -> Vec<Vec<u8>> {
+    (0..WINDOW)
+        .map(|i| {
+            let mut packet = Packet::new(Code::AccessRequest, SECRET);
+            packet.set_identifier(i as u8);
+            rfc2865::add_user_name(&mut packet, "admin");
+            rfc2865::add_user_password(&mut packet, b"p@ssw0rd").unwrap();
+            packet.encode().unwrap()
+        })
+        .collect()
+}
+
+fn throughput(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let datagrams = request_datagrams();
+
+    let mut group = c.benchmark_group("server");
+    group.throughput(Throughput::Elements(WINDOW as u64));
+    for (name, fast_path) in [("default", false), ("fast_path", true)] {
+        let (addr, conn) = runtime.block_on(async {
+            let mut builder = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+                .listener("127.0.0.1", 0);
+            if fast_path {
+                builder = builder.concurrency(Concurrency::Inline).fast_path(true);
+            }
+            let servers = builder.build().await.unwrap();
+            let addr = servers[0].get_listen_address().unwrap();
+            tokio::spawn(
+                async move { Server::run_all(&servers, std::future::pending::<()>()).await },
+            );
+            (addr, UdpSocket::bind("127.0.0.1:0").await.unwrap())
+        });
+
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter(|| async {
+                for datagram in &datagrams {
+                    conn.send_to(datagram, addr).await.unwrap();
+                }
+                let mut buf = [0; 4096];
+                for _ in 0..WINDOW {
+                    tokio::time::timeout(Duration::from_secs(1), conn.recv(&mut buf))
+                        .await
+                        .expect("a response has been lost")
+                        .unwrap();
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, throughput);
+criterion_main!(benches);
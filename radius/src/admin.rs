@@ -0,0 +1,556 @@
+//! Embedded admin API of the RADIUS server over HTTP/JSON (available with the `admin` feature).
+//!
+//! `AdminServer` exposes the following endpoints to operate the servers without restarting them:
+//!
+//! - `GET /stats`: the counters of the servers (see `ServerStats`) and whether they are draining
+//! - `GET /clients`: the names and the addresses of the clients (the secrets are never exposed)
+//! - `GET /sessions`: the number of the sessions in the session store
+//! - `POST /reload`: reload the clients and their secrets from the configuration file
+//! - `POST /drain`: drain the servers, i.e. they drop the new requests while they finish the ones in flight
+//! - `POST /resume`: stop draining the servers
+//! - `GET /metrics`: the Prometheus metrics of `set_metrics()` (with the `metrics` feature; see `radius::metrics`)
+//!
+//! The admin API is meant for the operators; listen on a loopback address, and set the bearer token by `set_token()`
+//! so that the requests have to have `Authorization: Bearer <token>`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use radius::admin::AdminServer;
+//! use radius::server::Server;
+//! # use radius::core::request::Request;
+//! # use radius::server::RequestHandler;
+//! # use tokio::net::UdpSocket;
+//! # struct MyRequestHandler {}
+//! # #[async_trait::async_trait]
+//! # impl RequestHandler<(), std::io::Error> for MyRequestHandler {
+//! #     async fn handle_radius_request(&self, _conn: &UdpSocket, _req: &Request) -> Result<(), std::io::Error> {
+//! #         Ok(())
+//! #     }
+//! # }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let servers = Server::from_config("radius.toml", MyRequestHandler {}).await.unwrap();
+//!
+//! let mut admin = AdminServer::listen("127.0.0.1", 8080).await.unwrap();
+//! admin.set_token(Some("admin-token".to_owned()));
+//! admin.set_config_path("radius.toml");
+//! admin.set_clients(servers[0].get_secret_provider());
+//! for server in &servers {
+//!     admin.add_server(server);
+//! }
+//!
+//! tokio::spawn(async move { admin.run(std::future::pending::<()>()).await });
+//! Server::run_all(&servers, tokio::signal::ctrl_c()).await.unwrap();
+//! # }
+//! ```
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tokio::time::timeout;
+
+use crate::config::{ClientsSecretProvider, ServerConfig};
+use crate::core::constant_time;
+use crate::export::write_json_string;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use crate::server::{RequestHandler, SecretProvider, Server, ServerControl};
+use crate::sessions::SessionStore;
+use crate::tasks;
+
+const MAX_REQUEST_LENGTH: usize = 8192;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// An HTTP/JSON server of the admin API.
+pub struct AdminServer {
+    listener: TcpListener,
+    max_connections: usize,
+    state: AdminState,
+}
+
+/// What the admin requests operate on; each connection has a copy of that.
+#[derive(Clone)]
+struct AdminState {
+    servers: Vec<(String, Arc<ServerControl>)>,
+    clients: Option<Arc<ClientsSecretProvider>>,
+    config_path: Option<PathBuf>,
+    session_store: Option<Arc<dyn SessionStore>>,
+    token: Option<String>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<Metrics>>,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Response {
+            status: "200 OK",
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn text(body: String) -> Self {
+        Response {
+            status: "200 OK",
+            content_type: "text/plain; version=0.0.4",
+            body,
+        }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        let mut body = String::from("{\"error\":");
+        write_json_string(&mut body, message);
+        body.push('}');
+        Response {
+            status,
+            content_type: "application/json",
+            body,
+        }
+    }
+}
+
+impl AdminServer {
+    /// The default maximum number of the connections that are handled at once.
+    pub const DEFAULT_MAX_CONNECTIONS: usize = 64;
+
+    /// Starts TCP listening for the admin API.
+    pub async fn listen(host: &str, port: u16) -> Result<Self, io::Error> {
+        let listener = TcpListener::bind(format!("{host}:{port}")).await?;
+        Ok(AdminServer {
+            listener,
+            max_connections: Self::DEFAULT_MAX_CONNECTIONS,
+            state: AdminState {
+                servers: Vec::new(),
+                clients: None,
+                config_path: None,
+                session_store: None,
+                token: None,
+                #[cfg(feature = "metrics")]
+                metrics: None,
+            },
+        })
+    }
+
+    /// Set the maximum number of the connections that are handled at once (default: `64`); the further connections
+    /// wait to be accepted until one of those has been closed.
+    pub fn set_max_connections(&mut self, max_connections: usize) {
+        self.max_connections = max_connections;
+    }
+
+    /// Add a server to report the stats and to drain.
+    pub fn add_server<X, E: Debug, T: RequestHandler<X, E>, U: SecretProvider>(
+        &mut self,
+        server: &Server<X, E, T, U>,
+    ) {
+        let address = server
+            .get_listen_address()
+            .map(|addr| addr.to_string())
+            .unwrap_or_default();
+        self.state.servers.push((address, server.get_control()));
+    }
+
+    /// Set the clients to list and to reload (e.g. `get_secret_provider()` of the servers that are made by `Server::from_config()`).
+    pub fn set_clients(&mut self, clients: Arc<ClientsSecretProvider>) {
+        self.state.clients = Some(clients);
+    }
+
+    /// Set the configuration file to reload the clients from.
+    pub fn set_config_path<P: Into<PathBuf>>(&mut self, path: P) {
+        self.state.config_path = Some(path.into());
+    }
+
+    /// Set the session store to count the sessions.
+    pub fn set_session_store(&mut self, session_store: Arc<dyn SessionStore>) {
+        self.state.session_store = Some(session_store);
+    }
+
+    /// Set the bearer token that the requests have to have (default: `None`, i.e. no authorization).
+    pub fn set_token(&mut self, token: Option<String>) {
+        self.state.token = token;
+    }
+
+    /// Set the Prometheus metrics to serve by `GET /metrics`.
+    ///
+    /// This is available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn set_metrics(&mut self, metrics: Arc<Metrics>) {
+        self.state.metrics = Some(metrics);
+    }
+
+    /// Returns the listening address.
+    pub fn get_listen_address(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Starts handling the admin requests; each connection is handled by a task of its own, up to
+    /// `set_max_connections()` at once.
+    ///
+    /// ## Parameters
+    ///
+    /// - `shutdown_trigger`: an implementation of the `Future` to interrupt to shutdown the admin server
+    pub async fn run(&self, shutdown_trigger: impl Future) -> Result<(), io::Error> {
+        tokio::select! {
+            res = self.run_loop() => {
+                res
+            }
+            _ = shutdown_trigger => {
+                info!("admin server is shutting down");
+                Ok(())
+            }
+        }
+    }
+
+    async fn run_loop(&self) -> Result<(), io::Error> {
+        let state = Arc::new(self.state.clone());
+        let semaphore = Arc::new(Semaphore::new(self.max_connections));
+        loop {
+            let permit = semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("the semaphore is never closed");
+            let (stream, remote_addr) = self.listener.accept().await?;
+            let state = state.clone();
+            tasks::spawn(&tasks::ADMIN_CONNECTION, async move {
+                let _permit = permit;
+                if let Err(e) = state.handle_connection(stream).await {
+                    debug!(
+                        "failed to handle an admin request from {}; {}",
+                        remote_addr, e
+                    );
+                }
+            });
+        }
+    }
+}
+
+impl AdminState {
+    async fn handle_connection(&self, mut stream: TcpStream) -> Result<(), io::Error> {
+        let response = match timeout(READ_TIMEOUT, read_request_head(&mut stream)).await {
+            Ok(Ok(head)) => self.handle_request(&head),
+            Ok(Err(e)) => return Err(e),
+            Err(_) => Response::error("408 Request Timeout", "request timeout"),
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.status,
+            response.content_type,
+            response.body.len(),
+            response.body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+
+    fn handle_request(&self, head: &str) -> Response {
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap_or_default().split(' ');
+        let (method, path) = match (request_line.next(), request_line.next()) {
+            (Some(method), Some(path)) => (method, path),
+            _ => return Response::error("400 Bad Request", "malformed request"),
+        };
+
+        if let Some(token) = &self.token {
+            let authorized = lines
+                .filter_map(|line| line.split_once(':'))
+                .any(|(name, value)| {
+                    name.trim().eq_ignore_ascii_case("authorization")
+                        && value.trim().strip_prefix("Bearer ").is_some_and(|bearer| {
+                            constant_time::eq(bearer.as_bytes(), token.as_bytes())
+                        })
+                });
+            if !authorized {
+                return Response::error("401 Unauthorized", "unauthorized");
+            }
+        }
+
+        match (method, path.split('?').next().unwrap_or_default()) {
+            ("GET", "/stats") => Response::ok(self.stats()),
+            ("GET", "/clients") => self.clients(),
+            ("GET", "/sessions") => self.sessions(),
+            #[cfg(feature = "metrics")]
+            ("GET", "/metrics") => match &self.metrics {
+                Some(metrics) => Response::text(metrics.render()),
+                None => Response::error("404 Not Found", "the metrics are not configured"),
+            },
+            ("POST", "/reload") => self.reload(),
+            ("POST", "/drain") => {
+                self.servers.iter().for_each(|(_, control)| control.drain());
+                Response::ok(self.stats())
+            }
+            ("POST", "/resume") => {
+                self.servers
+                    .iter()
+                    .for_each(|(_, control)| control.resume());
+                Response::ok(self.stats())
+            }
+            (
+                _,
+                "/stats" | "/clients" | "/sessions" | "/reload" | "/drain" | "/resume" | "/metrics",
+            ) => Response::error("405 Method Not Allowed", "method not allowed"),
+            _ => Response::error("404 Not Found", "not found"),
+        }
+    }
+
+    fn stats(&self) -> String {
+        let servers = self
+            .servers
+            .iter()
+            .map(|(address, control)| {
+                let stats = control.get_stats();
+                let mut json = String::from("{\"address\":");
+                write_json_string(&mut json, address);
+                json.push_str(&format!(
+                    ",\"received\":{},\"handled\":{},\"handler_errors\":{},\"dropped\":{},\"malformed\":{},\"duplicated\":{},\"in_flight\":{},\"draining\":{}}}",
+                    stats.get_received(),
+                    stats.get_handled(),
+                    stats.get_handler_errors(),
+                    stats.get_dropped(),
+                    stats.get_malformed(),
+                    stats.get_duplicated(),
+                    stats.get_in_flight(),
+                    control.is_draining()
+                ));
+                json
+            })
+            .collect::<Vec<_>>();
+        format!("{{\"servers\":[{}]}}", servers.join(","))
+    }
+
+    fn clients(&self) -> Response {
+        let clients = match &self.clients {
+            Some(clients) => clients.get_clients(),
+            None => return Response::error("404 Not Found", "the clients are not configured"),
+        };
+        let clients = clients
+            .iter()
+            .map(|client| {
+                let mut json = String::from("{\"name\":");
+                match client.get_name() {
+                    Some(name) => write_json_string(&mut json, name),
+                    None => json.push_str("null"),
+                }
+                json.push_str(",\"address\":");
+                write_json_string(&mut json, client.get_address());
+                json.push('}');
+                json
+            })
+            .collect::<Vec<_>>();
+        Response::ok(format!("{{\"clients\":[{}]}}", clients.join(",")))
+    }
+
+    fn sessions(&self) -> Response {
+        let session_store = match &self.session_store {
+            Some(session_store) => session_store,
+            None => return Response::error("404 Not Found", "the session store is not configured"),
+        };
+        match session_store.count() {
+            Ok(count) => Response::ok(format!("{{\"count\":{}}}", count)),
+            Err(e) => Response::error("500 Internal Server Error", &e.to_string()),
+        }
+    }
+
+    fn reload(&self) -> Response {
+        let (clients, config_path) = match (&self.clients, &self.config_path) {
+            (Some(clients), Some(config_path)) => (clients, config_path),
+            _ => {
+                return Response::error(
+                    "404 Not Found",
+                    "the clients and the configuration file are not configured",
+                )
+            }
+        };
+        let result =
+            ServerConfig::load(config_path).and_then(|config| clients.reload(config.get_clients()));
+        match result {
+            Ok(()) => {
+                info!("reloaded the clients from {}", config_path.display());
+                Response::ok(format!("{{\"clients\":{}}}", clients.get_clients().len()))
+            }
+            Err(e) => Response::error("500 Internal Server Error", &e.to_string()),
+        }
+    }
+}
+
+/// Read the request line and the headers; the body (if any) is ignored.
+async fn read_request_head(stream: &mut TcpStream) -> Result<String, io::Error> {
+    let mut buf = Vec::new();
+    let mut chunk = [0; 1024];
+    loop {
+        let size = stream.read(&mut chunk).await?;
+        if size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed",
+            ));
+        }
+        buf.extend_from_slice(&chunk[..size]);
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            return Ok(String::from_utf8_lossy(&buf[..end]).into_owned());
+        }
+        if buf.len() > MAX_REQUEST_LENGTH {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request is too large",
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpStream, UdpSocket};
+    use tokio::time::timeout;
+
+    use crate::admin::AdminServer;
+    use crate::config::ServerConfig;
+    use crate::core::request::Request;
+    use crate::server::{RequestHandler, Server};
+    use crate::sessions::InMemorySessionStore;
+
+    struct NopHandler {}
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for NopHandler {
+        async fn handle_radius_request(
+            &self,
+            _conn: &UdpSocket,
+            _req: &Request,
+        ) -> Result<(), io::Error> {
+            Ok(())
+        }
+    }
+
+    async fn request(admin: &AdminServer, request: &str) -> String {
+        let mut stream = TcpStream::connect(admin.get_listen_address().unwrap())
+            .await
+            .unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_admin_server() {
+        let dir = std::env::temp_dir().join(format!("radius-admin-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("radius.toml");
+        let config = "[[listeners]]\nhost = \"127.0.0.1\"\nport = 0\n\n[[clients]]\nname = \"office\"\naddress = \"192.0.2.0/24\"\nsecret = \"secret\"\n";
+        fs::write(&path, config).unwrap();
+
+        let servers = Server::listen_with_config(
+            &ServerConfig::from_toml_str(config).unwrap(),
+            NopHandler {},
+        )
+        .await
+        .unwrap();
+        let mut admin = AdminServer::listen("127.0.0.1", 0).await.unwrap();
+        admin.set_token(Some("token".to_owned()));
+        admin.set_config_path(&path);
+        admin.set_clients(servers[0].get_secret_provider());
+        admin.set_session_store(Arc::new(InMemorySessionStore::new()));
+        admin.add_server(&servers[0]);
+        #[cfg(feature = "metrics")]
+        admin.set_metrics(Arc::new(crate::metrics::Metrics::new("radius")));
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let admin = Arc::new(admin);
+        let admin_task = tokio::spawn({
+            let admin = admin.clone();
+            async move { admin.run(shutdown_rx).await }
+        });
+
+        let auth = "Authorization: Bearer token\r\n";
+        // a connection that sends nothing doesn't block the others
+        let _idle = TcpStream::connect(admin.get_listen_address().unwrap())
+            .await
+            .unwrap();
+        let response = timeout(
+            Duration::from_secs(1),
+            request(&admin, "GET /stats HTTP/1.1\r\n\r\n"),
+        )
+        .await
+        .unwrap();
+        assert!(response.starts_with("HTTP/1.1 401"), "{response}");
+        let response = request(
+            &admin,
+            "GET /stats HTTP/1.1\r\nAuthorization: Bearer tokem\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 401"), "{response}");
+
+        let response = request(&admin, &format!("GET /stats HTTP/1.1\r\n{auth}\r\n")).await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains("\"received\":0"), "{response}");
+        assert!(response.contains("\"draining\":false"), "{response}");
+
+        let response = request(&admin, &format!("GET /clients HTTP/1.1\r\n{auth}\r\n")).await;
+        assert!(response
+            .ends_with("{\"clients\":[{\"name\":\"office\",\"address\":\"192.0.2.0/24\"}]}"));
+        assert!(!response.contains("secret"));
+
+        let response = request(&admin, &format!("GET /sessions HTTP/1.1\r\n{auth}\r\n")).await;
+        assert!(response.ends_with("{\"count\":0}"), "{response}");
+
+        fs::write(
+            &path,
+            format!("{config}\n[[clients]]\naddress = \"198.51.100.1\"\nsecret = \"s\"\n"),
+        )
+        .unwrap();
+        let response = request(&admin, &format!("POST /reload HTTP/1.1\r\n{auth}\r\n")).await;
+        assert!(response.ends_with("{\"clients\":2}"), "{response}");
+
+        let response = request(&admin, &format!("POST /drain HTTP/1.1\r\n{auth}\r\n")).await;
+        assert!(response.contains("\"draining\":true"), "{response}");
+        assert!(servers[0].get_control().is_draining());
+
+        let response = request(&admin, &format!("GET /drain HTTP/1.1\r\n{auth}\r\n")).await;
+        assert!(response.starts_with("HTTP/1.1 405"), "{response}");
+        let response = request(&admin, &format!("GET /unknown HTTP/1.1\r\n{auth}\r\n")).await;
+        assert!(response.starts_with("HTTP/1.1 404"), "{response}");
+
+        #[cfg(feature = "metrics")]
+        {
+            let response = request(&admin, &format!("GET /metrics HTTP/1.1\r\n{auth}\r\n")).await;
+            assert!(
+                response.contains("Content-Type: text/plain; version=0.0.4\r\n"),
+                "{response}"
+            );
+            assert!(
+                response.contains("radius_server_in_flight 0\n"),
+                "{response}"
+            );
+        }
+
+        shutdown_tx.send(()).unwrap();
+        admin_task.await.unwrap().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
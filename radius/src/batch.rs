@@ -0,0 +1,546 @@
+//! Batched UDP I/O by `recvmmsg(2)`/`sendmmsg(2)` and UDP GSO/GRO (available on Linux with the `batch-io` feature).
+//!
+//! These receive and send a number of datagrams by one system call, so a busy server or proxy spends less time in the
+//! syscalls per datagram. `Server::set_batch_size()` makes the server receive the requests in batches.
+//!
+//! For the bulk traffic to one peer (e.g. load testing, or a busy proxy towards its upstream), `send_segmented()` and
+//! `recv_segmented()` use the UDP segmentation/receive offload of the kernel, so that a number of datagrams cost about
+//! one datagram on the way through the network stack. They fall back to the plain I/O where the kernel doesn't support that.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use radius::batch;
+//! use tokio::net::UdpSocket;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> std::io::Result<()> {
+//! let socket = UdpSocket::bind("127.0.0.1:1812").await?;
+//! let mut bufs = vec![vec![0; 1500]; 32];
+//! let mut slices: Vec<&mut [u8]> = bufs.iter_mut().map(|buf| buf.as_mut_slice()).collect();
+//! for (len, remote_addr) in batch::recv_batch(&socket, &mut slices).await? {
+//!     println!("{len} bytes from {remote_addr}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+use std::mem;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::ops::Range;
+use std::os::unix::io::AsRawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tokio::io::Interest;
+use tokio::net::UdpSocket;
+
+// the maximum number of the segments of a GSO send (UDP_MAX_SEGMENTS of the kernel), and the maximum UDP payload
+const MAX_GSO_SEGMENTS: usize = 64;
+const MAX_GSO_PAYLOAD: usize = 65507;
+
+static GSO_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Receive up to `bufs.len()` datagrams by one `recvmmsg(2)`; this waits until at least one datagram is available.
+///
+/// This returns the length and the source address of each received datagram, in the order of `bufs`.
+/// A datagram that is longer than its buffer is truncated, as `recv_from()` does.
+pub async fn recv_batch(
+    socket: &UdpSocket,
+    bufs: &mut [&mut [u8]],
+) -> Result<Vec<(usize, SocketAddr)>, io::Error> {
+    if bufs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // SAFETY: sockaddr_storage is a plain C struct, so the zeroed value is valid
+    let mut addrs: Vec<libc::sockaddr_storage> = vec![unsafe { mem::zeroed() }; bufs.len()];
+    let lens = loop {
+        socket.readable().await?;
+        // the headers are made in the closure, so the future doesn't hold the raw pointers across the awaits
+        let result = socket.try_io(Interest::READABLE, || {
+            let mut iovecs: Vec<libc::iovec> = bufs
+                .iter_mut()
+                .map(|buf| libc::iovec {
+                    iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+                    iov_len: buf.len(),
+                })
+                .collect();
+            let mut msgs = make_headers(&mut iovecs, &mut addrs, |_| {
+                mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t
+            });
+            // SAFETY: the headers point to the buffers and the addresses that outlive this call
+            let count = unsafe {
+                libc::recvmmsg(
+                    socket.as_raw_fd(),
+                    msgs.as_mut_ptr(),
+                    msgs.len() as libc::c_uint,
+                    0,
+                    std::ptr::null_mut(),
+                )
+            };
+            if count < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(msgs[..count as usize]
+                .iter()
+                .map(|msg| msg.msg_len as usize)
+                .collect::<Vec<_>>())
+        });
+        match result {
+            Ok(lens) => break lens,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+            Err(e) => return Err(e),
+        }
+    };
+
+    lens.into_iter()
+        .zip(addrs.iter())
+        .map(|(len, addr)| Ok((len, to_socket_addr(addr)?)))
+        .collect()
+}
+
+/// Send the datagrams by `sendmmsg(2)`; this waits until all of them have been sent.
+///
+/// This returns the number of the sent datagrams, i.e. `datagrams.len()`.
+pub async fn send_batch(
+    socket: &UdpSocket,
+    datagrams: &[(&[u8], SocketAddr)],
+) -> Result<usize, io::Error> {
+    let (mut addrs, addr_lens): (Vec<_>, Vec<_>) = datagrams
+        .iter()
+        .map(|(_, addr)| from_socket_addr(addr))
+        .unzip();
+
+    let mut sent = 0;
+    while sent < datagrams.len() {
+        socket.writable().await?;
+        let result = socket.try_io(Interest::WRITABLE, || {
+            let mut iovecs: Vec<libc::iovec> = datagrams[sent..]
+                .iter()
+                .map(|(data, _)| libc::iovec {
+                    iov_base: data.as_ptr() as *mut libc::c_void,
+                    iov_len: data.len(),
+                })
+                .collect();
+            let mut msgs = make_headers(&mut iovecs, &mut addrs[sent..], |i| addr_lens[sent + i]);
+            // SAFETY: the headers point to the data and the addresses that outlive this call
+            let count = unsafe {
+                libc::sendmmsg(
+                    socket.as_raw_fd(),
+                    msgs.as_mut_ptr(),
+                    msgs.len() as libc::c_uint,
+                    0,
+                )
+            };
+            if count < 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(count as usize)
+            }
+        });
+        match result {
+            Ok(count) => sent += count,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(sent)
+}
+
+fn make_headers(
+    iovecs: &mut [libc::iovec],
+    addrs: &mut [libc::sockaddr_storage],
+    addr_len: impl Fn(usize) -> libc::socklen_t,
+) -> Vec<libc::mmsghdr> {
+    iovecs
+        .iter_mut()
+        .zip(addrs.iter_mut())
+        .enumerate()
+        .map(|(i, (iovec, addr))| {
+            // SAFETY: mmsghdr is a plain C struct, so the zeroed value is valid
+            let mut msg: libc::mmsghdr = unsafe { mem::zeroed() };
+            msg.msg_hdr.msg_name = addr as *mut libc::sockaddr_storage as *mut libc::c_void;
+            msg.msg_hdr.msg_namelen = addr_len(i);
+            msg.msg_hdr.msg_iov = iovec as *mut libc::iovec;
+            msg.msg_hdr.msg_iovlen = 1;
+            msg
+        })
+        .collect()
+}
+
+/// Send the datagrams to one peer with the UDP generic segmentation offload (`UDP_SEGMENT`).
+///
+/// Each run of the consecutive datagrams of the same length (the last one of a run can be shorter) is passed to
+/// the kernel as one buffer that is split into the datagrams on the way out. If the kernel doesn't support GSO,
+/// this falls back to `send_batch()`. This returns the number of the sent datagrams, i.e. `datagrams.len()`.
+pub async fn send_segmented(
+    socket: &UdpSocket,
+    datagrams: &[&[u8]],
+    target: SocketAddr,
+) -> Result<usize, io::Error> {
+    let mut sent = 0;
+    while sent < datagrams.len() {
+        let segment_size = datagrams[sent].len();
+        let mut end = sent + 1;
+        let mut total = segment_size;
+        while end < datagrams.len()
+            && end - sent < MAX_GSO_SEGMENTS
+            && datagrams[end - 1].len() == segment_size
+            && datagrams[end].len() <= segment_size
+            && total + datagrams[end].len() <= MAX_GSO_PAYLOAD
+        {
+            total += datagrams[end].len();
+            end += 1;
+        }
+
+        let run = &datagrams[sent..end];
+        let segmented =
+            run.len() > 1 && segment_size > 0 && !GSO_UNSUPPORTED.load(Ordering::Relaxed);
+        let mut fallback = !segmented;
+        if segmented {
+            if let Err(e) = send_gso(socket, run, segment_size, target).await {
+                if !is_gso_unsupported(&e) {
+                    return Err(e);
+                }
+                debug!("UDP GSO is not available, falling back to sendmmsg; {}", e);
+                GSO_UNSUPPORTED.store(true, Ordering::Relaxed);
+                fallback = true;
+            }
+        }
+        if fallback {
+            let run: Vec<_> = run.iter().map(|data| (*data, target)).collect();
+            send_batch(socket, &run).await?;
+        }
+        sent = end;
+    }
+    Ok(sent)
+}
+
+async fn send_gso(
+    socket: &UdpSocket,
+    run: &[&[u8]],
+    segment_size: usize,
+    target: SocketAddr,
+) -> Result<(), io::Error> {
+    let data = run.concat();
+    let (mut addr, addr_len) = from_socket_addr(&target);
+    loop {
+        socket.writable().await?;
+        let result = socket.try_io(Interest::WRITABLE, || {
+            // u64 for the alignment of cmsghdr; CMSG_SPACE(size_of::<u16>()) fits in that
+            let mut control = [0u64; 4];
+            let mut iovec = libc::iovec {
+                iov_base: data.as_ptr() as *mut libc::c_void,
+                iov_len: data.len(),
+            };
+            // SAFETY: msghdr is a plain C struct, so the zeroed value is valid
+            let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+            msg.msg_name = &mut addr as *mut libc::sockaddr_storage as *mut libc::c_void;
+            msg.msg_namelen = addr_len;
+            msg.msg_iov = &mut iovec;
+            msg.msg_iovlen = 1;
+            msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+            // SAFETY: CMSG_SPACE is a pure computation
+            msg.msg_controllen = unsafe { libc::CMSG_SPACE(mem::size_of::<u16>() as u32) } as _;
+            // SAFETY: the control buffer is large enough and aligned for one cmsghdr with a u16
+            let result = unsafe {
+                let cmsg = libc::CMSG_FIRSTHDR(&msg);
+                (*cmsg).cmsg_level = libc::SOL_UDP;
+                (*cmsg).cmsg_type = libc::UDP_SEGMENT;
+                (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<u16>() as u32) as _;
+                std::ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut u16, segment_size as u16);
+                libc::sendmsg(socket.as_raw_fd(), &msg, 0)
+            };
+            if result < 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        });
+        match result {
+            Ok(()) => return Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn is_gso_unsupported(e: &io::Error) -> bool {
+    // EIO is returned when the device can't do the checksum offload that GSO needs
+    matches!(
+        e.raw_os_error(),
+        Some(libc::EIO | libc::EINVAL | libc::ENOPROTOOPT | libc::EOPNOTSUPP)
+    )
+}
+
+/// Enable the UDP generic receive offload (`UDP_GRO`) on the socket; this returns `false` if the kernel doesn't support that.
+pub fn enable_gro(socket: &UdpSocket) -> Result<bool, io::Error> {
+    let enable: libc::c_int = 1;
+    // SAFETY: the option value is a c_int of the given length
+    let result = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_UDP,
+            libc::UDP_GRO,
+            &enable as *const libc::c_int as *const libc::c_void,
+            mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if result == 0 {
+        return Ok(true);
+    }
+    let e = io::Error::last_os_error();
+    match e.raw_os_error() {
+        Some(libc::ENOPROTOOPT | libc::EINVAL) => Ok(false),
+        _ => Err(e),
+    }
+}
+
+/// Receive the datagrams that the kernel may have coalesced by GRO (see `enable_gro()`) by one `recvmsg(2)`.
+///
+/// This returns the ranges of the datagrams in `buf` and their source address; without GRO, there is just one range.
+/// `buf` should have 65535 bytes to receive the coalesced datagrams without the truncation.
+pub async fn recv_segmented(
+    socket: &UdpSocket,
+    buf: &mut [u8],
+) -> Result<(Vec<Range<usize>>, SocketAddr), io::Error> {
+    // SAFETY: sockaddr_storage is a plain C struct, so the zeroed value is valid
+    let mut addr: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let (len, segment_size) = loop {
+        socket.readable().await?;
+        let result = socket.try_io(Interest::READABLE, || {
+            let mut control = [0u64; 8];
+            let mut iovec = libc::iovec {
+                iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+                iov_len: buf.len(),
+            };
+            // SAFETY: msghdr is a plain C struct, so the zeroed value is valid
+            let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+            msg.msg_name = &mut addr as *mut libc::sockaddr_storage as *mut libc::c_void;
+            msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+            msg.msg_iov = &mut iovec;
+            msg.msg_iovlen = 1;
+            msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+            msg.msg_controllen = mem::size_of_val(&control) as _;
+
+            // SAFETY: the header points to the buffer, the address and the control buffer that outlive this call
+            let len = unsafe { libc::recvmsg(socket.as_raw_fd(), &mut msg, 0) };
+            if len < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut segment_size = None;
+            // SAFETY: the kernel has filled the control messages within msg_controllen
+            unsafe {
+                let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+                while !cmsg.is_null() {
+                    if (*cmsg).cmsg_level == libc::SOL_UDP && (*cmsg).cmsg_type == libc::UDP_GRO {
+                        segment_size = Some(std::ptr::read_unaligned(
+                            libc::CMSG_DATA(cmsg) as *const libc::c_int
+                        ) as usize);
+                    }
+                    cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+                }
+            }
+            Ok((len as usize, segment_size))
+        });
+        match result {
+            Ok(received) => break received,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+            Err(e) => return Err(e),
+        }
+    };
+
+    let ranges = match segment_size {
+        Some(segment_size) if segment_size > 0 => (0..len)
+            .step_by(segment_size)
+            .map(|start| start..(start + segment_size).min(len))
+            .collect(),
+        _ => std::iter::once(0..len).collect(),
+    };
+    Ok((ranges, to_socket_addr(&addr)?))
+}
+
+fn to_socket_addr(addr: &libc::sockaddr_storage) -> Result<SocketAddr, io::Error> {
+    match addr.ss_family as libc::c_int {
+        libc::AF_INET => {
+            // SAFETY: the family says that it is a sockaddr_in, which fits in sockaddr_storage
+            let addr =
+                unsafe { &*(addr as *const libc::sockaddr_storage as *const libc::sockaddr_in) };
+            Ok(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)),
+                u16::from_be(addr.sin_port),
+            )))
+        }
+        libc::AF_INET6 => {
+            // SAFETY: the family says that it is a sockaddr_in6, which fits in sockaddr_storage
+            let addr =
+                unsafe { &*(addr as *const libc::sockaddr_storage as *const libc::sockaddr_in6) };
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                Ipv6Addr::from(addr.sin6_addr.s6_addr),
+                u16::from_be(addr.sin6_port),
+                addr.sin6_flowinfo,
+                addr.sin6_scope_id,
+            )))
+        }
+        family => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported address family {family}"),
+        )),
+    }
+}
+
+fn from_socket_addr(addr: &SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+    // SAFETY: sockaddr_storage is a plain C struct, so the zeroed value is valid
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let len = match addr {
+        SocketAddr::V4(addr) => {
+            // SAFETY: sockaddr_in fits in sockaddr_storage
+            let sin = unsafe {
+                &mut *(&mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr_in)
+            };
+            sin.sin_family = libc::AF_INET as libc::sa_family_t;
+            sin.sin_port = addr.port().to_be();
+            sin.sin_addr.s_addr = u32::from(*addr.ip()).to_be();
+            mem::size_of::<libc::sockaddr_in>()
+        }
+        SocketAddr::V6(addr) => {
+            // SAFETY: sockaddr_in6 fits in sockaddr_storage
+            let sin6 = unsafe {
+                &mut *(&mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr_in6)
+            };
+            sin6.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            sin6.sin6_port = addr.port().to_be();
+            sin6.sin6_addr.s6_addr = addr.ip().octets();
+            sin6.sin6_flowinfo = addr.flowinfo();
+            sin6.sin6_scope_id = addr.scope_id();
+            mem::size_of::<libc::sockaddr_in6>()
+        }
+    };
+    (storage, len as libc::socklen_t)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+
+    use crate::batch::{enable_gro, recv_batch, recv_segmented, send_batch, send_segmented};
+    use crate::client::Client;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::server::{RequestHandler, SecretProvider, SecretProviderError, Server};
+
+    struct AcceptingHandler {}
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for AcceptingHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let response = req.get_packet().make_response_packet(Code::AccessAccept);
+            conn.send_to(&response.encode().unwrap(), req.get_remote_addr())
+                .await?;
+            Ok(())
+        }
+    }
+
+    struct StaticSecretProvider {}
+
+    impl SecretProvider for StaticSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_and_recv_batch() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let receiver_addr = receiver.local_addr().unwrap();
+
+        let datagrams: Vec<(&[u8], _)> = vec![
+            (b"first", receiver_addr),
+            (b"second", receiver_addr),
+            (b"third", receiver_addr),
+        ];
+        assert_eq!(send_batch(&sender, &datagrams).await.unwrap(), 3);
+
+        let mut bufs = vec![vec![0; 16]; 4];
+        let mut received: Vec<Vec<u8>> = Vec::new();
+        while received.len() < 3 {
+            let mut slices: Vec<&mut [u8]> =
+                bufs.iter_mut().map(|buf| buf.as_mut_slice()).collect();
+            let lens = recv_batch(&receiver, &mut slices).await.unwrap();
+            for (buf, (len, remote_addr)) in bufs.iter().zip(lens) {
+                assert_eq!(remote_addr, sender.local_addr().unwrap());
+                received.push(buf[..len].to_vec());
+            }
+        }
+        assert_eq!(
+            received,
+            vec![b"first".to_vec(), b"second".to_vec(), b"third".to_vec()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_and_recv_segmented() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        // the datagrams are received either way; GRO just coalesces them if the kernel supports that
+        enable_gro(&receiver).unwrap();
+
+        let datagrams: Vec<&[u8]> = vec![b"aaaa", b"bbbb", b"cc", b"ddddd"];
+        let sent = send_segmented(&sender, &datagrams, receiver.local_addr().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(sent, 4);
+
+        let mut buf = vec![0; 65535];
+        let mut received: Vec<Vec<u8>> = Vec::new();
+        while received.len() < 4 {
+            let (ranges, remote_addr) = recv_segmented(&receiver, &mut buf).await.unwrap();
+            assert_eq!(remote_addr, sender.local_addr().unwrap());
+            received.extend(ranges.into_iter().map(|range| buf[range].to_vec()));
+        }
+        assert_eq!(
+            received,
+            datagrams
+                .iter()
+                .map(|data| data.to_vec())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_with_batch_size() {
+        let mut server =
+            Server::listen("127.0.0.1", 0, AcceptingHandler {}, StaticSecretProvider {})
+                .await
+                .unwrap();
+        server.set_batch_size(8);
+        let addr = server.get_listen_address().unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server_task = tokio::spawn(async move { server.run(shutdown_rx).await });
+
+        let client = Client::new(Some(Duration::from_secs(1)), Some(Duration::from_secs(1)));
+        for _ in 0..3 {
+            let response = client
+                .send_packet(&addr, &Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+
+        shutdown_tx.send(()).unwrap();
+        server_task.await.unwrap().unwrap();
+    }
+}
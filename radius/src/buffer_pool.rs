@@ -0,0 +1,148 @@
+//! A pool of the receive buffers of the server.
+//!
+//! The server receives each datagram into a buffer that is taken from the pool, and hands it to the task that handles
+//! the request; the buffer goes back to the pool when the task drops it. So the receive loop doesn't allocate nor copy
+//! per datagram once the pool has been warmed up.
+
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsHook;
+
+pub(crate) struct BufferPool {
+    buffers: Mutex<Vec<Vec<u8>>>,
+    buf_size: usize,
+    capacity: usize,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<dyn MetricsHook>>,
+}
+
+impl BufferPool {
+    /// Make a pool of the buffers of `buf_size` bytes; it keeps at most `capacity` idle buffers.
+    #[cfg_attr(feature = "metrics", allow(dead_code))]
+    pub(crate) fn new(buf_size: usize, capacity: usize) -> Arc<Self> {
+        Arc::new(BufferPool {
+            buffers: Mutex::new(Vec::with_capacity(capacity)),
+            buf_size,
+            capacity,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+        })
+    }
+
+    /// Make a pool that records the number of the idle buffers into `metrics`.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn with_metrics(
+        buf_size: usize,
+        capacity: usize,
+        metrics: Option<Arc<dyn MetricsHook>>,
+    ) -> Arc<Self> {
+        Arc::new(BufferPool {
+            buffers: Mutex::new(Vec::with_capacity(capacity)),
+            buf_size,
+            capacity,
+            metrics,
+        })
+    }
+
+    /// Take an idle buffer, or allocate one if there is none.
+    pub(crate) fn take(self: &Arc<Self>) -> PooledBuffer {
+        let buf = match self.buffers.lock().unwrap().pop() {
+            Some(buf) => {
+                self.add_idle(-1);
+                buf
+            }
+            None => vec![0; self.buf_size],
+        };
+        PooledBuffer {
+            buf,
+            len: 0,
+            pool: self.clone(),
+        }
+    }
+
+    fn give_back(&self, buf: Vec<u8>) {
+        let mut buffers = self.buffers.lock().unwrap();
+        if buffers.len() < self.capacity {
+            buffers.push(buf);
+            self.add_idle(1);
+        }
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn add_idle(&self, delta: i64) {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.on_server_buffer_pool_idle(delta);
+        }
+    }
+}
+
+impl Drop for BufferPool {
+    fn drop(&mut self) {
+        let idle = self.buffers.get_mut().unwrap().len();
+        self.add_idle(-(idle as i64));
+    }
+}
+
+/// A buffer that goes back to the pool on drop; it dereferences to the received bytes.
+pub(crate) struct PooledBuffer {
+    buf: Vec<u8>,
+    len: usize,
+    pool: Arc<BufferPool>,
+}
+
+impl PooledBuffer {
+    /// Returns the whole buffer to receive into.
+    pub(crate) fn as_mut_buffer(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+
+    /// Set the length of the received bytes.
+    pub(crate) fn set_len(&mut self, len: usize) {
+        self.len = len.min(self.buf.len());
+    }
+}
+
+impl Deref for PooledBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        self.pool.give_back(std::mem::take(&mut self.buf));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer_pool::BufferPool;
+
+    #[test]
+    fn test_reuse() {
+        let pool = BufferPool::new(16, 1);
+
+        let mut buf = pool.take();
+        buf.as_mut_buffer()[..3].copy_from_slice(b"abc");
+        buf.set_len(3);
+        assert_eq!(&*buf, b"abc");
+        let ptr = buf.as_ptr();
+        drop(buf);
+
+        let buf = pool.take();
+        assert_eq!(buf.as_ptr(), ptr);
+        assert!(buf.is_empty());
+
+        let mut second = pool.take();
+        assert_ne!(second.as_ptr(), ptr);
+        assert_eq!(second.as_mut_buffer().len(), 16);
+        drop(buf);
+        drop(second);
+        assert_eq!(pool.buffers.lock().unwrap().len(), 1);
+    }
+}
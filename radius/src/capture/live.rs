@@ -0,0 +1,129 @@
+use std::ffi::CString;
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+
+use chrono::Utc;
+use tokio::io::unix::AsyncFd;
+
+use crate::capture::{parse_frame, CaptureError, CapturedDatagram, DEFAULT_RADIUS_PORTS};
+
+// the frames of an AF_PACKET socket in SOCK_DGRAM mode have no link-layer header
+const LINKTYPE_RAW: u32 = 101;
+const ARPHRD_LOOPBACK: u16 = 772;
+const MAX_FRAME_SIZE: usize = 65536;
+
+/// A live capture of the RADIUS traffic on the network interfaces (available on Linux with the `live-capture` feature).
+///
+/// This uses an `AF_PACKET` socket, so it requires the `CAP_NET_RAW` capability (e.g. root).
+pub struct LiveCapture {
+    fd: AsyncFd<OwnedFd>,
+    ports: Vec<u16>,
+    buf: Vec<u8>,
+}
+
+impl LiveCapture {
+    /// Start capturing on the given interface (e.g. `eth0`), or on all of the interfaces if it is `None`.
+    pub fn open(interface: Option<&str>) -> Result<Self, CaptureError> {
+        let protocol = (libc::ETH_P_ALL as u16).to_be();
+        // SAFETY: it is a plain socket(2) call and the returned descriptor is owned by `OwnedFd` right after that
+        let fd = unsafe {
+            let fd = libc::socket(
+                libc::AF_PACKET,
+                libc::SOCK_DGRAM | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                i32::from(protocol),
+            );
+            if fd < 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+            OwnedFd::from_raw_fd(fd)
+        };
+
+        if let Some(interface) = interface {
+            let name = CString::new(interface).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidInput, "invalid interface name")
+            })?;
+            // SAFETY: `name` is a valid NUL-terminated string
+            let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+            if index == 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+
+            // SAFETY: sockaddr_ll is a plain C struct, so the zeroed value is valid
+            let mut addr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+            addr.sll_family = libc::AF_PACKET as u16;
+            addr.sll_protocol = protocol;
+            addr.sll_ifindex = index as i32;
+            // SAFETY: `addr` is a valid sockaddr_ll and the length matches
+            let result = unsafe {
+                libc::bind(
+                    fd.as_raw_fd(),
+                    &addr as *const libc::sockaddr_ll as *const libc::sockaddr,
+                    mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+                )
+            };
+            if result < 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+        }
+
+        Ok(LiveCapture {
+            fd: AsyncFd::new(fd)?,
+            ports: DEFAULT_RADIUS_PORTS.to_vec(),
+            buf: vec![0; MAX_FRAME_SIZE],
+        })
+    }
+
+    /// Set the UDP ports to capture (default: `DEFAULT_RADIUS_PORTS`).
+    /// A datagram is captured if either its source port or destination port matches.
+    pub fn set_ports(&mut self, ports: &[u16]) {
+        self.ports = ports.to_vec();
+    }
+
+    /// Wait for the next UDP datagram on the RADIUS ports.
+    pub async fn next_datagram(&mut self) -> Result<CapturedDatagram, CaptureError> {
+        loop {
+            let mut guard = self.fd.readable().await?;
+            // SAFETY: sockaddr_ll is a plain C struct, so the zeroed value is valid
+            let mut addr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+            let mut addr_len = mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t;
+            // SAFETY: the buffer and the address are valid for the given lengths
+            let len = unsafe {
+                libc::recvfrom(
+                    guard.get_inner().as_raw_fd(),
+                    self.buf.as_mut_ptr() as *mut libc::c_void,
+                    self.buf.len(),
+                    0,
+                    &mut addr as *mut libc::sockaddr_ll as *mut libc::sockaddr,
+                    &mut addr_len,
+                )
+            };
+            if len < 0 {
+                let e = io::Error::last_os_error();
+                if e.kind() == io::ErrorKind::WouldBlock {
+                    guard.clear_ready();
+                    continue;
+                }
+                return Err(e.into());
+            }
+
+            // a packet on the loopback interface is seen twice (i.e. outgoing and incoming)
+            if addr.sll_hatype == ARPHRD_LOOPBACK && addr.sll_pkttype == libc::PACKET_OUTGOING {
+                continue;
+            }
+
+            if let Some((source, destination, payload)) =
+                parse_frame(LINKTYPE_RAW, &self.buf[..len as usize])
+            {
+                if self.ports.contains(&source.port()) || self.ports.contains(&destination.port()) {
+                    return Ok(CapturedDatagram::new(
+                        Utc::now(),
+                        source,
+                        destination,
+                        payload.to_vec(),
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Extracting the RADIUS packets from the captured traffic (available with the `pcap` feature).
+//!
+//! `PcapReader` reads the capture files in the pcap and pcapng formats and yields the UDP datagrams on the RADIUS ports
+//! as `CapturedDatagram`. Each datagram can be decoded into a `Packet` with the shared-secret of the NAS.
+//!
+//! `Replayer` re-sends the captured requests to a target server and compares the responses with the captured ones.
+//!
+//! On Linux, `LiveCapture` captures the datagrams on the network interfaces in real time (with the `live-capture` feature).
+//!
+//! NOTE: IP fragments are not reassembled; the fragmented datagrams are skipped.
+
+#[cfg(all(feature = "live-capture", target_os = "linux"))]
+mod live;
+mod pcap;
+#[cfg(feature = "tokio")]
+mod replay;
+
+#[cfg(all(feature = "live-capture", target_os = "linux"))]
+pub use live::LiveCapture;
+pub use pcap::PcapReader;
+#[cfg(feature = "tokio")]
+pub use replay::{ReplayError, ReplayOutcome, ReplayPacing, Replayer};
+
+use std::convert::TryInto;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::core::packet::{Packet, PacketError};
+use crate::server::{SecretProvider, SecretProviderError};
+
+/// The well-known ports of RADIUS authentication, accounting and dynamic authorization (including the legacy ones).
+pub const DEFAULT_RADIUS_PORTS: &[u16] = &[1812, 1813, 1645, 1646, 3799];
+
+#[derive(Error, Debug)]
+pub enum CaptureError {
+    /// An error that is raised when it fails to read the capture.
+    #[error("failed to read the capture: {0}")]
+    IOError(#[from] std::io::Error),
+    /// An error that is raised when the capture is not in the supported format.
+    #[error("unsupported capture format: {0}")]
+    UnsupportedFormatError(String),
+    /// An error that is raised when the capture is truncated or corrupted.
+    #[error("malformed capture: {0}")]
+    MalformedCaptureError(String),
+}
+
+#[derive(Error, Debug)]
+pub enum CapturedPacketDecodingError {
+    /// An error that is raised when it fails to fetch a secret value for the datagram.
+    #[error(transparent)]
+    SecretProviderError(#[from] SecretProviderError),
+    /// An error that is raised when the datagram is not a valid RADIUS packet.
+    #[error(transparent)]
+    PacketError(#[from] PacketError),
+}
+
+/// A UDP datagram on the RADIUS ports that has been captured.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedDatagram {
+    timestamp: DateTime<Utc>,
+    source: SocketAddr,
+    destination: SocketAddr,
+    payload: Vec<u8>,
+}
+
+impl CapturedDatagram {
+    pub fn new(
+        timestamp: DateTime<Utc>,
+        source: SocketAddr,
+        destination: SocketAddr,
+        payload: Vec<u8>,
+    ) -> Self {
+        CapturedDatagram {
+            timestamp,
+            source,
+            destination,
+            payload,
+        }
+    }
+
+    pub fn get_timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    pub fn get_source(&self) -> SocketAddr {
+        self.source
+    }
+
+    pub fn get_destination(&self) -> SocketAddr {
+        self.destination
+    }
+
+    /// Returns the UDP payload (i.e. the RADIUS packet in the wire format).
+    pub fn get_payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Returns whether the datagram is sent to one of the given server ports (i.e. it is a request) or not.
+    pub fn is_request(&self, server_ports: &[u16]) -> bool {
+        server_ports.contains(&self.destination.port())
+    }
+
+    /// Returns the address of the RADIUS client (e.g. NAS) of the exchange: the source of a request
+    /// or the destination of a response.
+    pub fn get_client_addr(&self, server_ports: &[u16]) -> SocketAddr {
+        if self.is_request(server_ports) {
+            self.source
+        } else {
+            self.destination
+        }
+    }
+
+    /// Decode the payload with the given secret.
+    pub fn decode(&self, secret: &[u8]) -> Result<Packet, PacketError> {
+        Packet::decode(&self.payload, secret)
+    }
+
+    /// Decode the payload without the secret (see `Packet::decode_unverified()`), e.g. to log the traffic of the unknown
+    /// clients.
+    pub fn decode_unverified(&self) -> Result<Packet, PacketError> {
+        Packet::decode_unverified(&self.payload)
+    }
+
+    /// Decode the payload with the secret of the RADIUS client of the exchange.
+    /// The client is decided by `DEFAULT_RADIUS_PORTS`.
+    pub fn decode_with_secret_provider<U: SecretProvider>(
+        &self,
+        secret_provider: &U,
+    ) -> Result<Packet, CapturedPacketDecodingError> {
+        let secret = secret_provider.fetch_secret(self.get_client_addr(DEFAULT_RADIUS_PORTS))?;
+        Ok(self.decode(&secret)?)
+    }
+}
+
+// see also: https://www.tcpdump.org/linktypes.html
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_QINQ: u16 = 0x88a8;
+
+const IP_PROTOCOL_UDP: u8 = 17;
+const UDP_HEADER_LENGTH: usize = 8;
+
+pub(crate) fn is_supported_link_type(link_type: u32) -> bool {
+    matches!(
+        link_type,
+        LINKTYPE_NULL
+            | LINKTYPE_ETHERNET
+            | LINKTYPE_RAW
+            | LINKTYPE_LINUX_SLL
+            | LINKTYPE_IPV4
+            | LINKTYPE_IPV6
+            | LINKTYPE_LINUX_SLL2
+    )
+}
+
+/// Extract the UDP datagram from a link-layer frame, and return `(source, destination, payload)`.
+pub(crate) fn parse_frame(link_type: u32, frame: &[u8]) -> Option<(SocketAddr, SocketAddr, &[u8])> {
+    let ip_packet = match link_type {
+        LINKTYPE_NULL => frame.get(4..)?,
+        LINKTYPE_ETHERNET => {
+            let mut offset = 12;
+            let mut ether_type =
+                u16::from_be_bytes(frame.get(offset..offset + 2)?.try_into().ok()?);
+            while ether_type == ETHERTYPE_VLAN || ether_type == ETHERTYPE_QINQ {
+                offset += 4;
+                ether_type = u16::from_be_bytes(frame.get(offset..offset + 2)?.try_into().ok()?);
+            }
+            if ether_type != ETHERTYPE_IPV4 && ether_type != ETHERTYPE_IPV6 {
+                return None;
+            }
+            frame.get(offset + 2..)?
+        }
+        LINKTYPE_LINUX_SLL => frame.get(16..)?,
+        LINKTYPE_LINUX_SLL2 => frame.get(20..)?,
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => frame,
+        _ => return None,
+    };
+    parse_ip_packet(ip_packet)
+}
+
+fn parse_ip_packet(ip_packet: &[u8]) -> Option<(SocketAddr, SocketAddr, &[u8])> {
+    let (source_ip, destination_ip, udp_datagram) = match ip_packet.first()? >> 4 {
+        4 => {
+            let header_length = usize::from(ip_packet[0] & 0x0f) * 4;
+            let total_length =
+                usize::from(u16::from_be_bytes(ip_packet.get(2..4)?.try_into().ok()?));
+            let flags_and_fragment_offset =
+                u16::from_be_bytes(ip_packet.get(6..8)?.try_into().ok()?);
+            let is_fragment = flags_and_fragment_offset & 0x3fff != 0; // MF flag or non-zero offset
+            if *ip_packet.get(9)? != IP_PROTOCOL_UDP || is_fragment || header_length < 20 {
+                return None;
+            }
+            let source: [u8; 4] = ip_packet.get(12..16)?.try_into().ok()?;
+            let destination: [u8; 4] = ip_packet.get(16..20)?.try_into().ok()?;
+            (
+                IpAddr::V4(Ipv4Addr::from(source)),
+                IpAddr::V4(Ipv4Addr::from(destination)),
+                ip_packet.get(header_length..total_length.min(ip_packet.len()))?,
+            )
+        }
+        6 => {
+            // extension headers are not supported
+            if *ip_packet.get(6)? != IP_PROTOCOL_UDP {
+                return None;
+            }
+            let source: [u8; 16] = ip_packet.get(8..24)?.try_into().ok()?;
+            let destination: [u8; 16] = ip_packet.get(24..40)?.try_into().ok()?;
+            (
+                IpAddr::V6(Ipv6Addr::from(source)),
+                IpAddr::V6(Ipv6Addr::from(destination)),
+                ip_packet.get(40..)?,
+            )
+        }
+        _ => return None,
+    };
+
+    let source_port = u16::from_be_bytes(udp_datagram.get(0..2)?.try_into().ok()?);
+    let destination_port = u16::from_be_bytes(udp_datagram.get(2..4)?.try_into().ok()?);
+    let udp_length = usize::from(u16::from_be_bytes(udp_datagram.get(4..6)?.try_into().ok()?));
+    let payload = udp_datagram.get(UDP_HEADER_LENGTH..udp_length.min(udp_datagram.len()))?;
+    Some((
+        SocketAddr::new(source_ip, source_port),
+        SocketAddr::new(destination_ip, destination_port),
+        payload,
+    ))
+}
@@ -0,0 +1,530 @@
+use std::convert::{TryFrom, TryInto};
+use std::io::{self, Read};
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::capture::{
+    is_supported_link_type, parse_frame, CaptureError, CapturedDatagram, DEFAULT_RADIUS_PORTS,
+};
+
+const PCAP_MAGIC_MICROSECONDS: u32 = 0xa1b2_c3d4;
+const PCAP_MAGIC_NANOSECONDS: u32 = 0xa1b2_3c4d;
+const PCAPNG_SECTION_HEADER_BLOCK: u32 = 0x0a0d_0d0a;
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+const PCAPNG_INTERFACE_DESCRIPTION_BLOCK: u32 = 0x0000_0001;
+const PCAPNG_SIMPLE_PACKET_BLOCK: u32 = 0x0000_0003;
+const PCAPNG_ENHANCED_PACKET_BLOCK: u32 = 0x0000_0006;
+const PCAPNG_OPTION_IF_TSRESOL: u16 = 9;
+
+const MAX_RECORD_LENGTH: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ByteOrder {
+    Big,
+    Little,
+}
+
+impl ByteOrder {
+    fn u16(self, bs: &[u8]) -> u16 {
+        let bs: [u8; 2] = bs[..2].try_into().unwrap();
+        match self {
+            ByteOrder::Big => u16::from_be_bytes(bs),
+            ByteOrder::Little => u16::from_le_bytes(bs),
+        }
+    }
+
+    fn u32(self, bs: &[u8]) -> u32 {
+        let bs: [u8; 4] = bs[..4].try_into().unwrap();
+        match self {
+            ByteOrder::Big => u32::from_be_bytes(bs),
+            ByteOrder::Little => u32::from_le_bytes(bs),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Interface {
+    link_type: u32,
+    /// the number of the timestamp units per second
+    timestamp_resolution: u64,
+}
+
+#[derive(Debug)]
+enum Format {
+    Pcap { link_type: u32, nanoseconds: bool },
+    Pcapng { interfaces: Vec<Interface> },
+}
+
+/// A reader of the capture files (pcap and pcapng) that yields the UDP datagrams on the RADIUS ports.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::fs::File;
+/// use radius::capture::PcapReader;
+///
+/// let reader = PcapReader::new(File::open("radius.pcap").unwrap()).unwrap();
+/// for datagram in reader {
+///     let datagram = datagram.unwrap();
+///     if let Ok(packet) = datagram.decode(b"secret") {
+///         println!("{} -> {}: {}", datagram.get_source(), datagram.get_destination(), packet.get_code().string());
+///     }
+/// }
+/// ```
+pub struct PcapReader<R: Read> {
+    reader: R,
+    byte_order: ByteOrder,
+    format: Format,
+    ports: Vec<u16>,
+    finished: bool,
+}
+
+impl<R: Read> PcapReader<R> {
+    /// A constructor for a reader. This reads the file header to detect the format.
+    pub fn new(mut reader: R) -> Result<Self, CaptureError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+
+        if u32::from_be_bytes(magic) == PCAPNG_SECTION_HEADER_BLOCK {
+            let mut reader = PcapReader {
+                reader,
+                byte_order: ByteOrder::Big,
+                format: Format::Pcapng { interfaces: vec![] },
+                ports: DEFAULT_RADIUS_PORTS.to_vec(),
+                finished: false,
+            };
+            reader.read_section_header_body()?;
+            return Ok(reader);
+        }
+
+        let (byte_order, nanoseconds) = match (u32::from_be_bytes(magic), u32::from_le_bytes(magic))
+        {
+            (PCAP_MAGIC_MICROSECONDS, _) => (ByteOrder::Big, false),
+            (PCAP_MAGIC_NANOSECONDS, _) => (ByteOrder::Big, true),
+            (_, PCAP_MAGIC_MICROSECONDS) => (ByteOrder::Little, false),
+            (_, PCAP_MAGIC_NANOSECONDS) => (ByteOrder::Little, true),
+            _ => {
+                return Err(CaptureError::UnsupportedFormatError(format!(
+                    "unknown magic number {magic:02x?}"
+                )))
+            }
+        };
+        let mut header = [0u8; 20];
+        reader.read_exact(&mut header)?;
+        let link_type = byte_order.u32(&header[16..20]) & 0x0fff_ffff;
+        if !is_supported_link_type(link_type) {
+            return Err(CaptureError::UnsupportedFormatError(format!(
+                "unsupported link type {link_type}"
+            )));
+        }
+
+        Ok(PcapReader {
+            reader,
+            byte_order,
+            format: Format::Pcap {
+                link_type,
+                nanoseconds,
+            },
+            ports: DEFAULT_RADIUS_PORTS.to_vec(),
+            finished: false,
+        })
+    }
+
+    /// Set the UDP ports to extract (default: `DEFAULT_RADIUS_PORTS`).
+    /// A datagram is extracted if either its source port or destination port matches.
+    pub fn set_ports(&mut self, ports: &[u16]) {
+        self.ports = ports.to_vec();
+    }
+
+    fn read_record(&mut self) -> Result<Option<CapturedDatagram>, CaptureError> {
+        loop {
+            let frame = match &self.format {
+                Format::Pcap {
+                    link_type,
+                    nanoseconds,
+                } => {
+                    let (link_type, nanoseconds) = (*link_type, *nanoseconds);
+                    let mut header = [0u8; 16];
+                    if !read_exact_or_eof(&mut self.reader, &mut header)? {
+                        return Ok(None);
+                    }
+                    let seconds = self.byte_order.u32(&header[0..4]);
+                    let fraction = self.byte_order.u32(&header[4..8]);
+                    let captured_length = self.byte_order.u32(&header[8..12]) as usize;
+                    let data = self.read_bytes(captured_length)?;
+                    let nanos = if nanoseconds {
+                        fraction
+                    } else {
+                        fraction.saturating_mul(1000)
+                    };
+                    Some((
+                        link_type,
+                        to_timestamp(
+                            u64::from(seconds) * 1_000_000_000 + u64::from(nanos),
+                            1_000_000_000,
+                        ),
+                        data,
+                    ))
+                }
+                Format::Pcapng { .. } => match self.read_pcapng_block()? {
+                    Some(frame) => frame,
+                    None => return Ok(None),
+                },
+            };
+
+            if let Some((link_type, timestamp, data)) = frame {
+                if let Some((source, destination, payload)) = parse_frame(link_type, &data) {
+                    if self.ports.contains(&source.port())
+                        || self.ports.contains(&destination.port())
+                    {
+                        return Ok(Some(CapturedDatagram::new(
+                            timestamp.unwrap_or_else(|| Utc.timestamp_opt(0, 0).unwrap()),
+                            source,
+                            destination,
+                            payload.to_vec(),
+                        )));
+                    }
+                }
+            }
+        }
+    }
+
+    fn read_section_header_body(&mut self) -> Result<(), CaptureError> {
+        let mut header = [0u8; 8];
+        self.reader.read_exact(&mut header)?;
+        self.byte_order = match (
+            u32::from_be_bytes(header[4..8].try_into().unwrap()),
+            u32::from_le_bytes(header[4..8].try_into().unwrap()),
+        ) {
+            (PCAPNG_BYTE_ORDER_MAGIC, _) => ByteOrder::Big,
+            (_, PCAPNG_BYTE_ORDER_MAGIC) => ByteOrder::Little,
+            _ => {
+                return Err(CaptureError::MalformedCaptureError(
+                    "invalid byte-order magic of pcapng".to_owned(),
+                ))
+            }
+        };
+        let total_length = self.byte_order.u32(&header[0..4]) as usize;
+        if total_length < 28 {
+            return Err(CaptureError::MalformedCaptureError(format!(
+                "invalid length of the section header block: {total_length}"
+            )));
+        }
+        self.read_bytes(total_length - 12)?;
+        // interfaces are scoped per section
+        self.format = Format::Pcapng { interfaces: vec![] };
+        Ok(())
+    }
+
+    /// Returns `None` on EOF, `Some(None)` for a block that has no packet.
+    #[allow(clippy::type_complexity)]
+    fn read_pcapng_block(
+        &mut self,
+    ) -> Result<Option<Option<(u32, Option<DateTime<Utc>>, Vec<u8>)>>, CaptureError> {
+        let mut header = [0u8; 4];
+        if !read_exact_or_eof(&mut self.reader, &mut header)? {
+            return Ok(None);
+        }
+        let block_type = self.byte_order.u32(&header);
+        if block_type == PCAPNG_SECTION_HEADER_BLOCK {
+            self.read_section_header_body()?;
+            return Ok(Some(None));
+        }
+
+        self.reader.read_exact(&mut header)?;
+        let total_length = self.byte_order.u32(&header) as usize;
+        if total_length < 12 || !total_length.is_multiple_of(4) {
+            return Err(CaptureError::MalformedCaptureError(format!(
+                "invalid block length: {total_length}"
+            )));
+        }
+        let body = self.read_bytes(total_length - 8)?;
+        let body = &body[..body.len() - 4]; // trailing total length
+        let interfaces = match &mut self.format {
+            Format::Pcapng { interfaces } => interfaces,
+            Format::Pcap { .. } => unreachable!(),
+        };
+        let byte_order = self.byte_order;
+        let malformed =
+            || CaptureError::MalformedCaptureError(format!("truncated block of type {block_type}"));
+
+        match block_type {
+            PCAPNG_INTERFACE_DESCRIPTION_BLOCK => {
+                if body.len() < 8 {
+                    return Err(malformed());
+                }
+                let mut interface = Interface {
+                    link_type: u32::from(byte_order.u16(&body[0..2])),
+                    timestamp_resolution: 1_000_000,
+                };
+                let mut options = &body[8..];
+                while options.len() >= 4 {
+                    let code = byte_order.u16(&options[0..2]);
+                    let length = usize::from(byte_order.u16(&options[2..4]));
+                    let value = options.get(4..4 + length).ok_or_else(malformed)?;
+                    if code == PCAPNG_OPTION_IF_TSRESOL && length == 1 {
+                        let resolution = u32::from(value[0] & 0x7f);
+                        interface.timestamp_resolution = if value[0] & 0x80 == 0 {
+                            10u64.checked_pow(resolution).unwrap_or(u64::MAX)
+                        } else {
+                            2u64.checked_pow(resolution).unwrap_or(u64::MAX)
+                        };
+                    }
+                    if code == 0 {
+                        break;
+                    }
+                    options = options
+                        .get(4 + length.div_ceil(4) * 4..)
+                        .unwrap_or_default();
+                }
+                interfaces.push(interface);
+                Ok(Some(None))
+            }
+            PCAPNG_ENHANCED_PACKET_BLOCK => {
+                if body.len() < 20 {
+                    return Err(malformed());
+                }
+                let interface = match interfaces.get(byte_order.u32(&body[0..4]) as usize) {
+                    Some(interface) => interface,
+                    None => {
+                        return Err(CaptureError::MalformedCaptureError(
+                            "unknown interface".to_owned(),
+                        ))
+                    }
+                };
+                if !is_supported_link_type(interface.link_type) {
+                    return Ok(Some(None));
+                }
+                let timestamp = (u64::from(byte_order.u32(&body[4..8])) << 32)
+                    | u64::from(byte_order.u32(&body[8..12]));
+                let captured_length = byte_order.u32(&body[12..16]) as usize;
+                let data = body.get(20..20 + captured_length).ok_or_else(malformed)?;
+                Ok(Some(Some((
+                    interface.link_type,
+                    to_timestamp(timestamp, interface.timestamp_resolution),
+                    data.to_vec(),
+                ))))
+            }
+            PCAPNG_SIMPLE_PACKET_BLOCK => {
+                let interface = match interfaces.first() {
+                    Some(interface) => interface,
+                    None => {
+                        return Err(CaptureError::MalformedCaptureError(
+                            "unknown interface".to_owned(),
+                        ))
+                    }
+                };
+                if !is_supported_link_type(interface.link_type) || body.len() < 4 {
+                    return Ok(Some(None));
+                }
+                Ok(Some(Some((interface.link_type, None, body[4..].to_vec()))))
+            }
+            _ => Ok(Some(None)),
+        }
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<Vec<u8>, CaptureError> {
+        if length > MAX_RECORD_LENGTH {
+            return Err(CaptureError::MalformedCaptureError(format!(
+                "too large record: {length} bytes"
+            )));
+        }
+        let mut buf = vec![0u8; length];
+        match self.reader.read_exact(&mut buf) {
+            Ok(_) => Ok(buf),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Err(
+                CaptureError::MalformedCaptureError("unexpected end of the capture".to_owned()),
+            ),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = Result<CapturedDatagram, CaptureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.read_record() {
+            Ok(Some(datagram)) => Some(Ok(datagram)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Read the exact bytes, and return `false` if the reader is at EOF.
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<bool, CaptureError> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => {
+                return Err(CaptureError::MalformedCaptureError(
+                    "unexpected end of the capture".to_owned(),
+                ))
+            }
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(true)
+}
+
+fn to_timestamp(units: u64, units_per_second: u64) -> Option<DateTime<Utc>> {
+    let seconds = units / units_per_second;
+    let nanos = (u128::from(units % units_per_second) * 1_000_000_000
+        / u128::from(units_per_second)) as u32;
+    Utc.timestamp_opt(i64::try_from(seconds).ok()?, nanos)
+        .single()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::capture::PcapReader;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+
+    fn make_ethernet_frame(source: SocketAddr, destination: SocketAddr, payload: &[u8]) -> Vec<u8> {
+        let (source_ip, destination_ip) = match (source, destination) {
+            (SocketAddr::V4(s), SocketAddr::V4(d)) => (s.ip().octets(), d.ip().octets()),
+            _ => unreachable!(),
+        };
+        let udp_length = 8 + payload.len() as u16;
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&[0x08, 0x00]);
+        frame.extend_from_slice(&[0x45, 0x00]);
+        frame.extend_from_slice(&(20 + udp_length).to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0x40, 0, 64, 17, 0, 0]);
+        frame.extend_from_slice(&source_ip);
+        frame.extend_from_slice(&destination_ip);
+        frame.extend_from_slice(&source.port().to_be_bytes());
+        frame.extend_from_slice(&destination.port().to_be_bytes());
+        frame.extend_from_slice(&udp_length.to_be_bytes());
+        frame.extend_from_slice(&[0, 0]);
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    fn make_payload() -> Vec<u8> {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, "bob");
+        packet.encode().unwrap()
+    }
+
+    fn nas() -> SocketAddr {
+        "192.0.2.1:50000".parse().unwrap()
+    }
+
+    fn server() -> SocketAddr {
+        "192.0.2.254:1812".parse().unwrap()
+    }
+
+    #[test]
+    fn test_read_pcap() {
+        let payload = make_payload();
+        let mut pcap = vec![];
+        pcap.extend_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+        pcap.extend_from_slice(&[
+            2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 0, 0, 1, 0, 0, 0,
+        ]);
+        for (i, (source, destination, payload)) in [
+            (nas(), server(), payload.clone()),
+            (nas(), "192.0.2.254:53".parse().unwrap(), vec![1, 2, 3]),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let frame = make_ethernet_frame(*source, *destination, payload);
+            pcap.extend_from_slice(&(1_600_000_000u32 + i as u32).to_le_bytes());
+            pcap.extend_from_slice(&500_000u32.to_le_bytes());
+            pcap.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            pcap.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            pcap.extend_from_slice(&frame);
+        }
+
+        let datagrams: Vec<_> = PcapReader::new(pcap.as_slice())
+            .unwrap()
+            .map(|d| d.unwrap())
+            .collect();
+        assert_eq!(datagrams.len(), 1);
+        let datagram = &datagrams[0];
+        assert_eq!(datagram.get_source(), nas());
+        assert_eq!(datagram.get_destination(), server());
+        assert_eq!(datagram.get_payload(), payload.as_slice());
+        assert_eq!(
+            datagram.get_timestamp(),
+            Utc.timestamp_opt(1_600_000_000, 500_000_000).unwrap()
+        );
+        assert_eq!(
+            rfc2865::lookup_user_name(&datagram.decode(b"secret").unwrap())
+                .unwrap()
+                .unwrap(),
+            "bob"
+        );
+    }
+
+    #[test]
+    fn test_read_pcapng() {
+        let payload = make_payload();
+        let frame = make_ethernet_frame(server(), nas(), &payload);
+
+        let mut pcapng = vec![];
+        // section header block
+        pcapng.extend_from_slice(&0x0a0d_0d0au32.to_le_bytes());
+        pcapng.extend_from_slice(&28u32.to_le_bytes());
+        pcapng.extend_from_slice(&0x1a2b_3c4du32.to_le_bytes());
+        pcapng.extend_from_slice(&[1, 0, 0, 0]);
+        pcapng.extend_from_slice(&u64::MAX.to_le_bytes());
+        pcapng.extend_from_slice(&28u32.to_le_bytes());
+        // interface description block with if_tsresol = 9 (nanoseconds)
+        pcapng.extend_from_slice(&1u32.to_le_bytes());
+        pcapng.extend_from_slice(&32u32.to_le_bytes());
+        pcapng.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+        pcapng.extend_from_slice(&[9, 0, 1, 0, 9, 0, 0, 0]);
+        pcapng.extend_from_slice(&[0, 0, 0, 0]);
+        pcapng.extend_from_slice(&32u32.to_le_bytes());
+        // enhanced packet block
+        let padded_length = frame.len().div_ceil(4) * 4;
+        let block_length = (32 + padded_length) as u32;
+        let timestamp: u64 = 1_600_000_000_123_456_789;
+        pcapng.extend_from_slice(&6u32.to_le_bytes());
+        pcapng.extend_from_slice(&block_length.to_le_bytes());
+        pcapng.extend_from_slice(&0u32.to_le_bytes());
+        pcapng.extend_from_slice(&((timestamp >> 32) as u32).to_le_bytes());
+        pcapng.extend_from_slice(&(timestamp as u32).to_le_bytes());
+        pcapng.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        pcapng.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        pcapng.extend_from_slice(&frame);
+        pcapng.resize(pcapng.len() + padded_length - frame.len(), 0);
+        pcapng.extend_from_slice(&block_length.to_le_bytes());
+
+        let datagrams: Vec<_> = PcapReader::new(pcapng.as_slice())
+            .unwrap()
+            .map(|d| d.unwrap())
+            .collect();
+        assert_eq!(datagrams.len(), 1);
+        let datagram = &datagrams[0];
+        assert!(!datagram.is_request(&[1812]));
+        assert_eq!(datagram.get_client_addr(&[1812]), nas());
+        assert_eq!(datagram.get_payload(), payload.as_slice());
+        assert_eq!(
+            datagram.get_timestamp(),
+            Utc.timestamp_opt(1_600_000_000, 123_456_789).unwrap()
+        );
+    }
+}
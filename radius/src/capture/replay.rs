@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep_until, Instant};
+
+use crate::capture::{CaptureError, CapturedDatagram, DEFAULT_RADIUS_PORTS};
+use crate::client::{Client, ClientError};
+use crate::core::avp::{AVPError, AVP};
+use crate::core::code::Code;
+use crate::core::packet::{Packet, PacketError};
+use crate::core::{rfc2865, rfc2869};
+use crate::server::{SecretProvider, SecretProviderError};
+use crate::tasks;
+
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    /// An error that is raised when it fails to fetch the secret of the captured request.
+    #[error(transparent)]
+    SecretProviderError(#[from] SecretProviderError),
+    /// An error that is raised when the captured request cannot be decoded.
+    #[error("failed to decode the captured request: {0}")]
+    PacketDecodingError(#[from] PacketError),
+    /// An error that is raised when the captured request has a broken attribute (e.g. User-Password).
+    #[error("failed to rebuild the captured request: {0}")]
+    InvalidAttributeError(#[from] AVPError),
+    /// An error that is raised when the target server doesn't respond.
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+}
+
+/// The pacing of the replayed requests.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplayPacing {
+    /// The requests are sent at the same intervals as the capture.
+    Original,
+    /// The intervals of the capture are divided by the given factor (e.g. `2.0` replays the traffic twice as fast).
+    Accelerated(f64),
+    /// The requests are sent one by one, each right after the previous response.
+    AsFastAsPossible,
+}
+
+/// The result of a replayed request.
+#[derive(Debug)]
+pub struct ReplayOutcome {
+    captured_at: DateTime<Utc>,
+    client_addr: SocketAddr,
+    request_code: Code,
+    original_response_code: Option<Code>,
+    result: Result<Code, ReplayError>,
+}
+
+impl ReplayOutcome {
+    pub fn get_captured_at(&self) -> DateTime<Utc> {
+        self.captured_at
+    }
+
+    /// Returns the address of the RADIUS client that has sent the captured request.
+    pub fn get_client_addr(&self) -> SocketAddr {
+        self.client_addr
+    }
+
+    pub fn get_request_code(&self) -> Code {
+        self.request_code
+    }
+
+    /// Returns the code of the response in the capture, or `None` if the capture doesn't contain the response.
+    pub fn get_original_response_code(&self) -> Option<Code> {
+        self.original_response_code
+    }
+
+    /// Returns the code of the response from the target server.
+    pub fn get_result(&self) -> &Result<Code, ReplayError> {
+        &self.result
+    }
+
+    /// Returns whether the target server has responded differently from the capture.
+    /// A request that has no response in the capture is never a mismatch unless it fails.
+    pub fn is_mismatch(&self) -> bool {
+        match (&self.result, self.original_response_code) {
+            (Err(_), _) => true,
+            (Ok(code), Some(original)) => *code != original,
+            (Ok(_), None) => false,
+        }
+    }
+}
+
+type Rewrite = Box<dyn Fn(&mut Packet) + Send + Sync>;
+
+/// An engine that re-sends the captured Access-Requests and Accounting-Requests to a target server.
+///
+/// Each request is rebuilt with the secret of the target server: the authenticators are recomputed and
+/// User-Password is re-encrypted. The responses are compared with the ones in the capture, so it can be used
+/// for the regression testing of a server (or a migration to another server).
+///
+/// NOTE: Message-Authenticator is removed from the replayed requests.
+pub struct Replayer {
+    client: Arc<Client>,
+    target_addr: SocketAddr,
+    target_secret: Vec<u8>,
+    pacing: ReplayPacing,
+    server_ports: Vec<u16>,
+    rewrites: Vec<Rewrite>,
+}
+
+impl Replayer {
+    pub fn new(client: Client, target_addr: SocketAddr, target_secret: &[u8]) -> Self {
+        Replayer {
+            client: Arc::new(client),
+            target_addr,
+            target_secret: target_secret.to_vec(),
+            pacing: ReplayPacing::Original,
+            server_ports: DEFAULT_RADIUS_PORTS.to_vec(),
+            rewrites: vec![],
+        }
+    }
+
+    /// Set the pacing of the requests (default: `ReplayPacing::Original`).
+    pub fn set_pacing(&mut self, pacing: ReplayPacing) {
+        self.pacing = pacing;
+    }
+
+    /// Set the server ports in the capture (default: `DEFAULT_RADIUS_PORTS`).
+    /// The datagrams that are sent to these ports are regarded as requests.
+    pub fn set_server_ports(&mut self, server_ports: &[u16]) {
+        self.server_ports = server_ports.to_vec();
+    }
+
+    /// Add a rewrite of the requests (e.g. replacing NAS-IP-Address).
+    /// Rewrites are applied in order to each rebuilt request, which already has the secret of the target server.
+    pub fn add_rewrite<F: Fn(&mut Packet) + Send + Sync + 'static>(&mut self, rewrite: F) {
+        self.rewrites.push(Box::new(rewrite));
+    }
+
+    /// Rebuild a captured request for the target server and apply the rewrites.
+    pub fn make_replay_packet(&self, captured: &Packet) -> Result<Packet, ReplayError> {
+        let mut packet = Packet::new(captured.get_code(), &self.target_secret);
+        for avp in captured.get_attributes() {
+            match avp.typ {
+                rfc2869::MESSAGE_AUTHENTICATOR_TYPE => {}
+                rfc2865::USER_PASSWORD_TYPE => {
+                    let password = avp.encode_user_password(
+                        captured.get_secret(),
+                        captured.get_authenticator(),
+                    )?;
+                    packet.add(AVP::from_user_password(
+                        rfc2865::USER_PASSWORD_TYPE,
+                        &password,
+                        &self.target_secret,
+                        packet.get_authenticator(),
+                    )?);
+                }
+                _ => packet.add(avp.clone()),
+            }
+        }
+
+        // the CHAP challenge is the request authenticator unless CHAP-Challenge is given
+        if captured.lookup(rfc2865::CHAP_PASSWORD_TYPE).is_some()
+            && captured.lookup(rfc2865::CHAP_CHALLENGE_TYPE).is_none()
+        {
+            rfc2865::add_chap_challenge(&mut packet, captured.get_authenticator());
+        }
+
+        for rewrite in &self.rewrites {
+            rewrite(&mut packet);
+        }
+        Ok(packet)
+    }
+
+    /// Replay the requests in the captured datagrams (e.g. `PcapReader`) and return the outcome of each request.
+    ///
+    /// The captured requests are decoded with the secrets from `secret_provider` (i.e. the secrets of the original NASes).
+    /// The retransmissions in the capture are replayed only once.
+    pub async fn replay<I, U>(
+        &self,
+        datagrams: I,
+        secret_provider: &U,
+    ) -> Result<Vec<ReplayOutcome>, CaptureError>
+    where
+        I: IntoIterator<Item = Result<CapturedDatagram, CaptureError>>,
+        U: SecretProvider,
+    {
+        let exchanges = self.collect_exchanges(datagrams)?;
+
+        let (first_captured_at, started_at) = match exchanges.first() {
+            Some(exchange) => (exchange.request.get_timestamp(), Instant::now()),
+            None => return Ok(vec![]),
+        };
+
+        let mut results = Vec::with_capacity(exchanges.len());
+        for exchange in &exchanges {
+            let packet = match self.decode_request(&exchange.request, secret_provider) {
+                Ok(packet) => packet,
+                Err(e) => {
+                    results.push(PendingResult::Done(Err(e)));
+                    continue;
+                }
+            };
+
+            let factor = match self.pacing {
+                ReplayPacing::Original => 1.0,
+                ReplayPacing::Accelerated(factor) => factor,
+                ReplayPacing::AsFastAsPossible => {
+                    let result = send(&self.client, self.target_addr, packet).await;
+                    results.push(PendingResult::Done(result));
+                    continue;
+                }
+            };
+            let offset = (exchange.request.get_timestamp() - first_captured_at)
+                .to_std()
+                .unwrap_or_default();
+            sleep_until(started_at + Duration::from_secs_f64(offset.as_secs_f64() / factor)).await;
+
+            // the requests are sent concurrently so that a slow response doesn't delay the following requests
+            let client = self.client.clone();
+            let target_addr = self.target_addr;
+            results.push(PendingResult::Sending(tasks::spawn(
+                &tasks::REPLAY_SEND,
+                async move { send(&client, target_addr, packet).await },
+            )));
+        }
+
+        let mut outcomes = Vec::with_capacity(exchanges.len());
+        for (exchange, result) in exchanges.into_iter().zip(results) {
+            let result = match result {
+                PendingResult::Done(result) => result,
+                PendingResult::Sending(handle) => match handle.await {
+                    Ok(result) => result,
+                    Err(e) => std::panic::resume_unwind(e.into_panic()),
+                },
+            };
+            outcomes.push(ReplayOutcome {
+                captured_at: exchange.request.get_timestamp(),
+                client_addr: exchange.request.get_source(),
+                request_code: Code::from(exchange.request.get_payload()[0]),
+                original_response_code: exchange.original_response_code,
+                result,
+            });
+        }
+        Ok(outcomes)
+    }
+
+    fn decode_request<U: SecretProvider>(
+        &self,
+        datagram: &CapturedDatagram,
+        secret_provider: &U,
+    ) -> Result<Packet, ReplayError> {
+        let secret = secret_provider.fetch_secret(datagram.get_source())?;
+        let captured = datagram.decode(&secret)?;
+        self.make_replay_packet(&captured)
+    }
+
+    fn collect_exchanges<I>(&self, datagrams: I) -> Result<Vec<Exchange>, CaptureError>
+    where
+        I: IntoIterator<Item = Result<CapturedDatagram, CaptureError>>,
+    {
+        let mut exchanges: Vec<Exchange> = Vec::new();
+        // (client, server, identifier) => the index of the exchange that is waiting for the response
+        let mut waiting: HashMap<(SocketAddr, SocketAddr, u8), usize> = HashMap::new();
+
+        for datagram in datagrams {
+            let datagram = datagram?;
+            let (code, identifier) = match datagram.get_payload() {
+                [code, identifier, ..] => (Code::from(*code), *identifier),
+                _ => continue,
+            };
+
+            if datagram.is_request(&self.server_ports) {
+                if !matches!(code, Code::AccessRequest | Code::AccountingRequest) {
+                    continue;
+                }
+                let key = (
+                    datagram.get_source(),
+                    datagram.get_destination(),
+                    identifier,
+                );
+                if let Some(&index) = waiting.get(&key) {
+                    let is_retransmission = exchanges[index].request.get_payload().get(4..20)
+                        == datagram.get_payload().get(4..20);
+                    if is_retransmission {
+                        continue;
+                    }
+                }
+                waiting.insert(key, exchanges.len());
+                exchanges.push(Exchange {
+                    request: datagram,
+                    original_response_code: None,
+                });
+            } else {
+                let key = (
+                    datagram.get_destination(),
+                    datagram.get_source(),
+                    identifier,
+                );
+                if let Some(index) = waiting.remove(&key) {
+                    exchanges[index].original_response_code = Some(code);
+                }
+            }
+        }
+        Ok(exchanges)
+    }
+}
+
+enum PendingResult {
+    Done(Result<Code, ReplayError>),
+    Sending(JoinHandle<Result<Code, ReplayError>>),
+}
+
+struct Exchange {
+    request: CapturedDatagram,
+    original_response_code: Option<Code>,
+}
+
+async fn send(
+    client: &Client,
+    target_addr: SocketAddr,
+    packet: Packet,
+) -> Result<Code, ReplayError> {
+    let response = client.send_packet(&target_addr, &packet).await?;
+    Ok(response.get_code())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use chrono::{TimeZone, Utc};
+    use tokio::net::UdpSocket;
+
+    use crate::capture::replay::{ReplayPacing, Replayer};
+    use crate::capture::CapturedDatagram;
+    use crate::client::Client;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::server::{SecretProvider, SecretProviderError};
+
+    struct MySecretProvider {}
+
+    impl SecretProvider for MySecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"nas-secret".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay() {
+        let nas_addr: SocketAddr = "192.0.2.1:40000".parse().unwrap();
+        let server_addr: SocketAddr = "192.0.2.2:1812".parse().unwrap();
+
+        let mut accepted = Packet::new(Code::AccessRequest, b"nas-secret");
+        rfc2865::add_user_name(&mut accepted, "admin");
+        rfc2865::add_user_password(&mut accepted, b"p@ssw0rd").unwrap();
+        let accepted_data = accepted.encode().unwrap();
+        let mut rejected = Packet::new_with_identifier(Code::AccessRequest, b"nas-secret", 1);
+        rfc2865::add_user_name(&mut rejected, "admin");
+        rfc2865::add_user_password(&mut rejected, b"wrong").unwrap();
+        let rejected_data = rejected.encode().unwrap();
+        let accept_data = accepted
+            .make_response_packet(Code::AccessAccept)
+            .encode()
+            .unwrap();
+
+        let at = |millis: i64| {
+            Utc.timestamp_millis_opt(1_600_000_000_000 + millis)
+                .unwrap()
+        };
+        let datagrams = vec![
+            CapturedDatagram::new(at(0), nas_addr, server_addr, accepted_data.clone()),
+            // retransmission
+            CapturedDatagram::new(at(10), nas_addr, server_addr, accepted_data),
+            CapturedDatagram::new(at(20), server_addr, nas_addr, accept_data),
+            CapturedDatagram::new(at(50), nas_addr, server_addr, rejected_data),
+        ];
+
+        let target = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let target_addr = target.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            loop {
+                let (len, addr) = target.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"target-secret").unwrap();
+                assert_eq!(
+                    rfc2865::lookup_nas_identifier(&request).unwrap().unwrap(),
+                    "replay"
+                );
+                let code = match rfc2865::lookup_user_password(&request) {
+                    Some(Ok(password)) if password == b"p@ssw0rd" => Code::AccessAccept,
+                    _ => Code::AccessReject,
+                };
+                let response = request.make_response_packet(code).encode().unwrap();
+                target.send_to(&response, addr).await.unwrap();
+            }
+        });
+
+        let client = Client::new(Some(Duration::from_secs(1)), Some(Duration::from_secs(1)));
+        let mut replayer = Replayer::new(client, target_addr, b"target-secret");
+        replayer.set_pacing(ReplayPacing::Accelerated(10.0));
+        replayer.add_rewrite(|packet| rfc2865::add_nas_identifier(packet, "replay"));
+
+        let outcomes = replayer
+            .replay(datagrams.into_iter().map(Ok), &MySecretProvider {})
+            .await
+            .unwrap();
+        assert_eq!(outcomes.len(), 2);
+
+        assert_eq!(outcomes[0].get_client_addr(), nas_addr);
+        assert_eq!(
+            outcomes[0].get_original_response_code(),
+            Some(Code::AccessAccept)
+        );
+        assert_eq!(
+            *outcomes[0].get_result().as_ref().unwrap(),
+            Code::AccessAccept
+        );
+        assert!(!outcomes[0].is_mismatch());
+
+        assert_eq!(outcomes[1].get_original_response_code(), None);
+        assert_eq!(
+            *outcomes[1].get_result().as_ref().unwrap(),
+            Code::AccessReject
+        );
+        assert!(!outcomes[1].is_mismatch());
+    }
+}
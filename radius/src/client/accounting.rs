@@ -0,0 +1,342 @@
+//! An accounting client (RFC 2866) that assembles the Accounting-Requests of the sessions.
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::client::{Client, ClientError};
+use crate::core::accounting;
+use crate::core::avp::AVP;
+use crate::core::code::Code;
+use crate::core::packet::Packet;
+use crate::core::random;
+use crate::core::rfc2866::{self, AcctStatusType, AcctTerminateCause};
+use crate::core::{deterministic, rfc2869};
+
+/// An accounting session of a user, i.e. the Acct-Session-Id and the attributes that every record of the session has
+/// (e.g. User-Name and NAS-IP-Address).
+///
+/// Acct-Session-Time of the records is the elapsed time since the session has been made.
+#[derive(Debug, Clone)]
+pub struct AccountingSession {
+    session_id: String,
+    attributes: Vec<AVP>,
+    started_at: Instant,
+}
+
+impl AccountingSession {
+    /// A constructor for a session of the Acct-Session-Id.
+    pub fn new(session_id: &str) -> Self {
+        AccountingSession {
+            session_id: session_id.to_owned(),
+            attributes: Vec::new(),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// A constructor for a session of a random Acct-Session-Id (16 hex digits).
+    pub fn with_random_session_id() -> Self {
+        let session_id: u64 = random::rng().gen();
+        AccountingSession::new(&format!("{session_id:016X}"))
+    }
+
+    /// Returns the Acct-Session-Id.
+    pub fn get_session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Add an attribute that every record of the session has.
+    pub fn add_attribute(&mut self, avp: AVP) {
+        self.attributes.push(avp);
+    }
+
+    /// Returns the attributes that every record of the session has.
+    pub fn get_attributes(&self) -> &[AVP] {
+        &self.attributes
+    }
+
+    /// Returns the elapsed time since the session has been made.
+    pub fn get_session_time(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+/// The usage of a session that Interim-Update and Stop report.
+///
+/// The octets are the 64-bit counters, i.e. Acct-Input-Octets/Acct-Output-Octets and the Gigawords (RFC 2869).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountingUsage {
+    input_octets: u64,
+    output_octets: u64,
+    input_packets: u32,
+    output_packets: u32,
+}
+
+impl AccountingUsage {
+    pub fn new() -> Self {
+        AccountingUsage::default()
+    }
+
+    pub fn set_input_octets(&mut self, input_octets: u64) {
+        self.input_octets = input_octets;
+    }
+
+    pub fn get_input_octets(&self) -> u64 {
+        self.input_octets
+    }
+
+    pub fn set_output_octets(&mut self, output_octets: u64) {
+        self.output_octets = output_octets;
+    }
+
+    pub fn get_output_octets(&self) -> u64 {
+        self.output_octets
+    }
+
+    pub fn set_input_packets(&mut self, input_packets: u32) {
+        self.input_packets = input_packets;
+    }
+
+    pub fn get_input_packets(&self) -> u32 {
+        self.input_packets
+    }
+
+    pub fn set_output_packets(&mut self, output_packets: u32) {
+        self.output_packets = output_packets;
+    }
+
+    pub fn get_output_packets(&self) -> u32 {
+        self.output_packets
+    }
+
+    fn add_to(&self, packet: &mut Packet) {
+        accounting::add_acct_input_octets_u64(packet, self.input_octets);
+        accounting::add_acct_output_octets_u64(packet, self.output_octets);
+        rfc2866::add_acct_input_packets(packet, self.input_packets);
+        rfc2866::add_acct_output_packets(packet, self.output_packets);
+    }
+}
+
+/// A client that sends the Start, Interim-Update and Stop records of the sessions to an accounting server.
+///
+/// Each record has Acct-Status-Type, Acct-Session-Id, Acct-Delay-Time (`0`, and it's updated on every retransmission
+/// of the `Client`, see `Client::set_max_retransmissions()`), Event-Timestamp and the attributes of the session; the
+/// Interim-Update and the Stop have Acct-Session-Time and the usage as well.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use radius::client::Client;
+/// use radius::client::accounting::{AccountingClient, AccountingSession, AccountingUsage};
+/// use radius::core::rfc2865;
+/// use radius::core::avp::AVP;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = AccountingClient::new(
+///     Client::new(Some(Duration::from_secs(1)), Some(Duration::from_secs(3))),
+///     "192.0.2.1:1813".parse().unwrap(),
+///     b"secret",
+/// );
+/// let mut session = AccountingSession::with_random_session_id();
+/// session.add_attribute(AVP::from_string(rfc2865::USER_NAME_TYPE, "user"));
+///
+/// client.start(&session).await.unwrap();
+/// client.stop(&session, &AccountingUsage::new(), None).await.unwrap();
+/// # }
+/// ```
+pub struct AccountingClient {
+    client: Client,
+    remote_addr: SocketAddr,
+    secret: Vec<u8>,
+}
+
+impl AccountingClient {
+    /// A constructor for a client of the accounting server.
+    pub fn new(client: Client, remote_addr: SocketAddr, secret: &[u8]) -> Self {
+        AccountingClient {
+            client,
+            remote_addr,
+            secret: secret.to_owned(),
+        }
+    }
+
+    /// Returns the underlying client.
+    pub fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Send the Start record of a session.
+    pub async fn start(&self, session: &AccountingSession) -> Result<Packet, ClientError> {
+        let packet = self.make_request(session, rfc2866::ACCT_STATUS_TYPE_START);
+        self.send(packet).await
+    }
+
+    /// Send an Interim-Update record of a session with the usage so far.
+    pub async fn interim_update(
+        &self,
+        session: &AccountingSession,
+        usage: &AccountingUsage,
+    ) -> Result<Packet, ClientError> {
+        let mut packet = self.make_request(session, rfc2866::ACCT_STATUS_TYPE_INTERIM_UPDATE);
+        add_session_time(&mut packet, session);
+        usage.add_to(&mut packet);
+        self.send(packet).await
+    }
+
+    /// Send the Stop record of a session with the total usage, and with Acct-Terminate-Cause if it's given.
+    pub async fn stop(
+        &self,
+        session: &AccountingSession,
+        usage: &AccountingUsage,
+        terminate_cause: Option<AcctTerminateCause>,
+    ) -> Result<Packet, ClientError> {
+        let mut packet = self.make_request(session, rfc2866::ACCT_STATUS_TYPE_STOP);
+        add_session_time(&mut packet, session);
+        usage.add_to(&mut packet);
+        if let Some(terminate_cause) = terminate_cause {
+            rfc2866::add_acct_terminate_cause(&mut packet, terminate_cause);
+        }
+        self.send(packet).await
+    }
+
+    fn make_request(&self, session: &AccountingSession, status_type: AcctStatusType) -> Packet {
+        let mut packet = Packet::new(Code::AccountingRequest, &self.secret);
+        rfc2866::add_acct_status_type(&mut packet, status_type);
+        rfc2866::add_acct_session_id(&mut packet, &session.session_id);
+        accounting::set_acct_delay_time(&mut packet, 0);
+        rfc2869::add_event_timestamp(&mut packet, &deterministic::now());
+        packet.extend(session.attributes.clone());
+        packet
+    }
+
+    async fn send(&self, packet: Packet) -> Result<Packet, ClientError> {
+        self.client.send_packet(&self.remote_addr, &packet).await
+    }
+}
+
+fn add_session_time(packet: &mut Packet, session: &AccountingSession) {
+    let session_time = u32::try_from(session.get_session_time().as_secs()).unwrap_or(u32::MAX);
+    rfc2866::add_acct_session_time(packet, session_time);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::client::accounting::{AccountingClient, AccountingSession, AccountingUsage};
+    use crate::client::Client;
+    use crate::core::accounting;
+    use crate::core::avp::AVP;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2865, rfc2866, rfc2869};
+
+    #[tokio::test]
+    async fn test_accounting_client() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+
+        let mut client = Client::new(None, Some(Duration::from_millis(200)));
+        client.set_max_retransmissions(1);
+        let client = AccountingClient::new(client, server_addr, b"secret");
+        let mut session = AccountingSession::with_random_session_id();
+        session.add_attribute(AVP::from_string(rfc2865::USER_NAME_TYPE, "user"));
+        assert_eq!(session.get_session_id().len(), 16);
+
+        let handle = tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            let mut requests = Vec::new();
+            let mut dropped = false;
+            while requests.len() < 3 {
+                let (len, from) = server.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                // let the first Stop be timed out to see the retransmission
+                let status_type = rfc2866::lookup_acct_status_type(&request).unwrap().unwrap();
+                if status_type == rfc2866::ACCT_STATUS_TYPE_STOP && !dropped {
+                    dropped = true;
+                    continue;
+                }
+                let response = request.make_response_packet(Code::AccountingResponse);
+                server
+                    .send_to(&response.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+
+        let mut usage = AccountingUsage::new();
+        client.start(&session).await.unwrap();
+        usage.set_input_octets((1 << 32) + 10);
+        usage.set_output_packets(3);
+        client.interim_update(&session, &usage).await.unwrap();
+        let response = client
+            .stop(
+                &session,
+                &usage,
+                Some(rfc2866::ACCT_TERMINATE_CAUSE_USER_REQUEST),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccountingResponse);
+
+        let requests = handle.await.unwrap();
+        for (request, status_type) in requests.iter().zip([
+            rfc2866::ACCT_STATUS_TYPE_START,
+            rfc2866::ACCT_STATUS_TYPE_INTERIM_UPDATE,
+            rfc2866::ACCT_STATUS_TYPE_STOP,
+        ]) {
+            assert_eq!(
+                rfc2866::lookup_acct_status_type(request).unwrap().unwrap(),
+                status_type
+            );
+            assert_eq!(
+                rfc2866::lookup_acct_session_id(request).unwrap().unwrap(),
+                session.get_session_id()
+            );
+            assert_eq!(rfc2865::lookup_user_name(request).unwrap().unwrap(), "user");
+            assert_eq!(
+                rfc2869::lookup_all_event_timestamp(request).unwrap().len(),
+                1
+            );
+        }
+        assert!(rfc2866::lookup_acct_session_time(&requests[0]).is_none());
+        assert_eq!(
+            accounting::lookup_acct_delay_time_or_zero(&requests[1]).unwrap(),
+            0
+        );
+        assert_eq!(
+            accounting::lookup_acct_input_octets_u64(&requests[1])
+                .unwrap()
+                .unwrap(),
+            (1 << 32) + 10
+        );
+        assert_eq!(
+            rfc2866::lookup_acct_output_packets(&requests[1])
+                .unwrap()
+                .unwrap(),
+            3
+        );
+        assert!(rfc2866::lookup_acct_session_time(&requests[2]).is_some());
+        assert_eq!(
+            rfc2866::lookup_acct_terminate_cause(&requests[2])
+                .unwrap()
+                .unwrap(),
+            rfc2866::ACCT_TERMINATE_CAUSE_USER_REQUEST
+        );
+        // the retransmission has the updated Acct-Delay-Time instead of another one
+        assert_eq!(
+            rfc2866::lookup_all_acct_delay_time(&requests[2])
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
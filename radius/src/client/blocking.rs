@@ -0,0 +1,307 @@
+//! A blocking RADIUS client on `std::net::UdpSocket`, for the CLI tools and the non-async code without a tokio runtime.
+//!
+//! This is available with the `blocking` feature.
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::Duration;
+
+use crate::client::{local_bind_addr, ClientError, Deadline, TimeoutDetails};
+use crate::core::code::Code;
+use crate::core::deterministic;
+use crate::core::packet::{Packet, RADIUS_PACKET_HEADER_LENGTH};
+use crate::core::sansio::ClientTransaction;
+
+const MAX_DATAGRAM_SIZE: usize = 65507;
+
+/// A blocking client, i.e. `send_packet()` blocks the current thread until the response has been received.
+///
+/// This has the timeouts, the retransmissions and the Message-Authenticator check of `radius::client::Client`; a new
+/// socket is bound per request.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use radius::client::blocking::Client;
+/// use radius::core::code::Code;
+/// use radius::core::packet::Packet;
+///
+/// let client = Client::new(Some(Duration::from_secs(3)));
+/// let request = Packet::new(Code::AccessRequest, b"secret");
+/// let response = client.send_packet(&"127.0.0.1:1812".parse().unwrap(), &request).unwrap();
+/// println!("{}", response.get_code().string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Client {
+    receive_timeout: Option<Duration>,
+    total_timeout: Option<Duration>,
+    max_retransmissions: u32,
+    add_event_timestamp: bool,
+    require_message_authenticator: bool,
+    local_addr: Option<SocketAddr>,
+    max_datagram_size: usize,
+}
+
+impl Client {
+    /// A constructor for a client.
+    ///
+    /// * `receive_timeout` - A duration of receive timeout of each attempt. If the response is not returned in time, it retransmits the request
+    ///   (see `set_max_retransmissions()`) or the `SocketTimeoutError` occurs. If this value is `None`, it never timed-out.
+    ///
+    /// The overall deadline of a request is set by `set_total_timeout()`.
+    pub fn new(receive_timeout: Option<Duration>) -> Self {
+        Client {
+            receive_timeout,
+            total_timeout: None,
+            max_retransmissions: 0,
+            add_event_timestamp: true,
+            require_message_authenticator: false,
+            local_addr: None,
+            max_datagram_size: MAX_DATAGRAM_SIZE,
+        }
+    }
+
+    /// Set the receive timeout of each attempt (see `new()`).
+    pub fn set_receive_timeout(&mut self, receive_timeout: Option<Duration>) {
+        self.receive_timeout = receive_timeout;
+    }
+
+    /// Set the total timeout of a request, i.e. the deadline of all the attempts (default: `None`, i.e. no deadline).
+    ///
+    /// See `radius::client::Client::set_total_timeout()`.
+    pub fn set_total_timeout(&mut self, total_timeout: Option<Duration>) {
+        self.total_timeout = total_timeout;
+    }
+
+    /// Set the maximum number of retransmissions on socket timeout (default: `0`, i.e. no retransmission).
+    ///
+    /// See `radius::client::Client::set_max_retransmissions()`.
+    pub fn set_max_retransmissions(&mut self, max_retransmissions: u32) {
+        self.max_retransmissions = max_retransmissions;
+    }
+
+    /// Set a flag to specify whether to add Event-Timestamp to the Accounting-Request, CoA-Request and Disconnect-Request
+    /// that don't have that yet (default: `true`).
+    pub fn set_add_event_timestamp(&mut self, add_event_timestamp: bool) {
+        self.add_event_timestamp = add_event_timestamp;
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator against the forgery of the responses (default: `false`).
+    ///
+    /// See `radius::client::Client::set_require_message_authenticator()`.
+    pub fn set_require_message_authenticator(&mut self, require_message_authenticator: bool) {
+        self.require_message_authenticator = require_message_authenticator;
+    }
+
+    /// Set the local address to bind the sockets to (default: `None`, i.e. `0.0.0.0:0` or `[::]:0` by the destination).
+    pub fn set_local_addr(&mut self, local_addr: Option<SocketAddr>) {
+        self.local_addr = local_addr;
+    }
+
+    /// Set the maximum size of the response datagrams (default: `65507`).
+    ///
+    /// See `radius::client::Client::set_max_datagram_size()`.
+    pub fn set_max_datagram_size(&mut self, max_datagram_size: usize) {
+        self.max_datagram_size = max_datagram_size.max(RADIUS_PACKET_HEADER_LENGTH);
+    }
+
+    /// This method sends a packet to the destination, and blocks until the response has been received.
+    ///
+    /// This method retransmits the packet only when the socket timeout has exceeded and the retransmission is enabled by `set_max_retransmissions()`.
+    pub fn send_packet(
+        &self,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+    ) -> Result<Packet, ClientError> {
+        let deadline = Deadline::new(self.total_timeout);
+        let conn = UdpSocket::bind(local_bind_addr(self.local_addr, remote_addr))
+            .map_err(ClientError::FailedUdpSocketBindingError)?;
+        conn.connect(remote_addr).map_err(|e| {
+            ClientError::FailedEstablishingUdpConnectionError(remote_addr.to_string(), e)
+        })?;
+
+        let mut transaction = ClientTransaction::new(
+            request_packet,
+            self.add_event_timestamp,
+            self.max_retransmissions,
+            &deterministic::now(),
+        )
+        .map_err(ClientError::from)?;
+        transaction
+            .set_require_message_authenticator(self.require_message_authenticator)
+            .map_err(ClientError::from)?;
+
+        let mut buf = vec![0; self.max_datagram_size];
+        loop {
+            if let Err(e) = conn.send(transaction.get_request_datagram()) {
+                return Err(ClientError::FailedSendingRadiusPacketError(
+                    remote_addr.to_string(),
+                    e,
+                ));
+            }
+
+            // the datagrams that are not the response (e.g. the forged ones) don't extend the timeout of the attempt
+            let attempt = Deadline::new(deadline.cap(self.receive_timeout));
+            loop {
+                match Self::receive(&conn, &mut buf, attempt.cap(None)) {
+                    Ok(Some(len)) if !transaction.is_response(&buf[..len]) => debug!(
+                        "ignoring a datagram from {} that is not the response",
+                        remote_addr
+                    ),
+                    Ok(Some(len)) => {
+                        return transaction
+                            .on_response(&buf[..len])
+                            .map_err(ClientError::from)
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        return Err(ClientError::FailedReceivingResponseError(
+                            remote_addr.to_string(),
+                            e,
+                        ))
+                    }
+                }
+            }
+
+            let attempts = transaction.get_retransmissions() + 1;
+            if deadline.is_exceeded() {
+                return Err(deadline.exceeded(attempts));
+            }
+            if !transaction
+                .on_timeout(deadline.elapsed())
+                .map_err(ClientError::from)?
+            {
+                return Err(ClientError::SocketTimeoutError(TimeoutDetails::new(
+                    self.receive_timeout.unwrap_or_default(),
+                    attempts,
+                    deadline.elapsed(),
+                )));
+            }
+            debug!(
+                "retransmitting a request to {} ({}/{})",
+                remote_addr,
+                transaction.get_retransmissions(),
+                self.max_retransmissions
+            );
+        }
+    }
+
+    /// Send a Status-Server (RFC 5997) with Message-Authenticator to probe whether the server is alive.
+    ///
+    /// See `radius::client::Client::probe()`.
+    pub fn probe(&self, remote_addr: &SocketAddr, secret: &[u8]) -> Result<Packet, ClientError> {
+        let mut probe = Packet::new(Code::StatusServer, secret);
+        probe.add_message_authenticator();
+        self.send_packet(remote_addr, &probe)
+    }
+
+    /// Receive a datagram in the timeout; `None` is the timeout.
+    fn receive(
+        conn: &UdpSocket,
+        buf: &mut [u8],
+        receive_timeout: Option<Duration>,
+    ) -> io::Result<Option<usize>> {
+        // a zero timeout is an error of `set_read_timeout()`, and it's already timed-out anyway
+        if receive_timeout == Some(Duration::ZERO) {
+            return Ok(None);
+        }
+        conn.set_read_timeout(receive_timeout)?;
+        match conn.recv(buf) {
+            Ok(len) => Ok(Some(len)),
+            // the kind of the timeout depends on the platform
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::UdpSocket;
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::client::blocking::Client;
+    use crate::client::ClientError;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+
+    #[test]
+    fn test_blocking_client() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let handle = thread::spawn(move || {
+            let mut buf = vec![0; 4096];
+            // let the first transmission be timed out to see the retransmission
+            server.recv_from(&mut buf).unwrap();
+            let (len, from) = server.recv_from(&mut buf).unwrap();
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            let response = request.make_response_packet(Code::AccessAccept);
+            server.send_to(&response.encode().unwrap(), from).unwrap();
+            request
+        });
+
+        let mut client = Client::new(Some(Duration::from_millis(200)));
+        client.set_max_retransmissions(1);
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "user");
+        let response = client.send_packet(&server_addr, &request).unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        let request = handle.join().unwrap();
+        assert_eq!(
+            rfc2865::lookup_user_name(&request).unwrap().unwrap(),
+            "user"
+        );
+
+        // a server that never responds
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let mut client = Client::new(Some(Duration::from_millis(100)));
+        client.set_max_retransmissions(2);
+        match client.send_packet(&server.local_addr().unwrap(), &request) {
+            Err(ClientError::SocketTimeoutError(details)) => assert_eq!(details.get_attempts(), 3),
+            result => panic!("unexpected result: {result:?}"),
+        }
+        client.set_total_timeout(Some(Duration::from_millis(150)));
+        match client.send_packet(&server.local_addr().unwrap(), &request) {
+            Err(ClientError::TotalTimeoutError(details)) => assert_eq!(details.get_attempts(), 2),
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_forged_responses() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let server_addr = server.local_addr().unwrap();
+        thread::spawn(move || {
+            let mut buf = vec![0; 4096];
+            let (len, from) = server.recv_from(&mut buf).unwrap();
+            // an Access-Accept of the wrong secret and that of the other identifier precede the genuine Access-Reject
+            let forged = Packet::decode(&buf[..len], b"wrong")
+                .unwrap()
+                .make_response_packet(Code::AccessAccept);
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            let mut stray = request.make_response_packet(Code::AccessAccept);
+            stray.set_identifier(request.get_identifier().wrapping_add(1));
+            let response = request.make_response_packet(Code::AccessReject);
+            for packet in [forged, stray, response] {
+                server.send_to(&packet.encode().unwrap(), from).unwrap();
+            }
+        });
+
+        let client = Client::new(Some(Duration::from_secs(3)));
+        let response = client
+            .send_packet(&server_addr, &Packet::new(Code::AccessRequest, b"secret"))
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+    }
+}
@@ -0,0 +1,209 @@
+//! A builder that composes the timeouts, the retransmissions, the sockets and the Message-Authenticator enforcement
+//! into a client.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use radius::client::Client;
+//!
+//! let client = Client::builder()
+//!     .connect_timeout(Duration::from_secs(1))
+//!     .receive_timeout(Duration::from_secs(3))
+//!     .max_retransmissions(2)
+//!     .total_timeout(Duration::from_secs(10))
+//!     .recv_buffer_size(1 << 20)
+//!     .require_message_authenticator(true)
+//!     .build();
+//! ```
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::client::{Client, RetransmissionHook};
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsHook;
+
+/// A builder of `Client` (see `Client::builder()`).
+///
+/// Every option has the default of the corresponding setter of `Client`; unlike `Client::new()`, the connect timeout
+/// and the receive timeout are `None` (i.e. never timed-out) unless they are set.
+pub struct ClientBuilder {
+    client: Client,
+}
+
+impl ClientBuilder {
+    /// Returns a builder of the default options.
+    pub fn new() -> Self {
+        ClientBuilder {
+            client: Client::new(None, None),
+        }
+    }
+
+    /// Set the connect timeout (see `Client::new()`).
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.client.set_connect_timeout(Some(connect_timeout));
+        self
+    }
+
+    /// Set the receive timeout of each attempt (see `Client::new()`).
+    pub fn receive_timeout(mut self, receive_timeout: Duration) -> Self {
+        self.client.set_receive_timeout(Some(receive_timeout));
+        self
+    }
+
+    /// Set the total timeout of a request, i.e. the deadline of connecting and all the attempts
+    /// (see `Client::set_total_timeout()`).
+    pub fn total_timeout(mut self, total_timeout: Duration) -> Self {
+        self.client.set_total_timeout(Some(total_timeout));
+        self
+    }
+
+    /// Set the maximum number of retransmissions on socket timeout (see `Client::set_max_retransmissions()`).
+    pub fn max_retransmissions(mut self, max_retransmissions: u32) -> Self {
+        self.client.set_max_retransmissions(max_retransmissions);
+        self
+    }
+
+    /// Set a flag to specify whether to add Event-Timestamp to the requests (see `Client::set_add_event_timestamp()`).
+    pub fn add_event_timestamp(mut self, add_event_timestamp: bool) -> Self {
+        self.client.set_add_event_timestamp(add_event_timestamp);
+        self
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator against the forgery of the responses
+    /// (see `Client::set_require_message_authenticator()`).
+    pub fn require_message_authenticator(mut self, require_message_authenticator: bool) -> Self {
+        self.client
+            .set_require_message_authenticator(require_message_authenticator);
+        self
+    }
+
+    /// Set the local address to bind the sockets to (see `Client::set_local_addr()`).
+    pub fn local_addr(mut self, local_addr: SocketAddr) -> Self {
+        self.client.set_local_addr(Some(local_addr));
+        self
+    }
+
+    /// Set the maximum size of the response datagrams (see `Client::set_max_datagram_size()`).
+    pub fn max_datagram_size(mut self, max_datagram_size: usize) -> Self {
+        self.client.set_max_datagram_size(max_datagram_size);
+        self
+    }
+
+    /// Set the size of the receive buffer of the sockets (see `Client::set_recv_buffer_size()`).
+    pub fn recv_buffer_size(mut self, recv_buffer_size: usize) -> Self {
+        self.client.set_recv_buffer_size(Some(recv_buffer_size));
+        self
+    }
+
+    /// Set the size of the send buffer of the sockets (see `Client::set_send_buffer_size()`).
+    pub fn send_buffer_size(mut self, send_buffer_size: usize) -> Self {
+        self.client.set_send_buffer_size(Some(send_buffer_size));
+        self
+    }
+
+    /// Set the maximum number of the idle connected sockets to keep per destination
+    /// (see `Client::set_max_idle_sockets()`).
+    pub fn max_idle_sockets(mut self, max_idle_sockets: usize) -> Self {
+        self.client.set_max_idle_sockets(max_idle_sockets);
+        self
+    }
+
+    /// Set the delay between the attempts to the addresses of a host (see `Client::set_happy_eyeballs_delay()`).
+    pub fn happy_eyeballs_delay(mut self, happy_eyeballs_delay: Duration) -> Self {
+        self.client.set_happy_eyeballs_delay(happy_eyeballs_delay);
+        self
+    }
+
+    /// Set whether to log the requests and the responses (see `Client::set_log_packets()`).
+    pub fn log_packets(mut self, log_packets: bool) -> Self {
+        self.client.set_log_packets(log_packets);
+        self
+    }
+
+    /// Set a hook that rewrites the requests before each retransmission of them (see
+    /// `Client::set_retransmission_hook()`).
+    pub fn retransmission_hook(mut self, retransmission_hook: Arc<dyn RetransmissionHook>) -> Self {
+        self.client
+            .set_retransmission_hook(Some(retransmission_hook));
+        self
+    }
+
+    /// Set the metrics to record the requests of the client into (see `Client::set_metrics()`).
+    #[cfg(feature = "metrics")]
+    pub fn metrics(mut self, metrics: Arc<dyn MetricsHook>) -> Self {
+        self.client.set_metrics(metrics);
+        self
+    }
+
+    /// Returns the client of the options.
+    pub fn build(self) -> Client {
+        self.client
+    }
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        ClientBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::client::{Client, ClientError};
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2869;
+
+    #[tokio::test]
+    async fn test_build() {
+        let client = Client::builder().build();
+        assert_eq!(client.connect_timeout, None);
+        assert_eq!(client.receive_timeout, None);
+        assert_eq!(client.max_retransmissions, 0);
+        assert!(client.add_event_timestamp);
+
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(1))
+            .receive_timeout(Duration::from_millis(100))
+            .max_retransmissions(1)
+            .total_timeout(Duration::from_secs(3))
+            .local_addr("127.0.0.1:0".parse().unwrap())
+            .recv_buffer_size(1 << 16)
+            .send_buffer_size(1 << 16)
+            .require_message_authenticator(true)
+            .log_packets(true)
+            .build();
+        assert_eq!(client.connect_timeout, Some(Duration::from_secs(1)));
+        assert!(client.log_packets);
+        assert_eq!(client.total_timeout, Some(Duration::from_secs(3)));
+        assert_eq!(client.recv_buffer_size, Some(1 << 16));
+
+        // the response without Message-Authenticator is rejected after a retransmission has been answered
+        let responder = tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            server.recv_from(&mut buf).await.unwrap();
+            let (len, from) = server.recv_from(&mut buf).await.unwrap();
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            let mut response = request.make_response_packet(Code::AccessAccept);
+            response.delete(rfc2869::MESSAGE_AUTHENTICATOR_TYPE);
+            server
+                .send_to(&response.encode().unwrap(), from)
+                .await
+                .unwrap();
+        });
+        let err = client
+            .send_packet(&server_addr, &Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClientError::UnauthenticResponseError(_)));
+        responder.await.unwrap();
+    }
+}
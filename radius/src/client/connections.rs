@@ -0,0 +1,376 @@
+//! A managed pool of the connections to the servers, i.e. the warm sockets, the concurrency limit of each server and
+//! the health by the Status-Server keepalive.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::Semaphore;
+
+use crate::client::{Client, ClientError};
+use crate::core::packet::Packet;
+use crate::error::ErrorKind;
+
+const DEFAULT_MAX_FAILURES: u32 = 3;
+
+/// The health of a server of `ConnectionPool`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionHealth {
+    addr: SocketAddr,
+    alive: bool,
+    in_flight: usize,
+    consecutive_failures: u32,
+    last_probed_at: Option<Instant>,
+    last_probe_rtt: Option<Duration>,
+}
+
+impl ConnectionHealth {
+    /// Returns the address of the server.
+    pub fn get_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns whether the server is considered alive, i.e. it hasn't failed `set_max_failures()` times in a row.
+    pub fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    /// Returns the number of the requests that are being sent to the server.
+    pub fn get_in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// Returns the number of the failures (the timeouts and the I/O errors of the requests and the probes) in a row.
+    pub fn get_consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Returns when the server has been probed last, if ever.
+    pub fn get_last_probed_at(&self) -> Option<Instant> {
+        self.last_probed_at
+    }
+
+    /// Returns the round-trip time of the last probe that has been answered, if any.
+    pub fn get_last_probe_rtt(&self) -> Option<Duration> {
+        self.last_probe_rtt
+    }
+}
+
+struct Endpoint {
+    permits: Arc<Semaphore>,
+    health: ConnectionHealth,
+}
+
+/// A pool of the connections to the servers, keyed by the server address.
+///
+/// - The sockets to each server are kept warm (see `Client::set_max_idle_sockets()`), as many as the concurrency limit.
+/// - At most `max_concurrency` requests are sent to each server at a time; the others wait for a slot.
+/// - `run_keepalive()` probes the servers by Status-Server (RFC 5997) periodically, and a server that has failed
+///   `set_max_failures()` times in a row (by the requests or the probes) is marked dead until it answers again.
+///
+/// The health is surfaced by `get_health()`, and `FailoverClient::set_connection_pool()` skips the servers that the
+/// pool considers dead.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use radius::client::Client;
+/// use radius::client::connections::ConnectionPool;
+/// use radius::core::code::Code;
+/// use radius::core::packet::Packet;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let pool = Arc::new(ConnectionPool::new(
+///     Client::new(Some(Duration::from_secs(1)), Some(Duration::from_secs(3))),
+///     16,
+/// ));
+/// let keepalive = pool.clone();
+/// tokio::spawn(async move { keepalive.run_keepalive(b"secret", Duration::from_secs(30)).await });
+///
+/// let response = pool
+///     .send_packet(&"192.0.2.1:1812".parse().unwrap(), &Packet::new(Code::AccessRequest, b"secret"))
+///     .await;
+/// # }
+/// ```
+pub struct ConnectionPool {
+    client: Client,
+    max_concurrency: usize,
+    max_failures: u32,
+    endpoints: Mutex<HashMap<SocketAddr, Endpoint>>,
+}
+
+impl ConnectionPool {
+    /// A constructor for a pool that sends the requests by the client, at most `max_concurrency` (at least 1) requests
+    /// to each server at a time.
+    pub fn new(mut client: Client, max_concurrency: usize) -> Self {
+        let max_concurrency = max_concurrency.max(1);
+        client.set_max_idle_sockets(max_concurrency);
+        ConnectionPool {
+            client,
+            max_concurrency,
+            max_failures: DEFAULT_MAX_FAILURES,
+            endpoints: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the number of the failures in a row that marks a server dead (default: `3`, at least 1).
+    pub fn set_max_failures(&mut self, max_failures: u32) {
+        self.max_failures = max_failures.max(1);
+    }
+
+    /// Add a server to the pool so that the keepalive probes it before any request; the servers of the requests are
+    /// added implicitly.
+    pub fn add_server(&self, addr: SocketAddr) {
+        self.endpoint_permits(addr);
+    }
+
+    /// Returns the health of the server, or `None` if it isn't in the pool.
+    pub fn get_health(&self, addr: &SocketAddr) -> Option<ConnectionHealth> {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .get(addr)
+            .map(|endpoint| endpoint.health)
+    }
+
+    /// Returns the health of all of the servers of the pool, in the order of the addresses.
+    pub fn get_healths(&self) -> Vec<ConnectionHealth> {
+        let mut healths: Vec<_> = self
+            .endpoints
+            .lock()
+            .unwrap()
+            .values()
+            .map(|endpoint| endpoint.health)
+            .collect();
+        healths.sort_by_key(ConnectionHealth::get_addr);
+        healths
+    }
+
+    /// This method sends a packet to the server once a slot of the concurrency limit of that is free.
+    pub async fn send_packet(
+        &self,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+    ) -> Result<Packet, ClientError> {
+        let permits = self.endpoint_permits(*remote_addr);
+        // the semaphore is never closed
+        let _permit = permits.acquire().await.unwrap();
+        self.update(remote_addr, |health| health.in_flight += 1);
+        let result = self.client.send_packet(remote_addr, request_packet).await;
+        self.update(remote_addr, |health| health.in_flight -= 1);
+        match &result {
+            Ok(_) => self.record_success(remote_addr, None),
+            Err(e) if is_server_failure(e) => self.record_failure(remote_addr),
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Send a Status-Server (see `Client::probe()`) with the secret to each server of the pool, and update the health.
+    ///
+    /// This returns the number of the live servers.
+    pub async fn probe_servers(&self, secret: &[u8]) -> usize {
+        let addrs: Vec<_> = self
+            .get_healths()
+            .iter()
+            .map(ConnectionHealth::get_addr)
+            .collect();
+
+        let mut alive = 0;
+        for addr in addrs {
+            let started_at = Instant::now();
+            let result = self.client.probe(&addr, secret).await;
+            self.update(&addr, |health| health.last_probed_at = Some(started_at));
+            match result {
+                Ok(_) => self.record_success(&addr, Some(started_at.elapsed())),
+                Err(e) => {
+                    debug!("server {} doesn't answer the keepalive; {}", addr, e);
+                    self.record_failure(&addr);
+                }
+            }
+            if self.get_health(&addr).is_some_and(|health| health.alive) {
+                alive += 1;
+            }
+        }
+        alive
+    }
+
+    /// Probe the servers repeatedly at the given interval. This never returns.
+    pub async fn run_keepalive(&self, secret: &[u8], interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+            self.probe_servers(secret).await;
+        }
+    }
+
+    fn endpoint_permits(&self, addr: SocketAddr) -> Arc<Semaphore> {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .entry(addr)
+            .or_insert_with(|| Endpoint {
+                permits: Arc::new(Semaphore::new(self.max_concurrency)),
+                health: ConnectionHealth {
+                    addr,
+                    alive: true,
+                    in_flight: 0,
+                    consecutive_failures: 0,
+                    last_probed_at: None,
+                    last_probe_rtt: None,
+                },
+            })
+            .permits
+            .clone()
+    }
+
+    fn update(&self, addr: &SocketAddr, f: impl FnOnce(&mut ConnectionHealth)) {
+        if let Some(endpoint) = self.endpoints.lock().unwrap().get_mut(addr) {
+            f(&mut endpoint.health);
+        }
+    }
+
+    fn record_success(&self, addr: &SocketAddr, probe_rtt: Option<Duration>) {
+        self.update(addr, |health| {
+            if !health.alive {
+                info!("server {} has come back", addr);
+            }
+            health.alive = true;
+            health.consecutive_failures = 0;
+            if probe_rtt.is_some() {
+                health.last_probe_rtt = probe_rtt;
+            }
+        });
+    }
+
+    fn record_failure(&self, addr: &SocketAddr) {
+        let max_failures = self.max_failures;
+        self.update(addr, |health| {
+            health.consecutive_failures += 1;
+            if health.alive && health.consecutive_failures >= max_failures {
+                info!(
+                    "server {} has failed {} times in a row; marked dead",
+                    addr, health.consecutive_failures
+                );
+                health.alive = false;
+            }
+        });
+    }
+}
+
+fn is_server_failure(e: &ClientError) -> bool {
+    match e {
+        // the local failure doesn't change by the server
+        ClientError::FailedUdpSocketBindingError(_) => false,
+        e => matches!(e.get_kind(), ErrorKind::Timeout | ErrorKind::Io),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::client::connections::ConnectionPool;
+    use crate::client::Client;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let server = Arc::new(server);
+        let outstanding = Arc::new(AtomicUsize::new(0));
+        let max_outstanding = Arc::new(AtomicUsize::new(0));
+        {
+            let (outstanding, max_outstanding) = (outstanding.clone(), max_outstanding.clone());
+            tokio::spawn(async move {
+                let mut buf = vec![0; 4096];
+                loop {
+                    let (len, from) = server.recv_from(&mut buf).await.unwrap();
+                    let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                    let now = outstanding.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_outstanding.fetch_max(now, Ordering::SeqCst);
+                    let (server, outstanding) = (server.clone(), outstanding.clone());
+                    tokio::spawn(async move {
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        outstanding.fetch_sub(1, Ordering::SeqCst);
+                        let response = request.make_response_packet(Code::AccessAccept);
+                        server
+                            .send_to(&response.encode().unwrap(), from)
+                            .await
+                            .unwrap();
+                    });
+                }
+            });
+        }
+
+        let pool = Arc::new(ConnectionPool::new(
+            Client::new(None, Some(Duration::from_secs(3))),
+            2,
+        ));
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let pool = pool.clone();
+                tokio::spawn(async move {
+                    pool.send_packet(&server_addr, &Packet::new(Code::AccessRequest, b"secret"))
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.await.unwrap().get_code(), Code::AccessAccept);
+        }
+        assert!(max_outstanding.load(Ordering::SeqCst) <= 2);
+        let health = pool.get_health(&server_addr).unwrap();
+        assert!(health.is_alive());
+        assert_eq!(health.get_in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_keepalive() {
+        let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let silent_addr = silent.local_addr().unwrap();
+        let mut pool = ConnectionPool::new(Client::new(None, Some(Duration::from_millis(200))), 1);
+        pool.set_max_failures(2);
+        pool.add_server(silent_addr);
+        assert!(pool.get_health(&silent_addr).unwrap().is_alive());
+
+        assert_eq!(pool.probe_servers(b"secret").await, 1);
+        assert_eq!(pool.probe_servers(b"secret").await, 0);
+        let health = pool.get_health(&silent_addr).unwrap();
+        assert!(!health.is_alive());
+        assert_eq!(health.get_consecutive_failures(), 2);
+        assert!(health.get_last_probed_at().is_some());
+        assert_eq!(health.get_last_probe_rtt(), None);
+
+        // the server comes back and answers the probes (and the stale ones)
+        let responder = tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            loop {
+                let (len, from) = silent.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                let response = request.make_response_packet(Code::AccessAccept);
+                silent
+                    .send_to(&response.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+            }
+        });
+        assert_eq!(pool.probe_servers(b"secret").await, 1);
+        responder.abort();
+        let health = pool.get_health(&silent_addr).unwrap();
+        assert!(health.is_alive());
+        assert_eq!(health.get_consecutive_failures(), 0);
+        assert!(health.get_last_probe_rtt().is_some());
+    }
+}
@@ -0,0 +1,356 @@
+//! A RADIUS client that fails over among the ordered servers.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::client::connections::ConnectionPool;
+use crate::client::{Client, ClientError};
+use crate::core::packet::Packet;
+use crate::correlation;
+use crate::error::ErrorKind;
+
+const DEFAULT_DEAD_TIME: Duration = Duration::from_secs(30);
+
+/// A client that sends a request to the first live server of an ordered list, and moves to the next one on failures.
+///
+/// A server that doesn't respond (i.e. a timeout) or that is unreachable (e.g. an ICMP port unreachable) is marked dead,
+/// and the request is sent to the next server. The dead servers are skipped until the dead time has elapsed
+/// (see `set_dead_time()`) or a probe has been answered (see `probe_dead_servers()`), as the NASes do.
+/// When all of the servers are dead, they are tried in order anyway.
+///
+/// The retransmissions and the timeouts of each server are those of the `Client`.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use radius::client::Client;
+/// use radius::client::failover::FailoverClient;
+/// use radius::core::code::Code;
+/// use radius::core::packet::Packet;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = FailoverClient::new(
+///     Client::new(Some(Duration::from_secs(1)), Some(Duration::from_secs(3))),
+///     &["192.0.2.1:1812".parse().unwrap(), "192.0.2.2:1812".parse().unwrap()],
+/// );
+/// let response = client.send_packet(&Packet::new(Code::AccessRequest, b"secret")).await;
+/// # }
+/// ```
+pub struct FailoverClient {
+    client: Client,
+    servers: Mutex<Vec<ServerState>>,
+    dead_time: Option<Duration>,
+    connection_pool: Option<Arc<ConnectionPool>>,
+}
+
+/// The state of a server of `FailoverClient`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerState {
+    addr: SocketAddr,
+    dead_until: Option<Instant>,
+    dead: bool,
+}
+
+impl ServerState {
+    /// Returns the address of the server.
+    pub fn get_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns whether the server is marked dead.
+    pub fn is_dead(&self) -> bool {
+        self.dead
+    }
+
+    fn is_available(&self, now: Instant) -> bool {
+        !self.dead || self.dead_until.is_some_and(|dead_until| now >= dead_until)
+    }
+}
+
+impl FailoverClient {
+    /// A constructor for a client of the servers in the order of the preference.
+    pub fn new(client: Client, servers: &[SocketAddr]) -> Self {
+        FailoverClient {
+            client,
+            servers: Mutex::new(
+                servers
+                    .iter()
+                    .map(|addr| ServerState {
+                        addr: *addr,
+                        dead_until: None,
+                        dead: false,
+                    })
+                    .collect(),
+            ),
+            dead_time: Some(DEFAULT_DEAD_TIME),
+            connection_pool: None,
+        }
+    }
+
+    /// Set how long a dead server is skipped (default: `30s`).
+    ///
+    /// If this is `None`, a dead server is skipped until it answers a probe of `probe_dead_servers()`.
+    pub fn set_dead_time(&mut self, dead_time: Option<Duration>) {
+        self.dead_time = dead_time;
+    }
+
+    /// Set a connection pool to send the requests through (default: `None`, i.e. by the `Client` of `new()`).
+    ///
+    /// The servers are added to the pool, so its keepalive (see `ConnectionPool::run_keepalive()`) probes them, and the
+    /// servers that the pool considers dead are skipped like the dead servers of this client.
+    pub fn set_connection_pool(&mut self, connection_pool: Option<Arc<ConnectionPool>>) {
+        if let Some(connection_pool) = &connection_pool {
+            for server in self.servers.lock().unwrap().iter() {
+                connection_pool.add_server(server.addr);
+            }
+        }
+        self.connection_pool = connection_pool;
+    }
+
+    /// Returns the states of the servers, in the order of the preference.
+    pub fn get_server_states(&self) -> Vec<ServerState> {
+        self.servers.lock().unwrap().clone()
+    }
+
+    /// This method sends a packet to the first live server, failing over to the next ones.
+    ///
+    /// If all of the servers have failed, this returns the error of the last one.
+    pub async fn send_packet(&self, request_packet: &Packet) -> Result<Packet, ClientError> {
+        let candidates = {
+            let now = Instant::now();
+            let servers = self.servers.lock().unwrap();
+            let (available, dead): (Vec<&ServerState>, Vec<&ServerState>) =
+                servers.iter().partition(|server| {
+                    server.is_available(now)
+                        && self.connection_pool.as_ref().is_none_or(|pool| {
+                            pool.get_health(&server.addr)
+                                .is_none_or(|health| health.is_alive())
+                        })
+                });
+            available
+                .into_iter()
+                .chain(dead)
+                .map(|server| server.addr)
+                .collect::<Vec<_>>()
+        };
+
+        let mut last_error = None;
+        for addr in candidates {
+            let result = match &self.connection_pool {
+                Some(connection_pool) => connection_pool.send_packet(&addr, request_packet).await,
+                None => self.client.send_packet(&addr, request_packet).await,
+            };
+            match result {
+                Ok(response) => {
+                    self.mark_alive(addr);
+                    return Ok(response);
+                }
+                Err(e) if Self::is_server_failure(&e) => {
+                    info!(
+                        "server {} has failed, failing over to the next one; {}{}",
+                        addr,
+                        e,
+                        correlation::log_suffix()
+                    );
+                    self.mark_dead(addr);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or(ClientError::NoServersError()))
+    }
+
+    /// Send a Status-Server (RFC 5997, see `Client::probe()`) with the secret to each dead server,
+    /// and revive the ones that respond.
+    ///
+    /// This returns the number of the revived servers.
+    pub async fn probe_dead_servers(&self, secret: &[u8]) -> usize {
+        let dead: Vec<_> = self
+            .get_server_states()
+            .into_iter()
+            .filter(ServerState::is_dead)
+            .map(|server| server.addr)
+            .collect();
+
+        let mut revived = 0;
+        for addr in dead {
+            match self.client.probe(&addr, secret).await {
+                Ok(_) => {
+                    info!("server {} has come back", addr);
+                    self.mark_alive(addr);
+                    revived += 1;
+                }
+                Err(e) => debug!("server {} is still dead; {}", addr, e),
+            }
+        }
+        revived
+    }
+
+    /// Probe the dead servers repeatedly at the given interval. This never returns.
+    pub async fn run_probe_loop(&self, secret: &[u8], interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+            self.probe_dead_servers(secret).await;
+        }
+    }
+
+    fn is_server_failure(e: &ClientError) -> bool {
+        match e {
+            // the local failure doesn't change by the server
+            ClientError::FailedUdpSocketBindingError(_) => false,
+            e => matches!(e.get_kind(), ErrorKind::Timeout | ErrorKind::Io),
+        }
+    }
+
+    fn mark_dead(&self, addr: SocketAddr) {
+        let dead_until = self.dead_time.map(|dead_time| Instant::now() + dead_time);
+        for server in self.servers.lock().unwrap().iter_mut() {
+            if server.addr == addr {
+                server.dead = true;
+                server.dead_until = dead_until;
+            }
+        }
+    }
+
+    fn mark_alive(&self, addr: SocketAddr) {
+        for server in self.servers.lock().unwrap().iter_mut() {
+            if server.addr == addr {
+                server.dead = false;
+                server.dead_until = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::client::connections::ConnectionPool;
+    use crate::client::failover::FailoverClient;
+    use crate::client::{Client, ClientError};
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+
+    async fn answer(server: &UdpSocket, request_code: Code, code: Code) {
+        let mut buf = vec![0; 4096];
+        let (request, from) = loop {
+            let (len, from) = server.recv_from(&mut buf).await.unwrap();
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            // skip the requests that have been timed out
+            if request.get_code() == request_code {
+                break (request, from);
+            }
+        };
+        let response = request.make_response_packet(code);
+        server
+            .send_to(&response.encode().unwrap(), from)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failover() {
+        // nobody answers on the first server
+        let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let silent_addr: SocketAddr = silent.local_addr().unwrap();
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+
+        let mut client = FailoverClient::new(
+            Client::new(None, Some(Duration::from_millis(100))),
+            &[silent_addr, server_addr],
+        );
+        client.set_dead_time(None);
+
+        let responder = tokio::spawn(async move {
+            answer(&server, Code::AccessRequest, Code::AccessAccept).await;
+            answer(&server, Code::AccessRequest, Code::AccessAccept).await;
+        });
+        for _ in 0..2 {
+            let response = client
+                .send_packet(&Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+        responder.await.unwrap();
+
+        let states = client.get_server_states();
+        assert!(states[0].is_dead());
+        assert!(!states[1].is_dead());
+
+        // the first server comes back and answers the probe
+        let prober =
+            tokio::spawn(
+                async move { answer(&silent, Code::StatusServer, Code::AccessAccept).await },
+            );
+        assert_eq!(client.probe_dead_servers(b"secret").await, 1);
+        prober.await.unwrap();
+        assert!(!client.get_server_states()[0].is_dead());
+    }
+
+    #[tokio::test]
+    async fn test_connection_pool_health() {
+        let first = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let second = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let (first_addr, second_addr) = (first.local_addr().unwrap(), second.local_addr().unwrap());
+
+        let mut connection_pool =
+            ConnectionPool::new(Client::new(None, Some(Duration::from_millis(100))), 1);
+        connection_pool.set_max_failures(1);
+        let connection_pool = Arc::new(connection_pool);
+        let mut client = FailoverClient::new(
+            Client::new(None, Some(Duration::from_millis(100))),
+            &[first_addr, second_addr],
+        );
+        client.set_connection_pool(Some(connection_pool.clone()));
+
+        // the keepalive finds the first server dead, so the request goes to the second one at once
+        let prober = tokio::spawn(async move {
+            answer(&second, Code::StatusServer, Code::AccessAccept).await;
+            second
+        });
+        assert_eq!(connection_pool.probe_servers(b"secret").await, 1);
+        let second = prober.await.unwrap();
+        assert!(!connection_pool.get_health(&first_addr).unwrap().is_alive());
+
+        let responder =
+            tokio::spawn(
+                async move { answer(&second, Code::AccessRequest, Code::AccessAccept).await },
+            );
+        let response = client
+            .send_packet(&Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        responder.await.unwrap();
+        assert!(!client.get_server_states()[0].is_dead());
+        drop(first);
+    }
+
+    #[tokio::test]
+    async fn test_all_servers_failed() {
+        let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let client = FailoverClient::new(
+            Client::new(None, Some(Duration::from_millis(50))),
+            &[silent.local_addr().unwrap()],
+        );
+        for _ in 0..2 {
+            let err = client
+                .send_packet(&Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap_err();
+            assert!(matches!(err, ClientError::SocketTimeoutError(_)));
+        }
+    }
+}
@@ -0,0 +1,1478 @@
+//! RADIUS client implementation.
+
+#[cfg(feature = "tokio")]
+pub mod accounting;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "tokio")]
+pub mod builder;
+#[cfg(feature = "tokio")]
+pub mod connections;
+#[cfg(feature = "tokio")]
+pub mod failover;
+#[cfg(feature = "tokio")]
+pub mod multiplexed;
+#[cfg(feature = "tokio")]
+pub mod pool;
+
+#[cfg(feature = "tokio")]
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "tokio")]
+use std::future::{poll_fn, Future};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+#[cfg(any(feature = "metrics", feature = "tokio"))]
+use std::sync::Arc;
+#[cfg(feature = "tokio")]
+use std::sync::Mutex;
+#[cfg(feature = "tokio")]
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+#[cfg(feature = "tokio")]
+use tokio::net::UdpSocket;
+#[cfg(feature = "tokio")]
+use tokio::time::timeout;
+
+#[cfg(feature = "tokio")]
+use crate::client::builder::ClientBuilder;
+#[cfg(feature = "tokio")]
+use crate::core::code::Code;
+#[cfg(feature = "tokio")]
+use crate::core::deterministic;
+#[cfg(feature = "tokio")]
+use crate::core::packet::Packet;
+#[cfg(feature = "tokio")]
+use crate::core::packet::RADIUS_PACKET_HEADER_LENGTH;
+#[cfg(feature = "tokio")]
+use crate::core::rfc2865;
+#[cfg(feature = "tokio")]
+use crate::core::sansio::ClientTransaction;
+use crate::core::sansio::ClientTransactionError;
+#[cfg(feature = "tokio")]
+use crate::correlation;
+use crate::error::ErrorKind;
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsHook;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    /// This error is occurred when UDP socket binding has been failed.
+    #[error("failed to bind a UDP socket; {0}")]
+    FailedUdpSocketBindingError(#[source] io::Error),
+
+    /// This error is raised when it failed to establish the connection.
+    #[error("failed to establish a UDP connection to {0}; {1}")]
+    FailedEstablishingUdpConnectionError(String, #[source] io::Error),
+
+    /// This error is raised when encoding RADIUS packet has been failed.
+    #[error("failed to encode a RADIUS request; {0}")]
+    FailedRadiusPacketEncodingError(String),
+
+    /// This error is raised when it fails to send a RADIUS packet.
+    #[error("failed to send a UDP datagram to {0}; {1}")]
+    FailedSendingRadiusPacketError(String, #[source] io::Error),
+
+    /// This error is raised when it fails to receive a RADIUS response.
+    #[error("failed to receive the UDP response from {0}; {1}")]
+    FailedReceivingResponseError(String, #[source] io::Error),
+
+    /// This error is raised when it fails to resolve the host of the target, or the host has no addresses.
+    #[error("failed to resolve {0}; {1}")]
+    FailedResolvingHostError(String, #[source] io::Error),
+
+    /// This error is raised when it fails to decode a RADIUS response packet.
+    #[error("failed to decode a RADIUS response packet; {0}")]
+    FailedDecodingRadiusResponseError(String),
+
+    /// This error is raised when it exceeds the connect timeout (the given duration).
+    /// Connect timeout means it fails to establish a connection in time.
+    #[error("connection timeout ({0:?})")]
+    ConnectionTimeoutError(Duration),
+
+    /// This error is raised when it exceeds the receive timeout of the last attempt.
+    /// Receive timeout means it fails to receive a response from the request target in time.
+    #[error("socket timeout ({0})")]
+    SocketTimeoutError(TimeoutDetails),
+
+    /// This error is raised when it exceeds the total timeout.
+    /// Total timeout means the whole request, i.e. connecting and all the attempts, isn't completed in time.
+    #[error("total timeout ({0})")]
+    TotalTimeoutError(TimeoutDetails),
+
+    /// This error is raised when there are no servers to send the request to (e.g. `FailoverClient` of an empty list).
+    #[error("no servers to send the request to")]
+    NoServersError(),
+
+    /// This error is raised when the response has a bad Message-Authenticator or lacks the required one
+    /// (see `set_require_message_authenticator()`).
+    #[error("unauthentic RADIUS response; {0}")]
+    UnauthenticResponseError(String),
+
+    /// This error is raised when the request has been cancelled before the response (see `Client::send_packet_until()`).
+    #[error("the request has been cancelled")]
+    CancelledError(),
+}
+
+/// The details of a timeout of a request: the exceeded budget, the number of the attempts and the elapsed time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutDetails {
+    budget: Duration,
+    attempts: u32,
+    elapsed: Duration,
+}
+
+impl TimeoutDetails {
+    pub fn new(budget: Duration, attempts: u32, elapsed: Duration) -> Self {
+        TimeoutDetails {
+            budget,
+            attempts,
+            elapsed,
+        }
+    }
+
+    /// Returns the timeout that has been exceeded (i.e. the receive timeout or the total timeout).
+    pub fn get_budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Returns the number of the transmissions of the request, i.e. `1` + the retransmissions.
+    ///
+    /// This is `0` if the total timeout has been exceeded before the request was sent (e.g. while connecting).
+    pub fn get_attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns the time from the beginning of the request to the timeout.
+    pub fn get_elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl fmt::Display for TimeoutDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}; {} attempt(s) in {:?}",
+            self.budget, self.attempts, self.elapsed
+        )
+    }
+}
+
+/// Returns the local address to bind for the remote address: the configured one, or the wildcard address of the same family.
+pub(crate) fn local_bind_addr(
+    local_addr: Option<SocketAddr>,
+    remote_addr: &SocketAddr,
+) -> SocketAddr {
+    local_addr.unwrap_or_else(|| match remote_addr {
+        SocketAddr::V4(_) => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+        SocketAddr::V6(_) => SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
+    })
+}
+
+/// Returns the addresses of the IPv6 and the IPv4 ones alternately from an IPv6 one, in the resolved order of each
+/// family (RFC 8305 section 4).
+#[cfg(feature = "tokio")]
+fn interleave_address_families(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let (v6, v4): (Vec<SocketAddr>, Vec<SocketAddr>) =
+        addrs.into_iter().partition(SocketAddr::is_ipv6);
+    let mut v6 = v6.into_iter();
+    let mut v4 = v4.into_iter();
+    let mut interleaved = Vec::new();
+    loop {
+        match (v6.next(), v4.next()) {
+            (None, None) => return interleaved,
+            (v6, v4) => interleaved.extend(v6.into_iter().chain(v4)),
+        }
+    }
+}
+
+/// Set the sizes of the receive buffer and the send buffer of a socket, if they are configured.
+#[cfg(feature = "tokio")]
+pub(crate) fn set_buffer_sizes(
+    socket: socket2::SockRef<'_>,
+    recv_buffer_size: Option<usize>,
+    send_buffer_size: Option<usize>,
+) -> io::Result<()> {
+    if let Some(recv_buffer_size) = recv_buffer_size {
+        socket.set_recv_buffer_size(recv_buffer_size)?;
+    }
+    if let Some(send_buffer_size) = send_buffer_size {
+        socket.set_send_buffer_size(send_buffer_size)?;
+    }
+    Ok(())
+}
+
+/// A hook that rewrites a request before each retransmission of that (see `Client::set_retransmission_hook()`).
+///
+/// The client has already done the rewriting of the RFCs when this is called: Acct-Delay-Time of an
+/// Accounting-Request has been updated by the time since the request has been started, and Event-Timestamp of a
+/// CoA-Request or a Disconnect-Request has been refreshed (see `ClientTransaction::on_timeout()`). The client keeps
+/// the identifier and recomputes the Request Authenticator after this.
+#[cfg(feature = "tokio")]
+pub trait RetransmissionHook: Send + Sync {
+    /// This method is called with the request, the number of the retransmissions including this one, and the time
+    /// since the request has been started.
+    fn on_retransmission(
+        &self,
+        request_packet: &mut Packet,
+        retransmissions: u32,
+        elapsed: Duration,
+    );
+}
+
+/// The total timeout of a request; the timeout of each step is capped by the rest of it.
+pub(crate) struct Deadline {
+    started_at: Instant,
+    total_timeout: Option<Duration>,
+}
+
+impl Deadline {
+    pub(crate) fn new(total_timeout: Option<Duration>) -> Self {
+        Deadline {
+            started_at: Instant::now(),
+            total_timeout,
+        }
+    }
+
+    /// Returns the timeout of a step, which doesn't exceed the rest of the total timeout.
+    pub(crate) fn cap(&self, timeout: Option<Duration>) -> Option<Duration> {
+        let rest = self
+            .total_timeout
+            .map(|total_timeout| total_timeout.saturating_sub(self.elapsed()));
+        match (timeout, rest) {
+            (Some(timeout), Some(rest)) => Some(timeout.min(rest)),
+            (timeout, rest) => timeout.or(rest),
+        }
+    }
+
+    pub(crate) fn is_exceeded(&self) -> bool {
+        self.total_timeout
+            .is_some_and(|total_timeout| self.elapsed() >= total_timeout)
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Returns the `TotalTimeoutError` after the given number of the attempts.
+    pub(crate) fn exceeded(&self, attempts: u32) -> ClientError {
+        ClientError::TotalTimeoutError(TimeoutDetails::new(
+            self.total_timeout.unwrap_or_default(),
+            attempts,
+            self.elapsed(),
+        ))
+    }
+}
+
+impl ClientError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            ClientError::FailedUdpSocketBindingError(_)
+            | ClientError::FailedEstablishingUdpConnectionError(_, _)
+            | ClientError::FailedSendingRadiusPacketError(_, _)
+            | ClientError::FailedReceivingResponseError(_, _)
+            | ClientError::FailedResolvingHostError(_, _) => ErrorKind::Io,
+            ClientError::FailedRadiusPacketEncodingError(_) => ErrorKind::Encoding,
+            ClientError::FailedDecodingRadiusResponseError(_) => ErrorKind::Malformed,
+            ClientError::ConnectionTimeoutError(_)
+            | ClientError::SocketTimeoutError(_)
+            | ClientError::TotalTimeoutError(_) => ErrorKind::Timeout,
+            ClientError::NoServersError() => ErrorKind::Configuration,
+            ClientError::UnauthenticResponseError(_) => ErrorKind::Authenticity,
+            ClientError::CancelledError() => ErrorKind::Other,
+        }
+    }
+
+    /// Returns the I/O error of the socket or the resolver that has caused the error, if any; e.g. its `kind()` tells
+    /// `ConnectionRefused` (nothing listens on the port of the server) from `HostUnreachable`.
+    pub fn get_io_error(&self) -> Option<&io::Error> {
+        match self {
+            ClientError::FailedUdpSocketBindingError(e)
+            | ClientError::FailedEstablishingUdpConnectionError(_, e)
+            | ClientError::FailedSendingRadiusPacketError(_, e)
+            | ClientError::FailedReceivingResponseError(_, e)
+            | ClientError::FailedResolvingHostError(_, e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the same request may succeed later, or on another server.
+    ///
+    /// The I/O errors are classified by their `io::ErrorKind`: the local ones that the retries don't change (e.g.
+    /// `PermissionDenied`, or `AddrNotAvailable` of a wrong local address) are not retryable, and the others (e.g.
+    /// `ConnectionRefused` or `HostUnreachable`) are. The other errors follow `ErrorKind::is_retryable()`.
+    pub fn is_retryable(&self) -> bool {
+        match self.get_io_error() {
+            Some(e) => !matches!(
+                e.kind(),
+                io::ErrorKind::PermissionDenied
+                    | io::ErrorKind::AddrInUse
+                    | io::ErrorKind::AddrNotAvailable
+                    | io::ErrorKind::InvalidInput
+                    | io::ErrorKind::Unsupported
+            ),
+            None => self.get_kind().is_retryable(),
+        }
+    }
+}
+
+impl From<ClientTransactionError> for ClientError {
+    fn from(e: ClientTransactionError) -> Self {
+        match e {
+            ClientTransactionError::EncodingError(e) => {
+                ClientError::FailedRadiusPacketEncodingError(e)
+            }
+            ClientTransactionError::DecodingError(e) => {
+                ClientError::FailedDecodingRadiusResponseError(e)
+            }
+            ClientTransactionError::UnauthenticResponseError(e) => {
+                ClientError::UnauthenticResponseError(e)
+            }
+        }
+    }
+}
+
+/// A basic implementation of the RADIUS client.
+///
+/// This is available with the `tokio` feature (enabled by default); see `radius::runtime` for the other runtimes.
+#[cfg(feature = "tokio")]
+pub struct Client {
+    connect_timeout: Option<Duration>,
+    receive_timeout: Option<Duration>,
+    total_timeout: Option<Duration>,
+    max_retransmissions: u32,
+    add_event_timestamp: bool,
+    require_message_authenticator: bool,
+    local_addr: Option<SocketAddr>,
+    max_datagram_size: usize,
+    recv_buffer_size: Option<usize>,
+    send_buffer_size: Option<usize>,
+    max_idle_sockets: usize,
+    idle_sockets: Mutex<HashMap<SocketAddr, Vec<UdpSocket>>>,
+    happy_eyeballs_delay: Duration,
+    resolved_hosts: Mutex<HashMap<String, SocketAddr>>,
+    log_packets: bool,
+    retransmission_hook: Option<Arc<dyn RetransmissionHook>>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<dyn MetricsHook>>,
+}
+
+#[cfg(feature = "tokio")]
+impl Client {
+    const MAX_DATAGRAM_SIZE: usize = 65507;
+    /// The Resolution Delay of RFC 8305 section 5.
+    const HAPPY_EYEBALLS_DELAY: Duration = Duration::from_millis(250);
+
+    /// A constructor for a client.
+    ///
+    /// # Arguments
+    ///
+    /// * `connect_timeout` - A duration of connect timeout. If the connection is not established in time, the `ConnectionTimeoutError` occurs.
+    ///   If this value is `None`, it never timed-out.
+    /// * `receive_timeout` - A duration of receive timeout of each attempt. If the response is not returned in time, it retransmits the request
+    ///   (see `set_max_retransmissions()`) or the `SocketTimeoutError` occurs. If this value is `None`, it never timed-out.
+    ///
+    /// The overall deadline of a request is set by `set_total_timeout()`; see `builder()` for all of the options.
+    pub fn new(connect_timeout: Option<Duration>, receive_timeout: Option<Duration>) -> Self {
+        Client {
+            connect_timeout,
+            receive_timeout,
+            total_timeout: None,
+            max_retransmissions: 0,
+            add_event_timestamp: true,
+            require_message_authenticator: false,
+            local_addr: None,
+            max_datagram_size: Self::MAX_DATAGRAM_SIZE,
+            recv_buffer_size: None,
+            send_buffer_size: None,
+            max_idle_sockets: 0,
+            idle_sockets: Mutex::new(HashMap::new()),
+            happy_eyeballs_delay: Self::HAPPY_EYEBALLS_DELAY,
+            resolved_hosts: Mutex::new(HashMap::new()),
+            log_packets: false,
+            retransmission_hook: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+        }
+    }
+
+    /// Returns a builder that composes the timeouts, the retransmissions, the sockets and the Message-Authenticator
+    /// enforcement into a client.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Set the connect timeout (see `new()`).
+    pub fn set_connect_timeout(&mut self, connect_timeout: Option<Duration>) {
+        self.connect_timeout = connect_timeout;
+    }
+
+    /// Set the receive timeout of each attempt (see `new()`).
+    pub fn set_receive_timeout(&mut self, receive_timeout: Option<Duration>) {
+        self.receive_timeout = receive_timeout;
+    }
+
+    /// Set the total timeout of a request, i.e. the deadline of connecting and all the attempts (default: `None`, i.e. no deadline).
+    ///
+    /// Each step waits at most for the rest of the total timeout, so the request never exceeds it even while it is retransmitting;
+    /// the `TotalTimeoutError` tells the number of the attempts and the elapsed time.
+    pub fn set_total_timeout(&mut self, total_timeout: Option<Duration>) {
+        self.total_timeout = total_timeout;
+    }
+
+    /// Set the maximum number of retransmissions on socket timeout (default: `0`, i.e. no retransmission).
+    ///
+    /// The receive timeout applies to each attempt individually.
+    /// When an `Accounting-Request` is retransmitted, it keeps the identifier, and its `Acct-Delay-Time` is updated by
+    /// the elapsed time since `send_packet()` has been called (i.e. including the time to open the socket) and the
+    /// Request Authenticator is recomputed accordingly; see `set_retransmission_hook()` for the other rewriting.
+    pub fn set_max_retransmissions(&mut self, max_retransmissions: u32) {
+        self.max_retransmissions = max_retransmissions;
+    }
+
+    /// Set a flag to specify whether to add Event-Timestamp to the Accounting-Request, CoA-Request and Disconnect-Request
+    /// that don't have that yet (default: `true`).
+    pub fn set_add_event_timestamp(&mut self, add_event_timestamp: bool) {
+        self.add_event_timestamp = add_event_timestamp;
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator against the forgery of the responses
+    /// (CVE-2024-3596, a.k.a. Blast-RADIUS; default: `false`).
+    ///
+    /// If this is `true`, every request has Message-Authenticator as the first attribute, and Access-Accept,
+    /// Access-Reject and Access-Challenge without that are the `UnauthenticResponseError`.
+    /// A bad Message-Authenticator of any response is the `UnauthenticResponseError` regardless of this.
+    pub fn set_require_message_authenticator(&mut self, require_message_authenticator: bool) {
+        self.require_message_authenticator = require_message_authenticator;
+    }
+
+    /// Set the local address to bind the sockets to (default: `None`, i.e. `0.0.0.0:0` or `[::]:0` by the destination).
+    ///
+    /// This is for the servers that check the source address of the NAS; the scope id of an IPv6 address selects the interface
+    /// of a link-local address. With a non-zero port, the concurrent requests fail to bind the same port,
+    /// so send the requests one by one (with `set_max_idle_sockets()` the socket is kept) or use `MultiplexedClient`.
+    pub fn set_local_addr(&mut self, local_addr: Option<SocketAddr>) {
+        self.local_addr = local_addr;
+    }
+
+    /// Set the maximum size of the response datagrams (default: `65507`, i.e. the maximum UDP payload).
+    ///
+    /// A request allocates a receive buffer of this size once, and reuses that across the retransmissions; a longer
+    /// datagram is truncated, so it fails decoding. `4096` is enough for the servers that don't send jumbo responses
+    /// (RFC 2865 limits a packet to that).
+    pub fn set_max_datagram_size(&mut self, max_datagram_size: usize) {
+        self.max_datagram_size = max_datagram_size.max(RADIUS_PACKET_HEADER_LENGTH);
+    }
+
+    /// Set the size of the receive buffer (`SO_RCVBUF`) of the sockets (default: `None`, i.e. the default of the OS).
+    pub fn set_recv_buffer_size(&mut self, recv_buffer_size: Option<usize>) {
+        self.recv_buffer_size = recv_buffer_size;
+    }
+
+    /// Set the size of the send buffer (`SO_SNDBUF`) of the sockets (default: `None`, i.e. the default of the OS).
+    pub fn set_send_buffer_size(&mut self, send_buffer_size: Option<usize>) {
+        self.send_buffer_size = send_buffer_size;
+    }
+
+    /// Set the maximum number of the idle connected sockets to keep per destination for reuse (default: `0`, i.e. a new socket per request).
+    ///
+    /// A socket goes back to the pool after a request has been answered; the socket of a request that has failed (e.g. by a timeout
+    /// or an ICMP error) is closed, so the next request binds a new one. The datagrams that are left on a socket
+    /// (e.g. the late responses to the retransmissions) are discarded before it is reused.
+    pub fn set_max_idle_sockets(&mut self, max_idle_sockets: usize) {
+        self.max_idle_sockets = max_idle_sockets;
+        if max_idle_sockets == 0 {
+            self.idle_sockets.lock().unwrap().clear();
+        }
+    }
+
+    /// Set the delay between the attempts to the addresses of a host by `send_packet_to_host()` (default: `250ms`).
+    pub fn set_happy_eyeballs_delay(&mut self, happy_eyeballs_delay: Duration) {
+        self.happy_eyeballs_delay = happy_eyeballs_delay;
+    }
+
+    /// Set the metrics to record the requests of the client into, e.g. the Prometheus `Metrics` or an own `MetricsHook`
+    /// (see `radius::metrics`).
+    ///
+    /// This is available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn set_metrics(&mut self, metrics: Arc<dyn MetricsHook>) {
+        self.metrics = Some(metrics);
+    }
+
+    /// Set whether to log the requests and the responses at the debug level (default: `false`).
+    ///
+    /// The packets are logged by `Packet::redacted_debug()`, i.e. the attributes by the names with the passwords and
+    /// the keys masked, so this is safe to enable in production.
+    pub fn set_log_packets(&mut self, log_packets: bool) {
+        self.log_packets = log_packets;
+    }
+
+    /// Set a hook that rewrites the requests before each retransmission of them (default: `None`), e.g. to refresh an
+    /// attribute of the application along with Acct-Delay-Time.
+    pub fn set_retransmission_hook(
+        &mut self,
+        retransmission_hook: Option<Arc<dyn RetransmissionHook>>,
+    ) {
+        self.retransmission_hook = retransmission_hook;
+    }
+
+    /// This method sends a packet to the destination.
+    ///
+    /// This method retransmits the packet only when the socket timeout has exceeded and the retransmission is enabled by `set_max_retransmissions()`.
+    pub async fn send_packet(
+        &self,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+    ) -> Result<Packet, ClientError> {
+        if self.log_packets {
+            debug!(
+                "sending {} to {}",
+                request_packet.redacted_debug(),
+                remote_addr
+            );
+        }
+        let result = self.send_packet_observed(remote_addr, request_packet).await;
+        if let (true, Ok(response)) = (self.log_packets, &result) {
+            debug!(
+                "received {} from {}",
+                response.redacted_debug(),
+                remote_addr
+            );
+        }
+        result
+    }
+
+    /// This method sends a packet to the destination like `send_packet()`, and aborts that when `cancel` has been
+    /// resolved before the response (e.g. the shutdown of the service) with `CancelledError`.
+    ///
+    /// Dropping the future of `send_packet()` aborts the request as well; either way the socket of the aborted request
+    /// is closed rather than kept for reuse, so its late response never reaches another request.
+    pub async fn send_packet_until(
+        &self,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+        cancel: impl Future,
+    ) -> Result<Packet, ClientError> {
+        tokio::select! {
+            result = self.send_packet(remote_addr, request_packet) => result,
+            _ = cancel => Err(ClientError::CancelledError()),
+        }
+    }
+
+    async fn send_packet_observed(
+        &self,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+    ) -> Result<Packet, ClientError> {
+        correlation::request(remote_addr, request_packet.get_code(), async {
+            #[cfg(feature = "metrics")]
+            if let Some(metrics) = &self.metrics {
+                metrics.on_client_request(request_packet.get_code());
+                let started_at = Instant::now();
+                let result = self.exchange(remote_addr, request_packet).await;
+                crate::metrics::observe_client_result(
+                    metrics.as_ref(),
+                    &result,
+                    Packet::get_code,
+                    started_at.elapsed(),
+                );
+                return result;
+            }
+            self.exchange(remote_addr, request_packet).await
+        })
+        .await
+    }
+
+    /// This method sends a packet to the target of a host and a port (e.g. `radius.example.com:1812` or
+    /// `[2001:db8::1]:1812`), which is resolved asynchronously.
+    ///
+    /// The addresses of the host are attempted by Happy Eyeballs (RFC 8305): the IPv6 and the IPv4 ones alternately
+    /// from an IPv6 one, and the next one starts after `set_happy_eyeballs_delay()` (or when the previous one has
+    /// failed) while the previous ones are still waiting for the response. The address of the first response is cached
+    /// for the host, and the following requests are sent to that only; if that fails, the cache is cleared and
+    /// the next request resolves the host again.
+    ///
+    /// Since the same request can be sent to the multiple addresses, the server behind them may receive that more than
+    /// once (e.g. an Accounting-Request is recorded twice); use `send_packet()` for a resolved address if that matters.
+    pub async fn send_packet_to_host(
+        &self,
+        target: &str,
+        request_packet: &Packet,
+    ) -> Result<Packet, ClientError> {
+        if let Ok(remote_addr) = target.parse::<SocketAddr>() {
+            return self.send_packet(&remote_addr, request_packet).await;
+        }
+
+        let cached = self.resolved_hosts.lock().unwrap().get(target).copied();
+        if let Some(remote_addr) = cached {
+            let result = self.send_packet(&remote_addr, request_packet).await;
+            if result.is_err() {
+                self.resolved_hosts.lock().unwrap().remove(target);
+            }
+            return result;
+        }
+
+        let addrs: Vec<SocketAddr> = tokio::net::lookup_host(target)
+            .await
+            .map_err(|e| ClientError::FailedResolvingHostError(target.to_owned(), e))?
+            .collect();
+        if addrs.is_empty() {
+            return Err(ClientError::FailedResolvingHostError(
+                target.to_owned(),
+                io::Error::new(io::ErrorKind::NotFound, "no addresses"),
+            ));
+        }
+
+        let (remote_addr, response) = self
+            .race(&interleave_address_families(addrs), request_packet)
+            .await?;
+        self.resolved_hosts
+            .lock()
+            .unwrap()
+            .insert(target.to_owned(), remote_addr);
+        Ok(response)
+    }
+
+    /// Send the request to the addresses in order, starting the next one after the delay or the failure of the
+    /// previous ones, and returns the first response.
+    async fn race(
+        &self,
+        addrs: &[SocketAddr],
+        request_packet: &Packet,
+    ) -> Result<(SocketAddr, Packet), ClientError> {
+        type Attempt<'a> = Pin<Box<dyn Future<Output = Result<Packet, ClientError>> + Send + 'a>>;
+
+        let mut addrs = addrs.iter();
+        let mut attempts: Vec<(SocketAddr, Attempt<'_>)> = Vec::new();
+        let mut delay = Box::pin(tokio::time::sleep(Duration::ZERO));
+        loop {
+            let event = poll_fn(|cx| {
+                if addrs.len() > 0 && delay.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(None);
+                }
+                for (i, (_, attempt)) in attempts.iter_mut().enumerate() {
+                    if let Poll::Ready(result) = attempt.as_mut().poll(cx) {
+                        return Poll::Ready(Some((i, result)));
+                    }
+                }
+                Poll::Pending
+            })
+            .await;
+
+            match event {
+                None => {
+                    // addrs is not empty
+                    let remote_addr = *addrs.next().unwrap();
+                    attempts.push((
+                        remote_addr,
+                        Box::pin(
+                            async move { self.send_packet(&remote_addr, request_packet).await },
+                        ),
+                    ));
+                    delay
+                        .as_mut()
+                        .reset(tokio::time::Instant::now() + self.happy_eyeballs_delay);
+                }
+                Some((i, Ok(response))) => return Ok((attempts[i].0, response)),
+                Some((i, Err(e))) => {
+                    drop(attempts.remove(i));
+                    if attempts.is_empty() && addrs.len() == 0 {
+                        return Err(e);
+                    }
+                    // the next address starts without waiting for the delay
+                    delay.as_mut().reset(tokio::time::Instant::now());
+                }
+            }
+        }
+    }
+
+    /// This method sends a packet that may exceed 4096 bytes to a server that supports the fragmentation of RFC 7499
+    /// (see `radius::core::rfc7499`).
+    ///
+    /// An oversized request is sent by the chunks, each of which is answered by an Access-Challenge of
+    /// More-Data-Request; a fragmented response is reassembled by asking for its chunks. If the server answers a chunk
+    /// otherwise (e.g. by Access-Reject, since it doesn't support the fragmentation), that is the response.
+    ///
+    /// This is available with the `rfc7499` feature.
+    #[cfg(feature = "rfc7499")]
+    pub async fn send_fragmented_packet(
+        &self,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+    ) -> Result<Packet, ClientError> {
+        use crate::core::packet::MAX_PACKET_LENGTH;
+        use crate::core::rfc7499::{self, RequestFragmenter, ResponseReassembler, ResponseStep};
+
+        let mut fragmenter = RequestFragmenter::new(request_packet, MAX_PACKET_LENGTH)
+            .map_err(|e| ClientError::FailedRadiusPacketEncodingError(e.to_string()))?;
+        let mut state = None;
+        let mut response = loop {
+            // there is a chunk at least
+            let chunk = fragmenter.next_chunk(state.as_deref()).unwrap();
+            let response = self.send_packet(remote_addr, &chunk).await?;
+            if fragmenter.get_remaining_chunks() == 0 {
+                break response;
+            }
+            match rfc7499::lookup_more_data_request(&response) {
+                Some(next_state) => state = Some(next_state),
+                None => return Ok(response),
+            }
+        };
+
+        let mut reassembler = ResponseReassembler::new(request_packet);
+        loop {
+            match reassembler
+                .on_response(&response)
+                .map_err(|e| ClientError::FailedDecodingRadiusResponseError(e.to_string()))?
+            {
+                ResponseStep::Request(next) => {
+                    response = self.send_packet(remote_addr, &next).await?
+                }
+                ResponseStep::Complete(response) => return Ok(response),
+            }
+        }
+    }
+
+    /// Send a Status-Server (RFC 5997) with Message-Authenticator to probe whether the server is alive.
+    ///
+    /// The server answers by Access-Accept (on the authentication port) or Accounting-Response (on the accounting port).
+    pub async fn probe(
+        &self,
+        remote_addr: &SocketAddr,
+        secret: &[u8],
+    ) -> Result<Packet, ClientError> {
+        let mut probe = Packet::new(Code::StatusServer, secret);
+        probe.add_message_authenticator();
+        self.send_packet(remote_addr, &probe).await
+    }
+
+    /// Send an Access-Request and answer its Access-Challenges until the server returns the other response
+    /// (i.e. Access-Accept or Access-Reject), e.g. for the OTP or the EAP conversations.
+    ///
+    /// `challenge_handler` receives each Access-Challenge and returns the next Access-Request (e.g. of the OTP that
+    /// the user has entered for the Reply-Message, or of the next EAP-Message); the States of the challenge are set to
+    /// that, replacing its own ones. If the handler returns `None`, the conversation is aborted and this returns
+    /// that Access-Challenge.
+    ///
+    /// ```no_run
+    /// # use std::net::SocketAddr;
+    /// # use radius::client::{Client, ClientError};
+    /// use radius::core::code::Code;
+    /// use radius::core::packet::Packet;
+    /// use radius::core::rfc2865;
+    ///
+    /// # async fn f(client: &Client, remote_addr: &SocketAddr) -> Result<(), ClientError> {
+    /// let mut request = Packet::new(Code::AccessRequest, b"secret");
+    /// rfc2865::add_user_name(&mut request, "bob");
+    /// rfc2865::add_user_password(&mut request, b"p@ssw0rd").unwrap();
+    /// let response = client
+    ///     .send_with_challenge_handler(remote_addr, &request, |challenge| async move {
+    ///         let mut next = Packet::new(Code::AccessRequest, challenge.get_secret());
+    ///         rfc2865::add_user_name(&mut next, "bob");
+    ///         rfc2865::add_user_password(&mut next, b"123456").ok()?;
+    ///         Some(next)
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_with_challenge_handler<F, Fut>(
+        &self,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+        mut challenge_handler: F,
+    ) -> Result<Packet, ClientError>
+    where
+        F: FnMut(Packet) -> Fut,
+        Fut: Future<Output = Option<Packet>>,
+    {
+        let mut response = self.send_packet(remote_addr, request_packet).await?;
+        while response.get_code() == Code::AccessChallenge {
+            let states = rfc2865::lookup_all_state(&response);
+            let mut next = match challenge_handler(response.clone()).await {
+                Some(next) => next,
+                None => return Ok(response),
+            };
+            rfc2865::delete_state(&mut next);
+            for state in &states {
+                rfc2865::add_state(&mut next, state);
+            }
+            response = self.send_packet(remote_addr, &next).await?;
+        }
+        Ok(response)
+    }
+
+    async fn exchange(
+        &self,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+    ) -> Result<Packet, ClientError> {
+        let deadline = Deadline::new(self.total_timeout);
+        let conn = match self.take_idle_socket(remote_addr) {
+            Some(conn) => conn,
+            None => self.open(remote_addr, &deadline).await?,
+        };
+
+        let response = self
+            .transact(&conn, remote_addr, request_packet, &deadline)
+            .await;
+        if response.is_ok() {
+            self.put_idle_socket(remote_addr, conn);
+        }
+        response
+    }
+
+    async fn open(
+        &self,
+        remote_addr: &SocketAddr,
+        deadline: &Deadline,
+    ) -> Result<UdpSocket, ClientError> {
+        let local_addr = local_bind_addr(self.local_addr, remote_addr);
+        let conn = match UdpSocket::bind(local_addr).await {
+            Ok(conn) => conn,
+            Err(e) => return Err(ClientError::FailedUdpSocketBindingError(e)),
+        };
+        set_buffer_sizes(
+            socket2::SockRef::from(&conn),
+            self.recv_buffer_size,
+            self.send_buffer_size,
+        )
+        .map_err(ClientError::FailedUdpSocketBindingError)?;
+
+        match deadline.cap(self.connect_timeout) {
+            Some(connect_timeout) => {
+                match timeout(connect_timeout, self.connect(&conn, remote_addr)).await {
+                    Ok(conn_establish_res) => conn_establish_res,
+                    Err(_) if deadline.is_exceeded() => Err(deadline.exceeded(0)),
+                    Err(_) => Err(ClientError::ConnectionTimeoutError(connect_timeout)),
+                }
+            }
+            None => self.connect(&conn, remote_addr).await,
+        }?;
+        Ok(conn)
+    }
+
+    fn take_idle_socket(&self, remote_addr: &SocketAddr) -> Option<UdpSocket> {
+        if self.max_idle_sockets == 0 {
+            return None;
+        }
+        let mut idle_sockets = self.idle_sockets.lock().unwrap();
+        let conns = idle_sockets.get_mut(remote_addr)?;
+        let mut buf = vec![0; self.max_datagram_size];
+        while let Some(conn) = conns.pop() {
+            loop {
+                match conn.try_recv(&mut buf) {
+                    Ok(_) => debug!("discarding a stale datagram from {}", remote_addr),
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Some(conn),
+                    Err(e) => {
+                        debug!("closing an idle socket to {}; {}", remote_addr, e);
+                        break;
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn put_idle_socket(&self, remote_addr: &SocketAddr, conn: UdpSocket) {
+        if self.max_idle_sockets == 0 {
+            return;
+        }
+        let mut idle_sockets = self.idle_sockets.lock().unwrap();
+        let conns = idle_sockets.entry(*remote_addr).or_default();
+        if conns.len() < self.max_idle_sockets {
+            conns.push(conn);
+        }
+    }
+
+    async fn transact(
+        &self,
+        conn: &UdpSocket,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+        deadline: &Deadline,
+    ) -> Result<Packet, ClientError> {
+        let mut transaction = ClientTransaction::new(
+            request_packet,
+            self.add_event_timestamp,
+            self.max_retransmissions,
+            &deterministic::now(),
+        )
+        .map_err(ClientError::from)?;
+        transaction
+            .set_require_message_authenticator(self.require_message_authenticator)
+            .map_err(ClientError::from)?;
+
+        let mut buf = vec![0; self.max_datagram_size];
+        let len = loop {
+            let response = match deadline.cap(self.receive_timeout) {
+                Some(receive_timeout) => timeout(
+                    receive_timeout,
+                    self.request(conn, &transaction, remote_addr, &mut buf),
+                )
+                .await
+                .ok(),
+                None => Some(
+                    self.request(conn, &transaction, remote_addr, &mut buf)
+                        .await,
+                ),
+            };
+            if let Some(response) = response {
+                break response;
+            }
+
+            let attempts = transaction.get_retransmissions() + 1;
+            if deadline.is_exceeded() {
+                return Err(deadline.exceeded(attempts));
+            }
+            // Acct-Delay-Time counts the time since the request has been started, i.e. the deadline
+            let elapsed = deadline.elapsed();
+            if !transaction.on_timeout(elapsed).map_err(ClientError::from)? {
+                return Err(ClientError::SocketTimeoutError(TimeoutDetails::new(
+                    self.receive_timeout.unwrap_or_default(),
+                    attempts,
+                    elapsed,
+                )));
+            }
+            if let Some(retransmission_hook) = &self.retransmission_hook {
+                let retransmissions = transaction.get_retransmissions();
+                transaction
+                    .update_request_packet(|request_packet| {
+                        retransmission_hook.on_retransmission(
+                            request_packet,
+                            retransmissions,
+                            elapsed,
+                        )
+                    })
+                    .map_err(ClientError::from)?;
+            }
+            #[cfg(feature = "metrics")]
+            if let Some(metrics) = &self.metrics {
+                metrics.on_client_retransmission();
+            }
+            correlation::trace_retransmission(transaction.get_retransmissions());
+            debug!(
+                "retransmitting a request to {} ({}/{}){}",
+                remote_addr,
+                transaction.get_retransmissions(),
+                self.max_retransmissions,
+                correlation::log_suffix()
+            );
+        }?;
+
+        transaction
+            .on_response(&buf[..len])
+            .map_err(ClientError::from)
+    }
+
+    async fn connect(&self, conn: &UdpSocket, remote_addr: &SocketAddr) -> Result<(), ClientError> {
+        match conn.connect(remote_addr).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ClientError::FailedEstablishingUdpConnectionError(
+                remote_addr.to_string(),
+                e,
+            )),
+        }
+    }
+
+    async fn request(
+        &self,
+        conn: &UdpSocket,
+        transaction: &ClientTransaction,
+        remote_addr: &SocketAddr,
+        buf: &mut [u8],
+    ) -> Result<usize, ClientError> {
+        match conn.send(transaction.get_request_datagram()).await {
+            Ok(_) => {}
+            Err(e) => {
+                return Err(ClientError::FailedSendingRadiusPacketError(
+                    remote_addr.to_string(),
+                    e,
+                ))
+            }
+        };
+
+        loop {
+            match conn.recv(buf).await {
+                // the forged datagrams, and the late responses to the previous requests on a reused socket (they have the
+                // other identifiers), are not the response
+                Ok(len) if !transaction.is_response(&buf[..len]) => debug!(
+                    "ignoring a datagram from {} that is not the response{}",
+                    remote_addr,
+                    correlation::log_suffix()
+                ),
+                Ok(len) => return Ok(len),
+                Err(e) => {
+                    return Err(ClientError::FailedReceivingResponseError(
+                        remote_addr.to_string(),
+                        e,
+                    ))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use std::error::Error;
+    use std::io;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::client::{interleave_address_families, Client, ClientError, RetransmissionHook};
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::core::rfc2866;
+
+    #[test]
+    fn test_interleave_address_families() {
+        let addrs = [
+            "192.0.2.1:1812",
+            "192.0.2.2:1812",
+            "[2001:db8::1]:1812",
+            "192.0.2.3:1812",
+        ]
+        .iter()
+        .map(|addr| addr.parse().unwrap())
+        .collect();
+        let interleaved: Vec<String> = interleave_address_families(addrs)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            interleaved,
+            vec![
+                "[2001:db8::1]:1812",
+                "192.0.2.1:1812",
+                "192.0.2.2:1812",
+                "192.0.2.3:1812"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_packet_to_host() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = server.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            loop {
+                let (len, from) = server.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                let response = request.make_response_packet(Code::AccessAccept);
+                server
+                    .send_to(&response.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let client = Client::new(None, Some(Duration::from_secs(1)));
+        let target = format!("localhost:{port}");
+        for _ in 0..2 {
+            let response = client
+                .send_packet_to_host(&target, &Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+        assert_eq!(
+            client.resolved_hosts.lock().unwrap().get(&target),
+            Some(&format!("127.0.0.1:{port}").parse().unwrap())
+        );
+
+        // the cache is cleared by the failure
+        let client = Client::new(None, Some(Duration::from_millis(100)));
+        let target = "localhost:9".to_owned();
+        client
+            .resolved_hosts
+            .lock()
+            .unwrap()
+            .insert(target.clone(), "127.0.0.1:9".parse().unwrap());
+        assert!(client
+            .send_packet_to_host(&target, &Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .is_err());
+        assert!(client.resolved_hosts.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_io_error_source() {
+        // nothing listens on the port: the connected socket receives ECONNREFUSED by the ICMP port unreachable
+        let addr = UdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let client = Client::new(None, Some(Duration::from_secs(1)));
+        let e = client
+            .send_packet(&addr, &Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap_err();
+        assert!(matches!(e, ClientError::FailedReceivingResponseError(_, _)));
+        assert_eq!(
+            e.get_io_error().unwrap().kind(),
+            io::ErrorKind::ConnectionRefused
+        );
+        assert_eq!(
+            e.source()
+                .unwrap()
+                .downcast_ref::<io::Error>()
+                .unwrap()
+                .kind(),
+            io::ErrorKind::ConnectionRefused
+        );
+        assert!(e.is_retryable());
+
+        let e = ClientError::FailedUdpSocketBindingError(io::Error::from(
+            io::ErrorKind::AddrNotAvailable,
+        ));
+        assert!(!e.is_retryable());
+        assert!(!ClientError::NoServersError().is_retryable());
+        assert!(!ClientError::CancelledError().is_retryable());
+        assert!(ClientError::ConnectionTimeoutError(Duration::from_secs(1)).is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_send_packet_until() {
+        // a server that never responds
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+
+        let mut client = Client::new(None, Some(Duration::from_secs(3)));
+        client.set_max_idle_sockets(1);
+        let e = client
+            .send_packet_until(
+                &server_addr,
+                &Packet::new(Code::AccessRequest, b"secret"),
+                tokio::time::sleep(Duration::from_millis(50)),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(e, ClientError::CancelledError()));
+        assert!(client.take_idle_socket(&server_addr).is_none());
+        drop(server);
+    }
+
+    #[tokio::test]
+    async fn test_send_with_challenge_handler() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            for round in 0..3 {
+                let (len, from) = server.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                let states = rfc2865::lookup_all_state(&request);
+                let response = match round {
+                    0 => {
+                        assert!(states.is_empty());
+                        let mut challenge = request.make_response_packet(Code::AccessChallenge);
+                        rfc2865::add_state(&mut challenge, b"round-1");
+                        challenge
+                    }
+                    1 => {
+                        assert_eq!(states, vec![b"round-1".to_vec()]);
+                        let mut challenge = request.make_response_packet(Code::AccessChallenge);
+                        rfc2865::add_state(&mut challenge, b"round-2");
+                        challenge
+                    }
+                    _ => {
+                        assert_eq!(states, vec![b"round-2".to_vec()]);
+                        request.make_response_packet(Code::AccessAccept)
+                    }
+                };
+                server
+                    .send_to(&response.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let client = Client::new(None, Some(Duration::from_secs(1)));
+        let mut challenges = 0;
+        let response = client
+            .send_with_challenge_handler(
+                &server_addr,
+                &Packet::new(Code::AccessRequest, b"secret"),
+                |challenge| {
+                    challenges += 1;
+                    async move {
+                        let mut next = Packet::new(Code::AccessRequest, challenge.get_secret());
+                        // the stale State is replaced by the one of the challenge
+                        rfc2865::add_state(&mut next, b"stale");
+                        Some(next)
+                    }
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(challenges, 2);
+    }
+
+    struct ClassHook {}
+
+    impl RetransmissionHook for ClassHook {
+        fn on_retransmission(
+            &self,
+            request_packet: &mut Packet,
+            retransmissions: u32,
+            _elapsed: Duration,
+        ) {
+            rfc2865::delete_class(request_packet);
+            rfc2865::add_class(
+                request_packet,
+                format!("retry-{retransmissions}").as_bytes(),
+            );
+            // the client keeps the identifier regardless of this
+            request_packet.set_identifier(request_packet.get_identifier().wrapping_add(1));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retransmission_hook() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let datagrams = tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            let mut datagrams = vec![];
+            // the first transmission is lost
+            for _ in 0..2 {
+                let (len, from) = server.recv_from(&mut buf).await.unwrap();
+                datagrams.push((buf[..len].to_vec(), from));
+            }
+            let (datagram, from) = datagrams.last().unwrap();
+            let request = Packet::decode(datagram, b"secret").unwrap();
+            let response = request.make_response_packet(Code::AccountingResponse);
+            server
+                .send_to(&response.encode().unwrap(), from)
+                .await
+                .unwrap();
+            datagrams
+        });
+
+        let client = Client::builder()
+            .receive_timeout(Duration::from_secs(1))
+            .max_retransmissions(1)
+            .retransmission_hook(Arc::new(ClassHook {}))
+            .build();
+        let mut request = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut request, rfc2866::ACCT_STATUS_TYPE_STOP);
+        let response = client.send_packet(&server_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccountingResponse);
+
+        let datagrams = datagrams.await.unwrap();
+        let first = Packet::decode(&datagrams[0].0, b"secret").unwrap();
+        let retransmitted = Packet::decode(&datagrams[1].0, b"secret").unwrap();
+        assert!(Packet::is_authentic_request(&datagrams[1].0, b"secret"));
+        assert_eq!(retransmitted.get_identifier(), first.get_identifier());
+        assert!(rfc2866::lookup_acct_delay_time(&first).is_none());
+        assert_eq!(
+            rfc2866::lookup_acct_delay_time(&retransmitted)
+                .unwrap()
+                .unwrap(),
+            1
+        );
+        assert!(rfc2865::lookup_class(&first).is_none());
+        assert_eq!(rfc2865::lookup_class(&retransmitted).unwrap(), b"retry-1");
+    }
+
+    #[tokio::test]
+    async fn test_socket_reuse() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let sources = tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            let mut sources = vec![];
+            for i in 0..3 {
+                let (len, from) = server.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                if i == 1 {
+                    // a stale response of the other identifier precedes the real one
+                    let mut stale = request.make_response_packet(Code::AccessReject);
+                    stale.set_identifier(request.get_identifier().wrapping_add(1));
+                    server
+                        .send_to(&stale.encode().unwrap(), from)
+                        .await
+                        .unwrap();
+                }
+                let response = request.make_response_packet(Code::AccessAccept);
+                server
+                    .send_to(&response.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+                sources.push(from);
+            }
+            sources
+        });
+
+        let mut client = Client::new(None, Some(Duration::from_secs(3)));
+        client.set_max_idle_sockets(1);
+        for _ in 0..3 {
+            let response = client
+                .send_packet(&server_addr, &Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+
+        let sources = sources.await.unwrap();
+        assert_eq!(sources[0], sources[1]);
+        assert_eq!(sources[1], sources[2]);
+    }
+
+    #[tokio::test]
+    async fn test_max_datagram_size() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            loop {
+                let (len, from) = server.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                let mut response = request.make_response_packet(Code::AccessAccept);
+                rfc2865::add_reply_message(&mut response, &"x".repeat(200));
+                server
+                    .send_to(&response.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let mut client = Client::new(None, Some(Duration::from_secs(3)));
+        client.set_recv_buffer_size(Some(1 << 16));
+        client.set_max_datagram_size(4096);
+        let response = client
+            .send_packet(&server_addr, &Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+
+        // the truncated response is not authentic, so that is dropped
+        client.set_max_datagram_size(100);
+        client.set_receive_timeout(Some(Duration::from_millis(200)));
+        let err = client
+            .send_packet(&server_addr, &Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClientError::SocketTimeoutError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_forged_responses() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            let (len, from) = server.recv_from(&mut buf).await.unwrap();
+            // an Access-Accept of the wrong secret and that of the other identifier precede the genuine Access-Reject
+            let forged = Packet::decode(&buf[..len], b"wrong")
+                .unwrap()
+                .make_response_packet(Code::AccessAccept);
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            let mut stray = request.make_response_packet(Code::AccessAccept);
+            stray.set_identifier(request.get_identifier().wrapping_add(1));
+            let response = request.make_response_packet(Code::AccessReject);
+            for packet in [forged, stray, response] {
+                server
+                    .send_to(&packet.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let client = Client::new(None, Some(Duration::from_secs(3)));
+        let response = client
+            .send_packet(&server_addr, &Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+    }
+
+    #[cfg(feature = "rfc7499")]
+    #[tokio::test]
+    async fn test_send_fragmented_packet() {
+        use crate::core::rfc7499::{ReassemblyStep, RequestReassembler};
+
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let classes = tokio::spawn(async move {
+            let mut reassembler = RequestReassembler::new();
+            let mut buf = vec![0; 4096];
+            loop {
+                let (len, from) = server.recv_from(&mut buf).await.unwrap();
+                let chunk = Packet::decode(&buf[..len], b"secret").unwrap();
+                let response = match reassembler.on_request(&chunk).unwrap() {
+                    ReassemblyStep::Challenge(challenge) => challenge,
+                    ReassemblyStep::Complete(request) => {
+                        let response = request.make_response_packet(Code::AccessAccept);
+                        server
+                            .send_to(&response.encode().unwrap(), from)
+                            .await
+                            .unwrap();
+                        return rfc2865::lookup_all_class(&request).len();
+                    }
+                };
+                server
+                    .send_to(&response.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        for _ in 0..40 {
+            rfc2865::add_class(&mut request, &[0; 200]);
+        }
+        let client = Client::new(None, Some(Duration::from_secs(3)));
+        let response = client
+            .send_fragmented_packet(&server_addr, &request)
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(classes.await.unwrap(), 40);
+    }
+
+    #[tokio::test]
+    async fn test_local_addr() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let local_addr = UdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let source = tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            let (len, from) = server.recv_from(&mut buf).await.unwrap();
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            let response = request.make_response_packet(Code::AccessAccept);
+            server
+                .send_to(&response.encode().unwrap(), from)
+                .await
+                .unwrap();
+            from
+        });
+
+        let mut client = Client::new(None, Some(Duration::from_secs(3)));
+        client.set_local_addr(Some(local_addr));
+        client
+            .send_packet(&server_addr, &Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap();
+        assert_eq!(source.await.unwrap(), local_addr);
+    }
+}
@@ -0,0 +1,711 @@
+//! A RADIUS client that multiplexes the concurrent requests to one server over the shared sockets.
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+use rand::RngCore;
+use tokio::net::UdpSocket;
+use tokio::sync::{mpsc, Notify};
+use tokio::task::JoinHandle;
+use tokio::time::{timeout, timeout_at};
+
+use crate::client::{local_bind_addr, set_buffer_sizes, ClientError, Deadline, TimeoutDetails};
+use crate::core::deterministic;
+use crate::core::packet::{Packet, RADIUS_PACKET_HEADER_LENGTH};
+use crate::core::random;
+use crate::core::sansio::ClientTransaction;
+use crate::correlation;
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsHook;
+use crate::tasks;
+
+const MAX_DATAGRAM_SIZE: usize = 65507;
+const IDENTIFIER_SPACE: usize = 256;
+const DEFAULT_MAX_SOCKETS: usize = 16;
+// the datagrams of an identifier that a request hasn't checked yet; the others are discarded
+const MAX_QUEUED_DATAGRAMS: usize = 4;
+
+/// A client that sends the concurrent requests to one server over the shared UDP sockets.
+///
+/// Unlike `Client`, which binds a socket per request, this allocates a free identifier on a shared socket for each
+/// request and routes the responses to the waiting requests by their identifiers. Each socket (i.e. each source port)
+/// has its own space of 256 identifiers; when all of them are outstanding, another socket is opened, up to
+/// `set_max_sockets()`. Beyond that, the requests wait until an identifier is released.
+///
+/// A background task of each socket reads the datagrams and routes them by the identifiers; a request takes the one
+/// that has the Response Authenticator of its request (and discards the others, e.g. the forged ones or the late
+/// responses of a former request of the identifier), so a stray datagram doesn't fail the request.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use radius::client::multiplexed::MultiplexedClient;
+/// use radius::core::code::Code;
+/// use radius::core::packet::Packet;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = Arc::new(MultiplexedClient::new(
+///     "127.0.0.1:1812".parse().unwrap(),
+///     Some(Duration::from_secs(3)),
+/// ));
+/// let mut requests = Vec::new();
+/// for _ in 0..1000 {
+///     let client = client.clone();
+///     requests.push(tokio::spawn(async move {
+///         client.send_packet(&Packet::new(Code::AccessRequest, b"secret")).await
+///     }));
+/// }
+/// for request in requests {
+///     println!("{:?}", request.await.unwrap().map(|response| response.get_code()));
+/// }
+/// # }
+/// ```
+pub struct MultiplexedClient {
+    remote_addr: SocketAddr,
+    receive_timeout: Option<Duration>,
+    total_timeout: Option<Duration>,
+    max_retransmissions: u32,
+    add_event_timestamp: bool,
+    require_message_authenticator: bool,
+    max_sockets: usize,
+    local_addr: Option<SocketAddr>,
+    max_datagram_size: usize,
+    recv_buffer_size: Option<usize>,
+    send_buffer_size: Option<usize>,
+    endpoints: RwLock<Vec<Arc<Endpoint>>>,
+    released: Notify,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<dyn MetricsHook>>,
+}
+
+struct Endpoint {
+    conn: Arc<UdpSocket>,
+    pending: Arc<PendingTable>,
+    receiver: JoinHandle<()>,
+}
+
+impl Drop for Endpoint {
+    fn drop(&mut self) {
+        self.receiver.abort();
+    }
+}
+
+/// The table of the outstanding requests of a socket, i.e. the waiters of the responses by the identifiers.
+///
+/// The identifiers are allocated by the atomic compare-and-swap, and each identifier has its own lock of the waiter;
+/// so the requests and the receiver of the responses don't contend on a lock of the whole table. The datagrams are
+/// queued to the waiter until the request validates them.
+struct PendingTable {
+    slots: Vec<PendingSlot>,
+    next_identifier: AtomicUsize,
+    outstanding: AtomicUsize,
+    closed: AtomicBool,
+}
+
+struct PendingSlot {
+    // an identifier stays in use until the request has finished, so that a late response doesn't go to another request
+    in_use: AtomicBool,
+    waiter: Mutex<Option<mpsc::Sender<Vec<u8>>>>,
+}
+
+impl PendingTable {
+    fn new() -> Self {
+        PendingTable {
+            slots: (0..IDENTIFIER_SPACE)
+                .map(|_| PendingSlot {
+                    in_use: AtomicBool::new(false),
+                    waiter: Mutex::new(None),
+                })
+                .collect(),
+            next_identifier: AtomicUsize::new(random::rng().next_u32() as usize),
+            outstanding: AtomicUsize::new(0),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    fn allocate(&self) -> Option<(u8, mpsc::Receiver<Vec<u8>>)> {
+        if self.closed.load(Ordering::Acquire)
+            || self.outstanding.load(Ordering::Relaxed) >= IDENTIFIER_SPACE
+        {
+            return None;
+        }
+        let start = self.next_identifier.fetch_add(1, Ordering::Relaxed);
+        (0..IDENTIFIER_SPACE)
+            .map(|i| start.wrapping_add(i) % IDENTIFIER_SPACE)
+            .find(|&identifier| {
+                self.slots[identifier]
+                    .in_use
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            })
+            .map(|identifier| {
+                self.outstanding.fetch_add(1, Ordering::Relaxed);
+                let (tx, rx) = mpsc::channel(MAX_QUEUED_DATAGRAMS);
+                *self.slots[identifier].waiter.lock().unwrap() = Some(tx);
+                (identifier as u8, rx)
+            })
+    }
+
+    fn complete(&self, identifier: u8, datagram: Vec<u8>) {
+        if let Some(tx) = &*self.slots[identifier as usize].waiter.lock().unwrap() {
+            if tx.try_send(datagram).is_err() {
+                debug!("discarded a response of the identifier {}", identifier);
+            }
+        }
+    }
+
+    fn release(&self, identifier: u8) {
+        let slot = &self.slots[identifier as usize];
+        slot.waiter.lock().unwrap().take();
+        self.outstanding.fetch_sub(1, Ordering::Relaxed);
+        slot.in_use.store(false, Ordering::Release);
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        for slot in &self.slots {
+            slot.waiter.lock().unwrap().take();
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+}
+
+struct Slot<'a> {
+    endpoint: Arc<Endpoint>,
+    identifier: u8,
+    response_rx: mpsc::Receiver<Vec<u8>>,
+    released: &'a Notify,
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        self.endpoint.pending.release(self.identifier);
+        self.released.notify_one();
+    }
+}
+
+impl MultiplexedClient {
+    /// A constructor for a client of the server at `remote_addr`.
+    ///
+    /// * `receive_timeout` - A duration of receive timeout of each attempt. If the response is not returned in time, it retransmits the request
+    ///   (see `set_max_retransmissions()`) or the `SocketTimeoutError` occurs. If this value is `None`, it never timed-out.
+    ///
+    /// The overall deadline of a request, including waiting for a free identifier, is set by `set_total_timeout()`.
+    pub fn new(remote_addr: SocketAddr, receive_timeout: Option<Duration>) -> Self {
+        MultiplexedClient {
+            remote_addr,
+            receive_timeout,
+            total_timeout: None,
+            max_retransmissions: 0,
+            add_event_timestamp: true,
+            require_message_authenticator: false,
+            max_sockets: DEFAULT_MAX_SOCKETS,
+            local_addr: None,
+            max_datagram_size: MAX_DATAGRAM_SIZE,
+            recv_buffer_size: None,
+            send_buffer_size: None,
+            endpoints: RwLock::new(Vec::new()),
+            released: Notify::new(),
+            #[cfg(feature = "metrics")]
+            metrics: None,
+        }
+    }
+
+    /// Set the receive timeout of each attempt (see `new()`).
+    pub fn set_receive_timeout(&mut self, receive_timeout: Option<Duration>) {
+        self.receive_timeout = receive_timeout;
+    }
+
+    /// Set the total timeout of a request, i.e. the deadline of all the attempts (default: `None`, i.e. no deadline).
+    ///
+    /// See `Client::set_total_timeout()`.
+    pub fn set_total_timeout(&mut self, total_timeout: Option<Duration>) {
+        self.total_timeout = total_timeout;
+    }
+
+    /// Set the maximum number of retransmissions on socket timeout (default: `0`, i.e. no retransmission).
+    ///
+    /// See `Client::set_max_retransmissions()`.
+    pub fn set_max_retransmissions(&mut self, max_retransmissions: u32) {
+        self.max_retransmissions = max_retransmissions;
+    }
+
+    /// Set a flag to specify whether to add Event-Timestamp to the Accounting-Request, CoA-Request and Disconnect-Request
+    /// that don't have that yet (default: `true`).
+    pub fn set_add_event_timestamp(&mut self, add_event_timestamp: bool) {
+        self.add_event_timestamp = add_event_timestamp;
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator against the forgery of the responses (default: `false`).
+    ///
+    /// See `Client::set_require_message_authenticator()`.
+    pub fn set_require_message_authenticator(&mut self, require_message_authenticator: bool) {
+        self.require_message_authenticator = require_message_authenticator;
+    }
+
+    /// Set the maximum number of the sockets (i.e. the source ports) to open (default: `16`, i.e. 4096 outstanding requests).
+    pub fn set_max_sockets(&mut self, max_sockets: usize) {
+        self.max_sockets = max_sockets.max(1);
+    }
+
+    /// Set the local address to bind the sockets to (default: `None`, i.e. `0.0.0.0:0` or `[::]:0` by the destination).
+    ///
+    /// With a non-zero port only one socket can be bound, i.e. this limits the outstanding requests to 256 regardless of `set_max_sockets()`.
+    pub fn set_local_addr(&mut self, local_addr: Option<SocketAddr>) {
+        self.local_addr = local_addr;
+    }
+
+    /// Set the maximum size of the response datagrams (default: `65507`), i.e. the size of the receive buffer of each
+    /// socket (see `Client::set_max_datagram_size()`).
+    pub fn set_max_datagram_size(&mut self, max_datagram_size: usize) {
+        self.max_datagram_size = max_datagram_size.max(RADIUS_PACKET_HEADER_LENGTH);
+    }
+
+    /// Set the size of the receive buffer (`SO_RCVBUF`) of the sockets (default: `None`, i.e. the default of the OS).
+    ///
+    /// A large one keeps the responses of the bursts of the requests from being dropped before the receiver reads them.
+    pub fn set_recv_buffer_size(&mut self, recv_buffer_size: Option<usize>) {
+        self.recv_buffer_size = recv_buffer_size;
+    }
+
+    /// Set the size of the send buffer (`SO_SNDBUF`) of the sockets (default: `None`, i.e. the default of the OS).
+    pub fn set_send_buffer_size(&mut self, send_buffer_size: Option<usize>) {
+        self.send_buffer_size = send_buffer_size;
+    }
+
+    /// Set the metrics to record the requests of the client into, e.g. the Prometheus `Metrics` or an own `MetricsHook`
+    /// (see `radius::metrics`).
+    ///
+    /// This is available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn set_metrics(&mut self, metrics: Arc<dyn MetricsHook>) {
+        self.metrics = Some(metrics);
+    }
+
+    /// Returns the number of the open sockets.
+    pub fn get_socket_count(&self) -> usize {
+        self.endpoints.read().unwrap().len()
+    }
+
+    /// This method sends a packet to the server; the identifier of the packet is replaced with an allocated one.
+    ///
+    /// This method retransmits the packet only when the socket timeout has exceeded and the retransmission is enabled by `set_max_retransmissions()`.
+    pub async fn send_packet(&self, request_packet: &Packet) -> Result<Packet, ClientError> {
+        correlation::request(&self.remote_addr, request_packet.get_code(), async {
+            #[cfg(feature = "metrics")]
+            if let Some(metrics) = &self.metrics {
+                metrics.on_client_request(request_packet.get_code());
+                let started_at = Instant::now();
+                let result = self.exchange(request_packet).await;
+                crate::metrics::observe_client_result(
+                    metrics.as_ref(),
+                    &result,
+                    Packet::get_code,
+                    started_at.elapsed(),
+                );
+                return result;
+            }
+            self.exchange(request_packet).await
+        })
+        .await
+    }
+
+    async fn exchange(&self, request_packet: &Packet) -> Result<Packet, ClientError> {
+        let deadline = Deadline::new(self.total_timeout);
+        let mut slot = match deadline.cap(None) {
+            Some(rest) => timeout(rest, self.acquire())
+                .await
+                .unwrap_or_else(|_| Err(deadline.exceeded(0))),
+            None => self.acquire().await,
+        }?;
+
+        let mut request_packet = request_packet.clone();
+        request_packet.set_identifier(slot.identifier);
+        let mut transaction = ClientTransaction::new(
+            &request_packet,
+            self.add_event_timestamp,
+            self.max_retransmissions,
+            &deterministic::now(),
+        )
+        .map_err(ClientError::from)?;
+        transaction
+            .set_require_message_authenticator(self.require_message_authenticator)
+            .map_err(ClientError::from)?;
+
+        loop {
+            if let Err(e) = slot
+                .endpoint
+                .conn
+                .send(transaction.get_request_datagram())
+                .await
+            {
+                return Err(ClientError::FailedSendingRadiusPacketError(
+                    self.remote_addr.to_string(),
+                    e,
+                ));
+            }
+
+            let receive_deadline = deadline
+                .cap(self.receive_timeout)
+                .map(|receive_timeout| tokio::time::Instant::now() + receive_timeout);
+            let received = loop {
+                let received = match receive_deadline {
+                    Some(receive_deadline) => timeout_at(receive_deadline, slot.response_rx.recv())
+                        .await
+                        .ok(),
+                    None => Some(slot.response_rx.recv().await),
+                };
+                match received {
+                    Some(Some(datagram)) if !transaction.is_response(&datagram) => debug!(
+                        "discarded an unauthentic response from {}{}",
+                        self.remote_addr,
+                        correlation::log_suffix()
+                    ),
+                    received => break received,
+                }
+            };
+            match received {
+                Some(Some(datagram)) => {
+                    return transaction
+                        .on_response(&datagram)
+                        .map_err(ClientError::from)
+                }
+                Some(None) => {
+                    return Err(ClientError::FailedReceivingResponseError(
+                        self.remote_addr.to_string(),
+                        io::Error::new(io::ErrorKind::NotConnected, "the socket has been closed"),
+                    ))
+                }
+                None => {
+                    let attempts = transaction.get_retransmissions() + 1;
+                    if deadline.is_exceeded() {
+                        return Err(deadline.exceeded(attempts));
+                    }
+                    if !transaction
+                        .on_timeout(deadline.elapsed())
+                        .map_err(ClientError::from)?
+                    {
+                        return Err(ClientError::SocketTimeoutError(TimeoutDetails::new(
+                            self.receive_timeout.unwrap_or_default(),
+                            attempts,
+                            deadline.elapsed(),
+                        )));
+                    }
+                    #[cfg(feature = "metrics")]
+                    if let Some(metrics) = &self.metrics {
+                        metrics.on_client_retransmission();
+                    }
+                    correlation::trace_retransmission(transaction.get_retransmissions());
+                    debug!(
+                        "retransmitting a request to {} ({}/{}){}",
+                        self.remote_addr,
+                        transaction.get_retransmissions(),
+                        self.max_retransmissions,
+                        correlation::log_suffix()
+                    );
+                }
+            }
+        }
+    }
+
+    async fn acquire(&self) -> Result<Slot<'_>, ClientError> {
+        loop {
+            let notified = self.released.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if let Some(slot) = self.try_acquire()? {
+                return Ok(slot);
+            }
+            notified.await;
+        }
+    }
+
+    fn try_acquire(&self) -> Result<Option<Slot<'_>>, ClientError> {
+        // the usual case: a free identifier on one of the open sockets, under the shared lock of the list
+        if let Some(slot) = self.allocate(&self.endpoints.read().unwrap()) {
+            return Ok(Some(slot));
+        }
+
+        let mut endpoints = self.endpoints.write().unwrap();
+        endpoints.retain(|endpoint| !endpoint.pending.is_closed());
+        if let Some(slot) = self.allocate(&endpoints) {
+            return Ok(Some(slot));
+        }
+        let max_sockets = match self.local_addr {
+            // the port can be bound only once
+            Some(local_addr) if local_addr.port() != 0 => 1,
+            _ => self.max_sockets,
+        };
+        if endpoints.len() >= max_sockets {
+            return Ok(None);
+        }
+
+        if !endpoints.is_empty() {
+            debug!(
+                "all identifiers to {} are outstanding, opening another socket ({}/{})",
+                self.remote_addr,
+                endpoints.len() + 1,
+                max_sockets
+            );
+        }
+        endpoints.push(Arc::new(self.open()?));
+        Ok(self.allocate(&endpoints))
+    }
+
+    fn allocate(&self, endpoints: &[Arc<Endpoint>]) -> Option<Slot<'_>> {
+        endpoints.iter().find_map(|endpoint| {
+            endpoint
+                .pending
+                .allocate()
+                .map(|(identifier, response_rx)| Slot {
+                    endpoint: endpoint.clone(),
+                    identifier,
+                    response_rx,
+                    released: &self.released,
+                })
+        })
+    }
+
+    fn open(&self) -> Result<Endpoint, ClientError> {
+        let local_addr = local_bind_addr(self.local_addr, &self.remote_addr);
+        let conn = std::net::UdpSocket::bind(local_addr)
+            .and_then(|conn| conn.set_nonblocking(true).map(|_| conn))
+            .and_then(|conn| {
+                set_buffer_sizes(
+                    socket2::SockRef::from(&conn),
+                    self.recv_buffer_size,
+                    self.send_buffer_size,
+                )
+                .map(|_| conn)
+            })
+            .map_err(ClientError::FailedUdpSocketBindingError)?;
+        let conn = conn
+            .connect(self.remote_addr)
+            .and_then(|_| UdpSocket::from_std(conn))
+            .map_err(|e| {
+                ClientError::FailedEstablishingUdpConnectionError(self.remote_addr.to_string(), e)
+            })?;
+
+        let conn = Arc::new(conn);
+        let pending = Arc::new(PendingTable::new());
+        let receiver = tasks::spawn(
+            &tasks::MULTIPLEXED_CLIENT_RECEIVER,
+            Self::receive(conn.clone(), pending.clone(), self.max_datagram_size),
+        );
+        Ok(Endpoint {
+            conn,
+            pending,
+            receiver,
+        })
+    }
+
+    async fn receive(conn: Arc<UdpSocket>, pending: Arc<PendingTable>, max_datagram_size: usize) {
+        let mut buf = vec![0; max_datagram_size];
+        loop {
+            match conn.recv(&mut buf).await {
+                Ok(len) if len >= 2 => pending.complete(buf[1], buf[..len].to_vec()),
+                Ok(_) => debug!("ignored a too short datagram"),
+                // an ICMP port unreachable from the server; the requests are retransmitted or timed-out as usual
+                Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => {}
+                Err(e) => {
+                    error!("failed to receive the responses; {}", e);
+                    pending.close();
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::client::multiplexed::{MultiplexedClient, PendingTable};
+    use crate::client::ClientError;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+
+    // a server that holds the requests until `hold` of them have arrived, and then responds to all of them
+    async fn holding_server(hold: usize) -> std::net::SocketAddr {
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = conn.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            let mut buf = vec![0; 4096];
+            loop {
+                let (len, remote_addr) = conn.recv_from(&mut buf).await.unwrap();
+                held.push((Packet::decode(&buf[..len], b"secret").unwrap(), remote_addr));
+                if held.len() >= hold {
+                    for (request, remote_addr) in held.drain(..) {
+                        let response = request.make_response_packet(Code::AccessAccept);
+                        conn.send_to(&response.encode().unwrap(), remote_addr)
+                            .await
+                            .unwrap();
+                    }
+                }
+            }
+        });
+        addr
+    }
+
+    async fn send_concurrently(client: &Arc<MultiplexedClient>, count: usize) {
+        let requests: Vec<_> = (0..count)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move {
+                    client
+                        .send_packet(&Packet::new(Code::AccessRequest, b"secret"))
+                        .await
+                })
+            })
+            .collect();
+        for request in requests {
+            let response = request.await.unwrap().unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+    }
+
+    #[test]
+    fn test_pending_table() {
+        let table = Arc::new(PendingTable::new());
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let table = table.clone();
+                std::thread::spawn(move || {
+                    (0..32)
+                        .map(|_| table.allocate().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut allocated: Vec<_> = threads
+            .into_iter()
+            .flat_map(|thread| thread.join().unwrap())
+            .collect();
+        let mut identifiers: Vec<_> = allocated
+            .iter()
+            .map(|(identifier, _)| *identifier)
+            .collect();
+        identifiers.sort_unstable();
+        identifiers.dedup();
+        assert_eq!(identifiers.len(), 256);
+        assert!(table.allocate().is_none());
+
+        let (identifier, mut rx) = allocated.pop().unwrap();
+        table.complete(identifier, vec![1, 2, 3]);
+        table.complete(identifier, vec![4, 5, 6]);
+        assert_eq!(rx.blocking_recv().unwrap(), vec![1, 2, 3]);
+        assert_eq!(rx.blocking_recv().unwrap(), vec![4, 5, 6]);
+        table.release(identifier);
+        assert_eq!(table.allocate().unwrap().0, identifier);
+    }
+
+    #[tokio::test]
+    async fn test_more_outstanding_requests_than_identifiers() {
+        let addr = holding_server(300).await;
+        let mut client = MultiplexedClient::new(addr, Some(Duration::from_secs(5)));
+        client.set_max_sockets(2);
+        let client = Arc::new(client);
+
+        send_concurrently(&client, 300).await;
+        assert_eq!(client.get_socket_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_waiting_at_max_sockets() {
+        let addr = holding_server(1).await;
+        let mut client = MultiplexedClient::new(addr, Some(Duration::from_secs(5)));
+        client.set_max_sockets(1);
+        let client = Arc::new(client);
+
+        send_concurrently(&client, 600).await;
+        assert_eq!(client.get_socket_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unauthentic_responses() {
+        // a server that sends a forged response and a stray one of another request before the genuine response
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = conn.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            loop {
+                let (len, remote_addr) = conn.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                let mut forged = request.make_response_packet(Code::AccessAccept);
+                forged.set_secret(b"forged");
+                let mut stray = Packet::new(Code::AccessRequest, b"secret");
+                stray.set_identifier(request.get_identifier());
+                let stray = stray.make_response_packet(Code::AccessAccept);
+                let genuine = request.make_response_packet(Code::AccessReject);
+                for response in [forged, stray, genuine] {
+                    conn.send_to(&response.encode().unwrap(), remote_addr)
+                        .await
+                        .unwrap();
+                }
+            }
+        });
+
+        let client = MultiplexedClient::new(addr, Some(Duration::from_secs(5)));
+        for _ in 0..3 {
+            let response = client
+                .send_packet(&Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessReject);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_total_timeout() {
+        // a server that never responds
+        let addr = holding_server(usize::MAX).await;
+        let mut client = MultiplexedClient::new(addr, Some(Duration::from_millis(100)));
+        client.set_max_retransmissions(10);
+        client.set_total_timeout(Some(Duration::from_millis(250)));
+
+        let started_at = std::time::Instant::now();
+        let err = client
+            .send_packet(&Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap_err();
+        let elapsed = started_at.elapsed();
+        let details = match err {
+            ClientError::TotalTimeoutError(details) => details,
+            e => panic!("unexpected error: {e}"),
+        };
+        // 100ms + 100ms + the rest 50ms
+        assert_eq!(details.get_budget(), Duration::from_millis(250));
+        assert_eq!(details.get_attempts(), 3);
+        assert!(details.get_elapsed() >= Duration::from_millis(250));
+        assert!(details.get_elapsed() <= elapsed);
+        assert!(elapsed < Duration::from_millis(400));
+
+        client.set_total_timeout(None);
+        client.set_max_retransmissions(0);
+        let err = client
+            .send_packet(&Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("socket timeout (100ms; 1 attempt(s) in "));
+    }
+}
@@ -0,0 +1,446 @@
+//! A RADIUS client that balances the requests among a pool of servers.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::client::{Client, ClientError};
+use crate::core::packet::Packet;
+use crate::core::rfc2865;
+use crate::correlation;
+use crate::error::ErrorKind;
+
+const DEFAULT_DEAD_TIME: Duration = Duration::from_secs(30);
+
+/// The state of a server of `ServerPool`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolServerState {
+    addr: SocketAddr,
+    outstanding: usize,
+    dead_until: Option<Instant>,
+    dead: bool,
+}
+
+impl PoolServerState {
+    /// Returns the address of the server.
+    pub fn get_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the number of the requests that are waiting for the responses of the server.
+    pub fn get_outstanding(&self) -> usize {
+        self.outstanding
+    }
+
+    /// Returns whether the server is marked dead.
+    pub fn is_dead(&self) -> bool {
+        self.dead
+    }
+
+    fn is_available(&self, now: Instant) -> bool {
+        !self.dead || self.dead_until.is_some_and(|dead_until| now >= dead_until)
+    }
+}
+
+/// A strategy that selects the server of a request among the candidates of `ServerPool`.
+pub trait SelectionStrategy: 'static + Sync + Send {
+    /// Returns the index of the server in the candidates (never empty) to send the request to.
+    fn select(&self, request: &Packet, candidates: &[PoolServerState]) -> usize;
+}
+
+/// Selects the candidates in turn.
+#[derive(Debug, Default)]
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl RoundRobin {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl SelectionStrategy for RoundRobin {
+    fn select(&self, _request: &Packet, candidates: &[PoolServerState]) -> usize {
+        self.next.fetch_add(1, Ordering::Relaxed) % candidates.len()
+    }
+}
+
+/// Selects the candidate that has the fewest outstanding requests; the first one of them on a tie.
+#[derive(Debug, Default)]
+pub struct LeastOutstanding {}
+
+impl LeastOutstanding {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl SelectionStrategy for LeastOutstanding {
+    fn select(&self, _request: &Packet, candidates: &[PoolServerState]) -> usize {
+        candidates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, server)| server.outstanding)
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+}
+
+/// Selects a candidate by the hash of User-Name, or of Calling-Station-Id if the request doesn't have that, so the
+/// requests of a session (e.g. the rounds of an EAP conversation) go to the same server.
+///
+/// The hash is the rendezvous hash of the key and the server address, so a server that goes down moves only the
+/// sessions of that. The requests that have neither of them are selected in turn.
+#[derive(Debug, Default)]
+pub struct SessionHash {
+    fallback: RoundRobin,
+}
+
+impl SessionHash {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl SelectionStrategy for SessionHash {
+    fn select(&self, request: &Packet, candidates: &[PoolServerState]) -> usize {
+        let key = match request
+            .lookup(rfc2865::USER_NAME_TYPE)
+            .or_else(|| request.lookup(rfc2865::CALLING_STATION_ID_TYPE))
+        {
+            Some(avp) => avp.get_value().to_vec(),
+            None => return self.fallback.select(request, candidates),
+        };
+        candidates
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, server)| {
+                let mut hasher = DefaultHasher::new();
+                key.hash(&mut hasher);
+                server.addr.hash(&mut hasher);
+                hasher.finish()
+            })
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+}
+
+/// A client that balances the requests among the servers of a pool by a `SelectionStrategy`.
+///
+/// A server that doesn't respond or that is unreachable is marked dead like `FailoverClient` does, and the request
+/// is sent to another server that the strategy selects among the rest. The dead servers are skipped until the dead
+/// time has elapsed (see `set_dead_time()`) or a Status-Server probe has been answered (see `probe_servers()`).
+/// When all of the servers are dead, the strategy selects among them anyway.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use radius::client::Client;
+/// use radius::client::pool::{ServerPool, SessionHash};
+/// use radius::core::code::Code;
+/// use radius::core::packet::Packet;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let pool = ServerPool::new(
+///     Client::new(Some(Duration::from_secs(1)), Some(Duration::from_secs(3))),
+///     &["192.0.2.1:1812".parse().unwrap(), "192.0.2.2:1812".parse().unwrap()],
+///     SessionHash::new(),
+/// );
+/// let response = pool.send_packet(&Packet::new(Code::AccessRequest, b"secret")).await;
+/// # }
+/// ```
+pub struct ServerPool {
+    client: Client,
+    servers: Mutex<Vec<PoolServerState>>,
+    strategy: Box<dyn SelectionStrategy>,
+    dead_time: Option<Duration>,
+}
+
+impl ServerPool {
+    /// A constructor for a pool of the servers with the strategy.
+    pub fn new<S: SelectionStrategy>(client: Client, servers: &[SocketAddr], strategy: S) -> Self {
+        ServerPool {
+            client,
+            servers: Mutex::new(
+                servers
+                    .iter()
+                    .map(|addr| PoolServerState {
+                        addr: *addr,
+                        outstanding: 0,
+                        dead_until: None,
+                        dead: false,
+                    })
+                    .collect(),
+            ),
+            strategy: Box::new(strategy),
+            dead_time: Some(DEFAULT_DEAD_TIME),
+        }
+    }
+
+    /// Set how long a dead server is skipped (default: `30s`).
+    ///
+    /// If this is `None`, a dead server is skipped until it answers a probe of `probe_servers()`.
+    pub fn set_dead_time(&mut self, dead_time: Option<Duration>) {
+        self.dead_time = dead_time;
+    }
+
+    /// Returns the states of the servers.
+    pub fn get_server_states(&self) -> Vec<PoolServerState> {
+        self.servers.lock().unwrap().clone()
+    }
+
+    /// This method sends a packet to the server that the strategy selects, and to another one on the failure.
+    ///
+    /// If all of the servers have failed, this returns the error of the last one.
+    pub async fn send_packet(&self, request_packet: &Packet) -> Result<Packet, ClientError> {
+        let mut tried = Vec::new();
+        let mut last_error = None;
+        while let Some(addr) = self.select(request_packet, &tried) {
+            tried.push(addr);
+            let _outstanding = Outstanding::new(self, addr);
+            match self.client.send_packet(&addr, request_packet).await {
+                Ok(response) => {
+                    self.mark_alive(addr);
+                    return Ok(response);
+                }
+                Err(e) if is_server_failure(&e) => {
+                    info!(
+                        "server {} of the pool has failed, trying another one; {}{}",
+                        addr,
+                        e,
+                        correlation::log_suffix()
+                    );
+                    self.mark_dead(addr);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or(ClientError::NoServersError()))
+    }
+
+    /// Send a Status-Server (RFC 5997, see `Client::probe()`) with the secret to each server, and mark the ones that
+    /// respond alive and the others dead.
+    ///
+    /// This returns the number of the live servers.
+    pub async fn probe_servers(&self, secret: &[u8]) -> usize {
+        let addrs: Vec<_> = self
+            .get_server_states()
+            .iter()
+            .map(PoolServerState::get_addr)
+            .collect();
+
+        let mut alive = 0;
+        for addr in addrs {
+            match self.client.probe(&addr, secret).await {
+                Ok(_) => {
+                    self.mark_alive(addr);
+                    alive += 1;
+                }
+                Err(e) => {
+                    debug!(
+                        "server {} of the pool doesn't answer the probe; {}",
+                        addr, e
+                    );
+                    self.mark_dead(addr);
+                }
+            }
+        }
+        alive
+    }
+
+    /// Probe the servers repeatedly at the given interval. This never returns.
+    pub async fn run_probe_loop(&self, secret: &[u8], interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+            self.probe_servers(secret).await;
+        }
+    }
+
+    fn select(&self, request_packet: &Packet, tried: &[SocketAddr]) -> Option<SocketAddr> {
+        let now = Instant::now();
+        let servers = self.servers.lock().unwrap();
+        let untried: Vec<PoolServerState> = servers
+            .iter()
+            .filter(|server| !tried.contains(&server.addr))
+            .copied()
+            .collect();
+        let available: Vec<PoolServerState> = untried
+            .iter()
+            .filter(|server| server.is_available(now))
+            .copied()
+            .collect();
+        let candidates = if available.is_empty() {
+            untried
+        } else {
+            available
+        };
+        if candidates.is_empty() {
+            return None;
+        }
+        let index = self.strategy.select(request_packet, &candidates);
+        candidates.get(index).map(|server| server.addr)
+    }
+
+    fn update(&self, addr: SocketAddr, f: impl Fn(&mut PoolServerState)) {
+        for server in self.servers.lock().unwrap().iter_mut() {
+            if server.addr == addr {
+                f(server);
+            }
+        }
+    }
+
+    fn mark_dead(&self, addr: SocketAddr) {
+        let dead_until = self.dead_time.map(|dead_time| Instant::now() + dead_time);
+        self.update(addr, |server| {
+            server.dead = true;
+            server.dead_until = dead_until;
+        });
+    }
+
+    fn mark_alive(&self, addr: SocketAddr) {
+        self.update(addr, |server| {
+            server.dead = false;
+            server.dead_until = None;
+        });
+    }
+}
+
+/// Counts an outstanding request of a server while this is alive.
+struct Outstanding<'a> {
+    pool: &'a ServerPool,
+    addr: SocketAddr,
+}
+
+impl<'a> Outstanding<'a> {
+    fn new(pool: &'a ServerPool, addr: SocketAddr) -> Self {
+        pool.update(addr, |server| server.outstanding += 1);
+        Outstanding { pool, addr }
+    }
+}
+
+impl Drop for Outstanding<'_> {
+    fn drop(&mut self) {
+        self.pool
+            .update(self.addr, |server| server.outstanding -= 1);
+    }
+}
+
+fn is_server_failure(e: &ClientError) -> bool {
+    match e {
+        // the local failure doesn't change by the server
+        ClientError::FailedUdpSocketBindingError(_) => false,
+        e => matches!(e.get_kind(), ErrorKind::Timeout | ErrorKind::Io),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::client::pool::{
+        LeastOutstanding, PoolServerState, RoundRobin, SelectionStrategy, ServerPool, SessionHash,
+    };
+    use crate::client::Client;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+
+    fn states(outstanding: &[usize]) -> Vec<PoolServerState> {
+        outstanding
+            .iter()
+            .enumerate()
+            .map(|(i, outstanding)| PoolServerState {
+                addr: SocketAddr::from(([192, 0, 2, i as u8 + 1], 1812)),
+                outstanding: *outstanding,
+                dead_until: None,
+                dead: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_strategies() {
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let candidates = states(&[2, 0, 1]);
+
+        let round_robin = RoundRobin::new();
+        let selected: Vec<_> = (0..4)
+            .map(|_| round_robin.select(&request, &candidates))
+            .collect();
+        assert_eq!(selected, vec![0, 1, 2, 0]);
+
+        assert_eq!(LeastOutstanding::new().select(&request, &candidates), 1);
+
+        let session_hash = SessionHash::new();
+        let mut alice = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut alice, "alice");
+        let selected = session_hash.select(&alice, &candidates);
+        for _ in 0..8 {
+            assert_eq!(session_hash.select(&alice, &candidates), selected);
+        }
+        // the sessions of the other servers stay when a server goes down
+        let mut without_one = candidates.clone();
+        without_one.remove((selected + 1) % 3);
+        let addr = candidates[selected].get_addr();
+        assert_eq!(
+            without_one[session_hash.select(&alice, &without_one)].get_addr(),
+            addr
+        );
+        let mut station = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_calling_station_id(&mut station, "02-00-00-00-00-01");
+        let selected = session_hash.select(&station, &candidates);
+        assert_eq!(session_hash.select(&station, &candidates), selected);
+    }
+
+    #[tokio::test]
+    async fn test_server_pool() {
+        // nobody answers on the first server
+        let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            loop {
+                let (len, from) = server.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                let response = request.make_response_packet(Code::AccessAccept);
+                server
+                    .send_to(&response.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let mut client = Client::new(None, Some(Duration::from_millis(100)));
+        client.set_max_retransmissions(0);
+        let mut pool = ServerPool::new(
+            client,
+            &[silent.local_addr().unwrap(), server_addr],
+            RoundRobin::new(),
+        );
+        pool.set_dead_time(None);
+        for _ in 0..3 {
+            let response = pool
+                .send_packet(&Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+
+        let states = pool.get_server_states();
+        assert!(states[0].is_dead());
+        assert!(!states[1].is_dead());
+        assert!(states.iter().all(|state| state.get_outstanding() == 0));
+    }
+}
@@ -0,0 +1,591 @@
+//! Configuration file of the RADIUS server.
+//!
+//! `ServerConfig` is a schema of TOML (`.toml`) or YAML (`.yaml`/`.yml`) that covers the listeners, the clients
+//! (i.e. the NASes and their shared secrets), the realms and their upstreams, and the policy toggles of the server.
+//! `Server::from_config()` bootstraps the servers by that; the realms are available for the request handler
+//! through `ServerConfig::lookup_realm()`.
+//! `radsecproxy` imports an existing radsecproxy configuration into `ServerConfig`.
+//!
+//! ## Example
+//!
+//! ```toml
+//! [[listeners]]
+//! host = "0.0.0.0"
+//! port = 1812
+//!
+//! [[clients]]
+//! name = "office"
+//! address = "192.0.2.0/24"
+//! secret = "secret"
+//!
+//! [[realms]]
+//! name = "example.com"
+//! strip = true
+//! upstreams = [{ address = "198.51.100.1:1812", secret = "upstream-secret" }]
+//!
+//! [policy]
+//! skip_authenticity_validation = false
+//! require_message_authenticator = true
+//! event_timestamp_window = 300
+//! ```
+
+pub mod radsecproxy;
+
+use std::fs;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::error::ErrorKind;
+use crate::server::cidr::{CidrSecretProvider, IpNetwork};
+use crate::server::{SecretProvider, SecretProviderError};
+
+const DEFAULT_HOST: &str = "0.0.0.0";
+const DEFAULT_REALM_NAME: &str = "DEFAULT";
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    /// An error that is raised when reading a configuration file has been failed.
+    #[error("failed to read the configuration file {0}: {1}")]
+    FailedReadingError(String, String),
+    /// An error that is raised when the format of a configuration file cannot be determined by the extension.
+    #[error("unsupported configuration format (must be .toml, .yaml or .yml): {0}")]
+    UnsupportedFormatError(String),
+    /// An error that is raised when parsing a configuration has been failed.
+    #[error("failed to parse the configuration: {0}")]
+    ParsingError(String),
+    /// An error that is raised when a configuration is invalid.
+    #[error("invalid configuration: {0}")]
+    InvalidConfigError(String),
+    /// An error that is raised when listening on an address has been failed.
+    #[error("failed to listen on {0}: {1}")]
+    FailedListeningError(String, String),
+}
+
+impl ConfigError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            ConfigError::FailedListeningError(_, _) => ErrorKind::Io,
+            ConfigError::FailedReadingError(_, _)
+            | ConfigError::UnsupportedFormatError(_)
+            | ConfigError::ParsingError(_)
+            | ConfigError::InvalidConfigError(_) => ErrorKind::Configuration,
+        }
+    }
+}
+
+/// The configuration of a RADIUS server.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerConfig {
+    listeners: Vec<ListenerConfig>,
+    #[serde(default)]
+    clients: Vec<ClientConfig>,
+    #[serde(default)]
+    realms: Vec<RealmConfig>,
+    #[serde(default)]
+    policy: PolicyConfig,
+}
+
+/// An address to listen.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListenerConfig {
+    #[serde(default = "default_host")]
+    host: String,
+    port: u16,
+}
+
+/// A client (e.g. a NAS) that is allowed to send the requests, and its shared secret.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+    #[serde(default)]
+    name: Option<String>,
+    address: String,
+    secret: String,
+}
+
+/// A realm (i.e. the part after `@` of User-Name) and the upstream servers that handle that.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RealmConfig {
+    name: String,
+    #[serde(default)]
+    strip: bool,
+    #[serde(default)]
+    upstreams: Vec<UpstreamConfig>,
+}
+
+/// An upstream server of a realm.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpstreamConfig {
+    address: SocketAddr,
+    secret: String,
+}
+
+/// The policy toggles of the server.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyConfig {
+    #[serde(default)]
+    buffer_size: Option<usize>,
+    #[serde(default)]
+    skip_authenticity_validation: bool,
+    #[serde(default)]
+    require_message_authenticator: bool,
+    /// in seconds
+    #[serde(default)]
+    event_timestamp_window: Option<u64>,
+}
+
+fn default_host() -> String {
+    DEFAULT_HOST.to_owned()
+}
+
+impl ServerConfig {
+    /// Load a configuration file; the format is determined by the extension (`.toml`, `.yaml` or `.yml`).
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let path_str = path.display().to_string();
+        let content = fs::read_to_string(path)
+            .map_err(|e| ConfigError::FailedReadingError(path_str.clone(), e.to_string()))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml_str(&content),
+            Some("yaml") | Some("yml") => Self::from_yaml_str(&content),
+            _ => Err(ConfigError::UnsupportedFormatError(path_str)),
+        }
+    }
+
+    /// Parse a configuration in TOML.
+    pub fn from_toml_str(content: &str) -> Result<Self, ConfigError> {
+        let config: ServerConfig =
+            toml::from_str(content).map_err(|e| ConfigError::ParsingError(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse a configuration in YAML.
+    pub fn from_yaml_str(content: &str) -> Result<Self, ConfigError> {
+        let config: ServerConfig =
+            serde_yaml::from_str(content).map_err(|e| ConfigError::ParsingError(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.listeners.is_empty() {
+            return Err(ConfigError::InvalidConfigError(
+                "at least one listener is required".to_owned(),
+            ));
+        }
+        for client in &self.clients {
+            client.parse_address()?;
+        }
+        Ok(())
+    }
+
+    pub fn get_listeners(&self) -> &[ListenerConfig] {
+        &self.listeners
+    }
+
+    pub fn get_clients(&self) -> &[ClientConfig] {
+        &self.clients
+    }
+
+    pub fn get_realms(&self) -> &[RealmConfig] {
+        &self.realms
+    }
+
+    pub fn get_policy(&self) -> &PolicyConfig {
+        &self.policy
+    }
+
+    /// Lookup the realm of a User-Name (e.g. `user@example.com`); the realm names are compared case-insensitively.
+    ///
+    /// If there is no realm of that name (or the User-Name doesn't have a realm), this returns the realm named `DEFAULT` if that exists.
+    pub fn lookup_realm(&self, user_name: &str) -> Option<&RealmConfig> {
+        let realm_name = user_name.rsplit_once('@').map(|(_, realm)| realm);
+        realm_name
+            .and_then(|realm_name| {
+                self.realms
+                    .iter()
+                    .find(|realm| realm.name.eq_ignore_ascii_case(realm_name))
+            })
+            .or_else(|| {
+                self.realms
+                    .iter()
+                    .find(|realm| realm.name == DEFAULT_REALM_NAME)
+            })
+    }
+
+    /// Make a `SecretProvider` that serves the secrets of `clients`.
+    pub fn secret_provider(&self) -> Result<ClientsSecretProvider, ConfigError> {
+        ClientsSecretProvider::new(&self.clients)
+    }
+}
+
+impl ListenerConfig {
+    pub fn get_host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn get_port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl ClientConfig {
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the address of the client; that is an IP address or a network in CIDR notation (e.g. `192.0.2.0/24`).
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn get_secret(&self) -> &str {
+        &self.secret
+    }
+
+    fn parse_address(&self) -> Result<IpNetwork, ConfigError> {
+        self.address.parse().map_err(|_| {
+            ConfigError::InvalidConfigError(format!("invalid client address: {}", self.address))
+        })
+    }
+}
+
+impl RealmConfig {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns whether the realm has to be stripped from User-Name before forwarding the request to the upstream.
+    pub fn is_strip(&self) -> bool {
+        self.strip
+    }
+
+    pub fn get_upstreams(&self) -> &[UpstreamConfig] {
+        &self.upstreams
+    }
+
+    /// Returns User-Name to send to the upstream, i.e. that doesn't have the realm if `strip` is enabled.
+    pub fn rewrite_user_name<'a>(&self, user_name: &'a str) -> &'a str {
+        match user_name.rsplit_once('@') {
+            Some((user, _)) if self.strip => user,
+            _ => user_name,
+        }
+    }
+}
+
+impl UpstreamConfig {
+    pub fn get_address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn get_secret(&self) -> &str {
+        &self.secret
+    }
+}
+
+impl PolicyConfig {
+    pub fn get_buffer_size(&self) -> Option<usize> {
+        self.buffer_size
+    }
+
+    pub fn is_skip_authenticity_validation(&self) -> bool {
+        self.skip_authenticity_validation
+    }
+
+    pub fn is_require_message_authenticator(&self) -> bool {
+        self.require_message_authenticator
+    }
+
+    pub fn get_event_timestamp_window(&self) -> Option<Duration> {
+        self.event_timestamp_window.map(Duration::from_secs)
+    }
+}
+
+/// A `SecretProvider` that serves the secrets of the configured clients.
+///
+/// When the networks of the clients overlap, the longest prefix wins.
+/// The clients can be replaced at runtime by `reload()`, e.g. when the configuration file has been updated.
+#[derive(Debug)]
+pub struct ClientsSecretProvider {
+    table: RwLock<ClientTable>,
+}
+
+#[derive(Debug, Clone)]
+struct ClientTable {
+    configs: Vec<ClientConfig>,
+    networks: CidrSecretProvider,
+}
+
+impl ClientTable {
+    fn new(clients: &[ClientConfig]) -> Result<Self, ConfigError> {
+        let mut networks = CidrSecretProvider::new();
+        // the first one of the same network wins
+        for client in clients.iter().rev() {
+            networks.insert(client.parse_address()?, client.secret.as_bytes());
+        }
+        Ok(ClientTable {
+            configs: clients.to_vec(),
+            networks,
+        })
+    }
+}
+
+impl Clone for ClientsSecretProvider {
+    fn clone(&self) -> Self {
+        ClientsSecretProvider {
+            table: RwLock::new(self.table.read().unwrap().clone()),
+        }
+    }
+}
+
+impl ClientsSecretProvider {
+    pub fn new(clients: &[ClientConfig]) -> Result<Self, ConfigError> {
+        Ok(ClientsSecretProvider {
+            table: RwLock::new(ClientTable::new(clients)?),
+        })
+    }
+
+    /// Replace the clients; if any of the clients is invalid, this keeps the current ones and returns the error.
+    pub fn reload(&self, clients: &[ClientConfig]) -> Result<(), ConfigError> {
+        let table = ClientTable::new(clients)?;
+        *self.table.write().unwrap() = table;
+        Ok(())
+    }
+
+    /// Returns the current clients.
+    pub fn get_clients(&self) -> Vec<ClientConfig> {
+        self.table.read().unwrap().configs.clone()
+    }
+}
+
+impl SecretProvider for ClientsSecretProvider {
+    fn fetch_secret(&self, remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        self.table
+            .read()
+            .unwrap()
+            .networks
+            .fetch_secret(remote_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+
+    use crate::client::Client;
+    use crate::config::{ConfigError, ServerConfig};
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::server::{RequestHandler, SecretProvider, Server};
+
+    const TOML_CONFIG: &str = r#"
+[[listeners]]
+port = 1812
+
+[[listeners]]
+host = "127.0.0.1"
+port = 1813
+
+[[clients]]
+name = "office"
+address = "192.0.2.0/24"
+secret = "office-secret"
+
+[[clients]]
+address = "192.0.2.10"
+secret = "nas-secret"
+
+[[realms]]
+name = "example.com"
+strip = true
+upstreams = [{ address = "198.51.100.1:1812", secret = "upstream-secret" }]
+
+[[realms]]
+name = "DEFAULT"
+
+[policy]
+skip_authenticity_validation = true
+require_message_authenticator = true
+event_timestamp_window = 300
+"#;
+
+    #[test]
+    fn test_toml_config() {
+        let config = ServerConfig::from_toml_str(TOML_CONFIG).unwrap();
+
+        let listeners = config.get_listeners();
+        assert_eq!(listeners.len(), 2);
+        assert_eq!(listeners[0].get_host(), "0.0.0.0");
+        assert_eq!(listeners[1].get_port(), 1813);
+
+        let policy = config.get_policy();
+        assert!(policy.is_skip_authenticity_validation());
+        assert!(policy.is_require_message_authenticator());
+        assert_eq!(
+            policy.get_event_timestamp_window(),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(policy.get_buffer_size(), None);
+
+        let realm = config.lookup_realm("user@EXAMPLE.com").unwrap();
+        assert_eq!(realm.get_name(), "example.com");
+        assert_eq!(realm.rewrite_user_name("user@example.com"), "user");
+        assert_eq!(
+            realm.get_upstreams()[0].get_address(),
+            "198.51.100.1:1812".parse().unwrap()
+        );
+        assert_eq!(config.lookup_realm("user").unwrap().get_name(), "DEFAULT");
+        assert_eq!(
+            config
+                .lookup_realm("user@other.example")
+                .unwrap()
+                .get_name(),
+            "DEFAULT"
+        );
+    }
+
+    #[test]
+    fn test_yaml_config() {
+        let config = ServerConfig::from_yaml_str(
+            r#"
+listeners:
+  - port: 1812
+clients:
+  - address: "::1"
+    secret: secret
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.get_listeners()[0].get_port(), 1812);
+        assert_eq!(config.get_clients()[0].get_secret(), "secret");
+        assert!(config.lookup_realm("user@example.com").is_none());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(matches!(
+            ServerConfig::from_toml_str("listeners = []"),
+            Err(ConfigError::InvalidConfigError(_))
+        ));
+        assert!(matches!(
+            ServerConfig::from_toml_str(
+                "[[listeners]]\nport = 1812\n[[clients]]\naddress = \"192.0.2.0/33\"\nsecret = \"s\""
+            ),
+            Err(ConfigError::InvalidConfigError(_))
+        ));
+        assert!(matches!(
+            ServerConfig::from_toml_str("[[listeners]]\nport = 1812\nunknown = 1"),
+            Err(ConfigError::ParsingError(_))
+        ));
+        assert!(matches!(
+            ServerConfig::load("config.ini"),
+            Err(ConfigError::FailedReadingError(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_clients_secret_provider() {
+        let provider = ServerConfig::from_toml_str(TOML_CONFIG)
+            .unwrap()
+            .secret_provider()
+            .unwrap();
+
+        assert_eq!(
+            provider
+                .fetch_secret("192.0.2.10:5000".parse().unwrap())
+                .unwrap(),
+            b"nas-secret"
+        );
+        assert_eq!(
+            provider
+                .fetch_secret("192.0.2.11:5000".parse().unwrap())
+                .unwrap(),
+            b"office-secret"
+        );
+        assert_eq!(
+            provider
+                .fetch_secret("[::ffff:192.0.2.11]:5000".parse().unwrap())
+                .unwrap(),
+            b"office-secret"
+        );
+        assert!(provider
+            .fetch_secret("198.51.100.1:5000".parse().unwrap())
+            .is_err());
+    }
+
+    struct AcceptHandler {}
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for AcceptHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let response = req.get_packet().make_response_packet(Code::AccessAccept);
+            conn.send_to(&response.encode().unwrap(), req.get_remote_addr())
+                .await?;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_server_from_config() {
+        let dir = std::env::temp_dir().join(format!("radius-config-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("radius.yaml");
+        fs::write(
+            &path,
+            r#"
+listeners:
+  - host: 127.0.0.1
+    port: 0
+  - host: 127.0.0.1
+    port: 0
+clients:
+  - address: 127.0.0.0/8
+    secret: secret
+"#,
+        )
+        .unwrap();
+
+        let servers = Server::from_config(&path, AcceptHandler {}).await.unwrap();
+        assert_eq!(servers.len(), 2);
+        let addrs: Vec<_> = servers
+            .iter()
+            .map(|server| server.get_listen_address().unwrap())
+            .collect();
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server_task = tokio::spawn(async move { Server::run_all(&servers, shutdown_rx).await });
+
+        let client = Client::new(None, Some(Duration::from_secs(3)));
+        for addr in addrs {
+            let request = Packet::new(Code::AccessRequest, b"secret");
+            let response = client.send_packet(&addr, &request).await.unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+
+        shutdown_tx.send(()).unwrap();
+        server_task.await.unwrap().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,708 @@
+//! Import of the [radsecproxy](https://radsecproxy.github.io/) configuration.
+//!
+//! This maps the most common constructs of `radsecproxy.conf` onto `ServerConfig`, to migrate a proxy:
+//!
+//! - `ListenUDP` becomes a listener (`*:1812` if there is none)
+//! - the `client` blocks of `type udp` become the clients; their `host`s have to be IP addresses or networks in CIDR notation
+//! - the `realm` blocks become the realms, and their `server`s (the `server` blocks of `type udp`) become the upstreams.
+//!   `*` becomes `DEFAULT`, and the regular expressions of an exact realm (e.g. `/@example\.com$/`) become the realm names
+//! - the `rewrite` blocks that strip the realm from User-Name (e.g. `modifyAttribute 1:/^(.*)@.*$/\1/`) become `strip`
+//!   of the realms whose servers have those as `rewriteOut` (or as `defaultServer`)
+//!
+//! The other constructs (e.g. TLS, the host names, the other rewrites and the global options) are not imported;
+//! those are reported as the warnings to review.
+//!
+//! ## Example
+//!
+//! ```
+//! use radius::config::radsecproxy;
+//!
+//! let import = radsecproxy::import_str(r#"
+//! ListenUDP *:1812
+//!
+//! client 192.0.2.0/24 {
+//!     type udp
+//!     secret office-secret
+//! }
+//!
+//! server 198.51.100.1 {
+//!     type udp
+//!     secret upstream-secret
+//! }
+//!
+//! realm example.com {
+//!     server 198.51.100.1
+//! }
+//! "#).unwrap();
+//! let config = import.get_config();
+//! assert_eq!(config.lookup_realm("user@example.com").unwrap().get_upstreams().len(), 1);
+//! assert!(import.get_warnings().is_empty());
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+
+use crate::config::{
+    ClientConfig, ConfigError, ListenerConfig, PolicyConfig, RealmConfig, ServerConfig,
+    UpstreamConfig, DEFAULT_HOST, DEFAULT_REALM_NAME,
+};
+
+const DEFAULT_PORT: u16 = 1812;
+const DEFAULT_SERVER_REWRITE: &str = "defaultServer";
+const BLOCK_KINDS: &[&str] = &["client", "server", "realm", "rewrite", "tls"];
+const STRIP_REALM_PATTERNS: &[&str] = &["^(.*)@.*$", "^([^@]*)@.*$", "^([^@]+)@.*$", "(.*)@.*"];
+
+/// A configuration that has been imported from radsecproxy, and the warnings about the constructs that were not imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadsecproxyImport {
+    config: ServerConfig,
+    warnings: Vec<String>,
+}
+
+impl RadsecproxyImport {
+    pub fn get_config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    pub fn into_config(self) -> ServerConfig {
+        self.config
+    }
+
+    /// Returns the warnings about the constructs that were not imported; each one has the line number.
+    pub fn get_warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
+
+/// Import a radsecproxy configuration file (e.g. `/etc/radsecproxy.conf`).
+///
+/// `Include` is not followed; import the included files separately.
+pub fn import_file<P: AsRef<Path>>(path: P) -> Result<RadsecproxyImport, ConfigError> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)
+        .map_err(|e| ConfigError::FailedReadingError(path.display().to_string(), e.to_string()))?;
+    import_str(&content)
+}
+
+/// Import a radsecproxy configuration.
+pub fn import_str(content: &str) -> Result<RadsecproxyImport, ConfigError> {
+    let parsed = parse(content)?;
+    let mut warnings = Vec::new();
+
+    let mut listeners = Vec::new();
+    for option in &parsed.options {
+        match option.key.as_str() {
+            "listenudp" => match split_host_port(&option.value) {
+                Some((host, port)) => listeners.push(ListenerConfig {
+                    host: match host {
+                        "*" => DEFAULT_HOST.to_owned(),
+                        host => host.to_owned(),
+                    },
+                    port: port.unwrap_or(DEFAULT_PORT),
+                }),
+                None => warnings.push(option.warning("ListenUDP is not an address")),
+            },
+            "listentcp" | "listentls" | "listendtls" => {
+                warnings.push(option.warning("only the UDP listeners are imported"))
+            }
+            "include" => {
+                warnings.push(option.warning("Include is not followed; import that separately"))
+            }
+            _ => warnings.push(option.warning("the global option is not imported")),
+        }
+    }
+    if listeners.is_empty() {
+        listeners.push(ListenerConfig {
+            host: DEFAULT_HOST.to_owned(),
+            port: DEFAULT_PORT,
+        });
+    }
+
+    let rewrites: HashMap<&str, bool> = parsed
+        .blocks_of("rewrite")
+        .map(|block| (block.name.as_str(), import_rewrite(block, &mut warnings)))
+        .collect();
+
+    let mut clients = Vec::new();
+    for block in parsed.blocks_of("client") {
+        clients.extend(import_client(block, &mut warnings)?);
+    }
+
+    let mut servers = HashMap::new();
+    for block in parsed.blocks_of("server") {
+        if let Some(server) = import_server(block, &rewrites, &mut warnings)? {
+            servers.insert(block.name.as_str(), server);
+        }
+    }
+    let server_names: Vec<&str> = parsed
+        .blocks_of("server")
+        .map(|block| block.name.as_str())
+        .collect();
+
+    let mut realms = Vec::new();
+    for block in parsed.blocks_of("realm") {
+        let name = match realm_name(&block.name) {
+            Some(name) => name,
+            None => {
+                warnings.push(block.warning("the realm is not an exact realm name"));
+                continue;
+            }
+        };
+        let mut realm = RealmConfig {
+            name,
+            strip: false,
+            upstreams: Vec::new(),
+        };
+        for option in &block.options {
+            match option.key.as_str() {
+                "server" => match servers.get(option.value.as_str()) {
+                    Some(server) => {
+                        realm.strip |= server.strip;
+                        realm.upstreams.extend(server.upstreams.iter().cloned());
+                    }
+                    None if server_names.contains(&option.value.as_str()) => {
+                        warnings.push(option.warning("the server is not imported"))
+                    }
+                    None => {
+                        return Err(ConfigError::InvalidConfigError(format!(
+                            "line {}: unknown server: {}",
+                            option.line, option.value
+                        )))
+                    }
+                },
+                _ => warnings.push(option.warning("the realm option is not imported")),
+            }
+        }
+        realms.push(realm);
+    }
+
+    for block in parsed.blocks_of("tls") {
+        warnings.push(block.warning("the TLS block is not imported"));
+    }
+
+    let config = ServerConfig {
+        listeners,
+        clients,
+        realms,
+        policy: PolicyConfig::default(),
+    };
+    config.validate()?;
+    Ok(RadsecproxyImport { config, warnings })
+}
+
+struct ImportedServer {
+    upstreams: Vec<UpstreamConfig>,
+    strip: bool,
+}
+
+fn import_client(
+    block: &Block,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<ClientConfig>, ConfigError> {
+    if !block.is_udp(warnings) {
+        return Ok(vec![]);
+    }
+    let secret = block.secret()?;
+
+    let mut hosts = Vec::new();
+    for option in &block.options {
+        match option.key.as_str() {
+            "host" => hosts.push((option.value.as_str(), option.line)),
+            "type" | "secret" => {}
+            _ => warnings.push(option.warning("the client option is not imported")),
+        }
+    }
+    if hosts.is_empty() {
+        hosts.push((block.name.as_str(), block.line));
+    }
+
+    let mut clients = Vec::new();
+    for (host, line) in hosts {
+        let client = ClientConfig {
+            name: Some(block.name.clone()),
+            address: host.to_owned(),
+            secret: secret.to_owned(),
+        };
+        match client.parse_address() {
+            Ok(_) => clients.push(client),
+            Err(_) => warnings.push(format!(
+                "line {line}: the client host is not an IP address or a network: {host}"
+            )),
+        }
+    }
+    Ok(clients)
+}
+
+fn import_server(
+    block: &Block,
+    rewrites: &HashMap<&str, bool>,
+    warnings: &mut Vec<String>,
+) -> Result<Option<ImportedServer>, ConfigError> {
+    if !block.is_udp(warnings) {
+        return Ok(None);
+    }
+    let secret = block.secret()?;
+
+    let mut hosts = Vec::new();
+    let mut port = DEFAULT_PORT;
+    let mut rewrite = None;
+    for option in &block.options {
+        match option.key.as_str() {
+            "host" => hosts.push((option.value.as_str(), option.line)),
+            "port" => {
+                port = option.value.parse().map_err(|_| {
+                    ConfigError::InvalidConfigError(format!(
+                        "line {}: invalid port: {}",
+                        option.line, option.value
+                    ))
+                })?
+            }
+            "rewriteout" | "rewrite" => rewrite = Some(option),
+            "type" | "secret" => {}
+            _ => warnings.push(option.warning("the server option is not imported")),
+        }
+    }
+    if hosts.is_empty() {
+        hosts.push((block.name.as_str(), block.line));
+    }
+
+    let strip = match rewrite {
+        Some(option) => match rewrites.get(option.value.as_str()) {
+            Some(strip) => *strip,
+            None => {
+                return Err(ConfigError::InvalidConfigError(format!(
+                    "line {}: unknown rewrite: {}",
+                    option.line, option.value
+                )))
+            }
+        },
+        None => rewrites
+            .get(DEFAULT_SERVER_REWRITE)
+            .copied()
+            .unwrap_or(false),
+    };
+
+    let mut upstreams = Vec::new();
+    for (host, line) in hosts {
+        let address = split_host_port(host).and_then(|(addr, host_port)| {
+            let addr: IpAddr = addr.parse().ok()?;
+            Some(SocketAddr::new(addr, host_port.unwrap_or(port)))
+        });
+        match address {
+            Some(address) => upstreams.push(UpstreamConfig {
+                address,
+                secret: secret.to_owned(),
+            }),
+            None => warnings.push(format!(
+                "line {line}: the server host is not an IP address: {host}"
+            )),
+        }
+    }
+    Ok(Some(ImportedServer { upstreams, strip }))
+}
+
+/// Returns whether a rewrite strips the realm from User-Name.
+fn import_rewrite(block: &Block, warnings: &mut Vec<String>) -> bool {
+    let mut strip = false;
+    for option in &block.options {
+        let is_strip = option.key == "modifyattribute"
+            && option
+                .value
+                .split_once(':')
+                .and_then(|(attribute, expression)| {
+                    let (pattern, replacement) = expression
+                        .strip_prefix('/')?
+                        .strip_suffix('/')?
+                        .rsplit_once('/')?;
+                    Some(
+                        (attribute == "1" || attribute.eq_ignore_ascii_case("User-Name"))
+                            && STRIP_REALM_PATTERNS.contains(&pattern)
+                            && replacement == "\\1",
+                    )
+                })
+                .unwrap_or(false);
+        match is_strip {
+            true => strip = true,
+            false => warnings.push(option.warning("the rewrite is not imported")),
+        }
+    }
+    strip
+}
+
+/// Returns the name of `RealmConfig` of a radsecproxy realm; the regular expressions have to match an exact realm.
+fn realm_name(name: &str) -> Option<String> {
+    if name == "*" {
+        return Some(DEFAULT_REALM_NAME.to_owned());
+    }
+    let pattern = match name.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+        Some(pattern) => pattern,
+        None => return Some(name.to_owned()),
+    };
+    let realm = pattern
+        .strip_suffix('$')?
+        .trim_start_matches("^.*")
+        .trim_start_matches(".*")
+        .strip_prefix('@')?
+        .replace("\\.", ".");
+    match realm.contains(|c| "^$.*+?()[]{}|\\".contains(c) && c != '.') || realm.is_empty() {
+        true => None,
+        false => Some(realm),
+    }
+}
+
+/// Splits `host`, `host:port`, `[v6]` or `[v6]:port`; the port is `None` if that is missing.
+fn split_host_port(value: &str) -> Option<(&str, Option<u16>)> {
+    let (host, port) = match value.strip_prefix('[') {
+        Some(rest) => {
+            let (host, rest) = rest.split_once(']')?;
+            match rest {
+                "" => (host, None),
+                rest => (host, Some(rest.strip_prefix(':')?)),
+            }
+        }
+        None => match value.rsplit_once(':') {
+            Some((host, port)) if !host.contains(':') => (host, Some(port)),
+            _ => (value, None),
+        },
+    };
+    let port = match port {
+        Some(port) => Some(port.parse().ok()?),
+        None => None,
+    };
+    Some((host, port))
+}
+
+struct ConfOption {
+    key: String,
+    value: String,
+    line: usize,
+}
+
+impl ConfOption {
+    fn warning(&self, message: &str) -> String {
+        format!(
+            "line {}: {}: {} {}",
+            self.line, message, self.key, self.value
+        )
+    }
+}
+
+struct Block {
+    kind: String,
+    name: String,
+    line: usize,
+    options: Vec<ConfOption>,
+}
+
+impl Block {
+    fn warning(&self, message: &str) -> String {
+        format!(
+            "line {}: {}: {} {}",
+            self.line, message, self.kind, self.name
+        )
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|option| option.key == key)
+            .map(|option| option.value.as_str())
+    }
+
+    fn is_udp(&self, warnings: &mut Vec<String>) -> bool {
+        match self.get("type") {
+            Some(typ) if typ.eq_ignore_ascii_case("udp") => true,
+            Some(_) => {
+                warnings.push(self.warning("only the UDP blocks are imported"));
+                false
+            }
+            None => {
+                warnings.push(self.warning("the block doesn't have type"));
+                false
+            }
+        }
+    }
+
+    fn secret(&self) -> Result<&str, ConfigError> {
+        self.get("secret").ok_or_else(|| {
+            ConfigError::InvalidConfigError(format!(
+                "line {}: {} {} doesn't have secret",
+                self.line, self.kind, self.name
+            ))
+        })
+    }
+}
+
+struct Parsed {
+    options: Vec<ConfOption>,
+    blocks: Vec<Block>,
+}
+
+impl Parsed {
+    fn blocks_of<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a Block> + 'a {
+        self.blocks.iter().filter(move |block| block.kind == kind)
+    }
+}
+
+fn parse(content: &str) -> Result<Parsed, ConfigError> {
+    let error =
+        |line: usize, message: &str| ConfigError::ParsingError(format!("line {line}: {message}"));
+
+    let mut parsed = Parsed {
+        options: Vec::new(),
+        blocks: Vec::new(),
+    };
+    let mut current: Option<Block> = None;
+    let mut pending: Option<Block> = None;
+    for (i, line) in content.lines().enumerate() {
+        let line_number = i + 1;
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(block) = pending.take() {
+            if line != "{" {
+                return Err(error(block.line, "the block doesn't have {"));
+            }
+            current = Some(block);
+            continue;
+        }
+
+        if line == "}" {
+            match current.take() {
+                Some(block) => parsed.blocks.push(block),
+                None => return Err(error(line_number, "unexpected }")),
+            }
+            continue;
+        }
+
+        let (key, value) = split_option(line.strip_suffix('{').unwrap_or(line).trim());
+        let is_block = current.is_none()
+            && BLOCK_KINDS
+                .iter()
+                .any(|kind| kind.eq_ignore_ascii_case(&key));
+        if is_block {
+            if value.is_empty() {
+                return Err(error(line_number, "the block doesn't have a name"));
+            }
+            let block = Block {
+                kind: key,
+                name: value,
+                line: line_number,
+                options: Vec::new(),
+            };
+            match line.ends_with('{') {
+                true => current = Some(block),
+                false => pending = Some(block),
+            }
+            continue;
+        }
+        if line.ends_with('{') {
+            return Err(error(line_number, "unexpected {"));
+        }
+
+        let option = ConfOption {
+            key,
+            value,
+            line: line_number,
+        };
+        match current.as_mut() {
+            Some(block) => block.options.push(option),
+            None => parsed.options.push(option),
+        }
+    }
+
+    if let Some(block) = current.or(pending) {
+        return Err(error(block.line, "the block is not closed"));
+    }
+    Ok(parsed)
+}
+
+/// Splits `key value` or `key = value` into the lowercased key and the unquoted value.
+fn split_option(line: &str) -> (String, String) {
+    let (key, value) = line
+        .split_once(|c: char| c.is_whitespace() || c == '=')
+        .unwrap_or((line, ""));
+    let value = value.trim_start();
+    let value = value.strip_prefix('=').unwrap_or(value).trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+    (key.to_ascii_lowercase(), value.to_owned())
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::radsecproxy;
+    use crate::config::ConfigError;
+    use crate::server::SecretProvider;
+
+    const RADSECPROXY_CONFIG: &str = r#"
+# radsecproxy.conf
+ListenUDP *:1812
+ListenTLS *:2083
+LogLevel 3
+
+client office {
+    host 192.0.2.0/24
+    host 2001:db8::/32
+    type udp
+    secret "office secret" # quoted
+}
+
+client nas.example.com {
+    type udp
+    secret nas-secret
+}
+
+client radsec
+{
+    host 203.0.113.1
+    type tls
+}
+
+rewrite strip-realm {
+    modifyAttribute 1:/^(.*)@.*$/\1/
+}
+
+server 198.51.100.1 {
+    type udp
+    port = 11812
+    secret upstream-secret
+    rewriteOut strip-realm
+}
+
+server backup {
+    host [2001:db8::1]:1812
+    type udp
+    secret backup-secret
+}
+
+realm /@example\.com$/ {
+    server 198.51.100.1
+    server backup
+}
+
+realm example.org {
+    server backup
+    accountingServer backup
+}
+
+realm * {
+    replyMessage "unknown realm"
+}
+"#;
+
+    #[test]
+    fn test_import() {
+        let import = radsecproxy::import_str(RADSECPROXY_CONFIG).unwrap();
+        let config = import.get_config();
+
+        assert_eq!(config.get_listeners().len(), 1);
+        assert_eq!(config.get_listeners()[0].get_host(), "0.0.0.0");
+        assert_eq!(config.get_listeners()[0].get_port(), 1812);
+
+        let clients = config.get_clients();
+        assert_eq!(clients.len(), 2);
+        assert_eq!(clients[0].get_name(), Some("office"));
+        assert_eq!(clients[1].get_address(), "2001:db8::/32");
+        assert_eq!(
+            config
+                .secret_provider()
+                .unwrap()
+                .fetch_secret("192.0.2.1:5000".parse().unwrap())
+                .unwrap(),
+            b"office secret"
+        );
+
+        let realm = config.lookup_realm("user@EXAMPLE.com").unwrap();
+        assert_eq!(realm.get_name(), "example.com");
+        assert!(realm.is_strip());
+        let upstreams = realm.get_upstreams();
+        assert_eq!(upstreams.len(), 2);
+        assert_eq!(
+            upstreams[0].get_address(),
+            "198.51.100.1:11812".parse().unwrap()
+        );
+        assert_eq!(
+            upstreams[1].get_address(),
+            "[2001:db8::1]:1812".parse().unwrap()
+        );
+        assert_eq!(upstreams[1].get_secret(), "backup-secret");
+
+        let realm = config.lookup_realm("user@example.org").unwrap();
+        assert!(!realm.is_strip());
+        assert_eq!(realm.get_upstreams().len(), 1);
+        assert_eq!(
+            config
+                .lookup_realm("user@other.example")
+                .unwrap()
+                .get_name(),
+            "DEFAULT"
+        );
+
+        let warnings = import.get_warnings();
+        assert_eq!(warnings.len(), 6, "{warnings:?}");
+        assert!(warnings[0].starts_with("line 4:"));
+        assert!(warnings.iter().any(|w| w.contains("nas.example.com")));
+        assert!(warnings.iter().any(|w| w.contains("accountingserver")));
+    }
+
+    #[test]
+    fn test_import_realm_names() {
+        let import = radsecproxy::import_str(
+            r#"
+realm /^.*@example\.net$/ {
+}
+realm /\.example\.com$/ {
+}
+realm /@(a|b)\.example$/ {
+}
+"#,
+        )
+        .unwrap();
+        let realms = import.get_config().get_realms();
+        assert_eq!(realms.len(), 1);
+        assert_eq!(realms[0].get_name(), "example.net");
+        assert_eq!(import.get_warnings().len(), 2);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        for (content, expected_line) in [
+            ("client 192.0.2.1 {\n type udp\n", "line 1:"),
+            ("}", "line 1:"),
+            ("client 192.0.2.1\nsecret s\n", "line 1:"),
+            ("client 192.0.2.1 {\n type udp\n}\n", "line 1:"),
+            ("realm example.com {\n server unknown\n}\n", "line 2:"),
+            (
+                "server 192.0.2.1 {\n type udp\n secret s\n port none\n}\n",
+                "line 4:",
+            ),
+        ] {
+            match radsecproxy::import_str(content) {
+                Err(ConfigError::ParsingError(message))
+                | Err(ConfigError::InvalidConfigError(message)) => {
+                    assert!(message.starts_with(expected_line), "{content}: {message}")
+                }
+                result => panic!("{content}: {result:?}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Utility for the accounting packets.
+//!
+//! These helpers work across the accounting related dictionaries (e.g. rfc2866 and rfc2869)
+//! and take care of the details that are easy to get wrong when handling accounting records.
+
+use alloc::format;
+use core::convert::TryFrom;
+use core::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::core::avp::AVPError;
+use crate::core::packet::Packet;
+use crate::core::rfc2866;
+use crate::core::rfc2869;
+
+/// Replace all of the `acct_delay_time` values in a packet with the given value.
+pub fn set_acct_delay_time(packet: &mut Packet, value: u32) {
+    rfc2866::delete_acct_delay_time(packet);
+    rfc2866::add_acct_delay_time(packet, value);
+}
+
+/// Update `acct_delay_time` of a packet to `initial_delay_time` plus the given elapsed time.
+///
+/// Acct-Delay-Time indicates how many seconds the client has been trying to send the record,
+/// so this has to be refreshed every time the record gets retransmitted.
+/// The value saturates at `u32::MAX`.
+///
+/// see also: https://tools.ietf.org/html/rfc2866#section-5.2
+pub fn update_acct_delay_time(packet: &mut Packet, initial_delay_time: u32, elapsed: Duration) {
+    let elapsed_secs = u32::try_from(elapsed.as_secs()).unwrap_or(u32::MAX);
+    set_acct_delay_time(packet, initial_delay_time.saturating_add(elapsed_secs));
+}
+
+/// Lookup the `acct_delay_time` value from a packet, and it returns `0` if the packet doesn't have that.
+pub fn lookup_acct_delay_time_or_zero(packet: &Packet) -> Result<u32, AVPError> {
+    rfc2866::lookup_acct_delay_time(packet).unwrap_or(Ok(0))
+}
+
+/// Add `acct_input_octets` and `acct_input_gigawords` values to a packet by splitting the given 64-bit counter.
+///
+/// The lower 32 bits go to Acct-Input-Octets and the upper 32 bits go to Acct-Input-Gigawords.
+///
+/// see also: https://tools.ietf.org/html/rfc2869#section-5.1
+pub fn add_acct_input_octets_u64(packet: &mut Packet, value: u64) {
+    let (gigawords, octets) = split_u64_counter(value);
+    rfc2866::add_acct_input_octets(packet, octets);
+    rfc2869::add_acct_input_gigawords(packet, gigawords);
+}
+
+/// Replace all of the `acct_input_octets` and `acct_input_gigawords` values in a packet with the given 64-bit counter.
+pub fn set_acct_input_octets_u64(packet: &mut Packet, value: u64) {
+    rfc2866::delete_acct_input_octets(packet);
+    rfc2869::delete_acct_input_gigawords(packet);
+    add_acct_input_octets_u64(packet, value);
+}
+
+/// Lookup the `acct_input_octets` value combined with the `acct_input_gigawords` value from a packet as a 64-bit counter.
+///
+/// It returns `None` if there is no associated value with `acct_input_octets`.
+/// A missing `acct_input_gigawords` is treated as `0`, and an `acct_input_gigawords` without `acct_input_octets` is an
+/// error (its upper 32 bits would be lost).
+pub fn lookup_acct_input_octets_u64(packet: &Packet) -> Option<Result<u64, AVPError>> {
+    lookup_u64_counter(
+        rfc2866::lookup_acct_input_octets(packet),
+        rfc2869::lookup_acct_input_gigawords(packet),
+        "Acct-Input",
+    )
+}
+
+/// Add `acct_output_octets` and `acct_output_gigawords` values to a packet by splitting the given 64-bit counter.
+///
+/// The lower 32 bits go to Acct-Output-Octets and the upper 32 bits go to Acct-Output-Gigawords.
+///
+/// see also: https://tools.ietf.org/html/rfc2869#section-5.2
+pub fn add_acct_output_octets_u64(packet: &mut Packet, value: u64) {
+    let (gigawords, octets) = split_u64_counter(value);
+    rfc2866::add_acct_output_octets(packet, octets);
+    rfc2869::add_acct_output_gigawords(packet, gigawords);
+}
+
+/// Replace all of the `acct_output_octets` and `acct_output_gigawords` values in a packet with the given 64-bit counter.
+pub fn set_acct_output_octets_u64(packet: &mut Packet, value: u64) {
+    rfc2866::delete_acct_output_octets(packet);
+    rfc2869::delete_acct_output_gigawords(packet);
+    add_acct_output_octets_u64(packet, value);
+}
+
+/// Lookup the `acct_output_octets` value combined with the `acct_output_gigawords` value from a packet as a 64-bit counter.
+///
+/// It returns `None` if there is no associated value with `acct_output_octets`.
+/// A missing `acct_output_gigawords` is treated as `0`, and an `acct_output_gigawords` without `acct_output_octets` is
+/// an error (its upper 32 bits would be lost).
+pub fn lookup_acct_output_octets_u64(packet: &Packet) -> Option<Result<u64, AVPError>> {
+    lookup_u64_counter(
+        rfc2866::lookup_acct_output_octets(packet),
+        rfc2869::lookup_acct_output_gigawords(packet),
+        "Acct-Output",
+    )
+}
+
+/// Add `event_timestamp` value to a packet if the packet doesn't have that yet.
+pub fn add_event_timestamp_if_absent(packet: &mut Packet, now: &DateTime<Utc>) {
+    if packet.lookup(rfc2869::EVENT_TIMESTAMP_TYPE).is_none() {
+        rfc2869::add_event_timestamp(packet, now);
+    }
+}
+
+/// Returns whether the `event_timestamp` value of a packet is within `window` around `now` or not.
+///
+/// This can be used to reject the stale (or replayed) packets. A packet that doesn't have `event_timestamp` is regarded as valid.
+pub fn is_event_timestamp_within_window(
+    packet: &Packet,
+    now: &DateTime<Utc>,
+    window: Duration,
+) -> Result<bool, AVPError> {
+    let event_timestamp = match rfc2869::lookup_event_timestamp(packet) {
+        Some(event_timestamp) => event_timestamp?,
+        None => return Ok(true),
+    };
+    let skew = (*now - event_timestamp).num_seconds().unsigned_abs();
+    Ok(skew <= window.as_secs())
+}
+
+fn split_u64_counter(value: u64) -> (u32, u32) {
+    ((value >> 32) as u32, value as u32)
+}
+
+fn lookup_u64_counter(
+    octets: Option<Result<u32, AVPError>>,
+    gigawords: Option<Result<u32, AVPError>>,
+    counter_name: &str,
+) -> Option<Result<u64, AVPError>> {
+    match (octets, gigawords) {
+        (None, None) => None,
+        (None, Some(_)) => Some(Err(AVPError::DecodingError(format!(
+            "{counter_name}-Gigawords without {counter_name}-Octets"
+        )))),
+        (Some(octets), gigawords) => Some(octets.and_then(|octets| {
+            let gigawords = gigawords.unwrap_or(Ok(0))?;
+            Ok((u64::from(gigawords) << 32) | u64::from(octets))
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::accounting::{
+        add_acct_input_octets_u64, add_event_timestamp_if_absent, is_event_timestamp_within_window,
+        lookup_acct_delay_time_or_zero, lookup_acct_input_octets_u64,
+        lookup_acct_output_octets_u64, set_acct_output_octets_u64, update_acct_delay_time,
+    };
+    use crate::core::avp::AVPError;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2866, rfc2869};
+
+    #[test]
+    fn test_update_acct_delay_time() {
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        assert_eq!(lookup_acct_delay_time_or_zero(&packet).unwrap(), 0);
+
+        update_acct_delay_time(&mut packet, 0, Duration::from_millis(3500));
+        assert_eq!(
+            rfc2866::lookup_all_acct_delay_time(&packet).unwrap(),
+            vec![3]
+        );
+
+        update_acct_delay_time(&mut packet, 10, Duration::from_secs(5));
+        assert_eq!(
+            rfc2866::lookup_all_acct_delay_time(&packet).unwrap(),
+            vec![15]
+        );
+
+        update_acct_delay_time(&mut packet, u32::MAX - 1, Duration::from_secs(5));
+        assert_eq!(
+            rfc2866::lookup_all_acct_delay_time(&packet).unwrap(),
+            vec![u32::MAX]
+        );
+    }
+
+    #[test]
+    fn test_authenticator_changes_with_acct_delay_time() {
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_session_id(&mut packet, "session");
+        let first = packet.encode().unwrap();
+
+        update_acct_delay_time(&mut packet, 0, Duration::from_secs(2));
+        let retransmitted = packet.encode().unwrap();
+
+        assert_eq!(first[1], retransmitted[1]); // identifier
+        assert_ne!(first[4..20], retransmitted[4..20]); // request authenticator
+        assert!(Packet::is_authentic_request(&retransmitted, b"secret"));
+    }
+
+    #[test]
+    fn test_u64_octets_counter() {
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        assert!(lookup_acct_input_octets_u64(&packet).is_none());
+
+        let value: u64 = (5 << 32) + 1234;
+        add_acct_input_octets_u64(&mut packet, value);
+        assert_eq!(
+            rfc2866::lookup_acct_input_octets(&packet).unwrap(),
+            Ok(1234)
+        );
+        assert_eq!(
+            rfc2869::lookup_acct_input_gigawords(&packet).unwrap(),
+            Ok(5)
+        );
+        assert_eq!(lookup_acct_input_octets_u64(&packet).unwrap(), Ok(value));
+
+        // Gigawords is optional
+        rfc2866::add_acct_output_octets(&mut packet, 42);
+        assert_eq!(lookup_acct_output_octets_u64(&packet).unwrap(), Ok(42));
+
+        set_acct_output_octets_u64(&mut packet, u64::MAX);
+        assert_eq!(
+            rfc2866::lookup_all_acct_output_octets(&packet).unwrap(),
+            vec![u32::MAX]
+        );
+        assert_eq!(
+            lookup_acct_output_octets_u64(&packet).unwrap(),
+            Ok(u64::MAX)
+        );
+
+        // Gigawords alone would lose the upper 32 bits
+        rfc2866::delete_acct_output_octets(&mut packet);
+        assert_eq!(
+            lookup_acct_output_octets_u64(&packet).unwrap(),
+            Err(AVPError::DecodingError(
+                "Acct-Output-Gigawords without Acct-Output-Octets".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_event_timestamp_window() {
+        let now = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let window = Duration::from_secs(300);
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        assert!(is_event_timestamp_within_window(&packet, &now, window).unwrap());
+
+        add_event_timestamp_if_absent(&mut packet, &(now - chrono::Duration::seconds(301)));
+        add_event_timestamp_if_absent(&mut packet, &now);
+        assert_eq!(
+            rfc2869::lookup_all_event_timestamp(&packet).unwrap().len(),
+            1
+        );
+        assert!(!is_event_timestamp_within_window(&packet, &now, window).unwrap());
+        assert!(is_event_timestamp_within_window(
+            &packet,
+            &(now - chrono::Duration::seconds(600)),
+            window
+        )
+        .unwrap());
+    }
+}
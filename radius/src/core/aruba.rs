@@ -0,0 +1,610 @@
+// Code generated by machine generator; DO NOT EDIT.
+
+//! Utility for the Vendor-Specific attributes of Aruba (Vendor-Id 14823).
+//!
+//! This module handles the packet according to the following definition:
+//! ```text
+//! //! # -*- text -*-
+//! # Copyright (C) 2020 The FreeRADIUS Server project and contributors
+//! # This work is licensed under CC-BY version 4.0 https://creativecommons.org/licenses/by/4.0
+//! # Version $Id$
+//! #
+//! #    The common Aruba attributes, from the dictionary.aruba of FreeRADIUS.
+//! #
+//! #    $Id$
+//! #
+//!
+//! VENDOR        Aruba                14823
+//!
+//! BEGIN-VENDOR    Aruba
+//!
+//! ATTRIBUTE    Aruba-User-Role                1    string
+//! ATTRIBUTE    Aruba-User-Vlan                2    integer
+//! ATTRIBUTE    Aruba-Priv-Admin-User            3    integer
+//! ATTRIBUTE    Aruba-Admin-Role            4    string
+//! ATTRIBUTE    Aruba-Essid-Name            5    string
+//! ATTRIBUTE    Aruba-Location-Id            6    string
+//! ATTRIBUTE    Aruba-Port-Identifier            7    string
+//! ATTRIBUTE    Aruba-MMS-User-Template            8    string
+//! ATTRIBUTE    Aruba-Named-User-Vlan            9    string
+//! ATTRIBUTE    Aruba-AP-Group                10    string
+//! ATTRIBUTE    Aruba-Framed-IPv6-Address        11    string
+//! ATTRIBUTE    Aruba-Device-Type            12    string
+//! ATTRIBUTE    Aruba-No-DHCP-Fingerprint        14    integer
+//! ATTRIBUTE    Aruba-AirGroup-Device-Type        27    integer
+//! ATTRIBUTE    Aruba-AP-IP-Address            34    ipaddr
+//!
+//! VALUE    Aruba-AirGroup-Device-Type    Personal-Device        1
+//! VALUE    Aruba-AirGroup-Device-Type    Shared-Device        2
+//!
+//! END-VENDOR    Aruba
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::net::Ipv4Addr;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::packet::Packet;
+use crate::core::vsa::{self, VendorId};
+
+/// The Vendor-Id of Aruba.
+pub const VENDOR_ARUBA: VendorId = 14823;
+
+pub const ARUBA_USER_ROLE_TYPE: AVPType = 1;
+/// Delete all of `aruba_user_role` values from a packet.
+pub fn delete_aruba_user_role(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_USER_ROLE_TYPE);
+}
+/// Add `aruba_user_role` string value to a packet.
+pub fn add_aruba_user_role(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_USER_ROLE_TYPE, value),
+    );
+}
+/// Lookup a `aruba_user_role` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_user_role`, it returns `None`.
+pub fn lookup_aruba_user_role(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_USER_ROLE_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_user_role` string value from a packet.
+pub fn lookup_all_aruba_user_role(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_USER_ROLE_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_USER_VLAN_TYPE: AVPType = 2;
+/// Delete all of `aruba_user_vlan` values from a packet.
+pub fn delete_aruba_user_vlan(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_USER_VLAN_TYPE);
+}
+/// Add `aruba_user_vlan` integer value to a packet.
+pub fn add_aruba_user_vlan(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_u32(ARUBA_USER_VLAN_TYPE, value),
+    );
+}
+/// Lookup a `aruba_user_vlan` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_user_vlan`, it returns `None`.
+pub fn lookup_aruba_user_vlan(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_USER_VLAN_TYPE).map(|v| v.encode_u32())
+}
+/// Lookup all of the `aruba_user_vlan` integer value from a packet.
+pub fn lookup_all_aruba_user_vlan(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_USER_VLAN_TYPE) {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_PRIV_ADMIN_USER_TYPE: AVPType = 3;
+/// Delete all of `aruba_priv_admin_user` values from a packet.
+pub fn delete_aruba_priv_admin_user(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_PRIV_ADMIN_USER_TYPE);
+}
+/// Add `aruba_priv_admin_user` integer value to a packet.
+pub fn add_aruba_priv_admin_user(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_u32(ARUBA_PRIV_ADMIN_USER_TYPE, value),
+    );
+}
+/// Lookup a `aruba_priv_admin_user` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_priv_admin_user`, it returns `None`.
+pub fn lookup_aruba_priv_admin_user(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_PRIV_ADMIN_USER_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `aruba_priv_admin_user` integer value from a packet.
+pub fn lookup_all_aruba_priv_admin_user(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_PRIV_ADMIN_USER_TYPE) {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_ADMIN_ROLE_TYPE: AVPType = 4;
+/// Delete all of `aruba_admin_role` values from a packet.
+pub fn delete_aruba_admin_role(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_ADMIN_ROLE_TYPE);
+}
+/// Add `aruba_admin_role` string value to a packet.
+pub fn add_aruba_admin_role(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_ADMIN_ROLE_TYPE, value),
+    );
+}
+/// Lookup a `aruba_admin_role` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_admin_role`, it returns `None`.
+pub fn lookup_aruba_admin_role(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_ADMIN_ROLE_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_admin_role` string value from a packet.
+pub fn lookup_all_aruba_admin_role(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_ADMIN_ROLE_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_ESSID_NAME_TYPE: AVPType = 5;
+/// Delete all of `aruba_essid_name` values from a packet.
+pub fn delete_aruba_essid_name(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_ESSID_NAME_TYPE);
+}
+/// Add `aruba_essid_name` string value to a packet.
+pub fn add_aruba_essid_name(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_ESSID_NAME_TYPE, value),
+    );
+}
+/// Lookup a `aruba_essid_name` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_essid_name`, it returns `None`.
+pub fn lookup_aruba_essid_name(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_ESSID_NAME_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_essid_name` string value from a packet.
+pub fn lookup_all_aruba_essid_name(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_ESSID_NAME_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_LOCATION_ID_TYPE: AVPType = 6;
+/// Delete all of `aruba_location_id` values from a packet.
+pub fn delete_aruba_location_id(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_LOCATION_ID_TYPE);
+}
+/// Add `aruba_location_id` string value to a packet.
+pub fn add_aruba_location_id(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_LOCATION_ID_TYPE, value),
+    );
+}
+/// Lookup a `aruba_location_id` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_location_id`, it returns `None`.
+pub fn lookup_aruba_location_id(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_LOCATION_ID_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_location_id` string value from a packet.
+pub fn lookup_all_aruba_location_id(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_LOCATION_ID_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_PORT_IDENTIFIER_TYPE: AVPType = 7;
+/// Delete all of `aruba_port_identifier` values from a packet.
+pub fn delete_aruba_port_identifier(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_PORT_IDENTIFIER_TYPE);
+}
+/// Add `aruba_port_identifier` string value to a packet.
+pub fn add_aruba_port_identifier(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_PORT_IDENTIFIER_TYPE, value),
+    );
+}
+/// Lookup a `aruba_port_identifier` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_port_identifier`, it returns `None`.
+pub fn lookup_aruba_port_identifier(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_PORT_IDENTIFIER_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_port_identifier` string value from a packet.
+pub fn lookup_all_aruba_port_identifier(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_PORT_IDENTIFIER_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_MMS_USER_TEMPLATE_TYPE: AVPType = 8;
+/// Delete all of `aruba_mms_user_template` values from a packet.
+pub fn delete_aruba_mms_user_template(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_MMS_USER_TEMPLATE_TYPE);
+}
+/// Add `aruba_mms_user_template` string value to a packet.
+pub fn add_aruba_mms_user_template(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_MMS_USER_TEMPLATE_TYPE, value),
+    );
+}
+/// Lookup a `aruba_mms_user_template` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_mms_user_template`, it returns `None`.
+pub fn lookup_aruba_mms_user_template(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_MMS_USER_TEMPLATE_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_mms_user_template` string value from a packet.
+pub fn lookup_all_aruba_mms_user_template(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_MMS_USER_TEMPLATE_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_NAMED_USER_VLAN_TYPE: AVPType = 9;
+/// Delete all of `aruba_named_user_vlan` values from a packet.
+pub fn delete_aruba_named_user_vlan(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_NAMED_USER_VLAN_TYPE);
+}
+/// Add `aruba_named_user_vlan` string value to a packet.
+pub fn add_aruba_named_user_vlan(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_NAMED_USER_VLAN_TYPE, value),
+    );
+}
+/// Lookup a `aruba_named_user_vlan` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_named_user_vlan`, it returns `None`.
+pub fn lookup_aruba_named_user_vlan(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_NAMED_USER_VLAN_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_named_user_vlan` string value from a packet.
+pub fn lookup_all_aruba_named_user_vlan(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_NAMED_USER_VLAN_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_AP_GROUP_TYPE: AVPType = 10;
+/// Delete all of `aruba_ap_group` values from a packet.
+pub fn delete_aruba_ap_group(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_AP_GROUP_TYPE);
+}
+/// Add `aruba_ap_group` string value to a packet.
+pub fn add_aruba_ap_group(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_AP_GROUP_TYPE, value),
+    );
+}
+/// Lookup a `aruba_ap_group` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_ap_group`, it returns `None`.
+pub fn lookup_aruba_ap_group(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_AP_GROUP_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_ap_group` string value from a packet.
+pub fn lookup_all_aruba_ap_group(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_AP_GROUP_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_FRAMED_I_PV_6_ADDRESS_TYPE: AVPType = 11;
+/// Delete all of `aruba_framed_i_pv_6_address` values from a packet.
+pub fn delete_aruba_framed_i_pv_6_address(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_FRAMED_I_PV_6_ADDRESS_TYPE);
+}
+/// Add `aruba_framed_i_pv_6_address` string value to a packet.
+pub fn add_aruba_framed_i_pv_6_address(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_FRAMED_I_PV_6_ADDRESS_TYPE, value),
+    );
+}
+/// Lookup a `aruba_framed_i_pv_6_address` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_framed_i_pv_6_address`, it returns `None`.
+pub fn lookup_aruba_framed_i_pv_6_address(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_FRAMED_I_PV_6_ADDRESS_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_framed_i_pv_6_address` string value from a packet.
+pub fn lookup_all_aruba_framed_i_pv_6_address(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_FRAMED_I_PV_6_ADDRESS_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_DEVICE_TYPE_TYPE: AVPType = 12;
+/// Delete all of `aruba_device_type` values from a packet.
+pub fn delete_aruba_device_type(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_DEVICE_TYPE_TYPE);
+}
+/// Add `aruba_device_type` string value to a packet.
+pub fn add_aruba_device_type(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_string(ARUBA_DEVICE_TYPE_TYPE, value),
+    );
+}
+/// Lookup a `aruba_device_type` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_device_type`, it returns `None`.
+pub fn lookup_aruba_device_type(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_DEVICE_TYPE_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `aruba_device_type` string value from a packet.
+pub fn lookup_all_aruba_device_type(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_DEVICE_TYPE_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_NO_DHCP_FINGERPRINT_TYPE: AVPType = 14;
+/// Delete all of `aruba_no_dhcp_fingerprint` values from a packet.
+pub fn delete_aruba_no_dhcp_fingerprint(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_NO_DHCP_FINGERPRINT_TYPE);
+}
+/// Add `aruba_no_dhcp_fingerprint` integer value to a packet.
+pub fn add_aruba_no_dhcp_fingerprint(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_u32(ARUBA_NO_DHCP_FINGERPRINT_TYPE, value),
+    );
+}
+/// Lookup a `aruba_no_dhcp_fingerprint` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_no_dhcp_fingerprint`, it returns `None`.
+pub fn lookup_aruba_no_dhcp_fingerprint(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_NO_DHCP_FINGERPRINT_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `aruba_no_dhcp_fingerprint` integer value from a packet.
+pub fn lookup_all_aruba_no_dhcp_fingerprint(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_NO_DHCP_FINGERPRINT_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_AIR_GROUP_DEVICE_TYPE_TYPE: AVPType = 27;
+/// Delete all of `aruba_air_group_device_type` values from a packet.
+pub fn delete_aruba_air_group_device_type(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_AIR_GROUP_DEVICE_TYPE_TYPE);
+}
+/// Add `aruba_air_group_device_type` value-defined integer value to a packet.
+pub fn add_aruba_air_group_device_type(packet: &mut Packet, value: ArubaAirGroupDeviceType) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_u32(ARUBA_AIR_GROUP_DEVICE_TYPE_TYPE, value),
+    );
+}
+/// Lookup a `aruba_air_group_device_type` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_air_group_device_type`, it returns `None`.
+pub fn lookup_aruba_air_group_device_type(
+    packet: &Packet,
+) -> Option<Result<ArubaAirGroupDeviceType, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_AIR_GROUP_DEVICE_TYPE_TYPE)
+        .map(|v| Ok(v.encode_u32()? as ArubaAirGroupDeviceType))
+}
+/// Lookup all of the `aruba_air_group_device_type` value-defined integer value from a packet.
+pub fn lookup_all_aruba_air_group_device_type(
+    packet: &Packet,
+) -> Result<Vec<ArubaAirGroupDeviceType>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_AIR_GROUP_DEVICE_TYPE_TYPE)
+    {
+        vec.push(avp.encode_u32()? as ArubaAirGroupDeviceType)
+    }
+    Ok(vec)
+}
+/// Lookup a `aruba_air_group_device_type` value from a packet as `ArubaAirGroupDeviceTypeValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_air_group_device_type`, it returns `None`.
+pub fn lookup_aruba_air_group_device_type_value(
+    packet: &Packet,
+) -> Option<Result<ArubaAirGroupDeviceTypeValue, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_AIR_GROUP_DEVICE_TYPE_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `aruba_air_group_device_type` values from a packet as `ArubaAirGroupDeviceTypeValue`.
+pub fn lookup_all_aruba_air_group_device_type_value(
+    packet: &Packet,
+) -> Result<Vec<ArubaAirGroupDeviceTypeValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_AIR_GROUP_DEVICE_TYPE_TYPE)
+    {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
+
+pub const ARUBA_AP_IP_ADDRESS_TYPE: AVPType = 34;
+/// Delete all of `aruba_ap_ip_address` values from a packet.
+pub fn delete_aruba_ap_ip_address(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_AP_IP_ADDRESS_TYPE);
+}
+/// Add `aruba_ap_ip_address` ipaddr value to a packet.
+pub fn add_aruba_ap_ip_address(packet: &mut Packet, value: &Ipv4Addr) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_ARUBA,
+        AVP::from_ipv4(ARUBA_AP_IP_ADDRESS_TYPE, value),
+    );
+}
+/// Lookup a `aruba_ap_ip_address` ipaddr value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `aruba_ap_ip_address`, it returns `None`.
+pub fn lookup_aruba_ap_ip_address(packet: &Packet) -> Option<Result<Ipv4Addr, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_ARUBA, ARUBA_AP_IP_ADDRESS_TYPE)
+        .map(|v| v.encode_ipv4())
+}
+/// Lookup all of the `aruba_ap_ip_address` ipaddr value from a packet.
+pub fn lookup_all_aruba_ap_ip_address(packet: &Packet) -> Result<Vec<Ipv4Addr>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_ARUBA, ARUBA_AP_IP_ADDRESS_TYPE) {
+        vec.push(avp.encode_ipv4()?)
+    }
+    Ok(vec)
+}
+
+pub type ArubaAirGroupDeviceType = u32;
+pub const ARUBA_AIR_GROUP_DEVICE_TYPE_PERSONAL_DEVICE: ArubaAirGroupDeviceType = 1;
+pub const ARUBA_AIR_GROUP_DEVICE_TYPE_SHARED_DEVICE: ArubaAirGroupDeviceType = 2;
+
+/// The values of `Aruba-AirGroup-Device-Type` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `ArubaAirGroupDeviceTypeValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ArubaAirGroupDeviceTypeValue {
+    /// `Personal-Device`
+    PersonalDevice = 1,
+    /// `Shared-Device`
+    SharedDevice = 2,
+}
+
+impl From<ArubaAirGroupDeviceTypeValue> for u32 {
+    fn from(value: ArubaAirGroupDeviceTypeValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for ArubaAirGroupDeviceTypeValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(ArubaAirGroupDeviceTypeValue::PersonalDevice),
+            2 => Ok(ArubaAirGroupDeviceTypeValue::SharedDevice),
+            _ => Err(AVPError::UnknownValueError(
+                "Aruba-AirGroup-Device-Type".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Aruba-User-Role", ARUBA_USER_ROLE_TYPE, "string"),
+    AttributeDefinition::new("Aruba-User-Vlan", ARUBA_USER_VLAN_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Aruba-Priv-Admin-User",
+        ARUBA_PRIV_ADMIN_USER_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new("Aruba-Admin-Role", ARUBA_ADMIN_ROLE_TYPE, "string"),
+    AttributeDefinition::new("Aruba-Essid-Name", ARUBA_ESSID_NAME_TYPE, "string"),
+    AttributeDefinition::new("Aruba-Location-Id", ARUBA_LOCATION_ID_TYPE, "string"),
+    AttributeDefinition::new(
+        "Aruba-Port-Identifier",
+        ARUBA_PORT_IDENTIFIER_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Aruba-MMS-User-Template",
+        ARUBA_MMS_USER_TEMPLATE_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Aruba-Named-User-Vlan",
+        ARUBA_NAMED_USER_VLAN_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("Aruba-AP-Group", ARUBA_AP_GROUP_TYPE, "string"),
+    AttributeDefinition::new(
+        "Aruba-Framed-IPv6-Address",
+        ARUBA_FRAMED_I_PV_6_ADDRESS_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("Aruba-Device-Type", ARUBA_DEVICE_TYPE_TYPE, "string"),
+    AttributeDefinition::new(
+        "Aruba-No-DHCP-Fingerprint",
+        ARUBA_NO_DHCP_FINGERPRINT_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "Aruba-AirGroup-Device-Type",
+        ARUBA_AIR_GROUP_DEVICE_TYPE_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new("Aruba-AP-IP-Address", ARUBA_AP_IP_ADDRESS_TYPE, "ipaddr"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Aruba-AirGroup-Device-Type", "Personal-Device", 1),
+    ValueDefinition::new("Aruba-AirGroup-Device-Type", "Shared-Device", 2),
+];
@@ -0,0 +1,379 @@
+//! Mapping of the structs to the attributes of a packet.
+//!
+//! `RadiusAttributes` converts a struct from and into the attributes of a packet. With the `derive` feature,
+//! `#[derive(RadiusAttributes)]` implements it by the fields of a struct, as serde does:
+//!
+//! - `#[radius(name = "Cisco-AVPair")]` maps a field to an attribute of the built-in dictionary (see
+//!   `radius::core::dictionary`) by the name, including the vendors' ones; without `#[radius(...)]`, the name is the
+//!   one of the field (e.g. `user_name` is `User-Name`), since the names are compared case-insensitively.
+//! - `#[radius(type = 4)]` maps a field to an attribute by the type, and `#[radius(vendor = 14122, type = 1)]` to a
+//!   sub-attribute of Vendor-Specific by the Vendor-Id and the Vendor-Type.
+//! - `#[radius(skip)]` skips a field, which is `Default::default()` in `from_packet()`.
+//!
+//! A field of `T` requires the attribute, `Option<T>` is an optional attribute and `Vec<T>` has all of the attributes
+//! of the type, where `T` is an `AttributeValue` (e.g. `String`, `u32` or `Ipv4Addr`; `Vec<u8>` is the octets of an
+//! attribute). `apply_to_packet()` replaces the attributes of the fields in a packet and keeps the others.
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use std::net::Ipv4Addr;
+//!
+//! use radius::core::attribute_set::RadiusAttributes;
+//! use radius::core::code::Code;
+//! use radius::core::packet::Packet;
+//!
+//! #[derive(RadiusAttributes, Debug, PartialEq)]
+//! struct Authorization {
+//!     user_name: String,
+//!     #[radius(name = "Framed-IP-Address")]
+//!     address: Option<Ipv4Addr>,
+//!     #[radius(type = 25)]
+//!     classes: Vec<Vec<u8>>,
+//! }
+//!
+//! let authorization = Authorization {
+//!     user_name: "bob".to_owned(),
+//!     address: Some(Ipv4Addr::new(192, 0, 2, 10)),
+//!     classes: vec![b"gold".to_vec()],
+//! };
+//! let mut packet = Packet::new(Code::AccessAccept, b"secret");
+//! authorization.apply_to_packet(&mut packet).unwrap();
+//! assert_eq!(Authorization::from_packet(&packet).unwrap(), authorization);
+//! # }
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary;
+use crate::core::ipv6::{InterfaceId, Ipv6Prefix};
+use crate::core::packet::Packet;
+use crate::core::vsa::{self, VendorId};
+use crate::error::ErrorKind;
+
+#[cfg(feature = "derive")]
+pub use radius_derive::RadiusAttributes;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AttributeSetError {
+    /// An error that is raised when the built-in dictionary doesn't have the attribute of a field.
+    #[error("unknown attribute: {0}")]
+    UnknownAttributeError(String),
+    /// An error that is raised when a packet doesn't have the required attribute of a field.
+    #[error("the attribute is missing: {0}")]
+    MissingAttributeError(String),
+    /// An error that is raised when an attribute can't be converted from or into the value of a field.
+    #[error("invalid attribute {0}; {1}")]
+    InvalidAttributeError(String, AVPError),
+}
+
+impl AttributeSetError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            AttributeSetError::UnknownAttributeError(_) => ErrorKind::Configuration,
+            AttributeSetError::MissingAttributeError(_)
+            | AttributeSetError::InvalidAttributeError(_, _) => ErrorKind::Malformed,
+        }
+    }
+}
+
+/// A struct that is converted from and into the attributes of a packet (see the module document).
+pub trait RadiusAttributes: Sized {
+    /// Returns the struct of the attributes of a packet.
+    fn from_packet(packet: &Packet) -> Result<Self, AttributeSetError>;
+
+    /// Replace the attributes of the struct in a packet.
+    fn apply_to_packet(&self, packet: &mut Packet) -> Result<(), AttributeSetError>;
+}
+
+/// A value of a field that is an attribute.
+pub trait AttributeValue: Sized {
+    /// Returns the value of an attribute.
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError>;
+
+    /// Returns an attribute of the type with the value.
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError>;
+}
+
+impl AttributeValue for String {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        avp.encode_string()
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_string(typ, self))
+    }
+}
+
+impl AttributeValue for Vec<u8> {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        Ok(avp.encode_bytes())
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_bytes(typ, self))
+    }
+}
+
+impl AttributeValue for u16 {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        avp.encode_u16()
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_u16(typ, *self))
+    }
+}
+
+impl AttributeValue for u32 {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        avp.encode_u32()
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_u32(typ, *self))
+    }
+}
+
+impl AttributeValue for u64 {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        avp.encode_u64()
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_u64(typ, *self))
+    }
+}
+
+impl AttributeValue for Ipv4Addr {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        avp.encode_ipv4()
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_ipv4(typ, self))
+    }
+}
+
+impl AttributeValue for Ipv6Addr {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        avp.encode_ipv6()
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_ipv6(typ, self))
+    }
+}
+
+impl AttributeValue for Ipv6Prefix {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        avp.encode_ipv6_prefix_value()
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_ipv6_prefix_value(typ, self))
+    }
+}
+
+impl AttributeValue for InterfaceId {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        avp.encode_interface_id()
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_interface_id(typ, self))
+    }
+}
+
+impl AttributeValue for DateTime<Utc> {
+    fn from_avp(avp: &AVP) -> Result<Self, AVPError> {
+        avp.encode_date()
+    }
+
+    fn to_avp(&self, typ: AVPType) -> Result<AVP, AVPError> {
+        Ok(AVP::from_date(typ, self))
+    }
+}
+
+/// An attribute of a field: a type, or a Vendor-Type of a vendor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeId {
+    Standard(AVPType),
+    Vendor(VendorId, AVPType),
+}
+
+impl AttributeId {
+    /// Lookup an attribute of the built-in dictionary by the name, including the vendors' ones.
+    pub fn resolve(name: &str) -> Result<Self, AttributeSetError> {
+        if let Some(attribute) = dictionary::lookup_attribute_by_name(name) {
+            return Ok(AttributeId::Standard(attribute.get_type()));
+        }
+        dictionary::lookup_vendor_attribute_by_name(name)
+            .map(|(vendor_id, attribute)| AttributeId::Vendor(vendor_id, attribute.get_type()))
+            .ok_or_else(|| AttributeSetError::UnknownAttributeError(name.to_string()))
+    }
+
+    fn lookup_all(&self, packet: &Packet) -> Vec<AVP> {
+        match *self {
+            AttributeId::Standard(typ) => packet.lookup_all(typ).into_iter().cloned().collect(),
+            AttributeId::Vendor(vendor_id, typ) => {
+                vsa::lookup_all_vendor_attributes(packet, vendor_id, typ)
+            }
+        }
+    }
+
+    fn typ(&self) -> AVPType {
+        match *self {
+            AttributeId::Standard(typ) | AttributeId::Vendor(_, typ) => typ,
+        }
+    }
+
+    /// Lookup all of the values of the attribute; `name` is the one of the errors.
+    pub fn lookup_values<T: AttributeValue>(
+        &self,
+        packet: &Packet,
+        name: &str,
+    ) -> Result<Vec<T>, AttributeSetError> {
+        self.lookup_all(packet)
+            .iter()
+            .map(|avp| {
+                T::from_avp(avp)
+                    .map_err(|e| AttributeSetError::InvalidAttributeError(name.to_string(), e))
+            })
+            .collect()
+    }
+
+    /// Lookup the first value of the attribute, which is required; `name` is the one of the errors.
+    pub fn lookup_required<T: AttributeValue>(
+        &self,
+        packet: &Packet,
+        name: &str,
+    ) -> Result<T, AttributeSetError> {
+        self.lookup_optional(packet, name)?
+            .ok_or_else(|| AttributeSetError::MissingAttributeError(name.to_string()))
+    }
+
+    /// Lookup the first value of the attribute, if any; `name` is the one of the errors.
+    pub fn lookup_optional<T: AttributeValue>(
+        &self,
+        packet: &Packet,
+        name: &str,
+    ) -> Result<Option<T>, AttributeSetError> {
+        self.lookup_all(packet)
+            .first()
+            .map(T::from_avp)
+            .transpose()
+            .map_err(|e| AttributeSetError::InvalidAttributeError(name.to_string(), e))
+    }
+
+    /// Replace the attributes of the type in a packet with the values; `name` is the one of the errors.
+    pub fn replace_values<T: AttributeValue>(
+        &self,
+        packet: &mut Packet,
+        name: &str,
+        values: &[T],
+    ) -> Result<(), AttributeSetError> {
+        let avps = values
+            .iter()
+            .map(|value| value.to_avp(self.typ()))
+            .collect::<Result<Vec<AVP>, AVPError>>()
+            .map_err(|e| AttributeSetError::InvalidAttributeError(name.to_string(), e))?;
+        match *self {
+            AttributeId::Standard(typ) => {
+                packet.delete(typ);
+                for avp in avps {
+                    packet.add(avp);
+                }
+            }
+            AttributeId::Vendor(vendor_id, typ) => {
+                vsa::delete_vendor_attribute(packet, vendor_id, typ);
+                for avp in avps {
+                    vsa::add_vendor_attribute(packet, vendor_id, avp);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::net::Ipv4Addr;
+
+    use crate::core::attribute_set::{AttributeId, AttributeSetError};
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+
+    #[test]
+    fn test_attribute_id() {
+        assert_eq!(
+            AttributeId::resolve("user-name").unwrap(),
+            AttributeId::Standard(rfc2865::USER_NAME_TYPE)
+        );
+        #[cfg(feature = "cisco")]
+        assert_eq!(
+            AttributeId::resolve("Cisco-AVPair").unwrap(),
+            AttributeId::Vendor(9, 1)
+        );
+        assert_eq!(
+            AttributeId::resolve("Unknown-Attribute").unwrap_err(),
+            AttributeSetError::UnknownAttributeError("Unknown-Attribute".to_owned())
+        );
+
+        let mut packet = Packet::new(Code::AccessAccept, b"secret");
+        rfc2865::add_reply_message(&mut packet, "former");
+        let reply_message = AttributeId::Standard(rfc2865::REPLY_MESSAGE_TYPE);
+        reply_message
+            .replace_values(
+                &mut packet,
+                "Reply-Message",
+                &["hello".to_owned(), "world".to_owned()],
+            )
+            .unwrap();
+        assert_eq!(
+            reply_message
+                .lookup_values::<String>(&packet, "Reply-Message")
+                .unwrap(),
+            vec!["hello", "world"]
+        );
+
+        let acme = AttributeId::Vendor(32473, 1);
+        acme.replace_values(&mut packet, "Acme", &[Ipv4Addr::new(192, 0, 2, 1)])
+            .unwrap();
+        assert_eq!(
+            acme.lookup_required::<Ipv4Addr>(&packet, "Acme").unwrap(),
+            Ipv4Addr::new(192, 0, 2, 1)
+        );
+        assert_eq!(
+            rfc2865::lookup_all_reply_message(&packet).unwrap().len(),
+            2,
+            "the other attributes are kept"
+        );
+
+        let session_timeout = AttributeId::Standard(rfc2865::SESSION_TIMEOUT_TYPE);
+        assert_eq!(
+            session_timeout
+                .lookup_optional::<u32>(&packet, "Session-Timeout")
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            session_timeout
+                .lookup_required::<u32>(&packet, "Session-Timeout")
+                .unwrap_err(),
+            AttributeSetError::MissingAttributeError("Session-Timeout".to_owned())
+        );
+        assert!(matches!(
+            reply_message.lookup_required::<u32>(&packet, "Reply-Message"),
+            Err(AttributeSetError::InvalidAttributeError(_, _))
+        ));
+    }
+}
@@ -1,3 +1,7 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPType, AVP};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -20,11 +24,7 @@ impl Attributes {
 
             attrs.push(AVP {
                 typ: bs[i],
-                value: if length > 2 {
-                    bs[i + 2..i + length].to_vec()
-                } else {
-                    vec![]
-                },
+                value: bs[i + 2..i + length].into(),
             });
 
             i += length;
@@ -37,14 +37,41 @@ impl Attributes {
         self.0.push(avp)
     }
 
+    pub(crate) fn add_first(&mut self, avp: AVP) {
+        self.0.insert(0, avp)
+    }
+
     pub(crate) fn extend(&mut self, avps: Vec<AVP>) {
         self.0.extend(avps)
     }
 
+    pub(crate) fn insert(&mut self, index: usize, avp: AVP) {
+        self.0.insert(index, avp)
+    }
+
+    pub(crate) fn set(&mut self, avp: AVP) {
+        match self.0.iter_mut().find(|a| a.typ == avp.typ) {
+            Some(a) => *a = avp,
+            None => self.0.push(avp),
+        }
+    }
+
     pub(crate) fn del(&mut self, typ: AVPType) {
         self.0.retain(|avp| avp.typ != typ);
     }
 
+    pub(crate) fn take_all(&mut self, typ: AVPType) -> Vec<AVP> {
+        let (taken, kept) = core::mem::take(&mut self.0)
+            .into_iter()
+            .partition(|avp| avp.typ == typ);
+        self.0 = kept;
+        taken
+    }
+
+    pub(crate) fn retain<F: FnMut(&AVP) -> bool>(&mut self, f: F) {
+        self.0.retain(f);
+    }
+
     pub(crate) fn lookup(&self, typ: AVPType) -> Option<&AVP> {
         self.0.iter().find(|avp| avp.typ == typ)
     }
@@ -53,19 +80,33 @@ impl Attributes {
         self.0.iter().filter(|&avp| avp.typ == typ).collect()
     }
 
+    #[cfg(feature = "testing")]
     pub(crate) fn encode(&self) -> Result<Vec<u8>, String> {
-        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoded = alloc::vec![0; self.encoded_len()?];
+        self.encode_into(&mut encoded);
+        Ok(encoded)
+    }
 
-        for avp in &self.0 {
-            let attr_len = avp.value.len();
-            if attr_len > 253 {
+    /// Returns the length of the encoded attributes.
+    pub(crate) fn encoded_len(&self) -> Result<usize, String> {
+        self.0.iter().try_fold(0, |len, avp| {
+            if avp.value.len() > 253 {
                 return Err("attribute is too large".to_owned());
             }
-            encoded.push(avp.typ);
-            encoded.push(2 + attr_len as u8);
-            encoded.extend(&avp.value);
-        }
+            Ok(len + 2 + avp.value.len())
+        })
+    }
 
-        Ok(encoded)
+    /// Encode the attributes into the buffer, which has to have `encoded_len()` bytes at least.
+    pub(crate) fn encode_into(&self, buf: &mut [u8]) -> usize {
+        let mut offset = 0;
+        for avp in &self.0 {
+            let attr_len = avp.value.len();
+            buf[offset] = avp.typ;
+            buf[offset + 1] = 2 + attr_len as u8;
+            buf[offset + 2..offset + 2 + attr_len].copy_from_slice(&avp.value);
+            offset += 2 + attr_len;
+        }
+        offset
     }
 }
@@ -1,11 +1,23 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use core::net::{Ipv4Addr, Ipv6Addr};
 use rand::Rng;
-use std::convert::TryInto;
-use std::net::{Ipv4Addr, Ipv6Addr};
 
 use chrono::{DateTime, TimeZone, Utc};
 use thiserror::Error;
 
+use crate::core::ipv6::{InterfaceId, Ipv6Prefix};
+use crate::core::random;
+use crate::core::rfc2865;
 use crate::core::tag::{Tag, UNUSED_TAG_VALUE};
+use crate::core::tlv::{self, Tlv};
+use crate::core::vsa::VendorId;
+use crate::error::ErrorKind;
 
 #[derive(Error, PartialEq, Debug)]
 pub enum AVPError {
@@ -44,6 +56,33 @@ pub enum AVPError {
     /// This error is raised when a tag is invalid for the tagged-integer value.
     #[error("invalid tag for integer value. this must be less than or equal 0x1f")]
     InvalidTagForIntegerValueError(),
+
+    /// This error is raised when a value isn't any of the values of the attribute that the dictionaries define.
+    #[error("unknown value of {0}: {1}")]
+    UnknownValueError(String, u32),
+
+    /// This error is raised when a time is out of the range of the date value, i.e. from 1970 to 2106.
+    #[error("date is out of range: {0}")]
+    DateOutOfRangeError(String),
+}
+
+impl AVPError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            AVPError::UserPasswordPlainTextMaximumLengthExceededError(_)
+            | AVPError::InvalidRequestAuthenticatorLength()
+            | AVPError::InvalidTagForStringValueError()
+            | AVPError::InvalidTagForIntegerValueError()
+            | AVPError::DateOutOfRangeError(_) => ErrorKind::Encoding,
+            AVPError::PasswordSecretMissingError() => ErrorKind::Configuration,
+            AVPError::InvalidAttributeLengthError(_, _)
+            | AVPError::TagMissingError()
+            | AVPError::DecodingError(_)
+            | AVPError::InvalidSaltMSBError(_)
+            | AVPError::UnknownValueError(_, _) => ErrorKind::Malformed,
+        }
+    }
 }
 
 pub type AVPType = u8;
@@ -54,15 +93,72 @@ pub const TYPE_INVALID: AVPType = 255;
 #[derive(Debug, Clone, PartialEq)]
 pub struct AVP {
     pub(crate) typ: AVPType,
-    pub(crate) value: Vec<u8>,
+    pub(crate) value: Arc<[u8]>,
 }
 
 impl AVP {
+    /// Returns the type of the attribute.
+    pub fn get_type(&self) -> AVPType {
+        self.typ
+    }
+
+    /// Returns the raw value of the attribute.
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Returns the raw value of the attribute as the shared buffer.
+    ///
+    /// The values are reference-counted, so cloning an AVP (or a packet) doesn't copy them.
+    pub fn get_shared_value(&self) -> Arc<[u8]> {
+        self.value.clone()
+    }
+
+    /// Returns the Vendor-Id if the attribute is a Vendor-Specific attribute (type 26) that has one.
+    pub fn get_vendor_id(&self) -> Option<VendorId> {
+        match (self.typ, self.value.get(..4)) {
+            (rfc2865::VENDOR_SPECIFIC_TYPE, Some(&[a, b, c, d])) => {
+                Some(VendorId::from_be_bytes([a, b, c, d]))
+            }
+            _ => None,
+        }
+    }
+
+    /// Make an AVP from the raw bytes of an attribute on the wire, i.e. the type, the length and the value.
+    ///
+    /// The AVP is kept as it is, so `encode_raw()` returns the same bytes even if the attribute is unknown.
+    pub fn decode_raw(bs: &[u8]) -> Result<Self, AVPError> {
+        if bs.len() < 2 {
+            return Err(AVPError::DecodingError("short buffer".to_owned()));
+        }
+        if bs[1] as usize != bs.len() {
+            return Err(AVPError::InvalidAttributeLengthError(
+                format!("{} bytes", bs[1]),
+                bs.len(),
+            ));
+        }
+        Ok(AVP {
+            typ: bs[0],
+            value: bs[2..].into(),
+        })
+    }
+
+    /// Returns the raw bytes of the attribute on the wire, i.e. the type, the length and the value.
+    pub fn encode_raw(&self) -> Result<Vec<u8>, AVPError> {
+        if self.value.len() > 253 {
+            return Err(AVPError::InvalidAttributeLengthError(
+                "253 bytes".to_owned(),
+                self.value.len(),
+            ));
+        }
+        Ok([&[self.typ, self.value.len() as u8 + 2][..], &self.value].concat())
+    }
+
     /// (This method is for dictionary developers) make an AVP from a u32 value.
     pub fn from_u32(typ: AVPType, value: u32) -> Self {
         AVP {
             typ,
-            value: u32::to_be_bytes(value).to_vec(),
+            value: u32::to_be_bytes(value).into(),
         }
     }
 
@@ -70,11 +166,21 @@ impl AVP {
     pub fn from_u16(typ: AVPType, value: u16) -> Self {
         AVP {
             typ,
-            value: u16::to_be_bytes(value).to_vec(),
+            value: u16::to_be_bytes(value).into(),
+        }
+    }
+
+    /// (This method is for dictionary developers) make an AVP from a u64 value (`integer64`, RFC 6929).
+    pub fn from_u64(typ: AVPType, value: u64) -> Self {
+        AVP {
+            typ,
+            value: u64::to_be_bytes(value).into(),
         }
     }
 
     /// (This method is for dictionary developers) make an AVP from a tagged u32 value.
+    ///
+    /// The tag replaces the most significant octet of the value (RFC 2868 section 3.1), so the value is 24 bits.
     pub fn from_tagged_u32(typ: AVPType, tag: Option<&Tag>, value: u32) -> Self {
         let tag = match tag {
             None => &Tag {
@@ -85,7 +191,9 @@ impl AVP {
 
         AVP {
             typ,
-            value: [vec![tag.value], u32::to_be_bytes(value).to_vec()].concat(),
+            value: [&[tag.value], &u32::to_be_bytes(value)[1..]]
+                .concat()
+                .into(),
         }
     }
 
@@ -93,7 +201,7 @@ impl AVP {
     pub fn from_string(typ: AVPType, value: &str) -> Self {
         AVP {
             typ,
-            value: value.as_bytes().to_vec(),
+            value: value.as_bytes().into(),
         }
     }
 
@@ -102,11 +210,11 @@ impl AVP {
         match tag {
             None => AVP {
                 typ,
-                value: value.as_bytes().to_vec(),
+                value: value.as_bytes().into(),
             },
             Some(tag) => AVP {
                 typ,
-                value: [vec![tag.value], value.as_bytes().to_vec()].concat(),
+                value: [vec![tag.value], value.as_bytes().to_vec()].concat().into(),
             },
         }
     }
@@ -115,15 +223,35 @@ impl AVP {
     pub fn from_bytes(typ: AVPType, value: &[u8]) -> Self {
         AVP {
             typ,
-            value: value.to_vec(),
+            value: value.into(),
         }
     }
 
+    /// Make an AVP from a shared buffer without copying it (e.g. to forward a value of another packet).
+    pub fn from_shared_bytes(typ: AVPType, value: Arc<[u8]>) -> Self {
+        AVP { typ, value }
+    }
+
+    /// (This method is for dictionary developers) make an AVP from TLVs (see `radius::core::tlv`).
+    pub fn from_tlvs(typ: AVPType, tlvs: &[Tlv]) -> Result<Self, AVPError> {
+        let value = tlv::encode_tlvs(tlvs)?;
+        if value.len() > 253 {
+            return Err(AVPError::InvalidAttributeLengthError(
+                "253 bytes".to_owned(),
+                value.len(),
+            ));
+        }
+        Ok(AVP {
+            typ,
+            value: value.into(),
+        })
+    }
+
     /// (This method is for dictionary developers) make an AVP from a IPv4 value.
     pub fn from_ipv4(typ: AVPType, value: &Ipv4Addr) -> Self {
         AVP {
             typ,
-            value: value.octets().to_vec(),
+            value: value.octets().into(),
         }
     }
 
@@ -139,7 +267,9 @@ impl AVP {
 
         Ok(AVP {
             typ,
-            value: [vec![0x00, prefix_len as u8 & 0b00111111], prefix.to_vec()].concat::<u8>(),
+            value: [vec![0x00, prefix_len as u8 & 0b00111111], prefix.to_vec()]
+                .concat::<u8>()
+                .into(),
         })
     }
 
@@ -147,7 +277,7 @@ impl AVP {
     pub fn from_ipv6(typ: AVPType, value: &Ipv6Addr) -> Self {
         AVP {
             typ,
-            value: value.octets().to_vec(),
+            value: value.octets().into(),
         }
     }
 
@@ -163,10 +293,37 @@ impl AVP {
 
         Ok(AVP {
             typ,
-            value: [vec![0x00, (prefix_len * 8) as u8], prefix.to_vec()].concat::<u8>(),
+            value: [vec![0x00, (prefix_len * 8) as u8], prefix.to_vec()]
+                .concat::<u8>()
+                .into(),
         })
     }
 
+    /// (This method is for dictionary developers) make an AVP from a IPv6-prefix value with the prefix length.
+    ///
+    /// The prefix has only the octets that the prefix length covers (see RFC 3162, section 2.3).
+    pub fn from_ipv6_prefix_value(typ: AVPType, prefix: &Ipv6Prefix) -> Self {
+        let prefix_len = prefix.get_prefix_len();
+        let octets_len = usize::from(prefix_len).div_ceil(8);
+        AVP {
+            typ,
+            value: [
+                vec![0x00, prefix_len],
+                prefix.get_addr().octets()[..octets_len].to_vec(),
+            ]
+            .concat::<u8>()
+            .into(),
+        }
+    }
+
+    /// (This method is for dictionary developers) make an AVP from an interface-id value.
+    pub fn from_interface_id(typ: AVPType, value: &InterfaceId) -> Self {
+        AVP {
+            typ,
+            value: value.octets().into(),
+        }
+    }
+
     /// (This method is for dictionary developers) make an AVP from a user-password value.
     /// see also: https://tools.ietf.org/html/rfc2865#section-5.2
     pub fn from_user_password(
@@ -231,17 +388,34 @@ impl AVP {
             enc.extend(&buff);
         }
 
-        Ok(AVP { typ, value: enc })
+        Ok(AVP {
+            typ,
+            value: enc.into(),
+        })
     }
 
     /// (This method is for dictionary developers) make an AVP from a date value.
     pub fn from_date(typ: AVPType, dt: &DateTime<Utc>) -> Self {
         AVP {
             typ,
-            value: u32::to_be_bytes(dt.timestamp() as u32).to_vec(),
+            value: u32::to_be_bytes(dt.timestamp() as u32).into(),
         }
     }
 
+    /// (This method is for dictionary developers) make an AVP from a date value of `SystemTime`.
+    #[cfg(feature = "std")]
+    pub fn from_system_time(typ: AVPType, time: &std::time::SystemTime) -> Result<Self, AVPError> {
+        let timestamp = time
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .and_then(|since_epoch| u32::try_from(since_epoch.as_secs()).ok())
+            .ok_or_else(|| AVPError::DateOutOfRangeError(format!("{time:?}")))?;
+        Ok(AVP {
+            typ,
+            value: u32::to_be_bytes(timestamp).into(),
+        })
+    }
+
     /// (This method is for dictionary developers) make an AVP from a tunne-password value.
     /// see also: https://tools.ietf.org/html/rfc2868#section-3.5
     pub fn from_tunnel_password(
@@ -273,14 +447,15 @@ impl AVP {
          *  https://tools.ietf.org/html/rfc2868#section-3.5
          */
 
-        if request_authenticator.len() > 240 {
+        // the plaintext is the Data-Length, the password and the padding, which must fit in an attribute
+        if plain_text.len() > 239 {
             return Err(AVPError::InvalidAttributeLengthError(
-                "240 bytes".to_owned(),
-                request_authenticator.len(),
+                "239 bytes".to_owned(),
+                plain_text.len(),
             ));
         }
 
-        let mut rng = rand::thread_rng();
+        let mut rng = random::rng();
         let salt: [u8; 2] = [rng.gen::<u8>() | 0x80, rng.gen::<u8>()];
 
         if secret.is_empty() {
@@ -299,44 +474,26 @@ impl AVP {
         ]
         .concat();
 
-        let mut buff = [request_authenticator, &salt].concat();
-        if plain_text.is_empty() {
-            return Ok(AVP {
-                typ,
-                value: [
-                    enc,
-                    md5::compute([secret, &buff[..]].concat())
-                        .iter()
-                        .zip(vec![0; 16])
-                        .map(|(d, p)| d ^ p)
-                        .collect::<Vec<u8>>(),
-                ]
-                .concat(),
-            });
-        }
-
-        for chunk in plain_text.chunks(16) {
-            let mut chunk_vec = chunk.to_vec();
-            let l = chunk.len();
-            if l < 16 {
-                chunk_vec.extend(vec![0; 16 - l]); // zero padding
-            }
+        // the plaintext String starts with the one-octet Data-Length of the password, and is zero padded
+        let mut padded = [&[plain_text.len() as u8], plain_text].concat();
+        padded.resize(padded.len().div_ceil(16) * 16, 0);
 
+        let mut buff = [request_authenticator, &salt].concat();
+        for chunk in padded.chunks(16) {
             let enc_block = md5::compute([secret, &buff[..]].concat()).to_vec();
-            buff = enc_block
-                .iter()
-                .zip(chunk_vec)
-                .map(|(d, p)| d ^ p)
-                .collect();
+            buff = enc_block.iter().zip(chunk).map(|(d, p)| d ^ p).collect();
             enc.extend(&buff);
         }
 
-        Ok(AVP { typ, value: enc })
+        Ok(AVP {
+            typ,
+            value: enc.into(),
+        })
     }
 
     /// (This method is for dictionary developers) encode an AVP into a u32 value.
     pub fn encode_u32(&self) -> Result<u32, AVPError> {
-        const U32_SIZE: usize = std::mem::size_of::<u32>();
+        const U32_SIZE: usize = core::mem::size_of::<u32>();
         if self.value.len() != U32_SIZE {
             return Err(AVPError::InvalidAttributeLengthError(
                 format!("{U32_SIZE} bytes"),
@@ -353,7 +510,7 @@ impl AVP {
 
     /// (This method is for dictionary developers) encode an AVP into a u16 value.
     pub fn encode_u16(&self) -> Result<u16, AVPError> {
-        const U16_SIZE: usize = std::mem::size_of::<u16>();
+        const U16_SIZE: usize = core::mem::size_of::<u16>();
         if self.value.len() != U16_SIZE {
             return Err(AVPError::InvalidAttributeLengthError(
                 format!("{U16_SIZE} bytes"),
@@ -368,6 +525,18 @@ impl AVP {
         }
     }
 
+    /// (This method is for dictionary developers) encode an AVP into a u64 value (`integer64`, RFC 6929).
+    pub fn encode_u64(&self) -> Result<u64, AVPError> {
+        const U64_SIZE: usize = core::mem::size_of::<u64>();
+        match <[u8; U64_SIZE]>::try_from(&self.value[..]) {
+            Ok(bytes) => Ok(u64::from_be_bytes(bytes)),
+            Err(_) => Err(AVPError::InvalidAttributeLengthError(
+                format!("{U64_SIZE} bytes"),
+                self.value.len(),
+            )),
+        }
+    }
+
     /// (This method is for dictionary developers) encode an AVP into a tag and u32 value.
     pub fn encode_tagged_u32(&self) -> Result<(u32, Tag), AVPError> {
         if self.value.is_empty() {
@@ -385,18 +554,18 @@ impl AVP {
             return Err(AVPError::InvalidTagForIntegerValueError());
         }
 
-        const U32_SIZE: usize = std::mem::size_of::<u32>();
-        if self.value[1..].len() != U32_SIZE {
+        // the tag and the 3 octets of the value
+        const U32_SIZE: usize = core::mem::size_of::<u32>();
+        if self.value.len() != U32_SIZE {
             return Err(AVPError::InvalidAttributeLengthError(
-                format!("{} bytes", U32_SIZE + 1),
+                format!("{U32_SIZE} bytes"),
                 self.value.len(),
             ));
         }
-        let (int_bytes, _) = self.value[1..].split_at(U32_SIZE);
-        match int_bytes.try_into() {
-            Ok(boxed_array) => Ok((u32::from_be_bytes(boxed_array), tag)),
-            Err(e) => Err(AVPError::DecodingError(e.to_string())),
-        }
+        Ok((
+            u32::from_be_bytes([0, self.value[1], self.value[2], self.value[3]]),
+            tag,
+        ))
     }
 
     /// (This method is for dictionary developers) encode an AVP into a string value.
@@ -443,6 +612,11 @@ impl AVP {
         }
     }
 
+    /// (This method is for dictionary developers) encode an AVP into TLVs (of one level).
+    pub fn encode_tlvs(&self) -> Result<Vec<Tlv>, AVPError> {
+        tlv::decode_tlvs(&self.value)
+    }
+
     /// (This method is for dictionary developers) encode an AVP into bytes.
     pub fn encode_bytes(&self) -> Vec<u8> {
         self.value.to_vec()
@@ -450,7 +624,7 @@ impl AVP {
 
     /// (This method is for dictionary developers) encode an AVP into Ipv4 value.
     pub fn encode_ipv4(&self) -> Result<Ipv4Addr, AVPError> {
-        const IPV4_SIZE: usize = std::mem::size_of::<Ipv4Addr>();
+        const IPV4_SIZE: usize = core::mem::size_of::<Ipv4Addr>();
         if self.value.len() != IPV4_SIZE {
             return Err(AVPError::InvalidAttributeLengthError(
                 format!("{IPV4_SIZE} bytes"),
@@ -478,7 +652,7 @@ impl AVP {
 
     /// (This method is for dictionary developers) encode an AVP into Ipv6 value.
     pub fn encode_ipv6(&self) -> Result<Ipv6Addr, AVPError> {
-        const IPV6_SIZE: usize = std::mem::size_of::<Ipv6Addr>();
+        const IPV6_SIZE: usize = core::mem::size_of::<Ipv6Addr>();
         if self.value.len() != IPV6_SIZE {
             return Err(AVPError::InvalidAttributeLengthError(
                 format!("{IPV6_SIZE} bytes"),
@@ -504,6 +678,42 @@ impl AVP {
         }
     }
 
+    /// (This method is for dictionary developers) encode an AVP into Ipv6-prefix value with the prefix length.
+    ///
+    /// The bits beyond the prefix length are ignored.
+    pub fn encode_ipv6_prefix_value(&self) -> Result<Ipv6Prefix, AVPError> {
+        if self.value.len() < 2 || self.value.len() > 18 {
+            return Err(AVPError::InvalidAttributeLengthError(
+                "2 >= bytes && 18 <= bytes".to_owned(),
+                self.value.len(),
+            ));
+        }
+        let prefix_len = self.value[1];
+        let octets = &self.value[2..];
+        if usize::from(prefix_len) > octets.len() * 8 {
+            return Err(AVPError::DecodingError(format!(
+                "prefix length {} exceeds the prefix of {} bytes",
+                prefix_len,
+                octets.len()
+            )));
+        }
+        let mut addr = [0; 16];
+        addr[..octets.len()].copy_from_slice(octets);
+        Ipv6Prefix::new(Ipv6Addr::from(addr), prefix_len)
+            .map_err(|e| AVPError::DecodingError(e.to_string()))
+    }
+
+    /// (This method is for dictionary developers) encode an AVP into interface-id value.
+    pub fn encode_interface_id(&self) -> Result<InterfaceId, AVPError> {
+        match <[u8; 8]>::try_from(&self.value[..]) {
+            Ok(octets) => Ok(InterfaceId::new(octets)),
+            Err(_) => Err(AVPError::InvalidAttributeLengthError(
+                "8 bytes".to_owned(),
+                self.value.len(),
+            )),
+        }
+    }
+
     /// (This method is for dictionary developers) encode an AVP into user-password value as bytes.
     pub fn encode_user_password(
         &self,
@@ -525,7 +735,8 @@ impl AVP {
             return Err(AVPError::InvalidRequestAuthenticatorLength());
         }
 
-        let mut dec: Vec<u8> = Vec::new();
+        // the capacity is of the whole value, so the plain text isn't left behind by reallocating
+        let mut dec: Vec<u8> = Vec::with_capacity(self.value.len());
         let mut buff: Vec<u8> = request_authenticator.to_vec();
 
         // NOTE:
@@ -534,26 +745,23 @@ impl AVP {
         for chunk in self.value.chunks(16) {
             let chunk_vec = chunk.to_vec();
             let dec_block = md5::compute([secret, &buff[..]].concat()).to_vec();
-            dec.extend(
-                dec_block
-                    .iter()
-                    .zip(&chunk_vec)
-                    .map(|(d, p)| d ^ p)
-                    .collect::<Vec<u8>>(),
-            );
+            dec.extend(dec_block.iter().zip(&chunk_vec).map(|(d, p)| d ^ p));
             buff = chunk_vec.clone();
         }
 
         // remove trailing zero bytes
-        match dec.split(|b| *b == 0).next() {
-            Some(dec) => Ok(dec.to_vec()),
-            None => Ok(vec![]),
-        }
+        let password = match dec.split(|b| *b == 0).next() {
+            Some(password) => password.to_vec(),
+            None => vec![],
+        };
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut dec);
+        Ok(password)
     }
 
     /// (This method is for dictionary developers) encode an AVP into date value.
     pub fn encode_date(&self) -> Result<DateTime<Utc>, AVPError> {
-        const U32_SIZE: usize = std::mem::size_of::<u32>();
+        const U32_SIZE: usize = core::mem::size_of::<u32>();
         if self.value.len() != U32_SIZE {
             return Err(AVPError::InvalidAttributeLengthError(
                 format!("{U32_SIZE}"),
@@ -571,13 +779,23 @@ impl AVP {
         }
     }
 
+    /// (This method is for dictionary developers) encode an AVP into a date value of `SystemTime`.
+    #[cfg(feature = "std")]
+    pub fn encode_system_time(&self) -> Result<std::time::SystemTime, AVPError> {
+        let timestamp = self.encode_u32()?;
+        Ok(std::time::UNIX_EPOCH + std::time::Duration::from_secs(u64::from(timestamp)))
+    }
+
     /// (This method is for dictionary developers) encode an AVP into a tunnel-password value as bytes.
     pub fn encode_tunnel_password(
         &self,
         secret: &[u8],
         request_authenticator: &[u8],
     ) -> Result<(Vec<u8>, Tag), AVPError> {
-        if self.value.len() < 19 || self.value.len() > 243 || (self.value.len() - 3) % 16 != 0 {
+        if self.value.len() < 19
+            || self.value.len() > 243
+            || !(self.value.len() - 3).is_multiple_of(16)
+        {
             return Err(AVPError::InvalidAttributeLengthError(
                 "19 <= bytes && bytes <= 242 && (bytes - 3) % 16 == 0".to_owned(),
                 self.value.len(),
@@ -600,38 +818,39 @@ impl AVP {
         let tag = Tag {
             value: self.value[0],
         };
-        let mut dec: Vec<u8> = Vec::new();
+        let mut dec: Vec<u8> = Vec::with_capacity(self.value.len());
         let mut buff: Vec<u8> =
             [request_authenticator.to_vec(), self.value[1..3].to_vec()].concat();
 
         for chunk in self.value[3..].chunks(16) {
             let chunk_vec = chunk.to_vec();
             let dec_block = md5::compute([secret, &buff[..]].concat()).to_vec();
-            dec.extend(
-                dec_block
-                    .iter()
-                    .zip(&chunk_vec)
-                    .map(|(d, p)| d ^ p)
-                    .collect::<Vec<u8>>(),
-            );
+            dec.extend(dec_block.iter().zip(&chunk_vec).map(|(d, p)| d ^ p));
             buff = chunk_vec.clone();
         }
 
-        // remove trailing zero bytes
-        match dec.split(|b| *b == 0).next() {
-            Some(dec) => Ok((dec.to_vec(), tag)),
-            None => Ok((vec![], tag)),
+        // the first byte is the Data-Length, and the rest after the password is the padding
+        let data_len = dec[0] as usize;
+        if data_len > dec.len() - 1 {
+            return Err(AVPError::DecodingError(format!(
+                "invalid Data-Length of tunnel-password: {data_len}"
+            )));
         }
+        let password = dec[1..=data_len].to_vec();
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut dec);
+        Ok((password, tag))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::net::{Ipv4Addr, Ipv6Addr};
+    use core::net::{Ipv4Addr, Ipv6Addr};
 
     use chrono::Utc;
 
     use crate::core::avp::{AVPError, AVP};
+    use crate::core::ipv6::{InterfaceId, Ipv6Prefix};
     use crate::core::tag::Tag;
 
     #[test]
@@ -642,6 +861,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn it_should_convert_attribute_to_integer64() -> Result<(), AVPError> {
+        let given_u64 = 0x0102_0304_0506_0708;
+        let avp = AVP::from_u64(1, given_u64);
+        assert_eq!(avp.get_value(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(avp.encode_u64()?, given_u64);
+        assert_eq!(
+            AVP::from_u32(1, 1).encode_u64(),
+            Err(AVPError::InvalidAttributeLengthError(
+                "8 bytes".to_owned(),
+                4
+            ))
+        );
+        Ok(())
+    }
+
     #[test]
     fn it_should_convert_attribute_to_integer16() -> Result<(), AVPError> {
         let given_u16 = 65534;
@@ -652,13 +887,21 @@ mod tests {
 
     #[test]
     fn it_should_convert_attribute_to_tagged_integer32() -> Result<(), AVPError> {
-        let given_u32 = 16909060;
+        let given_u32 = 197380;
         let avp = AVP::from_tagged_u32(1, None, given_u32);
         assert_eq!(avp.encode_tagged_u32()?, (given_u32, Tag::new_unused()));
 
         let tag = Tag::new(2);
         let avp = AVP::from_tagged_u32(1, Some(&tag), given_u32);
+        assert_eq!(avp.value.as_ref(), &[0x02, 0x03, 0x03, 0x04]);
         assert_eq!(avp.encode_tagged_u32()?, (given_u32, tag));
+
+        assert_eq!(
+            AVP::from_bytes(1, &[0x02, 0x00, 0x00, 0x00, 0x0d])
+                .encode_tagged_u32()
+                .unwrap_err(),
+            AVPError::InvalidAttributeLengthError("4 bytes".to_owned(), 5)
+        );
         Ok(())
     }
 
@@ -808,7 +1051,7 @@ mod tests {
             },
             TestCase {
                 plain_text: "0123456789abcdef",
-                expected_encoded_len: 16 + 3,
+                expected_encoded_len: 32 + 3,
             },
             TestCase {
                 plain_text: "0123456789abcdef0",
@@ -816,7 +1059,11 @@ mod tests {
             },
             TestCase {
                 plain_text: "0123456789abcdef0123456789abcdef0123456789abcdef",
-                expected_encoded_len: 48 + 3,
+                expected_encoded_len: 64 + 3,
+            },
+            TestCase {
+                plain_text: "pass\0word",
+                expected_encoded_len: 16 + 3,
             },
         ];
 
@@ -839,8 +1086,35 @@ mod tests {
                 String::from_utf8(decoded_password).unwrap(),
                 test_case.plain_text
             );
+
+            // the first byte of the plaintext is the Data-Length (RFC 2868 section 3.5)
+            let b1 =
+                md5::compute([&secret[..], &request_authenticator[..], &avp.value[1..3]].concat());
+            assert_eq!((avp.value[3] ^ b1[0]) as usize, test_case.plain_text.len());
         }
 
+        assert!(AVP::from_tunnel_password(
+            1,
+            Some(&tag),
+            &[b'a'; 240],
+            &secret,
+            &request_authenticator
+        )
+        .is_err());
+        let avp = AVP::from_tunnel_password(
+            1,
+            Some(&tag),
+            &[b'a'; 239],
+            &secret,
+            &request_authenticator,
+        )?;
+        assert_eq!(avp.value.len(), 243);
+        assert_eq!(
+            avp.encode_tunnel_password(&secret, &request_authenticator)?
+                .0,
+            vec![b'a'; 239]
+        );
+
         Ok(())
     }
 
@@ -870,7 +1144,7 @@ mod tests {
         assert_eq!(
             AVP {
                 typ: 1,
-                value: vec![]
+                value: vec![].into()
             }
             .encode_ipv4_prefix()
             .unwrap_err(),
@@ -898,6 +1172,94 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn should_convert_system_time() -> Result<(), AVPError> {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let avp = AVP::from_system_time(1, &time)?;
+        assert_eq!(avp.encode_system_time()?, time);
+        assert_eq!(avp.encode_date()?.timestamp(), 1_700_000_000);
+
+        // the sub-second part is truncated
+        let avp = AVP::from_system_time(1, &(time + Duration::from_millis(999)))?;
+        assert_eq!(avp.encode_system_time()?, time);
+
+        for time in [
+            UNIX_EPOCH - Duration::from_secs(1),
+            UNIX_EPOCH + Duration::from_secs(u64::from(u32::MAX) + 1),
+        ] {
+            assert!(matches!(
+                AVP::from_system_time(1, &time),
+                Err(AVPError::DateOutOfRangeError(_))
+            ));
+        }
+        assert!(AVP::from_system_time(1, &SystemTime::now()).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn should_convert_ipv6_prefix_value() -> Result<(), AVPError> {
+        for prefix in [
+            "::/0",
+            "2001:db8::/32",
+            "2001:db8:1:2::/63",
+            "2001:db8::1/128",
+        ] {
+            let prefix: Ipv6Prefix = prefix.parse().unwrap();
+            let avp = AVP::from_ipv6_prefix_value(1, &prefix);
+            assert_eq!(avp.encode_ipv6_prefix_value()?, prefix);
+        }
+        assert_eq!(
+            AVP::from_ipv6_prefix_value(1, &"2001:db8:1:2::/63".parse().unwrap()).encode_bytes(),
+            vec![0x00, 63, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x01, 0x00, 0x02]
+        );
+
+        // the full 16 bytes of the other implementations, with the bits beyond the prefix length
+        let avp = AVP::from_bytes(
+            1,
+            &[
+                0x00, 32, 0x20, 0x01, 0x0d, 0xb8, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ],
+        );
+        assert_eq!(
+            avp.encode_ipv6_prefix_value()?,
+            "2001:db8::/32".parse().unwrap()
+        );
+
+        let avp = AVP::from_bytes(1, &[0x00, 33, 0x20, 0x01, 0x0d, 0xb8]);
+        assert!(matches!(
+            avp.encode_ipv6_prefix_value(),
+            Err(AVPError::DecodingError(_))
+        ));
+        let avp = AVP::from_bytes(1, &[0x00]);
+        assert_eq!(
+            avp.encode_ipv6_prefix_value(),
+            Err(AVPError::InvalidAttributeLengthError(
+                "2 >= bytes && 18 <= bytes".to_owned(),
+                1
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_convert_interface_id() -> Result<(), AVPError> {
+        let interface_id = InterfaceId::from(0x0123_4567_89ab_cdef);
+        let avp = AVP::from_interface_id(1, &interface_id);
+        assert_eq!(avp.encode_interface_id()?, interface_id);
+        assert_eq!(
+            AVP::from_bytes(1, &[0; 7]).encode_interface_id(),
+            Err(AVPError::InvalidAttributeLengthError(
+                "8 bytes".to_owned(),
+                7
+            ))
+        );
+        Ok(())
+    }
+
     #[test]
     fn should_convert_ipv6_prefix_fail_because_of_invalid_prefix_length() {
         let avp = AVP::from_ipv6_prefix(
@@ -912,4 +1274,20 @@ mod tests {
             AVPError::InvalidAttributeLengthError("16 bytes".to_owned(), 17)
         );
     }
+
+    #[test]
+    fn it_should_convert_attribute_to_raw_bytes() -> Result<(), AVPError> {
+        let raw = [26, 12, 0, 0, 0x86, 0x9f, 1, 6, b'o', b'p', b'a', b'q'];
+        let avp = AVP::decode_raw(&raw)?;
+        assert_eq!(avp.get_type(), 26);
+        assert_eq!(avp.get_vendor_id(), Some(34463));
+        assert_eq!(avp.encode_raw()?, raw);
+
+        assert_eq!(AVP::from_string(1, "user").get_vendor_id(), None);
+        assert_eq!(AVP::from_bytes(26, &[0, 0]).get_vendor_id(), None);
+        assert!(AVP::decode_raw(&[1]).is_err());
+        assert!(AVP::decode_raw(&[1, 5, 0]).is_err());
+        assert!(AVP::from_bytes(1, &[0; 254]).encode_raw().is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,138 @@
+//! CHAP-Password (RFC 2865 section 5.3), i.e. the CHAP Ident and the MD5 of the Ident, the password and the challenge
+//! (RFC 1994).
+//!
+//! The challenge is CHAP-Challenge of the request, or the Request Authenticator if the request doesn't have that.
+//!
+//! ```
+//! use radius::core::chap;
+//! use radius::core::code::Code;
+//! use radius::core::packet::Packet;
+//!
+//! // the client side
+//! let mut request = Packet::new(Code::AccessRequest, b"secret");
+//! chap::add_chap_password(&mut request, 1, b"p@ssw0rd", None);
+//!
+//! // the server side, with the cleartext password of the user
+//! assert_eq!(chap::verify_chap_password(&request, b"p@ssw0rd"), Some(Ok(true)));
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::core::avp::AVPError;
+use crate::core::constant_time;
+use crate::core::packet::Packet;
+use crate::core::rfc2865;
+
+/// The length of the response of CHAP-Password, i.e. without the Ident.
+pub const CHAP_RESPONSE_LENGTH: usize = 16;
+
+/// Returns the CHAP response, i.e. `MD5(ident + password + challenge)`.
+pub fn compute_chap_response(
+    ident: u8,
+    password: &[u8],
+    challenge: &[u8],
+) -> [u8; CHAP_RESPONSE_LENGTH] {
+    let mut context = md5::Context::new();
+    context.consume([ident]);
+    context.consume(password);
+    context.consume(challenge);
+    context.compute().0
+}
+
+/// Add CHAP-Password of the password to a request.
+///
+/// If `challenge` is given, it's added as CHAP-Challenge; otherwise the challenge is the Request Authenticator of the
+/// request, so the request must not be re-created (e.g. by `Packet::new()`) after this.
+pub fn add_chap_password(
+    packet: &mut Packet,
+    ident: u8,
+    password: &[u8],
+    challenge: Option<&[u8]>,
+) {
+    let response = match challenge {
+        Some(challenge) => {
+            rfc2865::add_chap_challenge(packet, challenge);
+            compute_chap_response(ident, password, challenge)
+        }
+        None => compute_chap_response(ident, password, packet.get_authenticator()),
+    };
+    let mut value = Vec::with_capacity(1 + CHAP_RESPONSE_LENGTH);
+    value.push(ident);
+    value.extend_from_slice(&response);
+    rfc2865::add_chap_password(packet, &value);
+}
+
+/// Returns the challenge of the CHAP-Password of a request, i.e. CHAP-Challenge or the Request Authenticator.
+pub fn lookup_chap_challenge_or_authenticator(packet: &Packet) -> Vec<u8> {
+    rfc2865::lookup_chap_challenge(packet).unwrap_or_else(|| packet.get_authenticator().to_owned())
+}
+
+/// Verify the CHAP-Password of a request against the cleartext password.
+///
+/// It returns `None` if the request doesn't have CHAP-Password; a CHAP-Password that isn't 17 bytes is an error.
+pub fn verify_chap_password(packet: &Packet, password: &[u8]) -> Option<Result<bool, AVPError>> {
+    let value = rfc2865::lookup_chap_password(packet)?;
+    if value.len() != 1 + CHAP_RESPONSE_LENGTH {
+        return Some(Err(AVPError::InvalidAttributeLengthError(
+            format!("{} bytes", 1 + CHAP_RESPONSE_LENGTH),
+            value.len(),
+        )));
+    }
+    let challenge = lookup_chap_challenge_or_authenticator(packet);
+    let expected = compute_chap_response(value[0], password, &challenge);
+    Some(Ok(constant_time::eq(&expected, &value[1..])))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::avp::AVPError;
+    use crate::core::chap::{add_chap_password, compute_chap_response, verify_chap_password};
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+
+    #[test]
+    fn test_compute_chap_response() {
+        // MD5(0x01 + "password" + 16 bytes of 0x00..0x0f)
+        let challenge: Vec<u8> = (0..16).collect();
+        assert_eq!(
+            compute_chap_response(1, b"password", &challenge),
+            md5::compute([&[1][..], b"password", &challenge].concat()).0
+        );
+    }
+
+    #[test]
+    fn test_chap_password() {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        assert_eq!(verify_chap_password(&request, b"p@ssw0rd"), None);
+
+        // the challenge is the Request Authenticator, also after encoding and decoding
+        add_chap_password(&mut request, 7, b"p@ssw0rd", None);
+        let decoded = Packet::decode(&request.encode().unwrap(), b"secret").unwrap();
+        assert_eq!(rfc2865::lookup_chap_password(&decoded).unwrap()[0], 7);
+        assert_eq!(verify_chap_password(&decoded, b"p@ssw0rd"), Some(Ok(true)));
+        assert_eq!(verify_chap_password(&decoded, b"password"), Some(Ok(false)));
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        add_chap_password(&mut request, 8, b"p@ssw0rd", Some(b"challenge"));
+        assert_eq!(
+            rfc2865::lookup_chap_challenge(&request).unwrap(),
+            b"challenge"
+        );
+        assert_eq!(verify_chap_password(&request, b"p@ssw0rd"), Some(Ok(true)));
+        rfc2865::delete_chap_challenge(&mut request);
+        assert_eq!(verify_chap_password(&request, b"p@ssw0rd"), Some(Ok(false)));
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_chap_password(&mut request, &[0; 16]);
+        assert_eq!(
+            verify_chap_password(&request, b"p@ssw0rd"),
+            Some(Err(AVPError::InvalidAttributeLengthError(
+                "17 bytes".to_owned(),
+                16
+            )))
+        );
+    }
+}
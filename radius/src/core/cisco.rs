@@ -0,0 +1,844 @@
+// Code generated by machine generator; DO NOT EDIT.
+
+//! Utility for the Vendor-Specific attributes of Cisco (Vendor-Id 9).
+//!
+//! This module handles the packet according to the following definition:
+//! ```text
+//! //! # -*- text -*-
+//! # Copyright (C) 2020 The FreeRADIUS Server project and contributors
+//! # This work is licensed under CC-BY version 4.0 https://creativecommons.org/licenses/by/4.0
+//! # Version $Id$
+//! #
+//! #    The common Cisco attributes, from the dictionary.cisco of FreeRADIUS.
+//! #
+//! #    $Id$
+//! #
+//!
+//! VENDOR        Cisco                9
+//!
+//! BEGIN-VENDOR    Cisco
+//!
+//! ATTRIBUTE    Cisco-AVPair                1    string
+//! ATTRIBUTE    Cisco-NAS-Port                2    string
+//!
+//! ATTRIBUTE    h323-remote-address            23    string
+//! ATTRIBUTE    h323-conf-id                24    string
+//! ATTRIBUTE    h323-setup-time                25    string
+//! ATTRIBUTE    h323-call-origin            26    string
+//! ATTRIBUTE    h323-call-type                27    string
+//! ATTRIBUTE    h323-connect-time            28    string
+//! ATTRIBUTE    h323-disconnect-time            29    string
+//! ATTRIBUTE    h323-disconnect-cause            30    string
+//! ATTRIBUTE    h323-voice-quality            31    string
+//! ATTRIBUTE    h323-gw-id                33    string
+//! ATTRIBUTE    h323-incoming-conf-id            35    string
+//!
+//! ATTRIBUTE    Cisco-Multilink-ID            187    integer
+//! ATTRIBUTE    Cisco-Num-In-Multilink            188    integer
+//! ATTRIBUTE    Cisco-Pre-Input-Octets            190    integer
+//! ATTRIBUTE    Cisco-Pre-Output-Octets            191    integer
+//! ATTRIBUTE    Cisco-Pre-Input-Packets            192    integer
+//! ATTRIBUTE    Cisco-Pre-Output-Packets        193    integer
+//! ATTRIBUTE    Cisco-Maximum-Time            194    integer
+//! ATTRIBUTE    Cisco-Disconnect-Cause            195    integer
+//!
+//! VALUE    Cisco-Disconnect-Cause        Unknown            2
+//! VALUE    Cisco-Disconnect-Cause        CLID-Authentication-Failure    4
+//! VALUE    Cisco-Disconnect-Cause        No-Carrier        10
+//! VALUE    Cisco-Disconnect-Cause        Lost-Carrier        11
+//! VALUE    Cisco-Disconnect-Cause        No-Detected-Result-Codes    12
+//! VALUE    Cisco-Disconnect-Cause        User-Ends-Session    20
+//! VALUE    Cisco-Disconnect-Cause        Idle-Timeout        21
+//! VALUE    Cisco-Disconnect-Cause        Exit-Telnet-Session    22
+//! VALUE    Cisco-Disconnect-Cause        No-Remote-IP-Addr    23
+//!
+//! END-VENDOR    Cisco
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::packet::Packet;
+use crate::core::vsa::{self, VendorId};
+
+/// The Vendor-Id of Cisco.
+pub const VENDOR_CISCO: VendorId = 9;
+
+pub const CISCO_AV_PAIR_TYPE: AVPType = 1;
+/// Delete all of `cisco_av_pair` values from a packet.
+pub fn delete_cisco_av_pair(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_AV_PAIR_TYPE);
+}
+/// Add `cisco_av_pair` string value to a packet.
+pub fn add_cisco_av_pair(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(CISCO_AV_PAIR_TYPE, value),
+    );
+}
+/// Lookup a `cisco_av_pair` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_av_pair`, it returns `None`.
+pub fn lookup_cisco_av_pair(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_AV_PAIR_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `cisco_av_pair` string value from a packet.
+pub fn lookup_all_cisco_av_pair(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_AV_PAIR_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const CISCO_NAS_PORT_TYPE: AVPType = 2;
+/// Delete all of `cisco_nas_port` values from a packet.
+pub fn delete_cisco_nas_port(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_NAS_PORT_TYPE);
+}
+/// Add `cisco_nas_port` string value to a packet.
+pub fn add_cisco_nas_port(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(CISCO_NAS_PORT_TYPE, value),
+    );
+}
+/// Lookup a `cisco_nas_port` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_nas_port`, it returns `None`.
+pub fn lookup_cisco_nas_port(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_NAS_PORT_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `cisco_nas_port` string value from a packet.
+pub fn lookup_all_cisco_nas_port(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_NAS_PORT_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_REMOTE_ADDRESS_TYPE: AVPType = 23;
+/// Delete all of `h_323_remote_address` values from a packet.
+pub fn delete_h_323_remote_address(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_REMOTE_ADDRESS_TYPE);
+}
+/// Add `h_323_remote_address` string value to a packet.
+pub fn add_h_323_remote_address(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_REMOTE_ADDRESS_TYPE, value),
+    );
+}
+/// Lookup a `h_323_remote_address` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_remote_address`, it returns `None`.
+pub fn lookup_h_323_remote_address(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_REMOTE_ADDRESS_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_remote_address` string value from a packet.
+pub fn lookup_all_h_323_remote_address(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_REMOTE_ADDRESS_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_CONF_ID_TYPE: AVPType = 24;
+/// Delete all of `h_323_conf_id` values from a packet.
+pub fn delete_h_323_conf_id(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_CONF_ID_TYPE);
+}
+/// Add `h_323_conf_id` string value to a packet.
+pub fn add_h_323_conf_id(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_CONF_ID_TYPE, value),
+    );
+}
+/// Lookup a `h_323_conf_id` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_conf_id`, it returns `None`.
+pub fn lookup_h_323_conf_id(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_CONF_ID_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_conf_id` string value from a packet.
+pub fn lookup_all_h_323_conf_id(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_CONF_ID_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_SETUP_TIME_TYPE: AVPType = 25;
+/// Delete all of `h_323_setup_time` values from a packet.
+pub fn delete_h_323_setup_time(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_SETUP_TIME_TYPE);
+}
+/// Add `h_323_setup_time` string value to a packet.
+pub fn add_h_323_setup_time(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_SETUP_TIME_TYPE, value),
+    );
+}
+/// Lookup a `h_323_setup_time` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_setup_time`, it returns `None`.
+pub fn lookup_h_323_setup_time(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_SETUP_TIME_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_setup_time` string value from a packet.
+pub fn lookup_all_h_323_setup_time(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_SETUP_TIME_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_CALL_ORIGIN_TYPE: AVPType = 26;
+/// Delete all of `h_323_call_origin` values from a packet.
+pub fn delete_h_323_call_origin(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_CALL_ORIGIN_TYPE);
+}
+/// Add `h_323_call_origin` string value to a packet.
+pub fn add_h_323_call_origin(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_CALL_ORIGIN_TYPE, value),
+    );
+}
+/// Lookup a `h_323_call_origin` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_call_origin`, it returns `None`.
+pub fn lookup_h_323_call_origin(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_CALL_ORIGIN_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_call_origin` string value from a packet.
+pub fn lookup_all_h_323_call_origin(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_CALL_ORIGIN_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_CALL_TYPE_TYPE: AVPType = 27;
+/// Delete all of `h_323_call_type` values from a packet.
+pub fn delete_h_323_call_type(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_CALL_TYPE_TYPE);
+}
+/// Add `h_323_call_type` string value to a packet.
+pub fn add_h_323_call_type(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_CALL_TYPE_TYPE, value),
+    );
+}
+/// Lookup a `h_323_call_type` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_call_type`, it returns `None`.
+pub fn lookup_h_323_call_type(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_CALL_TYPE_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_call_type` string value from a packet.
+pub fn lookup_all_h_323_call_type(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_CALL_TYPE_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_CONNECT_TIME_TYPE: AVPType = 28;
+/// Delete all of `h_323_connect_time` values from a packet.
+pub fn delete_h_323_connect_time(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_CONNECT_TIME_TYPE);
+}
+/// Add `h_323_connect_time` string value to a packet.
+pub fn add_h_323_connect_time(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_CONNECT_TIME_TYPE, value),
+    );
+}
+/// Lookup a `h_323_connect_time` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_connect_time`, it returns `None`.
+pub fn lookup_h_323_connect_time(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_CONNECT_TIME_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_connect_time` string value from a packet.
+pub fn lookup_all_h_323_connect_time(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_CONNECT_TIME_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_DISCONNECT_TIME_TYPE: AVPType = 29;
+/// Delete all of `h_323_disconnect_time` values from a packet.
+pub fn delete_h_323_disconnect_time(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_DISCONNECT_TIME_TYPE);
+}
+/// Add `h_323_disconnect_time` string value to a packet.
+pub fn add_h_323_disconnect_time(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_DISCONNECT_TIME_TYPE, value),
+    );
+}
+/// Lookup a `h_323_disconnect_time` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_disconnect_time`, it returns `None`.
+pub fn lookup_h_323_disconnect_time(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_DISCONNECT_TIME_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_disconnect_time` string value from a packet.
+pub fn lookup_all_h_323_disconnect_time(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_DISCONNECT_TIME_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_DISCONNECT_CAUSE_TYPE: AVPType = 30;
+/// Delete all of `h_323_disconnect_cause` values from a packet.
+pub fn delete_h_323_disconnect_cause(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_DISCONNECT_CAUSE_TYPE);
+}
+/// Add `h_323_disconnect_cause` string value to a packet.
+pub fn add_h_323_disconnect_cause(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_DISCONNECT_CAUSE_TYPE, value),
+    );
+}
+/// Lookup a `h_323_disconnect_cause` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_disconnect_cause`, it returns `None`.
+pub fn lookup_h_323_disconnect_cause(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_DISCONNECT_CAUSE_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_disconnect_cause` string value from a packet.
+pub fn lookup_all_h_323_disconnect_cause(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_DISCONNECT_CAUSE_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_VOICE_QUALITY_TYPE: AVPType = 31;
+/// Delete all of `h_323_voice_quality` values from a packet.
+pub fn delete_h_323_voice_quality(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_VOICE_QUALITY_TYPE);
+}
+/// Add `h_323_voice_quality` string value to a packet.
+pub fn add_h_323_voice_quality(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_VOICE_QUALITY_TYPE, value),
+    );
+}
+/// Lookup a `h_323_voice_quality` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_voice_quality`, it returns `None`.
+pub fn lookup_h_323_voice_quality(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_VOICE_QUALITY_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_voice_quality` string value from a packet.
+pub fn lookup_all_h_323_voice_quality(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_VOICE_QUALITY_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_GW_ID_TYPE: AVPType = 33;
+/// Delete all of `h_323_gw_id` values from a packet.
+pub fn delete_h_323_gw_id(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_GW_ID_TYPE);
+}
+/// Add `h_323_gw_id` string value to a packet.
+pub fn add_h_323_gw_id(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_GW_ID_TYPE, value),
+    );
+}
+/// Lookup a `h_323_gw_id` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_gw_id`, it returns `None`.
+pub fn lookup_h_323_gw_id(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_GW_ID_TYPE).map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_gw_id` string value from a packet.
+pub fn lookup_all_h_323_gw_id(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_GW_ID_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const H_323_INCOMING_CONF_ID_TYPE: AVPType = 35;
+/// Delete all of `h_323_incoming_conf_id` values from a packet.
+pub fn delete_h_323_incoming_conf_id(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, H_323_INCOMING_CONF_ID_TYPE);
+}
+/// Add `h_323_incoming_conf_id` string value to a packet.
+pub fn add_h_323_incoming_conf_id(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_string(H_323_INCOMING_CONF_ID_TYPE, value),
+    );
+}
+/// Lookup a `h_323_incoming_conf_id` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `h_323_incoming_conf_id`, it returns `None`.
+pub fn lookup_h_323_incoming_conf_id(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, H_323_INCOMING_CONF_ID_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `h_323_incoming_conf_id` string value from a packet.
+pub fn lookup_all_h_323_incoming_conf_id(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, H_323_INCOMING_CONF_ID_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const CISCO_MULTILINK_ID_TYPE: AVPType = 187;
+/// Delete all of `cisco_multilink_id` values from a packet.
+pub fn delete_cisco_multilink_id(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_MULTILINK_ID_TYPE);
+}
+/// Add `cisco_multilink_id` integer value to a packet.
+pub fn add_cisco_multilink_id(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_u32(CISCO_MULTILINK_ID_TYPE, value),
+    );
+}
+/// Lookup a `cisco_multilink_id` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_multilink_id`, it returns `None`.
+pub fn lookup_cisco_multilink_id(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_MULTILINK_ID_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `cisco_multilink_id` integer value from a packet.
+pub fn lookup_all_cisco_multilink_id(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_MULTILINK_ID_TYPE) {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const CISCO_NUM_IN_MULTILINK_TYPE: AVPType = 188;
+/// Delete all of `cisco_num_in_multilink` values from a packet.
+pub fn delete_cisco_num_in_multilink(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_NUM_IN_MULTILINK_TYPE);
+}
+/// Add `cisco_num_in_multilink` integer value to a packet.
+pub fn add_cisco_num_in_multilink(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_u32(CISCO_NUM_IN_MULTILINK_TYPE, value),
+    );
+}
+/// Lookup a `cisco_num_in_multilink` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_num_in_multilink`, it returns `None`.
+pub fn lookup_cisco_num_in_multilink(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_NUM_IN_MULTILINK_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `cisco_num_in_multilink` integer value from a packet.
+pub fn lookup_all_cisco_num_in_multilink(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_NUM_IN_MULTILINK_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const CISCO_PRE_INPUT_OCTETS_TYPE: AVPType = 190;
+/// Delete all of `cisco_pre_input_octets` values from a packet.
+pub fn delete_cisco_pre_input_octets(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_PRE_INPUT_OCTETS_TYPE);
+}
+/// Add `cisco_pre_input_octets` integer value to a packet.
+pub fn add_cisco_pre_input_octets(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_u32(CISCO_PRE_INPUT_OCTETS_TYPE, value),
+    );
+}
+/// Lookup a `cisco_pre_input_octets` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_pre_input_octets`, it returns `None`.
+pub fn lookup_cisco_pre_input_octets(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_PRE_INPUT_OCTETS_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `cisco_pre_input_octets` integer value from a packet.
+pub fn lookup_all_cisco_pre_input_octets(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_PRE_INPUT_OCTETS_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const CISCO_PRE_OUTPUT_OCTETS_TYPE: AVPType = 191;
+/// Delete all of `cisco_pre_output_octets` values from a packet.
+pub fn delete_cisco_pre_output_octets(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_PRE_OUTPUT_OCTETS_TYPE);
+}
+/// Add `cisco_pre_output_octets` integer value to a packet.
+pub fn add_cisco_pre_output_octets(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_u32(CISCO_PRE_OUTPUT_OCTETS_TYPE, value),
+    );
+}
+/// Lookup a `cisco_pre_output_octets` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_pre_output_octets`, it returns `None`.
+pub fn lookup_cisco_pre_output_octets(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_PRE_OUTPUT_OCTETS_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `cisco_pre_output_octets` integer value from a packet.
+pub fn lookup_all_cisco_pre_output_octets(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_PRE_OUTPUT_OCTETS_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const CISCO_PRE_INPUT_PACKETS_TYPE: AVPType = 192;
+/// Delete all of `cisco_pre_input_packets` values from a packet.
+pub fn delete_cisco_pre_input_packets(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_PRE_INPUT_PACKETS_TYPE);
+}
+/// Add `cisco_pre_input_packets` integer value to a packet.
+pub fn add_cisco_pre_input_packets(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_u32(CISCO_PRE_INPUT_PACKETS_TYPE, value),
+    );
+}
+/// Lookup a `cisco_pre_input_packets` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_pre_input_packets`, it returns `None`.
+pub fn lookup_cisco_pre_input_packets(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_PRE_INPUT_PACKETS_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `cisco_pre_input_packets` integer value from a packet.
+pub fn lookup_all_cisco_pre_input_packets(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_PRE_INPUT_PACKETS_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const CISCO_PRE_OUTPUT_PACKETS_TYPE: AVPType = 193;
+/// Delete all of `cisco_pre_output_packets` values from a packet.
+pub fn delete_cisco_pre_output_packets(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_PRE_OUTPUT_PACKETS_TYPE);
+}
+/// Add `cisco_pre_output_packets` integer value to a packet.
+pub fn add_cisco_pre_output_packets(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_u32(CISCO_PRE_OUTPUT_PACKETS_TYPE, value),
+    );
+}
+/// Lookup a `cisco_pre_output_packets` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_pre_output_packets`, it returns `None`.
+pub fn lookup_cisco_pre_output_packets(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_PRE_OUTPUT_PACKETS_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `cisco_pre_output_packets` integer value from a packet.
+pub fn lookup_all_cisco_pre_output_packets(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_PRE_OUTPUT_PACKETS_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const CISCO_MAXIMUM_TIME_TYPE: AVPType = 194;
+/// Delete all of `cisco_maximum_time` values from a packet.
+pub fn delete_cisco_maximum_time(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_MAXIMUM_TIME_TYPE);
+}
+/// Add `cisco_maximum_time` integer value to a packet.
+pub fn add_cisco_maximum_time(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_u32(CISCO_MAXIMUM_TIME_TYPE, value),
+    );
+}
+/// Lookup a `cisco_maximum_time` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_maximum_time`, it returns `None`.
+pub fn lookup_cisco_maximum_time(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_MAXIMUM_TIME_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `cisco_maximum_time` integer value from a packet.
+pub fn lookup_all_cisco_maximum_time(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_MAXIMUM_TIME_TYPE) {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const CISCO_DISCONNECT_CAUSE_TYPE: AVPType = 195;
+/// Delete all of `cisco_disconnect_cause` values from a packet.
+pub fn delete_cisco_disconnect_cause(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, CISCO_DISCONNECT_CAUSE_TYPE);
+}
+/// Add `cisco_disconnect_cause` value-defined integer value to a packet.
+pub fn add_cisco_disconnect_cause(packet: &mut Packet, value: CiscoDisconnectCause) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_CISCO,
+        AVP::from_u32(CISCO_DISCONNECT_CAUSE_TYPE, value),
+    );
+}
+/// Lookup a `cisco_disconnect_cause` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_disconnect_cause`, it returns `None`.
+pub fn lookup_cisco_disconnect_cause(
+    packet: &Packet,
+) -> Option<Result<CiscoDisconnectCause, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_DISCONNECT_CAUSE_TYPE)
+        .map(|v| Ok(v.encode_u32()? as CiscoDisconnectCause))
+}
+/// Lookup all of the `cisco_disconnect_cause` value-defined integer value from a packet.
+pub fn lookup_all_cisco_disconnect_cause(
+    packet: &Packet,
+) -> Result<Vec<CiscoDisconnectCause>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_DISCONNECT_CAUSE_TYPE)
+    {
+        vec.push(avp.encode_u32()? as CiscoDisconnectCause)
+    }
+    Ok(vec)
+}
+/// Lookup a `cisco_disconnect_cause` value from a packet as `CiscoDisconnectCauseValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `cisco_disconnect_cause`, it returns `None`.
+pub fn lookup_cisco_disconnect_cause_value(
+    packet: &Packet,
+) -> Option<Result<CiscoDisconnectCauseValue, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, CISCO_DISCONNECT_CAUSE_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `cisco_disconnect_cause` values from a packet as `CiscoDisconnectCauseValue`.
+pub fn lookup_all_cisco_disconnect_cause_value(
+    packet: &Packet,
+) -> Result<Vec<CiscoDisconnectCauseValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_CISCO, CISCO_DISCONNECT_CAUSE_TYPE)
+    {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
+
+pub type CiscoDisconnectCause = u32;
+pub const CISCO_DISCONNECT_CAUSE_UNKNOWN: CiscoDisconnectCause = 2;
+pub const CISCO_DISCONNECT_CAUSE_CLID_AUTHENTICATION_FAILURE: CiscoDisconnectCause = 4;
+pub const CISCO_DISCONNECT_CAUSE_NO_CARRIER: CiscoDisconnectCause = 10;
+pub const CISCO_DISCONNECT_CAUSE_LOST_CARRIER: CiscoDisconnectCause = 11;
+pub const CISCO_DISCONNECT_CAUSE_NO_DETECTED_RESULT_CODES: CiscoDisconnectCause = 12;
+pub const CISCO_DISCONNECT_CAUSE_USER_ENDS_SESSION: CiscoDisconnectCause = 20;
+pub const CISCO_DISCONNECT_CAUSE_IDLE_TIMEOUT: CiscoDisconnectCause = 21;
+pub const CISCO_DISCONNECT_CAUSE_EXIT_TELNET_SESSION: CiscoDisconnectCause = 22;
+pub const CISCO_DISCONNECT_CAUSE_NO_REMOTE_IP_ADDR: CiscoDisconnectCause = 23;
+
+/// The values of `Cisco-Disconnect-Cause` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `CiscoDisconnectCauseValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum CiscoDisconnectCauseValue {
+    /// `Unknown`
+    Unknown = 2,
+    /// `CLID-Authentication-Failure`
+    ClidAuthenticationFailure = 4,
+    /// `No-Carrier`
+    NoCarrier = 10,
+    /// `Lost-Carrier`
+    LostCarrier = 11,
+    /// `No-Detected-Result-Codes`
+    NoDetectedResultCodes = 12,
+    /// `User-Ends-Session`
+    UserEndsSession = 20,
+    /// `Idle-Timeout`
+    IdleTimeout = 21,
+    /// `Exit-Telnet-Session`
+    ExitTelnetSession = 22,
+    /// `No-Remote-IP-Addr`
+    NoRemoteIPAddr = 23,
+}
+
+impl From<CiscoDisconnectCauseValue> for u32 {
+    fn from(value: CiscoDisconnectCauseValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for CiscoDisconnectCauseValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            2 => Ok(CiscoDisconnectCauseValue::Unknown),
+            4 => Ok(CiscoDisconnectCauseValue::ClidAuthenticationFailure),
+            10 => Ok(CiscoDisconnectCauseValue::NoCarrier),
+            11 => Ok(CiscoDisconnectCauseValue::LostCarrier),
+            12 => Ok(CiscoDisconnectCauseValue::NoDetectedResultCodes),
+            20 => Ok(CiscoDisconnectCauseValue::UserEndsSession),
+            21 => Ok(CiscoDisconnectCauseValue::IdleTimeout),
+            22 => Ok(CiscoDisconnectCauseValue::ExitTelnetSession),
+            23 => Ok(CiscoDisconnectCauseValue::NoRemoteIPAddr),
+            _ => Err(AVPError::UnknownValueError(
+                "Cisco-Disconnect-Cause".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Cisco-AVPair", CISCO_AV_PAIR_TYPE, "string"),
+    AttributeDefinition::new("Cisco-NAS-Port", CISCO_NAS_PORT_TYPE, "string"),
+    AttributeDefinition::new("h323-remote-address", H_323_REMOTE_ADDRESS_TYPE, "string"),
+    AttributeDefinition::new("h323-conf-id", H_323_CONF_ID_TYPE, "string"),
+    AttributeDefinition::new("h323-setup-time", H_323_SETUP_TIME_TYPE, "string"),
+    AttributeDefinition::new("h323-call-origin", H_323_CALL_ORIGIN_TYPE, "string"),
+    AttributeDefinition::new("h323-call-type", H_323_CALL_TYPE_TYPE, "string"),
+    AttributeDefinition::new("h323-connect-time", H_323_CONNECT_TIME_TYPE, "string"),
+    AttributeDefinition::new("h323-disconnect-time", H_323_DISCONNECT_TIME_TYPE, "string"),
+    AttributeDefinition::new(
+        "h323-disconnect-cause",
+        H_323_DISCONNECT_CAUSE_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("h323-voice-quality", H_323_VOICE_QUALITY_TYPE, "string"),
+    AttributeDefinition::new("h323-gw-id", H_323_GW_ID_TYPE, "string"),
+    AttributeDefinition::new(
+        "h323-incoming-conf-id",
+        H_323_INCOMING_CONF_ID_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("Cisco-Multilink-ID", CISCO_MULTILINK_ID_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Cisco-Num-In-Multilink",
+        CISCO_NUM_IN_MULTILINK_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "Cisco-Pre-Input-Octets",
+        CISCO_PRE_INPUT_OCTETS_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "Cisco-Pre-Output-Octets",
+        CISCO_PRE_OUTPUT_OCTETS_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "Cisco-Pre-Input-Packets",
+        CISCO_PRE_INPUT_PACKETS_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "Cisco-Pre-Output-Packets",
+        CISCO_PRE_OUTPUT_PACKETS_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new("Cisco-Maximum-Time", CISCO_MAXIMUM_TIME_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Cisco-Disconnect-Cause",
+        CISCO_DISCONNECT_CAUSE_TYPE,
+        "integer",
+    ),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Cisco-Disconnect-Cause", "Unknown", 2),
+    ValueDefinition::new("Cisco-Disconnect-Cause", "CLID-Authentication-Failure", 4),
+    ValueDefinition::new("Cisco-Disconnect-Cause", "No-Carrier", 10),
+    ValueDefinition::new("Cisco-Disconnect-Cause", "Lost-Carrier", 11),
+    ValueDefinition::new("Cisco-Disconnect-Cause", "No-Detected-Result-Codes", 12),
+    ValueDefinition::new("Cisco-Disconnect-Cause", "User-Ends-Session", 20),
+    ValueDefinition::new("Cisco-Disconnect-Cause", "Idle-Timeout", 21),
+    ValueDefinition::new("Cisco-Disconnect-Cause", "Exit-Telnet-Session", 22),
+    ValueDefinition::new("Cisco-Disconnect-Cause", "No-Remote-IP-Addr", 23),
+];
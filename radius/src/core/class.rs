@@ -0,0 +1,191 @@
+//! Utility for the correlation between authentication and accounting by the Class attribute.
+//!
+//! The server puts an opaque Class value into Access-Accept, and the NAS sends that back in the Accounting-Requests
+//! of the session as it is (see also: https://tools.ietf.org/html/rfc2865#section-5.25).
+//! `ClassIssuer` generates the value that is signed with a server-side key, so it can be verified that
+//! the value in an Accounting-Request is the one that this server has issued.
+
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use chrono::{DateTime, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use md5_digest::Md5;
+use rand::Rng;
+use thiserror::Error;
+
+#[cfg(feature = "std")]
+use crate::core::deterministic;
+use crate::core::packet::Packet;
+use crate::core::random;
+use crate::core::rfc2865;
+
+const CLASS_MAGIC: &[u8] = b"rrc1";
+const ID_LENGTH: usize = 16;
+const TIMESTAMP_LENGTH: usize = 8;
+const MAC_LENGTH: usize = 16;
+const CLASS_LENGTH: usize = CLASS_MAGIC.len() + ID_LENGTH + TIMESTAMP_LENGTH + MAC_LENGTH;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ClassError {
+    /// An error that is raised when the Class value has not been issued by `ClassIssuer`.
+    #[error("the class value has not been issued by this server")]
+    UnknownClassFormatError(),
+    /// An error that is raised when the signature of the Class value doesn't match.
+    #[error("the class value has an invalid signature")]
+    InvalidSignatureError(),
+}
+
+/// The content of a Class value that has been issued by `ClassIssuer`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClassToken {
+    id: [u8; ID_LENGTH],
+    issued_at: DateTime<Utc>,
+}
+
+impl ClassToken {
+    /// Returns the random identifier that is unique per Access-Accept.
+    pub fn get_id(&self) -> &[u8] {
+        &self.id
+    }
+
+    pub fn get_issued_at(&self) -> DateTime<Utc> {
+        self.issued_at
+    }
+}
+
+/// An issuer of the signed Class values.
+pub struct ClassIssuer {
+    key: Vec<u8>,
+}
+
+impl ClassIssuer {
+    /// A constructor for an issuer with the key to sign the Class values.
+    /// The key has to be shared between the servers that handle the authentication and the accounting.
+    pub fn new(key: &[u8]) -> Self {
+        ClassIssuer { key: key.to_vec() }
+    }
+
+    /// HMAC-MD5 of the key, for the signature of a value.
+    fn mac(&self) -> Hmac<Md5> {
+        Hmac::<Md5>::new_from_slice(&self.key).expect("HMAC accepts any key length")
+    }
+
+    /// Generate a new Class value, and return that with its content.
+    pub fn issue(&self, issued_at: DateTime<Utc>) -> (ClassToken, Vec<u8>) {
+        let id: [u8; ID_LENGTH] = random::rng().gen();
+
+        let mut value = Vec::with_capacity(CLASS_LENGTH);
+        value.extend_from_slice(CLASS_MAGIC);
+        value.extend_from_slice(&id);
+        value.extend_from_slice(&issued_at.timestamp().to_be_bytes());
+        let mut mac = self.mac();
+        mac.update(&value);
+        value.extend_from_slice(&mac.finalize().into_bytes());
+
+        let issued_at = Utc.timestamp_opt(issued_at.timestamp(), 0).unwrap();
+        (ClassToken { id, issued_at }, value)
+    }
+
+    /// Add a new Class value to a packet (e.g. Access-Accept), and return its content.
+    #[cfg(feature = "std")]
+    pub fn add_class(&self, packet: &mut Packet) -> ClassToken {
+        let (token, value) = self.issue(deterministic::now());
+        rfc2865::add_class(packet, &value);
+        token
+    }
+
+    /// Verify a Class value and return its content.
+    pub fn verify(&self, value: &[u8]) -> Result<ClassToken, ClassError> {
+        if value.len() != CLASS_LENGTH || !value.starts_with(CLASS_MAGIC) {
+            return Err(ClassError::UnknownClassFormatError());
+        }
+
+        let (signed, mac) = value.split_at(CLASS_LENGTH - MAC_LENGTH);
+        let mut expected_mac = self.mac();
+        expected_mac.update(signed);
+        if expected_mac.verify_slice(mac).is_err() {
+            return Err(ClassError::InvalidSignatureError());
+        }
+
+        let id_end = CLASS_MAGIC.len() + ID_LENGTH;
+        let timestamp = i64::from_be_bytes(signed[id_end..].try_into().unwrap());
+        let issued_at = match Utc.timestamp_opt(timestamp, 0).single() {
+            Some(issued_at) => issued_at,
+            None => return Err(ClassError::UnknownClassFormatError()),
+        };
+        Ok(ClassToken {
+            id: signed[CLASS_MAGIC.len()..id_end].try_into().unwrap(),
+            issued_at,
+        })
+    }
+
+    /// Lookup the Class value that has been issued by this issuer from a packet (e.g. Accounting-Request) and verify that.
+    ///
+    /// The Class values in other formats (e.g. the ones from the other servers) are skipped;
+    /// it returns `None` if there is no Class value in the format of this issuer.
+    pub fn lookup_class(&self, packet: &Packet) -> Option<Result<ClassToken, ClassError>> {
+        rfc2865::lookup_all_class(packet)
+            .iter()
+            .map(|value| self.verify(value))
+            .find(|result| result != &Err(ClassError::UnknownClassFormatError()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::class::{ClassError, ClassIssuer};
+
+    #[test]
+    fn test_issue_and_verify() {
+        let issuer = ClassIssuer::new(b"class-key");
+        let issued_at = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let (token, value) = issuer.issue(issued_at);
+        assert_eq!(token.get_issued_at(), issued_at);
+        assert_eq!(issuer.verify(&value), Ok(token.clone()));
+
+        let (another_token, _) = issuer.issue(issued_at);
+        assert_ne!(token.get_id(), another_token.get_id());
+
+        let mut tampered = value.clone();
+        tampered[10] ^= 0x01;
+        assert_eq!(
+            issuer.verify(&tampered),
+            Err(ClassError::InvalidSignatureError())
+        );
+        assert_eq!(
+            ClassIssuer::new(b"another-key").verify(&value),
+            Err(ClassError::InvalidSignatureError())
+        );
+        assert_eq!(
+            issuer.verify(b"foreign-class"),
+            Err(ClassError::UnknownClassFormatError())
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lookup_class() {
+        use crate::core::code::Code;
+        use crate::core::packet::Packet;
+        use crate::core::rfc2865;
+
+        let issuer = ClassIssuer::new(b"class-key");
+        let mut accept = Packet::new(Code::AccessAccept, b"secret");
+        let token = issuer.add_class(&mut accept);
+
+        let mut accounting_request = Packet::new(Code::AccountingRequest, b"secret");
+        assert!(issuer.lookup_class(&accounting_request).is_none());
+
+        rfc2865::add_class(&mut accounting_request, b"foreign-class");
+        assert!(issuer.lookup_class(&accounting_request).is_none());
+
+        rfc2865::add_class(
+            &mut accounting_request,
+            &rfc2865::lookup_class(&accept).unwrap(),
+        );
+        assert_eq!(issuer.lookup_class(&accounting_request), Some(Ok(token)));
+    }
+}
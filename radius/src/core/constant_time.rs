@@ -0,0 +1,22 @@
+//! Constant-time comparisons of the authenticators and the digests.
+//!
+//! The verifications of the crate (e.g. the Request/Response Authenticators, Message-Authenticator, CHAP-Password,
+//! the MS-CHAPv2 and EAP-MD5 responses and the signed Class) compare the computed value with the received one by
+//! `eq()`, so the time of a verification doesn't tell how many bytes of a forged value match. A server that verifies
+//! its own digests (e.g. of a custom authentication) should compare them by this too.
+//!
+//! ```
+//! use radius::core::constant_time;
+//!
+//! assert!(constant_time::eq(b"digest", b"digest"));
+//! assert!(!constant_time::eq(b"digest", b"digesT"));
+//! assert!(!constant_time::eq(b"digest", b"dig"));
+//! ```
+
+use subtle::ConstantTimeEq;
+
+/// Returns whether the two byte strings are equal, in the time that depends only on their lengths; the lengths
+/// aren't secret, so the ones that differ are unequal immediately.
+pub fn eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
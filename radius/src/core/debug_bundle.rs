@@ -0,0 +1,445 @@
+//! Self-contained textual representation of a packet for the bug reports and the tests.
+//!
+//! A bundle has the metadata, the attributes that are resolved by the built-in dictionary, and the hex dump of the datagram.
+//! Only the hex dump is authoritative; the parser reads that back, and the other sections are for the human readers.
+//! The shared secret is never included, so pass that separately to decode the packet.
+//!
+//! ```text
+//! -----BEGIN RADIUS DEBUG BUNDLE-----
+//! Version: 1
+//! Generator: radius-rs 0.3.1
+//! Code: Access-Request (1)
+//! Identifier: 1
+//! Length: 33
+//! Authenticator: 000102030405060708090a0b0c0d0e0f
+//!
+//! Attributes:
+//!   User-Name (1) = "admin"
+//!   Service-Type (6) = Framed-User (2)
+//!
+//! Hex:
+//! 0000  01 01 00 21 00 01 02 03 04 05 06 07 08 09 0a 0b  |...!............|
+//! 0010  0c 0d 0e 0f 01 07 61 64 6d 69 6e 06 06 00 00 00  |......admin.....|
+//! 0020  02                                               |.|
+//! -----END RADIUS DEBUG BUNDLE-----
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use thiserror::Error;
+
+use crate::core::attributes::Attributes;
+use crate::core::avp::AVP;
+use crate::core::code::Code;
+use crate::core::dictionary;
+use crate::core::packet::{Packet, PacketError};
+
+const BEGIN_MARKER: &str = "-----BEGIN RADIUS DEBUG BUNDLE-----";
+const END_MARKER: &str = "-----END RADIUS DEBUG BUNDLE-----";
+const VERSION: &str = "1";
+const BYTES_PER_LINE: usize = 16;
+const HEADER_LENGTH: usize = 20;
+
+const VERSION_KEY: &str = "Version";
+const LENGTH_KEY: &str = "Length";
+const GENERATED_KEYS: &[&str] = &[
+    VERSION_KEY,
+    "Generator",
+    "Code",
+    "Identifier",
+    LENGTH_KEY,
+    "Authenticator",
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DebugBundleError {
+    /// An error that is raised when a bundle doesn't have the required part.
+    #[error("debug bundle doesn't have {0}")]
+    MissingPartError(String),
+    /// An error that is raised when a line of a bundle is malformed.
+    #[error("invalid line {0} of the debug bundle: {1}")]
+    InvalidLineError(usize, String),
+    /// An error that is raised when the version of a bundle is not supported.
+    #[error("unsupported version of the debug bundle: {0}")]
+    UnsupportedVersionError(String),
+    /// An error that is raised when the hex dump doesn't have the length in the metadata (e.g. it has been truncated).
+    #[error("hex dump has {1} bytes, but the metadata says {0} bytes")]
+    LengthMismatchError(usize, usize),
+    /// An error that is raised when decoding the datagram of a bundle has been failed.
+    #[error("failed to decode the packet of the debug bundle: {0}")]
+    DecodingError(PacketError),
+}
+
+/// A datagram and its metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugBundle {
+    datagram: Vec<u8>,
+    metadata: Vec<(String, String)>,
+}
+
+impl DebugBundle {
+    /// Make a bundle of a datagram (e.g. a received one as it is).
+    pub fn new(datagram: &[u8]) -> Self {
+        DebugBundle {
+            datagram: datagram.to_vec(),
+            metadata: Vec::new(),
+        }
+    }
+
+    /// Add a metadata (e.g. `("Source", "192.0.2.1:1812")`); the line breaks in the value are replaced by spaces.
+    ///
+    /// The keys that are generated by the bundle (e.g. `Code` and `Length`) are ignored.
+    pub fn add_metadata(&mut self, key: &str, value: &str) {
+        let key = key.trim().replace([':', '\r', '\n'], " ");
+        if key.is_empty() || GENERATED_KEYS.contains(&key.as_str()) {
+            return;
+        }
+        self.metadata
+            .push((key, value.trim().replace(['\r', '\n'], " ")));
+    }
+
+    pub fn get_datagram(&self) -> &[u8] {
+        &self.datagram
+    }
+
+    /// Returns the metadata that have been added (i.e. without the generated ones) in the order of the addition.
+    pub fn get_metadata(&self) -> &[(String, String)] {
+        &self.metadata
+    }
+
+    /// Decode the datagram with the shared secret.
+    pub fn to_packet(&self, secret: &[u8]) -> Result<Packet, DebugBundleError> {
+        Packet::decode(&self.datagram, secret).map_err(DebugBundleError::DecodingError)
+    }
+
+    /// Parse a bundle; the text around the bundle (e.g. the body of an issue) is ignored.
+    pub fn parse(text: &str) -> Result<Self, DebugBundleError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim_end()))
+            .skip_while(|(_, line)| line.trim() != BEGIN_MARKER);
+        if lines.next().is_none() {
+            return Err(DebugBundleError::MissingPartError(BEGIN_MARKER.to_owned()));
+        }
+
+        let mut version = None;
+        let mut length = None;
+        let mut metadata = Vec::new();
+        let mut datagram = Vec::new();
+        let mut in_hex = false;
+        let mut in_attributes = false;
+        let mut has_end = false;
+        for (line_number, line) in lines {
+            let invalid = |message: &str| {
+                DebugBundleError::InvalidLineError(line_number, format!("{message}: {line}"))
+            };
+            let trimmed = line.trim();
+            if trimmed == END_MARKER {
+                has_end = true;
+                break;
+            }
+            if trimmed.is_empty() {
+                continue;
+            }
+            if trimmed == "Attributes:" {
+                in_attributes = true;
+                continue;
+            }
+            if trimmed == "Hex:" {
+                in_attributes = false;
+                in_hex = true;
+                continue;
+            }
+            if in_hex {
+                let dump = trimmed.split("  |").next().unwrap_or_default();
+                let mut fields = dump.split_whitespace();
+                let offset = fields
+                    .next()
+                    .and_then(|offset| usize::from_str_radix(offset, 16).ok())
+                    .ok_or_else(|| invalid("invalid offset"))?;
+                if offset != datagram.len() {
+                    return Err(invalid("unexpected offset"));
+                }
+                for field in fields {
+                    let b = u8::from_str_radix(field, 16).map_err(|_| invalid("invalid byte"))?;
+                    datagram.push(b);
+                }
+                continue;
+            }
+            if in_attributes {
+                continue;
+            }
+
+            let (key, value) = trimmed
+                .split_once(':')
+                .ok_or_else(|| invalid("invalid metadata"))?;
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                VERSION_KEY => version = Some(value.to_owned()),
+                LENGTH_KEY => {
+                    length = Some(
+                        value
+                            .parse::<usize>()
+                            .map_err(|_| invalid("invalid length"))?,
+                    )
+                }
+                key if GENERATED_KEYS.contains(&key) => {}
+                key => metadata.push((key.to_owned(), value.to_owned())),
+            }
+        }
+
+        if !has_end {
+            return Err(DebugBundleError::MissingPartError(END_MARKER.to_owned()));
+        }
+        match version {
+            Some(version) if version == VERSION => {}
+            Some(version) => return Err(DebugBundleError::UnsupportedVersionError(version)),
+            None => return Err(DebugBundleError::MissingPartError(VERSION_KEY.to_owned())),
+        }
+        if !in_hex {
+            return Err(DebugBundleError::MissingPartError("Hex".to_owned()));
+        }
+        if let Some(length) = length {
+            if length != datagram.len() {
+                return Err(DebugBundleError::LengthMismatchError(
+                    length,
+                    datagram.len(),
+                ));
+            }
+        }
+
+        Ok(DebugBundle { datagram, metadata })
+    }
+
+    fn write_attributes(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let attributes = if self.datagram.len() >= HEADER_LENGTH {
+            let len = usize::from(u16::from_be_bytes([self.datagram[2], self.datagram[3]]));
+            let end = len.clamp(HEADER_LENGTH, self.datagram.len());
+            Attributes::decode(&self.datagram[HEADER_LENGTH..end])
+        } else {
+            Err("too short".to_owned())
+        };
+        match attributes {
+            Ok(attributes) => {
+                for avp in &attributes.0 {
+                    let typ = avp.get_type();
+                    match dictionary::lookup_attribute_by_type(typ) {
+                        Some(definition) => writeln!(
+                            f,
+                            "  {} ({}) = {}",
+                            definition.get_name(),
+                            typ,
+                            format_value(definition.get_name(), definition.get_data_type(), avp)
+                        )?,
+                        None => {
+                            writeln!(f, "  Attr-{} ({}) = {}", typ, typ, to_hex(avp.get_value()))?
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Err(e) => writeln!(f, "  (malformed: {e})"),
+        }
+    }
+}
+
+impl fmt::Display for DebugBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{BEGIN_MARKER}")?;
+        writeln!(f, "{VERSION_KEY}: {VERSION}")?;
+        writeln!(f, "Generator: radius-rs {}", env!("CARGO_PKG_VERSION"))?;
+        if self.datagram.len() >= HEADER_LENGTH {
+            let code = Code::from(self.datagram[0]);
+            writeln!(f, "Code: {} ({})", code.string(), self.datagram[0])?;
+            writeln!(f, "Identifier: {}", self.datagram[1])?;
+        }
+        writeln!(f, "{LENGTH_KEY}: {}", self.datagram.len())?;
+        if self.datagram.len() >= HEADER_LENGTH {
+            writeln!(
+                f,
+                "Authenticator: {}",
+                to_hex(&self.datagram[4..HEADER_LENGTH]).trim_start_matches("0x")
+            )?;
+        }
+        for (key, value) in &self.metadata {
+            writeln!(f, "{key}: {value}")?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Attributes:")?;
+        self.write_attributes(f)?;
+
+        writeln!(f)?;
+        writeln!(f, "Hex:")?;
+        for (i, chunk) in self.datagram.chunks(BYTES_PER_LINE).enumerate() {
+            let mut hex = String::new();
+            for b in chunk {
+                let _ = write!(hex, "{b:02x} ");
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|b| {
+                    if b.is_ascii_graphic() || *b == b' ' {
+                        *b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            writeln!(
+                f,
+                "{:04x}  {:<width$} |{}|",
+                i * BYTES_PER_LINE,
+                hex,
+                ascii,
+                width = BYTES_PER_LINE * 3
+            )?;
+        }
+        writeln!(f, "{END_MARKER}")
+    }
+}
+
+impl Packet {
+    /// Make a debug bundle of the encoded packet (see `radius::core::debug_bundle`).
+    pub fn to_debug_bundle(&self) -> Result<String, PacketError> {
+        Ok(DebugBundle::new(&self.encode()?).to_string())
+    }
+
+    /// Parse a debug bundle, and decode the packet with the shared secret.
+    pub fn from_debug_bundle(text: &str, secret: &[u8]) -> Result<Packet, DebugBundleError> {
+        DebugBundle::parse(text)?.to_packet(secret)
+    }
+}
+
+fn format_value(name: &str, data_type: &str, avp: &AVP) -> String {
+    let value = avp.get_value();
+    let formatted = match data_type {
+        "string" if name != "User-Password" => core::str::from_utf8(value)
+            .ok()
+            .filter(|s| !s.chars().any(char::is_control))
+            .map(|s| format!("{s:?}")),
+        "integer" => avp
+            .encode_u32()
+            .ok()
+            .map(|v| match dictionary::lookup_value(name, v) {
+                Some(definition) => format!("{} ({})", definition.get_name(), v),
+                None => v.to_string(),
+            }),
+        "short" => avp.encode_u16().ok().map(|v| v.to_string()),
+        "integer64" => avp.encode_u64().ok().map(|v| v.to_string()),
+        "date" => avp.encode_date().ok().map(|v| v.to_rfc3339()),
+        "ipaddr" => <[u8; 4]>::try_from(value)
+            .ok()
+            .map(|v| Ipv4Addr::from(v).to_string()),
+        "ipv6addr" => <[u8; 16]>::try_from(value)
+            .ok()
+            .map(|v| Ipv6Addr::from(v).to_string()),
+        "tlv" => avp.encode_tlvs().ok().map(|tlvs| {
+            let tlvs: Vec<String> = tlvs
+                .iter()
+                .map(|tlv| format!("{}: {}", tlv.get_type(), to_hex(tlv.get_value())))
+                .collect();
+            format!("{{{}}}", tlvs.join(", "))
+        }),
+        "extended" | "long-extended" => value.split_first().map(|(extended_type, rest)| {
+            format!("Extended-Type {extended_type}, {}", to_hex(rest))
+        }),
+        _ => None,
+    };
+    formatted.unwrap_or_else(|| to_hex(value))
+}
+
+pub(crate) fn to_hex(bs: &[u8]) -> String {
+    let mut hex = String::from("0x");
+    for b in bs {
+        let _ = write!(hex, "{b:02x}");
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::code::Code;
+    use crate::core::debug_bundle::{DebugBundle, DebugBundleError};
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+
+    #[test]
+    fn test_debug_bundle() {
+        let mut packet = Packet::new_with_identifier(Code::AccessRequest, b"secret", 1);
+        rfc2865::add_user_name(&mut packet, "admin");
+        rfc2865::add_service_type(&mut packet, rfc2865::SERVICE_TYPE_FRAMED_USER);
+        rfc2865::add_user_password(&mut packet, b"p@ssw0rd").unwrap();
+        packet.add(crate::core::avp::AVP::from_bytes(200, &[0xde, 0xad]));
+
+        let bundle = packet.to_debug_bundle().unwrap();
+        assert!(bundle.contains("Code: Access-Request (1)\n"));
+        assert!(bundle.contains("  User-Name (1) = \"admin\"\n"));
+        assert!(bundle.contains("  Service-Type (6) = Framed-User (2)\n"));
+        assert!(bundle.contains("  Attr-200 (200) = 0xdead\n"));
+        assert!(!bundle.contains("p@ssw0rd"));
+        assert!(bundle.contains("\n0000  01 01 00 "));
+
+        let report = format!("The server rejects this:\n\n{bundle}\nThanks!");
+        assert_eq!(
+            Packet::from_debug_bundle(&report, b"secret").unwrap(),
+            packet
+        );
+    }
+
+    #[test]
+    fn test_metadata() {
+        let packet = Packet::new(Code::AccountingRequest, b"secret");
+        let mut bundle = DebugBundle::new(&packet.encode().unwrap());
+        bundle.add_metadata("Source", "192.0.2.1:1813");
+        bundle.add_metadata("Note", "line1\nline2");
+        bundle.add_metadata("Code", "ignored");
+
+        let parsed = DebugBundle::parse(&bundle.to_string()).unwrap();
+        assert_eq!(parsed, bundle);
+        assert_eq!(
+            parsed.get_metadata(),
+            &[
+                ("Source".to_owned(), "192.0.2.1:1813".to_owned()),
+                ("Note".to_owned(), "line1 line2".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_bundle() {
+        let packet = Packet::new(Code::AccessRequest, b"secret");
+        let bundle = packet.to_debug_bundle().unwrap();
+
+        assert_eq!(
+            DebugBundle::parse("no bundle"),
+            Err(DebugBundleError::MissingPartError(
+                "-----BEGIN RADIUS DEBUG BUNDLE-----".to_owned()
+            ))
+        );
+        let truncated: String = bundle
+            .lines()
+            .filter(|line| !line.starts_with("0010"))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        assert!(matches!(
+            DebugBundle::parse(&truncated),
+            Err(DebugBundleError::LengthMismatchError(20, 16))
+        ));
+        assert_eq!(
+            DebugBundle::parse(&bundle.replace("Version: 1", "Version: 2")),
+            Err(DebugBundleError::UnsupportedVersionError("2".to_owned()))
+        );
+        assert!(matches!(
+            DebugBundle::parse(&bundle.replace("0000  01", "0000  zz")),
+            Err(DebugBundleError::InvalidLineError(_, _))
+        ));
+    }
+}
@@ -0,0 +1,175 @@
+//! Deterministic mode for the golden-file tests (available with the `std` feature).
+//!
+//! While the mode is enabled, the random values (the authenticators, the identifiers, the salts of the encrypted
+//! attributes and the IDs of the `Class` values) come from a generator that is seeded by the given seed,
+//! and the current time (e.g. the Event-Timestamp that the client adds and the server validates) is fixed;
+//! so the same program encodes the byte-identical packets on every run.
+//!
+//! The mode applies to the current thread; the tests that run in parallel don't affect each other.
+//! Run the async code on a current-thread runtime (e.g. `#[tokio::test]`) so that all of the tasks see the mode.
+//! Without `std`, register a fixed random source by the `custom` feature of `getrandom` instead.
+//!
+//! ## Example
+//!
+//! ```
+//! use chrono::{TimeZone, Utc};
+//! use radius::core::code::Code;
+//! use radius::core::deterministic;
+//! use radius::core::packet::Packet;
+//!
+//! let now = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+//! let encode = || Packet::new(Code::AccessRequest, b"secret").encode().unwrap();
+//! let first = deterministic::run(42, now, encode);
+//! let second = deterministic::run(42, now, encode);
+//! assert_eq!(first, second);
+//! ```
+
+use std::cell::Cell;
+
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+
+#[derive(Clone, Copy)]
+struct State {
+    rng_state: u64,
+    now: DateTime<Utc>,
+}
+
+thread_local! {
+    static STATE: Cell<Option<State>> = const { Cell::new(None) };
+}
+
+/// Enable the deterministic mode on the current thread with the seed of the random values and the fixed current time.
+///
+/// Enabling that again restarts the random values from the new seed.
+pub fn enable(seed: u64, now: DateTime<Utc>) {
+    STATE.with(|state| {
+        state.set(Some(State {
+            rng_state: seed,
+            now,
+        }))
+    });
+}
+
+/// Disable the deterministic mode on the current thread.
+pub fn disable() {
+    STATE.with(|state| state.set(None));
+}
+
+/// Returns whether the deterministic mode is enabled on the current thread.
+pub fn is_enabled() -> bool {
+    STATE.with(|state| state.get().is_some())
+}
+
+/// Set the fixed current time of the deterministic mode (e.g. to let the sessions expire).
+///
+/// This does nothing if the mode is disabled.
+pub fn set_now(now: DateTime<Utc>) {
+    STATE.with(|state| {
+        if let Some(mut current) = state.get() {
+            current.now = now;
+            state.set(Some(current));
+        }
+    });
+}
+
+/// Run a function in the deterministic mode, and then restore the previous mode of the current thread.
+pub fn run<T>(seed: u64, now: DateTime<Utc>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<State>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            STATE.with(|state| state.set(self.0));
+        }
+    }
+
+    let _restore = Restore(STATE.with(|state| state.get()));
+    enable(seed, now);
+    f()
+}
+
+/// Returns the current time; it is the fixed one in the deterministic mode.
+pub(crate) fn now() -> DateTime<Utc> {
+    STATE
+        .with(|state| state.get())
+        .map_or_else(Utc::now, |state| state.now)
+}
+
+/// The generator of the deterministic mode (SplitMix64); it falls back to the thread's generator if the mode is disabled.
+pub(crate) struct DeterministicRng;
+
+impl RngCore for DeterministicRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        STATE.with(|state| match state.get() {
+            Some(mut current) => {
+                current.rng_state = current.rng_state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                state.set(Some(current));
+
+                let mut z = current.rng_state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                z ^ (z >> 31)
+            }
+            None => rand::thread_rng().next_u64(),
+        })
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::code::Code;
+    use crate::core::deterministic;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+
+    fn encode() -> Vec<u8> {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, "admin");
+        rfc2865::add_user_password(&mut packet, b"p@ssw0rd").unwrap();
+        packet.encode().unwrap()
+    }
+
+    #[test]
+    fn test_run() {
+        let now = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+
+        let first = deterministic::run(42, now, encode);
+        assert_eq!(first, deterministic::run(42, now, encode));
+        assert_ne!(first, deterministic::run(43, now, encode));
+        assert_eq!(deterministic::run(42, now, deterministic::now), now);
+        assert!(!deterministic::is_enabled());
+    }
+
+    #[test]
+    fn test_set_now() {
+        let now = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let later = Utc.timestamp_opt(1_600_000_060, 0).unwrap();
+
+        deterministic::set_now(later);
+        assert_ne!(deterministic::now(), later);
+
+        deterministic::enable(1, now);
+        assert_eq!(deterministic::now(), now);
+        deterministic::set_now(later);
+        assert_eq!(deterministic::now(), later);
+        deterministic::disable();
+        assert!(!deterministic::is_enabled());
+    }
+}
@@ -0,0 +1,1320 @@
+//! `Dictionary`, a dictionary that is loaded from the FreeRADIUS `dictionary.*` files at runtime.
+//!
+//! The built-in dictionary has only the attributes of the generated modules; a `Dictionary` has the ones of the given
+//! files, and adds, looks up and formats the attributes of a packet by their names, with the values in the notation
+//! of FreeRADIUS (e.g. `Service-Type = Framed-User`).
+//!
+//! The `ATTRIBUTE`, `VALUE`, `VENDOR`, `BEGIN-VENDOR`/`END-VENDOR` and `$INCLUDE` lines are supported, and so is the
+//! vendor at the end of an `ATTRIBUTE` line of the old notation. The attributes that can't be handled by a number,
+//! i.e. the ones of the dotted numbers (e.g. `241.1`) and the ones of the vendors whose format isn't `1,1`, are
+//! skipped, and so are the `BEGIN-TLV`/`END-TLV`, `FLAGS` and `PROTOCOL` lines.
+
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use core::net::{Ipv4Addr, Ipv6Addr};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use chrono::{DateTime, TimeZone, Utc};
+use thiserror::Error;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::debug_bundle::to_hex;
+use crate::core::packet::Packet;
+use crate::core::rfc2865;
+use crate::core::vsa::{self, VendorId};
+use crate::error::ErrorKind;
+
+/// The maximum depth of the nested `$INCLUDE`s.
+#[cfg(feature = "std")]
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// The attributes that have the secrets though they aren't encrypted, in addition to the encrypted ones (e.g.
+/// User-Password); `Packet::to_pretty_string()` doesn't show their values.
+const REDACTED_ATTRIBUTES: &[&str] = &[
+    "CHAP-Password",
+    "EAP-Key-Name",
+    "MS-CHAP-Response",
+    "MS-CHAP2-Response",
+    "MS-MPPE-Send-Key",
+    "MS-MPPE-Recv-Key",
+];
+
+/// The attributes of the built-in dictionary that are encrypted (i.e. `encrypt=N`), which the generated modules don't
+/// define.
+const BUILT_IN_ENCRYPTED_ATTRIBUTES: &[(&str, u8)] =
+    &[("User-Password", 1), ("Tunnel-Password", 2)];
+
+/// The attributes of the built-in dictionary that have the tag (RFC 2868).
+const BUILT_IN_TAGGED_ATTRIBUTES: &[&str] = &[
+    "Tunnel-Type",
+    "Tunnel-Medium-Type",
+    "Tunnel-Client-Endpoint",
+    "Tunnel-Server-Endpoint",
+    "Tunnel-Password",
+    "Tunnel-Private-Group-Id",
+    "Tunnel-Assignment-Id",
+    "Tunnel-Preference",
+    "Tunnel-Client-Auth-Id",
+    "Tunnel-Server-Auth-Id",
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DictionaryError {
+    /// An error that is raised when reading a dictionary file has been failed.
+    #[error("failed to read the dictionary file: {0}")]
+    FailedReadingError(String),
+    /// An error that is raised when a line of a dictionary is malformed.
+    #[error("invalid dictionary entry at {0} line {1}: {2}")]
+    InvalidEntryError(String, usize, String),
+    /// An error that is raised when an attribute is not in the dictionary.
+    #[error("unknown attribute: {0}")]
+    UnknownAttributeError(String),
+    /// An error that is raised when a value can't be converted into the data type of the attribute.
+    #[error("invalid value for {0}: {1}")]
+    InvalidValueError(String, String),
+    /// An error that is raised when the data type (or the encryption) of an attribute is not supported.
+    #[error("unsupported data type of {0}: {1}")]
+    UnsupportedDataTypeError(String, String),
+    /// An error that is raised when an attribute value can't be encoded or decoded.
+    #[error(transparent)]
+    AVPError(#[from] AVPError),
+}
+
+impl DictionaryError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            DictionaryError::FailedReadingError(_) => ErrorKind::Io,
+            DictionaryError::InvalidEntryError(_, _, _)
+            | DictionaryError::UnknownAttributeError(_) => ErrorKind::Configuration,
+            DictionaryError::InvalidValueError(_, _)
+            | DictionaryError::UnsupportedDataTypeError(_, _) => ErrorKind::Encoding,
+            DictionaryError::AVPError(e) => e.get_kind(),
+        }
+    }
+}
+
+/// An attribute of a `Dictionary` (i.e. an `ATTRIBUTE` line).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryAttribute {
+    name: String,
+    vendor_id: Option<VendorId>,
+    typ: AVPType,
+    data_type: String,
+    has_tag: bool,
+    encrypt: Option<u8>,
+}
+
+impl DictionaryAttribute {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the Vendor-Id if the attribute is a sub-attribute of Vendor-Specific.
+    pub fn get_vendor_id(&self) -> Option<VendorId> {
+        self.vendor_id
+    }
+
+    /// Returns the type, or the Vendor-Type of the attribute of a vendor.
+    pub fn get_type(&self) -> AVPType {
+        self.typ
+    }
+
+    /// Returns the data type in the dictionary notation (e.g. `string`, `integer`, `ipaddr`).
+    pub fn get_data_type(&self) -> &str {
+        &self.data_type
+    }
+
+    pub fn has_tag(&self) -> bool {
+        self.has_tag
+    }
+
+    /// Returns the method of the encryption (`encrypt=N`), e.g. `1` for User-Password and `2` for Tunnel-Password.
+    pub fn get_encrypt(&self) -> Option<u8> {
+        self.encrypt
+    }
+}
+
+/// A named value of an attribute of a `Dictionary` (i.e. a `VALUE` line).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryValue {
+    attribute_name: String,
+    name: String,
+    value: u32,
+}
+
+impl DictionaryValue {
+    pub fn get_attribute_name(&self) -> &str {
+        &self.attribute_name
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_value(&self) -> u32 {
+        self.value
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Vendor {
+    name: String,
+    id: VendorId,
+    /// Whether the Vendor-Type and the Vendor-Length are one octet each (`format=1,1`, the default).
+    supported: bool,
+}
+
+type IncludeFn<'a> = dyn FnMut(&mut Dictionary, &str, bool) -> Result<(), DictionaryError> + 'a;
+
+/// A dictionary that is loaded at runtime.
+///
+/// The names of the attributes and the values are compared case-insensitively; a later definition of the same name
+/// replaces the former one.
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    vendors: Vec<Vendor>,
+    attributes: Vec<DictionaryAttribute>,
+    attribute_names: BTreeMap<String, usize>,
+    attribute_types: BTreeMap<(Option<VendorId>, AVPType), usize>,
+    values: Vec<DictionaryValue>,
+    value_names: BTreeMap<(String, String), usize>,
+    value_numbers: BTreeMap<(String, u32), usize>,
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
+        Dictionary::default()
+    }
+
+    /// Returns a dictionary of the built-in definitions (see `radius::core::dictionary`), i.e. the attributes and the
+    /// values of the modules that are enabled by the features, including the ones of the vendors.
+    ///
+    /// The dictionary files can be loaded on top of that, e.g. for the attributes that the crate doesn't define.
+    pub fn built_in() -> Self {
+        let mut dictionary = Dictionary::new();
+        let vendor_attributes =
+            super::VENDOR_DICTIONARIES
+                .iter()
+                .flat_map(|(vendor_id, attributes, _)| {
+                    attributes
+                        .iter()
+                        .map(move |attribute| (Some(*vendor_id), attribute))
+                });
+        for (vendor_id, attribute) in super::attributes()
+            .map(|attribute| (None, attribute))
+            .chain(vendor_attributes)
+        {
+            let name = attribute.get_name();
+            dictionary.insert_attribute(DictionaryAttribute {
+                name: name.to_owned(),
+                vendor_id,
+                typ: attribute.get_type(),
+                data_type: attribute.get_data_type().to_owned(),
+                has_tag: vendor_id.is_none() && BUILT_IN_TAGGED_ATTRIBUTES.contains(&name),
+                encrypt: BUILT_IN_ENCRYPTED_ATTRIBUTES
+                    .iter()
+                    .find(|(encrypted, _)| vendor_id.is_none() && *encrypted == name)
+                    .map(|(_, encrypt)| *encrypt),
+            });
+        }
+        for value in super::values() {
+            dictionary.insert_value(DictionaryValue {
+                attribute_name: value.get_attribute_name().to_owned(),
+                name: value.get_name().to_owned(),
+                value: value.get_value(),
+            });
+        }
+        dictionary
+    }
+
+    /// Load a dictionary file (e.g. `/usr/share/freeradius/dictionary`) and the files that it includes.
+    #[cfg(feature = "std")]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, DictionaryError> {
+        let mut dictionary = Dictionary::new();
+        dictionary.load_file(path)?;
+        Ok(dictionary)
+    }
+
+    /// Add the definitions of a dictionary file and the files that it includes; the path of `$INCLUDE` is relative
+    /// to the directory of the file, and a missing file of `$INCLUDE-` is ignored.
+    #[cfg(feature = "std")]
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), DictionaryError> {
+        self.load_file_with_depth(path.as_ref(), 0)
+    }
+
+    #[cfg(feature = "std")]
+    fn load_file_with_depth(&mut self, path: &Path, depth: usize) -> Result<(), DictionaryError> {
+        let source = path.display().to_string();
+        if depth > MAX_INCLUDE_DEPTH {
+            return Err(DictionaryError::FailedReadingError(format!(
+                "{source}: $INCLUDE is nested too deeply"
+            )));
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| DictionaryError::FailedReadingError(format!("{source}: {e}")))?;
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        self.parse(
+            &content,
+            &source,
+            Some(
+                &mut |dictionary: &mut Dictionary, include: &str, optional: bool| {
+                    let include_path = dir.join(include);
+                    if optional && !include_path.exists() {
+                        return Ok(());
+                    }
+                    dictionary.load_file_with_depth(&include_path, depth + 1)
+                },
+            ),
+        )
+    }
+
+    /// Add the definitions of the content of a dictionary file; the content can't have `$INCLUDE`.
+    pub fn parse_str(&mut self, content: &str) -> Result<(), DictionaryError> {
+        self.parse(content, "<string>", None)
+    }
+
+    fn parse(
+        &mut self,
+        content: &str,
+        source: &str,
+        mut include: Option<&mut IncludeFn>,
+    ) -> Result<(), DictionaryError> {
+        let mut current_vendor: Option<(VendorId, bool)> = None;
+        for (i, line) in content.lines().enumerate() {
+            let invalid = |message: String| {
+                DictionaryError::InvalidEntryError(source.to_owned(), i + 1, message)
+            };
+            let items: Vec<&str> = line
+                .split('#')
+                .next()
+                .unwrap_or_default()
+                .split_whitespace()
+                .collect();
+            let Some(&kind) = items.first() else {
+                continue;
+            };
+
+            match kind {
+                "ATTRIBUTE" => {
+                    if items.len() < 4 {
+                        return Err(invalid(
+                            "ATTRIBUTE needs the name, the number and the type".to_owned(),
+                        ));
+                    }
+                    let mut vendor = current_vendor;
+                    let mut has_tag = false;
+                    let mut encrypt = None;
+                    if let Some(options) = items.get(4) {
+                        match self.lookup_vendor(options) {
+                            Some(v) => vendor = Some((v.id, v.supported)),
+                            None => {
+                                for option in options.split(',') {
+                                    if option == "has_tag" {
+                                        has_tag = true;
+                                    } else if let Some(n) = option.strip_prefix("encrypt=") {
+                                        encrypt = Some(n.parse().map_err(|_| {
+                                            invalid(format!("invalid encryption: {option}"))
+                                        })?);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if let Some((_, false)) = vendor {
+                        continue;
+                    }
+                    let typ = match parse_number(items[2]).map(u8::try_from) {
+                        Some(Ok(typ)) => typ,
+                        _ if items[2].contains('.') => continue,
+                        _ => {
+                            return Err(invalid(format!("invalid attribute number: {}", items[2])))
+                        }
+                    };
+                    self.insert_attribute(DictionaryAttribute {
+                        name: items[1].to_owned(),
+                        vendor_id: vendor.map(|(id, _)| id),
+                        typ,
+                        // e.g. `octets[16]`
+                        data_type: items[3].split('[').next().unwrap_or_default().to_owned(),
+                        has_tag,
+                        encrypt,
+                    });
+                }
+                "VALUE" => {
+                    if items.len() < 4 {
+                        return Err(invalid(
+                            "VALUE needs the attribute, the name and the number".to_owned(),
+                        ));
+                    }
+                    if items[3].starts_with('-') {
+                        // the values of `signed`
+                        continue;
+                    }
+                    let value = match parse_number(items[3]) {
+                        Some(v) => v,
+                        None => return Err(invalid(format!("invalid value number: {}", items[3]))),
+                    };
+                    // the values of `integer64` are out of the range
+                    if let Ok(value) = u32::try_from(value) {
+                        self.insert_value(DictionaryValue {
+                            attribute_name: items[1].to_owned(),
+                            name: items[2].to_owned(),
+                            value,
+                        });
+                    }
+                }
+                "VENDOR" => {
+                    if items.len() < 3 {
+                        return Err(invalid("VENDOR needs the name and the number".to_owned()));
+                    }
+                    let id = parse_number(items[2])
+                        .and_then(|id| VendorId::try_from(id).ok())
+                        .ok_or_else(|| invalid(format!("invalid vendor number: {}", items[2])))?;
+                    let supported = match items.get(3) {
+                        Some(format) => *format == "format=1,1",
+                        None => true,
+                    };
+                    self.vendors
+                        .retain(|v| !v.name.eq_ignore_ascii_case(items[1]));
+                    self.vendors.push(Vendor {
+                        name: items[1].to_owned(),
+                        id,
+                        supported,
+                    });
+                }
+                "BEGIN-VENDOR" => {
+                    let vendor = items
+                        .get(1)
+                        .and_then(|name| self.lookup_vendor(name))
+                        .ok_or_else(|| {
+                            invalid(format!("unknown vendor: {}", items.get(1).unwrap_or(&"")))
+                        })?;
+                    current_vendor = Some((vendor.id, vendor.supported));
+                }
+                "END-VENDOR" => current_vendor = None,
+                "$INCLUDE" | "$INCLUDE-" => {
+                    let Some(path) = items.get(1) else {
+                        return Err(invalid("$INCLUDE needs the file".to_owned()));
+                    };
+                    match include.as_mut() {
+                        Some(include) => include(self, path, kind == "$INCLUDE-")?,
+                        None => {
+                            return Err(invalid(
+                                "$INCLUDE is available only in the files (see `Dictionary::load_file()`)".to_owned(),
+                            ))
+                        }
+                    }
+                }
+                "BEGIN-TLV" | "END-TLV" | "FLAGS" | "PROTOCOL" | "BEGIN-PROTOCOL"
+                | "END-PROTOCOL" => {}
+                _ => return Err(invalid(format!("unknown keyword: {kind}"))),
+            }
+        }
+        Ok(())
+    }
+
+    fn lookup_vendor(&self, name: &str) -> Option<&Vendor> {
+        self.vendors
+            .iter()
+            .find(|v| v.name.eq_ignore_ascii_case(name))
+    }
+
+    fn insert_attribute(&mut self, attribute: DictionaryAttribute) {
+        let name = attribute.name.to_ascii_lowercase();
+        let typ = (attribute.vendor_id, attribute.typ);
+        let index = match self.attribute_names.get(&name) {
+            Some(&index) => {
+                let former = &self.attributes[index];
+                self.attribute_types.remove(&(former.vendor_id, former.typ));
+                self.attributes[index] = attribute;
+                index
+            }
+            None => {
+                self.attributes.push(attribute);
+                self.attributes.len() - 1
+            }
+        };
+        self.attribute_names.insert(name, index);
+        self.attribute_types.insert(typ, index);
+    }
+
+    fn insert_value(&mut self, value: DictionaryValue) {
+        let attribute_name = value.attribute_name.to_ascii_lowercase();
+        let name_key = (attribute_name.clone(), value.name.to_ascii_lowercase());
+        let number_key = (attribute_name, value.value);
+        let index = match self.value_names.get(&name_key) {
+            Some(&index) => {
+                self.value_numbers
+                    .remove(&(name_key.0.clone(), self.values[index].value));
+                self.values[index] = value;
+                index
+            }
+            None => {
+                self.values.push(value);
+                self.values.len() - 1
+            }
+        };
+        self.value_names.insert(name_key, index);
+        self.value_numbers.entry(number_key).or_insert(index);
+    }
+
+    /// Returns the all attributes of the dictionary.
+    pub fn attributes(&self) -> impl Iterator<Item = &DictionaryAttribute> {
+        self.attributes.iter()
+    }
+
+    /// Returns the all named values of the dictionary.
+    pub fn values(&self) -> impl Iterator<Item = &DictionaryValue> {
+        self.values.iter()
+    }
+
+    /// Lookup an attribute by the name (e.g. `User-Name`).
+    pub fn lookup_attribute_by_name(&self, name: &str) -> Option<&DictionaryAttribute> {
+        self.attribute_names
+            .get(&name.to_ascii_lowercase())
+            .map(|&index| &self.attributes[index])
+    }
+
+    /// Lookup an attribute by the Vendor-Id (`None` for the attributes that aren't of a vendor) and the type.
+    pub fn lookup_attribute_by_type(
+        &self,
+        vendor_id: Option<VendorId>,
+        typ: AVPType,
+    ) -> Option<&DictionaryAttribute> {
+        self.attribute_types
+            .get(&(vendor_id, typ))
+            .map(|&index| &self.attributes[index])
+    }
+
+    /// Lookup a named value of an attribute (e.g. `Service-Type` and `Framed-User`).
+    pub fn lookup_value_by_name(
+        &self,
+        attribute_name: &str,
+        name: &str,
+    ) -> Option<&DictionaryValue> {
+        self.value_names
+            .get(&(
+                attribute_name.to_ascii_lowercase(),
+                name.to_ascii_lowercase(),
+            ))
+            .map(|&index| &self.values[index])
+    }
+
+    /// Lookup the name of a value of an attribute (e.g. `Service-Type` and `2`).
+    pub fn lookup_value(&self, attribute_name: &str, value: u32) -> Option<&DictionaryValue> {
+        self.value_numbers
+            .get(&(attribute_name.to_ascii_lowercase(), value))
+            .map(|&index| &self.values[index])
+    }
+
+    fn require_attribute(&self, name: &str) -> Result<&DictionaryAttribute, DictionaryError> {
+        self.lookup_attribute_by_name(name)
+            .ok_or_else(|| DictionaryError::UnknownAttributeError(name.to_owned()))
+    }
+
+    /// Add an attribute to a packet by the name and the value in the notation of FreeRADIUS, e.g. `Framed-User` (or
+    /// `2`) for `Service-Type`, `192.0.2.1` for an `ipaddr`, and `0x0102` for an `octets`.
+    ///
+    /// The passwords (`encrypt=1` and `encrypt=2`) are encrypted by the secret and the authenticator of the packet.
+    pub fn add(&self, packet: &mut Packet, name: &str, value: &str) -> Result<(), DictionaryError> {
+        let attribute = self.require_attribute(name)?;
+        let avp = self.make_avp(Some(packet), attribute, value)?;
+        match attribute.vendor_id {
+            Some(vendor_id) => vsa::add_vendor_attribute(packet, vendor_id, avp),
+            None => packet.add(avp),
+        }
+        Ok(())
+    }
+
+    /// Lookup the value of an attribute from a packet by the name, in the notation of FreeRADIUS.
+    ///
+    /// It returns the first looked up value. If there is no associated value with the attribute, it returns `None`.
+    pub fn lookup(&self, packet: &Packet, name: &str) -> Result<Option<String>, DictionaryError> {
+        Ok(self.lookup_all(packet, name)?.into_iter().next())
+    }
+
+    /// Lookup all of the values of an attribute from a packet by the name, in the notation of FreeRADIUS.
+    pub fn lookup_all(&self, packet: &Packet, name: &str) -> Result<Vec<String>, DictionaryError> {
+        let attribute = self.require_attribute(name)?;
+        let avps = match attribute.vendor_id {
+            Some(vendor_id) => vsa::lookup_all_vendor_attributes(packet, vendor_id, attribute.typ),
+            None => packet
+                .lookup_all(attribute.typ)
+                .into_iter()
+                .cloned()
+                .collect(),
+        };
+        avps.iter()
+            .map(|avp| self.format_avp(Some(packet), attribute, avp))
+            .collect()
+    }
+
+    /// Delete all of the values of an attribute from a packet by the name.
+    pub fn delete(&self, packet: &mut Packet, name: &str) -> Result<(), DictionaryError> {
+        let attribute = self.require_attribute(name)?;
+        match attribute.vendor_id {
+            Some(vendor_id) => vsa::delete_vendor_attribute(packet, vendor_id, attribute.typ),
+            None => packet.delete(attribute.typ),
+        }
+        Ok(())
+    }
+
+    /// Format the attributes of a packet as the lines of `Name = value`, like FreeRADIUS does.
+    ///
+    /// Each sub-attribute of Vendor-Specific has its own line, and the unknown attributes are `Attr-N` (or
+    /// `Attr-26.<Vendor-Id>.<Vendor-Type>`) with the hex value; the passwords are shown as they are encrypted.
+    pub fn format_packet(&self, packet: &Packet) -> String {
+        self.format_attributes(packet, "", false)
+    }
+
+    /// Format the attributes of a packet, each line of them has the prefix; the values of the secrets are
+    /// `<redacted>` if `redact` is true.
+    fn format_attributes(&self, packet: &Packet, prefix: &str, redact: bool) -> String {
+        let mut formatted = String::new();
+        for avp in packet.get_attributes() {
+            if avp.get_type() == rfc2865::VENDOR_SPECIFIC_TYPE {
+                if let Ok((vendor_id, tlvs)) = vsa::decode_vendor_specific(avp) {
+                    for tlv in tlvs {
+                        let sub_avp = AVP::from_bytes(tlv.get_type(), tlv.get_value());
+                        let line =
+                            match self.lookup_attribute_by_type(Some(vendor_id), tlv.get_type()) {
+                                Some(attribute) => {
+                                    self.format_line(packet, attribute, &sub_avp, redact)
+                                }
+                                None => format!(
+                                    "Attr-26.{vendor_id}.{} = {}",
+                                    tlv.get_type(),
+                                    to_hex(tlv.get_value())
+                                ),
+                            };
+                        formatted.push_str(prefix);
+                        formatted.push_str(&line);
+                        formatted.push('\n');
+                    }
+                    continue;
+                }
+            }
+            let line = match self.lookup_attribute_by_type(None, avp.get_type()) {
+                Some(attribute) => self.format_line(packet, attribute, avp, redact),
+                None => format!("Attr-{} = {}", avp.get_type(), to_hex(avp.get_value())),
+            };
+            formatted.push_str(prefix);
+            formatted.push_str(&line);
+            formatted.push('\n');
+        }
+        formatted
+    }
+
+    fn format_line(
+        &self,
+        packet: &Packet,
+        attribute: &DictionaryAttribute,
+        avp: &AVP,
+        redact: bool,
+    ) -> String {
+        format!(
+            "{} = {}",
+            attribute.name,
+            self.format_line_value(Some(packet), attribute, avp, redact)
+        )
+    }
+
+    /// Returns the value of a line of `format_line()`, i.e. the strings are quoted and the encrypted values are in hex.
+    fn format_line_value(
+        &self,
+        packet: Option<&Packet>,
+        attribute: &DictionaryAttribute,
+        avp: &AVP,
+        redact: bool,
+    ) -> String {
+        let is_secret = attribute.encrypt.is_some()
+            || REDACTED_ATTRIBUTES
+                .iter()
+                .any(|name| attribute.name.eq_ignore_ascii_case(name));
+        if redact && is_secret {
+            return "<redacted>".to_owned();
+        }
+        match (attribute.data_type.as_str(), attribute.encrypt) {
+            (_, Some(_)) => to_hex(avp.get_value()),
+            ("string" | "date", None) => match self.format_avp(packet, attribute, avp) {
+                Ok(value) => format!("{value:?}"),
+                Err(_) => to_hex(avp.get_value()),
+            },
+            _ => self
+                .format_avp(packet, attribute, avp)
+                .unwrap_or_else(|_| to_hex(avp.get_value())),
+        }
+    }
+
+    /// Returns the name and the value of an attribute (a sub-attribute of the vendor if `vendor_id` is given) in the
+    /// notation of `format_packet()`, with the secrets `<redacted>`; the unknown ones are `Attr-N` (or
+    /// `Attr-26.<Vendor-Id>.<Vendor-Type>`) with the hex value.
+    pub(crate) fn describe_attribute(
+        &self,
+        vendor_id: Option<VendorId>,
+        avp: &AVP,
+    ) -> (String, String) {
+        match self.lookup_attribute_by_type(vendor_id, avp.get_type()) {
+            Some(attribute) => (
+                attribute.name.clone(),
+                self.format_line_value(None, attribute, avp, true),
+            ),
+            None => (
+                match vendor_id {
+                    Some(vendor_id) => format!("Attr-26.{vendor_id}.{}", avp.get_type()),
+                    None => format!("Attr-{}", avp.get_type()),
+                },
+                to_hex(avp.get_value()),
+            ),
+        }
+    }
+
+    /// Convert a value in the notation of FreeRADIUS (see `add()`) into an attribute by the name, e.g.
+    /// `parse_value("Framed-IP-Address", "10.0.0.1")`.
+    ///
+    /// The attribute of a vendor is wrapped in Vendor-Specific, so it can be added to a packet as it is. The passwords
+    /// (`encrypt=1` and `encrypt=2`) are encrypted by the secret and the authenticator of a packet, so they are
+    /// `UnsupportedDataTypeError` here; `add()` them to the packet instead.
+    pub fn parse_value(&self, name: &str, value: &str) -> Result<AVP, DictionaryError> {
+        let attribute = self.require_attribute(name)?;
+        let avp = self.make_avp(None, attribute, value)?;
+        Ok(match attribute.vendor_id {
+            Some(vendor_id) => {
+                vsa::make_vendor_specific(vendor_id, avp.get_type(), avp.get_value())
+            }
+            None => avp,
+        })
+    }
+
+    /// Format the value of an attribute in the notation of FreeRADIUS, i.e. the reverse of `parse_value()`.
+    ///
+    /// The attribute is looked up by its type, and a Vendor-Specific that carries a sub-attribute is formatted as that
+    /// sub-attribute of the vendor. The encrypted values are formatted in hex, as `format_packet()` does, since they
+    /// can't be decrypted without the packet; use `lookup()` for them.
+    pub fn format_value(&self, avp: &AVP) -> Result<String, DictionaryError> {
+        let unknown = || DictionaryError::UnknownAttributeError(format!("Attr-{}", avp.get_type()));
+        if avp.get_type() == rfc2865::VENDOR_SPECIFIC_TYPE {
+            if let Ok((vendor_id, tlvs)) = vsa::decode_vendor_specific(avp) {
+                if let [tlv] = tlvs.as_slice() {
+                    let attribute = self
+                        .lookup_attribute_by_type(Some(vendor_id), tlv.get_type())
+                        .ok_or_else(|| {
+                            DictionaryError::UnknownAttributeError(format!(
+                                "Attr-26.{vendor_id}.{}",
+                                tlv.get_type()
+                            ))
+                        })?;
+                    let sub_avp = AVP::from_bytes(tlv.get_type(), tlv.get_value());
+                    return self.format_avp(None, attribute, &sub_avp);
+                }
+            }
+        }
+        let attribute = self
+            .lookup_attribute_by_type(None, avp.get_type())
+            .ok_or_else(unknown)?;
+        self.format_avp(None, attribute, avp)
+    }
+
+    /// Returns the packet of an encrypted value, which is required to encrypt or decrypt that.
+    fn require_packet<'a>(
+        attribute: &DictionaryAttribute,
+        packet: Option<&'a Packet>,
+    ) -> Result<&'a Packet, DictionaryError> {
+        packet.ok_or_else(|| {
+            DictionaryError::UnsupportedDataTypeError(
+                attribute.name.clone(),
+                format!(
+                    "{} encrypt={} without a packet",
+                    attribute.data_type,
+                    attribute.encrypt.unwrap_or_default()
+                ),
+            )
+        })
+    }
+
+    fn make_avp(
+        &self,
+        packet: Option<&Packet>,
+        attribute: &DictionaryAttribute,
+        value: &str,
+    ) -> Result<AVP, DictionaryError> {
+        let typ = attribute.typ;
+        let invalid =
+            || DictionaryError::InvalidValueError(attribute.name.clone(), value.to_owned());
+        let avp = match (attribute.data_type.as_str(), attribute.encrypt) {
+            ("string", Some(1)) => {
+                let packet = Self::require_packet(attribute, packet)?;
+                AVP::from_user_password(
+                    typ,
+                    value.as_bytes(),
+                    packet.get_secret(),
+                    packet.get_authenticator(),
+                )?
+            }
+            ("string", Some(2)) => {
+                let packet = Self::require_packet(attribute, packet)?;
+                AVP::from_tunnel_password(
+                    typ,
+                    None,
+                    value.as_bytes(),
+                    packet.get_secret(),
+                    packet.get_authenticator(),
+                )?
+            }
+            (data_type, Some(encrypt)) => {
+                return Err(DictionaryError::UnsupportedDataTypeError(
+                    attribute.name.clone(),
+                    format!("{data_type} encrypt={encrypt}"),
+                ))
+            }
+            ("string", None) if attribute.has_tag => AVP::from_tagged_string(typ, None, value),
+            ("string", None) => AVP::from_string(typ, value),
+            ("integer", None) => {
+                let v = match self.lookup_value_by_name(&attribute.name, value) {
+                    Some(v) => v.value,
+                    None => parse_number(value)
+                        .and_then(|v| u32::try_from(v).ok())
+                        .ok_or_else(invalid)?,
+                };
+                match attribute.has_tag {
+                    true => AVP::from_tagged_u32(typ, None, v),
+                    false => AVP::from_u32(typ, v),
+                }
+            }
+            ("short", None) => AVP::from_u16(typ, value.parse().map_err(|_| invalid())?),
+            ("byte", None) => AVP::from_bytes(typ, &[value.parse::<u8>().map_err(|_| invalid())?]),
+            ("integer64", None) => AVP::from_u64(typ, value.parse().map_err(|_| invalid())?),
+            ("signed", None) => AVP::from_bytes(
+                typ,
+                &value.parse::<i32>().map_err(|_| invalid())?.to_be_bytes(),
+            ),
+            ("date", None) => AVP::from_date(typ, &parse_date(value).ok_or_else(invalid)?),
+            ("ipaddr", None) => {
+                AVP::from_ipv4(typ, &Ipv4Addr::from_str(value).map_err(|_| invalid())?)
+            }
+            ("ipv6addr", None) => {
+                AVP::from_ipv6(typ, &Ipv6Addr::from_str(value).map_err(|_| invalid())?)
+            }
+            ("ether", None) => AVP::from_bytes(typ, &parse_ether(value).ok_or_else(invalid)?),
+            // `octets` and the others
+            _ => match value.strip_prefix("0x") {
+                Some(hex) => AVP::from_bytes(typ, &parse_hex(hex).ok_or_else(invalid)?),
+                None => AVP::from_bytes(typ, value.as_bytes()),
+            },
+        };
+        Ok(avp)
+    }
+
+    /// Format a value; the encrypted ones are decrypted by the packet, or they are in hex without that.
+    fn format_avp(
+        &self,
+        packet: Option<&Packet>,
+        attribute: &DictionaryAttribute,
+        avp: &AVP,
+    ) -> Result<String, DictionaryError> {
+        let value = match (attribute.data_type.as_str(), attribute.encrypt, packet) {
+            ("string", Some(1), Some(packet)) => String::from_utf8_lossy(
+                &avp.encode_user_password(packet.get_secret(), packet.get_authenticator())?,
+            )
+            .into_owned(),
+            ("string", Some(2), Some(packet)) => String::from_utf8_lossy(
+                &avp.encode_tunnel_password(packet.get_secret(), packet.get_authenticator())?
+                    .0,
+            )
+            .into_owned(),
+            (_, Some(_), _) => to_hex(avp.get_value()),
+            ("string", None, _) if attribute.has_tag => avp.encode_tagged_string()?.0,
+            ("string", None, _) => avp.encode_string()?,
+            ("integer", None, _) => {
+                let v = match attribute.has_tag {
+                    true => avp.encode_tagged_u32()?.0,
+                    false => avp.encode_u32()?,
+                };
+                match self.lookup_value(&attribute.name, v) {
+                    Some(definition) => definition.name.clone(),
+                    None => v.to_string(),
+                }
+            }
+            ("short", None, _) => avp.encode_u16()?.to_string(),
+            ("byte", None, _) => u8::from_be_bytes(fixed_bytes(avp)?).to_string(),
+            ("integer64", None, _) => avp.encode_u64()?.to_string(),
+            ("signed", None, _) => i32::from_be_bytes(fixed_bytes(avp)?).to_string(),
+            ("date", None, _) => avp.encode_date()?.to_rfc3339(),
+            ("ipaddr", None, _) => avp.encode_ipv4()?.to_string(),
+            ("ipv6addr", None, _) => avp.encode_ipv6()?.to_string(),
+            ("ether", None, _) => {
+                let bytes: [u8; 6] = fixed_bytes(avp)?;
+                bytes
+                    .iter()
+                    .map(|b| format!("{b:02x}"))
+                    .collect::<Vec<_>>()
+                    .join(":")
+            }
+            _ => to_hex(avp.get_value()),
+        };
+        Ok(value)
+    }
+}
+
+impl FromStr for Dictionary {
+    type Err = DictionaryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut dictionary = Dictionary::new();
+        dictionary.parse_str(s)?;
+        Ok(dictionary)
+    }
+}
+
+/// Parse a decimal or a hex (`0x`) number.
+fn parse_number(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| s.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+        .collect()
+}
+
+fn parse_ether(s: &str) -> Option<Vec<u8>> {
+    let bytes = s
+        .split([':', '-'])
+        .map(|b| u8::from_str_radix(b, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    (bytes.len() == 6).then_some(bytes)
+}
+
+impl Packet {
+    /// Returns the human-readable dump of the packet, i.e. the code, the identifier, the authenticator and the
+    /// attributes that are decoded by the dictionary (see `Dictionary::format_packet()`).
+    ///
+    /// The values of the passwords and the other secrets (the encrypted attributes, CHAP-Password and so on) are
+    /// `<redacted>`, so the dump can be shared to debug the interoperability.
+    pub fn to_pretty_string(&self, dictionary: &Dictionary) -> String {
+        format!(
+            "{} ({}) Id {}\nAuthenticator = {}\n{}",
+            self.get_code().string(),
+            self.get_code() as u8,
+            self.get_identifier(),
+            to_hex(self.get_authenticator()),
+            dictionary.format_attributes(self, "\t", true)
+        )
+    }
+
+    /// Returns the one-line representation of the packet for the logs, i.e. the code, the identifier and the
+    /// attributes by the names of the built-in dictionary, e.g.
+    /// `Access-Request Id 7 [User-Name = "bob", User-Password = <redacted>]`.
+    ///
+    /// The secrets are `<redacted>` in the same way as `to_pretty_string()` (User-Password, CHAP-Password,
+    /// Tunnel-Password, the MPPE keys and so on), so this is safe to log.
+    pub fn redacted_debug(&self) -> String {
+        with_built_in(|dictionary| {
+            format!(
+                "{} Id {} [{}]",
+                self.get_code().string(),
+                self.get_identifier(),
+                dictionary
+                    .format_attributes(self, "", true)
+                    .lines()
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )
+        })
+    }
+}
+
+/// Run a function with `Dictionary::built_in()`, which is built once with `std` (and per call without that).
+pub(crate) fn with_built_in<R>(f: impl FnOnce(&Dictionary) -> R) -> R {
+    #[cfg(feature = "std")]
+    let dictionary = {
+        static BUILT_IN: std::sync::OnceLock<Dictionary> = std::sync::OnceLock::new();
+        BUILT_IN.get_or_init(Dictionary::built_in)
+    };
+    #[cfg(not(feature = "std"))]
+    let dictionary = &Dictionary::built_in();
+    f(dictionary)
+}
+
+/// Parse a date of the UNIX time or the RFC 3339 notation.
+fn parse_date(s: &str) -> Option<DateTime<Utc>> {
+    match s.parse::<i64>() {
+        Ok(timestamp) => Utc.timestamp_opt(timestamp, 0).single(),
+        Err(_) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc)),
+    }
+}
+
+fn fixed_bytes<const N: usize>(avp: &AVP) -> Result<[u8; N], AVPError> {
+    avp.get_value().try_into().map_err(|_| {
+        AVPError::InvalidAttributeLengthError(format!("{N} bytes"), avp.get_value().len())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use crate::core::avp::AVP;
+    use crate::core::code::Code;
+    use crate::core::dictionary::{Dictionary, DictionaryError};
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::core::vsa;
+    use crate::error::ErrorKind;
+
+    const DICTIONARY: &str = "
+# a comment
+ATTRIBUTE   User-Name       1   string
+ATTRIBUTE   User-Password   2   string  encrypt=1
+ATTRIBUTE   Service-Type    6   integer
+ATTRIBUTE   Framed-IP-Address   8   ipaddr
+ATTRIBUTE   Class           25  octets
+ATTRIBUTE   Vendor-Specific 26  vsa
+ATTRIBUTE   Event-Timestamp 55  date
+ATTRIBUTE   Tunnel-Type     64  integer has_tag
+ATTRIBUTE   Frag-Status     241.1   integer
+
+VALUE   Service-Type    Login-User      1
+VALUE   Service-Type    Framed-User     2   # a trailing comment
+VALUE   Tunnel-Type     VLAN            13
+
+VENDOR      Cisco       9
+BEGIN-VENDOR    Cisco
+ATTRIBUTE   Cisco-AVPair    1   string
+END-VENDOR      Cisco
+
+VENDOR      USR         429     format=4,0
+ATTRIBUTE   USR-Prompt      0x0064  integer USR
+";
+
+    #[test]
+    fn test_parse() {
+        let dictionary: Dictionary = DICTIONARY.parse().unwrap();
+        let attribute = dictionary
+            .lookup_attribute_by_name("user-password")
+            .unwrap();
+        assert_eq!(attribute.get_type(), 2);
+        assert_eq!(attribute.get_encrypt(), Some(1));
+        let attribute = dictionary.lookup_attribute_by_type(Some(9), 1).unwrap();
+        assert_eq!(attribute.get_name(), "Cisco-AVPair");
+        assert!(dictionary
+            .lookup_attribute_by_type(None, 1)
+            .unwrap()
+            .get_vendor_id()
+            .is_none());
+        assert!(dictionary
+            .lookup_attribute_by_type(None, 64)
+            .unwrap()
+            .has_tag());
+        assert!(dictionary.lookup_attribute_by_name("Frag-Status").is_none());
+        assert!(dictionary.lookup_attribute_by_name("USR-Prompt").is_none());
+        assert_eq!(
+            dictionary
+                .lookup_value("Service-Type", 2)
+                .unwrap()
+                .get_name(),
+            "Framed-User"
+        );
+
+        let e = "ATTRIBUTE User-Name 1".parse::<Dictionary>().unwrap_err();
+        assert_eq!(
+            e,
+            DictionaryError::InvalidEntryError(
+                "<string>".to_owned(),
+                1,
+                "ATTRIBUTE needs the name, the number and the type".to_owned()
+            )
+        );
+        assert_eq!(e.get_kind(), ErrorKind::Configuration);
+        assert!("\nATTRIBUTE User-Name 300 string"
+            .parse::<Dictionary>()
+            .is_err());
+        assert!("BEGIN-VENDOR Unknown".parse::<Dictionary>().is_err());
+        assert!("$INCLUDE dictionary.rfc2865".parse::<Dictionary>().is_err());
+        assert!("UNKNOWN a b c".parse::<Dictionary>().is_err());
+    }
+
+    #[test]
+    fn test_add_and_lookup() {
+        let dictionary: Dictionary = DICTIONARY.parse().unwrap();
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        dictionary.add(&mut packet, "User-Name", "bob").unwrap();
+        dictionary
+            .add(&mut packet, "User-Password", "p@ssw0rd")
+            .unwrap();
+        dictionary
+            .add(&mut packet, "service-type", "Framed-User")
+            .unwrap();
+        dictionary
+            .add(&mut packet, "Framed-IP-Address", "192.0.2.1")
+            .unwrap();
+        dictionary.add(&mut packet, "Class", "0x0102").unwrap();
+        dictionary
+            .add(&mut packet, "Event-Timestamp", "1700000000")
+            .unwrap();
+        dictionary.add(&mut packet, "Tunnel-Type", "VLAN").unwrap();
+        dictionary
+            .add(&mut packet, "Cisco-AVPair", "shell:priv-lvl=15")
+            .unwrap();
+
+        assert_eq!(rfc2865::lookup_user_name(&packet).unwrap().unwrap(), "bob");
+        assert_eq!(
+            rfc2865::lookup_service_type(&packet).unwrap().unwrap(),
+            rfc2865::SERVICE_TYPE_FRAMED_USER
+        );
+        assert_eq!(
+            dictionary
+                .lookup(&packet, "User-Password")
+                .unwrap()
+                .unwrap(),
+            "p@ssw0rd"
+        );
+        assert_eq!(
+            dictionary.lookup(&packet, "Service-Type").unwrap().unwrap(),
+            "Framed-User"
+        );
+        assert_eq!(
+            dictionary.lookup(&packet, "Tunnel-Type").unwrap().unwrap(),
+            "VLAN"
+        );
+        assert_eq!(
+            dictionary.lookup(&packet, "Cisco-AVPair").unwrap().unwrap(),
+            "shell:priv-lvl=15"
+        );
+        assert_eq!(
+            dictionary.lookup_all(&packet, "Class").unwrap(),
+            vec!["0x0102"]
+        );
+
+        packet.add(crate::core::avp::AVP::from_bytes(200, &[0xde, 0xad]));
+        let formatted = dictionary.format_packet(&packet);
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines[0], "User-Name = \"bob\"");
+        assert!(lines[1].starts_with("User-Password = 0x"));
+        assert_eq!(lines[2], "Service-Type = Framed-User");
+        assert_eq!(lines[3], "Framed-IP-Address = 192.0.2.1");
+        assert_eq!(lines[5], "Event-Timestamp = \"2023-11-14T22:13:20+00:00\"");
+        assert_eq!(lines[7], "Cisco-AVPair = \"shell:priv-lvl=15\"");
+        assert_eq!(lines[8], "Attr-200 = 0xdead");
+
+        dictionary.delete(&mut packet, "Cisco-AVPair").unwrap();
+        assert!(dictionary
+            .lookup(&packet, "Cisco-AVPair")
+            .unwrap()
+            .is_none());
+
+        assert_eq!(
+            dictionary.add(&mut packet, "No-Such-Attribute", "1"),
+            Err(DictionaryError::UnknownAttributeError(
+                "No-Such-Attribute".to_owned()
+            ))
+        );
+        assert_eq!(
+            dictionary
+                .add(&mut packet, "Service-Type", "No-Such-Value")
+                .unwrap_err()
+                .get_kind(),
+            ErrorKind::Encoding
+        );
+        assert!(dictionary
+            .add(&mut packet, "Framed-IP-Address", "::1")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_and_format_value() {
+        let dictionary: Dictionary = DICTIONARY.parse().unwrap();
+        let avp = dictionary
+            .parse_value("Framed-IP-Address", "10.0.0.1")
+            .unwrap();
+        assert_eq!(avp.get_type(), 8);
+        assert_eq!(avp.encode_ipv4().unwrap(), Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(dictionary.format_value(&avp).unwrap(), "10.0.0.1");
+
+        for (name, value) in [
+            ("Service-Type", "Framed-User"),
+            ("Tunnel-Type", "VLAN"),
+            ("Class", "0x0102"),
+            ("User-Name", "bob"),
+            ("Cisco-AVPair", "shell:priv-lvl=15"),
+        ] {
+            let avp = dictionary.parse_value(name, value).unwrap();
+            assert_eq!(dictionary.format_value(&avp).unwrap(), value);
+        }
+        let avp = dictionary
+            .parse_value("Cisco-AVPair", "shell:priv-lvl=15")
+            .unwrap();
+        assert_eq!(avp.get_type(), rfc2865::VENDOR_SPECIFIC_TYPE);
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        packet.add(avp);
+        assert_eq!(
+            dictionary.lookup(&packet, "Cisco-AVPair").unwrap().unwrap(),
+            "shell:priv-lvl=15"
+        );
+
+        assert_eq!(
+            dictionary.parse_value("User-Password", "p@ssw0rd"),
+            Err(DictionaryError::UnsupportedDataTypeError(
+                "User-Password".to_owned(),
+                "string encrypt=1 without a packet".to_owned()
+            ))
+        );
+        assert_eq!(
+            dictionary.parse_value("Framed-IP-Address", "::1"),
+            Err(DictionaryError::InvalidValueError(
+                "Framed-IP-Address".to_owned(),
+                "::1".to_owned()
+            ))
+        );
+        assert_eq!(
+            dictionary.format_value(&AVP::from_bytes(200, &[0xde, 0xad])),
+            Err(DictionaryError::UnknownAttributeError(
+                "Attr-200".to_owned()
+            ))
+        );
+        assert_eq!(
+            dictionary.format_value(&vsa::make_vendor_specific(9, 2, b"x")),
+            Err(DictionaryError::UnknownAttributeError(
+                "Attr-26.9.2".to_owned()
+            ))
+        );
+    }
+
+    #[cfg(all(feature = "rfc2868", feature = "cisco"))]
+    #[test]
+    fn test_built_in() {
+        let dictionary = Dictionary::built_in();
+        let attribute = dictionary
+            .lookup_attribute_by_name("User-Password")
+            .unwrap();
+        assert_eq!(attribute.get_encrypt(), Some(1));
+        assert!(dictionary
+            .lookup_attribute_by_name("Tunnel-Type")
+            .unwrap()
+            .has_tag());
+        assert_eq!(
+            dictionary
+                .lookup_attribute_by_name("Cisco-AVPair")
+                .unwrap()
+                .get_vendor_id(),
+            Some(9)
+        );
+
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        dictionary.add(&mut packet, "User-Name", "bob").unwrap();
+        dictionary
+            .add(&mut packet, "User-Password", "p@ssw0rd")
+            .unwrap();
+        dictionary
+            .add(&mut packet, "Service-Type", "Framed-User")
+            .unwrap();
+        dictionary
+            .add(&mut packet, "Cisco-AVPair", "shell:priv-lvl=15")
+            .unwrap();
+        assert_eq!(
+            rfc2865::lookup_user_password(&packet).unwrap().unwrap(),
+            b"p@ssw0rd"
+        );
+        assert_eq!(
+            dictionary
+                .lookup(&packet, "User-Password")
+                .unwrap()
+                .unwrap(),
+            "p@ssw0rd"
+        );
+        let formatted = dictionary.format_packet(&packet);
+        assert!(formatted.contains("Service-Type = Framed-User\n"));
+        assert!(formatted.contains("Cisco-AVPair = \"shell:priv-lvl=15\"\n"));
+    }
+
+    #[test]
+    fn test_to_pretty_string() {
+        let dictionary: Dictionary = DICTIONARY.parse().unwrap();
+        let mut packet = Packet::new_with_identifier(Code::AccessRequest, b"secret", 7);
+        dictionary.add(&mut packet, "User-Name", "bob").unwrap();
+        dictionary
+            .add(&mut packet, "User-Password", "hunter2")
+            .unwrap();
+        dictionary
+            .add(&mut packet, "Service-Type", "Framed-User")
+            .unwrap();
+
+        let pretty = packet.to_pretty_string(&dictionary);
+        let lines: Vec<&str> = pretty.lines().collect();
+        assert_eq!(lines[0], "Access-Request (1) Id 7");
+        assert!(lines[1].starts_with("Authenticator = 0x"));
+        assert_eq!(lines[1].len(), "Authenticator = 0x".len() + 32);
+        assert_eq!(lines[2], "\tUser-Name = \"bob\"");
+        assert_eq!(lines[3], "\tUser-Password = <redacted>");
+        assert_eq!(lines[4], "\tService-Type = Framed-User");
+        assert!(!pretty.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_redacted_debug() {
+        let mut packet = Packet::new_with_identifier(Code::AccessRequest, b"secret", 7);
+        crate::core::rfc2865::add_user_name(&mut packet, "bob");
+        crate::core::rfc2865::add_user_password(&mut packet, b"hunter2").unwrap();
+        crate::core::rfc2865::add_chap_password(&mut packet, &[1; 17]);
+        assert_eq!(
+            packet.redacted_debug(),
+            "Access-Request Id 7 [User-Name = \"bob\", User-Password = <redacted>, CHAP-Password = <redacted>]"
+        );
+
+        #[cfg(all(feature = "rfc2548", feature = "rfc2868"))]
+        {
+            let mut response = packet.make_response_packet(Code::AccessAccept);
+            crate::core::rfc2868::add_tunnel_password(&mut response, None, b"tunnel").unwrap();
+            crate::core::rfc2548::add_ms_mppe_send_key(&mut response, &[1; 16]).unwrap();
+            crate::core::rfc2548::add_ms_mppe_recv_key(&mut response, &[2; 16]).unwrap();
+            assert_eq!(
+                response.redacted_debug(),
+                "Access-Accept Id 7 [Tunnel-Password = <redacted>, MS-MPPE-Send-Key = <redacted>, \
+                 MS-MPPE-Recv-Key = <redacted>]"
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_load_file() {
+        let dir = std::env::temp_dir().join(format!("radius-dictionary-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("dictionary"),
+            "$INCLUDE dictionary.rfc2865\n$INCLUDE- dictionary.missing\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("dictionary.rfc2865"),
+            "ATTRIBUTE User-Name 1 string\n",
+        )
+        .unwrap();
+
+        let dictionary = Dictionary::from_file(dir.join("dictionary")).unwrap();
+        assert!(dictionary.lookup_attribute_by_name("User-Name").is_some());
+        assert_eq!(
+            Dictionary::from_file(dir.join("no-such-dictionary"))
+                .unwrap_err()
+                .get_kind(),
+            ErrorKind::Io
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,424 @@
+//! The built-in dictionary, i.e. the names of the attributes and the values that are defined by the `rfcXXXX` modules.
+//!
+//! The names are compared case-insensitively, as FreeRADIUS does.
+//! The dictionary has only the modules that are enabled by the features (see `radius::core`).
+//! The attributes of the vendor modules (e.g. `cisco`) are the sub-attributes of Vendor-Specific, so they are looked
+//! up with the Vendor-Id by `lookup_vendor_attribute_by_name()` and `lookup_vendor_attribute_by_type()`.
+//!
+//! `Dictionary` is a dictionary that is loaded from the FreeRADIUS dictionary files at runtime (see `freeradius`).
+
+mod freeradius;
+
+pub(crate) use freeradius::with_built_in;
+pub use freeradius::{Dictionary, DictionaryAttribute, DictionaryError, DictionaryValue};
+
+#[cfg(feature = "aruba")]
+use crate::core::aruba;
+use crate::core::avp::{AVPType, AVP};
+#[cfg(feature = "cisco")]
+use crate::core::cisco;
+#[cfg(feature = "juniper")]
+use crate::core::juniper;
+#[cfg(feature = "mikrotik")]
+use crate::core::mikrotik;
+#[cfg(feature = "rfc2548")]
+use crate::core::rfc2548;
+#[cfg(feature = "rfc2867")]
+use crate::core::rfc2867;
+#[cfg(feature = "rfc2868")]
+use crate::core::rfc2868;
+#[cfg(feature = "rfc3162")]
+use crate::core::rfc3162;
+#[cfg(feature = "rfc3576")]
+use crate::core::rfc3576;
+#[cfg(feature = "rfc3580")]
+use crate::core::rfc3580;
+#[cfg(feature = "rfc4072")]
+use crate::core::rfc4072;
+#[cfg(feature = "rfc4372")]
+use crate::core::rfc4372;
+#[cfg(feature = "rfc4603")]
+use crate::core::rfc4603;
+#[cfg(feature = "rfc4675")]
+use crate::core::rfc4675;
+#[cfg(feature = "rfc4818")]
+use crate::core::rfc4818;
+#[cfg(feature = "rfc4849")]
+use crate::core::rfc4849;
+#[cfg(feature = "rfc5090")]
+use crate::core::rfc5090;
+#[cfg(feature = "rfc5176")]
+use crate::core::rfc5176;
+#[cfg(feature = "rfc5580")]
+use crate::core::rfc5580;
+#[cfg(feature = "rfc5607")]
+use crate::core::rfc5607;
+#[cfg(feature = "rfc5904")]
+use crate::core::rfc5904;
+#[cfg(feature = "rfc6519")]
+use crate::core::rfc6519;
+#[cfg(feature = "rfc6572")]
+use crate::core::rfc6572;
+#[cfg(feature = "rfc6677")]
+use crate::core::rfc6677;
+#[cfg(feature = "rfc6911")]
+use crate::core::rfc6911;
+#[cfg(feature = "rfc6929")]
+use crate::core::rfc6929;
+#[cfg(feature = "rfc7055")]
+use crate::core::rfc7055;
+#[cfg(feature = "rfc7155")]
+use crate::core::rfc7155;
+use crate::core::vsa::{self, VendorId};
+#[cfg(feature = "wispr")]
+use crate::core::wispr;
+use crate::core::{rfc2865, rfc2866, rfc2869};
+
+/// The definition of an attribute (i.e. an `ATTRIBUTE` line of a dictionary).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttributeDefinition {
+    name: &'static str,
+    typ: AVPType,
+    data_type: &'static str,
+}
+
+impl AttributeDefinition {
+    pub const fn new(name: &'static str, typ: AVPType, data_type: &'static str) -> Self {
+        AttributeDefinition {
+            name,
+            typ,
+            data_type,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_type(&self) -> AVPType {
+        self.typ
+    }
+
+    /// Returns the data type in the dictionary notation (e.g. `string`, `integer`, `ipaddr`).
+    pub fn get_data_type(&self) -> &'static str {
+        self.data_type
+    }
+}
+
+/// The definition of a named value of an attribute (i.e. a `VALUE` line of a dictionary).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueDefinition {
+    attribute_name: &'static str,
+    name: &'static str,
+    value: u32,
+}
+
+impl ValueDefinition {
+    pub const fn new(attribute_name: &'static str, name: &'static str, value: u32) -> Self {
+        ValueDefinition {
+            attribute_name,
+            name,
+            value,
+        }
+    }
+
+    pub fn get_attribute_name(&self) -> &'static str {
+        self.attribute_name
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_value(&self) -> u32 {
+        self.value
+    }
+}
+
+const DICTIONARIES: &[(&[AttributeDefinition], &[ValueDefinition])] = &[
+    (rfc2865::ATTRIBUTES, rfc2865::VALUES),
+    (rfc2866::ATTRIBUTES, rfc2866::VALUES),
+    #[cfg(feature = "rfc2867")]
+    (rfc2867::ATTRIBUTES, rfc2867::VALUES),
+    #[cfg(feature = "rfc2868")]
+    (rfc2868::ATTRIBUTES, rfc2868::VALUES),
+    (rfc2869::ATTRIBUTES, rfc2869::VALUES),
+    #[cfg(feature = "rfc3162")]
+    (rfc3162::ATTRIBUTES, rfc3162::VALUES),
+    #[cfg(feature = "rfc3576")]
+    (rfc3576::ATTRIBUTES, rfc3576::VALUES),
+    #[cfg(feature = "rfc3580")]
+    (rfc3580::ATTRIBUTES, rfc3580::VALUES),
+    #[cfg(feature = "rfc4072")]
+    (rfc4072::ATTRIBUTES, rfc4072::VALUES),
+    #[cfg(feature = "rfc4372")]
+    (rfc4372::ATTRIBUTES, rfc4372::VALUES),
+    #[cfg(feature = "rfc4603")]
+    (rfc4603::ATTRIBUTES, rfc4603::VALUES),
+    #[cfg(feature = "rfc4675")]
+    (rfc4675::ATTRIBUTES, rfc4675::VALUES),
+    #[cfg(feature = "rfc4818")]
+    (rfc4818::ATTRIBUTES, rfc4818::VALUES),
+    #[cfg(feature = "rfc4849")]
+    (rfc4849::ATTRIBUTES, rfc4849::VALUES),
+    #[cfg(feature = "rfc5090")]
+    (rfc5090::ATTRIBUTES, rfc5090::VALUES),
+    #[cfg(feature = "rfc5176")]
+    (rfc5176::ATTRIBUTES, rfc5176::VALUES),
+    #[cfg(feature = "rfc5580")]
+    (rfc5580::ATTRIBUTES, rfc5580::VALUES),
+    #[cfg(feature = "rfc5607")]
+    (rfc5607::ATTRIBUTES, rfc5607::VALUES),
+    #[cfg(feature = "rfc5904")]
+    (rfc5904::ATTRIBUTES, rfc5904::VALUES),
+    #[cfg(feature = "rfc6519")]
+    (rfc6519::ATTRIBUTES, rfc6519::VALUES),
+    #[cfg(feature = "rfc6572")]
+    (rfc6572::ATTRIBUTES, rfc6572::VALUES),
+    #[cfg(feature = "rfc6677")]
+    (rfc6677::ATTRIBUTES, rfc6677::VALUES),
+    #[cfg(feature = "rfc6911")]
+    (rfc6911::ATTRIBUTES, rfc6911::VALUES),
+    #[cfg(feature = "rfc6929")]
+    (rfc6929::ATTRIBUTES, rfc6929::VALUES),
+    #[cfg(feature = "rfc7055")]
+    (rfc7055::ATTRIBUTES, rfc7055::VALUES),
+    #[cfg(feature = "rfc7155")]
+    (rfc7155::ATTRIBUTES, rfc7155::VALUES),
+];
+
+type VendorDictionary = (
+    VendorId,
+    &'static [AttributeDefinition],
+    &'static [ValueDefinition],
+);
+
+const VENDOR_DICTIONARIES: &[VendorDictionary] = &[
+    #[cfg(feature = "aruba")]
+    (aruba::VENDOR_ARUBA, aruba::ATTRIBUTES, aruba::VALUES),
+    #[cfg(feature = "cisco")]
+    (cisco::VENDOR_CISCO, cisco::ATTRIBUTES, cisco::VALUES),
+    #[cfg(feature = "juniper")]
+    (
+        juniper::VENDOR_JUNIPER,
+        juniper::ATTRIBUTES,
+        juniper::VALUES,
+    ),
+    #[cfg(feature = "rfc2548")]
+    (
+        rfc2548::VENDOR_MICROSOFT,
+        rfc2548::ATTRIBUTES,
+        rfc2548::VALUES,
+    ),
+    #[cfg(feature = "mikrotik")]
+    (
+        mikrotik::VENDOR_MIKROTIK,
+        mikrotik::ATTRIBUTES,
+        mikrotik::VALUES,
+    ),
+    #[cfg(feature = "wispr")]
+    (wispr::VENDOR_WIS_PR, wispr::ATTRIBUTES, wispr::VALUES),
+];
+
+/// Returns the all attribute definitions of the built-in dictionary.
+pub fn attributes() -> impl Iterator<Item = &'static AttributeDefinition> {
+    DICTIONARIES
+        .iter()
+        .flat_map(|(attributes, _)| attributes.iter())
+}
+
+/// Returns the all value definitions of the built-in dictionary, including the ones of the vendors.
+pub fn values() -> impl Iterator<Item = &'static ValueDefinition> {
+    DICTIONARIES
+        .iter()
+        .flat_map(|(_, values)| values.iter())
+        .chain(
+            VENDOR_DICTIONARIES
+                .iter()
+                .flat_map(|(_, _, values)| values.iter()),
+        )
+}
+
+/// Lookup an attribute definition by the name (e.g. `User-Name`).
+pub fn lookup_attribute_by_name(name: &str) -> Option<&'static AttributeDefinition> {
+    attributes().find(|attribute| attribute.name.eq_ignore_ascii_case(name))
+}
+
+/// Lookup an attribute definition by the type (e.g. `1` for `User-Name`).
+pub fn lookup_attribute_by_type(typ: AVPType) -> Option<&'static AttributeDefinition> {
+    attributes().find(|attribute| attribute.typ == typ)
+}
+
+/// Lookup an attribute definition of a vendor by the name (e.g. `Cisco-AVPair`); it returns the Vendor-Id with it.
+pub fn lookup_vendor_attribute_by_name(
+    name: &str,
+) -> Option<(VendorId, &'static AttributeDefinition)> {
+    VENDOR_DICTIONARIES
+        .iter()
+        .flat_map(|(vendor_id, attributes, _)| {
+            attributes
+                .iter()
+                .map(move |attribute| (*vendor_id, attribute))
+        })
+        .find(|(_, attribute)| attribute.name.eq_ignore_ascii_case(name))
+}
+
+/// Lookup an attribute definition of a vendor by the Vendor-Id and the Vendor-Type (e.g. `9` and `1` for
+/// `Cisco-AVPair`).
+pub fn lookup_vendor_attribute_by_type(
+    vendor_id: VendorId,
+    typ: AVPType,
+) -> Option<&'static AttributeDefinition> {
+    VENDOR_DICTIONARIES
+        .iter()
+        .filter(|(id, _, _)| *id == vendor_id)
+        .flat_map(|(_, attributes, _)| attributes.iter())
+        .find(|attribute| attribute.typ == typ)
+}
+
+/// Returns whether the built-in dictionary defines an attribute.
+///
+/// A Vendor-Specific attribute is known if the dictionary defines all of the sub-attributes of it (the ones of
+/// Microsoft, i.e. `rfc2548`, are always known with that feature).
+pub fn is_known_attribute(avp: &AVP) -> bool {
+    if avp.get_type() != rfc2865::VENDOR_SPECIFIC_TYPE {
+        return lookup_attribute_by_type(avp.get_type()).is_some();
+    }
+    match vsa::decode_vendor_specific(avp) {
+        #[cfg(feature = "rfc2548")]
+        Ok((rfc2548::VENDOR_MICROSOFT, _)) => true,
+        Ok((vendor_id, tlvs)) => {
+            !tlvs.is_empty()
+                && tlvs
+                    .iter()
+                    .all(|tlv| lookup_vendor_attribute_by_type(vendor_id, tlv.get_type()).is_some())
+        }
+        Err(_) => false,
+    }
+}
+
+/// Lookup a named value of an attribute (e.g. `Service-Type` and `Framed-User`).
+pub fn lookup_value_by_name(attribute_name: &str, name: &str) -> Option<&'static ValueDefinition> {
+    values().find(|value| {
+        value.attribute_name.eq_ignore_ascii_case(attribute_name)
+            && value.name.eq_ignore_ascii_case(name)
+    })
+}
+
+/// Lookup the name of a value of an attribute (e.g. `Service-Type` and `2`).
+pub fn lookup_value(attribute_name: &str, value: u32) -> Option<&'static ValueDefinition> {
+    values().find(|definition| {
+        definition
+            .attribute_name
+            .eq_ignore_ascii_case(attribute_name)
+            && definition.value == value
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::dictionary;
+    use crate::core::rfc2865;
+    use crate::core::rfc2869;
+
+    #[test]
+    fn test_lookup_attribute() {
+        let attribute = dictionary::lookup_attribute_by_name("user-name").unwrap();
+        assert_eq!(attribute.get_name(), "User-Name");
+        assert_eq!(attribute.get_type(), rfc2865::USER_NAME_TYPE);
+        assert_eq!(attribute.get_data_type(), "string");
+
+        let attribute =
+            dictionary::lookup_attribute_by_type(rfc2869::EVENT_TIMESTAMP_TYPE).unwrap();
+        assert_eq!(attribute.get_name(), "Event-Timestamp");
+        assert_eq!(attribute.get_data_type(), "date");
+
+        assert!(dictionary::lookup_attribute_by_name("No-Such-Attribute").is_none());
+
+        #[cfg(feature = "rfc5580")]
+        {
+            let attribute = dictionary::lookup_attribute_by_name("Operator-Name").unwrap();
+            assert_eq!(
+                attribute.get_type(),
+                crate::core::rfc5580::OPERATOR_NAME_TYPE
+            );
+            let value = dictionary::lookup_value_by_name("Requested-Location-Info", "NAS-Location")
+                .unwrap();
+            assert_eq!(
+                value.get_value(),
+                crate::core::rfc5580::REQUESTED_LOCATION_INFO_NAS_LOCATION
+            );
+        }
+    }
+
+    #[test]
+    fn test_lookup_value() {
+        let value = dictionary::lookup_value_by_name("Service-Type", "Framed-User").unwrap();
+        assert_eq!(value.get_value(), rfc2865::SERVICE_TYPE_FRAMED_USER);
+
+        let value = dictionary::lookup_value("Acct-Status-Type", 2).unwrap();
+        assert_eq!(value.get_name(), "Stop");
+
+        assert!(dictionary::lookup_value("Service-Type", 9999).is_none());
+    }
+
+    #[cfg(feature = "cisco")]
+    #[test]
+    fn test_lookup_vendor_attribute() {
+        use crate::core::cisco;
+
+        let (vendor_id, attribute) =
+            dictionary::lookup_vendor_attribute_by_name("cisco-avpair").unwrap();
+        assert_eq!(vendor_id, cisco::VENDOR_CISCO);
+        assert_eq!(attribute.get_type(), cisco::CISCO_AV_PAIR_TYPE);
+
+        let attribute = dictionary::lookup_vendor_attribute_by_type(
+            cisco::VENDOR_CISCO,
+            cisco::CISCO_DISCONNECT_CAUSE_TYPE,
+        )
+        .unwrap();
+        assert_eq!(attribute.get_name(), "Cisco-Disconnect-Cause");
+        assert_eq!(attribute.get_data_type(), "integer");
+        assert_eq!(
+            dictionary::lookup_value("Cisco-Disconnect-Cause", 21)
+                .unwrap()
+                .get_name(),
+            "Idle-Timeout"
+        );
+
+        assert!(dictionary::lookup_attribute_by_name("Cisco-AVPair").is_none());
+        assert!(dictionary::lookup_vendor_attribute_by_type(cisco::VENDOR_CISCO, 100).is_none());
+    }
+
+    #[cfg(feature = "wispr")]
+    #[test]
+    fn test_lookup_wispr_attribute() {
+        use crate::core::code::Code;
+        use crate::core::packet::Packet;
+        use crate::core::wispr;
+
+        let (vendor_id, attribute) =
+            dictionary::lookup_vendor_attribute_by_name("WISPr-Bandwidth-Max-Down").unwrap();
+        assert_eq!(vendor_id, wispr::VENDOR_WIS_PR);
+        assert_eq!(attribute.get_type(), wispr::WIS_PR_BANDWIDTH_MAX_DOWN_TYPE);
+
+        let mut packet = Packet::new(Code::AccessAccept, b"secret");
+        wispr::add_wis_pr_bandwidth_max_up(&mut packet, 1_000_000);
+        wispr::add_wis_pr_redirection_url(&mut packet, "https://portal.example.com/");
+        wispr::add_wis_pr_session_terminate_time(&mut packet, "2026-10-14T23:59:59+00:00");
+        let decoded = Packet::decode(&packet.encode().unwrap(), b"secret").unwrap();
+        assert_eq!(
+            wispr::lookup_wis_pr_bandwidth_max_up(&decoded)
+                .unwrap()
+                .unwrap(),
+            1_000_000
+        );
+        assert_eq!(
+            wispr::lookup_wis_pr_redirection_url(&decoded)
+                .unwrap()
+                .unwrap(),
+            "https://portal.example.com/"
+        );
+        assert!(dictionary::is_known_attribute(&decoded.get_attributes()[2]));
+    }
+}
@@ -0,0 +1,321 @@
+//! The differences between two packets, e.g. to compare a response of this crate with the one of FreeRADIUS (or a
+//! request with the one of `radclient`) in a capture while chasing an interoperability issue.
+//!
+//! `Packet::diff()` compares the headers and the attributes by the names of the built-in dictionary: the values of each
+//! attribute are paired in order, so a value that differs is `Changed`, and the extra ones of either packet are
+//! `Removed` or `Added`. The sub-attributes of Vendor-Specific are compared one by one as well. The values are in the
+//! notation of `Dictionary::format_packet()`, and the secrets are `<redacted>` as `Packet::to_pretty_string()` does,
+//! so the report can be shared.
+//!
+//! ```
+//! use radius::core::code::Code;
+//! use radius::core::packet::Packet;
+//! use radius::core::rfc2865;
+//!
+//! let mut expected = Packet::new(Code::AccessAccept, b"secret");
+//! rfc2865::add_reply_message(&mut expected, "welcome");
+//! rfc2865::add_session_timeout(&mut expected, 3600);
+//! let mut actual = expected.clone();
+//! rfc2865::delete_session_timeout(&mut actual);
+//! rfc2865::add_session_timeout(&mut actual, 600);
+//!
+//! let diff = expected.diff(&actual);
+//! assert_eq!(diff.to_string(), "~ Session-Timeout: 3600 -> 600");
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::core::avp::{AVPType, AVP};
+use crate::core::code::Code;
+use crate::core::debug_bundle::to_hex;
+use crate::core::dictionary::{self, Dictionary};
+use crate::core::packet::Packet;
+use crate::core::rfc2865;
+use crate::core::vsa::{self, VendorId};
+
+/// An attribute of a `PacketDifference`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffAttribute {
+    vendor_id: Option<VendorId>,
+    typ: AVPType,
+    name: String,
+    value: Vec<u8>,
+    formatted_value: String,
+}
+
+impl DiffAttribute {
+    fn new(dictionary: &Dictionary, vendor_id: Option<VendorId>, avp: AVP) -> Self {
+        let (name, formatted_value) = dictionary.describe_attribute(vendor_id, &avp);
+        DiffAttribute {
+            vendor_id,
+            typ: avp.get_type(),
+            name,
+            value: avp.get_value().to_vec(),
+            formatted_value,
+        }
+    }
+
+    /// Returns the Vendor-Id if this is a sub-attribute of Vendor-Specific.
+    pub fn get_vendor_id(&self) -> Option<VendorId> {
+        self.vendor_id
+    }
+
+    /// Returns the type of the attribute, or the Vendor-Type of a sub-attribute.
+    pub fn get_type(&self) -> AVPType {
+        self.typ
+    }
+
+    /// Returns the name in the built-in dictionary, or `Attr-N` (`Attr-26.<Vendor-Id>.<Vendor-Type>`) of an unknown one.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the raw value as it is in the packet.
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Returns the value in the notation of `Dictionary::format_packet()`, with the secrets `<redacted>`.
+    pub fn get_formatted_value(&self) -> &str {
+        &self.formatted_value
+    }
+}
+
+/// A difference of the packets, from the packet of `diff()` (the left one) to the other.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PacketDifference {
+    /// The codes differ.
+    Code(Code, Code),
+    /// The identifiers differ.
+    Identifier(u8, u8),
+    /// The authenticators differ.
+    Authenticator(Vec<u8>, Vec<u8>),
+    /// The other packet has an attribute value that the left one doesn't have.
+    Added(DiffAttribute),
+    /// The left packet has an attribute value that the other one doesn't have.
+    Removed(DiffAttribute),
+    /// The attribute has another value at the same position among the values of that attribute.
+    Changed(DiffAttribute, DiffAttribute),
+}
+
+impl fmt::Display for PacketDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PacketDifference::Code(left, right) => {
+                write!(f, "Code: {} -> {}", left.string(), right.string())
+            }
+            PacketDifference::Identifier(left, right) => {
+                write!(f, "Identifier: {left} -> {right}")
+            }
+            PacketDifference::Authenticator(left, right) => {
+                write!(f, "Authenticator: {} -> {}", to_hex(left), to_hex(right))
+            }
+            PacketDifference::Added(attribute) => {
+                write!(f, "+ {} = {}", attribute.name, attribute.formatted_value)
+            }
+            PacketDifference::Removed(attribute) => {
+                write!(f, "- {} = {}", attribute.name, attribute.formatted_value)
+            }
+            PacketDifference::Changed(left, right) => write!(
+                f,
+                "~ {}: {} -> {}",
+                left.name, left.formatted_value, right.formatted_value
+            ),
+        }
+    }
+}
+
+/// The report of `Packet::diff()`; its `Display` has a line per difference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PacketDiff {
+    differences: Vec<PacketDifference>,
+}
+
+impl PacketDiff {
+    /// Returns the differences: the ones of the header first, then the ones of the attributes in the order that they
+    /// appear in the packets.
+    pub fn get_differences(&self) -> &[PacketDifference] {
+        &self.differences
+    }
+
+    /// Returns whether the packets are the same.
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+impl fmt::Display for PacketDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, difference) in self.differences.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{difference}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the attributes of a packet with the sub-attributes of Vendor-Specific taken out of that.
+fn flatten_attributes(packet: &Packet) -> Vec<(Option<VendorId>, AVP)> {
+    let mut attributes = Vec::new();
+    for avp in packet.get_attributes() {
+        if avp.get_type() == rfc2865::VENDOR_SPECIFIC_TYPE {
+            if let Ok((vendor_id, tlvs)) = vsa::decode_vendor_specific(avp) {
+                attributes.extend(tlvs.iter().map(|tlv| {
+                    (
+                        Some(vendor_id),
+                        AVP::from_bytes(tlv.get_type(), tlv.get_value()),
+                    )
+                }));
+                continue;
+            }
+        }
+        attributes.push((None, avp.clone()));
+    }
+    attributes
+}
+
+impl Packet {
+    /// Returns the differences from this packet to the other one (see `radius::core::diff`).
+    pub fn diff(&self, other: &Packet) -> PacketDiff {
+        let mut differences = Vec::new();
+        if self.get_code() != other.get_code() {
+            differences.push(PacketDifference::Code(self.get_code(), other.get_code()));
+        }
+        if self.get_identifier() != other.get_identifier() {
+            differences.push(PacketDifference::Identifier(
+                self.get_identifier(),
+                other.get_identifier(),
+            ));
+        }
+        if self.get_authenticator() != other.get_authenticator() {
+            differences.push(PacketDifference::Authenticator(
+                self.get_authenticator().to_vec(),
+                other.get_authenticator().to_vec(),
+            ));
+        }
+
+        let left = flatten_attributes(self);
+        let right = flatten_attributes(other);
+        let mut keys: Vec<(Option<VendorId>, AVPType)> = Vec::new();
+        for (vendor_id, avp) in left.iter().chain(right.iter()) {
+            let key = (*vendor_id, avp.get_type());
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        dictionary::with_built_in(|dictionary| {
+            let values_of = |attributes: &[(Option<VendorId>, AVP)], key| -> Vec<AVP> {
+                attributes
+                    .iter()
+                    .filter(|(vendor_id, avp)| (*vendor_id, avp.get_type()) == key)
+                    .map(|(_, avp)| avp.clone())
+                    .collect()
+            };
+            for key in keys {
+                let (vendor_id, _) = key;
+                let mut left_values = values_of(&left, key).into_iter();
+                let mut right_values = values_of(&right, key).into_iter();
+                loop {
+                    let difference = match (left_values.next(), right_values.next()) {
+                        (None, None) => break,
+                        (Some(l), Some(r)) if l.get_value() == r.get_value() => continue,
+                        (Some(l), Some(r)) => PacketDifference::Changed(
+                            DiffAttribute::new(dictionary, vendor_id, l),
+                            DiffAttribute::new(dictionary, vendor_id, r),
+                        ),
+                        (Some(l), None) => {
+                            PacketDifference::Removed(DiffAttribute::new(dictionary, vendor_id, l))
+                        }
+                        (None, Some(r)) => {
+                            PacketDifference::Added(DiffAttribute::new(dictionary, vendor_id, r))
+                        }
+                    };
+                    differences.push(difference);
+                }
+            }
+        });
+        PacketDiff { differences }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::code::Code;
+    use crate::core::diff::PacketDifference;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::core::vsa;
+
+    #[test]
+    fn test_diff() {
+        let mut left = Packet::new(Code::AccessAccept, b"secret");
+        rfc2865::add_reply_message(&mut left, "first");
+        rfc2865::add_reply_message(&mut left, "second");
+        rfc2865::add_framed_ip_address(&mut left, &"192.0.2.1".parse().unwrap());
+        rfc2865::add_class(&mut left, b"class");
+        assert!(left.diff(&left.clone()).is_empty());
+
+        let mut right = left.clone();
+        right.set_identifier(left.get_identifier().wrapping_add(1));
+        rfc2865::delete_reply_message(&mut right);
+        rfc2865::add_reply_message(&mut right, "first");
+        rfc2865::delete_framed_ip_address(&mut right);
+        rfc2865::add_framed_ip_address(&mut right, &"192.0.2.2".parse().unwrap());
+        rfc2865::add_user_password(&mut right, b"p@ssw0rd").unwrap();
+        right.add(vsa::make_vendor_specific(9, 1, b"shell:priv-lvl=15"));
+        right.add(vsa::make_vendor_specific(65535, 1, &[0xab]));
+
+        let diff = left.diff(&right);
+        let lines: Vec<String> = diff
+            .get_differences()
+            .iter()
+            .map(|difference| difference.to_string())
+            .collect();
+        let mut expected = vec![
+            format!(
+                "Identifier: {} -> {}",
+                left.get_identifier(),
+                right.get_identifier()
+            ),
+            "- Reply-Message = \"second\"".to_owned(),
+            "~ Framed-IP-Address: 192.0.2.1 -> 192.0.2.2".to_owned(),
+            "+ User-Password = <redacted>".to_owned(),
+        ];
+        #[cfg(feature = "cisco")]
+        expected.push("+ Cisco-AVPair = \"shell:priv-lvl=15\"".to_owned());
+        #[cfg(not(feature = "cisco"))]
+        expected.push("+ Attr-26.9.1 = 0x7368656c6c3a707269762d6c766c3d3135".to_owned());
+        expected.push("+ Attr-26.65535.1 = 0xab".to_owned());
+        assert_eq!(lines, expected);
+        assert_eq!(diff.to_string(), expected.join("\n"));
+
+        match &diff.get_differences()[2] {
+            PacketDifference::Changed(l, r) => {
+                assert_eq!(l.get_type(), rfc2865::FRAMED_IP_ADDRESS_TYPE);
+                assert_eq!(l.get_value(), &[192, 0, 2, 1]);
+                assert_eq!(r.get_formatted_value(), "192.0.2.2");
+            }
+            difference => panic!("unexpected difference: {difference}"),
+        }
+        assert_eq!(
+            right.diff(&left).to_string().lines().last(),
+            Some("- Attr-26.65535.1 = 0xab")
+        );
+
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let accept = request.make_response_packet(Code::AccessAccept);
+        let reject = request.make_response_packet(Code::AccessReject);
+        assert_eq!(
+            accept.diff(&reject).get_differences(),
+            &[PacketDifference::Code(
+                Code::AccessAccept,
+                Code::AccessReject
+            )]
+        );
+    }
+}
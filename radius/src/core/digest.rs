@@ -0,0 +1,341 @@
+//! HTTP Digest authentication (RFC 5090) of the SIP proxies and the HTTP servers, i.e. the `response` and the
+//! `rspauth` of RFC 2617 by the Digest-* attributes of `radius::core::rfc5090`.
+//!
+//! A SIP proxy (the RADIUS client) copies the Authorization header into an Access-Request by `add_digest_request()`,
+//! and the RADIUS server verifies Digest-Response by the password (or the H(A1)) of the user and answers
+//! Digest-Response-Auth in the Access-Accept.
+//!
+//! ```
+//! use radius::core::code::Code;
+//! use radius::core::digest::{self, DigestRequest};
+//! use radius::core::packet::Packet;
+//!
+//! let mut request = DigestRequest::new("Mufasa", "testrealm@host.com", "dcd98b7102dd2f0e8b11d0f600bfb0c093", "GET", "/dir/index.html");
+//! request.set_qop(Some("auth"), "0a4f113b", "00000001");
+//! let mut packet = Packet::new(Code::AccessRequest, b"secret");
+//! digest::add_digest_request(&mut packet, &request, "6629fae49393a05397450978507c4ef1");
+//!
+//! // the server side
+//! let (request, response) = digest::lookup_digest_request(&packet).unwrap();
+//! let ha1 = digest::compute_ha1("Mufasa", "testrealm@host.com", "Circle Of Life");
+//! assert!(request.verify_response(&ha1, &response));
+//! let mut accept = packet.make_response_packet(Code::AccessAccept);
+//! digest::add_digest_response_auth(&mut accept, &request, &ha1);
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+
+use thiserror::Error;
+
+use crate::core::avp::AVPError;
+use crate::core::constant_time;
+use crate::core::packet::Packet;
+use crate::core::rfc5090;
+use crate::error::ErrorKind;
+
+const ALGORITHM_MD5_SESS: &str = "MD5-sess";
+const QOP_AUTH_INT: &str = "auth-int";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DigestError {
+    /// An error that is raised when a request lacks an attribute that the digest needs.
+    #[error("the attribute is missing: {0}")]
+    MissingAttributeError(String),
+    /// An error that is raised when an attribute can't be decoded.
+    #[error("failed to decode the digest attribute: {0}")]
+    DecodingError(#[from] AVPError),
+}
+
+impl DigestError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            DigestError::MissingAttributeError(_) | DigestError::DecodingError(_) => {
+                ErrorKind::Malformed
+            }
+        }
+    }
+}
+
+/// The parameters of a digest response (i.e. of the Authorization header) except the response itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestRequest {
+    username: String,
+    realm: String,
+    nonce: String,
+    method: String,
+    uri: String,
+    qop: Option<String>,
+    cnonce: Option<String>,
+    nonce_count: Option<String>,
+    algorithm: Option<String>,
+    entity_body_hash: Option<String>,
+}
+
+impl DigestRequest {
+    /// A constructor for a digest request without `qop` (i.e. of RFC 2069) and with the MD5 algorithm.
+    pub fn new(username: &str, realm: &str, nonce: &str, method: &str, uri: &str) -> Self {
+        DigestRequest {
+            username: username.to_owned(),
+            realm: realm.to_owned(),
+            nonce: nonce.to_owned(),
+            method: method.to_owned(),
+            uri: uri.to_owned(),
+            qop: None,
+            cnonce: None,
+            nonce_count: None,
+            algorithm: None,
+            entity_body_hash: None,
+        }
+    }
+
+    /// Set `qop` (`auth` or `auth-int`) with `cnonce` and `nc`; `None` removes them.
+    pub fn set_qop(&mut self, qop: Option<&str>, cnonce: &str, nonce_count: &str) {
+        self.qop = qop.map(str::to_owned);
+        self.cnonce = qop.map(|_| cnonce.to_owned());
+        self.nonce_count = qop.map(|_| nonce_count.to_owned());
+    }
+
+    /// Set `algorithm` (`MD5` or `MD5-sess`; default: `None`, i.e. `MD5`).
+    pub fn set_algorithm(&mut self, algorithm: Option<&str>) {
+        self.algorithm = algorithm.map(str::to_owned);
+    }
+
+    /// Set the H(entity-body) of `qop=auth-int` in hex (Digest-Entity-Body-Hash).
+    pub fn set_entity_body_hash(&mut self, entity_body_hash: Option<&str>) {
+        self.entity_body_hash = entity_body_hash.map(str::to_owned);
+    }
+
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn get_realm(&self) -> &str {
+        &self.realm
+    }
+
+    pub fn get_nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    pub fn get_method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn get_uri(&self) -> &str {
+        &self.uri
+    }
+
+    pub fn get_qop(&self) -> Option<&str> {
+        self.qop.as_deref()
+    }
+
+    pub fn get_algorithm(&self) -> Option<&str> {
+        self.algorithm.as_deref()
+    }
+
+    /// Returns the `response` of the H(A1) in hex (see `compute_ha1()`).
+    pub fn compute_response(&self, ha1: &str) -> String {
+        self.compute(ha1, &self.method)
+    }
+
+    /// Returns the `rspauth` of the H(A1) in hex, i.e. Digest-Response-Auth of the Access-Accept; it's the `response`
+    /// without the method.
+    pub fn compute_response_auth(&self, ha1: &str) -> String {
+        self.compute(ha1, "")
+    }
+
+    /// Returns whether the `response` (i.e. Digest-Response) is the one of the H(A1).
+    pub fn verify_response(&self, ha1: &str, response: &str) -> bool {
+        let expected = self.compute_response(ha1);
+        constant_time::eq(
+            expected.as_bytes(),
+            response.to_ascii_lowercase().as_bytes(),
+        )
+    }
+
+    fn compute(&self, ha1: &str, method: &str) -> String {
+        let ha1 = match self.algorithm.as_deref() {
+            Some(algorithm) if algorithm.eq_ignore_ascii_case(ALGORITHM_MD5_SESS) => {
+                md5_hex(&format!(
+                    "{ha1}:{}:{}",
+                    self.nonce,
+                    self.cnonce.as_deref().unwrap_or_default()
+                ))
+            }
+            _ => ha1.to_ascii_lowercase(),
+        };
+        let ha2 = match self.qop.as_deref() {
+            Some(QOP_AUTH_INT) => md5_hex(&format!(
+                "{method}:{}:{}",
+                self.uri,
+                self.entity_body_hash.as_deref().unwrap_or_default()
+            )),
+            _ => md5_hex(&format!("{method}:{}", self.uri)),
+        };
+        match &self.qop {
+            Some(qop) => md5_hex(&format!(
+                "{ha1}:{}:{}:{}:{qop}:{ha2}",
+                self.nonce,
+                self.nonce_count.as_deref().unwrap_or_default(),
+                self.cnonce.as_deref().unwrap_or_default(),
+            )),
+            None => md5_hex(&format!("{ha1}:{}:{ha2}", self.nonce)),
+        }
+    }
+}
+
+/// Returns the H(A1) of a user in hex, i.e. `MD5(username:realm:password)` (Digest-HA1).
+pub fn compute_ha1(username: &str, realm: &str, password: &str) -> String {
+    md5_hex(&format!("{username}:{realm}:{password}"))
+}
+
+/// Add Digest-Response and the attributes of the parameters to a request.
+pub fn add_digest_request(packet: &mut Packet, request: &DigestRequest, response: &str) {
+    rfc5090::add_digest_response(packet, response);
+    rfc5090::add_digest_realm(packet, &request.realm);
+    rfc5090::add_digest_nonce(packet, &request.nonce);
+    rfc5090::add_digest_method(packet, &request.method);
+    rfc5090::add_digest_uri(packet, &request.uri);
+    rfc5090::add_digest_username(packet, &request.username);
+    if let Some(qop) = &request.qop {
+        rfc5090::add_digest_qop(packet, qop);
+    }
+    if let Some(algorithm) = &request.algorithm {
+        rfc5090::add_digest_algorithm(packet, algorithm);
+    }
+    if let Some(entity_body_hash) = &request.entity_body_hash {
+        rfc5090::add_digest_entity_body_hash(packet, entity_body_hash);
+    }
+    if let Some(cnonce) = &request.cnonce {
+        rfc5090::add_digest_c_nonce(packet, cnonce);
+    }
+    if let Some(nonce_count) = &request.nonce_count {
+        rfc5090::add_digest_nonce_count(packet, nonce_count);
+    }
+}
+
+/// Add Digest-Response-Auth (i.e. `rspauth`) of the H(A1) to a response of the request.
+pub fn add_digest_response_auth(packet: &mut Packet, request: &DigestRequest, ha1: &str) {
+    rfc5090::add_digest_response_auth(packet, &request.compute_response_auth(ha1));
+}
+
+/// Lookup the parameters and Digest-Response of a request.
+pub fn lookup_digest_request(packet: &Packet) -> Result<(DigestRequest, String), DigestError> {
+    fn required(
+        value: Option<Result<String, AVPError>>,
+        name: &str,
+    ) -> Result<String, DigestError> {
+        match value {
+            Some(value) => Ok(value?),
+            None => Err(DigestError::MissingAttributeError(name.to_owned())),
+        }
+    }
+
+    let response = required(rfc5090::lookup_digest_response(packet), "Digest-Response")?;
+    let request = DigestRequest {
+        username: required(rfc5090::lookup_digest_username(packet), "Digest-Username")?,
+        realm: required(rfc5090::lookup_digest_realm(packet), "Digest-Realm")?,
+        nonce: required(rfc5090::lookup_digest_nonce(packet), "Digest-Nonce")?,
+        method: required(rfc5090::lookup_digest_method(packet), "Digest-Method")?,
+        uri: required(rfc5090::lookup_digest_uri(packet), "Digest-URI")?,
+        qop: rfc5090::lookup_digest_qop(packet).transpose()?,
+        cnonce: rfc5090::lookup_digest_c_nonce(packet).transpose()?,
+        nonce_count: rfc5090::lookup_digest_nonce_count(packet).transpose()?,
+        algorithm: rfc5090::lookup_digest_algorithm(packet).transpose()?,
+        entity_body_hash: rfc5090::lookup_digest_entity_body_hash(packet).transpose()?,
+    };
+    Ok((request, response))
+}
+
+fn md5_hex(data: &str) -> String {
+    md5::compute(data)
+        .0
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::code::Code;
+    use crate::core::digest::{
+        add_digest_request, add_digest_response_auth, compute_ha1, lookup_digest_request,
+        DigestError, DigestRequest,
+    };
+    use crate::core::packet::Packet;
+    use crate::core::rfc5090;
+
+    fn make_request() -> DigestRequest {
+        // the example of RFC 2617 section 3.5
+        let mut request = DigestRequest::new(
+            "Mufasa",
+            "testrealm@host.com",
+            "dcd98b7102dd2f0e8b11d0f600bfb0c093",
+            "GET",
+            "/dir/index.html",
+        );
+        request.set_qop(Some("auth"), "0a4f113b", "00000001");
+        request
+    }
+
+    #[test]
+    fn test_compute_response() {
+        let ha1 = compute_ha1("Mufasa", "testrealm@host.com", "Circle Of Life");
+        assert_eq!(ha1, "939e7578ed9e3c518a452acee763bce9");
+
+        let request = make_request();
+        assert_eq!(
+            request.compute_response(&ha1),
+            "6629fae49393a05397450978507c4ef1"
+        );
+        assert!(request.verify_response(&ha1, "6629FAE49393A05397450978507C4EF1"));
+        assert!(!request.verify_response(&ha1, "6629fae49393a05397450978507c4ef2"));
+        assert_ne!(
+            request.compute_response_auth(&ha1),
+            request.compute_response(&ha1)
+        );
+
+        let mut sess = make_request();
+        sess.set_algorithm(Some("MD5-sess"));
+        assert_ne!(sess.compute_response(&ha1), request.compute_response(&ha1));
+        let mut rfc2069 = make_request();
+        rfc2069.set_qop(None, "", "");
+        assert_ne!(
+            rfc2069.compute_response(&ha1),
+            request.compute_response(&ha1)
+        );
+    }
+
+    #[test]
+    fn test_digest_attributes() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        let request = make_request();
+        add_digest_request(&mut packet, &request, "6629fae49393a05397450978507c4ef1");
+        let decoded = Packet::decode(&packet.encode().unwrap(), b"secret").unwrap();
+        assert_eq!(
+            lookup_digest_request(&decoded).unwrap(),
+            (
+                request.clone(),
+                "6629fae49393a05397450978507c4ef1".to_owned()
+            )
+        );
+
+        let mut accept = packet.make_response_packet(Code::AccessAccept);
+        add_digest_response_auth(&mut accept, &request, "939e7578ed9e3c518a452acee763bce9");
+        assert_eq!(
+            rfc5090::lookup_digest_response_auth(&accept)
+                .unwrap()
+                .unwrap(),
+            request.compute_response_auth("939e7578ed9e3c518a452acee763bce9")
+        );
+
+        rfc5090::delete_digest_uri(&mut packet);
+        assert_eq!(
+            lookup_digest_request(&packet),
+            Err(DigestError::MissingAttributeError("Digest-URI".to_owned()))
+        );
+    }
+}
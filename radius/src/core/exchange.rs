@@ -0,0 +1,210 @@
+//! A client over a request/response transport that the application provides.
+//!
+//! `Exchange` carries one request datagram and returns the response datagram, e.g. by an HTTP POST to a gateway that
+//! relays the datagrams to the RADIUS server over UDP. This runs where the sockets and tokio are not available, such
+//! as the browsers and the edge runtimes on `wasm32-unknown-unknown` (`fetch()`), or a `no_std` device with its own
+//! network stack; the retransmissions are up to the transport, since such a transport is usually reliable.
+//!
+//! `ExchangeClient` encodes a request, hands it to the transport, and verifies and decodes the response the same way
+//! as `Client` (see `radius::core::sansio::ClientTransaction`). There is no clock without `std`, so the caller gives
+//! the current time for Event-Timestamp.
+//!
+//! ```
+//! use core::future::Future;
+//!
+//! use chrono::Utc;
+//! use radius::core::code::Code;
+//! use radius::core::exchange::{Exchange, ExchangeClient, ExchangeError};
+//! use radius::core::packet::Packet;
+//!
+//! /// A transport of the application, e.g. by `fetch()` of the browser.
+//! struct HttpTunnel {
+//!     url: String,
+//! }
+//!
+//! impl Exchange for HttpTunnel {
+//!     type Error = String;
+//!
+//!     fn exchange(&self, request: &[u8]) -> impl Future<Output = Result<Vec<u8>, String>> {
+//!         let request = request.to_vec();
+//!         async move { Err(format!("POST {} of {} bytes is not implemented", self.url, request.len())) }
+//!     }
+//! }
+//!
+//! async fn authenticate() -> Result<Packet, ExchangeError<String>> {
+//!     let client = ExchangeClient::new(HttpTunnel { url: "https://gateway.example/radius".to_owned() });
+//!     let request = Packet::new(Code::AccessRequest, b"secret");
+//!     client.send_packet(&request, &Utc::now()).await
+//! }
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::future::Future;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::core::packet::Packet;
+use crate::core::sansio::{ClientTransaction, ClientTransactionError};
+use crate::error::ErrorKind;
+
+/// A request/response transport of the datagrams.
+pub trait Exchange {
+    /// The error of the transport.
+    type Error: Display;
+
+    /// Send a request datagram, and return the response datagram.
+    fn exchange(&self, request: &[u8]) -> impl Future<Output = Result<Vec<u8>, Self::Error>>;
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ExchangeError<E: Display> {
+    /// An error that is raised when the transport has failed.
+    #[error("failed to exchange the datagrams; {0}")]
+    TransportError(E),
+    /// An error that is raised when the transport has returned a datagram that is not the response of the request,
+    /// i.e. it has another identifier or the Response Authenticator of another secret.
+    #[error("the datagram is not the response of the request")]
+    NotResponseError(),
+    /// An error of the encoding of the request or of the response, see `ClientTransactionError`.
+    #[error(transparent)]
+    TransactionError(#[from] ClientTransactionError),
+}
+
+impl<E: Display> ExchangeError<E> {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            ExchangeError::TransportError(_) => ErrorKind::Io,
+            ExchangeError::NotResponseError() => ErrorKind::Authenticity,
+            ExchangeError::TransactionError(e) => e.get_kind(),
+        }
+    }
+}
+
+/// A RADIUS client over an `Exchange`.
+pub struct ExchangeClient<T: Exchange> {
+    exchange: T,
+    add_event_timestamp: bool,
+    require_message_authenticator: bool,
+}
+
+impl<T: Exchange> ExchangeClient<T> {
+    /// A constructor for a client over the transport.
+    pub fn new(exchange: T) -> Self {
+        ExchangeClient {
+            exchange,
+            add_event_timestamp: true,
+            require_message_authenticator: false,
+        }
+    }
+
+    /// Add Event-Timestamp to the Accounting-Request, CoA-Request and Disconnect-Request that don't have that yet
+    /// (default: `true`; see `ClientTransaction::new()`).
+    pub fn set_add_event_timestamp(&mut self, add_event_timestamp: bool) {
+        self.add_event_timestamp = add_event_timestamp;
+    }
+
+    /// Require Message-Authenticator of the responses (default: `false`; see
+    /// `ClientTransaction::set_require_message_authenticator()`).
+    pub fn set_require_message_authenticator(&mut self, require_message_authenticator: bool) {
+        self.require_message_authenticator = require_message_authenticator;
+    }
+
+    pub fn get_exchange(&self) -> &T {
+        &self.exchange
+    }
+
+    /// Send a request through the transport, and return the verified response.
+    pub async fn send_packet(
+        &self,
+        request_packet: &Packet,
+        now: &DateTime<Utc>,
+    ) -> Result<Packet, ExchangeError<T::Error>> {
+        let mut transaction =
+            ClientTransaction::new(request_packet, self.add_event_timestamp, 0, now)?;
+        transaction.set_require_message_authenticator(self.require_message_authenticator)?;
+
+        let response = self
+            .exchange
+            .exchange(transaction.get_request_datagram())
+            .await
+            .map_err(ExchangeError::TransportError)?;
+        if !transaction.is_response(&response) {
+            return Err(ExchangeError::NotResponseError());
+        }
+        Ok(transaction.on_response(&response)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::future::Future;
+
+    use chrono::Utc;
+
+    use crate::core::code::Code;
+    use crate::core::exchange::{Exchange, ExchangeClient, ExchangeError};
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::error::ErrorKind;
+
+    /// A transport that answers the requests as a server of the secret.
+    struct Loopback {
+        secret: &'static [u8],
+    }
+
+    impl Exchange for Loopback {
+        type Error = &'static str;
+
+        fn exchange(&self, request: &[u8]) -> impl Future<Output = Result<Vec<u8>, Self::Error>> {
+            let response = Packet::decode(request, self.secret).map(|request| {
+                let mut response = request.make_response_packet(Code::AccessAccept);
+                rfc2865::add_reply_message(&mut response, "welcome");
+                response.encode().unwrap()
+            });
+            async move { response.map_err(|_| "undecodable request") }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_packet() {
+        let mut client = ExchangeClient::new(Loopback { secret: b"secret" });
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "bob");
+        let response = client.send_packet(&request, &Utc::now()).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(
+            rfc2865::lookup_reply_message(&response).unwrap().unwrap(),
+            "welcome"
+        );
+
+        let request = Packet::new(Code::AccessRequest, b"another");
+        let e = client.send_packet(&request, &Utc::now()).await.unwrap_err();
+        assert_eq!(e, ExchangeError::NotResponseError());
+        assert_eq!(e.get_kind(), ErrorKind::Authenticity);
+
+        client.set_require_message_authenticator(true);
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let response = client.send_packet(&request, &Utc::now()).await.unwrap();
+        assert!(response
+            .lookup(crate::core::rfc2869::MESSAGE_AUTHENTICATOR_TYPE)
+            .is_some());
+
+        struct Unreachable;
+        impl Exchange for Unreachable {
+            type Error = &'static str;
+
+            async fn exchange(&self, _: &[u8]) -> Result<Vec<u8>, Self::Error> {
+                Err("unreachable")
+            }
+        }
+        let e = ExchangeClient::new(Unreachable)
+            .send_packet(&request, &Utc::now())
+            .await
+            .unwrap_err();
+        assert_eq!(e, ExchangeError::TransportError("unreachable"));
+        assert_eq!(e.get_kind(), ErrorKind::Io);
+    }
+}
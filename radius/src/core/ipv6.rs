@@ -0,0 +1,235 @@
+//! The IPv6 value types of the attributes (RFC 3162): the prefixes (`ipv6prefix`, e.g. Framed-IPv6-Prefix and
+//! Delegated-IPv6-Prefix) and the interface identifiers (`ifid`, e.g. Framed-Interface-Id).
+//!
+//! ```
+//! # #[cfg(feature = "rfc3162")]
+//! # fn main() {
+//! use radius::core::code::Code;
+//! use radius::core::ipv6::{InterfaceId, Ipv6Prefix};
+//! use radius::core::packet::Packet;
+//! use radius::core::rfc3162;
+//!
+//! let mut packet = Packet::new(Code::AccessAccept, b"secret");
+//! rfc3162::add_framed_ipv6_prefix_value(&mut packet, &"2001:db8:1::/48".parse().unwrap());
+//! rfc3162::add_framed_interface_id_value(&mut packet, &"0:0:0:1".parse().unwrap());
+//!
+//! let prefix: Ipv6Prefix = rfc3162::lookup_framed_ipv6_prefix_value(&packet).unwrap().unwrap();
+//! assert_eq!(prefix.get_prefix_len(), 48);
+//! let interface_id: InterfaceId = rfc3162::lookup_framed_interface_id_value(&packet).unwrap().unwrap();
+//! assert_eq!(interface_id.octets(), [0, 0, 0, 0, 0, 0, 0, 1]);
+//! # }
+//! # #[cfg(not(feature = "rfc3162"))]
+//! # fn main() {}
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::fmt;
+use core::net::Ipv6Addr;
+use core::str::FromStr;
+
+use thiserror::Error;
+
+use crate::error::ErrorKind;
+
+const MAX_PREFIX_LEN: u8 = 128;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ipv6Error {
+    /// An error that is raised when a prefix length exceeds 128.
+    #[error("invalid prefix length: {0}")]
+    InvalidPrefixLengthError(u8),
+    /// An error that is raised when a string is neither an IPv6 prefix nor an interface identifier.
+    #[error("invalid format: {0}")]
+    InvalidFormatError(String),
+}
+
+impl Ipv6Error {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            Ipv6Error::InvalidPrefixLengthError(_) | Ipv6Error::InvalidFormatError(_) => {
+                ErrorKind::Encoding
+            }
+        }
+    }
+}
+
+/// An IPv6 prefix, i.e. an address and a prefix length (e.g. `2001:db8::/32`).
+///
+/// The bits of the address beyond the prefix length are always zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ipv6Prefix {
+    addr: Ipv6Addr,
+    prefix_len: u8,
+}
+
+impl Ipv6Prefix {
+    /// A constructor for a prefix; the bits of the address beyond the prefix length are cleared.
+    pub fn new(addr: Ipv6Addr, prefix_len: u8) -> Result<Self, Ipv6Error> {
+        if prefix_len > MAX_PREFIX_LEN {
+            return Err(Ipv6Error::InvalidPrefixLengthError(prefix_len));
+        }
+        let mask = match prefix_len {
+            0 => 0,
+            len => u128::MAX << (MAX_PREFIX_LEN - len),
+        };
+        Ok(Ipv6Prefix {
+            addr: Ipv6Addr::from(u128::from(addr) & mask),
+            prefix_len,
+        })
+    }
+
+    pub fn get_addr(&self) -> Ipv6Addr {
+        self.addr
+    }
+
+    pub fn get_prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns whether the prefix contains the address.
+    pub fn contains(&self, addr: &Ipv6Addr) -> bool {
+        match Ipv6Prefix::new(*addr, self.prefix_len) {
+            Ok(prefix) => prefix.addr == self.addr,
+            Err(_) => false,
+        }
+    }
+}
+
+impl FromStr for Ipv6Prefix {
+    type Err = Ipv6Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Ipv6Error::InvalidFormatError(s.to_owned());
+        let (addr, prefix_len) = s.split_once('/').ok_or_else(invalid)?;
+        let addr: Ipv6Addr = addr.parse().map_err(|_| invalid())?;
+        let prefix_len: u8 = prefix_len.parse().map_err(|_| invalid())?;
+        Ipv6Prefix::new(addr, prefix_len)
+    }
+}
+
+impl fmt::Display for Ipv6Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+/// An interface identifier of 64 bits, i.e. the lower half of an IPv6 address (e.g. `0:0:0:1`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InterfaceId([u8; 8]);
+
+impl InterfaceId {
+    pub fn new(octets: [u8; 8]) -> Self {
+        InterfaceId(octets)
+    }
+
+    pub fn octets(&self) -> [u8; 8] {
+        self.0
+    }
+}
+
+impl From<[u8; 8]> for InterfaceId {
+    fn from(octets: [u8; 8]) -> Self {
+        InterfaceId(octets)
+    }
+}
+
+impl From<u64> for InterfaceId {
+    fn from(value: u64) -> Self {
+        InterfaceId(value.to_be_bytes())
+    }
+}
+
+impl From<InterfaceId> for u64 {
+    fn from(interface_id: InterfaceId) -> Self {
+        u64::from_be_bytes(interface_id.0)
+    }
+}
+
+/// Parses the four groups of the hexadecimal digits like the FreeRADIUS notation, e.g. `fe80:0:0:1`.
+impl FromStr for InterfaceId {
+    type Err = Ipv6Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Ipv6Error::InvalidFormatError(s.to_owned());
+        let mut octets = [0; 8];
+        let mut groups = s.split(':');
+        for chunk in octets.chunks_mut(2) {
+            let group = groups.next().ok_or_else(invalid)?;
+            if group.is_empty() || group.len() > 4 {
+                return Err(invalid());
+            }
+            let group = u16::from_str_radix(group, 16).map_err(|_| invalid())?;
+            chunk.copy_from_slice(&group.to_be_bytes());
+        }
+        match groups.next() {
+            Some(_) => Err(invalid()),
+            None => Ok(InterfaceId(octets)),
+        }
+    }
+}
+
+impl fmt::Display for InterfaceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let group = |i: usize| u16::from_be_bytes([self.0[i], self.0[i + 1]]);
+        write!(
+            f,
+            "{:x}:{:x}:{:x}:{:x}",
+            group(0),
+            group(2),
+            group(4),
+            group(6)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::ToOwned;
+    use alloc::string::ToString;
+
+    use crate::core::ipv6::{InterfaceId, Ipv6Error, Ipv6Prefix};
+
+    #[test]
+    fn test_ipv6_prefix() {
+        let prefix: Ipv6Prefix = "2001:db8:1:2::1/48".parse().unwrap();
+        assert_eq!(
+            prefix.get_addr(),
+            "2001:db8:1::".parse::<core::net::Ipv6Addr>().unwrap()
+        );
+        assert_eq!(prefix.to_string(), "2001:db8:1::/48");
+        assert!(prefix.contains(&"2001:db8:1:ffff::1".parse().unwrap()));
+        assert!(!prefix.contains(&"2001:db8:2::1".parse().unwrap()));
+        assert_eq!("::/0".parse::<Ipv6Prefix>().unwrap().get_prefix_len(), 0);
+
+        assert_eq!(
+            "2001:db8::/129".parse::<Ipv6Prefix>(),
+            Err(Ipv6Error::InvalidPrefixLengthError(129))
+        );
+        for invalid in ["2001:db8::", "192.0.2.0/24", "2001:db8::/x"] {
+            assert_eq!(
+                invalid.parse::<Ipv6Prefix>(),
+                Err(Ipv6Error::InvalidFormatError(invalid.to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_interface_id() {
+        let interface_id: InterfaceId = "fe80:0:12:abcd".parse().unwrap();
+        assert_eq!(
+            interface_id.octets(),
+            [0xfe, 0x80, 0x00, 0x00, 0x00, 0x12, 0xab, 0xcd]
+        );
+        assert_eq!(interface_id.to_string(), "fe80:0:12:abcd");
+        assert_eq!(u64::from(InterfaceId::from(1u64)), 1);
+
+        for invalid in ["0:0:0", "0:0:0:0:0", "0:0::0", "0:0:0:12345", "0:0:0:g"] {
+            assert_eq!(
+                invalid.parse::<InterfaceId>(),
+                Err(Ipv6Error::InvalidFormatError(invalid.to_owned()))
+            );
+        }
+    }
+}
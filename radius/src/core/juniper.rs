@@ -0,0 +1,965 @@
+// Code generated by machine generator; DO NOT EDIT.
+
+//! Utility for the Vendor-Specific attributes of Juniper (Vendor-Id 2636).
+//!
+//! This module handles the packet according to the following definition:
+//! ```text
+//! //! # -*- text -*-
+//! # Copyright (C) 2020 The FreeRADIUS Server project and contributors
+//! # This work is licensed under CC-BY version 4.0 https://creativecommons.org/licenses/by/4.0
+//! # Version $Id$
+//! #
+//! #    The common Juniper attributes, from the dictionary.juniper of FreeRADIUS.
+//! #
+//! #    $Id$
+//! #
+//!
+//! VENDOR        Juniper                2636
+//!
+//! BEGIN-VENDOR    Juniper
+//!
+//! ATTRIBUTE    Juniper-Local-User-Name            1    string
+//! ATTRIBUTE    Juniper-Allow-Commands            2    string
+//! ATTRIBUTE    Juniper-Deny-Commands            3    string
+//! ATTRIBUTE    Juniper-Allow-Configuration        4    string
+//! ATTRIBUTE    Juniper-Deny-Configuration        5    string
+//! ATTRIBUTE    Juniper-Interactive-Command        8    string
+//! ATTRIBUTE    Juniper-Configuration-Change        9    string
+//! ATTRIBUTE    Juniper-User-Permissions        10    string
+//!
+//! ATTRIBUTE    Juniper-CTP-Group            21    integer
+//! ATTRIBUTE    Juniper-CTPView-APP-Group        22    integer
+//! ATTRIBUTE    Juniper-CTPView-OS-Group        23    integer
+//!
+//! ATTRIBUTE    Juniper-Primary-Dns            31    ipaddr
+//! ATTRIBUTE    Juniper-Primary-Wins            32    ipaddr
+//! ATTRIBUTE    Juniper-Secondary-Dns            33    ipaddr
+//! ATTRIBUTE    Juniper-Secondary-Wins            34    ipaddr
+//! ATTRIBUTE    Juniper-Interface-id            35    string
+//! ATTRIBUTE    Juniper-Ip-Pool-Name            36    string
+//! ATTRIBUTE    Juniper-Keep-Alive            37    integer
+//!
+//! ATTRIBUTE    Juniper-Switching-Filter        48    string
+//! ATTRIBUTE    Juniper-VoIP-Vlan            49    string
+//!
+//! VALUE    Juniper-CTP-Group        Read_Only        1
+//! VALUE    Juniper-CTP-Group        Admin            2
+//! VALUE    Juniper-CTP-Group        Privileged_Admin    3
+//! VALUE    Juniper-CTP-Group        Auditor            4
+//!
+//! VALUE    Juniper-CTPView-APP-Group    Net_View        1
+//! VALUE    Juniper-CTPView-APP-Group    Net_Admin        2
+//! VALUE    Juniper-CTPView-APP-Group    Global_Admin        3
+//!
+//! VALUE    Juniper-CTPView-OS-Group    Web_Manager        1
+//! VALUE    Juniper-CTPView-OS-Group    System_Admin        2
+//! VALUE    Juniper-CTPView-OS-Group    Auditor            3
+//!
+//! END-VENDOR    Juniper
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::net::Ipv4Addr;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::packet::Packet;
+use crate::core::vsa::{self, VendorId};
+
+/// The Vendor-Id of Juniper.
+pub const VENDOR_JUNIPER: VendorId = 2636;
+
+pub const JUNIPER_LOCAL_USER_NAME_TYPE: AVPType = 1;
+/// Delete all of `juniper_local_user_name` values from a packet.
+pub fn delete_juniper_local_user_name(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_LOCAL_USER_NAME_TYPE);
+}
+/// Add `juniper_local_user_name` string value to a packet.
+pub fn add_juniper_local_user_name(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_LOCAL_USER_NAME_TYPE, value),
+    );
+}
+/// Lookup a `juniper_local_user_name` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_local_user_name`, it returns `None`.
+pub fn lookup_juniper_local_user_name(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_LOCAL_USER_NAME_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_local_user_name` string value from a packet.
+pub fn lookup_all_juniper_local_user_name(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_LOCAL_USER_NAME_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_ALLOW_COMMANDS_TYPE: AVPType = 2;
+/// Delete all of `juniper_allow_commands` values from a packet.
+pub fn delete_juniper_allow_commands(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_ALLOW_COMMANDS_TYPE);
+}
+/// Add `juniper_allow_commands` string value to a packet.
+pub fn add_juniper_allow_commands(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_ALLOW_COMMANDS_TYPE, value),
+    );
+}
+/// Lookup a `juniper_allow_commands` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_allow_commands`, it returns `None`.
+pub fn lookup_juniper_allow_commands(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_ALLOW_COMMANDS_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_allow_commands` string value from a packet.
+pub fn lookup_all_juniper_allow_commands(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_ALLOW_COMMANDS_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_DENY_COMMANDS_TYPE: AVPType = 3;
+/// Delete all of `juniper_deny_commands` values from a packet.
+pub fn delete_juniper_deny_commands(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_DENY_COMMANDS_TYPE);
+}
+/// Add `juniper_deny_commands` string value to a packet.
+pub fn add_juniper_deny_commands(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_DENY_COMMANDS_TYPE, value),
+    );
+}
+/// Lookup a `juniper_deny_commands` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_deny_commands`, it returns `None`.
+pub fn lookup_juniper_deny_commands(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_DENY_COMMANDS_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_deny_commands` string value from a packet.
+pub fn lookup_all_juniper_deny_commands(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_DENY_COMMANDS_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_ALLOW_CONFIGURATION_TYPE: AVPType = 4;
+/// Delete all of `juniper_allow_configuration` values from a packet.
+pub fn delete_juniper_allow_configuration(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_ALLOW_CONFIGURATION_TYPE);
+}
+/// Add `juniper_allow_configuration` string value to a packet.
+pub fn add_juniper_allow_configuration(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_ALLOW_CONFIGURATION_TYPE, value),
+    );
+}
+/// Lookup a `juniper_allow_configuration` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_allow_configuration`, it returns `None`.
+pub fn lookup_juniper_allow_configuration(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_ALLOW_CONFIGURATION_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_allow_configuration` string value from a packet.
+pub fn lookup_all_juniper_allow_configuration(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_ALLOW_CONFIGURATION_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_DENY_CONFIGURATION_TYPE: AVPType = 5;
+/// Delete all of `juniper_deny_configuration` values from a packet.
+pub fn delete_juniper_deny_configuration(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_DENY_CONFIGURATION_TYPE);
+}
+/// Add `juniper_deny_configuration` string value to a packet.
+pub fn add_juniper_deny_configuration(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_DENY_CONFIGURATION_TYPE, value),
+    );
+}
+/// Lookup a `juniper_deny_configuration` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_deny_configuration`, it returns `None`.
+pub fn lookup_juniper_deny_configuration(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_DENY_CONFIGURATION_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_deny_configuration` string value from a packet.
+pub fn lookup_all_juniper_deny_configuration(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_DENY_CONFIGURATION_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_INTERACTIVE_COMMAND_TYPE: AVPType = 8;
+/// Delete all of `juniper_interactive_command` values from a packet.
+pub fn delete_juniper_interactive_command(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_INTERACTIVE_COMMAND_TYPE);
+}
+/// Add `juniper_interactive_command` string value to a packet.
+pub fn add_juniper_interactive_command(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_INTERACTIVE_COMMAND_TYPE, value),
+    );
+}
+/// Lookup a `juniper_interactive_command` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_interactive_command`, it returns `None`.
+pub fn lookup_juniper_interactive_command(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_INTERACTIVE_COMMAND_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_interactive_command` string value from a packet.
+pub fn lookup_all_juniper_interactive_command(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_INTERACTIVE_COMMAND_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_CONFIGURATION_CHANGE_TYPE: AVPType = 9;
+/// Delete all of `juniper_configuration_change` values from a packet.
+pub fn delete_juniper_configuration_change(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CONFIGURATION_CHANGE_TYPE);
+}
+/// Add `juniper_configuration_change` string value to a packet.
+pub fn add_juniper_configuration_change(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_CONFIGURATION_CHANGE_TYPE, value),
+    );
+}
+/// Lookup a `juniper_configuration_change` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_configuration_change`, it returns `None`.
+pub fn lookup_juniper_configuration_change(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CONFIGURATION_CHANGE_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_configuration_change` string value from a packet.
+pub fn lookup_all_juniper_configuration_change(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_CONFIGURATION_CHANGE_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_USER_PERMISSIONS_TYPE: AVPType = 10;
+/// Delete all of `juniper_user_permissions` values from a packet.
+pub fn delete_juniper_user_permissions(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_USER_PERMISSIONS_TYPE);
+}
+/// Add `juniper_user_permissions` string value to a packet.
+pub fn add_juniper_user_permissions(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_USER_PERMISSIONS_TYPE, value),
+    );
+}
+/// Lookup a `juniper_user_permissions` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_user_permissions`, it returns `None`.
+pub fn lookup_juniper_user_permissions(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_USER_PERMISSIONS_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_user_permissions` string value from a packet.
+pub fn lookup_all_juniper_user_permissions(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_USER_PERMISSIONS_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_CTP_GROUP_TYPE: AVPType = 21;
+/// Delete all of `juniper_ctp_group` values from a packet.
+pub fn delete_juniper_ctp_group(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CTP_GROUP_TYPE);
+}
+/// Add `juniper_ctp_group` value-defined integer value to a packet.
+pub fn add_juniper_ctp_group(packet: &mut Packet, value: JuniperCTPGroup) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_u32(JUNIPER_CTP_GROUP_TYPE, value),
+    );
+}
+/// Lookup a `juniper_ctp_group` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_ctp_group`, it returns `None`.
+pub fn lookup_juniper_ctp_group(packet: &Packet) -> Option<Result<JuniperCTPGroup, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CTP_GROUP_TYPE)
+        .map(|v| Ok(v.encode_u32()? as JuniperCTPGroup))
+}
+/// Lookup all of the `juniper_ctp_group` value-defined integer value from a packet.
+pub fn lookup_all_juniper_ctp_group(packet: &Packet) -> Result<Vec<JuniperCTPGroup>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_CTP_GROUP_TYPE) {
+        vec.push(avp.encode_u32()? as JuniperCTPGroup)
+    }
+    Ok(vec)
+}
+/// Lookup a `juniper_ctp_group` value from a packet as `JuniperCTPGroupValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_ctp_group`, it returns `None`.
+pub fn lookup_juniper_ctp_group_value(
+    packet: &Packet,
+) -> Option<Result<JuniperCTPGroupValue, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CTP_GROUP_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `juniper_ctp_group` values from a packet as `JuniperCTPGroupValue`.
+pub fn lookup_all_juniper_ctp_group_value(
+    packet: &Packet,
+) -> Result<Vec<JuniperCTPGroupValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_CTP_GROUP_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_CTP_VIEW_APP_GROUP_TYPE: AVPType = 22;
+/// Delete all of `juniper_ctp_view_app_group` values from a packet.
+pub fn delete_juniper_ctp_view_app_group(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_APP_GROUP_TYPE);
+}
+/// Add `juniper_ctp_view_app_group` value-defined integer value to a packet.
+pub fn add_juniper_ctp_view_app_group(packet: &mut Packet, value: JuniperCTPViewAPPGroup) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_u32(JUNIPER_CTP_VIEW_APP_GROUP_TYPE, value),
+    );
+}
+/// Lookup a `juniper_ctp_view_app_group` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_ctp_view_app_group`, it returns `None`.
+pub fn lookup_juniper_ctp_view_app_group(
+    packet: &Packet,
+) -> Option<Result<JuniperCTPViewAPPGroup, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_APP_GROUP_TYPE)
+        .map(|v| Ok(v.encode_u32()? as JuniperCTPViewAPPGroup))
+}
+/// Lookup all of the `juniper_ctp_view_app_group` value-defined integer value from a packet.
+pub fn lookup_all_juniper_ctp_view_app_group(
+    packet: &Packet,
+) -> Result<Vec<JuniperCTPViewAPPGroup>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_APP_GROUP_TYPE)
+    {
+        vec.push(avp.encode_u32()? as JuniperCTPViewAPPGroup)
+    }
+    Ok(vec)
+}
+/// Lookup a `juniper_ctp_view_app_group` value from a packet as `JuniperCTPViewAPPGroupValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_ctp_view_app_group`, it returns `None`.
+pub fn lookup_juniper_ctp_view_app_group_value(
+    packet: &Packet,
+) -> Option<Result<JuniperCTPViewAPPGroupValue, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_APP_GROUP_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `juniper_ctp_view_app_group` values from a packet as `JuniperCTPViewAPPGroupValue`.
+pub fn lookup_all_juniper_ctp_view_app_group_value(
+    packet: &Packet,
+) -> Result<Vec<JuniperCTPViewAPPGroupValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_APP_GROUP_TYPE)
+    {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_CTP_VIEW_OS_GROUP_TYPE: AVPType = 23;
+/// Delete all of `juniper_ctp_view_os_group` values from a packet.
+pub fn delete_juniper_ctp_view_os_group(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_OS_GROUP_TYPE);
+}
+/// Add `juniper_ctp_view_os_group` value-defined integer value to a packet.
+pub fn add_juniper_ctp_view_os_group(packet: &mut Packet, value: JuniperCTPViewOSGroup) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_u32(JUNIPER_CTP_VIEW_OS_GROUP_TYPE, value),
+    );
+}
+/// Lookup a `juniper_ctp_view_os_group` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_ctp_view_os_group`, it returns `None`.
+pub fn lookup_juniper_ctp_view_os_group(
+    packet: &Packet,
+) -> Option<Result<JuniperCTPViewOSGroup, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_OS_GROUP_TYPE)
+        .map(|v| Ok(v.encode_u32()? as JuniperCTPViewOSGroup))
+}
+/// Lookup all of the `juniper_ctp_view_os_group` value-defined integer value from a packet.
+pub fn lookup_all_juniper_ctp_view_os_group(
+    packet: &Packet,
+) -> Result<Vec<JuniperCTPViewOSGroup>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_OS_GROUP_TYPE)
+    {
+        vec.push(avp.encode_u32()? as JuniperCTPViewOSGroup)
+    }
+    Ok(vec)
+}
+/// Lookup a `juniper_ctp_view_os_group` value from a packet as `JuniperCTPViewOSGroupValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_ctp_view_os_group`, it returns `None`.
+pub fn lookup_juniper_ctp_view_os_group_value(
+    packet: &Packet,
+) -> Option<Result<JuniperCTPViewOSGroupValue, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_OS_GROUP_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `juniper_ctp_view_os_group` values from a packet as `JuniperCTPViewOSGroupValue`.
+pub fn lookup_all_juniper_ctp_view_os_group_value(
+    packet: &Packet,
+) -> Result<Vec<JuniperCTPViewOSGroupValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_CTP_VIEW_OS_GROUP_TYPE)
+    {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_PRIMARY_DNS_TYPE: AVPType = 31;
+/// Delete all of `juniper_primary_dns` values from a packet.
+pub fn delete_juniper_primary_dns(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_PRIMARY_DNS_TYPE);
+}
+/// Add `juniper_primary_dns` ipaddr value to a packet.
+pub fn add_juniper_primary_dns(packet: &mut Packet, value: &Ipv4Addr) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_ipv4(JUNIPER_PRIMARY_DNS_TYPE, value),
+    );
+}
+/// Lookup a `juniper_primary_dns` ipaddr value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_primary_dns`, it returns `None`.
+pub fn lookup_juniper_primary_dns(packet: &Packet) -> Option<Result<Ipv4Addr, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_PRIMARY_DNS_TYPE)
+        .map(|v| v.encode_ipv4())
+}
+/// Lookup all of the `juniper_primary_dns` ipaddr value from a packet.
+pub fn lookup_all_juniper_primary_dns(packet: &Packet) -> Result<Vec<Ipv4Addr>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_PRIMARY_DNS_TYPE) {
+        vec.push(avp.encode_ipv4()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_PRIMARY_WINS_TYPE: AVPType = 32;
+/// Delete all of `juniper_primary_wins` values from a packet.
+pub fn delete_juniper_primary_wins(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_PRIMARY_WINS_TYPE);
+}
+/// Add `juniper_primary_wins` ipaddr value to a packet.
+pub fn add_juniper_primary_wins(packet: &mut Packet, value: &Ipv4Addr) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_ipv4(JUNIPER_PRIMARY_WINS_TYPE, value),
+    );
+}
+/// Lookup a `juniper_primary_wins` ipaddr value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_primary_wins`, it returns `None`.
+pub fn lookup_juniper_primary_wins(packet: &Packet) -> Option<Result<Ipv4Addr, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_PRIMARY_WINS_TYPE)
+        .map(|v| v.encode_ipv4())
+}
+/// Lookup all of the `juniper_primary_wins` ipaddr value from a packet.
+pub fn lookup_all_juniper_primary_wins(packet: &Packet) -> Result<Vec<Ipv4Addr>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_PRIMARY_WINS_TYPE)
+    {
+        vec.push(avp.encode_ipv4()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_SECONDARY_DNS_TYPE: AVPType = 33;
+/// Delete all of `juniper_secondary_dns` values from a packet.
+pub fn delete_juniper_secondary_dns(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_SECONDARY_DNS_TYPE);
+}
+/// Add `juniper_secondary_dns` ipaddr value to a packet.
+pub fn add_juniper_secondary_dns(packet: &mut Packet, value: &Ipv4Addr) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_ipv4(JUNIPER_SECONDARY_DNS_TYPE, value),
+    );
+}
+/// Lookup a `juniper_secondary_dns` ipaddr value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_secondary_dns`, it returns `None`.
+pub fn lookup_juniper_secondary_dns(packet: &Packet) -> Option<Result<Ipv4Addr, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_SECONDARY_DNS_TYPE)
+        .map(|v| v.encode_ipv4())
+}
+/// Lookup all of the `juniper_secondary_dns` ipaddr value from a packet.
+pub fn lookup_all_juniper_secondary_dns(packet: &Packet) -> Result<Vec<Ipv4Addr>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_SECONDARY_DNS_TYPE)
+    {
+        vec.push(avp.encode_ipv4()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_SECONDARY_WINS_TYPE: AVPType = 34;
+/// Delete all of `juniper_secondary_wins` values from a packet.
+pub fn delete_juniper_secondary_wins(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_SECONDARY_WINS_TYPE);
+}
+/// Add `juniper_secondary_wins` ipaddr value to a packet.
+pub fn add_juniper_secondary_wins(packet: &mut Packet, value: &Ipv4Addr) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_ipv4(JUNIPER_SECONDARY_WINS_TYPE, value),
+    );
+}
+/// Lookup a `juniper_secondary_wins` ipaddr value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_secondary_wins`, it returns `None`.
+pub fn lookup_juniper_secondary_wins(packet: &Packet) -> Option<Result<Ipv4Addr, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_SECONDARY_WINS_TYPE)
+        .map(|v| v.encode_ipv4())
+}
+/// Lookup all of the `juniper_secondary_wins` ipaddr value from a packet.
+pub fn lookup_all_juniper_secondary_wins(packet: &Packet) -> Result<Vec<Ipv4Addr>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_SECONDARY_WINS_TYPE)
+    {
+        vec.push(avp.encode_ipv4()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_INTERFACE_ID_TYPE: AVPType = 35;
+/// Delete all of `juniper_interface_id` values from a packet.
+pub fn delete_juniper_interface_id(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_INTERFACE_ID_TYPE);
+}
+/// Add `juniper_interface_id` string value to a packet.
+pub fn add_juniper_interface_id(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_INTERFACE_ID_TYPE, value),
+    );
+}
+/// Lookup a `juniper_interface_id` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_interface_id`, it returns `None`.
+pub fn lookup_juniper_interface_id(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_INTERFACE_ID_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_interface_id` string value from a packet.
+pub fn lookup_all_juniper_interface_id(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_INTERFACE_ID_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_IP_POOL_NAME_TYPE: AVPType = 36;
+/// Delete all of `juniper_ip_pool_name` values from a packet.
+pub fn delete_juniper_ip_pool_name(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_IP_POOL_NAME_TYPE);
+}
+/// Add `juniper_ip_pool_name` string value to a packet.
+pub fn add_juniper_ip_pool_name(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_IP_POOL_NAME_TYPE, value),
+    );
+}
+/// Lookup a `juniper_ip_pool_name` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_ip_pool_name`, it returns `None`.
+pub fn lookup_juniper_ip_pool_name(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_IP_POOL_NAME_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_ip_pool_name` string value from a packet.
+pub fn lookup_all_juniper_ip_pool_name(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_IP_POOL_NAME_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_KEEP_ALIVE_TYPE: AVPType = 37;
+/// Delete all of `juniper_keep_alive` values from a packet.
+pub fn delete_juniper_keep_alive(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_KEEP_ALIVE_TYPE);
+}
+/// Add `juniper_keep_alive` integer value to a packet.
+pub fn add_juniper_keep_alive(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_u32(JUNIPER_KEEP_ALIVE_TYPE, value),
+    );
+}
+/// Lookup a `juniper_keep_alive` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_keep_alive`, it returns `None`.
+pub fn lookup_juniper_keep_alive(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_KEEP_ALIVE_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `juniper_keep_alive` integer value from a packet.
+pub fn lookup_all_juniper_keep_alive(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_KEEP_ALIVE_TYPE) {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_SWITCHING_FILTER_TYPE: AVPType = 48;
+/// Delete all of `juniper_switching_filter` values from a packet.
+pub fn delete_juniper_switching_filter(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_SWITCHING_FILTER_TYPE);
+}
+/// Add `juniper_switching_filter` string value to a packet.
+pub fn add_juniper_switching_filter(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_SWITCHING_FILTER_TYPE, value),
+    );
+}
+/// Lookup a `juniper_switching_filter` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_switching_filter`, it returns `None`.
+pub fn lookup_juniper_switching_filter(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_SWITCHING_FILTER_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_switching_filter` string value from a packet.
+pub fn lookup_all_juniper_switching_filter(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_SWITCHING_FILTER_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const JUNIPER_VO_IP_VLAN_TYPE: AVPType = 49;
+/// Delete all of `juniper_vo_ip_vlan` values from a packet.
+pub fn delete_juniper_vo_ip_vlan(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_VO_IP_VLAN_TYPE);
+}
+/// Add `juniper_vo_ip_vlan` string value to a packet.
+pub fn add_juniper_vo_ip_vlan(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_JUNIPER,
+        AVP::from_string(JUNIPER_VO_IP_VLAN_TYPE, value),
+    );
+}
+/// Lookup a `juniper_vo_ip_vlan` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `juniper_vo_ip_vlan`, it returns `None`.
+pub fn lookup_juniper_vo_ip_vlan(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_JUNIPER, JUNIPER_VO_IP_VLAN_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `juniper_vo_ip_vlan` string value from a packet.
+pub fn lookup_all_juniper_vo_ip_vlan(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_JUNIPER, JUNIPER_VO_IP_VLAN_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub type JuniperCTPGroup = u32;
+pub const JUNIPER_CTP_GROUP_READ_ONLY: JuniperCTPGroup = 1;
+pub const JUNIPER_CTP_GROUP_ADMIN: JuniperCTPGroup = 2;
+pub const JUNIPER_CTP_GROUP_PRIVILEGED_ADMIN: JuniperCTPGroup = 3;
+pub const JUNIPER_CTP_GROUP_AUDITOR: JuniperCTPGroup = 4;
+
+/// The values of `Juniper-CTP-Group` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `JuniperCTPGroupValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum JuniperCTPGroupValue {
+    /// `Read_Only`
+    ReadOnly = 1,
+    /// `Admin`
+    Admin = 2,
+    /// `Privileged_Admin`
+    PrivilegedAdmin = 3,
+    /// `Auditor`
+    Auditor = 4,
+}
+
+impl From<JuniperCTPGroupValue> for u32 {
+    fn from(value: JuniperCTPGroupValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for JuniperCTPGroupValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(JuniperCTPGroupValue::ReadOnly),
+            2 => Ok(JuniperCTPGroupValue::Admin),
+            3 => Ok(JuniperCTPGroupValue::PrivilegedAdmin),
+            4 => Ok(JuniperCTPGroupValue::Auditor),
+            _ => Err(AVPError::UnknownValueError(
+                "Juniper-CTP-Group".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+pub type JuniperCTPViewAPPGroup = u32;
+pub const JUNIPER_CTP_VIEW_APP_GROUP_NET_VIEW: JuniperCTPViewAPPGroup = 1;
+pub const JUNIPER_CTP_VIEW_APP_GROUP_NET_ADMIN: JuniperCTPViewAPPGroup = 2;
+pub const JUNIPER_CTP_VIEW_APP_GROUP_GLOBAL_ADMIN: JuniperCTPViewAPPGroup = 3;
+
+/// The values of `Juniper-CTPView-APP-Group` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `JuniperCTPViewAPPGroupValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum JuniperCTPViewAPPGroupValue {
+    /// `Net_View`
+    NetView = 1,
+    /// `Net_Admin`
+    NetAdmin = 2,
+    /// `Global_Admin`
+    GlobalAdmin = 3,
+}
+
+impl From<JuniperCTPViewAPPGroupValue> for u32 {
+    fn from(value: JuniperCTPViewAPPGroupValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for JuniperCTPViewAPPGroupValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(JuniperCTPViewAPPGroupValue::NetView),
+            2 => Ok(JuniperCTPViewAPPGroupValue::NetAdmin),
+            3 => Ok(JuniperCTPViewAPPGroupValue::GlobalAdmin),
+            _ => Err(AVPError::UnknownValueError(
+                "Juniper-CTPView-APP-Group".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+pub type JuniperCTPViewOSGroup = u32;
+pub const JUNIPER_CTP_VIEW_OS_GROUP_WEB_MANAGER: JuniperCTPViewOSGroup = 1;
+pub const JUNIPER_CTP_VIEW_OS_GROUP_SYSTEM_ADMIN: JuniperCTPViewOSGroup = 2;
+pub const JUNIPER_CTP_VIEW_OS_GROUP_AUDITOR: JuniperCTPViewOSGroup = 3;
+
+/// The values of `Juniper-CTPView-OS-Group` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `JuniperCTPViewOSGroupValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum JuniperCTPViewOSGroupValue {
+    /// `Web_Manager`
+    WebManager = 1,
+    /// `System_Admin`
+    SystemAdmin = 2,
+    /// `Auditor`
+    Auditor = 3,
+}
+
+impl From<JuniperCTPViewOSGroupValue> for u32 {
+    fn from(value: JuniperCTPViewOSGroupValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for JuniperCTPViewOSGroupValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(JuniperCTPViewOSGroupValue::WebManager),
+            2 => Ok(JuniperCTPViewOSGroupValue::SystemAdmin),
+            3 => Ok(JuniperCTPViewOSGroupValue::Auditor),
+            _ => Err(AVPError::UnknownValueError(
+                "Juniper-CTPView-OS-Group".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new(
+        "Juniper-Local-User-Name",
+        JUNIPER_LOCAL_USER_NAME_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Juniper-Allow-Commands",
+        JUNIPER_ALLOW_COMMANDS_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Juniper-Deny-Commands",
+        JUNIPER_DENY_COMMANDS_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Juniper-Allow-Configuration",
+        JUNIPER_ALLOW_CONFIGURATION_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Juniper-Deny-Configuration",
+        JUNIPER_DENY_CONFIGURATION_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Juniper-Interactive-Command",
+        JUNIPER_INTERACTIVE_COMMAND_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Juniper-Configuration-Change",
+        JUNIPER_CONFIGURATION_CHANGE_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Juniper-User-Permissions",
+        JUNIPER_USER_PERMISSIONS_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("Juniper-CTP-Group", JUNIPER_CTP_GROUP_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Juniper-CTPView-APP-Group",
+        JUNIPER_CTP_VIEW_APP_GROUP_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "Juniper-CTPView-OS-Group",
+        JUNIPER_CTP_VIEW_OS_GROUP_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new("Juniper-Primary-Dns", JUNIPER_PRIMARY_DNS_TYPE, "ipaddr"),
+    AttributeDefinition::new("Juniper-Primary-Wins", JUNIPER_PRIMARY_WINS_TYPE, "ipaddr"),
+    AttributeDefinition::new(
+        "Juniper-Secondary-Dns",
+        JUNIPER_SECONDARY_DNS_TYPE,
+        "ipaddr",
+    ),
+    AttributeDefinition::new(
+        "Juniper-Secondary-Wins",
+        JUNIPER_SECONDARY_WINS_TYPE,
+        "ipaddr",
+    ),
+    AttributeDefinition::new("Juniper-Interface-id", JUNIPER_INTERFACE_ID_TYPE, "string"),
+    AttributeDefinition::new("Juniper-Ip-Pool-Name", JUNIPER_IP_POOL_NAME_TYPE, "string"),
+    AttributeDefinition::new("Juniper-Keep-Alive", JUNIPER_KEEP_ALIVE_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Juniper-Switching-Filter",
+        JUNIPER_SWITCHING_FILTER_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("Juniper-VoIP-Vlan", JUNIPER_VO_IP_VLAN_TYPE, "string"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Juniper-CTP-Group", "Read_Only", 1),
+    ValueDefinition::new("Juniper-CTP-Group", "Admin", 2),
+    ValueDefinition::new("Juniper-CTP-Group", "Privileged_Admin", 3),
+    ValueDefinition::new("Juniper-CTP-Group", "Auditor", 4),
+    ValueDefinition::new("Juniper-CTPView-APP-Group", "Net_View", 1),
+    ValueDefinition::new("Juniper-CTPView-APP-Group", "Net_Admin", 2),
+    ValueDefinition::new("Juniper-CTPView-APP-Group", "Global_Admin", 3),
+    ValueDefinition::new("Juniper-CTPView-OS-Group", "Web_Manager", 1),
+    ValueDefinition::new("Juniper-CTPView-OS-Group", "System_Admin", 2),
+    ValueDefinition::new("Juniper-CTPView-OS-Group", "Auditor", 3),
+];
@@ -0,0 +1,700 @@
+//! Linter of the packets that reports the violations of the RFCs.
+//!
+//! `lint()` checks a packet by itself, and `lint_response()` checks a response with the request that it answers
+//! (e.g. whether Proxy-State is echoed). `lint_datagram()` (i.e. `Packet::validate()`) is the strict validation of a
+//! received datagram; it reports all the violations of the header and the attributes instead of the first decoding
+//! error, and the ones of `lint()` if the datagram can be decoded. These are meant for CI of the RADIUS clients and the NAS firmware;
+//! the checks don't depend on the secret, so the packets decoded with any secret (e.g. captured ones) can be linted.
+//!
+//! The checks:
+//! - the Length of the datagram is 20 to 4096 bytes and the datagram has that many bytes, the attributes fill the
+//!   Length exactly, and nothing follows the Length
+//! - the encoded packet doesn't exceed 4096 bytes, and each attribute value doesn't exceed 253 bytes
+//! - each attribute value has the length of the data type in the dictionary (e.g. 4 bytes of `integer`, and 1 byte at
+//!   least of `string`) and Message-Authenticator has 16 bytes
+//! - the number of each attribute conforms to the tables of RFC 2865 (section 5.44) and RFC 2866 (section 5.13)
+//! - Access-Request has User-Password, CHAP-Password, State or EAP-Message, but not both User-Password and CHAP-Password
+//! - Access-Request and Accounting-Request have NAS-IP-Address, NAS-IPv6-Address or NAS-Identifier
+//! - the packets that have EAP-Message (RFC 3579) and Status-Server (RFC 5997) have Message-Authenticator
+//! - the response has the Proxy-State values of the request unmodified and in order
+//!
+//! ## Example
+//!
+//! ```
+//! use radius::core::code::Code;
+//! use radius::core::lint::{lint, LintRule};
+//! use radius::core::packet::Packet;
+//! use radius::core::rfc2865;
+//!
+//! let mut packet = Packet::new(Code::AccessRequest, b"secret");
+//! rfc2865::add_user_name(&mut packet, "admin");
+//! rfc2865::add_user_password(&mut packet, b"p@ssw0rd").unwrap();
+//! rfc2865::add_chap_password(&mut packet, &[0; 17]);
+//! rfc2865::add_nas_identifier(&mut packet, "nas");
+//!
+//! let diagnostics = lint(&packet);
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].get_rule(), LintRule::PasswordConflict);
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::core::avp::AVPType;
+use crate::core::code::Code;
+use crate::core::dictionary;
+use crate::core::message_authenticator::MESSAGE_AUTHENTICATOR_LENGTH;
+use crate::core::packet::{Packet, MAX_PACKET_LENGTH, RADIUS_PACKET_HEADER_LENGTH};
+use crate::core::{rfc2865, rfc2866, rfc2869};
+
+const MAX_VALUE_LENGTH: usize = 253;
+/// NAS-IPv6-Address of RFC 3162, which is behind the `rfc3162` feature.
+const NAS_IPV6_ADDRESS_TYPE: AVPType = 95;
+
+/// A rule of the linter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+    /// The Length of the datagram is less than 20 bytes, exceeds 4096 bytes, or exceeds the received bytes
+    /// (RFC 2865 section 3).
+    InvalidPacketLength,
+    /// The datagram has the bytes beyond the Length, which the receivers ignore as padding (RFC 2865 section 3).
+    TrailingData,
+    /// The Length of an attribute is less than 2 bytes or overruns the packet, i.e. the datagram can't be decoded
+    /// (RFC 2865 section 5).
+    MalformedAttribute,
+    /// The length of an attribute value doesn't fit the data type of the attribute (RFC 2865 section 5, RFC 8044).
+    InvalidAttributeLength,
+    /// The encoded packet exceeds the maximum length (RFC 2865 section 3).
+    OversizedPacket,
+    /// An attribute value exceeds 253 bytes (RFC 2865 section 5).
+    OversizedAttribute,
+    /// The number of an attribute is not allowed in the packet of the code (RFC 2865 section 5.44, RFC 2866 section 5.13).
+    IllegalAttributeCount,
+    /// Access-Request has both User-Password and CHAP-Password (RFC 2865 section 5.2).
+    PasswordConflict,
+    /// Access-Request has none of User-Password, CHAP-Password, State and EAP-Message (RFC 2865 section 4.1, RFC 3579 section 3.1).
+    MissingAuthenticationAttribute,
+    /// The request has none of NAS-IP-Address, NAS-IPv6-Address and NAS-Identifier (RFC 2865 section 4.1, RFC 2866 section 4.1).
+    MissingNasIdentification,
+    /// The packet has EAP-Message, or is Status-Server, without Message-Authenticator (RFC 3579 section 3.3, RFC 5997 section 3).
+    MissingMessageAuthenticator,
+    /// The response doesn't have the Proxy-State values of the request unmodified and in order (RFC 2865 section 5.33).
+    ProxyStateNotEchoed,
+}
+
+/// A violation that the linter found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    rule: LintRule,
+    attribute_type: Option<AVPType>,
+    message: String,
+}
+
+impl Diagnostic {
+    fn new(rule: LintRule, attribute_type: Option<AVPType>, message: String) -> Self {
+        Diagnostic {
+            rule,
+            attribute_type,
+            message,
+        }
+    }
+
+    pub fn get_rule(&self) -> LintRule {
+        self.rule
+    }
+
+    /// Returns the type of the attribute that violates the rule, if the violation is about an attribute.
+    pub fn get_attribute_type(&self) -> Option<AVPType> {
+        self.attribute_type
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.rule, self.message)
+    }
+}
+
+/// The allowed number of an attribute in a packet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Count {
+    /// `0`
+    Zero,
+    /// `0-1`
+    Optional,
+    /// `0+`
+    Any,
+    /// `1`
+    One,
+}
+
+use Count::{Any, One, Optional, Zero};
+
+/// RFC 2865 section 5.44 (and RFC 3579 section 3.3 for EAP-Message and Message-Authenticator):
+/// Access-Request, Access-Accept, Access-Reject and Access-Challenge.
+#[rustfmt::skip]
+const ACCESS_ATTRIBUTE_COUNTS: &[(AVPType, [Count; 4])] = &[
+    (rfc2865::USER_NAME_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::USER_PASSWORD_TYPE, [Optional, Zero, Zero, Zero]),
+    (rfc2865::CHAP_PASSWORD_TYPE, [Optional, Zero, Zero, Zero]),
+    (rfc2865::NAS_IP_ADDRESS_TYPE, [Optional, Zero, Zero, Zero]),
+    (rfc2865::NAS_PORT_TYPE, [Optional, Zero, Zero, Zero]),
+    (rfc2865::SERVICE_TYPE_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::FRAMED_PROTOCOL_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::FRAMED_IP_ADDRESS_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::FRAMED_IP_NETMASK_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::FRAMED_ROUTING_TYPE, [Zero, Optional, Zero, Zero]),
+    (rfc2865::FILTER_ID_TYPE, [Zero, Any, Zero, Zero]),
+    (rfc2865::FRAMED_MTU_TYPE, [Zero, Optional, Zero, Zero]),
+    (rfc2865::FRAMED_COMPRESSION_TYPE, [Any, Any, Zero, Zero]),
+    (rfc2865::LOGIN_IP_HOST_TYPE, [Any, Any, Zero, Zero]),
+    (rfc2865::LOGIN_SERVICE_TYPE, [Zero, Optional, Zero, Zero]),
+    (rfc2865::LOGIN_TCP_PORT_TYPE, [Zero, Optional, Zero, Zero]),
+    (rfc2865::REPLY_MESSAGE_TYPE, [Zero, Any, Any, Any]),
+    (rfc2865::CALLBACK_NUMBER_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::CALLBACK_ID_TYPE, [Zero, Optional, Zero, Zero]),
+    (rfc2865::FRAMED_ROUTE_TYPE, [Zero, Any, Zero, Zero]),
+    (rfc2865::FRAMED_IPX_NETWORK_TYPE, [Zero, Optional, Zero, Zero]),
+    (rfc2865::STATE_TYPE, [Optional, Optional, Zero, Optional]),
+    (rfc2865::CLASS_TYPE, [Zero, Any, Zero, Zero]),
+    (rfc2865::VENDOR_SPECIFIC_TYPE, [Any, Any, Zero, Any]),
+    (rfc2865::SESSION_TIMEOUT_TYPE, [Zero, Optional, Zero, Optional]),
+    (rfc2865::IDLE_TIMEOUT_TYPE, [Zero, Optional, Zero, Optional]),
+    (rfc2865::TERMINATION_ACTION_TYPE, [Zero, Optional, Zero, Zero]),
+    (rfc2865::CALLED_STATION_ID_TYPE, [Optional, Zero, Zero, Zero]),
+    (rfc2865::CALLING_STATION_ID_TYPE, [Optional, Zero, Zero, Zero]),
+    (rfc2865::NAS_IDENTIFIER_TYPE, [Optional, Zero, Zero, Zero]),
+    (rfc2865::PROXY_STATE_TYPE, [Any, Any, Any, Any]),
+    (rfc2865::LOGIN_LAT_SERVICE_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::LOGIN_LAT_NODE_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::LOGIN_LAT_GROUP_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::FRAMED_APPLE_TALK_LINK_TYPE, [Zero, Optional, Zero, Zero]),
+    (rfc2865::FRAMED_APPLE_TALK_NETWORK_TYPE, [Zero, Any, Zero, Zero]),
+    (rfc2865::FRAMED_APPLE_TALK_ZONE_TYPE, [Zero, Optional, Zero, Zero]),
+    (rfc2865::CHAP_CHALLENGE_TYPE, [Optional, Zero, Zero, Zero]),
+    (rfc2865::NAS_PORT_TYPE_TYPE, [Optional, Zero, Zero, Zero]),
+    (rfc2865::PORT_LIMIT_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2865::LOGIN_LAT_PORT_TYPE, [Optional, Optional, Zero, Zero]),
+    (rfc2869::EAP_MESSAGE_TYPE, [Any, Any, Any, Any]),
+    (rfc2869::MESSAGE_AUTHENTICATOR_TYPE, [Optional, Optional, Optional, Optional]),
+];
+
+/// RFC 2866 section 5.13: Accounting-Request and Accounting-Response.
+#[rustfmt::skip]
+const ACCOUNTING_ATTRIBUTE_COUNTS: &[(AVPType, [Count; 2])] = &[
+    (rfc2865::USER_NAME_TYPE, [Optional, Zero]),
+    (rfc2865::USER_PASSWORD_TYPE, [Zero, Zero]),
+    (rfc2865::CHAP_PASSWORD_TYPE, [Zero, Zero]),
+    (rfc2865::NAS_IP_ADDRESS_TYPE, [Optional, Zero]),
+    (rfc2865::NAS_PORT_TYPE, [Optional, Zero]),
+    (rfc2865::SERVICE_TYPE_TYPE, [Optional, Zero]),
+    (rfc2865::FRAMED_PROTOCOL_TYPE, [Optional, Zero]),
+    (rfc2865::FRAMED_IP_ADDRESS_TYPE, [Optional, Zero]),
+    (rfc2865::FRAMED_IP_NETMASK_TYPE, [Optional, Zero]),
+    (rfc2865::FRAMED_ROUTING_TYPE, [Optional, Zero]),
+    (rfc2865::FILTER_ID_TYPE, [Any, Zero]),
+    (rfc2865::FRAMED_MTU_TYPE, [Optional, Zero]),
+    (rfc2865::FRAMED_COMPRESSION_TYPE, [Any, Zero]),
+    (rfc2865::LOGIN_IP_HOST_TYPE, [Any, Zero]),
+    (rfc2865::LOGIN_SERVICE_TYPE, [Optional, Zero]),
+    (rfc2865::LOGIN_TCP_PORT_TYPE, [Optional, Zero]),
+    (rfc2865::REPLY_MESSAGE_TYPE, [Zero, Zero]),
+    (rfc2865::CALLBACK_NUMBER_TYPE, [Optional, Zero]),
+    (rfc2865::CALLBACK_ID_TYPE, [Optional, Zero]),
+    (rfc2865::FRAMED_ROUTE_TYPE, [Any, Zero]),
+    (rfc2865::FRAMED_IPX_NETWORK_TYPE, [Optional, Zero]),
+    (rfc2865::STATE_TYPE, [Zero, Zero]),
+    (rfc2865::CLASS_TYPE, [Any, Zero]),
+    (rfc2865::VENDOR_SPECIFIC_TYPE, [Any, Any]),
+    (rfc2865::SESSION_TIMEOUT_TYPE, [Optional, Zero]),
+    (rfc2865::IDLE_TIMEOUT_TYPE, [Optional, Zero]),
+    (rfc2865::TERMINATION_ACTION_TYPE, [Optional, Zero]),
+    (rfc2865::CALLED_STATION_ID_TYPE, [Optional, Zero]),
+    (rfc2865::CALLING_STATION_ID_TYPE, [Optional, Zero]),
+    (rfc2865::NAS_IDENTIFIER_TYPE, [Optional, Zero]),
+    (rfc2865::PROXY_STATE_TYPE, [Any, Any]),
+    (rfc2865::LOGIN_LAT_SERVICE_TYPE, [Optional, Zero]),
+    (rfc2865::LOGIN_LAT_NODE_TYPE, [Optional, Zero]),
+    (rfc2865::LOGIN_LAT_GROUP_TYPE, [Optional, Zero]),
+    (rfc2865::FRAMED_APPLE_TALK_LINK_TYPE, [Optional, Zero]),
+    (rfc2865::FRAMED_APPLE_TALK_NETWORK_TYPE, [Optional, Zero]),
+    (rfc2865::FRAMED_APPLE_TALK_ZONE_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_STATUS_TYPE_TYPE, [One, Zero]),
+    (rfc2866::ACCT_DELAY_TIME_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_INPUT_OCTETS_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_OUTPUT_OCTETS_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_SESSION_ID_TYPE, [One, Zero]),
+    (rfc2866::ACCT_AUTHENTIC_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_SESSION_TIME_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_INPUT_PACKETS_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_OUTPUT_PACKETS_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_TERMINATE_CAUSE_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_MULTI_SESSION_ID_TYPE, [Optional, Zero]),
+    (rfc2866::ACCT_LINK_COUNT_TYPE, [Optional, Zero]),
+    (rfc2865::CHAP_CHALLENGE_TYPE, [Zero, Zero]),
+    (rfc2865::NAS_PORT_TYPE_TYPE, [Optional, Zero]),
+    (rfc2865::PORT_LIMIT_TYPE, [Optional, Zero]),
+    (rfc2865::LOGIN_LAT_PORT_TYPE, [Optional, Zero]),
+];
+
+/// Check a received datagram strictly, and return the violations of the header and the attributes, and of `lint()`
+/// if the datagram can be decoded; it returns an empty `Vec` if the datagram has no problem.
+///
+/// The authenticators aren't checked since they depend on the secret (see `Packet::verify_request_authenticator()`).
+pub fn lint_datagram(bs: &[u8]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    if bs.len() < RADIUS_PACKET_HEADER_LENGTH {
+        diagnostics.push(Diagnostic::new(
+            LintRule::InvalidPacketLength,
+            None,
+            format!(
+                "the datagram has {} bytes, but the header has {RADIUS_PACKET_HEADER_LENGTH} bytes",
+                bs.len()
+            ),
+        ));
+        return diagnostics;
+    }
+
+    let length = u16::from_be_bytes([bs[2], bs[3]]) as usize;
+    if !(RADIUS_PACKET_HEADER_LENGTH..=MAX_PACKET_LENGTH).contains(&length) {
+        diagnostics.push(Diagnostic::new(
+            LintRule::InvalidPacketLength,
+            None,
+            format!(
+                "the Length is {length} bytes (minimum: {RADIUS_PACKET_HEADER_LENGTH} bytes, maximum: {MAX_PACKET_LENGTH} bytes)"
+            ),
+        ));
+        return diagnostics;
+    }
+    if length > bs.len() {
+        diagnostics.push(Diagnostic::new(
+            LintRule::InvalidPacketLength,
+            None,
+            format!(
+                "the Length is {length} bytes, but the datagram has {} bytes",
+                bs.len()
+            ),
+        ));
+        return diagnostics;
+    }
+    if length < bs.len() {
+        diagnostics.push(Diagnostic::new(
+            LintRule::TrailingData,
+            None,
+            format!(
+                "the datagram has {} bytes beyond the Length ({length} bytes)",
+                bs.len() - length
+            ),
+        ));
+    }
+
+    let mut offset = RADIUS_PACKET_HEADER_LENGTH;
+    while offset < length {
+        let rest = length - offset;
+        let attribute_length = match bs.get(offset + 1) {
+            Some(attribute_length) if rest >= 2 => *attribute_length as usize,
+            _ => 0,
+        };
+        if attribute_length < 2 || attribute_length > rest {
+            diagnostics.push(Diagnostic::new(
+                LintRule::MalformedAttribute,
+                Some(bs[offset]),
+                format!(
+                    "{} at offset {offset} has the Length {attribute_length}, but {rest} bytes remain in the packet",
+                    attribute_name(bs[offset])
+                ),
+            ));
+            return diagnostics;
+        }
+        offset += attribute_length;
+    }
+
+    // the checks above cover the decoding errors
+    if let Ok(packet) = Packet::decode_unverified(&bs[..length]) {
+        diagnostics.extend(lint(&packet));
+    }
+    diagnostics
+}
+
+/// Check a packet, and return the violations; it returns an empty `Vec` if the packet has no problem.
+pub fn lint(packet: &Packet) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    lint_lengths(packet, &mut diagnostics);
+    lint_value_lengths(packet, &mut diagnostics);
+    lint_attribute_counts(packet, &mut diagnostics);
+
+    let has = |typ: AVPType| packet.lookup(typ).is_some();
+    let code = packet.get_code();
+    if code == Code::AccessRequest {
+        if has(rfc2865::USER_PASSWORD_TYPE) && has(rfc2865::CHAP_PASSWORD_TYPE) {
+            diagnostics.push(Diagnostic::new(
+                LintRule::PasswordConflict,
+                Some(rfc2865::CHAP_PASSWORD_TYPE),
+                "Access-Request has both User-Password and CHAP-Password".into(),
+            ));
+        }
+        if ![
+            rfc2865::USER_PASSWORD_TYPE,
+            rfc2865::CHAP_PASSWORD_TYPE,
+            rfc2865::STATE_TYPE,
+            rfc2869::EAP_MESSAGE_TYPE,
+        ]
+        .into_iter()
+        .any(has)
+        {
+            diagnostics.push(Diagnostic::new(
+                LintRule::MissingAuthenticationAttribute,
+                None,
+                "Access-Request has none of User-Password, CHAP-Password, State and EAP-Message"
+                    .into(),
+            ));
+        }
+    }
+    if (code == Code::AccessRequest || code == Code::AccountingRequest)
+        && ![
+            rfc2865::NAS_IP_ADDRESS_TYPE,
+            NAS_IPV6_ADDRESS_TYPE,
+            rfc2865::NAS_IDENTIFIER_TYPE,
+        ]
+        .into_iter()
+        .any(has)
+    {
+        diagnostics.push(Diagnostic::new(
+            LintRule::MissingNasIdentification,
+            None,
+            format!(
+                "{} has none of NAS-IP-Address, NAS-IPv6-Address and NAS-Identifier",
+                code.string()
+            ),
+        ));
+    }
+    if !has(rfc2869::MESSAGE_AUTHENTICATOR_TYPE) {
+        if has(rfc2869::EAP_MESSAGE_TYPE) {
+            diagnostics.push(Diagnostic::new(
+                LintRule::MissingMessageAuthenticator,
+                Some(rfc2869::MESSAGE_AUTHENTICATOR_TYPE),
+                format!(
+                    "{} has EAP-Message without Message-Authenticator",
+                    code.string()
+                ),
+            ));
+        } else if code == Code::StatusServer {
+            diagnostics.push(Diagnostic::new(
+                LintRule::MissingMessageAuthenticator,
+                Some(rfc2869::MESSAGE_AUTHENTICATOR_TYPE),
+                "Status-Server doesn't have Message-Authenticator".into(),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// Check a response with the request that it answers, and return the violations of both `lint()` and the response.
+pub fn lint_response(response: &Packet, request: &Packet) -> Vec<Diagnostic> {
+    let mut diagnostics = lint(response);
+    let request_proxy_states = request.lookup_all(rfc2865::PROXY_STATE_TYPE);
+    let response_proxy_states = response.lookup_all(rfc2865::PROXY_STATE_TYPE);
+    if request_proxy_states != response_proxy_states {
+        diagnostics.push(Diagnostic::new(
+            LintRule::ProxyStateNotEchoed,
+            Some(rfc2865::PROXY_STATE_TYPE),
+            format!(
+                "the request has {} Proxy-State, but the response doesn't echo those unmodified and in order ({} Proxy-State)",
+                request_proxy_states.len(),
+                response_proxy_states.len()
+            ),
+        ));
+    }
+    diagnostics
+}
+
+fn lint_lengths(packet: &Packet, diagnostics: &mut Vec<Diagnostic>) {
+    let mut length = RADIUS_PACKET_HEADER_LENGTH;
+    for avp in packet.get_attributes() {
+        let value_length = avp.get_value().len();
+        length += 2 + value_length;
+        if value_length > MAX_VALUE_LENGTH {
+            diagnostics.push(Diagnostic::new(
+                LintRule::OversizedAttribute,
+                Some(avp.get_type()),
+                format!(
+                    "{} has {} bytes value (maximum: {} bytes)",
+                    attribute_name(avp.get_type()),
+                    value_length,
+                    MAX_VALUE_LENGTH
+                ),
+            ));
+        }
+    }
+    if length > MAX_PACKET_LENGTH {
+        diagnostics.push(Diagnostic::new(
+            LintRule::OversizedPacket,
+            None,
+            format!("the packet has {length} bytes (maximum: {MAX_PACKET_LENGTH} bytes)"),
+        ));
+    }
+}
+
+fn lint_value_lengths(packet: &Packet, diagnostics: &mut Vec<Diagnostic>) {
+    for avp in packet.get_attributes() {
+        let typ = avp.get_type();
+        let range = if typ == rfc2869::MESSAGE_AUTHENTICATOR_TYPE {
+            MESSAGE_AUTHENTICATOR_LENGTH..=MESSAGE_AUTHENTICATOR_LENGTH
+        } else {
+            match dictionary::lookup_attribute_by_type(typ).map(|d| d.get_data_type()) {
+                Some("byte") => 1..=1,
+                Some("short") => 2..=2,
+                Some("integer" | "date" | "ipaddr") => 4..=4,
+                Some("ether") => 6..=6,
+                Some("ipv4prefix") => 6..=6,
+                Some("integer64" | "ifid") => 8..=8,
+                Some("ipv6addr") => 16..=16,
+                Some("ipv6prefix") => 2..=18,
+                Some("string" | "text" | "octets") => 1..=MAX_VALUE_LENGTH,
+                _ => continue,
+            }
+        };
+        let value_length = avp.get_value().len();
+        // the oversized values are the ones of `lint_lengths()`
+        if value_length < *range.start()
+            || (value_length > *range.end() && value_length <= MAX_VALUE_LENGTH)
+        {
+            let expected = if range.start() == range.end() {
+                format!("{} bytes", range.start())
+            } else {
+                format!("{} to {} bytes", range.start(), range.end())
+            };
+            diagnostics.push(Diagnostic::new(
+                LintRule::InvalidAttributeLength,
+                Some(typ),
+                format!(
+                    "{} has {value_length} bytes value (expected: {expected})",
+                    attribute_name(typ)
+                ),
+            ));
+        }
+    }
+}
+
+fn lint_attribute_counts(packet: &Packet, diagnostics: &mut Vec<Diagnostic>) {
+    let code = packet.get_code();
+    let column = match code {
+        Code::AccessRequest => 0,
+        Code::AccessAccept => 1,
+        Code::AccessReject => 2,
+        Code::AccessChallenge => 3,
+        Code::AccountingRequest => 0,
+        Code::AccountingResponse => 1,
+        _ => return,
+    };
+    let counts = match code {
+        Code::AccountingRequest | Code::AccountingResponse => ACCOUNTING_ATTRIBUTE_COUNTS
+            .iter()
+            .map(|(typ, counts)| (*typ, counts[column]))
+            .collect::<Vec<_>>(),
+        _ => ACCESS_ATTRIBUTE_COUNTS
+            .iter()
+            .map(|(typ, counts)| (*typ, counts[column]))
+            .collect(),
+    };
+
+    for (typ, count) in counts {
+        let actual = packet.lookup_all(typ).len();
+        let (allowed, expected) = match count {
+            Zero => (actual == 0, "not allowed"),
+            Optional => (actual <= 1, "allowed at most once"),
+            Any => (true, ""),
+            One => (actual == 1, "required exactly once"),
+        };
+        if !allowed {
+            diagnostics.push(Diagnostic::new(
+                LintRule::IllegalAttributeCount,
+                Some(typ),
+                format!(
+                    "{} is {} in {}, but the packet has {}",
+                    attribute_name(typ),
+                    expected,
+                    code.string(),
+                    actual
+                ),
+            ));
+        }
+    }
+}
+
+fn attribute_name(typ: AVPType) -> String {
+    match dictionary::lookup_attribute_by_type(typ) {
+        Some(definition) => definition.get_name().into(),
+        None => format!("Attribute-{typ}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::avp::AVP;
+    use crate::core::code::Code;
+    use crate::core::lint::{lint, lint_datagram, lint_response, Diagnostic, LintRule};
+    use crate::core::packet::{Packet, RADIUS_PACKET_HEADER_LENGTH};
+    use crate::core::{rfc2865, rfc2866, rfc2869};
+
+    fn rules(diagnostics: &[Diagnostic]) -> Vec<LintRule> {
+        diagnostics.iter().map(|d| d.get_rule()).collect()
+    }
+
+    #[test]
+    fn test_lint_access_request() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, "admin");
+        rfc2865::add_user_password(&mut packet, b"p@ssw0rd").unwrap();
+        rfc2865::add_nas_ip_address(&mut packet, &"192.0.2.1".parse().unwrap());
+        assert!(lint(&packet).is_empty());
+
+        rfc2865::add_user_name(&mut packet, "admin");
+        rfc2865::add_reply_message(&mut packet, "hello");
+        rfc2869::add_eap_message(&mut packet, &[0; 300]);
+        let diagnostics = lint(&packet);
+        assert_eq!(
+            rules(&diagnostics),
+            vec![
+                LintRule::IllegalAttributeCount,
+                LintRule::IllegalAttributeCount,
+                LintRule::MissingMessageAuthenticator,
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].get_attribute_type(),
+            Some(rfc2865::USER_NAME_TYPE)
+        );
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "IllegalAttributeCount: User-Name is allowed at most once in Access-Request, but the packet has 2"
+        );
+
+        let packet = Packet::new(Code::AccessRequest, b"secret");
+        assert_eq!(
+            rules(&lint(&packet)),
+            vec![
+                LintRule::MissingAuthenticationAttribute,
+                LintRule::MissingNasIdentification,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_accounting_and_lengths() {
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2865::add_nas_identifier(&mut packet, "nas");
+        rfc2866::add_acct_status_type(&mut packet, rfc2866::ACCT_STATUS_TYPE_START);
+        let diagnostics = lint(&packet);
+        assert_eq!(rules(&diagnostics), vec![LintRule::IllegalAttributeCount]);
+        assert_eq!(
+            diagnostics[0].get_attribute_type(),
+            Some(rfc2866::ACCT_SESSION_ID_TYPE)
+        );
+
+        let mut packet = Packet::new(Code::StatusServer, b"secret");
+        for _ in 0..17 {
+            rfc2865::add_proxy_state(&mut packet, &[0; 250]);
+        }
+        packet.add(AVP::from_bytes(rfc2865::VENDOR_SPECIFIC_TYPE, &[0; 254]));
+        assert_eq!(
+            rules(&lint(&packet)),
+            vec![
+                LintRule::OversizedAttribute,
+                LintRule::OversizedPacket,
+                LintRule::MissingMessageAuthenticator,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_value_lengths() {
+        let mut packet = Packet::new(Code::AccessAccept, b"secret");
+        packet.add(AVP::from_bytes(rfc2865::SESSION_TIMEOUT_TYPE, &[0; 3]));
+        packet.add(AVP::from_bytes(rfc2865::REPLY_MESSAGE_TYPE, &[]));
+        packet.add(AVP::from_bytes(
+            rfc2869::MESSAGE_AUTHENTICATOR_TYPE,
+            &[0; 15],
+        ));
+        let diagnostics = lint(&packet);
+        assert_eq!(
+            rules(&diagnostics),
+            vec![LintRule::InvalidAttributeLength; 3]
+        );
+        assert_eq!(
+            diagnostics[0].get_message(),
+            "Session-Timeout has 3 bytes value (expected: 4 bytes)"
+        );
+        assert_eq!(
+            diagnostics[1].get_message(),
+            "Reply-Message has 0 bytes value (expected: 1 to 253 bytes)"
+        );
+    }
+
+    #[test]
+    fn test_lint_datagram() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_password(&mut packet, b"p@ssw0rd").unwrap();
+        rfc2865::add_nas_identifier(&mut packet, "nas");
+        let datagram = packet.encode().unwrap();
+        assert!(lint_datagram(&datagram).is_empty());
+        assert!(Packet::validate(&datagram).is_empty());
+
+        assert_eq!(
+            rules(&lint_datagram(&datagram[..19])),
+            vec![LintRule::InvalidPacketLength]
+        );
+        // the Length exceeds the received bytes
+        let diagnostics = lint_datagram(&datagram[..datagram.len() - 1]);
+        assert_eq!(rules(&diagnostics), vec![LintRule::InvalidPacketLength]);
+
+        // the padding is reported, and the packet of the Length is linted
+        let mut padded = datagram.clone();
+        padded.extend_from_slice(&[0; 3]);
+        rfc2865::add_user_name(&mut packet, "first");
+        rfc2865::add_user_name(&mut packet, "second");
+        let mut duplicated = packet.encode().unwrap();
+        duplicated.extend_from_slice(&[0; 3]);
+        assert_eq!(rules(&lint_datagram(&padded)), vec![LintRule::TrailingData]);
+        assert_eq!(
+            rules(&lint_datagram(&duplicated)),
+            vec![LintRule::TrailingData, LintRule::IllegalAttributeCount]
+        );
+
+        // an attribute that overruns the Length
+        let mut malformed = datagram.clone();
+        malformed[RADIUS_PACKET_HEADER_LENGTH + 1] = 255;
+        let diagnostics = lint_datagram(&malformed);
+        assert_eq!(rules(&diagnostics), vec![LintRule::MalformedAttribute]);
+        assert_eq!(
+            diagnostics[0].get_attribute_type(),
+            Some(rfc2865::USER_PASSWORD_TYPE)
+        );
+    }
+
+    #[test]
+    fn test_lint_response() {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_password(&mut request, b"p@ssw0rd").unwrap();
+        rfc2865::add_nas_identifier(&mut request, "nas");
+        rfc2865::add_proxy_state(&mut request, b"first");
+        rfc2865::add_proxy_state(&mut request, b"second");
+
+        let response = request.make_response_packet(Code::AccessAccept);
+        assert!(lint_response(&response, &request).is_empty());
+
+        let mut response = request.make_response_packet_without_proxy_state(Code::AccessAccept);
+        rfc2865::add_proxy_state(&mut response, b"second");
+        rfc2865::add_proxy_state(&mut response, b"first");
+        assert_eq!(
+            rules(&lint_response(&response, &request)),
+            vec![LintRule::ProxyStateNotEchoed]
+        );
+    }
+}
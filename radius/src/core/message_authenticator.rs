@@ -0,0 +1,169 @@
+//! Message-Authenticator (RFC 3579 section 3.2), i.e. the HMAC-MD5 of the whole packet by the shared secret.
+//!
+//! `Packet::encode()` computes the Message-Authenticator of a packet that has the attribute
+//! (e.g. by `Packet::add_message_authenticator()`), so the value of the attribute doesn't matter before the encoding.
+//! The HMAC is computed with the Message-Authenticator filled with zeros and with the Authenticator field of:
+//!
+//! - the Request Authenticator for Access-Request and Status-Server;
+//! - zeros for Accounting-Request, CoA-Request and Disconnect-Request (RFC 5176 section 3.3);
+//! - the Request Authenticator of the request for the responses.
+
+use alloc::vec::Vec;
+
+use hmac::{Hmac, Mac};
+use md5_digest::Md5;
+
+use crate::core::code::Code;
+use crate::core::constant_time;
+use crate::core::packet::RADIUS_PACKET_HEADER_LENGTH;
+use crate::core::rfc2869;
+
+/// The length of the value of Message-Authenticator.
+pub const MESSAGE_AUTHENTICATOR_LENGTH: usize = 16;
+
+/// Returns whether the Authenticator field is filled with zeros for the Message-Authenticator of the code.
+pub(crate) fn has_zero_authenticator(code: Code) -> bool {
+    matches!(
+        code,
+        Code::AccountingRequest | Code::CoARequest | Code::DisconnectRequest
+    )
+}
+
+/// Returns the offset of the value of the first Message-Authenticator in the encoded packet, if any.
+pub(crate) fn find_value_offset(bs: &[u8]) -> Option<usize> {
+    let mut offset = RADIUS_PACKET_HEADER_LENGTH;
+    while offset + 2 <= bs.len() {
+        let typ = bs[offset];
+        let len = bs[offset + 1] as usize;
+        if len < 2 || offset + len > bs.len() {
+            return None;
+        }
+        if typ == rfc2869::MESSAGE_AUTHENTICATOR_TYPE && len == 2 + MESSAGE_AUTHENTICATOR_LENGTH {
+            return Some(offset + 2);
+        }
+        offset += len;
+    }
+    None
+}
+
+fn compute(bs: &mut [u8], value_offset: usize, authenticator: &[u8], secret: &[u8]) -> [u8; 16] {
+    bs[4..RADIUS_PACKET_HEADER_LENGTH].copy_from_slice(authenticator);
+    bs[value_offset..value_offset + MESSAGE_AUTHENTICATOR_LENGTH].fill(0);
+    let mut mac = Hmac::<Md5>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(bs);
+    mac.finalize().into_bytes().into()
+}
+
+/// Fill the Message-Authenticator of the encoded packet, whose Authenticator field is the one for the computation.
+pub(crate) fn sign(bs: &mut [u8], secret: &[u8]) {
+    if let Some(value_offset) = find_value_offset(bs) {
+        let authenticator = if has_zero_authenticator(Code::from(bs[0])) {
+            [0; 16].to_vec()
+        } else {
+            bs[4..RADIUS_PACKET_HEADER_LENGTH].to_vec()
+        };
+        let mac = compute(bs, value_offset, &authenticator, secret);
+        bs[4..RADIUS_PACKET_HEADER_LENGTH].copy_from_slice(&authenticator);
+        bs[value_offset..value_offset + MESSAGE_AUTHENTICATOR_LENGTH].copy_from_slice(&mac);
+    }
+}
+
+/// Verify the Message-Authenticator of a received packet by the Authenticator field of the computation, i.e. the one of
+/// the request for a response, and zeros for the requests but Access-Request and Status-Server.
+///
+/// This returns `None` if the packet doesn't have Message-Authenticator; `verify_request()` and `verify_response()`
+/// give the authenticator by the code.
+pub fn verify(datagram: &[u8], authenticator: &[u8], secret: &[u8]) -> Option<bool> {
+    if datagram.len() < RADIUS_PACKET_HEADER_LENGTH {
+        return None;
+    }
+    let len = (u16::from_be_bytes([datagram[2], datagram[3]]) as usize).min(datagram.len());
+    let mut bs: Vec<u8> = datagram[..len].to_vec();
+    let value_offset = find_value_offset(&bs)?;
+    let expected = bs[value_offset..value_offset + MESSAGE_AUTHENTICATOR_LENGTH].to_vec();
+    Some(constant_time::eq(
+        &compute(&mut bs, value_offset, authenticator, secret),
+        &expected,
+    ))
+}
+
+/// Verify the Message-Authenticator of a received request.
+///
+/// This returns `None` if the request doesn't have Message-Authenticator.
+pub fn verify_request(request: &[u8], secret: &[u8]) -> Option<bool> {
+    if request.len() < RADIUS_PACKET_HEADER_LENGTH {
+        return None;
+    }
+    if has_zero_authenticator(Code::from(request[0])) {
+        verify(request, &[0; 16], secret)
+    } else {
+        verify(request, &request[4..RADIUS_PACKET_HEADER_LENGTH], secret)
+    }
+}
+
+/// Verify the Message-Authenticator of a received response with the request that it answers.
+///
+/// This returns `None` if the response doesn't have Message-Authenticator.
+pub fn verify_response(response: &[u8], request: &[u8], secret: &[u8]) -> Option<bool> {
+    if request.len() < RADIUS_PACKET_HEADER_LENGTH {
+        return None;
+    }
+    verify(response, &request[4..RADIUS_PACKET_HEADER_LENGTH], secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::code::Code;
+    use crate::core::message_authenticator::{verify_request, verify_response};
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+
+    #[test]
+    fn test_status_server() {
+        // RFC 5997 section 6
+        let request_datagram: Vec<u8> = vec![
+            0x0c, 0xda, 0x00, 0x26, 0x8a, 0x54, 0xf4, 0x68, 0x6f, 0xb3, 0x94, 0xc5, 0x28, 0x66,
+            0xe3, 0x02, 0x18, 0x5d, 0x06, 0x23, 0x50, 0x12, 0x5a, 0x66, 0x5e, 0x2e, 0x1e, 0x84,
+            0x11, 0xf3, 0xe2, 0x43, 0x82, 0x20, 0x97, 0xc8, 0x4f, 0xa3,
+        ];
+        assert_eq!(verify_request(&request_datagram, b"xyzzy5461"), Some(true));
+        assert_eq!(verify_request(&request_datagram, b"wrong"), Some(false));
+
+        let request = Packet::decode(&request_datagram, b"xyzzy5461").unwrap();
+        assert_eq!(request.encode().unwrap(), request_datagram);
+
+        let mut response = request.make_response_packet(Code::AccessAccept);
+        response.add_message_authenticator();
+        let response_datagram = response.encode().unwrap();
+        assert_eq!(
+            verify_response(&response_datagram, &request_datagram, b"xyzzy5461"),
+            Some(true)
+        );
+        assert!(Packet::is_authentic_response(
+            &response_datagram,
+            &request_datagram,
+            b"xyzzy5461"
+        ));
+    }
+
+    #[test]
+    fn test_sign() {
+        for code in [
+            Code::AccessRequest,
+            Code::AccountingRequest,
+            Code::CoARequest,
+        ] {
+            let mut request = Packet::new(code, b"secret");
+            rfc2865::add_user_name(&mut request, "alice");
+            request.add_message_authenticator();
+            let datagram = request.encode().unwrap();
+            assert_eq!(verify_request(&datagram, b"secret"), Some(true));
+            assert!(
+                code == Code::AccessRequest || Packet::is_authentic_request(&datagram, b"secret")
+            );
+        }
+
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        assert_eq!(verify_request(&request.encode().unwrap(), b"secret"), None);
+    }
+}
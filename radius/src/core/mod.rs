@@ -1,32 +1,111 @@
 //! RADIUS core implementation for server, client and application.
+//!
+//! The `rfcXXXX` modules are generated from the dictionaries. `rfc2865`, `rfc2866` and `rfc2869` are always available,
+//! since the codec and the sans-io core use them; each of the others is behind the feature of the same name
+//! (e.g. `rfc3580`), and `all-dictionaries` (a default feature) enables all of them. `rfc2548` (the Microsoft
+//! Vendor-Specific attributes) is written by hand, since its attributes are encapsulated in Vendor-Specific and
+//! MS-MPPE-Send-Key/Recv-Key have the salt encryption of their own, and so are `rfc6929` (the Extended and the Long
+//! Extended attributes), `rfc7499` (the fragmentation, whose attributes are Extended) and `rfc6218` (the keywrap
+//! attributes, whose keys are wrapped by AES Key Wrap).
 
+pub mod accounting;
+#[cfg(feature = "aruba")]
+pub mod aruba;
+pub mod attribute_set;
 pub(crate) mod attributes;
 pub mod avp;
+pub mod chap;
+#[cfg(feature = "cisco")]
+pub mod cisco;
+pub mod class;
 pub mod code;
+pub mod constant_time;
+pub mod debug_bundle;
+#[cfg(feature = "std")]
+pub mod deterministic;
+pub mod dictionary;
+pub mod diff;
+#[cfg(feature = "rfc5090")]
+pub mod digest;
+pub mod exchange;
+pub mod ipv6;
+#[cfg(feature = "juniper")]
+pub mod juniper;
+pub mod lint;
+pub mod message_authenticator;
+#[cfg(feature = "mikrotik")]
+pub mod mikrotik;
+#[cfg(feature = "mschap")]
+pub mod mschap;
 pub mod packet;
+pub mod packet_builder;
+pub(crate) mod random;
 pub mod request;
+#[cfg(feature = "rfc2548")]
+pub mod rfc2548;
 pub mod rfc2865;
 pub mod rfc2866;
+#[cfg(feature = "rfc2867")]
 pub mod rfc2867;
+#[cfg(feature = "rfc2868")]
 pub mod rfc2868;
 pub mod rfc2869;
+#[cfg(feature = "rfc3162")]
 pub mod rfc3162;
+#[cfg(feature = "rfc3576")]
 pub mod rfc3576;
+#[cfg(feature = "rfc3580")]
 pub mod rfc3580;
+#[cfg(feature = "rfc4072")]
 pub mod rfc4072;
+#[cfg(feature = "rfc4372")]
 pub mod rfc4372;
+#[cfg(feature = "rfc4603")]
 pub mod rfc4603;
+#[cfg(feature = "rfc4675")]
 pub mod rfc4675;
+#[cfg(feature = "rfc4818")]
 pub mod rfc4818;
+#[cfg(feature = "rfc4849")]
 pub mod rfc4849;
+#[cfg(feature = "rfc5090")]
 pub mod rfc5090;
+#[cfg(feature = "rfc5176")]
 pub mod rfc5176;
+#[cfg(feature = "rfc5580")]
+pub mod rfc5580;
+#[cfg(feature = "rfc5607")]
 pub mod rfc5607;
+#[cfg(feature = "rfc5904")]
 pub mod rfc5904;
+#[cfg(feature = "rfc6218")]
+pub mod rfc6218;
+#[cfg(feature = "rfc6519")]
 pub mod rfc6519;
+#[cfg(feature = "rfc6572")]
 pub mod rfc6572;
+#[cfg(feature = "rfc6677")]
 pub mod rfc6677;
+#[cfg(feature = "rfc6911")]
 pub mod rfc6911;
+#[cfg(feature = "rfc6929")]
+pub mod rfc6929;
+#[cfg(feature = "rfc7055")]
 pub mod rfc7055;
+#[cfg(feature = "rfc7155")]
 pub mod rfc7155;
+#[cfg(feature = "rfc7499")]
+pub mod rfc7499;
+pub mod sansio;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod state;
 pub mod tag;
+pub mod tlv;
+#[cfg(feature = "rfc2868")]
+pub mod tunnel;
+pub mod vsa;
+#[cfg(feature = "wispr")]
+pub mod wispr;
+#[cfg(feature = "zeroize")]
+pub mod zeroizing;
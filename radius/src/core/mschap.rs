@@ -0,0 +1,228 @@
+//! MS-CHAPv2 (RFC 2759) and the MPPE keys by that (RFC 3079).
+//!
+//! These are for the MS-CHAP2-Response of `radius::core::rfc2548` and for EAP-MSCHAPv2;
+//! MD4 and DES here are broken and never used for anything else.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use des::cipher::{BlockEncrypt, KeyInit};
+use des::Des;
+use md4::Md4;
+use sha1::{Digest, Sha1};
+
+const MAGIC1: &[u8] = b"Magic server to client signing constant";
+const MAGIC2: &[u8] = b"Pad to make it do more than one iteration";
+const MASTER_KEY_MAGIC: &[u8] = b"This is the MPPE Master Key";
+const CLIENT_SEND_MAGIC: &[u8] =
+    b"On the client side, this is the send key; on the server side, it is the receive key.";
+const SERVER_SEND_MAGIC: &[u8] =
+    b"On the client side, this is the receive key; on the server side, it is the send key.";
+
+/// Encrypt a block by DES with a 56-bit key (i.e. the key without the parity bits), as MS-CHAP does.
+fn des_encrypt(key: &[u8; 7], block: &[u8; 8]) -> [u8; 8] {
+    // spread the 56 bits to the upper 7 bits of each byte; the parity bits are ignored
+    let key56 = key.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+    let key64 = (0..8).fold(0u64, |acc, i| {
+        (acc << 8) | (((key56 >> (49 - 7 * i)) & 0x7f) << 1)
+    });
+
+    let mut block = (*block).into();
+    Des::new(&key64.to_be_bytes().into()).encrypt_block(&mut block);
+    block.into()
+}
+
+fn md4(data: &[u8]) -> [u8; 16] {
+    Md4::digest(data).into()
+}
+
+/// NtPasswordHash of RFC 2759 section 8.3, i.e. MD4 of the UTF-16LE password.
+pub fn nt_password_hash(password: &str) -> [u8; 16] {
+    let unicode: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    md4(&unicode)
+}
+
+/// ChallengeHash of RFC 2759 section 8.2; the user name is without the domain.
+fn challenge_hash(
+    peer_challenge: &[u8],
+    authenticator_challenge: &[u8],
+    user_name: &str,
+) -> [u8; 8] {
+    let digest = Sha1::new()
+        .chain_update(peer_challenge)
+        .chain_update(authenticator_challenge)
+        .chain_update(user_name.as_bytes())
+        .finalize();
+    let mut challenge = [0; 8];
+    challenge.copy_from_slice(&digest[..8]);
+    challenge
+}
+
+/// ChallengeResponse of RFC 2759 section 8.5, i.e. the challenge encrypted by the thirds of the padded hash.
+fn challenge_response(challenge: &[u8; 8], password_hash: &[u8; 16]) -> [u8; 24] {
+    let mut padded = [0; 21];
+    padded[..16].copy_from_slice(password_hash);
+    let mut response = [0; 24];
+    for i in 0..3 {
+        let mut key = [0; 7];
+        key.copy_from_slice(&padded[i * 7..i * 7 + 7]);
+        response[i * 8..i * 8 + 8].copy_from_slice(&des_encrypt(&key, challenge));
+    }
+    response
+}
+
+/// GenerateNTResponse of RFC 2759 section 8.1.
+pub fn generate_nt_response(
+    authenticator_challenge: &[u8],
+    peer_challenge: &[u8],
+    user_name: &str,
+    password: &str,
+) -> [u8; 24] {
+    let challenge = challenge_hash(peer_challenge, authenticator_challenge, user_name);
+    challenge_response(&challenge, &nt_password_hash(password))
+}
+
+/// GenerateAuthenticatorResponse of RFC 2759 section 8.7, i.e. `S=` and the 40 hexadecimal digits.
+pub fn generate_authenticator_response(
+    password: &str,
+    nt_response: &[u8],
+    peer_challenge: &[u8],
+    authenticator_challenge: &[u8],
+    user_name: &str,
+) -> String {
+    let password_hash_hash = md4(&nt_password_hash(password));
+    let digest = Sha1::new()
+        .chain_update(password_hash_hash)
+        .chain_update(nt_response)
+        .chain_update(MAGIC1)
+        .finalize();
+    let challenge = challenge_hash(peer_challenge, authenticator_challenge, user_name);
+    let digest = Sha1::new()
+        .chain_update(digest)
+        .chain_update(challenge)
+        .chain_update(MAGIC2)
+        .finalize();
+    format!("S={}", to_hex(&digest))
+}
+
+pub(crate) fn to_hex(bs: &[u8]) -> String {
+    bs.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+/// Returns the 128-bit MPPE keys of the server, i.e. MS-MPPE-Send-Key and MS-MPPE-Recv-Key (RFC 3079 section 3.3),
+/// by the password and the NT-Response of the peer.
+pub fn generate_mppe_keys(password: &str, nt_response: &[u8]) -> ([u8; 16], [u8; 16]) {
+    let password_hash_hash = md4(&nt_password_hash(password));
+    let digest = Sha1::new()
+        .chain_update(password_hash_hash)
+        .chain_update(nt_response)
+        .chain_update(MASTER_KEY_MAGIC)
+        .finalize();
+    let master_key = &digest[..16];
+    (
+        asymmetric_start_key(master_key, SERVER_SEND_MAGIC),
+        asymmetric_start_key(master_key, CLIENT_SEND_MAGIC),
+    )
+}
+
+/// GetAsymmetricStartKey of RFC 3079 section 3.4 for the 128-bit key.
+fn asymmetric_start_key(master_key: &[u8], magic: &[u8]) -> [u8; 16] {
+    let digest = Sha1::new()
+        .chain_update(master_key)
+        .chain_update([0; 40])
+        .chain_update(magic)
+        .chain_update([0xf2; 40])
+        .finalize();
+    let mut key = [0; 16];
+    key.copy_from_slice(&digest[..16]);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::mschap::{
+        challenge_hash, des_encrypt, generate_authenticator_response, generate_mppe_keys,
+        generate_nt_response, nt_password_hash,
+    };
+
+    #[test]
+    fn test_des_encrypt() {
+        // the key 0x133457799BBCDFF1 without the parity bits
+        let key = [0x13, 0x34, 0x57, 0x79, 0x9b, 0xbc, 0xdf, 0xf1];
+        let key56 = key
+            .iter()
+            .fold(0u64, |acc, &b| (acc << 7) | (b >> 1) as u64);
+        let mut key7 = [0; 7];
+        key7.copy_from_slice(&key56.to_be_bytes()[1..]);
+        assert_eq!(
+            des_encrypt(&key7, &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]),
+            [0x85, 0xe8, 0x13, 0x54, 0x0f, 0x0a, 0xb4, 0x05]
+        );
+    }
+
+    #[test]
+    fn test_rfc2759_vectors() {
+        // RFC 2759 section 9.2
+        let authenticator_challenge = [
+            0x5b, 0x5d, 0x7c, 0x7d, 0x7b, 0x3f, 0x2f, 0x3e, 0x3c, 0x2c, 0x60, 0x21, 0x32, 0x26,
+            0x26, 0x28,
+        ];
+        let peer_challenge = [
+            0x21, 0x40, 0x23, 0x24, 0x25, 0x5e, 0x26, 0x2a, 0x28, 0x29, 0x5f, 0x2b, 0x3a, 0x33,
+            0x7c, 0x7e,
+        ];
+
+        assert_eq!(
+            challenge_hash(&peer_challenge, &authenticator_challenge, "User"),
+            [0xd0, 0x2e, 0x43, 0x86, 0xbc, 0xe9, 0x12, 0x26]
+        );
+        assert_eq!(
+            nt_password_hash("clientPass"),
+            [
+                0x44, 0xeb, 0xba, 0x8d, 0x53, 0x12, 0xb8, 0xd6, 0x11, 0x47, 0x44, 0x11, 0xf5, 0x69,
+                0x89, 0xae
+            ]
+        );
+        let nt_response = generate_nt_response(
+            &authenticator_challenge,
+            &peer_challenge,
+            "User",
+            "clientPass",
+        );
+        assert_eq!(
+            nt_response,
+            [
+                0x82, 0x30, 0x9e, 0xcd, 0x8d, 0x70, 0x8b, 0x5e, 0xa0, 0x8f, 0xaa, 0x39, 0x81, 0xcd,
+                0x83, 0x54, 0x42, 0x33, 0x11, 0x4a, 0x3d, 0x85, 0xd6, 0xdf
+            ]
+        );
+        assert_eq!(
+            generate_authenticator_response(
+                "clientPass",
+                &nt_response,
+                &peer_challenge,
+                &authenticator_challenge,
+                "User"
+            ),
+            "S=407A5589115FD0D6209F510FE9C04566932CDA56"
+        );
+
+        // RFC 3079 section 3.5.3
+        let (send_key, recv_key) = generate_mppe_keys("clientPass", &nt_response);
+        assert_eq!(
+            send_key,
+            [
+                0x8b, 0x7c, 0xdc, 0x14, 0x9b, 0x99, 0x3a, 0x1b, 0xa1, 0x18, 0xcb, 0x15, 0x3f, 0x56,
+                0xdc, 0xcb
+            ]
+        );
+        assert_eq!(
+            recv_key,
+            [
+                0xd5, 0xf0, 0xe9, 0x52, 0x1e, 0x3e, 0xa9, 0x58, 0x96, 0x45, 0xe8, 0x60, 0x51, 0xc8,
+                0x22, 0x26
+            ]
+        );
+    }
+}
@@ -1,4 +1,9 @@
-use std::convert::TryInto;
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
 
 use rand::Rng;
 use thiserror::Error;
@@ -6,9 +11,18 @@ use thiserror::Error;
 use crate::core::attributes::Attributes;
 use crate::core::avp::{AVPType, AVP};
 use crate::core::code::Code;
+use crate::core::constant_time;
+use crate::core::dictionary;
+use crate::core::lint;
+use crate::core::message_authenticator;
+use crate::core::packet_builder::PacketBuilder;
+use crate::core::random;
+use crate::core::rfc2865;
+use crate::core::rfc2869;
+use crate::error::ErrorKind;
 
-const MAX_PACKET_LENGTH: usize = 4096;
-const RADIUS_PACKET_HEADER_LENGTH: usize = 20; // i.e. minimum packet length
+pub(crate) const MAX_PACKET_LENGTH: usize = 4096;
+pub(crate) const RADIUS_PACKET_HEADER_LENGTH: usize = 20; // i.e. minimum packet length
 
 #[derive(Error, Debug, PartialEq)]
 pub enum PacketError {
@@ -32,11 +46,31 @@ pub enum PacketError {
     #[error("failed to encode the packet: {0}")]
     EncodingError(String),
 
+    /// An error that is raised when the buffer to encode a packet into is shorter than the packet.
+    #[error("the buffer has {1} bytes, but the encoded packet has {0} bytes")]
+    InsufficientBufferLengthError(usize, usize),
+
     /// An error that is raised when it received unknown packet type code of RADIUS.
     #[error("Unknown RADIUS packet type code: {0}")]
     UnknownCodeError(String),
 }
 
+impl PacketError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            PacketError::EncodingError(_) | PacketError::InsufficientBufferLengthError(_, _) => {
+                ErrorKind::Encoding
+            }
+            PacketError::InsufficientPacketPayloadLengthError(_, _)
+            | PacketError::InsufficientHeaderDefinedPacketLengthError(_, _)
+            | PacketError::HeaderDefinedPacketLengthExceedsMaximumLimitError(_, _)
+            | PacketError::DecodingError(_)
+            | PacketError::UnknownCodeError(_) => ErrorKind::Malformed,
+        }
+    }
+}
+
 /// This struct represents a packet of RADIUS for request and response.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Packet {
@@ -64,7 +98,7 @@ impl Packet {
     }
 
     fn _new(code: Code, secret: &[u8], maybe_identifier: Option<u8>) -> Self {
-        let mut rng = rand::thread_rng();
+        let mut rng = random::rng();
         let authenticator = (0..16).map(|_| rng.gen()).collect::<Vec<u8>>();
         Packet {
             code: code.to_owned(),
@@ -99,8 +133,55 @@ impl Packet {
         self.identifier = identifier;
     }
 
+    /// Set the shared secret, e.g. of a deserialized packet (see `radius::core::serialization`).
+    pub fn set_secret(&mut self, secret: &[u8]) {
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut self.secret);
+        self.secret = secret.to_owned();
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn set_authenticator(&mut self, authenticator: &[u8]) {
+        self.authenticator = authenticator.to_owned();
+    }
+
+    /// Returns a fluent builder of a packet of the code, which validates the packet by `build()`
+    /// (see `radius::core::packet_builder`).
+    pub fn builder(code: Code, secret: &[u8]) -> PacketBuilder {
+        PacketBuilder::new(code, secret)
+    }
+
     /// This decodes bytes into a Packet.
+    ///
+    /// With the `tracing` feature, this runs in a `radius.packet.decode` span and emits a trace event of the code and
+    /// the identifier (or the error); the attributes aren't traced.
     pub fn decode(bs: &[u8], secret: &[u8]) -> Result<Self, PacketError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("radius.packet.decode", length = bs.len()).entered();
+        let result = Self::decode_datagram(bs, secret);
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(packet) => tracing::trace!(
+                code = packet.code.string(),
+                identifier = packet.identifier,
+                "decoded a packet"
+            ),
+            Err(e) => tracing::trace!(error = %e, "failed to decode a packet"),
+        }
+        result
+    }
+
+    /// This decodes bytes into a Packet without the shared secret, e.g. for the sniffers, the loggers and the test
+    /// tools that inspect the traffic they can't authenticate.
+    ///
+    /// The structure and the attributes are decoded as `decode()` does, and the authenticator isn't verified. The
+    /// packet has the empty secret, so the encrypted attributes can't be decrypted (e.g. `rfc2865::lookup_user_password()`
+    /// returns `PasswordSecretMissingError`) until the secret is given by `set_secret()`.
+    pub fn decode_unverified(bs: &[u8]) -> Result<Self, PacketError> {
+        Self::decode(bs, &[])
+    }
+
+    fn decode_datagram(bs: &[u8], secret: &[u8]) -> Result<Self, PacketError> {
         if bs.len() < RADIUS_PACKET_HEADER_LENGTH {
             return Err(PacketError::InsufficientPacketPayloadLengthError(
                 RADIUS_PACKET_HEADER_LENGTH,
@@ -148,59 +229,55 @@ impl Packet {
     }
 
     /// This method makes a response packet according to self (i.e. request packet).
+    ///
+    /// If the request has Message-Authenticator, the response has that as well (see `add_message_authenticator()`).
+    /// The Proxy-State attributes of the request are copied into the response unmodified and in the same order,
+    /// as RFC 2865 requires; use `make_response_packet_without_proxy_state()` to opt out of that.
+    ///
+    /// see also: https://tools.ietf.org/html/rfc2865#section-5.33
     pub fn make_response_packet(&self, code: Code) -> Self {
-        Packet {
+        let mut response = self.make_response_packet_without_proxy_state(code);
+        for avp in self.lookup_all(rfc2865::PROXY_STATE_TYPE) {
+            response.add(avp.clone());
+        }
+        response
+    }
+
+    /// This method makes a response packet according to self (i.e. request packet) like `make_response_packet()`,
+    /// but it doesn't copy the Proxy-State attributes of the request, e.g. for a proxy that echoes those of
+    /// the upstream response instead.
+    pub fn make_response_packet_without_proxy_state(&self, code: Code) -> Self {
+        let mut response = Packet {
             code,
             identifier: self.identifier,
             authenticator: self.authenticator.clone(),
             secret: self.secret.clone(),
             attributes: Attributes(vec![]),
+        };
+        if rfc2869::lookup_message_authenticator(self).is_some() {
+            response.add_message_authenticator();
         }
+        response
     }
 
     /// This method encodes the Packet into bytes.
     pub fn encode(&self) -> Result<Vec<u8>, PacketError> {
-        let mut bs = match self.marshal_binary() {
-            Ok(bs) => bs,
-            Err(e) => return Err(PacketError::EncodingError(e)),
-        };
+        let mut bs = vec![0; self.encoded_len()?];
+        self.encode_into(&mut bs)?;
+        Ok(bs)
+    }
 
-        match self.code {
-            Code::AccessRequest | Code::StatusServer => Ok(bs),
-            Code::AccessAccept
-            | Code::AccessReject
-            | Code::AccountingRequest
-            | Code::AccountingResponse
-            | Code::AccessChallenge
-            | Code::DisconnectRequest
-            | Code::DisconnectACK
-            | Code::DisconnectNAK
-            | Code::CoARequest
-            | Code::CoAACK
-            | Code::CoANAK => {
-                let mut buf: Vec<u8> = bs[..4].to_vec();
-                match self.code {
-                    Code::AccountingRequest // see "Request Authenticator" in https://tools.ietf.org/html/rfc2866#section-3
-                    | Code::DisconnectRequest // same as "RFC2866"; https://tools.ietf.org/html/rfc5176#section-2.3
-                    | Code::CoARequest // same as "RFC2866"; https://tools.ietf.org/html/rfc5176#section-2.3
-                    => {
-                        buf.extend(vec![
-                            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-                            0x00, 0x00, 0x00, 0x00,
-                        ]);
-                    }
-                    _ => {
-                        buf.extend(self.authenticator.clone()); // TODO take from `bs`?
-                    }
-                }
-                buf.extend(bs[RADIUS_PACKET_HEADER_LENGTH..].to_vec());
-                buf.extend(&self.secret);
-                bs.splice(4..20, md5::compute(&buf).to_vec());
-
-                Ok(bs)
-            }
-            _ => Err(PacketError::UnknownCodeError(format!("{:?}", self.code))),
+    /// Returns the length of the encoded packet, i.e. the size of the buffer that `encode_into()` needs.
+    pub fn encoded_len(&self) -> Result<usize, PacketError> {
+        let size = RADIUS_PACKET_HEADER_LENGTH
+            + self
+                .attributes
+                .encoded_len()
+                .map_err(PacketError::EncodingError)?;
+        if size > MAX_PACKET_LENGTH {
+            return Err(PacketError::EncodingError("packet is too large".to_owned()));
         }
+        Ok(size)
     }
 
     /*
@@ -218,27 +295,60 @@ impl Packet {
      *  |  Attributes ...
      *  +-+-+-+-+-+-+-+-+-+-+-+-+-
      */
-    fn marshal_binary(&self) -> Result<Vec<u8>, String> {
-        let encoded_avp = match self.attributes.encode() {
-            Ok(encoded) => encoded,
-            Err(e) => return Err(e),
+    /// This method encodes the Packet into the buffer, and returns the length of the encoded packet.
+    ///
+    /// This is `encode()` without the allocation, e.g. to reuse a buffer on the hot paths; the buffer has to have
+    /// `encoded_len()` bytes at least.
+    pub fn encode_into(&self, buf: &mut [u8]) -> Result<usize, PacketError> {
+        let size = self.encoded_len()?;
+        let zero_authenticator = match self.code {
+            Code::AccessRequest | Code::StatusServer => None,
+            Code::AccountingRequest // see "Request Authenticator" in https://tools.ietf.org/html/rfc2866#section-3
+            | Code::DisconnectRequest // same as "RFC2866"; https://tools.ietf.org/html/rfc5176#section-2.3
+            | Code::CoARequest // same as "RFC2866"; https://tools.ietf.org/html/rfc5176#section-2.3
+            => Some(true),
+            Code::AccessAccept
+            | Code::AccessReject
+            | Code::AccountingResponse
+            | Code::AccessChallenge
+            | Code::DisconnectACK
+            | Code::DisconnectNAK
+            | Code::CoAACK
+            | Code::CoANAK => Some(false),
+            _ => return Err(PacketError::UnknownCodeError(format!("{:?}", self.code))),
         };
+        if buf.len() < size {
+            return Err(PacketError::InsufficientBufferLengthError(size, buf.len()));
+        }
 
-        let size = RADIUS_PACKET_HEADER_LENGTH as u16 + encoded_avp.len() as u16;
-        if size as usize > MAX_PACKET_LENGTH {
-            return Err("packet is too large".to_owned());
+        let bs = &mut buf[..size];
+        bs[0] = self.code as u8;
+        bs[1] = self.identifier;
+        bs[2..4].copy_from_slice(&(size as u16).to_be_bytes());
+        bs[4..RADIUS_PACKET_HEADER_LENGTH].copy_from_slice(&self.authenticator);
+        self.attributes
+            .encode_into(&mut bs[RADIUS_PACKET_HEADER_LENGTH..]);
+        message_authenticator::sign(bs, &self.secret);
+
+        if let Some(zero_authenticator) = zero_authenticator {
+            let mut context = md5::Context::new();
+            context.consume(&bs[..4]);
+            if zero_authenticator {
+                context.consume([0; 16]);
+            } else {
+                context.consume(&self.authenticator);
+            }
+            context.consume(&bs[RADIUS_PACKET_HEADER_LENGTH..]);
+            context.consume(&self.secret);
+            bs[4..RADIUS_PACKET_HEADER_LENGTH].copy_from_slice(&context.compute().0);
         }
+        Ok(size)
+    }
 
-        let mut bs: Vec<u8> = Vec::new();
-        bs.push(self.code as u8);
-        bs.push(self.identifier);
-        bs.extend(u16::to_be_bytes(size).to_vec());
-        bs.extend(self.authenticator.to_vec());
-        bs.extend(match self.attributes.encode() {
-            Ok(encoded) => encoded,
-            Err(e) => return Err(e),
-        });
-        Ok(bs)
+    /// This method encodes the response of `make_response_packet()` into the buffer, and returns the length of
+    /// the encoded response (see `encode_into()`).
+    pub fn encode_response_into(&self, code: Code, buf: &mut [u8]) -> Result<usize, PacketError> {
+        self.make_response_packet(code).encode_into(buf)
     }
 
     /// Returns whether the Packet is authentic response or not.
@@ -250,17 +360,15 @@ impl Packet {
             return false;
         }
 
-        md5::compute(
-            [
-                &response[..4],
-                &request[4..RADIUS_PACKET_HEADER_LENGTH],
-                &response[RADIUS_PACKET_HEADER_LENGTH..],
-                secret,
-            ]
-            .concat(),
+        let mut context = md5::Context::new();
+        context.consume(&response[..4]);
+        context.consume(&request[4..RADIUS_PACKET_HEADER_LENGTH]);
+        context.consume(&response[RADIUS_PACKET_HEADER_LENGTH..]);
+        context.consume(secret);
+        constant_time::eq(
+            &context.compute().0,
+            &response[4..RADIUS_PACKET_HEADER_LENGTH],
         )
-        .to_vec()
-        .eq(&response[4..RADIUS_PACKET_HEADER_LENGTH].to_vec())
     }
 
     /// Returns whether the Packet is authentic request or not.
@@ -271,24 +379,107 @@ impl Packet {
 
         match Code::from(request[0]) {
             Code::AccessRequest | Code::StatusServer => true,
-            Code::AccountingRequest | Code::DisconnectRequest | Code::CoARequest => md5::compute(
-                [
-                    &request[..4],
-                    &[
-                        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-                        0x00, 0x00, 0x00, 0x00,
-                    ],
-                    &request[RADIUS_PACKET_HEADER_LENGTH..],
-                    secret,
-                ]
-                .concat(),
-            )
-            .to_vec()
-            .eq(&request[4..RADIUS_PACKET_HEADER_LENGTH].to_vec()),
+            Code::AccountingRequest | Code::DisconnectRequest | Code::CoARequest => {
+                let mut context = md5::Context::new();
+                context.consume(&request[..4]);
+                context.consume([0x00; 16]);
+                context.consume(&request[RADIUS_PACKET_HEADER_LENGTH..]);
+                context.consume(secret);
+                constant_time::eq(
+                    &context.compute().0,
+                    &request[4..RADIUS_PACKET_HEADER_LENGTH],
+                )
+            }
             _ => false,
         }
     }
 
+    /// Validate a received datagram strictly, and return all the violations of the RFCs in that, e.g. the Length
+    /// that doesn't match the datagram, the attribute values of the wrong lengths and EAP-Message without
+    /// Message-Authenticator; it returns an empty `Vec` if the datagram has no problem.
+    ///
+    /// This is `radius::core::lint::lint_datagram()`; `decode()` returns only the first decoding error.
+    pub fn validate(bs: &[u8]) -> Vec<lint::Diagnostic> {
+        lint::lint_datagram(bs)
+    }
+
+    /// Returns whether a decoded request (e.g. received through a transport of the application) is authentic, i.e.
+    /// the Request Authenticator of the Accounting-Request, CoA-Request and Disconnect-Request
+    /// (see `is_authentic_request()`) and Message-Authenticator if the request has that.
+    pub fn verify_request_authenticator(&self, secret: &[u8]) -> bool {
+        match self.encode_as_received() {
+            Some(datagram) => {
+                Self::is_authentic_request(&datagram, secret)
+                    && message_authenticator::verify_request(&datagram, secret).unwrap_or(true)
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether a decoded response is authentic for the request, i.e. the Response Authenticator
+    /// (see `is_authentic_response()`) and Message-Authenticator if the response has that, by the secret of the
+    /// response.
+    pub fn verify_response_authenticator(&self, request: &Packet) -> bool {
+        match self.encode_as_received() {
+            Some(datagram) => {
+                let request_header = [&[0; 4], &request.authenticator[..]].concat();
+                Self::is_authentic_response(&datagram, &request_header, &self.secret)
+                    && message_authenticator::verify_response(
+                        &datagram,
+                        &request_header,
+                        &self.secret,
+                    )
+                    .unwrap_or(true)
+            }
+            None => false,
+        }
+    }
+
+    /// Encodes the packet with the authenticator as is, i.e. the datagram that the decoded packet came from.
+    fn encode_as_received(&self) -> Option<Vec<u8>> {
+        let size = self.encoded_len().ok()?;
+        let mut bs = vec![0; size];
+        bs[0] = self.code as u8;
+        bs[1] = self.identifier;
+        bs[2..4].copy_from_slice(&(size as u16).to_be_bytes());
+        bs[4..RADIUS_PACKET_HEADER_LENGTH].copy_from_slice(self.authenticator.get(..16)?);
+        self.attributes
+            .encode_into(&mut bs[RADIUS_PACKET_HEADER_LENGTH..]);
+        Some(bs)
+    }
+
+    /// Add a Message-Authenticator as the first attribute (replacing the existing one), whose value is computed by `encode()`.
+    ///
+    /// It is the first one against the forgery by the MD5 collision (CVE-2024-3596, a.k.a. Blast-RADIUS).
+    ///
+    /// see also: `radius::core::message_authenticator`
+    pub fn add_message_authenticator(&mut self) {
+        rfc2869::delete_message_authenticator(self);
+        self.attributes.add_first(AVP::from_bytes(
+            rfc2869::MESSAGE_AUTHENTICATOR_TYPE,
+            &[0; message_authenticator::MESSAGE_AUTHENTICATOR_LENGTH],
+        ));
+    }
+
+    /// Add an EAP packet (replacing the existing one), split into the EAP-Message attributes of at most 253 bytes
+    /// (RFC 3579 section 3.1).
+    ///
+    /// This adds a Message-Authenticator as well unless the packet has that, because a packet with EAP-Message must have that.
+    pub fn add_eap_message(&mut self, eap_message: &[u8]) {
+        rfc2869::delete_eap_message(self);
+        rfc2869::add_eap_message(self, eap_message);
+        if rfc2869::lookup_message_authenticator(self).is_none() {
+            self.add_message_authenticator();
+        }
+    }
+
+    /// Returns the EAP packet, i.e. the concatenation of the values of the EAP-Message attributes in the order of the packet.
+    ///
+    /// This is empty if the packet doesn't have EAP-Message.
+    pub fn get_eap_message(&self) -> Vec<u8> {
+        rfc2869::lookup_eap_message(self).unwrap_or_default()
+    }
+
     /// Add an AVP to the list of AVPs.
     pub fn add(&mut self, avp: AVP) {
         self.attributes.add(avp);
@@ -299,11 +490,36 @@ impl Packet {
         self.attributes.extend(avps)
     }
 
+    /// Insert an AVP at the position of the list of AVPs, e.g. before the other attributes for a server that
+    /// requires the order.
+    ///
+    /// This panics if the index is greater than the number of the AVPs.
+    pub fn insert(&mut self, index: usize, avp: AVP) {
+        self.attributes.insert(index, avp);
+    }
+
+    /// Replace the first AVP of the type of the given AVP by that in place, or add that if there are no AVPs of
+    /// the type; the other AVPs of the type are kept.
+    pub fn set(&mut self, avp: AVP) {
+        self.attributes.set(avp);
+    }
+
     /// Delete all of AVPs from the list according to given AVP type.
     pub fn delete(&mut self, typ: AVPType) {
         self.attributes.del(typ);
     }
 
+    /// Delete all of AVPs of the type like `delete()`, and returns them in the order of the packet (e.g. to move them
+    /// to another packet).
+    pub fn delete_all(&mut self, typ: AVPType) -> Vec<AVP> {
+        self.attributes.take_all(typ)
+    }
+
+    /// Keep only the AVPs that satisfy the predicate in the order (e.g. to delete the attributes of a vendor).
+    pub fn retain_attributes<F: FnMut(&AVP) -> bool>(&mut self, f: F) {
+        self.attributes.retain(f);
+    }
+
     /// Returns an AVP that matches at first with the given AVP type. If there are not any matched ones, this returns `None`.
     pub fn lookup(&self, typ: AVPType) -> Option<&AVP> {
         self.attributes.lookup(typ)
@@ -313,18 +529,222 @@ impl Packet {
     pub fn lookup_all(&self, typ: AVPType) -> Vec<&AVP> {
         self.attributes.lookup_all(typ)
     }
+
+    /// Returns the all AVPs in the order of the packet.
+    pub fn get_attributes(&self) -> &[AVP] {
+        &self.attributes.0
+    }
+
+    /// Returns an index of the AVPs by the types, for the handlers that read many attributes of a large packet
+    /// (see `AttributeIndex`).
+    pub fn index(&self) -> AttributeIndex<'_> {
+        AttributeIndex::new(&self.attributes.0)
+    }
+
+    /// Returns the AVPs that the built-in dictionary doesn't define (see `dictionary::is_known_attribute()`) in the
+    /// order of the packet.
+    ///
+    /// They are kept as they are on the wire, so `AVP::get_type()`, `AVP::get_vendor_id()` and `AVP::encode_raw()`
+    /// introspect them.
+    pub fn get_unknown_attributes(&self) -> Vec<&AVP> {
+        self.attributes
+            .0
+            .iter()
+            .filter(|avp| !dictionary::is_known_attribute(avp))
+            .collect()
+    }
+
+    /// Copy the unknown AVPs to another packet (e.g. to the proxied request or to the response) unmodified.
+    pub fn copy_unknown_attributes(&self, packet: &mut Packet) {
+        packet.extend(self.get_unknown_attributes().into_iter().cloned().collect());
+    }
+
+    /// Delete all of the unknown AVPs.
+    pub fn delete_unknown_attributes(&mut self) {
+        self.attributes.retain(dictionary::is_known_attribute);
+    }
+}
+
+/// The shared secret is wiped from the memory when the packet is dropped (see `radius::core::zeroizing`).
+#[cfg(feature = "zeroize")]
+impl Drop for Packet {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.secret);
+    }
+}
+
+/// An index of the AVPs of a packet by the types.
+///
+/// `Packet::lookup()` and the `lookup_*()` functions of the dictionary modules scan the AVPs on every call; this is built
+/// once in O(n log n), and then looks the AVPs of a type up in O(1). The typed values are decoded by the methods of
+/// `AVP` (e.g. `encode_string()`). Since this borrows the packet, the packet can't be modified while this is alive.
+///
+/// ```
+/// use radius::core::code::Code;
+/// use radius::core::packet::Packet;
+/// use radius::core::rfc2865;
+///
+/// let mut packet = Packet::new(Code::AccessRequest, b"secret");
+/// rfc2865::add_user_name(&mut packet, "alice");
+/// let index = packet.index();
+/// let user_name = index.lookup(rfc2865::USER_NAME_TYPE).unwrap().encode_string().unwrap();
+/// assert_eq!(user_name, "alice");
+/// ```
+#[derive(Debug, Clone)]
+pub struct AttributeIndex<'a> {
+    // the AVPs by the types in the order of the packet; the ones of a type are `avps[offsets[typ]..offsets[typ + 1]]`
+    avps: Vec<&'a AVP>,
+    offsets: [usize; 257],
+}
+
+impl<'a> AttributeIndex<'a> {
+    fn new(attributes: &'a [AVP]) -> Self {
+        let mut avps: Vec<&AVP> = attributes.iter().collect();
+        // the sort is stable, i.e. it keeps the order of the packet
+        avps.sort_by_key(|avp| avp.typ);
+        let mut offsets = [0; 257];
+        for avp in &avps {
+            offsets[avp.typ as usize + 1] += 1;
+        }
+        for typ in 1..offsets.len() {
+            offsets[typ] += offsets[typ - 1];
+        }
+        AttributeIndex { avps, offsets }
+    }
+
+    /// Returns the first AVP of the type, i.e. the same as `Packet::lookup()`.
+    pub fn lookup(&self, typ: AVPType) -> Option<&'a AVP> {
+        self.lookup_all(typ).first().copied()
+    }
+
+    /// Returns the AVPs of the type in the order of the packet, i.e. the same as `Packet::lookup_all()`.
+    pub fn lookup_all(&self, typ: AVPType) -> &[&'a AVP] {
+        &self.avps[self.offsets[typ as usize]..self.offsets[typ as usize + 1]]
+    }
+
+    /// Returns whether the packet has an AVP of the type.
+    pub fn contains(&self, typ: AVPType) -> bool {
+        self.offsets[typ as usize] < self.offsets[typ as usize + 1]
+    }
+
+    /// Returns the number of the AVPs.
+    pub fn len(&self) -> usize {
+        self.avps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.avps.is_empty()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::net::Ipv4Addr;
+    use core::net::Ipv4Addr;
 
-    use crate::core::avp::AVP;
+    use crate::core::avp::{AVPError, AVP};
     use crate::core::code::Code;
+    use crate::core::message_authenticator::verify_request;
     use crate::core::packet::{
         Packet, PacketError, MAX_PACKET_LENGTH, RADIUS_PACKET_HEADER_LENGTH,
     };
     use crate::core::rfc2865;
+    #[cfg(feature = "rfc2868")]
+    use crate::core::rfc2868;
+    use crate::core::rfc2869;
+    #[cfg(feature = "rfc3576")]
+    use crate::core::rfc3576;
+    #[cfg(feature = "rfc2868")]
+    use crate::core::tag::Tag;
+    use crate::core::vsa;
+
+    #[test]
+    fn test_attribute_mutation() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, "alice");
+        rfc2865::add_reply_message(&mut packet, "first");
+        rfc2865::add_reply_message(&mut packet, "second");
+        rfc2865::add_nas_identifier(&mut packet, "nas");
+
+        packet.set(AVP::from_string(rfc2865::REPLY_MESSAGE_TYPE, "replaced"));
+        packet.set(AVP::from_string(rfc2865::CALLED_STATION_ID_TYPE, "added"));
+        packet.insert(
+            0,
+            AVP::from_string(rfc2865::CALLING_STATION_ID_TYPE, "head"),
+        );
+        let types: Vec<u8> = packet.get_attributes().iter().map(AVP::get_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                rfc2865::CALLING_STATION_ID_TYPE,
+                rfc2865::USER_NAME_TYPE,
+                rfc2865::REPLY_MESSAGE_TYPE,
+                rfc2865::REPLY_MESSAGE_TYPE,
+                rfc2865::NAS_IDENTIFIER_TYPE,
+                rfc2865::CALLED_STATION_ID_TYPE,
+            ]
+        );
+        assert_eq!(
+            rfc2865::lookup_all_reply_message(&packet).unwrap(),
+            vec!["replaced", "second"]
+        );
+
+        let deleted = packet.delete_all(rfc2865::REPLY_MESSAGE_TYPE);
+        assert_eq!(deleted.len(), 2);
+        assert_eq!(deleted[1].encode_string().unwrap(), "second");
+        assert!(packet.lookup(rfc2865::REPLY_MESSAGE_TYPE).is_none());
+
+        packet.retain_attributes(|avp| avp.get_type() != rfc2865::NAS_IDENTIFIER_TYPE);
+        assert_eq!(packet.get_attributes().len(), 3);
+        assert_eq!(
+            rfc2865::lookup_user_name(&packet).unwrap().unwrap(),
+            "alice"
+        );
+    }
+
+    #[test]
+    fn test_decode_unverified() {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        rfc2865::add_user_password(&mut request, b"p@ssw0rd").unwrap();
+        let encoded = request.encode().unwrap();
+
+        let mut decoded = Packet::decode_unverified(&encoded).unwrap();
+        assert_eq!(decoded.get_identifier(), request.get_identifier());
+        assert!(decoded.get_secret().is_empty());
+        assert_eq!(
+            rfc2865::lookup_user_name(&decoded).unwrap().unwrap(),
+            "alice"
+        );
+        assert_eq!(
+            rfc2865::lookup_user_password(&decoded).unwrap(),
+            Err(AVPError::PasswordSecretMissingError())
+        );
+
+        decoded.set_secret(b"secret");
+        assert_eq!(
+            rfc2865::lookup_user_password(&decoded).unwrap().unwrap(),
+            b"p@ssw0rd"
+        );
+        assert!(Packet::decode_unverified(&encoded[..10]).is_err());
+    }
+
+    #[test]
+    fn test_make_response_packet_echoes_proxy_state() {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_proxy_state(&mut request, b"first");
+        rfc2865::add_user_name(&mut request, "alice");
+        rfc2865::add_proxy_state(&mut request, b"second");
+
+        let response = request.make_response_packet(Code::AccessAccept);
+        assert_eq!(
+            rfc2865::lookup_all_proxy_state(&response),
+            vec![b"first".to_vec(), b"second".to_vec()]
+        );
+        assert!(rfc2865::lookup_user_name(&response).is_none());
+
+        let response = request.make_response_packet_without_proxy_state(Code::AccessAccept);
+        assert!(rfc2865::lookup_all_proxy_state(&response).is_empty());
+    }
 
     #[test]
     fn test_for_rfc2865_7_1() -> Result<(), PacketError> {
@@ -506,6 +926,30 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "rfc2868")]
+    #[test]
+    fn test_tunnel_password() {
+        let secret = b"xyzzy5461";
+        let request = Packet::new(Code::AccessRequest, secret);
+        let tag = Tag::new(1);
+
+        // the response by make_response_packet() encrypts by the Request Authenticator
+        let mut response = request.make_response_packet(Code::AccessAccept);
+        rfc2868::add_tunnel_password(&mut response, Some(&tag), b"vlan-key").unwrap();
+        assert_eq!(
+            rfc2868::lookup_tunnel_password(&response).unwrap().unwrap(),
+            (b"vlan-key".to_vec(), Tag::new(1))
+        );
+
+        let decoded = Packet::decode(&response.encode().unwrap(), secret).unwrap();
+        let avp = decoded.lookup(rfc2868::TUNNEL_PASSWORD_TYPE).unwrap();
+        assert_eq!(
+            avp.encode_tunnel_password(secret, request.get_authenticator())
+                .unwrap(),
+            (b"vlan-key".to_vec(), tag)
+        );
+    }
+
     #[test]
     fn test_parse_invalid() {
         struct TestCase<'a> {
@@ -549,7 +993,7 @@ mod tests {
         let mut packet = Packet::new(Code::AccessRequest, b"12345");
         packet.add(AVP {
             typ: 1,
-            value: vec![1; 253],
+            value: vec![1; 253].into(),
         });
         let encoded = packet.encode();
         assert!(encoded.is_ok());
@@ -557,7 +1001,7 @@ mod tests {
         let mut packet = Packet::new(Code::AccessRequest, b"12345");
         packet.add(AVP {
             typ: 1,
-            value: vec![1; 254],
+            value: vec![1; 254].into(),
         });
         let encoded = packet.encode();
         assert!(encoded.is_err());
@@ -567,6 +1011,153 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_into() {
+        let mut buf = [0; 4096];
+        for code in [
+            Code::AccessRequest,
+            Code::AccountingRequest,
+            Code::AccessAccept,
+        ] {
+            let mut packet = Packet::new(code, b"secret");
+            rfc2865::add_user_name(&mut packet, "alice");
+            packet.add_message_authenticator();
+            let encoded = packet.encode().unwrap();
+            assert_eq!(packet.encoded_len().unwrap(), encoded.len());
+            let len = packet.encode_into(&mut buf).unwrap();
+            assert_eq!(&buf[..len], encoded.as_slice());
+
+            assert_eq!(
+                packet.encode_into(&mut buf[..len - 1]),
+                Err(PacketError::InsufficientBufferLengthError(len, len - 1))
+            );
+        }
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        request.add_message_authenticator();
+        let len = request
+            .encode_response_into(Code::AccessReject, &mut buf)
+            .unwrap();
+        let response = request.make_response_packet(Code::AccessReject);
+        assert_eq!(&buf[..len], response.encode().unwrap().as_slice());
+        assert!(Packet::is_authentic_response(
+            &buf[..len],
+            &request.encode().unwrap(),
+            b"secret"
+        ));
+    }
+
+    #[test]
+    fn test_attribute_index() {
+        let mut packet = Packet::new(Code::AccessAccept, b"secret");
+        rfc2865::add_reply_message(&mut packet, "first");
+        rfc2865::add_session_timeout(&mut packet, 3600);
+        rfc2865::add_reply_message(&mut packet, "second");
+        packet.add(AVP {
+            typ: 255,
+            value: vec![1].into(),
+        });
+
+        let index = packet.index();
+        assert_eq!(index.len(), 4);
+        for typ in 0..=255 {
+            assert_eq!(index.lookup(typ), packet.lookup(typ));
+            assert_eq!(index.lookup_all(typ), packet.lookup_all(typ).as_slice());
+            assert_eq!(index.contains(typ), packet.lookup(typ).is_some());
+        }
+        let reply_messages: Vec<_> = index
+            .lookup_all(rfc2865::REPLY_MESSAGE_TYPE)
+            .iter()
+            .map(|avp| avp.encode_string().unwrap())
+            .collect();
+        assert_eq!(reply_messages, vec!["first", "second"]);
+        assert!(Packet::new(Code::AccessAccept, b"secret")
+            .index()
+            .is_empty());
+    }
+
+    #[cfg(all(feature = "rfc2868", feature = "rfc3576"))]
+    #[test]
+    fn test_value_enums() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_service_type(&mut packet, rfc2865::ServiceTypeValue::FramedUser.into());
+        // a value of RFC 3576 for the attribute of RFC 2865
+        rfc2865::add_service_type(&mut packet, rfc3576::SERVICE_TYPE_AUTHORIZE_ONLY);
+        rfc2868::add_tunnel_type(
+            &mut packet,
+            Some(&Tag::new(1)),
+            rfc2868::TunnelTypeValue::L2Tp.into(),
+        );
+
+        assert_eq!(
+            rfc2865::lookup_service_type_value(&packet)
+                .unwrap()
+                .unwrap(),
+            rfc2865::ServiceTypeValue::FramedUser
+        );
+        assert_eq!(
+            rfc2865::lookup_all_service_type_value(&packet).unwrap(),
+            vec![
+                rfc2865::ServiceTypeValue::FramedUser,
+                rfc2865::ServiceTypeValue::AuthorizeOnly
+            ]
+        );
+        assert_eq!(
+            rfc2868::lookup_tunnel_type_value(&packet).unwrap().unwrap(),
+            (rfc2868::TunnelTypeValue::L2Tp, Tag::new(1))
+        );
+        assert_eq!(rfc2865::lookup_nas_port_type_value(&packet), None);
+
+        assert_eq!(
+            rfc2865::ServiceTypeValue::try_from(rfc2865::SERVICE_TYPE_CALL_CHECK),
+            Ok(rfc2865::ServiceTypeValue::CallCheck)
+        );
+        assert_eq!(
+            rfc2865::ServiceTypeValue::try_from(255),
+            Err(AVPError::UnknownValueError("Service-Type".to_owned(), 255))
+        );
+        rfc2865::add_service_type(&mut packet, 255);
+        assert!(rfc2865::lookup_all_service_type_value(&packet).is_err());
+    }
+
+    #[test]
+    fn test_verify_authenticators() {
+        let mut request = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        let request = Packet::decode(&request.encode().unwrap(), b"secret").unwrap();
+        assert!(request.verify_request_authenticator(b"secret"));
+        assert!(!request.verify_request_authenticator(b"wrong"));
+
+        let mut response = request.make_response_packet(Code::AccountingResponse);
+        rfc2865::add_reply_message(&mut response, "ok");
+        let response = Packet::decode(&response.encode().unwrap(), b"secret").unwrap();
+        assert!(response.verify_response_authenticator(&request));
+        assert!(!response
+            .verify_response_authenticator(&Packet::new(Code::AccountingRequest, b"secret")));
+        let mut forged = response.clone();
+        rfc2865::add_reply_message(&mut forged, "forged");
+        assert!(!forged.verify_response_authenticator(&request));
+
+        // the Request Authenticator of Access-Request is random, but Message-Authenticator is verified
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        request.add_message_authenticator();
+        let request = Packet::decode(&request.encode().unwrap(), b"secret").unwrap();
+        assert!(request.verify_request_authenticator(b"secret"));
+        assert!(!request.verify_request_authenticator(b"wrong"));
+        let response = Packet::decode(
+            &request
+                .make_response_packet(Code::AccessAccept)
+                .encode()
+                .unwrap(),
+            b"secret",
+        )
+        .unwrap();
+        assert!(response.verify_response_authenticator(&request));
+        let mut response = response;
+        response.set_secret(b"wrong");
+        assert!(!response.verify_response_authenticator(&request));
+    }
+
     #[test]
     fn test_with_arbitrary_identifier() {
         let mut packet = Packet::new(Code::AccessRequest, b"12345");
@@ -578,4 +1169,78 @@ mod tests {
         packet = Packet::new_with_identifier(Code::AccessRequest, b"12345", expected_ident);
         assert_eq!(packet.get_identifier(), expected_ident);
     }
+
+    #[test]
+    fn test_clone_shares_values() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, "admin");
+        let cloned = packet.clone();
+        assert!(alloc::sync::Arc::ptr_eq(
+            &packet
+                .lookup(rfc2865::USER_NAME_TYPE)
+                .unwrap()
+                .get_shared_value(),
+            &cloned
+                .lookup(rfc2865::USER_NAME_TYPE)
+                .unwrap()
+                .get_shared_value()
+        ));
+    }
+
+    #[test]
+    fn test_eap_message() {
+        let eap_message: Vec<u8> = (0..600).map(|i| i as u8).collect();
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        assert!(packet.get_eap_message().is_empty());
+        packet.add_eap_message(&[0x02, 0x01, 0x00, 0x04]);
+        packet.add_eap_message(&eap_message);
+
+        let lengths: Vec<usize> = packet
+            .lookup_all(rfc2869::EAP_MESSAGE_TYPE)
+            .iter()
+            .map(|avp| avp.encode_bytes().len())
+            .collect();
+        assert_eq!(lengths, vec![253, 253, 94]);
+        assert_eq!(
+            packet.lookup_all(rfc2869::MESSAGE_AUTHENTICATOR_TYPE).len(),
+            1
+        );
+
+        let datagram = packet.encode().unwrap();
+        assert_eq!(verify_request(&datagram, b"secret"), Some(true));
+        let decoded = Packet::decode(&datagram, b"secret").unwrap();
+        assert_eq!(decoded.get_eap_message(), eap_message);
+    }
+
+    #[test]
+    fn test_unknown_attributes() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, "user");
+        packet.add(AVP::from_bytes(200, &[1, 2, 3]));
+        packet.add(vsa::make_vendor_specific(99999, 1, b"opaque"));
+        rfc2865::add_nas_identifier(&mut packet, "nas");
+
+        let decoded = Packet::decode(&packet.encode().unwrap(), b"secret").unwrap();
+        let unknown = decoded.get_unknown_attributes();
+        assert_eq!(unknown.len(), 2);
+        assert_eq!(unknown[0].get_type(), 200);
+        assert_eq!(unknown[0].get_vendor_id(), None);
+        assert_eq!(unknown[0].encode_raw().unwrap(), vec![200, 5, 1, 2, 3]);
+        assert_eq!(unknown[1].get_type(), rfc2865::VENDOR_SPECIFIC_TYPE);
+        assert_eq!(unknown[1].get_vendor_id(), Some(99999));
+
+        let mut response = decoded.make_response_packet(Code::AccessAccept);
+        decoded.copy_unknown_attributes(&mut response);
+        let response = Packet::decode(&response.encode().unwrap(), b"secret").unwrap();
+        assert_eq!(
+            response.get_attributes(),
+            &[unknown[0].clone(), unknown[1].clone()]
+        );
+
+        let mut packet = decoded.clone();
+        packet.delete_unknown_attributes();
+        assert!(packet.get_unknown_attributes().is_empty());
+        assert_eq!(packet.get_attributes().len(), 2);
+        assert!(rfc2865::lookup_nas_identifier(&packet).is_some());
+    }
 }
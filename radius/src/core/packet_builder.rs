@@ -0,0 +1,312 @@
+//! A fluent builder of the packets.
+//!
+//! `PacketBuilder` chains the attributes of the common dictionaries instead of the sequential `add_*()` calls, and
+//! `build()` validates the packet by `lint()` (e.g. the attributes that the code requires, such as NAS-IP-Address or
+//! NAS-Identifier of the requests), so a malformed request is found before it is sent.
+//!
+//! ```
+//! use std::net::{IpAddr, Ipv4Addr};
+//!
+//! use radius::core::code::Code;
+//! use radius::core::packet::Packet;
+//! use radius::core::rfc2865;
+//!
+//! let packet = Packet::builder(Code::AccessRequest, b"secret")
+//!     .user_name("bob")
+//!     .user_password(b"p@ssw0rd")
+//!     .nas_ip(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)))
+//!     .message_authenticator()
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(rfc2865::lookup_user_name(&packet).unwrap().unwrap(), "bob");
+//!
+//! // Access-Request requires a NAS identification
+//! let err = Packet::builder(Code::AccessRequest, b"secret")
+//!     .user_name("bob")
+//!     .user_password(b"p@ssw0rd")
+//!     .build()
+//!     .unwrap_err();
+//! assert!(err.to_string().contains("MissingNasIdentification"));
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::net::{IpAddr, Ipv4Addr};
+
+use thiserror::Error;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::code::Code;
+use crate::core::lint::{lint, Diagnostic};
+use crate::core::packet::Packet;
+use crate::core::{rfc2865, rfc2866};
+use crate::error::ErrorKind;
+
+/// NAS-IPv6-Address of RFC 3162, which is behind the `rfc3162` feature.
+const NAS_IPV6_ADDRESS_TYPE: AVPType = 95;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PacketBuilderError {
+    /// An error that is raised when an attribute can't be encoded, e.g. User-Password without the secret.
+    #[error("failed to add an attribute; {0}")]
+    AttributeError(#[from] AVPError),
+    /// An error that is raised when the packet violates the RFCs (see `radius::core::lint`).
+    #[error("the packet violates the RFCs; {}", join_diagnostics(.0))]
+    ValidationError(Vec<Diagnostic>),
+}
+
+fn join_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl PacketBuilderError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        ErrorKind::Encoding
+    }
+}
+
+/// A builder of `Packet` (see `Packet::builder()`).
+///
+/// The attributes are added in the order of the calls; the first error of an attribute is returned by `build()`.
+pub struct PacketBuilder {
+    packet: Packet,
+    error: Option<AVPError>,
+}
+
+impl PacketBuilder {
+    /// Returns a builder of a packet of the code, which has a random identifier and Request Authenticator.
+    pub fn new(code: Code, secret: &[u8]) -> Self {
+        PacketBuilder {
+            packet: Packet::new(code, secret),
+            error: None,
+        }
+    }
+
+    /// Set the identifier.
+    pub fn identifier(mut self, identifier: u8) -> Self {
+        self.packet.set_identifier(identifier);
+        self
+    }
+
+    /// Add an attribute, e.g. of the dictionaries that this doesn't have the method of.
+    pub fn attribute(mut self, avp: AVP) -> Self {
+        self.packet.add(avp);
+        self
+    }
+
+    /// Add User-Name.
+    pub fn user_name(mut self, user_name: &str) -> Self {
+        rfc2865::add_user_name(&mut self.packet, user_name);
+        self
+    }
+
+    /// Add User-Password, encrypted by the secret.
+    pub fn user_password(mut self, password: &[u8]) -> Self {
+        if let Err(e) = rfc2865::add_user_password(&mut self.packet, password) {
+            self.error.get_or_insert(e);
+        }
+        self
+    }
+
+    /// Add CHAP-Password of the CHAP identifier and the response (see `radius::core::chap`).
+    pub fn chap_password(mut self, chap_password: &[u8]) -> Self {
+        rfc2865::add_chap_password(&mut self.packet, chap_password);
+        self
+    }
+
+    /// Add NAS-IP-Address of an IPv4 address, or NAS-IPv6-Address of an IPv6 address.
+    pub fn nas_ip(mut self, nas_ip: IpAddr) -> Self {
+        match nas_ip {
+            IpAddr::V4(nas_ip) => rfc2865::add_nas_ip_address(&mut self.packet, &nas_ip),
+            IpAddr::V6(nas_ip) => self
+                .packet
+                .add(AVP::from_ipv6(NAS_IPV6_ADDRESS_TYPE, &nas_ip)),
+        }
+        self
+    }
+
+    /// Add NAS-Identifier.
+    pub fn nas_identifier(mut self, nas_identifier: &str) -> Self {
+        rfc2865::add_nas_identifier(&mut self.packet, nas_identifier);
+        self
+    }
+
+    /// Add NAS-Port.
+    pub fn nas_port(mut self, nas_port: u32) -> Self {
+        rfc2865::add_nas_port(&mut self.packet, nas_port);
+        self
+    }
+
+    /// Add Service-Type.
+    pub fn service_type(mut self, service_type: rfc2865::ServiceType) -> Self {
+        rfc2865::add_service_type(&mut self.packet, service_type);
+        self
+    }
+
+    /// Add Framed-IP-Address.
+    pub fn framed_ip_address(mut self, framed_ip_address: Ipv4Addr) -> Self {
+        rfc2865::add_framed_ip_address(&mut self.packet, &framed_ip_address);
+        self
+    }
+
+    /// Add Called-Station-Id.
+    pub fn called_station_id(mut self, called_station_id: &str) -> Self {
+        rfc2865::add_called_station_id(&mut self.packet, called_station_id);
+        self
+    }
+
+    /// Add Calling-Station-Id.
+    pub fn calling_station_id(mut self, calling_station_id: &str) -> Self {
+        rfc2865::add_calling_station_id(&mut self.packet, calling_station_id);
+        self
+    }
+
+    /// Add State.
+    pub fn state(mut self, state: &[u8]) -> Self {
+        rfc2865::add_state(&mut self.packet, state);
+        self
+    }
+
+    /// Add Reply-Message.
+    pub fn reply_message(mut self, reply_message: &str) -> Self {
+        rfc2865::add_reply_message(&mut self.packet, reply_message);
+        self
+    }
+
+    /// Add Session-Timeout.
+    pub fn session_timeout(mut self, session_timeout: u32) -> Self {
+        rfc2865::add_session_timeout(&mut self.packet, session_timeout);
+        self
+    }
+
+    /// Add Proxy-State.
+    pub fn proxy_state(mut self, proxy_state: &[u8]) -> Self {
+        rfc2865::add_proxy_state(&mut self.packet, proxy_state);
+        self
+    }
+
+    /// Add Acct-Status-Type.
+    pub fn acct_status_type(mut self, acct_status_type: rfc2866::AcctStatusType) -> Self {
+        rfc2866::add_acct_status_type(&mut self.packet, acct_status_type);
+        self
+    }
+
+    /// Add Acct-Session-Id.
+    pub fn acct_session_id(mut self, acct_session_id: &str) -> Self {
+        rfc2866::add_acct_session_id(&mut self.packet, acct_session_id);
+        self
+    }
+
+    /// Add EAP-Message of an EAP packet and Message-Authenticator (see `Packet::add_eap_message()`).
+    pub fn eap_message(mut self, eap_message: &[u8]) -> Self {
+        self.packet.add_eap_message(eap_message);
+        self
+    }
+
+    /// Add Message-Authenticator as the first attribute (see `Packet::add_message_authenticator()`).
+    pub fn message_authenticator(mut self) -> Self {
+        self.packet.add_message_authenticator();
+        self
+    }
+
+    /// Returns the packet, or the error of an attribute or the violations of `lint()`.
+    pub fn build(self) -> Result<Packet, PacketBuilderError> {
+        let packet = self.build_unchecked()?;
+        let diagnostics = lint(&packet);
+        if !diagnostics.is_empty() {
+            return Err(PacketBuilderError::ValidationError(diagnostics));
+        }
+        Ok(packet)
+    }
+
+    /// Returns the packet without the validation of `lint()`, e.g. to build an invalid packet for the tests;
+    /// the error of an attribute is still returned.
+    pub fn build_unchecked(self) -> Result<Packet, PacketBuilderError> {
+        match self.error {
+            Some(e) => Err(PacketBuilderError::AttributeError(e)),
+            None => Ok(self.packet),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::net::{IpAddr, Ipv6Addr};
+
+    use crate::core::avp::AVPError;
+    use crate::core::code::Code;
+    use crate::core::lint::LintRule;
+    use crate::core::packet::Packet;
+    use crate::core::packet_builder::PacketBuilderError;
+    use crate::core::{rfc2865, rfc2866, rfc2869};
+
+    #[test]
+    fn test_build() {
+        let packet = Packet::builder(Code::AccountingRequest, b"secret")
+            .identifier(7)
+            .acct_status_type(rfc2866::ACCT_STATUS_TYPE_START)
+            .acct_session_id("session")
+            .nas_ip(IpAddr::V6(Ipv6Addr::LOCALHOST))
+            .user_name("bob")
+            .build()
+            .unwrap();
+        assert_eq!(packet.get_identifier(), 7);
+        assert_eq!(
+            rfc2866::lookup_acct_session_id(&packet).unwrap().unwrap(),
+            "session"
+        );
+        assert_eq!(
+            packet.lookup(95).unwrap().encode_ipv6().unwrap(),
+            Ipv6Addr::LOCALHOST
+        );
+
+        let packet = Packet::builder(Code::StatusServer, b"secret")
+            .message_authenticator()
+            .build()
+            .unwrap();
+        assert!(rfc2869::lookup_message_authenticator(&packet).is_some());
+
+        // Access-Request requires the authentication attributes, and Status-Server requires Message-Authenticator
+        match Packet::builder(Code::AccessRequest, b"secret")
+            .user_name("bob")
+            .nas_identifier("nas")
+            .build()
+        {
+            Err(PacketBuilderError::ValidationError(diagnostics)) => {
+                assert_eq!(diagnostics.len(), 1);
+                assert_eq!(
+                    diagnostics[0].get_rule(),
+                    LintRule::MissingAuthenticationAttribute
+                );
+            }
+            result => panic!("unexpected result: {result:?}"),
+        }
+        assert!(Packet::builder(Code::StatusServer, b"secret")
+            .build()
+            .is_err());
+        assert!(Packet::builder(Code::StatusServer, b"secret")
+            .build_unchecked()
+            .is_ok());
+
+        assert_eq!(
+            Packet::builder(Code::AccessRequest, b"")
+                .user_password(b"p@ssw0rd")
+                .nas_identifier("nas")
+                .build()
+                .unwrap_err(),
+            PacketBuilderError::AttributeError(AVPError::PasswordSecretMissingError())
+        );
+        let packet = Packet::builder(Code::AccessRequest, b"secret")
+            .user_name("bob")
+            .chap_password(&[1; 17])
+            .nas_identifier("nas")
+            .build()
+            .unwrap();
+        assert_eq!(rfc2865::lookup_chap_password(&packet).unwrap(), vec![1; 17]);
+    }
+}
@@ -0,0 +1,20 @@
+//! The random number generator for the authenticators, the identifiers and the salts.
+//!
+//! Without `std`, this is the OS (or the platform) source of `getrandom`; on the targets that `getrandom`
+//! doesn't support, register a custom source by its `custom` feature.
+//! With `std`, this is the seeded generator while `radius::core::deterministic` mode is enabled.
+
+use rand::RngCore;
+
+#[cfg(feature = "std")]
+pub(crate) fn rng() -> Box<dyn RngCore> {
+    match crate::core::deterministic::is_enabled() {
+        true => Box::new(crate::core::deterministic::DeterministicRng),
+        false => Box::new(rand::thread_rng()),
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn rng() -> impl RngCore {
+    rand::rngs::OsRng
+}
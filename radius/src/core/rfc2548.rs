@@ -0,0 +1,619 @@
+//! Utility for rfc2548 packet, i.e. the Microsoft Vendor-Specific attributes of RADIUS.
+//!
+//! Each of the attributes is a sub-attribute of Vendor-Specific with the Vendor-Id of Microsoft (311); a
+//! Vendor-Specific attribute carries one of them. MS-CHAP-Challenge, MS-CHAP2-Response, MS-CHAP2-Success and
+//! MS-CHAP-Error are the MS-CHAPv2 (RFC 2759) authentication of an Access-Request (see `verify_ms_chap2()` and
+//! `add_ms_chap2_accept_attributes()`), and MS-MPPE-Send-Key and MS-MPPE-Recv-Key are the MPPE keys of
+//! an Access-Accept, encrypted by the salt encryption of RFC 2548 section 2.4.2.
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use rand::Rng;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::constant_time;
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::mschap::{
+    generate_authenticator_response, generate_mppe_keys, generate_nt_response,
+};
+use crate::core::packet::Packet;
+use crate::core::random;
+use crate::core::rfc2865;
+use crate::core::vsa::{self, VendorId};
+
+/// The Vendor-Id of Microsoft.
+pub const VENDOR_MICROSOFT: VendorId = 311;
+
+/// The Vendor-Type of a Microsoft attribute.
+pub type MicrosoftType = AVPType;
+
+pub const MS_CHAP_RESPONSE_TYPE: MicrosoftType = 1;
+pub const MS_CHAP_ERROR_TYPE: MicrosoftType = 2;
+pub const MS_MPPE_ENCRYPTION_POLICY_TYPE: MicrosoftType = 7;
+pub const MS_MPPE_ENCRYPTION_TYPES_TYPE: MicrosoftType = 8;
+pub const MS_CHAP_CHALLENGE_TYPE: MicrosoftType = 11;
+pub const MS_MPPE_SEND_KEY_TYPE: MicrosoftType = 16;
+pub const MS_MPPE_RECV_KEY_TYPE: MicrosoftType = 17;
+pub const MS_CHAP2_RESPONSE_TYPE: MicrosoftType = 25;
+pub const MS_CHAP2_SUCCESS_TYPE: MicrosoftType = 26;
+
+pub type MsMppeEncryptionPolicy = u32;
+pub const MS_MPPE_ENCRYPTION_POLICY_ENCRYPTION_ALLOWED: MsMppeEncryptionPolicy = 1;
+pub const MS_MPPE_ENCRYPTION_POLICY_ENCRYPTION_REQUIRED: MsMppeEncryptionPolicy = 2;
+
+pub type MsMppeEncryptionTypes = u32;
+pub const MS_MPPE_ENCRYPTION_TYPES_RC4_40_BIT_ALLOWED: MsMppeEncryptionTypes = 2;
+pub const MS_MPPE_ENCRYPTION_TYPES_RC4_128_BIT_ALLOWED: MsMppeEncryptionTypes = 4;
+pub const MS_MPPE_ENCRYPTION_TYPES_RC4_40_OR_128_BIT_ALLOWED: MsMppeEncryptionTypes = 6;
+
+/// The length of the value of MS-CHAP2-Response.
+const MS_CHAP2_RESPONSE_LENGTH: usize = 50;
+
+fn make_vendor_specific(typ: MicrosoftType, value: &[u8]) -> AVP {
+    vsa::make_vendor_specific(VENDOR_MICROSOFT, typ, value)
+}
+
+fn lookup_vendor_value(packet: &Packet, typ: MicrosoftType) -> Option<Vec<u8>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_MICROSOFT, typ).map(|avp| avp.encode_bytes())
+}
+
+fn delete_vendor_value(packet: &mut Packet, typ: MicrosoftType) {
+    vsa::delete_vendor_attribute(packet, VENDOR_MICROSOFT, typ);
+}
+
+fn decode_u32(typ: MicrosoftType, value: &[u8]) -> Result<u32, AVPError> {
+    let bytes: [u8; 4] = value.try_into().map_err(|_| {
+        AVPError::InvalidAttributeLengthError(format!("4 bytes (type {typ})"), value.len())
+    })?;
+    Ok(u32::from_be_bytes(bytes))
+}
+
+/// The value of MS-CHAP2-Response (RFC 2548 section 2.3.2).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsChap2Response {
+    ident: u8,
+    flags: u8,
+    peer_challenge: [u8; 16],
+    nt_response: [u8; 24],
+}
+
+impl MsChap2Response {
+    pub fn new(ident: u8, peer_challenge: [u8; 16], nt_response: [u8; 24]) -> Self {
+        MsChap2Response {
+            ident,
+            flags: 0,
+            peer_challenge,
+            nt_response,
+        }
+    }
+
+    /// A constructor for the response of the peer to the MS-CHAP-Challenge, i.e. the NT-Response of RFC 2759 with
+    /// the password.
+    pub fn generate(
+        ident: u8,
+        authenticator_challenge: &[u8],
+        peer_challenge: [u8; 16],
+        user_name: &str,
+        password: &str,
+    ) -> Self {
+        let nt_response = generate_nt_response(
+            authenticator_challenge,
+            &peer_challenge,
+            user_name,
+            password,
+        );
+        MsChap2Response::new(ident, peer_challenge, nt_response)
+    }
+
+    pub fn get_ident(&self) -> u8 {
+        self.ident
+    }
+
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+
+    pub fn get_peer_challenge(&self) -> &[u8; 16] {
+        &self.peer_challenge
+    }
+
+    pub fn get_nt_response(&self) -> &[u8; 24] {
+        &self.nt_response
+    }
+
+    fn decode(value: &[u8]) -> Result<Self, AVPError> {
+        if value.len() != MS_CHAP2_RESPONSE_LENGTH {
+            return Err(AVPError::InvalidAttributeLengthError(
+                format!("{MS_CHAP2_RESPONSE_LENGTH} bytes"),
+                value.len(),
+            ));
+        }
+        Ok(MsChap2Response {
+            ident: value[0],
+            flags: value[1],
+            peer_challenge: value[2..18].try_into().unwrap(),
+            // value[18..26] is reserved
+            nt_response: value[26..50].try_into().unwrap(),
+        })
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        [
+            &[self.ident, self.flags],
+            &self.peer_challenge[..],
+            &[0; 8],
+            &self.nt_response[..],
+        ]
+        .concat()
+    }
+}
+
+/// Delete all of `ms_chap_challenge` values from a packet.
+pub fn delete_ms_chap_challenge(packet: &mut Packet) {
+    delete_vendor_value(packet, MS_CHAP_CHALLENGE_TYPE);
+}
+/// Add `ms_chap_challenge` octets value to a packet.
+pub fn add_ms_chap_challenge(packet: &mut Packet, value: &[u8]) {
+    packet.add(make_vendor_specific(MS_CHAP_CHALLENGE_TYPE, value));
+}
+/// Lookup a `ms_chap_challenge` octets value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `ms_chap_challenge`, it returns `None`.
+pub fn lookup_ms_chap_challenge(packet: &Packet) -> Option<Vec<u8>> {
+    lookup_vendor_value(packet, MS_CHAP_CHALLENGE_TYPE)
+}
+
+/// Delete all of `ms_chap2_response` values from a packet.
+pub fn delete_ms_chap2_response(packet: &mut Packet) {
+    delete_vendor_value(packet, MS_CHAP2_RESPONSE_TYPE);
+}
+/// Add `ms_chap2_response` value to a packet.
+pub fn add_ms_chap2_response(packet: &mut Packet, value: &MsChap2Response) {
+    packet.add(make_vendor_specific(
+        MS_CHAP2_RESPONSE_TYPE,
+        &value.encode(),
+    ));
+}
+/// Lookup a `ms_chap2_response` value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `ms_chap2_response`, it returns `None`.
+pub fn lookup_ms_chap2_response(packet: &Packet) -> Option<Result<MsChap2Response, AVPError>> {
+    lookup_vendor_value(packet, MS_CHAP2_RESPONSE_TYPE).map(|v| MsChap2Response::decode(&v))
+}
+
+/// Delete all of `ms_chap2_success` values from a packet.
+pub fn delete_ms_chap2_success(packet: &mut Packet) {
+    delete_vendor_value(packet, MS_CHAP2_SUCCESS_TYPE);
+}
+/// Add `ms_chap2_success` value to a packet, i.e. the Ident of the MS-CHAP2-Response and
+/// the authenticator response (`S=...`).
+pub fn add_ms_chap2_success(packet: &mut Packet, ident: u8, authenticator_response: &str) {
+    packet.add(make_vendor_specific(
+        MS_CHAP2_SUCCESS_TYPE,
+        &[&[ident], authenticator_response.as_bytes()].concat(),
+    ));
+}
+/// Lookup a `ms_chap2_success` value from a packet, as the pair of the Ident and the authenticator response.
+///
+/// It returns the first looked up value. If there is no associated value with `ms_chap2_success`, it returns `None`.
+pub fn lookup_ms_chap2_success(packet: &Packet) -> Option<Result<(u8, String), AVPError>> {
+    lookup_vendor_value(packet, MS_CHAP2_SUCCESS_TYPE).map(|v| decode_ident_string(&v))
+}
+
+/// Delete all of `ms_chap_error` values from a packet.
+pub fn delete_ms_chap_error(packet: &mut Packet) {
+    delete_vendor_value(packet, MS_CHAP_ERROR_TYPE);
+}
+/// Add `ms_chap_error` value to a packet, i.e. the Ident of the response and the message (e.g. `E=691 R=0`).
+pub fn add_ms_chap_error(packet: &mut Packet, ident: u8, message: &str) {
+    packet.add(make_vendor_specific(
+        MS_CHAP_ERROR_TYPE,
+        &[&[ident], message.as_bytes()].concat(),
+    ));
+}
+/// Lookup a `ms_chap_error` value from a packet, as the pair of the Ident and the message.
+///
+/// It returns the first looked up value. If there is no associated value with `ms_chap_error`, it returns `None`.
+pub fn lookup_ms_chap_error(packet: &Packet) -> Option<Result<(u8, String), AVPError>> {
+    lookup_vendor_value(packet, MS_CHAP_ERROR_TYPE).map(|v| decode_ident_string(&v))
+}
+
+fn decode_ident_string(value: &[u8]) -> Result<(u8, String), AVPError> {
+    match value.split_first() {
+        Some((&ident, rest)) => String::from_utf8(rest.to_vec())
+            .map(|s| (ident, s))
+            .map_err(|e| AVPError::DecodingError(e.to_string())),
+        None => Err(AVPError::InvalidAttributeLengthError(
+            "at least 1 byte".to_owned(),
+            0,
+        )),
+    }
+}
+
+/// Delete all of `ms_mppe_encryption_policy` values from a packet.
+pub fn delete_ms_mppe_encryption_policy(packet: &mut Packet) {
+    delete_vendor_value(packet, MS_MPPE_ENCRYPTION_POLICY_TYPE);
+}
+/// Add `ms_mppe_encryption_policy` value-defined integer value to a packet.
+pub fn add_ms_mppe_encryption_policy(packet: &mut Packet, value: MsMppeEncryptionPolicy) {
+    packet.add(make_vendor_specific(
+        MS_MPPE_ENCRYPTION_POLICY_TYPE,
+        &value.to_be_bytes(),
+    ));
+}
+/// Lookup a `ms_mppe_encryption_policy` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `ms_mppe_encryption_policy`, it returns `None`.
+pub fn lookup_ms_mppe_encryption_policy(
+    packet: &Packet,
+) -> Option<Result<MsMppeEncryptionPolicy, AVPError>> {
+    lookup_vendor_value(packet, MS_MPPE_ENCRYPTION_POLICY_TYPE)
+        .map(|v| decode_u32(MS_MPPE_ENCRYPTION_POLICY_TYPE, &v))
+}
+
+/// Delete all of `ms_mppe_encryption_types` values from a packet.
+pub fn delete_ms_mppe_encryption_types(packet: &mut Packet) {
+    delete_vendor_value(packet, MS_MPPE_ENCRYPTION_TYPES_TYPE);
+}
+/// Add `ms_mppe_encryption_types` value-defined integer value to a packet.
+pub fn add_ms_mppe_encryption_types(packet: &mut Packet, value: MsMppeEncryptionTypes) {
+    packet.add(make_vendor_specific(
+        MS_MPPE_ENCRYPTION_TYPES_TYPE,
+        &value.to_be_bytes(),
+    ));
+}
+/// Lookup a `ms_mppe_encryption_types` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `ms_mppe_encryption_types`, it returns `None`.
+pub fn lookup_ms_mppe_encryption_types(
+    packet: &Packet,
+) -> Option<Result<MsMppeEncryptionTypes, AVPError>> {
+    lookup_vendor_value(packet, MS_MPPE_ENCRYPTION_TYPES_TYPE)
+        .map(|v| decode_u32(MS_MPPE_ENCRYPTION_TYPES_TYPE, &v))
+}
+
+/// Delete all of `ms_mppe_send_key` values from a packet.
+pub fn delete_ms_mppe_send_key(packet: &mut Packet) {
+    delete_vendor_value(packet, MS_MPPE_SEND_KEY_TYPE);
+}
+/// Add `ms_mppe_send_key` salt encrypted value to a packet.
+///
+/// The key is encrypted by the secret and the authenticator of the packet, i.e. of the Access-Request
+/// for an Access-Accept by `Packet::make_response_packet()`.
+pub fn add_ms_mppe_send_key(packet: &mut Packet, key: &[u8]) -> Result<(), AVPError> {
+    let value = encrypt_key(key, packet.get_secret(), packet.get_authenticator())?;
+    packet.add(make_vendor_specific(MS_MPPE_SEND_KEY_TYPE, &value));
+    Ok(())
+}
+/// Lookup a `ms_mppe_send_key` salt encrypted value from a response packet, by the authenticator of the request.
+///
+/// It returns the first looked up value. If there is no associated value with `ms_mppe_send_key`, it returns `None`.
+pub fn lookup_ms_mppe_send_key(
+    packet: &Packet,
+    request_authenticator: &[u8],
+) -> Option<Result<Vec<u8>, AVPError>> {
+    lookup_vendor_value(packet, MS_MPPE_SEND_KEY_TYPE)
+        .map(|v| decrypt_key(&v, packet.get_secret(), request_authenticator))
+}
+
+/// Delete all of `ms_mppe_recv_key` values from a packet.
+pub fn delete_ms_mppe_recv_key(packet: &mut Packet) {
+    delete_vendor_value(packet, MS_MPPE_RECV_KEY_TYPE);
+}
+/// Add `ms_mppe_recv_key` salt encrypted value to a packet.
+///
+/// The key is encrypted by the secret and the authenticator of the packet, i.e. of the Access-Request
+/// for an Access-Accept by `Packet::make_response_packet()`.
+pub fn add_ms_mppe_recv_key(packet: &mut Packet, key: &[u8]) -> Result<(), AVPError> {
+    let value = encrypt_key(key, packet.get_secret(), packet.get_authenticator())?;
+    packet.add(make_vendor_specific(MS_MPPE_RECV_KEY_TYPE, &value));
+    Ok(())
+}
+/// Lookup a `ms_mppe_recv_key` salt encrypted value from a response packet, by the authenticator of the request.
+///
+/// It returns the first looked up value. If there is no associated value with `ms_mppe_recv_key`, it returns `None`.
+pub fn lookup_ms_mppe_recv_key(
+    packet: &Packet,
+    request_authenticator: &[u8],
+) -> Option<Result<Vec<u8>, AVPError>> {
+    lookup_vendor_value(packet, MS_MPPE_RECV_KEY_TYPE)
+        .map(|v| decrypt_key(&v, packet.get_secret(), request_authenticator))
+}
+
+/// Verify the MS-CHAP2-Response of an Access-Request by the MS-CHAP-Challenge, the User-Name and the password of
+/// the user; this returns `Ok(false)` if the NT-Response doesn't match.
+pub fn verify_ms_chap2(request: &Packet, password: &str) -> Result<bool, AVPError> {
+    let (challenge, response, user_name) = lookup_ms_chap2_request(request)?;
+    let expected = generate_nt_response(
+        &challenge,
+        response.get_peer_challenge(),
+        &user_name,
+        password,
+    );
+    Ok(constant_time::eq(&expected, response.get_nt_response()))
+}
+
+/// Add the attributes of the Access-Accept to the MS-CHAPv2 Access-Request that has been verified by
+/// `verify_ms_chap2()`: MS-CHAP2-Success with the authenticator response, and MS-MPPE-Send-Key and
+/// MS-MPPE-Recv-Key with the MPPE keys of RFC 3079.
+pub fn add_ms_chap2_accept_attributes(
+    response: &mut Packet,
+    request: &Packet,
+    password: &str,
+) -> Result<(), AVPError> {
+    let (challenge, ms_chap2_response, user_name) = lookup_ms_chap2_request(request)?;
+    let authenticator_response = generate_authenticator_response(
+        password,
+        ms_chap2_response.get_nt_response(),
+        ms_chap2_response.get_peer_challenge(),
+        &challenge,
+        &user_name,
+    );
+    add_ms_chap2_success(
+        response,
+        ms_chap2_response.get_ident(),
+        &authenticator_response,
+    );
+
+    let (send_key, recv_key) = generate_mppe_keys(password, ms_chap2_response.get_nt_response());
+    add_ms_mppe_send_key(response, &send_key)?;
+    add_ms_mppe_recv_key(response, &recv_key)?;
+    Ok(())
+}
+
+/// Returns the MS-CHAP-Challenge, the MS-CHAP2-Response and the user name (without the domain) of an Access-Request.
+fn lookup_ms_chap2_request(
+    request: &Packet,
+) -> Result<(Vec<u8>, MsChap2Response, String), AVPError> {
+    let challenge = lookup_ms_chap_challenge(request)
+        .ok_or_else(|| AVPError::DecodingError("MS-CHAP-Challenge is missing".to_owned()))?;
+    let response = lookup_ms_chap2_response(request)
+        .ok_or_else(|| AVPError::DecodingError("MS-CHAP2-Response is missing".to_owned()))??;
+    let user_name = rfc2865::lookup_user_name(request)
+        .ok_or_else(|| AVPError::DecodingError("User-Name is missing".to_owned()))??;
+    // the domain is not a part of the user name of the hash (RFC 2759 section 4)
+    let user_name = user_name.rsplit('\\').next().unwrap_or_default().to_owned();
+    Ok((challenge, response, user_name))
+}
+
+/// Encrypt a key by the salt encryption (RFC 2548 section 2.4.2).
+fn encrypt_key(
+    key: &[u8],
+    secret: &[u8],
+    request_authenticator: &[u8],
+) -> Result<Vec<u8>, AVPError> {
+    /*
+     *   b(1) = MD5(S + R + A)    c(1) = p(1) xor b(1)   C = c(1)
+     *   b(2) = MD5(S + c(1))     c(2) = p(2) xor b(2)   C = C + c(2)
+     *               .                      .
+     *   b(i) = MD5(S + c(i-1))   c(i) = p(i) xor b(i)   C = C + c(i)
+     *
+     *  The plaintext P is the Key-Length, the key and the padding of zeros to a multiple of 16 bytes,
+     *  and the value is A + C, where A is the Salt whose most significant bit is set.
+     */
+    if key.len() > 239 {
+        return Err(AVPError::InvalidAttributeLengthError(
+            "239 bytes".to_owned(),
+            key.len(),
+        ));
+    }
+    if secret.is_empty() {
+        return Err(AVPError::PasswordSecretMissingError());
+    }
+    if request_authenticator.len() != 16 {
+        return Err(AVPError::InvalidRequestAuthenticatorLength());
+    }
+
+    let mut rng = random::rng();
+    let salt: [u8; 2] = [rng.gen::<u8>() | 0x80, rng.gen::<u8>()];
+
+    let mut plain_text = [&[key.len() as u8], key].concat();
+    plain_text.resize(plain_text.len().div_ceil(16) * 16, 0);
+
+    let mut enc = salt.to_vec();
+    let mut buff = [request_authenticator, &salt[..]].concat();
+    for chunk in plain_text.chunks(16) {
+        let block = md5::compute([secret, &buff[..]].concat());
+        let cipher: Vec<u8> = block.iter().zip(chunk).map(|(b, p)| b ^ p).collect();
+        enc.extend(&cipher);
+        buff = cipher;
+    }
+    Ok(enc)
+}
+
+/// Decrypt a key by the salt encryption (RFC 2548 section 2.4.2).
+fn decrypt_key(
+    value: &[u8],
+    secret: &[u8],
+    request_authenticator: &[u8],
+) -> Result<Vec<u8>, AVPError> {
+    if value.len() < 18 || !(value.len() - 2).is_multiple_of(16) {
+        return Err(AVPError::InvalidAttributeLengthError(
+            "18 <= bytes && (bytes - 2) % 16 == 0".to_owned(),
+            value.len(),
+        ));
+    }
+    if value[0] & 0x80 != 0x80 {
+        return Err(AVPError::InvalidSaltMSBError(value[0]));
+    }
+    if secret.is_empty() {
+        return Err(AVPError::PasswordSecretMissingError());
+    }
+    if request_authenticator.len() != 16 {
+        return Err(AVPError::InvalidRequestAuthenticatorLength());
+    }
+
+    let mut dec = Vec::with_capacity(value.len() - 2);
+    let mut buff = [request_authenticator, &value[..2]].concat();
+    for chunk in value[2..].chunks(16) {
+        let block = md5::compute([secret, &buff[..]].concat());
+        dec.extend(block.iter().zip(chunk).map(|(b, c)| b ^ c));
+        buff = chunk.to_vec();
+    }
+
+    let key_len = dec[0] as usize;
+    if key_len > dec.len() - 1 {
+        return Err(AVPError::DecodingError(format!(
+            "invalid Key-Length: {key_len}"
+        )));
+    }
+    let key = dec[1..=key_len].to_vec();
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(&mut dec);
+    Ok(key)
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("MS-CHAP-Response", MS_CHAP_RESPONSE_TYPE, "octets"),
+    AttributeDefinition::new("MS-CHAP-Error", MS_CHAP_ERROR_TYPE, "string"),
+    AttributeDefinition::new(
+        "MS-MPPE-Encryption-Policy",
+        MS_MPPE_ENCRYPTION_POLICY_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "MS-MPPE-Encryption-Types",
+        MS_MPPE_ENCRYPTION_TYPES_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new("MS-CHAP-Challenge", MS_CHAP_CHALLENGE_TYPE, "octets"),
+    AttributeDefinition::new("MS-MPPE-Send-Key", MS_MPPE_SEND_KEY_TYPE, "octets"),
+    AttributeDefinition::new("MS-MPPE-Recv-Key", MS_MPPE_RECV_KEY_TYPE, "octets"),
+    AttributeDefinition::new("MS-CHAP2-Response", MS_CHAP2_RESPONSE_TYPE, "octets"),
+    AttributeDefinition::new("MS-CHAP2-Success", MS_CHAP2_SUCCESS_TYPE, "octets"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new(
+        "MS-MPPE-Encryption-Policy",
+        "Encryption-Allowed",
+        MS_MPPE_ENCRYPTION_POLICY_ENCRYPTION_ALLOWED,
+    ),
+    ValueDefinition::new(
+        "MS-MPPE-Encryption-Policy",
+        "Encryption-Required",
+        MS_MPPE_ENCRYPTION_POLICY_ENCRYPTION_REQUIRED,
+    ),
+    ValueDefinition::new(
+        "MS-MPPE-Encryption-Types",
+        "RC4-40bit-Allowed",
+        MS_MPPE_ENCRYPTION_TYPES_RC4_40_BIT_ALLOWED,
+    ),
+    ValueDefinition::new(
+        "MS-MPPE-Encryption-Types",
+        "RC4-128bit-Allowed",
+        MS_MPPE_ENCRYPTION_TYPES_RC4_128_BIT_ALLOWED,
+    ),
+    ValueDefinition::new(
+        "MS-MPPE-Encryption-Types",
+        "RC4-40or128-bit-Allowed",
+        MS_MPPE_ENCRYPTION_TYPES_RC4_40_OR_128_BIT_ALLOWED,
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::core::avp::AVP;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2548::*;
+    use crate::core::rfc2865;
+
+    #[test]
+    fn test_mppe_key_round_trip() {
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let mut response = request.make_response_packet(Code::AccessAccept);
+        let key = [0x5a; 16];
+        add_ms_mppe_send_key(&mut response, &key).unwrap();
+        add_ms_mppe_recv_key(&mut response, &key[..5]).unwrap();
+        add_ms_mppe_encryption_policy(&mut response, MS_MPPE_ENCRYPTION_POLICY_ENCRYPTION_REQUIRED);
+        // a Vendor-Specific attribute of another vendor
+        response.add(AVP::from_bytes(
+            rfc2865::VENDOR_SPECIFIC_TYPE,
+            &[0, 0, 0, 9, 1, 3, 0],
+        ));
+
+        // Salt (2) + Key-Length (1) + key (16) + padding (15)
+        let value = lookup_vendor_value(&response, MS_MPPE_SEND_KEY_TYPE).unwrap();
+        assert_eq!(value.len(), 34);
+        assert_eq!(value[0] & 0x80, 0x80);
+
+        let encoded = response.encode().unwrap();
+        let decoded = Packet::decode(&encoded, b"secret").unwrap();
+        let request_authenticator = request.get_authenticator();
+        assert_eq!(
+            lookup_ms_mppe_send_key(&decoded, request_authenticator)
+                .unwrap()
+                .unwrap(),
+            key
+        );
+        assert_eq!(
+            lookup_ms_mppe_recv_key(&decoded, request_authenticator)
+                .unwrap()
+                .unwrap(),
+            &key[..5]
+        );
+        assert_eq!(
+            lookup_ms_mppe_encryption_policy(&decoded).unwrap().unwrap(),
+            MS_MPPE_ENCRYPTION_POLICY_ENCRYPTION_REQUIRED
+        );
+
+        delete_ms_mppe_send_key(&mut response);
+        assert!(lookup_ms_mppe_send_key(&response, request_authenticator).is_none());
+        assert!(lookup_ms_mppe_recv_key(&response, request_authenticator).is_some());
+        assert_eq!(response.lookup_all(rfc2865::VENDOR_SPECIFIC_TYPE).len(), 3);
+    }
+
+    #[test]
+    fn test_ms_chap2() {
+        // the vectors of RFC 2759 section 9.2
+        let authenticator_challenge = [
+            0x5B, 0x5D, 0x7C, 0x7D, 0x7B, 0x3F, 0x2F, 0x3E, 0x3C, 0x2C, 0x60, 0x21, 0x32, 0x26,
+            0x26, 0x28,
+        ];
+        let peer_challenge = [
+            0x21, 0x40, 0x23, 0x24, 0x25, 0x5E, 0x26, 0x2A, 0x28, 0x29, 0x5F, 0x2B, 0x3A, 0x33,
+            0x7C, 0x7E,
+        ];
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "User");
+        add_ms_chap_challenge(&mut request, &authenticator_challenge);
+        add_ms_chap2_response(
+            &mut request,
+            &MsChap2Response::generate(
+                7,
+                &authenticator_challenge,
+                peer_challenge,
+                "User",
+                "clientPass",
+            ),
+        );
+        let request = Packet::decode(&request.encode().unwrap(), b"secret").unwrap();
+
+        assert!(verify_ms_chap2(&request, "clientPass").unwrap());
+        assert!(!verify_ms_chap2(&request, "wrong").unwrap());
+        assert!(
+            verify_ms_chap2(&Packet::new(Code::AccessRequest, b"secret"), "clientPass").is_err()
+        );
+
+        let mut response = request.make_response_packet(Code::AccessAccept);
+        add_ms_chap2_accept_attributes(&mut response, &request, "clientPass").unwrap();
+        assert_eq!(
+            lookup_ms_chap2_success(&response).unwrap().unwrap(),
+            (7, "S=407A5589115FD0D6209F510FE9C04566932CDA56".to_owned())
+        );
+        assert_eq!(
+            lookup_ms_mppe_send_key(&response, request.get_authenticator())
+                .unwrap()
+                .unwrap()
+                .len(),
+            16
+        );
+    }
+}
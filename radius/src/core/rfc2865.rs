@@ -146,9 +146,13 @@
 //! VALUE    NAS-Port-Type            Wireless-802.11        19
 //! ```
 
-use std::net::Ipv4Addr;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::net::Ipv4Addr;
 
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const USER_NAME_TYPE: AVPType = 1;
@@ -304,6 +308,22 @@ pub fn lookup_all_service_type(packet: &Packet) -> Result<Vec<ServiceType>, AVPE
     }
     Ok(vec)
 }
+/// Lookup a `service_type` value from a packet as `ServiceTypeValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `service_type`, it returns `None`.
+pub fn lookup_service_type_value(packet: &Packet) -> Option<Result<ServiceTypeValue, AVPError>> {
+    packet
+        .lookup(SERVICE_TYPE_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `service_type` values from a packet as `ServiceTypeValue`.
+pub fn lookup_all_service_type_value(packet: &Packet) -> Result<Vec<ServiceTypeValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(SERVICE_TYPE_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const FRAMED_PROTOCOL_TYPE: AVPType = 7;
 /// Delete all of `framed_protocol` values from a packet.
@@ -330,6 +350,26 @@ pub fn lookup_all_framed_protocol(packet: &Packet) -> Result<Vec<FramedProtocol>
     }
     Ok(vec)
 }
+/// Lookup a `framed_protocol` value from a packet as `FramedProtocolValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `framed_protocol`, it returns `None`.
+pub fn lookup_framed_protocol_value(
+    packet: &Packet,
+) -> Option<Result<FramedProtocolValue, AVPError>> {
+    packet
+        .lookup(FRAMED_PROTOCOL_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `framed_protocol` values from a packet as `FramedProtocolValue`.
+pub fn lookup_all_framed_protocol_value(
+    packet: &Packet,
+) -> Result<Vec<FramedProtocolValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(FRAMED_PROTOCOL_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const FRAMED_IP_ADDRESS_TYPE: AVPType = 8;
 /// Delete all of `framed_ip_address` values from a packet.
@@ -408,6 +448,26 @@ pub fn lookup_all_framed_routing(packet: &Packet) -> Result<Vec<FramedRouting>,
     }
     Ok(vec)
 }
+/// Lookup a `framed_routing` value from a packet as `FramedRoutingValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `framed_routing`, it returns `None`.
+pub fn lookup_framed_routing_value(
+    packet: &Packet,
+) -> Option<Result<FramedRoutingValue, AVPError>> {
+    packet
+        .lookup(FRAMED_ROUTING_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `framed_routing` values from a packet as `FramedRoutingValue`.
+pub fn lookup_all_framed_routing_value(
+    packet: &Packet,
+) -> Result<Vec<FramedRoutingValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(FRAMED_ROUTING_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const FILTER_ID_TYPE: AVPType = 11;
 /// Delete all of `filter_id` values from a packet.
@@ -482,6 +542,26 @@ pub fn lookup_all_framed_compression(packet: &Packet) -> Result<Vec<FramedCompre
     }
     Ok(vec)
 }
+/// Lookup a `framed_compression` value from a packet as `FramedCompressionValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `framed_compression`, it returns `None`.
+pub fn lookup_framed_compression_value(
+    packet: &Packet,
+) -> Option<Result<FramedCompressionValue, AVPError>> {
+    packet
+        .lookup(FRAMED_COMPRESSION_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `framed_compression` values from a packet as `FramedCompressionValue`.
+pub fn lookup_all_framed_compression_value(
+    packet: &Packet,
+) -> Result<Vec<FramedCompressionValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(FRAMED_COMPRESSION_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const LOGIN_IP_HOST_TYPE: AVPType = 14;
 /// Delete all of `login_ip_host` values from a packet.
@@ -532,6 +612,22 @@ pub fn lookup_all_login_service(packet: &Packet) -> Result<Vec<LoginService>, AV
     }
     Ok(vec)
 }
+/// Lookup a `login_service` value from a packet as `LoginServiceValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `login_service`, it returns `None`.
+pub fn lookup_login_service_value(packet: &Packet) -> Option<Result<LoginServiceValue, AVPError>> {
+    packet
+        .lookup(LOGIN_SERVICE_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `login_service` values from a packet as `LoginServiceValue`.
+pub fn lookup_all_login_service_value(packet: &Packet) -> Result<Vec<LoginServiceValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(LOGIN_SERVICE_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const LOGIN_TCP_PORT_TYPE: AVPType = 16;
 /// Delete all of `login_tcp_port` values from a packet.
@@ -558,6 +654,24 @@ pub fn lookup_all_login_tcp_port(packet: &Packet) -> Result<Vec<LoginTCPPort>, A
     }
     Ok(vec)
 }
+/// Lookup a `login_tcp_port` value from a packet as `LoginTCPPortValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `login_tcp_port`, it returns `None`.
+pub fn lookup_login_tcp_port_value(packet: &Packet) -> Option<Result<LoginTCPPortValue, AVPError>> {
+    packet
+        .lookup(LOGIN_TCP_PORT_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `login_tcp_port` values from a packet as `LoginTCPPortValue`.
+pub fn lookup_all_login_tcp_port_value(
+    packet: &Packet,
+) -> Result<Vec<LoginTCPPortValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(LOGIN_TCP_PORT_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const REPLY_MESSAGE_TYPE: AVPType = 18;
 /// Delete all of `reply_message` values from a packet.
@@ -810,6 +924,26 @@ pub fn lookup_all_termination_action(packet: &Packet) -> Result<Vec<TerminationA
     }
     Ok(vec)
 }
+/// Lookup a `termination_action` value from a packet as `TerminationActionValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `termination_action`, it returns `None`.
+pub fn lookup_termination_action_value(
+    packet: &Packet,
+) -> Option<Result<TerminationActionValue, AVPError>> {
+    packet
+        .lookup(TERMINATION_ACTION_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `termination_action` values from a packet as `TerminationActionValue`.
+pub fn lookup_all_termination_action_value(
+    packet: &Packet,
+) -> Result<Vec<TerminationActionValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(TERMINATION_ACTION_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const CALLED_STATION_ID_TYPE: AVPType = 30;
 /// Delete all of `called_station_id` values from a packet.
@@ -1118,6 +1252,22 @@ pub fn lookup_all_nas_port_type(packet: &Packet) -> Result<Vec<NasPortType>, AVP
     }
     Ok(vec)
 }
+/// Lookup a `nas_port_type` value from a packet as `NasPortTypeValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `nas_port_type`, it returns `None`.
+pub fn lookup_nas_port_type_value(packet: &Packet) -> Option<Result<NasPortTypeValue, AVPError>> {
+    packet
+        .lookup(NAS_PORT_TYPE_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `nas_port_type` values from a packet as `NasPortTypeValue`.
+pub fn lookup_all_nas_port_type_value(packet: &Packet) -> Result<Vec<NasPortTypeValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(NAS_PORT_TYPE_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const PORT_LIMIT_TYPE: AVPType = 62;
 /// Delete all of `port_limit` values from a packet.
@@ -1175,6 +1325,45 @@ pub const FRAMED_COMPRESSION_VAN_JACOBSON_TCP_IP: FramedCompression = 1;
 pub const FRAMED_COMPRESSION_IPX_HEADER_COMPRESSION: FramedCompression = 2;
 pub const FRAMED_COMPRESSION_STAC_LZS: FramedCompression = 3;
 
+/// The values of `Framed-Compression` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `FramedCompressionValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum FramedCompressionValue {
+    /// `None`
+    None = 0,
+    /// `Van-Jacobson-TCP-IP`
+    VanJacobsonTCPIP = 1,
+    /// `IPX-Header-Compression`
+    IpxHeaderCompression = 2,
+    /// `Stac-LZS`
+    StacLZS = 3,
+}
+
+impl From<FramedCompressionValue> for u32 {
+    fn from(value: FramedCompressionValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for FramedCompressionValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FramedCompressionValue::None),
+            1 => Ok(FramedCompressionValue::VanJacobsonTCPIP),
+            2 => Ok(FramedCompressionValue::IpxHeaderCompression),
+            3 => Ok(FramedCompressionValue::StacLZS),
+            _ => Err(AVPError::UnknownValueError(
+                "Framed-Compression".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type FramedProtocol = u32;
 pub const FRAMED_PROTOCOL_PPP: FramedProtocol = 1;
 pub const FRAMED_PROTOCOL_SLIP: FramedProtocol = 2;
@@ -1183,12 +1372,96 @@ pub const FRAMED_PROTOCOL_GANDALF_SLML: FramedProtocol = 4;
 pub const FRAMED_PROTOCOL_XYLOGICS_IPX_SLIP: FramedProtocol = 5;
 pub const FRAMED_PROTOCOL_X_75_SYNCHRONOUS: FramedProtocol = 6;
 
+/// The values of `Framed-Protocol` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `FramedProtocolValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum FramedProtocolValue {
+    /// `PPP`
+    Ppp = 1,
+    /// `SLIP`
+    Slip = 2,
+    /// `ARAP`
+    Arap = 3,
+    /// `Gandalf-SLML`
+    GandalfSLML = 4,
+    /// `Xylogics-IPX-SLIP`
+    XylogicsIPXSLIP = 5,
+    /// `X.75-Synchronous`
+    X75Synchronous = 6,
+}
+
+impl From<FramedProtocolValue> for u32 {
+    fn from(value: FramedProtocolValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for FramedProtocolValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(FramedProtocolValue::Ppp),
+            2 => Ok(FramedProtocolValue::Slip),
+            3 => Ok(FramedProtocolValue::Arap),
+            4 => Ok(FramedProtocolValue::GandalfSLML),
+            5 => Ok(FramedProtocolValue::XylogicsIPXSLIP),
+            6 => Ok(FramedProtocolValue::X75Synchronous),
+            _ => Err(AVPError::UnknownValueError(
+                "Framed-Protocol".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type FramedRouting = u32;
 pub const FRAMED_ROUTING_NONE: FramedRouting = 0;
 pub const FRAMED_ROUTING_BROADCAST: FramedRouting = 1;
 pub const FRAMED_ROUTING_LISTEN: FramedRouting = 2;
 pub const FRAMED_ROUTING_BROADCAST_LISTEN: FramedRouting = 3;
 
+/// The values of `Framed-Routing` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `FramedRoutingValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum FramedRoutingValue {
+    /// `None`
+    None = 0,
+    /// `Broadcast`
+    Broadcast = 1,
+    /// `Listen`
+    Listen = 2,
+    /// `Broadcast-Listen`
+    BroadcastListen = 3,
+}
+
+impl From<FramedRoutingValue> for u32 {
+    fn from(value: FramedRoutingValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for FramedRoutingValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FramedRoutingValue::None),
+            1 => Ok(FramedRoutingValue::Broadcast),
+            2 => Ok(FramedRoutingValue::Listen),
+            3 => Ok(FramedRoutingValue::BroadcastListen),
+            _ => Err(AVPError::UnknownValueError(
+                "Framed-Routing".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type LoginService = u32;
 pub const LOGIN_SERVICE_TELNET: LoginService = 0;
 pub const LOGIN_SERVICE_RLOGIN: LoginService = 1;
@@ -1199,11 +1472,98 @@ pub const LOGIN_SERVICE_X25_PAD: LoginService = 5;
 pub const LOGIN_SERVICE_X25_T3POS: LoginService = 6;
 pub const LOGIN_SERVICE_TCP_CLEAR_QUIET: LoginService = 8;
 
+/// The values of `Login-Service` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `LoginServiceValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum LoginServiceValue {
+    /// `Telnet`
+    Telnet = 0,
+    /// `Rlogin`
+    Rlogin = 1,
+    /// `TCP-Clear`
+    TcpClear = 2,
+    /// `PortMaster`
+    PortMaster = 3,
+    /// `LAT`
+    Lat = 4,
+    /// `X25-PAD`
+    X25Pad = 5,
+    /// `X25-T3POS`
+    X25T3Pos = 6,
+    /// `TCP-Clear-Quiet`
+    TcpClearQuiet = 8,
+}
+
+impl From<LoginServiceValue> for u32 {
+    fn from(value: LoginServiceValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for LoginServiceValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(LoginServiceValue::Telnet),
+            1 => Ok(LoginServiceValue::Rlogin),
+            2 => Ok(LoginServiceValue::TcpClear),
+            3 => Ok(LoginServiceValue::PortMaster),
+            4 => Ok(LoginServiceValue::Lat),
+            5 => Ok(LoginServiceValue::X25Pad),
+            6 => Ok(LoginServiceValue::X25T3Pos),
+            8 => Ok(LoginServiceValue::TcpClearQuiet),
+            _ => Err(AVPError::UnknownValueError(
+                "Login-Service".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type LoginTCPPort = u32;
 pub const LOGIN_TCP_PORT_TELNET: LoginTCPPort = 23;
 pub const LOGIN_TCP_PORT_RLOGIN: LoginTCPPort = 513;
 pub const LOGIN_TCP_PORT_RSH: LoginTCPPort = 514;
 
+/// The values of `Login-TCP-Port` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `LoginTCPPortValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum LoginTCPPortValue {
+    /// `Telnet`
+    Telnet = 23,
+    /// `Rlogin`
+    Rlogin = 513,
+    /// `Rsh`
+    Rsh = 514,
+}
+
+impl From<LoginTCPPortValue> for u32 {
+    fn from(value: LoginTCPPortValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for LoginTCPPortValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            23 => Ok(LoginTCPPortValue::Telnet),
+            513 => Ok(LoginTCPPortValue::Rlogin),
+            514 => Ok(LoginTCPPortValue::Rsh),
+            _ => Err(AVPError::UnknownValueError(
+                "Login-TCP-Port".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type NasPortType = u32;
 pub const NAS_PORT_TYPE_ASYNC: NasPortType = 0;
 pub const NAS_PORT_TYPE_SYNC: NasPortType = 1;
@@ -1226,6 +1586,114 @@ pub const NAS_PORT_TYPE_CABLE: NasPortType = 17;
 pub const NAS_PORT_TYPE_WIRELESS_OTHER: NasPortType = 18;
 pub const NAS_PORT_TYPE_WIRELESS_802_11: NasPortType = 19;
 
+/// The values of `NAS-Port-Type` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `NasPortTypeValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum NasPortTypeValue {
+    /// `Async`
+    Async = 0,
+    /// `Sync`
+    Sync = 1,
+    /// `ISDN`
+    Isdn = 2,
+    /// `ISDN-V120`
+    IsdnV120 = 3,
+    /// `ISDN-V110`
+    IsdnV110 = 4,
+    /// `Virtual`
+    Virtual = 5,
+    /// `PIAFS`
+    Piafs = 6,
+    /// `HDLC-Clear-Channel`
+    HdlcClearChannel = 7,
+    /// `X.25`
+    X25 = 8,
+    /// `X.75`
+    X75 = 9,
+    /// `G.3-Fax`
+    G3Fax = 10,
+    /// `SDSL`
+    Sdsl = 11,
+    /// `ADSL-CAP`
+    AdslCap = 12,
+    /// `ADSL-DMT`
+    AdslDmt = 13,
+    /// `IDSL`
+    Idsl = 14,
+    /// `Ethernet`
+    Ethernet = 15,
+    /// `xDSL`
+    Xdsl = 16,
+    /// `Cable`
+    Cable = 17,
+    /// `Wireless-Other`
+    WirelessOther = 18,
+    /// `Wireless-802.11`
+    Wireless80211 = 19,
+    /// `Token-Ring`
+    TokenRing = 20,
+    /// `FDDI`
+    Fddi = 21,
+    /// `PPPoA`
+    PppoA = 30,
+    /// `PPPoEoA`
+    PppoEoA = 31,
+    /// `PPPoEoE`
+    PppoEoE = 32,
+    /// `PPPoEoVLAN`
+    PppoEoVLAN = 33,
+    /// `PPPoEoQinQ`
+    PppoEoQinQ = 34,
+}
+
+impl From<NasPortTypeValue> for u32 {
+    fn from(value: NasPortTypeValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for NasPortTypeValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(NasPortTypeValue::Async),
+            1 => Ok(NasPortTypeValue::Sync),
+            2 => Ok(NasPortTypeValue::Isdn),
+            3 => Ok(NasPortTypeValue::IsdnV120),
+            4 => Ok(NasPortTypeValue::IsdnV110),
+            5 => Ok(NasPortTypeValue::Virtual),
+            6 => Ok(NasPortTypeValue::Piafs),
+            7 => Ok(NasPortTypeValue::HdlcClearChannel),
+            8 => Ok(NasPortTypeValue::X25),
+            9 => Ok(NasPortTypeValue::X75),
+            10 => Ok(NasPortTypeValue::G3Fax),
+            11 => Ok(NasPortTypeValue::Sdsl),
+            12 => Ok(NasPortTypeValue::AdslCap),
+            13 => Ok(NasPortTypeValue::AdslDmt),
+            14 => Ok(NasPortTypeValue::Idsl),
+            15 => Ok(NasPortTypeValue::Ethernet),
+            16 => Ok(NasPortTypeValue::Xdsl),
+            17 => Ok(NasPortTypeValue::Cable),
+            18 => Ok(NasPortTypeValue::WirelessOther),
+            19 => Ok(NasPortTypeValue::Wireless80211),
+            20 => Ok(NasPortTypeValue::TokenRing),
+            21 => Ok(NasPortTypeValue::Fddi),
+            30 => Ok(NasPortTypeValue::PppoA),
+            31 => Ok(NasPortTypeValue::PppoEoA),
+            32 => Ok(NasPortTypeValue::PppoEoE),
+            33 => Ok(NasPortTypeValue::PppoEoVLAN),
+            34 => Ok(NasPortTypeValue::PppoEoQinQ),
+            _ => Err(AVPError::UnknownValueError(
+                "NAS-Port-Type".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type ServiceType = u32;
 pub const SERVICE_TYPE_LOGIN_USER: ServiceType = 1;
 pub const SERVICE_TYPE_FRAMED_USER: ServiceType = 2;
@@ -1239,6 +1707,224 @@ pub const SERVICE_TYPE_CALLBACK_NAS_PROMPT: ServiceType = 9;
 pub const SERVICE_TYPE_CALL_CHECK: ServiceType = 10;
 pub const SERVICE_TYPE_CALLBACK_ADMINISTRATIVE: ServiceType = 11;
 
+/// The values of `Service-Type` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `ServiceTypeValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ServiceTypeValue {
+    /// `Login-User`
+    LoginUser = 1,
+    /// `Framed-User`
+    FramedUser = 2,
+    /// `Callback-Login-User`
+    CallbackLoginUser = 3,
+    /// `Callback-Framed-User`
+    CallbackFramedUser = 4,
+    /// `Outbound-User`
+    OutboundUser = 5,
+    /// `Administrative-User`
+    AdministrativeUser = 6,
+    /// `NAS-Prompt-User`
+    NasPromptUser = 7,
+    /// `Authenticate-Only`
+    AuthenticateOnly = 8,
+    /// `Callback-NAS-Prompt`
+    CallbackNASPrompt = 9,
+    /// `Call-Check`
+    CallCheck = 10,
+    /// `Callback-Administrative`
+    CallbackAdministrative = 11,
+    /// `Authorize-Only`
+    AuthorizeOnly = 17,
+    /// `Framed-Management`
+    FramedManagement = 18,
+}
+
+impl From<ServiceTypeValue> for u32 {
+    fn from(value: ServiceTypeValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for ServiceTypeValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(ServiceTypeValue::LoginUser),
+            2 => Ok(ServiceTypeValue::FramedUser),
+            3 => Ok(ServiceTypeValue::CallbackLoginUser),
+            4 => Ok(ServiceTypeValue::CallbackFramedUser),
+            5 => Ok(ServiceTypeValue::OutboundUser),
+            6 => Ok(ServiceTypeValue::AdministrativeUser),
+            7 => Ok(ServiceTypeValue::NasPromptUser),
+            8 => Ok(ServiceTypeValue::AuthenticateOnly),
+            9 => Ok(ServiceTypeValue::CallbackNASPrompt),
+            10 => Ok(ServiceTypeValue::CallCheck),
+            11 => Ok(ServiceTypeValue::CallbackAdministrative),
+            17 => Ok(ServiceTypeValue::AuthorizeOnly),
+            18 => Ok(ServiceTypeValue::FramedManagement),
+            _ => Err(AVPError::UnknownValueError(
+                "Service-Type".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type TerminationAction = u32;
 pub const TERMINATION_ACTION_DEFAULT: TerminationAction = 0;
 pub const TERMINATION_ACTION_RADIUS_REQUEST: TerminationAction = 1;
+
+/// The values of `Termination-Action` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `TerminationActionValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum TerminationActionValue {
+    /// `Default`
+    Default = 0,
+    /// `RADIUS-Request`
+    RadiusRequest = 1,
+}
+
+impl From<TerminationActionValue> for u32 {
+    fn from(value: TerminationActionValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for TerminationActionValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TerminationActionValue::Default),
+            1 => Ok(TerminationActionValue::RadiusRequest),
+            _ => Err(AVPError::UnknownValueError(
+                "Termination-Action".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("User-Name", USER_NAME_TYPE, "string"),
+    AttributeDefinition::new("User-Password", USER_PASSWORD_TYPE, "string"),
+    AttributeDefinition::new("CHAP-Password", CHAP_PASSWORD_TYPE, "octets"),
+    AttributeDefinition::new("NAS-IP-Address", NAS_IP_ADDRESS_TYPE, "ipaddr"),
+    AttributeDefinition::new("NAS-Port", NAS_PORT_TYPE, "integer"),
+    AttributeDefinition::new("Service-Type", SERVICE_TYPE_TYPE, "integer"),
+    AttributeDefinition::new("Framed-Protocol", FRAMED_PROTOCOL_TYPE, "integer"),
+    AttributeDefinition::new("Framed-IP-Address", FRAMED_IP_ADDRESS_TYPE, "ipaddr"),
+    AttributeDefinition::new("Framed-IP-Netmask", FRAMED_IP_NETMASK_TYPE, "ipaddr"),
+    AttributeDefinition::new("Framed-Routing", FRAMED_ROUTING_TYPE, "integer"),
+    AttributeDefinition::new("Filter-Id", FILTER_ID_TYPE, "string"),
+    AttributeDefinition::new("Framed-MTU", FRAMED_MTU_TYPE, "integer"),
+    AttributeDefinition::new("Framed-Compression", FRAMED_COMPRESSION_TYPE, "integer"),
+    AttributeDefinition::new("Login-IP-Host", LOGIN_IP_HOST_TYPE, "ipaddr"),
+    AttributeDefinition::new("Login-Service", LOGIN_SERVICE_TYPE, "integer"),
+    AttributeDefinition::new("Login-TCP-Port", LOGIN_TCP_PORT_TYPE, "integer"),
+    AttributeDefinition::new("Reply-Message", REPLY_MESSAGE_TYPE, "string"),
+    AttributeDefinition::new("Callback-Number", CALLBACK_NUMBER_TYPE, "string"),
+    AttributeDefinition::new("Callback-Id", CALLBACK_ID_TYPE, "string"),
+    AttributeDefinition::new("Framed-Route", FRAMED_ROUTE_TYPE, "string"),
+    AttributeDefinition::new("Framed-IPX-Network", FRAMED_IPX_NETWORK_TYPE, "ipaddr"),
+    AttributeDefinition::new("State", STATE_TYPE, "octets"),
+    AttributeDefinition::new("Class", CLASS_TYPE, "octets"),
+    AttributeDefinition::new("Vendor-Specific", VENDOR_SPECIFIC_TYPE, "vsa"),
+    AttributeDefinition::new("Session-Timeout", SESSION_TIMEOUT_TYPE, "integer"),
+    AttributeDefinition::new("Idle-Timeout", IDLE_TIMEOUT_TYPE, "integer"),
+    AttributeDefinition::new("Termination-Action", TERMINATION_ACTION_TYPE, "integer"),
+    AttributeDefinition::new("Called-Station-Id", CALLED_STATION_ID_TYPE, "string"),
+    AttributeDefinition::new("Calling-Station-Id", CALLING_STATION_ID_TYPE, "string"),
+    AttributeDefinition::new("NAS-Identifier", NAS_IDENTIFIER_TYPE, "string"),
+    AttributeDefinition::new("Proxy-State", PROXY_STATE_TYPE, "octets"),
+    AttributeDefinition::new("Login-LAT-Service", LOGIN_LAT_SERVICE_TYPE, "string"),
+    AttributeDefinition::new("Login-LAT-Node", LOGIN_LAT_NODE_TYPE, "string"),
+    AttributeDefinition::new("Login-LAT-Group", LOGIN_LAT_GROUP_TYPE, "octets"),
+    AttributeDefinition::new(
+        "Framed-AppleTalk-Link",
+        FRAMED_APPLE_TALK_LINK_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "Framed-AppleTalk-Network",
+        FRAMED_APPLE_TALK_NETWORK_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "Framed-AppleTalk-Zone",
+        FRAMED_APPLE_TALK_ZONE_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("CHAP-Challenge", CHAP_CHALLENGE_TYPE, "octets"),
+    AttributeDefinition::new("NAS-Port-Type", NAS_PORT_TYPE_TYPE, "integer"),
+    AttributeDefinition::new("Port-Limit", PORT_LIMIT_TYPE, "integer"),
+    AttributeDefinition::new("Login-LAT-Port", LOGIN_LAT_PORT_TYPE, "string"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Framed-Compression", "None", 0),
+    ValueDefinition::new("Framed-Compression", "Van-Jacobson-TCP-IP", 1),
+    ValueDefinition::new("Framed-Compression", "IPX-Header-Compression", 2),
+    ValueDefinition::new("Framed-Compression", "Stac-LZS", 3),
+    ValueDefinition::new("Framed-Protocol", "PPP", 1),
+    ValueDefinition::new("Framed-Protocol", "SLIP", 2),
+    ValueDefinition::new("Framed-Protocol", "ARAP", 3),
+    ValueDefinition::new("Framed-Protocol", "Gandalf-SLML", 4),
+    ValueDefinition::new("Framed-Protocol", "Xylogics-IPX-SLIP", 5),
+    ValueDefinition::new("Framed-Protocol", "X.75-Synchronous", 6),
+    ValueDefinition::new("Framed-Routing", "None", 0),
+    ValueDefinition::new("Framed-Routing", "Broadcast", 1),
+    ValueDefinition::new("Framed-Routing", "Listen", 2),
+    ValueDefinition::new("Framed-Routing", "Broadcast-Listen", 3),
+    ValueDefinition::new("Login-Service", "Telnet", 0),
+    ValueDefinition::new("Login-Service", "Rlogin", 1),
+    ValueDefinition::new("Login-Service", "TCP-Clear", 2),
+    ValueDefinition::new("Login-Service", "PortMaster", 3),
+    ValueDefinition::new("Login-Service", "LAT", 4),
+    ValueDefinition::new("Login-Service", "X25-PAD", 5),
+    ValueDefinition::new("Login-Service", "X25-T3POS", 6),
+    ValueDefinition::new("Login-Service", "TCP-Clear-Quiet", 8),
+    ValueDefinition::new("Login-TCP-Port", "Telnet", 23),
+    ValueDefinition::new("Login-TCP-Port", "Rlogin", 513),
+    ValueDefinition::new("Login-TCP-Port", "Rsh", 514),
+    ValueDefinition::new("NAS-Port-Type", "Async", 0),
+    ValueDefinition::new("NAS-Port-Type", "Sync", 1),
+    ValueDefinition::new("NAS-Port-Type", "ISDN", 2),
+    ValueDefinition::new("NAS-Port-Type", "ISDN-V120", 3),
+    ValueDefinition::new("NAS-Port-Type", "ISDN-V110", 4),
+    ValueDefinition::new("NAS-Port-Type", "Virtual", 5),
+    ValueDefinition::new("NAS-Port-Type", "PIAFS", 6),
+    ValueDefinition::new("NAS-Port-Type", "HDLC-Clear-Channel", 7),
+    ValueDefinition::new("NAS-Port-Type", "X.25", 8),
+    ValueDefinition::new("NAS-Port-Type", "X.75", 9),
+    ValueDefinition::new("NAS-Port-Type", "G.3-Fax", 10),
+    ValueDefinition::new("NAS-Port-Type", "SDSL", 11),
+    ValueDefinition::new("NAS-Port-Type", "ADSL-CAP", 12),
+    ValueDefinition::new("NAS-Port-Type", "ADSL-DMT", 13),
+    ValueDefinition::new("NAS-Port-Type", "IDSL", 14),
+    ValueDefinition::new("NAS-Port-Type", "Ethernet", 15),
+    ValueDefinition::new("NAS-Port-Type", "xDSL", 16),
+    ValueDefinition::new("NAS-Port-Type", "Cable", 17),
+    ValueDefinition::new("NAS-Port-Type", "Wireless-Other", 18),
+    ValueDefinition::new("NAS-Port-Type", "Wireless-802.11", 19),
+    ValueDefinition::new("Service-Type", "Login-User", 1),
+    ValueDefinition::new("Service-Type", "Framed-User", 2),
+    ValueDefinition::new("Service-Type", "Callback-Login-User", 3),
+    ValueDefinition::new("Service-Type", "Callback-Framed-User", 4),
+    ValueDefinition::new("Service-Type", "Outbound-User", 5),
+    ValueDefinition::new("Service-Type", "Administrative-User", 6),
+    ValueDefinition::new("Service-Type", "NAS-Prompt-User", 7),
+    ValueDefinition::new("Service-Type", "Authenticate-Only", 8),
+    ValueDefinition::new("Service-Type", "Callback-NAS-Prompt", 9),
+    ValueDefinition::new("Service-Type", "Call-Check", 10),
+    ValueDefinition::new("Service-Type", "Callback-Administrative", 11),
+    ValueDefinition::new("Termination-Action", "Default", 0),
+    ValueDefinition::new("Termination-Action", "RADIUS-Request", 1),
+];
@@ -66,7 +66,12 @@
 //! VALUE    Acct-Terminate-Cause        Host-Request        18
 //! ```
 
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const ACCT_STATUS_TYPE_TYPE: AVPType = 40;
@@ -94,6 +99,26 @@ pub fn lookup_all_acct_status_type(packet: &Packet) -> Result<Vec<AcctStatusType
     }
     Ok(vec)
 }
+/// Lookup a `acct_status_type` value from a packet as `AcctStatusTypeValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `acct_status_type`, it returns `None`.
+pub fn lookup_acct_status_type_value(
+    packet: &Packet,
+) -> Option<Result<AcctStatusTypeValue, AVPError>> {
+    packet
+        .lookup(ACCT_STATUS_TYPE_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `acct_status_type` values from a packet as `AcctStatusTypeValue`.
+pub fn lookup_all_acct_status_type_value(
+    packet: &Packet,
+) -> Result<Vec<AcctStatusTypeValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(ACCT_STATUS_TYPE_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const ACCT_DELAY_TIME_TYPE: AVPType = 41;
 /// Delete all of `acct_delay_time` values from a packet.
@@ -222,6 +247,26 @@ pub fn lookup_all_acct_authentic(packet: &Packet) -> Result<Vec<AcctAuthentic>,
     }
     Ok(vec)
 }
+/// Lookup a `acct_authentic` value from a packet as `AcctAuthenticValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `acct_authentic`, it returns `None`.
+pub fn lookup_acct_authentic_value(
+    packet: &Packet,
+) -> Option<Result<AcctAuthenticValue, AVPError>> {
+    packet
+        .lookup(ACCT_AUTHENTIC_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `acct_authentic` values from a packet as `AcctAuthenticValue`.
+pub fn lookup_all_acct_authentic_value(
+    packet: &Packet,
+) -> Result<Vec<AcctAuthenticValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(ACCT_AUTHENTIC_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const ACCT_SESSION_TIME_TYPE: AVPType = 46;
 /// Delete all of `acct_session_time` values from a packet.
@@ -330,6 +375,26 @@ pub fn lookup_all_acct_terminate_cause(
     }
     Ok(vec)
 }
+/// Lookup a `acct_terminate_cause` value from a packet as `AcctTerminateCauseValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `acct_terminate_cause`, it returns `None`.
+pub fn lookup_acct_terminate_cause_value(
+    packet: &Packet,
+) -> Option<Result<AcctTerminateCauseValue, AVPError>> {
+    packet
+        .lookup(ACCT_TERMINATE_CAUSE_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `acct_terminate_cause` values from a packet as `AcctTerminateCauseValue`.
+pub fn lookup_all_acct_terminate_cause_value(
+    packet: &Packet,
+) -> Result<Vec<AcctTerminateCauseValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(ACCT_TERMINATE_CAUSE_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const ACCT_MULTI_SESSION_ID_TYPE: AVPType = 50;
 /// Delete all of `acct_multi_session_id` values from a packet.
@@ -387,6 +452,45 @@ pub const ACCT_AUTHENTIC_LOCAL: AcctAuthentic = 2;
 pub const ACCT_AUTHENTIC_REMOTE: AcctAuthentic = 3;
 pub const ACCT_AUTHENTIC_DIAMETER: AcctAuthentic = 4;
 
+/// The values of `Acct-Authentic` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `AcctAuthenticValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum AcctAuthenticValue {
+    /// `RADIUS`
+    Radius = 1,
+    /// `Local`
+    Local = 2,
+    /// `Remote`
+    Remote = 3,
+    /// `Diameter`
+    Diameter = 4,
+}
+
+impl From<AcctAuthenticValue> for u32 {
+    fn from(value: AcctAuthenticValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for AcctAuthenticValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(AcctAuthenticValue::Radius),
+            2 => Ok(AcctAuthenticValue::Local),
+            3 => Ok(AcctAuthenticValue::Remote),
+            4 => Ok(AcctAuthenticValue::Diameter),
+            _ => Err(AVPError::UnknownValueError(
+                "Acct-Authentic".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type AcctStatusType = u32;
 pub const ACCT_STATUS_TYPE_START: AcctStatusType = 1;
 pub const ACCT_STATUS_TYPE_STOP: AcctStatusType = 2;
@@ -396,6 +500,69 @@ pub const ACCT_STATUS_TYPE_ACCOUNTING_ON: AcctStatusType = 7;
 pub const ACCT_STATUS_TYPE_ACCOUNTING_OFF: AcctStatusType = 8;
 pub const ACCT_STATUS_TYPE_FAILED: AcctStatusType = 15;
 
+/// The values of `Acct-Status-Type` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `AcctStatusTypeValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum AcctStatusTypeValue {
+    /// `Start`
+    Start = 1,
+    /// `Stop`
+    Stop = 2,
+    /// `Alive`
+    Alive = 3,
+    /// `Accounting-On`
+    AccountingOn = 7,
+    /// `Accounting-Off`
+    AccountingOff = 8,
+    /// `Failed`
+    Failed = 15,
+    /// `Tunnel-Start`
+    TunnelStart = 9,
+    /// `Tunnel-Stop`
+    TunnelStop = 10,
+    /// `Tunnel-Reject`
+    TunnelReject = 11,
+    /// `Tunnel-Link-Start`
+    TunnelLinkStart = 12,
+    /// `Tunnel-Link-Stop`
+    TunnelLinkStop = 13,
+    /// `Tunnel-Link-Reject`
+    TunnelLinkReject = 14,
+}
+
+impl From<AcctStatusTypeValue> for u32 {
+    fn from(value: AcctStatusTypeValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for AcctStatusTypeValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(AcctStatusTypeValue::Start),
+            2 => Ok(AcctStatusTypeValue::Stop),
+            3 => Ok(AcctStatusTypeValue::Alive),
+            7 => Ok(AcctStatusTypeValue::AccountingOn),
+            8 => Ok(AcctStatusTypeValue::AccountingOff),
+            15 => Ok(AcctStatusTypeValue::Failed),
+            9 => Ok(AcctStatusTypeValue::TunnelStart),
+            10 => Ok(AcctStatusTypeValue::TunnelStop),
+            11 => Ok(AcctStatusTypeValue::TunnelReject),
+            12 => Ok(AcctStatusTypeValue::TunnelLinkStart),
+            13 => Ok(AcctStatusTypeValue::TunnelLinkStop),
+            14 => Ok(AcctStatusTypeValue::TunnelLinkReject),
+            _ => Err(AVPError::UnknownValueError(
+                "Acct-Status-Type".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type AcctTerminateCause = u32;
 pub const ACCT_TERMINATE_CAUSE_USER_REQUEST: AcctTerminateCause = 1;
 pub const ACCT_TERMINATE_CAUSE_LOST_CARRIER: AcctTerminateCause = 2;
@@ -415,3 +582,149 @@ pub const ACCT_TERMINATE_CAUSE_SERVICE_UNAVAILABLE: AcctTerminateCause = 15;
 pub const ACCT_TERMINATE_CAUSE_CALLBACK: AcctTerminateCause = 16;
 pub const ACCT_TERMINATE_CAUSE_USER_ERROR: AcctTerminateCause = 17;
 pub const ACCT_TERMINATE_CAUSE_HOST_REQUEST: AcctTerminateCause = 18;
+
+/// The values of `Acct-Terminate-Cause` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `AcctTerminateCauseValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum AcctTerminateCauseValue {
+    /// `User-Request`
+    UserRequest = 1,
+    /// `Lost-Carrier`
+    LostCarrier = 2,
+    /// `Lost-Service`
+    LostService = 3,
+    /// `Idle-Timeout`
+    IdleTimeout = 4,
+    /// `Session-Timeout`
+    SessionTimeout = 5,
+    /// `Admin-Reset`
+    AdminReset = 6,
+    /// `Admin-Reboot`
+    AdminReboot = 7,
+    /// `Port-Error`
+    PortError = 8,
+    /// `NAS-Error`
+    NasError = 9,
+    /// `NAS-Request`
+    NasRequest = 10,
+    /// `NAS-Reboot`
+    NasReboot = 11,
+    /// `Port-Unneeded`
+    PortUnneeded = 12,
+    /// `Port-Preempted`
+    PortPreempted = 13,
+    /// `Port-Suspended`
+    PortSuspended = 14,
+    /// `Service-Unavailable`
+    ServiceUnavailable = 15,
+    /// `Callback`
+    Callback = 16,
+    /// `User-Error`
+    UserError = 17,
+    /// `Host-Request`
+    HostRequest = 18,
+    /// `Supplicant-Restart`
+    SupplicantRestart = 19,
+    /// `Reauthentication-Failure`
+    ReauthenticationFailure = 20,
+    /// `Port-Reinit`
+    PortReinit = 21,
+    /// `Port-Disabled`
+    PortDisabled = 22,
+}
+
+impl From<AcctTerminateCauseValue> for u32 {
+    fn from(value: AcctTerminateCauseValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for AcctTerminateCauseValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(AcctTerminateCauseValue::UserRequest),
+            2 => Ok(AcctTerminateCauseValue::LostCarrier),
+            3 => Ok(AcctTerminateCauseValue::LostService),
+            4 => Ok(AcctTerminateCauseValue::IdleTimeout),
+            5 => Ok(AcctTerminateCauseValue::SessionTimeout),
+            6 => Ok(AcctTerminateCauseValue::AdminReset),
+            7 => Ok(AcctTerminateCauseValue::AdminReboot),
+            8 => Ok(AcctTerminateCauseValue::PortError),
+            9 => Ok(AcctTerminateCauseValue::NasError),
+            10 => Ok(AcctTerminateCauseValue::NasRequest),
+            11 => Ok(AcctTerminateCauseValue::NasReboot),
+            12 => Ok(AcctTerminateCauseValue::PortUnneeded),
+            13 => Ok(AcctTerminateCauseValue::PortPreempted),
+            14 => Ok(AcctTerminateCauseValue::PortSuspended),
+            15 => Ok(AcctTerminateCauseValue::ServiceUnavailable),
+            16 => Ok(AcctTerminateCauseValue::Callback),
+            17 => Ok(AcctTerminateCauseValue::UserError),
+            18 => Ok(AcctTerminateCauseValue::HostRequest),
+            19 => Ok(AcctTerminateCauseValue::SupplicantRestart),
+            20 => Ok(AcctTerminateCauseValue::ReauthenticationFailure),
+            21 => Ok(AcctTerminateCauseValue::PortReinit),
+            22 => Ok(AcctTerminateCauseValue::PortDisabled),
+            _ => Err(AVPError::UnknownValueError(
+                "Acct-Terminate-Cause".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Acct-Status-Type", ACCT_STATUS_TYPE_TYPE, "integer"),
+    AttributeDefinition::new("Acct-Delay-Time", ACCT_DELAY_TIME_TYPE, "integer"),
+    AttributeDefinition::new("Acct-Input-Octets", ACCT_INPUT_OCTETS_TYPE, "integer"),
+    AttributeDefinition::new("Acct-Output-Octets", ACCT_OUTPUT_OCTETS_TYPE, "integer"),
+    AttributeDefinition::new("Acct-Session-Id", ACCT_SESSION_ID_TYPE, "string"),
+    AttributeDefinition::new("Acct-Authentic", ACCT_AUTHENTIC_TYPE, "integer"),
+    AttributeDefinition::new("Acct-Session-Time", ACCT_SESSION_TIME_TYPE, "integer"),
+    AttributeDefinition::new("Acct-Input-Packets", ACCT_INPUT_PACKETS_TYPE, "integer"),
+    AttributeDefinition::new("Acct-Output-Packets", ACCT_OUTPUT_PACKETS_TYPE, "integer"),
+    AttributeDefinition::new("Acct-Terminate-Cause", ACCT_TERMINATE_CAUSE_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Acct-Multi-Session-Id",
+        ACCT_MULTI_SESSION_ID_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("Acct-Link-Count", ACCT_LINK_COUNT_TYPE, "integer"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Acct-Authentic", "RADIUS", 1),
+    ValueDefinition::new("Acct-Authentic", "Local", 2),
+    ValueDefinition::new("Acct-Authentic", "Remote", 3),
+    ValueDefinition::new("Acct-Authentic", "Diameter", 4),
+    ValueDefinition::new("Acct-Status-Type", "Start", 1),
+    ValueDefinition::new("Acct-Status-Type", "Stop", 2),
+    ValueDefinition::new("Acct-Status-Type", "Alive", 3),
+    ValueDefinition::new("Acct-Status-Type", "Interim-Update", 3),
+    ValueDefinition::new("Acct-Status-Type", "Accounting-On", 7),
+    ValueDefinition::new("Acct-Status-Type", "Accounting-Off", 8),
+    ValueDefinition::new("Acct-Status-Type", "Failed", 15),
+    ValueDefinition::new("Acct-Terminate-Cause", "User-Request", 1),
+    ValueDefinition::new("Acct-Terminate-Cause", "Lost-Carrier", 2),
+    ValueDefinition::new("Acct-Terminate-Cause", "Lost-Service", 3),
+    ValueDefinition::new("Acct-Terminate-Cause", "Idle-Timeout", 4),
+    ValueDefinition::new("Acct-Terminate-Cause", "Session-Timeout", 5),
+    ValueDefinition::new("Acct-Terminate-Cause", "Admin-Reset", 6),
+    ValueDefinition::new("Acct-Terminate-Cause", "Admin-Reboot", 7),
+    ValueDefinition::new("Acct-Terminate-Cause", "Port-Error", 8),
+    ValueDefinition::new("Acct-Terminate-Cause", "NAS-Error", 9),
+    ValueDefinition::new("Acct-Terminate-Cause", "NAS-Request", 10),
+    ValueDefinition::new("Acct-Terminate-Cause", "NAS-Reboot", 11),
+    ValueDefinition::new("Acct-Terminate-Cause", "Port-Unneeded", 12),
+    ValueDefinition::new("Acct-Terminate-Cause", "Port-Preempted", 13),
+    ValueDefinition::new("Acct-Terminate-Cause", "Port-Suspended", 14),
+    ValueDefinition::new("Acct-Terminate-Cause", "Service-Unavailable", 15),
+    ValueDefinition::new("Acct-Terminate-Cause", "Callback", 16),
+    ValueDefinition::new("Acct-Terminate-Cause", "User-Error", 17),
+    ValueDefinition::new("Acct-Terminate-Cause", "Host-Request", 18),
+];
@@ -25,7 +25,11 @@
 //! VALUE    Acct-Status-Type        Tunnel-Link-Reject    14
 //! ```
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 use crate::core::rfc2866;
@@ -87,3 +91,27 @@ pub const ACCT_STATUS_TYPE_TUNNEL_REJECT: rfc2866::AcctStatusType = 11;
 pub const ACCT_STATUS_TYPE_TUNNEL_LINK_START: rfc2866::AcctStatusType = 12;
 pub const ACCT_STATUS_TYPE_TUNNEL_LINK_STOP: rfc2866::AcctStatusType = 13;
 pub const ACCT_STATUS_TYPE_TUNNEL_LINK_REJECT: rfc2866::AcctStatusType = 14;
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new(
+        "Acct-Tunnel-Connection",
+        ACCT_TUNNEL_CONNECTION_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Acct-Tunnel-Packets-Lost",
+        ACCT_TUNNEL_PACKETS_LOST_TYPE,
+        "integer",
+    ),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Acct-Status-Type", "Tunnel-Start", 9),
+    ValueDefinition::new("Acct-Status-Type", "Tunnel-Stop", 10),
+    ValueDefinition::new("Acct-Status-Type", "Tunnel-Reject", 11),
+    ValueDefinition::new("Acct-Status-Type", "Tunnel-Link-Start", 12),
+    ValueDefinition::new("Acct-Status-Type", "Tunnel-Link-Stop", 13),
+    ValueDefinition::new("Acct-Status-Type", "Tunnel-Link-Reject", 14),
+];
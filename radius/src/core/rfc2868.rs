@@ -63,7 +63,12 @@
 //! VALUE    Tunnel-Medium-Type        E.164-NSAP        15
 //! ```
 
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 use crate::core::tag::Tag;
 
@@ -94,6 +99,28 @@ pub fn lookup_all_tunnel_type(packet: &Packet) -> Result<Vec<(TunnelType, Tag)>,
     }
     Ok(vec)
 }
+/// Lookup a `tunnel_type` tagged value from a packet as `TunnelTypeValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `tunnel_type`, it returns `None`.
+pub fn lookup_tunnel_type_value(
+    packet: &Packet,
+) -> Option<Result<(TunnelTypeValue, Tag), AVPError>> {
+    packet.lookup(TUNNEL_TYPE_TYPE).map(|v| {
+        let (v, t) = v.encode_tagged_u32()?;
+        Ok((core::convert::TryFrom::try_from(v)?, t))
+    })
+}
+/// Lookup all of the `tunnel_type` tagged values from a packet as `TunnelTypeValue`.
+pub fn lookup_all_tunnel_type_value(
+    packet: &Packet,
+) -> Result<Vec<(TunnelTypeValue, Tag)>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(TUNNEL_TYPE_TYPE) {
+        let (v, t) = avp.encode_tagged_u32()?;
+        vec.push((core::convert::TryFrom::try_from(v)?, t))
+    }
+    Ok(vec)
+}
 
 pub const TUNNEL_MEDIUM_TYPE_TYPE: AVPType = 65;
 /// Delete all of `tunnel_medium_type` values from a packet.
@@ -126,6 +153,28 @@ pub fn lookup_all_tunnel_medium_type(
     }
     Ok(vec)
 }
+/// Lookup a `tunnel_medium_type` tagged value from a packet as `TunnelMediumTypeValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `tunnel_medium_type`, it returns `None`.
+pub fn lookup_tunnel_medium_type_value(
+    packet: &Packet,
+) -> Option<Result<(TunnelMediumTypeValue, Tag), AVPError>> {
+    packet.lookup(TUNNEL_MEDIUM_TYPE_TYPE).map(|v| {
+        let (v, t) = v.encode_tagged_u32()?;
+        Ok((core::convert::TryFrom::try_from(v)?, t))
+    })
+}
+/// Lookup all of the `tunnel_medium_type` tagged values from a packet as `TunnelMediumTypeValue`.
+pub fn lookup_all_tunnel_medium_type_value(
+    packet: &Packet,
+) -> Result<Vec<(TunnelMediumTypeValue, Tag)>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(TUNNEL_MEDIUM_TYPE_TYPE) {
+        let (v, t) = avp.encode_tagged_u32()?;
+        vec.push((core::convert::TryFrom::try_from(v)?, t))
+    }
+    Ok(vec)
+}
 
 pub const TUNNEL_CLIENT_ENDPOINT_TYPE: AVPType = 66;
 /// Delete all of `tunnel_client_endpoint` values from a packet.
@@ -412,6 +461,78 @@ pub const TUNNEL_MEDIUM_TYPE_DEC_NET_IV: TunnelMediumType = 13;
 pub const TUNNEL_MEDIUM_TYPE_BANYAN_VINES: TunnelMediumType = 14;
 pub const TUNNEL_MEDIUM_TYPE_E_164_NSAP: TunnelMediumType = 15;
 
+/// The values of `Tunnel-Medium-Type` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `TunnelMediumTypeValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum TunnelMediumTypeValue {
+    /// `IP`
+    Ip = 1,
+    /// `IPv6`
+    Ipv6 = 2,
+    /// `NSAP`
+    Nsap = 3,
+    /// `HDLC`
+    Hdlc = 4,
+    /// `BBN-1822`
+    Bbn1822 = 5,
+    /// `IEEE-802`
+    Ieee802 = 6,
+    /// `E.163`
+    E163 = 7,
+    /// `E.164`
+    E164 = 8,
+    /// `F.69`
+    F69 = 9,
+    /// `X.121`
+    X121 = 10,
+    /// `IPX`
+    Ipx = 11,
+    /// `Appletalk`
+    Appletalk = 12,
+    /// `DecNet-IV`
+    DecNetIV = 13,
+    /// `Banyan-Vines`
+    BanyanVines = 14,
+    /// `E.164-NSAP`
+    E164Nsap = 15,
+}
+
+impl From<TunnelMediumTypeValue> for u32 {
+    fn from(value: TunnelMediumTypeValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for TunnelMediumTypeValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(TunnelMediumTypeValue::Ip),
+            2 => Ok(TunnelMediumTypeValue::Ipv6),
+            3 => Ok(TunnelMediumTypeValue::Nsap),
+            4 => Ok(TunnelMediumTypeValue::Hdlc),
+            5 => Ok(TunnelMediumTypeValue::Bbn1822),
+            6 => Ok(TunnelMediumTypeValue::Ieee802),
+            7 => Ok(TunnelMediumTypeValue::E163),
+            8 => Ok(TunnelMediumTypeValue::E164),
+            9 => Ok(TunnelMediumTypeValue::F69),
+            10 => Ok(TunnelMediumTypeValue::X121),
+            11 => Ok(TunnelMediumTypeValue::Ipx),
+            12 => Ok(TunnelMediumTypeValue::Appletalk),
+            13 => Ok(TunnelMediumTypeValue::DecNetIV),
+            14 => Ok(TunnelMediumTypeValue::BanyanVines),
+            15 => Ok(TunnelMediumTypeValue::E164Nsap),
+            _ => Err(AVPError::UnknownValueError(
+                "Tunnel-Medium-Type".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type TunnelType = u32;
 pub const TUNNEL_TYPE_PPTP: TunnelType = 1;
 pub const TUNNEL_TYPE_L2F: TunnelType = 2;
@@ -425,3 +546,132 @@ pub const TUNNEL_TYPE_ESP: TunnelType = 9;
 pub const TUNNEL_TYPE_GRE: TunnelType = 10;
 pub const TUNNEL_TYPE_DVS: TunnelType = 11;
 pub const TUNNEL_TYPE_IP_IN_IP: TunnelType = 12;
+
+/// The values of `Tunnel-Type` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `TunnelTypeValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum TunnelTypeValue {
+    /// `PPTP`
+    Pptp = 1,
+    /// `L2F`
+    L2F = 2,
+    /// `L2TP`
+    L2Tp = 3,
+    /// `ATMP`
+    Atmp = 4,
+    /// `VTP`
+    Vtp = 5,
+    /// `AH`
+    Ah = 6,
+    /// `IP`
+    Ip = 7,
+    /// `MIN-IP`
+    MinIp = 8,
+    /// `ESP`
+    Esp = 9,
+    /// `GRE`
+    Gre = 10,
+    /// `DVS`
+    Dvs = 11,
+    /// `IP-in-IP`
+    IpInIP = 12,
+    /// `VLAN`
+    Vlan = 13,
+}
+
+impl From<TunnelTypeValue> for u32 {
+    fn from(value: TunnelTypeValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for TunnelTypeValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(TunnelTypeValue::Pptp),
+            2 => Ok(TunnelTypeValue::L2F),
+            3 => Ok(TunnelTypeValue::L2Tp),
+            4 => Ok(TunnelTypeValue::Atmp),
+            5 => Ok(TunnelTypeValue::Vtp),
+            6 => Ok(TunnelTypeValue::Ah),
+            7 => Ok(TunnelTypeValue::Ip),
+            8 => Ok(TunnelTypeValue::MinIp),
+            9 => Ok(TunnelTypeValue::Esp),
+            10 => Ok(TunnelTypeValue::Gre),
+            11 => Ok(TunnelTypeValue::Dvs),
+            12 => Ok(TunnelTypeValue::IpInIP),
+            13 => Ok(TunnelTypeValue::Vlan),
+            _ => Err(AVPError::UnknownValueError("Tunnel-Type".to_owned(), value)),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Tunnel-Type", TUNNEL_TYPE_TYPE, "integer"),
+    AttributeDefinition::new("Tunnel-Medium-Type", TUNNEL_MEDIUM_TYPE_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Tunnel-Client-Endpoint",
+        TUNNEL_CLIENT_ENDPOINT_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Tunnel-Server-Endpoint",
+        TUNNEL_SERVER_ENDPOINT_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("Tunnel-Password", TUNNEL_PASSWORD_TYPE, "string"),
+    AttributeDefinition::new(
+        "Tunnel-Private-Group-Id",
+        TUNNEL_PRIVATE_GROUP_ID_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("Tunnel-Assignment-Id", TUNNEL_ASSIGNMENT_ID_TYPE, "string"),
+    AttributeDefinition::new("Tunnel-Preference", TUNNEL_PREFERENCE_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Tunnel-Client-Auth-Id",
+        TUNNEL_CLIENT_AUTH_ID_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Tunnel-Server-Auth-Id",
+        TUNNEL_SERVER_AUTH_ID_TYPE,
+        "string",
+    ),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Tunnel-Medium-Type", "IP", 1),
+    ValueDefinition::new("Tunnel-Medium-Type", "IPv4", 1),
+    ValueDefinition::new("Tunnel-Medium-Type", "IPv6", 2),
+    ValueDefinition::new("Tunnel-Medium-Type", "NSAP", 3),
+    ValueDefinition::new("Tunnel-Medium-Type", "HDLC", 4),
+    ValueDefinition::new("Tunnel-Medium-Type", "BBN-1822", 5),
+    ValueDefinition::new("Tunnel-Medium-Type", "IEEE-802", 6),
+    ValueDefinition::new("Tunnel-Medium-Type", "E.163", 7),
+    ValueDefinition::new("Tunnel-Medium-Type", "E.164", 8),
+    ValueDefinition::new("Tunnel-Medium-Type", "F.69", 9),
+    ValueDefinition::new("Tunnel-Medium-Type", "X.121", 10),
+    ValueDefinition::new("Tunnel-Medium-Type", "IPX", 11),
+    ValueDefinition::new("Tunnel-Medium-Type", "Appletalk", 12),
+    ValueDefinition::new("Tunnel-Medium-Type", "DecNet-IV", 13),
+    ValueDefinition::new("Tunnel-Medium-Type", "Banyan-Vines", 14),
+    ValueDefinition::new("Tunnel-Medium-Type", "E.164-NSAP", 15),
+    ValueDefinition::new("Tunnel-Type", "PPTP", 1),
+    ValueDefinition::new("Tunnel-Type", "L2F", 2),
+    ValueDefinition::new("Tunnel-Type", "L2TP", 3),
+    ValueDefinition::new("Tunnel-Type", "ATMP", 4),
+    ValueDefinition::new("Tunnel-Type", "VTP", 5),
+    ValueDefinition::new("Tunnel-Type", "AH", 6),
+    ValueDefinition::new("Tunnel-Type", "IP", 7),
+    ValueDefinition::new("Tunnel-Type", "MIN-IP", 8),
+    ValueDefinition::new("Tunnel-Type", "ESP", 9),
+    ValueDefinition::new("Tunnel-Type", "GRE", 10),
+    ValueDefinition::new("Tunnel-Type", "DVS", 11),
+    ValueDefinition::new("Tunnel-Type", "IP-in-IP", 12),
+];
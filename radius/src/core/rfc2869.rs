@@ -48,9 +48,14 @@
 //! VALUE    Prompt                Echo            1
 //! ```
 
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use chrono::{DateTime, Utc};
 
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const ACCT_INPUT_GIGAWORDS_TYPE: AVPType = 52;
@@ -128,6 +133,39 @@ pub fn lookup_all_event_timestamp(packet: &Packet) -> Result<Vec<DateTime<Utc>>,
     }
     Ok(vec)
 }
+/// Add `event_timestamp` date value of `SystemTime` to a packet.
+///
+/// It fails if the value isn't in the range of the date (i.e. from 1970 to 2106).
+#[cfg(feature = "std")]
+pub fn add_event_timestamp_system_time(
+    packet: &mut Packet,
+    value: &std::time::SystemTime,
+) -> Result<(), AVPError> {
+    packet.add(AVP::from_system_time(EVENT_TIMESTAMP_TYPE, value)?);
+    Ok(())
+}
+/// Lookup a `event_timestamp` date value of `SystemTime` from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `event_timestamp`, it returns `None`.
+#[cfg(feature = "std")]
+pub fn lookup_event_timestamp_system_time(
+    packet: &Packet,
+) -> Option<Result<std::time::SystemTime, AVPError>> {
+    packet
+        .lookup(EVENT_TIMESTAMP_TYPE)
+        .map(|v| v.encode_system_time())
+}
+/// Lookup all of the `event_timestamp` date value of `SystemTime` from a packet.
+#[cfg(feature = "std")]
+pub fn lookup_all_event_timestamp_system_time(
+    packet: &Packet,
+) -> Result<Vec<std::time::SystemTime>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(EVENT_TIMESTAMP_TYPE) {
+        vec.push(avp.encode_system_time()?)
+    }
+    Ok(vec)
+}
 
 pub const ARAP_PASSWORD_TYPE: AVPType = 70;
 /// Delete all of `arap_password` values from a packet.
@@ -216,6 +254,26 @@ pub fn lookup_all_arap_zone_access(packet: &Packet) -> Result<Vec<ArapZoneAccess
     }
     Ok(vec)
 }
+/// Lookup a `arap_zone_access` value from a packet as `ArapZoneAccessValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `arap_zone_access`, it returns `None`.
+pub fn lookup_arap_zone_access_value(
+    packet: &Packet,
+) -> Option<Result<ArapZoneAccessValue, AVPError>> {
+    packet
+        .lookup(ARAP_ZONE_ACCESS_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `arap_zone_access` values from a packet as `ArapZoneAccessValue`.
+pub fn lookup_all_arap_zone_access_value(
+    packet: &Packet,
+) -> Result<Vec<ArapZoneAccessValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(ARAP_ZONE_ACCESS_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const ARAP_SECURITY_TYPE: AVPType = 73;
 /// Delete all of `arap_security` values from a packet.
@@ -316,6 +374,22 @@ pub fn lookup_all_prompt(packet: &Packet) -> Result<Vec<Prompt>, AVPError> {
     }
     Ok(vec)
 }
+/// Lookup a `prompt` value from a packet as `PromptValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `prompt`, it returns `None`.
+pub fn lookup_prompt_value(packet: &Packet) -> Option<Result<PromptValue, AVPError>> {
+    packet
+        .lookup(PROMPT_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `prompt` values from a packet as `PromptValue`.
+pub fn lookup_all_prompt_value(packet: &Packet) -> Result<Vec<PromptValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(PROMPT_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const CONNECT_INFO_TYPE: AVPType = 77;
 /// Delete all of `connect_info` values from a packet.
@@ -529,6 +603,119 @@ pub const ARAP_ZONE_ACCESS_DEFAULT_ZONE: ArapZoneAccess = 1;
 pub const ARAP_ZONE_ACCESS_ZONE_FILTER_INCLUSIVE: ArapZoneAccess = 2;
 pub const ARAP_ZONE_ACCESS_ZONE_FILTER_EXCLUSIVE: ArapZoneAccess = 4;
 
+/// The values of `ARAP-Zone-Access` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `ArapZoneAccessValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ArapZoneAccessValue {
+    /// `Default-Zone`
+    DefaultZone = 1,
+    /// `Zone-Filter-Inclusive`
+    ZoneFilterInclusive = 2,
+    /// `Zone-Filter-Exclusive`
+    ZoneFilterExclusive = 4,
+}
+
+impl From<ArapZoneAccessValue> for u32 {
+    fn from(value: ArapZoneAccessValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for ArapZoneAccessValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(ArapZoneAccessValue::DefaultZone),
+            2 => Ok(ArapZoneAccessValue::ZoneFilterInclusive),
+            4 => Ok(ArapZoneAccessValue::ZoneFilterExclusive),
+            _ => Err(AVPError::UnknownValueError(
+                "ARAP-Zone-Access".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type Prompt = u32;
 pub const PROMPT_NO_ECHO: Prompt = 0;
 pub const PROMPT_ECHO: Prompt = 1;
+
+/// The values of `Prompt` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `PromptValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum PromptValue {
+    /// `No-Echo`
+    NoEcho = 0,
+    /// `Echo`
+    Echo = 1,
+}
+
+impl From<PromptValue> for u32 {
+    fn from(value: PromptValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for PromptValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PromptValue::NoEcho),
+            1 => Ok(PromptValue::Echo),
+            _ => Err(AVPError::UnknownValueError("Prompt".to_owned(), value)),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Acct-Input-Gigawords", ACCT_INPUT_GIGAWORDS_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Acct-Output-Gigawords",
+        ACCT_OUTPUT_GIGAWORDS_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new("Event-Timestamp", EVENT_TIMESTAMP_TYPE, "date"),
+    AttributeDefinition::new("ARAP-Password", ARAP_PASSWORD_TYPE, "octets"),
+    AttributeDefinition::new("ARAP-Features", ARAP_FEATURES_TYPE, "octets"),
+    AttributeDefinition::new("ARAP-Zone-Access", ARAP_ZONE_ACCESS_TYPE, "integer"),
+    AttributeDefinition::new("ARAP-Security", ARAP_SECURITY_TYPE, "integer"),
+    AttributeDefinition::new("ARAP-Security-Data", ARAP_SECURITY_DATA_TYPE, "string"),
+    AttributeDefinition::new("Password-Retry", PASSWORD_RETRY_TYPE, "integer"),
+    AttributeDefinition::new("Prompt", PROMPT_TYPE, "integer"),
+    AttributeDefinition::new("Connect-Info", CONNECT_INFO_TYPE, "string"),
+    AttributeDefinition::new("Configuration-Token", CONFIGURATION_TOKEN_TYPE, "string"),
+    AttributeDefinition::new("EAP-Message", EAP_MESSAGE_TYPE, "octets"),
+    AttributeDefinition::new(
+        "Message-Authenticator",
+        MESSAGE_AUTHENTICATOR_TYPE,
+        "octets",
+    ),
+    AttributeDefinition::new(
+        "ARAP-Challenge-Response",
+        ARAP_CHALLENGE_RESPONSE_TYPE,
+        "octets",
+    ),
+    AttributeDefinition::new(
+        "Acct-Interim-Interval",
+        ACCT_INTERIM_INTERVAL_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new("NAS-Port-Id", NAS_PORT_ID_TYPE, "string"),
+    AttributeDefinition::new("Framed-Pool", FRAMED_POOL_TYPE, "string"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("ARAP-Zone-Access", "Default-Zone", 1),
+    ValueDefinition::new("ARAP-Zone-Access", "Zone-Filter-Inclusive", 2),
+    ValueDefinition::new("ARAP-Zone-Access", "Zone-Filter-Exclusive", 4),
+    ValueDefinition::new("Prompt", "No-Echo", 0),
+    ValueDefinition::new("Prompt", "Echo", 1),
+];
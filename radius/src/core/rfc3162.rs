@@ -22,9 +22,14 @@
 //! ATTRIBUTE    Framed-IPV6-Pool            100    string
 //! ```
 
-use std::net::Ipv6Addr;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::net::Ipv6Addr;
 
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::ipv6::{InterfaceId, Ipv6Prefix};
 use crate::core::packet::Packet;
 
 pub const NAS_IPV6_ADDRESS_TYPE: AVPType = 95;
@@ -85,6 +90,26 @@ pub fn lookup_all_framed_interface_id(packet: &Packet) -> Vec<Vec<u8>> {
     }
     vec
 }
+/// Add `framed_interface_id` interface id value to a packet.
+pub fn add_framed_interface_id_value(packet: &mut Packet, value: &InterfaceId) {
+    packet.add(AVP::from_interface_id(FRAMED_INTERFACE_ID_TYPE, value));
+}
+/// Lookup a `framed_interface_id` interface id value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `framed_interface_id`, it returns `None`.
+pub fn lookup_framed_interface_id_value(packet: &Packet) -> Option<Result<InterfaceId, AVPError>> {
+    packet
+        .lookup(FRAMED_INTERFACE_ID_TYPE)
+        .map(|v| v.encode_interface_id())
+}
+/// Lookup all of the `framed_interface_id` interface id value from a packet.
+pub fn lookup_all_framed_interface_id_value(packet: &Packet) -> Result<Vec<InterfaceId>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(FRAMED_INTERFACE_ID_TYPE) {
+        vec.push(avp.encode_interface_id()?)
+    }
+    Ok(vec)
+}
 
 pub const FRAMED_IPV6_PREFIX_TYPE: AVPType = 97;
 /// Delete all of `framed_ipv6_prefix` values from a packet.
@@ -112,6 +137,26 @@ pub fn lookup_all_framed_ipv6_prefix(packet: &Packet) -> Result<Vec<Vec<u8>>, AV
     }
     Ok(vec)
 }
+/// Add `framed_ipv6_prefix` ipv6 prefix value with the prefix length to a packet.
+pub fn add_framed_ipv6_prefix_value(packet: &mut Packet, value: &Ipv6Prefix) {
+    packet.add(AVP::from_ipv6_prefix_value(FRAMED_IPV6_PREFIX_TYPE, value));
+}
+/// Lookup a `framed_ipv6_prefix` ipv6 prefix value with the prefix length from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `framed_ipv6_prefix`, it returns `None`.
+pub fn lookup_framed_ipv6_prefix_value(packet: &Packet) -> Option<Result<Ipv6Prefix, AVPError>> {
+    packet
+        .lookup(FRAMED_IPV6_PREFIX_TYPE)
+        .map(|v| v.encode_ipv6_prefix_value())
+}
+/// Lookup all of the `framed_ipv6_prefix` ipv6 prefix value with the prefix length from a packet.
+pub fn lookup_all_framed_ipv6_prefix_value(packet: &Packet) -> Result<Vec<Ipv6Prefix>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(FRAMED_IPV6_PREFIX_TYPE) {
+        vec.push(avp.encode_ipv6_prefix_value()?)
+    }
+    Ok(vec)
+}
 
 pub const LOGIN_IPV6_HOST_TYPE: AVPType = 98;
 /// Delete all of `login_ipv6_host` values from a packet.
@@ -188,3 +233,15 @@ pub fn lookup_all_framed_ipv6_pool(packet: &Packet) -> Result<Vec<String>, AVPEr
     }
     Ok(vec)
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("NAS-IPV6-Address", NAS_IPV6_ADDRESS_TYPE, "ipv6addr"),
+    AttributeDefinition::new("Framed-Interface-Id", FRAMED_INTERFACE_ID_TYPE, "ifid"),
+    AttributeDefinition::new("Framed-IPV6-Prefix", FRAMED_IPV6_PREFIX_TYPE, "ipv6prefix"),
+    AttributeDefinition::new("Login-IPV6-Host", LOGIN_IPV6_HOST_TYPE, "ipv6addr"),
+    AttributeDefinition::new("Framed-IPV6-Route", FRAMED_IPV6_ROUTE_TYPE, "string"),
+    AttributeDefinition::new("Framed-IPV6-Pool", FRAMED_IPV6_POOL_TYPE, "string"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
@@ -39,7 +39,11 @@
 //! VALUE    Error-Cause            Request-Initiated    507
 //! ```
 
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 use crate::core::rfc2865;
@@ -69,6 +73,22 @@ pub fn lookup_all_error_cause(packet: &Packet) -> Result<Vec<ErrorCause>, AVPErr
     }
     Ok(vec)
 }
+/// Lookup a `error_cause` value from a packet as `ErrorCauseValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `error_cause`, it returns `None`.
+pub fn lookup_error_cause_value(packet: &Packet) -> Option<Result<ErrorCauseValue, AVPError>> {
+    packet
+        .lookup(ERROR_CAUSE_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `error_cause` values from a packet as `ErrorCauseValue`.
+pub fn lookup_all_error_cause_value(packet: &Packet) -> Result<Vec<ErrorCauseValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(ERROR_CAUSE_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub type ErrorCause = u32;
 pub const ERROR_CAUSE_RESIDUAL_CONTEXT_REMOVED: ErrorCause = 201;
@@ -87,4 +107,106 @@ pub const ERROR_CAUSE_PROXY_PROCESSING_ERROR: ErrorCause = 505;
 pub const ERROR_CAUSE_RESOURCES_UNAVAILABLE: ErrorCause = 506;
 pub const ERROR_CAUSE_REQUEST_INITIATED: ErrorCause = 507;
 
+/// The values of `Error-Cause` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `ErrorCauseValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ErrorCauseValue {
+    /// `Residual-Context-Removed`
+    ResidualContextRemoved = 201,
+    /// `Invalid-EAP-Packet`
+    InvalidEAPPacket = 202,
+    /// `Unsupported-Attribute`
+    UnsupportedAttribute = 401,
+    /// `Missing-Attribute`
+    MissingAttribute = 402,
+    /// `NAS-Identification-Mismatch`
+    NasIdentificationMismatch = 403,
+    /// `Invalid-Request`
+    InvalidRequest = 404,
+    /// `Unsupported-Service`
+    UnsupportedService = 405,
+    /// `Unsupported-Extension`
+    UnsupportedExtension = 406,
+    /// `Administratively-Prohibited`
+    AdministrativelyProhibited = 501,
+    /// `Proxy-Request-Not-Routable`
+    ProxyRequestNotRoutable = 502,
+    /// `Session-Context-Not-Found`
+    SessionContextNotFound = 503,
+    /// `Session-Context-Not-Removable`
+    SessionContextNotRemovable = 504,
+    /// `Proxy-Processing-Error`
+    ProxyProcessingError = 505,
+    /// `Resources-Unavailable`
+    ResourcesUnavailable = 506,
+    /// `Request-Initiated`
+    RequestInitiated = 507,
+    /// `Invalid-Attribute-Value`
+    InvalidAttributeValue = 407,
+    /// `Multiple-Session-Selection-Unsupported`
+    MultipleSessionSelectionUnsupported = 508,
+}
+
+impl From<ErrorCauseValue> for u32 {
+    fn from(value: ErrorCauseValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for ErrorCauseValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            201 => Ok(ErrorCauseValue::ResidualContextRemoved),
+            202 => Ok(ErrorCauseValue::InvalidEAPPacket),
+            401 => Ok(ErrorCauseValue::UnsupportedAttribute),
+            402 => Ok(ErrorCauseValue::MissingAttribute),
+            403 => Ok(ErrorCauseValue::NasIdentificationMismatch),
+            404 => Ok(ErrorCauseValue::InvalidRequest),
+            405 => Ok(ErrorCauseValue::UnsupportedService),
+            406 => Ok(ErrorCauseValue::UnsupportedExtension),
+            501 => Ok(ErrorCauseValue::AdministrativelyProhibited),
+            502 => Ok(ErrorCauseValue::ProxyRequestNotRoutable),
+            503 => Ok(ErrorCauseValue::SessionContextNotFound),
+            504 => Ok(ErrorCauseValue::SessionContextNotRemovable),
+            505 => Ok(ErrorCauseValue::ProxyProcessingError),
+            506 => Ok(ErrorCauseValue::ResourcesUnavailable),
+            507 => Ok(ErrorCauseValue::RequestInitiated),
+            407 => Ok(ErrorCauseValue::InvalidAttributeValue),
+            508 => Ok(ErrorCauseValue::MultipleSessionSelectionUnsupported),
+            _ => Err(AVPError::UnknownValueError("Error-Cause".to_owned(), value)),
+        }
+    }
+}
+
 pub const SERVICE_TYPE_AUTHORIZE_ONLY: rfc2865::ServiceType = 17;
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[AttributeDefinition::new(
+    "Error-Cause",
+    ERROR_CAUSE_TYPE,
+    "integer",
+)];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Error-Cause", "Residual-Context-Removed", 201),
+    ValueDefinition::new("Error-Cause", "Invalid-EAP-Packet", 202),
+    ValueDefinition::new("Error-Cause", "Unsupported-Attribute", 401),
+    ValueDefinition::new("Error-Cause", "Missing-Attribute", 402),
+    ValueDefinition::new("Error-Cause", "NAS-Identification-Mismatch", 403),
+    ValueDefinition::new("Error-Cause", "Invalid-Request", 404),
+    ValueDefinition::new("Error-Cause", "Unsupported-Service", 405),
+    ValueDefinition::new("Error-Cause", "Unsupported-Extension", 406),
+    ValueDefinition::new("Error-Cause", "Administratively-Prohibited", 501),
+    ValueDefinition::new("Error-Cause", "Proxy-Request-Not-Routable", 502),
+    ValueDefinition::new("Error-Cause", "Session-Context-Not-Found", 503),
+    ValueDefinition::new("Error-Cause", "Session-Context-Not-Removable", 504),
+    ValueDefinition::new("Error-Cause", "Proxy-Processing-Error", 505),
+    ValueDefinition::new("Error-Cause", "Resources-Unavailable", 506),
+    ValueDefinition::new("Error-Cause", "Request-Initiated", 507),
+    ValueDefinition::new("Service-Type", "Authorize-Only", 17),
+];
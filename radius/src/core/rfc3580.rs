@@ -25,11 +25,11 @@
 //! VALUE    Tunnel-Type            VLAN            13
 //! ```
 
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+
 use crate::core::rfc2865;
 use crate::core::rfc2866;
-
 use crate::core::rfc2868;
-
 pub const ACCT_TERMINATE_CAUSE_SUPPLICANT_RESTART: rfc2866::AcctTerminateCause = 19;
 pub const ACCT_TERMINATE_CAUSE_REAUTHENTICATION_FAILURE: rfc2866::AcctTerminateCause = 20;
 pub const ACCT_TERMINATE_CAUSE_PORT_REINIT: rfc2866::AcctTerminateCause = 21;
@@ -39,3 +39,17 @@ pub const NAS_PORT_TYPE_TOKEN_RING: rfc2865::NasPortType = 20;
 pub const NAS_PORT_TYPE_FDDI: rfc2865::NasPortType = 21;
 
 pub const TUNNEL_TYPE_VLAN: rfc2868::TunnelType = 13;
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Acct-Terminate-Cause", "Supplicant-Restart", 19),
+    ValueDefinition::new("Acct-Terminate-Cause", "Reauthentication-Failure", 20),
+    ValueDefinition::new("Acct-Terminate-Cause", "Port-Reinit", 21),
+    ValueDefinition::new("Acct-Terminate-Cause", "Port-Disabled", 22),
+    ValueDefinition::new("NAS-Port-Type", "Token-Ring", 20),
+    ValueDefinition::new("NAS-Port-Type", "FDDI", 21),
+    ValueDefinition::new("Tunnel-Type", "VLAN", 13),
+];
@@ -18,7 +18,10 @@
 //! ATTRIBUTE    EAP-Key-Name                102    octets
 //! ```
 
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const EAP_KEY_NAME_TYPE: AVPType = 102;
@@ -44,3 +47,12 @@ pub fn lookup_all_eap_key_name(packet: &Packet) -> Vec<Vec<u8>> {
     }
     vec
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[AttributeDefinition::new(
+    "EAP-Key-Name",
+    EAP_KEY_NAME_TYPE,
+    "octets",
+)];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
@@ -17,7 +17,10 @@
 //! ATTRIBUTE    Chargeable-User-Identity        89    octets
 //! ```
 
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const CHARGEABLE_USER_IDENTITY_TYPE: AVPType = 89;
@@ -45,3 +48,12 @@ pub fn lookup_all_chargeable_user_identity(packet: &Packet) -> Vec<Vec<u8>> {
     }
     vec
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[AttributeDefinition::new(
+    "Chargeable-User-Identity",
+    CHARGEABLE_USER_IDENTITY_TYPE,
+    "octets",
+)];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
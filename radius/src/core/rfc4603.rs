@@ -25,10 +25,23 @@
 //!
 //! ```
 
-use crate::core::rfc2865;
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 
+use crate::core::rfc2865;
 pub const NAS_PORT_TYPE_PP_PO_A: rfc2865::NasPortType = 30;
 pub const NAS_PORT_TYPE_PP_PO_EO_A: rfc2865::NasPortType = 31;
 pub const NAS_PORT_TYPE_PP_PO_EO_E: rfc2865::NasPortType = 32;
 pub const NAS_PORT_TYPE_PP_PO_EO_VLAN: rfc2865::NasPortType = 33;
 pub const NAS_PORT_TYPE_PP_PO_EO_QIN_Q: rfc2865::NasPortType = 34;
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("NAS-Port-Type", "PPPoA", 30),
+    ValueDefinition::new("NAS-Port-Type", "PPPoEoA", 31),
+    ValueDefinition::new("NAS-Port-Type", "PPPoEoE", 32),
+    ValueDefinition::new("NAS-Port-Type", "PPPoEoVLAN", 33),
+    ValueDefinition::new("NAS-Port-Type", "PPPoEoQinQ", 34),
+];
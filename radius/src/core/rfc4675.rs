@@ -37,7 +37,12 @@
 //! VALUE    Ingress-Filters            Disabled        2
 //! ```
 
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const EGRESS_VLANID_TYPE: AVPType = 56;
@@ -89,6 +94,26 @@ pub fn lookup_all_ingress_filters(packet: &Packet) -> Result<Vec<IngressFilters>
     }
     Ok(vec)
 }
+/// Lookup a `ingress_filters` value from a packet as `IngressFiltersValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `ingress_filters`, it returns `None`.
+pub fn lookup_ingress_filters_value(
+    packet: &Packet,
+) -> Option<Result<IngressFiltersValue, AVPError>> {
+    packet
+        .lookup(INGRESS_FILTERS_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `ingress_filters` values from a packet as `IngressFiltersValue`.
+pub fn lookup_all_ingress_filters_value(
+    packet: &Packet,
+) -> Result<Vec<IngressFiltersValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(INGRESS_FILTERS_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const EGRESS_VLAN_NAME_TYPE: AVPType = 58;
 /// Delete all of `egress_vlan_name` values from a packet.
@@ -145,3 +170,50 @@ pub fn lookup_all_user_priority_table(packet: &Packet) -> Vec<Vec<u8>> {
 pub type IngressFilters = u32;
 pub const INGRESS_FILTERS_ENABLED: IngressFilters = 1;
 pub const INGRESS_FILTERS_DISABLED: IngressFilters = 2;
+
+/// The values of `Ingress-Filters` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `IngressFiltersValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum IngressFiltersValue {
+    /// `Enabled`
+    Enabled = 1,
+    /// `Disabled`
+    Disabled = 2,
+}
+
+impl From<IngressFiltersValue> for u32 {
+    fn from(value: IngressFiltersValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for IngressFiltersValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(IngressFiltersValue::Enabled),
+            2 => Ok(IngressFiltersValue::Disabled),
+            _ => Err(AVPError::UnknownValueError(
+                "Ingress-Filters".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Egress-VLANID", EGRESS_VLANID_TYPE, "integer"),
+    AttributeDefinition::new("Ingress-Filters", INGRESS_FILTERS_TYPE, "integer"),
+    AttributeDefinition::new("Egress-VLAN-Name", EGRESS_VLAN_NAME_TYPE, "string"),
+    AttributeDefinition::new("User-Priority-Table", USER_PRIORITY_TABLE_TYPE, "octets"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Ingress-Filters", "Enabled", 1),
+    ValueDefinition::new("Ingress-Filters", "Disabled", 2),
+];
@@ -20,7 +20,11 @@
 //! ATTRIBUTE    Delegated-IPV6-Prefix            123    ipv6prefix
 //! ```
 
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::ipv6::Ipv6Prefix;
 use crate::core::packet::Packet;
 
 pub const DELEGATED_IPV6_PREFIX_TYPE: AVPType = 123;
@@ -49,3 +53,37 @@ pub fn lookup_all_delegated_ipv6_prefix(packet: &Packet) -> Result<Vec<Vec<u8>>,
     }
     Ok(vec)
 }
+/// Add `delegated_ipv6_prefix` ipv6 prefix value with the prefix length to a packet.
+pub fn add_delegated_ipv6_prefix_value(packet: &mut Packet, value: &Ipv6Prefix) {
+    packet.add(AVP::from_ipv6_prefix_value(
+        DELEGATED_IPV6_PREFIX_TYPE,
+        value,
+    ));
+}
+/// Lookup a `delegated_ipv6_prefix` ipv6 prefix value with the prefix length from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `delegated_ipv6_prefix`, it returns `None`.
+pub fn lookup_delegated_ipv6_prefix_value(packet: &Packet) -> Option<Result<Ipv6Prefix, AVPError>> {
+    packet
+        .lookup(DELEGATED_IPV6_PREFIX_TYPE)
+        .map(|v| v.encode_ipv6_prefix_value())
+}
+/// Lookup all of the `delegated_ipv6_prefix` ipv6 prefix value with the prefix length from a packet.
+pub fn lookup_all_delegated_ipv6_prefix_value(
+    packet: &Packet,
+) -> Result<Vec<Ipv6Prefix>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(DELEGATED_IPV6_PREFIX_TYPE) {
+        vec.push(avp.encode_ipv6_prefix_value()?)
+    }
+    Ok(vec)
+}
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[AttributeDefinition::new(
+    "Delegated-IPV6-Prefix",
+    DELEGATED_IPV6_PREFIX_TYPE,
+    "ipv6prefix",
+)];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
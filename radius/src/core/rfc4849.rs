@@ -17,7 +17,11 @@
 //! ATTRIBUTE    NAS-Filter-Rule                92    string
 //! ```
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const NAS_FILTER_RULE_TYPE: AVPType = 92;
@@ -45,3 +49,12 @@ pub fn lookup_all_nas_filter_rule(packet: &Packet) -> Result<Vec<String>, AVPErr
     }
     Ok(vec)
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[AttributeDefinition::new(
+    "NAS-Filter-Rule",
+    NAS_FILTER_RULE_TYPE,
+    "string",
+)];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
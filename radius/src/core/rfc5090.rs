@@ -36,7 +36,11 @@
 //! ATTRIBUTE    SIP-AOR                    122    string
 //! ```
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const DIGEST_RESPONSE_TYPE: AVPType = 103;
@@ -538,3 +542,33 @@ pub fn lookup_all_sip_aor(packet: &Packet) -> Result<Vec<String>, AVPError> {
     }
     Ok(vec)
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Digest-Response", DIGEST_RESPONSE_TYPE, "string"),
+    AttributeDefinition::new("Digest-Realm", DIGEST_REALM_TYPE, "string"),
+    AttributeDefinition::new("Digest-Nonce", DIGEST_NONCE_TYPE, "string"),
+    AttributeDefinition::new("Digest-Response-Auth", DIGEST_RESPONSE_AUTH_TYPE, "string"),
+    AttributeDefinition::new("Digest-Nextnonce", DIGEST_NEXTNONCE_TYPE, "string"),
+    AttributeDefinition::new("Digest-Method", DIGEST_METHOD_TYPE, "string"),
+    AttributeDefinition::new("Digest-URI", DIGEST_URI_TYPE, "string"),
+    AttributeDefinition::new("Digest-Qop", DIGEST_QOP_TYPE, "string"),
+    AttributeDefinition::new("Digest-Algorithm", DIGEST_ALGORITHM_TYPE, "string"),
+    AttributeDefinition::new(
+        "Digest-Entity-Body-Hash",
+        DIGEST_ENTITY_BODY_HASH_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new("Digest-CNonce", DIGEST_C_NONCE_TYPE, "string"),
+    AttributeDefinition::new("Digest-Nonce-Count", DIGEST_NONCE_COUNT_TYPE, "string"),
+    AttributeDefinition::new("Digest-Username", DIGEST_USERNAME_TYPE, "string"),
+    AttributeDefinition::new("Digest-Opaque", DIGEST_OPAQUE_TYPE, "string"),
+    AttributeDefinition::new("Digest-Auth-Param", DIGEST_AUTH_PARAM_TYPE, "string"),
+    AttributeDefinition::new("Digest-AKA-Auts", DIGEST_AKA_AUTS_TYPE, "string"),
+    AttributeDefinition::new("Digest-Domain", DIGEST_DOMAIN_TYPE, "string"),
+    AttributeDefinition::new("Digest-Stale", DIGEST_STALE_TYPE, "string"),
+    AttributeDefinition::new("Digest-HA1", DIGEST_HA1_TYPE, "string"),
+    AttributeDefinition::new("SIP-AOR", SIP_AOR_TYPE, "string"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
@@ -18,7 +18,17 @@
 //! VALUE    Error-Cause            Multiple-Session-Selection-Unsupported    508
 //! ```
 
-use crate::core::rfc3576;
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 
+use crate::core::rfc3576;
 pub const ERROR_CAUSE_INVALID_ATTRIBUTE_VALUE: rfc3576::ErrorCause = 407;
 pub const ERROR_CAUSE_MULTIPLE_SESSION_SELECTION_UNSUPPORTED: rfc3576::ErrorCause = 508;
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Error-Cause", "Invalid-Attribute-Value", 407),
+    ValueDefinition::new("Error-Cause", "Multiple-Session-Selection-Unsupported", 508),
+];
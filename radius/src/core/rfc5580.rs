@@ -0,0 +1,413 @@
+// Code generated by machine generator; DO NOT EDIT.
+
+//! Utility for rfc5580 packet.
+//!
+//! This module handles the packet according to the following definition:
+//! ```text
+//! //! # -*- text -*-
+//! # Copyright (C) 2020 The FreeRADIUS Server project and contributors
+//! # This work is licensed under CC-BY version 4.0 https://creativecommons.org/licenses/by/4.0
+//! # Version $Id$
+//! #
+//! #    Attributes and values defined in RFC 5580.
+//! #    http://www.ietf.org/rfc/rfc5580.txt
+//! #
+//! #    $Id$
+//! #
+//!
+//! # One ASCII character of Namespace ID, then a string.
+//! # Really "text".
+//! ATTRIBUTE    Operator-Name                126    string
+//!
+//! # Index, Code, and then the Location information.
+//! ATTRIBUTE    Location-Information            127    octets
+//!
+//! # Index, and then the Location data.
+//! ATTRIBUTE    Location-Data                128    octets
+//!
+//! # Flags, Retention Expires, Note Well, and the Ruleset Reference.
+//! ATTRIBUTE    Basic-Location-Policy-Rules        129    octets
+//!
+//! # The Ruleset Reference.
+//! ATTRIBUTE    Extended-Location-Policy-Rules        130    octets
+//!
+//! # Really a bit-packed field
+//! ATTRIBUTE    Location-Capable            131    integer
+//! VALUE    Location-Capable        Civix-Location        1
+//! VALUE    Location-Capable        Geo-Location        2
+//! VALUE    Location-Capable        Users-Location        4
+//! VALUE    Location-Capable        NAS-Location        8
+//!
+//! ATTRIBUTE    Requested-Location-Info            132    integer
+//! VALUE    Requested-Location-Info        Civix-Location        1
+//! VALUE    Requested-Location-Info        Geo-Location        2
+//! VALUE    Requested-Location-Info        Users-Location        4
+//! VALUE    Requested-Location-Info        NAS-Location        8
+//! VALUE    Requested-Location-Info        Future-Requests        16
+//! VALUE    Requested-Location-Info        None            32
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::packet::Packet;
+
+pub const OPERATOR_NAME_TYPE: AVPType = 126;
+/// Delete all of `operator_name` values from a packet.
+pub fn delete_operator_name(packet: &mut Packet) {
+    packet.delete(OPERATOR_NAME_TYPE);
+}
+/// Add `operator_name` string value to a packet.
+pub fn add_operator_name(packet: &mut Packet, value: &str) {
+    packet.add(AVP::from_string(OPERATOR_NAME_TYPE, value));
+}
+/// Lookup a `operator_name` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `operator_name`, it returns `None`.
+pub fn lookup_operator_name(packet: &Packet) -> Option<Result<String, AVPError>> {
+    packet.lookup(OPERATOR_NAME_TYPE).map(|v| v.encode_string())
+}
+/// Lookup all of the `operator_name` string value from a packet.
+pub fn lookup_all_operator_name(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(OPERATOR_NAME_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const LOCATION_INFORMATION_TYPE: AVPType = 127;
+/// Delete all of `location_information` values from a packet.
+pub fn delete_location_information(packet: &mut Packet) {
+    packet.delete(LOCATION_INFORMATION_TYPE);
+}
+/// Add `location_information` octets value to a packet.
+pub fn add_location_information(packet: &mut Packet, value: &[u8]) {
+    packet.add(AVP::from_bytes(LOCATION_INFORMATION_TYPE, value));
+}
+/// Lookup a `location_information` octets value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `location_information`, it returns `None`.
+pub fn lookup_location_information(packet: &Packet) -> Option<Vec<u8>> {
+    packet
+        .lookup(LOCATION_INFORMATION_TYPE)
+        .map(|v| v.encode_bytes())
+}
+/// Lookup all of the `location_information` octets value from a packet.
+pub fn lookup_all_location_information(packet: &Packet) -> Vec<Vec<u8>> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(LOCATION_INFORMATION_TYPE) {
+        vec.push(avp.encode_bytes())
+    }
+    vec
+}
+
+pub const LOCATION_DATA_TYPE: AVPType = 128;
+/// Delete all of `location_data` values from a packet.
+pub fn delete_location_data(packet: &mut Packet) {
+    packet.delete(LOCATION_DATA_TYPE);
+}
+/// Add `location_data` octets value to a packet.
+pub fn add_location_data(packet: &mut Packet, value: &[u8]) {
+    packet.add(AVP::from_bytes(LOCATION_DATA_TYPE, value));
+}
+/// Lookup a `location_data` octets value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `location_data`, it returns `None`.
+pub fn lookup_location_data(packet: &Packet) -> Option<Vec<u8>> {
+    packet.lookup(LOCATION_DATA_TYPE).map(|v| v.encode_bytes())
+}
+/// Lookup all of the `location_data` octets value from a packet.
+pub fn lookup_all_location_data(packet: &Packet) -> Vec<Vec<u8>> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(LOCATION_DATA_TYPE) {
+        vec.push(avp.encode_bytes())
+    }
+    vec
+}
+
+pub const BASIC_LOCATION_POLICY_RULES_TYPE: AVPType = 129;
+/// Delete all of `basic_location_policy_rules` values from a packet.
+pub fn delete_basic_location_policy_rules(packet: &mut Packet) {
+    packet.delete(BASIC_LOCATION_POLICY_RULES_TYPE);
+}
+/// Add `basic_location_policy_rules` octets value to a packet.
+pub fn add_basic_location_policy_rules(packet: &mut Packet, value: &[u8]) {
+    packet.add(AVP::from_bytes(BASIC_LOCATION_POLICY_RULES_TYPE, value));
+}
+/// Lookup a `basic_location_policy_rules` octets value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `basic_location_policy_rules`, it returns `None`.
+pub fn lookup_basic_location_policy_rules(packet: &Packet) -> Option<Vec<u8>> {
+    packet
+        .lookup(BASIC_LOCATION_POLICY_RULES_TYPE)
+        .map(|v| v.encode_bytes())
+}
+/// Lookup all of the `basic_location_policy_rules` octets value from a packet.
+pub fn lookup_all_basic_location_policy_rules(packet: &Packet) -> Vec<Vec<u8>> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(BASIC_LOCATION_POLICY_RULES_TYPE) {
+        vec.push(avp.encode_bytes())
+    }
+    vec
+}
+
+pub const EXTENDED_LOCATION_POLICY_RULES_TYPE: AVPType = 130;
+/// Delete all of `extended_location_policy_rules` values from a packet.
+pub fn delete_extended_location_policy_rules(packet: &mut Packet) {
+    packet.delete(EXTENDED_LOCATION_POLICY_RULES_TYPE);
+}
+/// Add `extended_location_policy_rules` octets value to a packet.
+pub fn add_extended_location_policy_rules(packet: &mut Packet, value: &[u8]) {
+    packet.add(AVP::from_bytes(EXTENDED_LOCATION_POLICY_RULES_TYPE, value));
+}
+/// Lookup a `extended_location_policy_rules` octets value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `extended_location_policy_rules`, it returns `None`.
+pub fn lookup_extended_location_policy_rules(packet: &Packet) -> Option<Vec<u8>> {
+    packet
+        .lookup(EXTENDED_LOCATION_POLICY_RULES_TYPE)
+        .map(|v| v.encode_bytes())
+}
+/// Lookup all of the `extended_location_policy_rules` octets value from a packet.
+pub fn lookup_all_extended_location_policy_rules(packet: &Packet) -> Vec<Vec<u8>> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(EXTENDED_LOCATION_POLICY_RULES_TYPE) {
+        vec.push(avp.encode_bytes())
+    }
+    vec
+}
+
+pub const LOCATION_CAPABLE_TYPE: AVPType = 131;
+/// Delete all of `location_capable` values from a packet.
+pub fn delete_location_capable(packet: &mut Packet) {
+    packet.delete(LOCATION_CAPABLE_TYPE);
+}
+/// Add `location_capable` value-defined integer value to a packet.
+pub fn add_location_capable(packet: &mut Packet, value: LocationCapable) {
+    packet.add(AVP::from_u32(LOCATION_CAPABLE_TYPE, value));
+}
+/// Lookup a `location_capable` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `location_capable`, it returns `None`.
+pub fn lookup_location_capable(packet: &Packet) -> Option<Result<LocationCapable, AVPError>> {
+    packet
+        .lookup(LOCATION_CAPABLE_TYPE)
+        .map(|v| Ok(v.encode_u32()? as LocationCapable))
+}
+/// Lookup all of the `location_capable` value-defined integer value from a packet.
+pub fn lookup_all_location_capable(packet: &Packet) -> Result<Vec<LocationCapable>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(LOCATION_CAPABLE_TYPE) {
+        vec.push(avp.encode_u32()? as LocationCapable)
+    }
+    Ok(vec)
+}
+/// Lookup a `location_capable` value from a packet as `LocationCapableValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `location_capable`, it returns `None`.
+pub fn lookup_location_capable_value(
+    packet: &Packet,
+) -> Option<Result<LocationCapableValue, AVPError>> {
+    packet
+        .lookup(LOCATION_CAPABLE_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `location_capable` values from a packet as `LocationCapableValue`.
+pub fn lookup_all_location_capable_value(
+    packet: &Packet,
+) -> Result<Vec<LocationCapableValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(LOCATION_CAPABLE_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
+
+pub const REQUESTED_LOCATION_INFO_TYPE: AVPType = 132;
+/// Delete all of `requested_location_info` values from a packet.
+pub fn delete_requested_location_info(packet: &mut Packet) {
+    packet.delete(REQUESTED_LOCATION_INFO_TYPE);
+}
+/// Add `requested_location_info` value-defined integer value to a packet.
+pub fn add_requested_location_info(packet: &mut Packet, value: RequestedLocationInfo) {
+    packet.add(AVP::from_u32(REQUESTED_LOCATION_INFO_TYPE, value));
+}
+/// Lookup a `requested_location_info` value-defined integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `requested_location_info`, it returns `None`.
+pub fn lookup_requested_location_info(
+    packet: &Packet,
+) -> Option<Result<RequestedLocationInfo, AVPError>> {
+    packet
+        .lookup(REQUESTED_LOCATION_INFO_TYPE)
+        .map(|v| Ok(v.encode_u32()? as RequestedLocationInfo))
+}
+/// Lookup all of the `requested_location_info` value-defined integer value from a packet.
+pub fn lookup_all_requested_location_info(
+    packet: &Packet,
+) -> Result<Vec<RequestedLocationInfo>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(REQUESTED_LOCATION_INFO_TYPE) {
+        vec.push(avp.encode_u32()? as RequestedLocationInfo)
+    }
+    Ok(vec)
+}
+/// Lookup a `requested_location_info` value from a packet as `RequestedLocationInfoValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `requested_location_info`, it returns `None`.
+pub fn lookup_requested_location_info_value(
+    packet: &Packet,
+) -> Option<Result<RequestedLocationInfoValue, AVPError>> {
+    packet
+        .lookup(REQUESTED_LOCATION_INFO_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `requested_location_info` values from a packet as `RequestedLocationInfoValue`.
+pub fn lookup_all_requested_location_info_value(
+    packet: &Packet,
+) -> Result<Vec<RequestedLocationInfoValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(REQUESTED_LOCATION_INFO_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
+
+pub type LocationCapable = u32;
+pub const LOCATION_CAPABLE_CIVIX_LOCATION: LocationCapable = 1;
+pub const LOCATION_CAPABLE_GEO_LOCATION: LocationCapable = 2;
+pub const LOCATION_CAPABLE_USERS_LOCATION: LocationCapable = 4;
+pub const LOCATION_CAPABLE_NAS_LOCATION: LocationCapable = 8;
+
+/// The values of `Location-Capable` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `LocationCapableValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum LocationCapableValue {
+    /// `Civix-Location`
+    CivixLocation = 1,
+    /// `Geo-Location`
+    GeoLocation = 2,
+    /// `Users-Location`
+    UsersLocation = 4,
+    /// `NAS-Location`
+    NasLocation = 8,
+}
+
+impl From<LocationCapableValue> for u32 {
+    fn from(value: LocationCapableValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for LocationCapableValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(LocationCapableValue::CivixLocation),
+            2 => Ok(LocationCapableValue::GeoLocation),
+            4 => Ok(LocationCapableValue::UsersLocation),
+            8 => Ok(LocationCapableValue::NasLocation),
+            _ => Err(AVPError::UnknownValueError(
+                "Location-Capable".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+pub type RequestedLocationInfo = u32;
+pub const REQUESTED_LOCATION_INFO_CIVIX_LOCATION: RequestedLocationInfo = 1;
+pub const REQUESTED_LOCATION_INFO_GEO_LOCATION: RequestedLocationInfo = 2;
+pub const REQUESTED_LOCATION_INFO_USERS_LOCATION: RequestedLocationInfo = 4;
+pub const REQUESTED_LOCATION_INFO_NAS_LOCATION: RequestedLocationInfo = 8;
+pub const REQUESTED_LOCATION_INFO_FUTURE_REQUESTS: RequestedLocationInfo = 16;
+pub const REQUESTED_LOCATION_INFO_NONE: RequestedLocationInfo = 32;
+
+/// The values of `Requested-Location-Info` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `RequestedLocationInfoValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum RequestedLocationInfoValue {
+    /// `Civix-Location`
+    CivixLocation = 1,
+    /// `Geo-Location`
+    GeoLocation = 2,
+    /// `Users-Location`
+    UsersLocation = 4,
+    /// `NAS-Location`
+    NasLocation = 8,
+    /// `Future-Requests`
+    FutureRequests = 16,
+    /// `None`
+    None = 32,
+}
+
+impl From<RequestedLocationInfoValue> for u32 {
+    fn from(value: RequestedLocationInfoValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for RequestedLocationInfoValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(RequestedLocationInfoValue::CivixLocation),
+            2 => Ok(RequestedLocationInfoValue::GeoLocation),
+            4 => Ok(RequestedLocationInfoValue::UsersLocation),
+            8 => Ok(RequestedLocationInfoValue::NasLocation),
+            16 => Ok(RequestedLocationInfoValue::FutureRequests),
+            32 => Ok(RequestedLocationInfoValue::None),
+            _ => Err(AVPError::UnknownValueError(
+                "Requested-Location-Info".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Operator-Name", OPERATOR_NAME_TYPE, "string"),
+    AttributeDefinition::new("Location-Information", LOCATION_INFORMATION_TYPE, "octets"),
+    AttributeDefinition::new("Location-Data", LOCATION_DATA_TYPE, "octets"),
+    AttributeDefinition::new(
+        "Basic-Location-Policy-Rules",
+        BASIC_LOCATION_POLICY_RULES_TYPE,
+        "octets",
+    ),
+    AttributeDefinition::new(
+        "Extended-Location-Policy-Rules",
+        EXTENDED_LOCATION_POLICY_RULES_TYPE,
+        "octets",
+    ),
+    AttributeDefinition::new("Location-Capable", LOCATION_CAPABLE_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Requested-Location-Info",
+        REQUESTED_LOCATION_INFO_TYPE,
+        "integer",
+    ),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Location-Capable", "Civix-Location", 1),
+    ValueDefinition::new("Location-Capable", "Geo-Location", 2),
+    ValueDefinition::new("Location-Capable", "Users-Location", 4),
+    ValueDefinition::new("Location-Capable", "NAS-Location", 8),
+    ValueDefinition::new("Requested-Location-Info", "Civix-Location", 1),
+    ValueDefinition::new("Requested-Location-Info", "Geo-Location", 2),
+    ValueDefinition::new("Requested-Location-Info", "Users-Location", 4),
+    ValueDefinition::new("Requested-Location-Info", "NAS-Location", 8),
+    ValueDefinition::new("Requested-Location-Info", "Future-Requests", 16),
+    ValueDefinition::new("Requested-Location-Info", "None", 32),
+];
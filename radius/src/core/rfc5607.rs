@@ -39,7 +39,12 @@
 //! ATTRIBUTE    Management-Privilege-Level        136    integer
 //! ```
 
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 use crate::core::rfc2865;
@@ -69,6 +74,26 @@ pub fn lookup_all_framed_management(packet: &Packet) -> Result<Vec<FramedManagem
     }
     Ok(vec)
 }
+/// Lookup a `framed_management` value from a packet as `FramedManagementValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `framed_management`, it returns `None`.
+pub fn lookup_framed_management_value(
+    packet: &Packet,
+) -> Option<Result<FramedManagementValue, AVPError>> {
+    packet
+        .lookup(FRAMED_MANAGEMENT_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `framed_management` values from a packet as `FramedManagementValue`.
+pub fn lookup_all_framed_management_value(
+    packet: &Packet,
+) -> Result<Vec<FramedManagementValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(FRAMED_MANAGEMENT_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const MANAGEMENT_TRANSPORT_PROTECTION_TYPE: AVPType = 134;
 /// Delete all of `management_transport_protection` values from a packet.
@@ -102,6 +127,26 @@ pub fn lookup_all_management_transport_protection(
     }
     Ok(vec)
 }
+/// Lookup a `management_transport_protection` value from a packet as `ManagementTransportProtectionValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `management_transport_protection`, it returns `None`.
+pub fn lookup_management_transport_protection_value(
+    packet: &Packet,
+) -> Option<Result<ManagementTransportProtectionValue, AVPError>> {
+    packet
+        .lookup(MANAGEMENT_TRANSPORT_PROTECTION_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `management_transport_protection` values from a packet as `ManagementTransportProtectionValue`.
+pub fn lookup_all_management_transport_protection_value(
+    packet: &Packet,
+) -> Result<Vec<ManagementTransportProtectionValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(MANAGEMENT_TRANSPORT_PROTECTION_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub const MANAGEMENT_POLICY_ID_TYPE: AVPType = 135;
 /// Delete all of `management_policy_id` values from a packet.
@@ -165,10 +210,133 @@ pub const FRAMED_MANAGEMENT_SFTP: FramedManagement = 6;
 pub const FRAMED_MANAGEMENT_RCP: FramedManagement = 7;
 pub const FRAMED_MANAGEMENT_SCP: FramedManagement = 8;
 
+/// The values of `Framed-Management` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `FramedManagementValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum FramedManagementValue {
+    /// `SNMP`
+    Snmp = 1,
+    /// `Web-Based`
+    WebBased = 2,
+    /// `Netconf`
+    Netconf = 3,
+    /// `FTP`
+    Ftp = 4,
+    /// `TFTP`
+    Tftp = 5,
+    /// `SFTP`
+    Sftp = 6,
+    /// `RCP`
+    Rcp = 7,
+    /// `SCP`
+    Scp = 8,
+}
+
+impl From<FramedManagementValue> for u32 {
+    fn from(value: FramedManagementValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for FramedManagementValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(FramedManagementValue::Snmp),
+            2 => Ok(FramedManagementValue::WebBased),
+            3 => Ok(FramedManagementValue::Netconf),
+            4 => Ok(FramedManagementValue::Ftp),
+            5 => Ok(FramedManagementValue::Tftp),
+            6 => Ok(FramedManagementValue::Sftp),
+            7 => Ok(FramedManagementValue::Rcp),
+            8 => Ok(FramedManagementValue::Scp),
+            _ => Err(AVPError::UnknownValueError(
+                "Framed-Management".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub type ManagementTransportProtection = u32;
 pub const MANAGEMENT_TRANSPORT_PROTECTION_NO_PROTECTION: ManagementTransportProtection = 1;
 pub const MANAGEMENT_TRANSPORT_PROTECTION_INTEGRITY_PROTECTION: ManagementTransportProtection = 2;
 pub const MANAGEMENT_TRANSPORT_PROTECTION_INTEGRITY_CONFIDENTIALITY_PROTECTION:
     ManagementTransportProtection = 3;
 
+/// The values of `Management-Transport-Protection` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `ManagementTransportProtectionValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ManagementTransportProtectionValue {
+    /// `No-Protection`
+    NoProtection = 1,
+    /// `Integrity-Protection`
+    IntegrityProtection = 2,
+    /// `Integrity-Confidentiality-Protection`
+    IntegrityConfidentialityProtection = 3,
+}
+
+impl From<ManagementTransportProtectionValue> for u32 {
+    fn from(value: ManagementTransportProtectionValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for ManagementTransportProtectionValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(ManagementTransportProtectionValue::NoProtection),
+            2 => Ok(ManagementTransportProtectionValue::IntegrityProtection),
+            3 => Ok(ManagementTransportProtectionValue::IntegrityConfidentialityProtection),
+            _ => Err(AVPError::UnknownValueError(
+                "Management-Transport-Protection".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
 pub const SERVICE_TYPE_FRAMED_MANAGEMENT: rfc2865::ServiceType = 18;
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Framed-Management", FRAMED_MANAGEMENT_TYPE, "integer"),
+    AttributeDefinition::new(
+        "Management-Transport-Protection",
+        MANAGEMENT_TRANSPORT_PROTECTION_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new("Management-Policy-Id", MANAGEMENT_POLICY_ID_TYPE, "string"),
+    AttributeDefinition::new(
+        "Management-Privilege-Level",
+        MANAGEMENT_PRIVILEGE_LEVEL_TYPE,
+        "integer",
+    ),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("Framed-Management", "SNMP", 1),
+    ValueDefinition::new("Framed-Management", "Web-Based", 2),
+    ValueDefinition::new("Framed-Management", "Netconf", 3),
+    ValueDefinition::new("Framed-Management", "FTP", 4),
+    ValueDefinition::new("Framed-Management", "TFTP", 5),
+    ValueDefinition::new("Framed-Management", "SFTP", 6),
+    ValueDefinition::new("Framed-Management", "RCP", 7),
+    ValueDefinition::new("Framed-Management", "SCP", 8),
+    ValueDefinition::new("Management-Transport-Protection", "No-Protection", 1),
+    ValueDefinition::new("Management-Transport-Protection", "Integrity-Protection", 2),
+    ValueDefinition::new(
+        "Management-Transport-Protection",
+        "Integrity-Confidentiality-Protection",
+        3,
+    ),
+    ValueDefinition::new("Service-Type", "Framed-Management", 18),
+];
@@ -31,7 +31,10 @@
 //! ATTRIBUTE    PKM-Auth-Key                143    octets
 //! ```
 
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const PKM_SS_CERT_TYPE: AVPType = 137;
@@ -207,3 +210,16 @@ pub fn lookup_all_pkm_auth_key(packet: &Packet) -> Vec<Vec<u8>> {
     }
     vec
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("PKM-SS-Cert", PKM_SS_CERT_TYPE, "octets"),
+    AttributeDefinition::new("PKM-CA-Cert", PKM_CA_CERT_TYPE, "octets"),
+    AttributeDefinition::new("PKM-Config-Settings", PKM_CONFIG_SETTINGS_TYPE, "octets"),
+    AttributeDefinition::new("PKM-Cryptosuite-List", PKM_CRYPTOSUITE_LIST_TYPE, "octets"),
+    AttributeDefinition::new("PKM-SAID", PKM_SAID_TYPE, "short"),
+    AttributeDefinition::new("PKM-SA-Descriptor", PKM_SA_DESCRIPTOR_TYPE, "octets"),
+    AttributeDefinition::new("PKM-Auth-Key", PKM_AUTH_KEY_TYPE, "octets"),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
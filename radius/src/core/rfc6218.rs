@@ -0,0 +1,506 @@
+//! Utility for the keywrap attributes of RFC 6218, i.e. the delivery of the keying material (e.g. the MSK of EAP)
+//! wrapped by AES Key Wrap (RFC 3394) instead of the MPPE-style hiding of MS-MPPE-Send-Key/Recv-Key.
+//!
+//! The attributes are the Vendor-Specific attributes of Cisco (Vendor-Id 9):
+//!
+//! - Keying-Material carries the key wrapped by a KEK (Key Encryption Key) that the server and the NAS share out of
+//!   band, with the App-ID, the KEK-ID of the KEK and the lifetime of the key;
+//! - MAC-Randomizer is a random value that makes the Message-Authentication-Code of each packet unique;
+//! - Message-Authentication-Code is the HMAC-SHA-256 of the whole packet by a MAC key (MACK), which is shared the
+//!   same way as the KEK.
+//!
+//! Check the Vendor-Type numbers against the dictionary of the NAS before the deployment.
+//!
+//! `Packet::encode()` doesn't know the MAC key, so encode a packet that has Message-Authentication-Code
+//! (by `add_message_authentication_code()`) with `encode_packet()`, which fills the MAC and then the
+//! Message-Authenticator and the Authenticator field that cover it.
+//! The MAC is computed with the Message-Authentication-Code and the Message-Authenticator filled with zeros, and with
+//! the Authenticator field of the same rules as Message-Authenticator (see `radius::core::message_authenticator`).
+//!
+//! ```
+//! use radius::core::code::Code;
+//! use radius::core::packet::Packet;
+//! use radius::core::rfc6218::{self, KeyingMaterial};
+//!
+//! let kek = [0x11; 16];
+//! let mac_key = [0x22; 32];
+//! let request = Packet::new(Code::AccessRequest, b"secret");
+//!
+//! let mut response = request.make_response_packet(Code::AccessAccept);
+//! let keying_material = KeyingMaterial::new([0; 8], [1; 16], 3600, &[0x33; 64]);
+//! rfc6218::add_keying_material(&mut response, &kek, &keying_material).unwrap();
+//! rfc6218::add_mac_randomizer(&mut response);
+//! rfc6218::add_message_authentication_code(&mut response);
+//! let datagram = rfc6218::encode_packet(&response, &mac_key).unwrap();
+//!
+//! let request_datagram = request.encode().unwrap();
+//! assert_eq!(
+//!     rfc6218::verify_response(&datagram, &request_datagram, &mac_key),
+//!     Some(true)
+//! );
+//! let received = Packet::decode(&datagram, b"secret").unwrap();
+//! let delivered = rfc6218::lookup_keying_material(&received, &kek).unwrap().unwrap();
+//! assert_eq!(delivered.get_key(), &[0x33; 64]);
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use aes::{Aes128, Aes192, Aes256};
+use aes_kw::Kek;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha2::Sha256;
+
+use crate::core::avp::{AVPError, AVPType};
+use crate::core::code::Code;
+use crate::core::constant_time;
+use crate::core::message_authenticator::{self, MESSAGE_AUTHENTICATOR_LENGTH};
+use crate::core::packet::{Packet, PacketError, RADIUS_PACKET_HEADER_LENGTH};
+use crate::core::random;
+use crate::core::rfc2865;
+use crate::core::vsa::{self, VendorId};
+
+pub const VENDOR_CISCO: VendorId = 9;
+
+pub const KEYING_MATERIAL_TYPE: AVPType = 54;
+pub const MAC_RANDOMIZER_TYPE: AVPType = 55;
+pub const MESSAGE_AUTHENTICATION_CODE_TYPE: AVPType = 56;
+
+/// The length of the value of MAC-Randomizer.
+pub const MAC_RANDOMIZER_LENGTH: usize = 32;
+/// The length of the value of Message-Authentication-Code, i.e. the output of HMAC-SHA-256.
+pub const MESSAGE_AUTHENTICATION_CODE_LENGTH: usize = 32;
+
+const SEMIBLOCK_LENGTH: usize = 8;
+const APP_ID_LENGTH: usize = 8;
+const KEK_ID_LENGTH: usize = 16;
+const KEYING_MATERIAL_HEADER_LENGTH: usize = APP_ID_LENGTH + KEK_ID_LENGTH + 4;
+/// The maximum length of the value of a vendor's sub-attribute, i.e. 255 - the headers of Vendor-Specific.
+const MAX_VENDOR_VALUE_LENGTH: usize = 255 - 2 - 4 - 2;
+
+/// A KEK of the key size of AES.
+enum KeyEncryptionKey {
+    Aes128(Kek<Aes128>),
+    Aes192(Kek<Aes192>),
+    Aes256(Kek<Aes256>),
+}
+
+impl KeyEncryptionKey {
+    fn new(kek: &[u8]) -> Result<Self, AVPError> {
+        match kek.len() {
+            16 => Kek::try_from(kek).map(KeyEncryptionKey::Aes128),
+            24 => Kek::try_from(kek).map(KeyEncryptionKey::Aes192),
+            32 => Kek::try_from(kek).map(KeyEncryptionKey::Aes256),
+            _ => Err(aes_kw::Error::InvalidKekSize { size: kek.len() }),
+        }
+        .map_err(|_| {
+            AVPError::InvalidAttributeLengthError("16, 24 or 32 bytes of KEK".to_owned(), kek.len())
+        })
+    }
+
+    fn wrap(&self, key: &[u8]) -> Result<Vec<u8>, aes_kw::Error> {
+        match self {
+            KeyEncryptionKey::Aes128(kek) => kek.wrap_vec(key),
+            KeyEncryptionKey::Aes192(kek) => kek.wrap_vec(key),
+            KeyEncryptionKey::Aes256(kek) => kek.wrap_vec(key),
+        }
+    }
+
+    fn unwrap(&self, wrapped: &[u8]) -> Result<Vec<u8>, aes_kw::Error> {
+        match self {
+            KeyEncryptionKey::Aes128(kek) => kek.unwrap_vec(wrapped),
+            KeyEncryptionKey::Aes192(kek) => kek.unwrap_vec(wrapped),
+            KeyEncryptionKey::Aes256(kek) => kek.unwrap_vec(wrapped),
+        }
+    }
+}
+
+/// Wrap a key by a KEK with AES Key Wrap (RFC 3394); the key has to be a multiple of 8 bytes, and at least 16 bytes.
+pub fn wrap_key(kek: &[u8], key: &[u8]) -> Result<Vec<u8>, AVPError> {
+    if key.len() < 2 * SEMIBLOCK_LENGTH || !key.len().is_multiple_of(SEMIBLOCK_LENGTH) {
+        return Err(AVPError::InvalidAttributeLengthError(
+            "a multiple of 8 bytes (at least 16 bytes) of key".to_owned(),
+            key.len(),
+        ));
+    }
+    Ok(KeyEncryptionKey::new(kek)?
+        .wrap(key)
+        .expect("the length of the key has been checked"))
+}
+
+/// Unwrap a key that has been wrapped by `wrap_key()`, and check its integrity.
+pub fn unwrap_key(kek: &[u8], wrapped: &[u8]) -> Result<Vec<u8>, AVPError> {
+    if wrapped.len() < 3 * SEMIBLOCK_LENGTH || !wrapped.len().is_multiple_of(SEMIBLOCK_LENGTH) {
+        return Err(AVPError::InvalidAttributeLengthError(
+            "a multiple of 8 bytes (at least 24 bytes) of wrapped key".to_owned(),
+            wrapped.len(),
+        ));
+    }
+    KeyEncryptionKey::new(kek)?.unwrap(wrapped).map_err(|_| {
+        AVPError::DecodingError("the integrity check of the wrapped key has failed".to_owned())
+    })
+}
+
+/// The content of a Keying-Material attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyingMaterial {
+    app_id: [u8; APP_ID_LENGTH],
+    kek_id: [u8; KEK_ID_LENGTH],
+    lifetime: u32,
+    key: Vec<u8>,
+}
+
+impl KeyingMaterial {
+    /// A constructor for the keying material of an application (e.g. EAP) with the identifier of the KEK that wraps
+    /// the key, and the lifetime of the key in seconds (0 means no expiration).
+    pub fn new(
+        app_id: [u8; APP_ID_LENGTH],
+        kek_id: [u8; KEK_ID_LENGTH],
+        lifetime: u32,
+        key: &[u8],
+    ) -> Self {
+        KeyingMaterial {
+            app_id,
+            kek_id,
+            lifetime,
+            key: key.to_vec(),
+        }
+    }
+
+    pub fn get_app_id(&self) -> &[u8; APP_ID_LENGTH] {
+        &self.app_id
+    }
+
+    pub fn get_kek_id(&self) -> &[u8; KEK_ID_LENGTH] {
+        &self.kek_id
+    }
+
+    pub fn get_lifetime(&self) -> u32 {
+        self.lifetime
+    }
+
+    /// Returns the unwrapped key.
+    pub fn get_key(&self) -> &[u8] {
+        &self.key
+    }
+
+    fn encode(&self, kek: &[u8]) -> Result<Vec<u8>, AVPError> {
+        let value = [
+            &self.app_id[..],
+            &self.kek_id,
+            &self.lifetime.to_be_bytes(),
+            &wrap_key(kek, &self.key)?,
+        ]
+        .concat();
+        if value.len() > MAX_VENDOR_VALUE_LENGTH {
+            return Err(AVPError::InvalidAttributeLengthError(
+                "at most 247 bytes of Keying-Material".to_owned(),
+                value.len(),
+            ));
+        }
+        Ok(value)
+    }
+
+    fn decode(value: &[u8], kek: &[u8]) -> Result<Self, AVPError> {
+        if value.len() < KEYING_MATERIAL_HEADER_LENGTH {
+            return Err(AVPError::InvalidAttributeLengthError(
+                "at least 28 bytes of Keying-Material".to_owned(),
+                value.len(),
+            ));
+        }
+        Ok(KeyingMaterial {
+            app_id: value[..APP_ID_LENGTH].try_into().unwrap(),
+            kek_id: value[APP_ID_LENGTH..APP_ID_LENGTH + KEK_ID_LENGTH]
+                .try_into()
+                .unwrap(),
+            lifetime: u32::from_be_bytes(
+                value[APP_ID_LENGTH + KEK_ID_LENGTH..KEYING_MATERIAL_HEADER_LENGTH]
+                    .try_into()
+                    .unwrap(),
+            ),
+            key: unwrap_key(kek, &value[KEYING_MATERIAL_HEADER_LENGTH..])?,
+        })
+    }
+}
+
+/// Delete all of `keying_material` values from a packet.
+pub fn delete_keying_material(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, KEYING_MATERIAL_TYPE);
+}
+/// Add `keying_material` value to a packet, whose key is wrapped by the KEK.
+pub fn add_keying_material(
+    packet: &mut Packet,
+    kek: &[u8],
+    value: &KeyingMaterial,
+) -> Result<(), AVPError> {
+    packet.add(vsa::make_vendor_specific(
+        VENDOR_CISCO,
+        KEYING_MATERIAL_TYPE,
+        &value.encode(kek)?,
+    ));
+    Ok(())
+}
+/// Lookup a `keying_material` value from a packet, and unwrap its key by the KEK.
+///
+/// It returns the first looked up value. If there is no associated value with `keying_material`, it returns `None`.
+pub fn lookup_keying_material(
+    packet: &Packet,
+    kek: &[u8],
+) -> Option<Result<KeyingMaterial, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, KEYING_MATERIAL_TYPE)
+        .map(|avp| KeyingMaterial::decode(&avp.encode_bytes(), kek))
+}
+
+/// Delete all of `mac_randomizer` values from a packet.
+pub fn delete_mac_randomizer(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, MAC_RANDOMIZER_TYPE);
+}
+/// Add a new random `mac_randomizer` value to a packet; the existing values are replaced.
+pub fn add_mac_randomizer(packet: &mut Packet) {
+    let randomizer: [u8; MAC_RANDOMIZER_LENGTH] = random::rng().gen();
+    delete_mac_randomizer(packet);
+    packet.add(vsa::make_vendor_specific(
+        VENDOR_CISCO,
+        MAC_RANDOMIZER_TYPE,
+        &randomizer,
+    ));
+}
+/// Lookup a `mac_randomizer` value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `mac_randomizer`, it returns `None`.
+pub fn lookup_mac_randomizer(packet: &Packet) -> Option<Vec<u8>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_CISCO, MAC_RANDOMIZER_TYPE)
+        .map(|avp| avp.encode_bytes())
+}
+
+/// Add a Message-Authentication-Code to a packet, whose value is filled by `encode_packet()`; the existing one is
+/// replaced.
+pub fn add_message_authentication_code(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_CISCO, MESSAGE_AUTHENTICATION_CODE_TYPE);
+    packet.add(vsa::make_vendor_specific(
+        VENDOR_CISCO,
+        MESSAGE_AUTHENTICATION_CODE_TYPE,
+        &[0; MESSAGE_AUTHENTICATION_CODE_LENGTH],
+    ));
+}
+
+/// Returns the offset of the value of the first Message-Authentication-Code in the encoded packet, if any.
+fn find_value_offset(bs: &[u8]) -> Option<usize> {
+    const ATTRIBUTE_LENGTH: usize = 2 + 4 + 2 + MESSAGE_AUTHENTICATION_CODE_LENGTH;
+
+    let mut offset = RADIUS_PACKET_HEADER_LENGTH;
+    while offset + 2 <= bs.len() {
+        let typ = bs[offset];
+        let len = bs[offset + 1] as usize;
+        if len < 2 || offset + len > bs.len() {
+            return None;
+        }
+        if typ == rfc2865::VENDOR_SPECIFIC_TYPE
+            && len == ATTRIBUTE_LENGTH
+            && bs[offset + 2..offset + 6] == VENDOR_CISCO.to_be_bytes()
+            && bs[offset + 6] == MESSAGE_AUTHENTICATION_CODE_TYPE
+            && bs[offset + 7] as usize == 2 + MESSAGE_AUTHENTICATION_CODE_LENGTH
+        {
+            return Some(offset + 8);
+        }
+        offset += len;
+    }
+    None
+}
+
+fn compute(
+    bs: &mut [u8],
+    value_offset: usize,
+    authenticator: &[u8],
+    mac_key: &[u8],
+) -> [u8; MESSAGE_AUTHENTICATION_CODE_LENGTH] {
+    bs[4..RADIUS_PACKET_HEADER_LENGTH].copy_from_slice(authenticator);
+    bs[value_offset..value_offset + MESSAGE_AUTHENTICATION_CODE_LENGTH].fill(0);
+    if let Some(offset) = message_authenticator::find_value_offset(bs) {
+        bs[offset..offset + MESSAGE_AUTHENTICATOR_LENGTH].fill(0);
+    }
+    let mut mac = Hmac::<Sha256>::new_from_slice(mac_key).expect("HMAC accepts any key length");
+    mac.update(bs);
+    mac.finalize().into_bytes().into()
+}
+
+/// Encode a packet and fill its Message-Authentication-Code by the MAC key, if any (see `Packet::encode()`).
+pub fn encode_packet(packet: &Packet, mac_key: &[u8]) -> Result<Vec<u8>, PacketError> {
+    let mut bs = packet.encode()?;
+    let value_offset = match find_value_offset(&bs) {
+        Some(value_offset) => value_offset,
+        None => return Ok(bs),
+    };
+    let code = packet.get_code();
+    let authenticator = if message_authenticator::has_zero_authenticator(code) {
+        [0; 16].to_vec()
+    } else {
+        packet.get_authenticator().clone()
+    };
+    let mac = compute(&mut bs, value_offset, &authenticator, mac_key);
+    bs[value_offset..value_offset + MESSAGE_AUTHENTICATION_CODE_LENGTH].copy_from_slice(&mac);
+
+    // the Authenticator field is the one of the computation here, as `Packet::encode_into()` does
+    message_authenticator::sign(&mut bs, packet.get_secret());
+    if !matches!(code, Code::AccessRequest | Code::StatusServer) {
+        let mut context = md5::Context::new();
+        context.consume(&bs);
+        context.consume(packet.get_secret());
+        bs[4..RADIUS_PACKET_HEADER_LENGTH].copy_from_slice(&context.compute().0);
+    }
+    Ok(bs)
+}
+
+/// Verify the Message-Authentication-Code of a received packet by the Authenticator field of the computation, i.e.
+/// the one of the request for a response, and zeros for the requests but Access-Request and Status-Server.
+///
+/// This returns `None` if the packet doesn't have Message-Authentication-Code.
+pub fn verify(datagram: &[u8], authenticator: &[u8], mac_key: &[u8]) -> Option<bool> {
+    if datagram.len() < RADIUS_PACKET_HEADER_LENGTH {
+        return None;
+    }
+    let len = (u16::from_be_bytes([datagram[2], datagram[3]]) as usize).min(datagram.len());
+    let mut bs: Vec<u8> = datagram[..len].to_vec();
+    let value_offset = find_value_offset(&bs)?;
+    let expected = bs[value_offset..value_offset + MESSAGE_AUTHENTICATION_CODE_LENGTH].to_vec();
+    Some(constant_time::eq(
+        &compute(&mut bs, value_offset, authenticator, mac_key),
+        &expected,
+    ))
+}
+
+/// Verify the Message-Authentication-Code of a received request.
+///
+/// This returns `None` if the request doesn't have Message-Authentication-Code.
+pub fn verify_request(request: &[u8], mac_key: &[u8]) -> Option<bool> {
+    if request.len() < RADIUS_PACKET_HEADER_LENGTH {
+        return None;
+    }
+    if message_authenticator::has_zero_authenticator(Code::from(request[0])) {
+        verify(request, &[0; 16], mac_key)
+    } else {
+        verify(request, &request[4..RADIUS_PACKET_HEADER_LENGTH], mac_key)
+    }
+}
+
+/// Verify the Message-Authentication-Code of a received response with the request that it answers.
+///
+/// This returns `None` if the response doesn't have Message-Authentication-Code.
+pub fn verify_response(response: &[u8], request: &[u8], mac_key: &[u8]) -> Option<bool> {
+    if request.len() < RADIUS_PACKET_HEADER_LENGTH {
+        return None;
+    }
+    verify(response, &request[4..RADIUS_PACKET_HEADER_LENGTH], mac_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::avp::AVPError;
+    use crate::core::code::Code;
+    use crate::core::message_authenticator;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::core::rfc6218::{self, unwrap_key, wrap_key, KeyingMaterial};
+
+    #[test]
+    fn test_key_wrap() {
+        // test vectors from RFC 3394 section 4
+        let kek: Vec<u8> = (0..32).collect();
+        let key: Vec<u8> = (0..16).map(|i| i * 0x11).chain(0..16).collect::<Vec<u8>>();
+        for (kek, key, expected) in [
+            (
+                &kek[..16],
+                &key[..16],
+                "1fa68b0a8112b447aef34bd8fb5a7b829d3e862371d2cfe5",
+            ),
+            (
+                &kek[..],
+                &key[..],
+                "28c9f404c4b810f4cbccb35cfb87f8263f5786e2d80ed326cbc7f0e71a99f43bfb988b9b7a02dd21",
+            ),
+        ] {
+            let wrapped = wrap_key(kek, key).unwrap();
+            let expected: Vec<u8> = (0..expected.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&expected[i..i + 2], 16).unwrap())
+                .collect();
+            assert_eq!(wrapped, expected);
+            assert_eq!(unwrap_key(kek, &wrapped).unwrap(), key);
+        }
+
+        let mut wrapped = wrap_key(&kek[..16], &key[..16]).unwrap();
+        wrapped[10] ^= 0x01;
+        assert!(matches!(
+            unwrap_key(&kek[..16], &wrapped),
+            Err(AVPError::DecodingError(_))
+        ));
+        assert!(unwrap_key(&kek[16..], &wrap_key(&kek[..16], &key).unwrap()).is_err());
+        assert!(wrap_key(&kek[..16], &[0; 12]).is_err());
+        assert!(wrap_key(&[0; 10], &key).is_err());
+    }
+
+    #[test]
+    fn test_keying_material_and_mac() {
+        let kek = [0x11; 16];
+        let mac_key = b"mac-key";
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "bob");
+        let request_datagram = request.encode().unwrap();
+
+        let mut response = request.make_response_packet(Code::AccessAccept);
+        let keying_material = KeyingMaterial::new([1; 8], [2; 16], 3600, &[3; 64]);
+        rfc6218::add_keying_material(&mut response, &kek, &keying_material).unwrap();
+        rfc6218::add_mac_randomizer(&mut response);
+        rfc6218::add_mac_randomizer(&mut response);
+        response.add_message_authenticator();
+        rfc6218::add_message_authentication_code(&mut response);
+        let datagram = rfc6218::encode_packet(&response, mac_key).unwrap();
+
+        assert_eq!(
+            rfc6218::verify_response(&datagram, &request_datagram, mac_key),
+            Some(true)
+        );
+        assert_eq!(
+            rfc6218::verify_response(&datagram, &request_datagram, b"wrong"),
+            Some(false)
+        );
+        assert_eq!(
+            message_authenticator::verify_response(&datagram, &request_datagram, b"secret"),
+            Some(true)
+        );
+        assert!(Packet::is_authentic_response(
+            &datagram,
+            &request_datagram,
+            b"secret"
+        ));
+
+        let received = Packet::decode(&datagram, b"secret").unwrap();
+        assert_eq!(
+            rfc6218::lookup_keying_material(&received, &kek)
+                .unwrap()
+                .unwrap(),
+            keying_material
+        );
+        assert!(rfc6218::lookup_keying_material(&received, &[0x12; 16])
+            .unwrap()
+            .is_err());
+        let randomizer = rfc6218::lookup_mac_randomizer(&received).unwrap();
+        assert_eq!(randomizer.len(), rfc6218::MAC_RANDOMIZER_LENGTH);
+
+        let mut tampered = datagram.clone();
+        let offset = datagram.len() - 1;
+        tampered[offset] ^= 0x01;
+        assert_eq!(
+            rfc6218::verify_response(&tampered, &request_datagram, mac_key),
+            Some(false)
+        );
+
+        let mut accounting = Packet::new(Code::AccountingRequest, b"secret");
+        rfc6218::add_message_authentication_code(&mut accounting);
+        let datagram = rfc6218::encode_packet(&accounting, mac_key).unwrap();
+        assert_eq!(rfc6218::verify_request(&datagram, mac_key), Some(true));
+        assert_eq!(rfc6218::verify_request(&request_datagram, mac_key), None);
+    }
+}
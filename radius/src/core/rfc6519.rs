@@ -18,7 +18,11 @@
 //! ATTRIBUTE    DS-Lite-Tunnel-Name            144    string
 //! ```
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const DS_LITE_TUNNEL_NAME_TYPE: AVPType = 144;
@@ -46,3 +50,12 @@ pub fn lookup_all_ds_lite_tunnel_name(packet: &Packet) -> Result<Vec<String>, AV
     }
     Ok(vec)
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[AttributeDefinition::new(
+    "DS-Lite-Tunnel-Name",
+    DS_LITE_TUNNEL_NAME_TYPE,
+    "string",
+)];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
@@ -35,9 +35,14 @@
 //! ATTRIBUTE    PMIP6-Visited-IPV4-Gateway        162    ipaddr
 //! ```
 
-use std::net::{Ipv4Addr, Ipv6Addr};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::net::{Ipv4Addr, Ipv6Addr};
 
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::ipv6::{InterfaceId, Ipv6Prefix};
 use crate::core::packet::Packet;
 
 pub const MOBILE_NODE_IDENTIFIER_TYPE: AVPType = 145;
@@ -230,6 +235,29 @@ pub fn lookup_all_pmip6_home_hn_prefix(packet: &Packet) -> Result<Vec<Vec<u8>>,
     }
     Ok(vec)
 }
+/// Add `pmip6_home_hn_prefix` ipv6 prefix value with the prefix length to a packet.
+pub fn add_pmip6_home_hn_prefix_value(packet: &mut Packet, value: &Ipv6Prefix) {
+    packet.add(AVP::from_ipv6_prefix_value(
+        PMIP6_HOME_HN_PREFIX_TYPE,
+        value,
+    ));
+}
+/// Lookup a `pmip6_home_hn_prefix` ipv6 prefix value with the prefix length from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `pmip6_home_hn_prefix`, it returns `None`.
+pub fn lookup_pmip6_home_hn_prefix_value(packet: &Packet) -> Option<Result<Ipv6Prefix, AVPError>> {
+    packet
+        .lookup(PMIP6_HOME_HN_PREFIX_TYPE)
+        .map(|v| v.encode_ipv6_prefix_value())
+}
+/// Lookup all of the `pmip6_home_hn_prefix` ipv6 prefix value with the prefix length from a packet.
+pub fn lookup_all_pmip6_home_hn_prefix_value(packet: &Packet) -> Result<Vec<Ipv6Prefix>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(PMIP6_HOME_HN_PREFIX_TYPE) {
+        vec.push(avp.encode_ipv6_prefix_value()?)
+    }
+    Ok(vec)
+}
 
 pub const PMIP6_VISITED_HN_PREFIX_TYPE: AVPType = 152;
 /// Delete all of `pmip6_visited_hn_prefix` values from a packet.
@@ -257,6 +285,33 @@ pub fn lookup_all_pmip6_visited_hn_prefix(packet: &Packet) -> Result<Vec<Vec<u8>
     }
     Ok(vec)
 }
+/// Add `pmip6_visited_hn_prefix` ipv6 prefix value with the prefix length to a packet.
+pub fn add_pmip6_visited_hn_prefix_value(packet: &mut Packet, value: &Ipv6Prefix) {
+    packet.add(AVP::from_ipv6_prefix_value(
+        PMIP6_VISITED_HN_PREFIX_TYPE,
+        value,
+    ));
+}
+/// Lookup a `pmip6_visited_hn_prefix` ipv6 prefix value with the prefix length from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `pmip6_visited_hn_prefix`, it returns `None`.
+pub fn lookup_pmip6_visited_hn_prefix_value(
+    packet: &Packet,
+) -> Option<Result<Ipv6Prefix, AVPError>> {
+    packet
+        .lookup(PMIP6_VISITED_HN_PREFIX_TYPE)
+        .map(|v| v.encode_ipv6_prefix_value())
+}
+/// Lookup all of the `pmip6_visited_hn_prefix` ipv6 prefix value with the prefix length from a packet.
+pub fn lookup_all_pmip6_visited_hn_prefix_value(
+    packet: &Packet,
+) -> Result<Vec<Ipv6Prefix>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(PMIP6_VISITED_HN_PREFIX_TYPE) {
+        vec.push(avp.encode_ipv6_prefix_value()?)
+    }
+    Ok(vec)
+}
 
 pub const PMIP6_HOME_INTERFACE_ID_TYPE: AVPType = 153;
 /// Delete all of `pmip6_home_interface_id` values from a packet.
@@ -290,6 +345,30 @@ pub fn lookup_all_pmip6_home_interface_id(packet: &Packet) -> Vec<Vec<u8>> {
     }
     vec
 }
+/// Add `pmip6_home_interface_id` interface id value to a packet.
+pub fn add_pmip6_home_interface_id_value(packet: &mut Packet, value: &InterfaceId) {
+    packet.add(AVP::from_interface_id(PMIP6_HOME_INTERFACE_ID_TYPE, value));
+}
+/// Lookup a `pmip6_home_interface_id` interface id value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `pmip6_home_interface_id`, it returns `None`.
+pub fn lookup_pmip6_home_interface_id_value(
+    packet: &Packet,
+) -> Option<Result<InterfaceId, AVPError>> {
+    packet
+        .lookup(PMIP6_HOME_INTERFACE_ID_TYPE)
+        .map(|v| v.encode_interface_id())
+}
+/// Lookup all of the `pmip6_home_interface_id` interface id value from a packet.
+pub fn lookup_all_pmip6_home_interface_id_value(
+    packet: &Packet,
+) -> Result<Vec<InterfaceId>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(PMIP6_HOME_INTERFACE_ID_TYPE) {
+        vec.push(avp.encode_interface_id()?)
+    }
+    Ok(vec)
+}
 
 pub const PMIP6_VISITED_INTERFACE_ID_TYPE: AVPType = 154;
 /// Delete all of `pmip6_visited_interface_id` values from a packet.
@@ -323,6 +402,33 @@ pub fn lookup_all_pmip6_visited_interface_id(packet: &Packet) -> Vec<Vec<u8>> {
     }
     vec
 }
+/// Add `pmip6_visited_interface_id` interface id value to a packet.
+pub fn add_pmip6_visited_interface_id_value(packet: &mut Packet, value: &InterfaceId) {
+    packet.add(AVP::from_interface_id(
+        PMIP6_VISITED_INTERFACE_ID_TYPE,
+        value,
+    ));
+}
+/// Lookup a `pmip6_visited_interface_id` interface id value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `pmip6_visited_interface_id`, it returns `None`.
+pub fn lookup_pmip6_visited_interface_id_value(
+    packet: &Packet,
+) -> Option<Result<InterfaceId, AVPError>> {
+    packet
+        .lookup(PMIP6_VISITED_INTERFACE_ID_TYPE)
+        .map(|v| v.encode_interface_id())
+}
+/// Lookup all of the `pmip6_visited_interface_id` interface id value from a packet.
+pub fn lookup_all_pmip6_visited_interface_id_value(
+    packet: &Packet,
+) -> Result<Vec<InterfaceId>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(PMIP6_VISITED_INTERFACE_ID_TYPE) {
+        vec.push(avp.encode_interface_id()?)
+    }
+    Ok(vec)
+}
 
 pub const PMIP6_HOME_IPV4_HO_A_TYPE: AVPType = 155;
 /// Delete all of `pmip6_home_ipv4_ho_a` values from a packet.
@@ -555,3 +661,95 @@ pub fn lookup_all_pmip6_visited_ipv4_gateway(packet: &Packet) -> Result<Vec<Ipv4
     }
     Ok(vec)
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new(
+        "Mobile-Node-Identifier",
+        MOBILE_NODE_IDENTIFIER_TYPE,
+        "octets",
+    ),
+    AttributeDefinition::new("Service-Selection", SERVICE_SELECTION_TYPE, "string"),
+    AttributeDefinition::new(
+        "PMIP6-Home-LMA-IPV6-Address",
+        PMIP6_HOME_LMA_IPV6_ADDRESS_TYPE,
+        "ipv6addr",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Visited-LMA-IPV6-Address",
+        PMIP6_VISITED_LMA_IPV6_ADDRESS_TYPE,
+        "ipv6addr",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Home-LMA-IPV4-Address",
+        PMIP6_HOME_LMA_IPV4_ADDRESS_TYPE,
+        "ipaddr",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Visited-LMA-IPV4-Address",
+        PMIP6_VISITED_LMA_IPV4_ADDRESS_TYPE,
+        "ipaddr",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Home-HN-Prefix",
+        PMIP6_HOME_HN_PREFIX_TYPE,
+        "ipv6prefix",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Visited-HN-Prefix",
+        PMIP6_VISITED_HN_PREFIX_TYPE,
+        "ipv6prefix",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Home-Interface-ID",
+        PMIP6_HOME_INTERFACE_ID_TYPE,
+        "ifid",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Visited-Interface-ID",
+        PMIP6_VISITED_INTERFACE_ID_TYPE,
+        "ifid",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Home-IPV4-HoA",
+        PMIP6_HOME_IPV4_HO_A_TYPE,
+        "ipv4prefix",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Visited-IPV4-HoA",
+        PMIP6_VISITED_IPV4_HO_A_TYPE,
+        "ipv4prefix",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Home-DHCP4-Server-Address",
+        PMIP6_HOME_DHCP4_SERVER_ADDRESS_TYPE,
+        "ipaddr",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Visited-DHCP4-Server-Address",
+        PMIP6_VISITED_DHCP4_SERVER_ADDRESS_TYPE,
+        "ipaddr",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Home-DHCP6-Server-Address",
+        PMIP6_HOME_DHCP6_SERVER_ADDRESS_TYPE,
+        "ipv6addr",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Visited-DHCP6-Server-Address",
+        PMIP6_VISITED_DHCP6_SERVER_ADDRESS_TYPE,
+        "ipv6addr",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Home-IPV4-Gateway",
+        PMIP6_HOME_IPV4_GATEWAY_TYPE,
+        "ipaddr",
+    ),
+    AttributeDefinition::new(
+        "PMIP6-Visited-IPV4-Gateway",
+        PMIP6_VISITED_IPV4_GATEWAY_TYPE,
+        "ipaddr",
+    ),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
@@ -26,7 +26,11 @@
 //! VALUE    EAP-Lower-Layer            PANA-Preauth        9
 //! ```
 
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const EAP_LOWER_LAYER_TYPE: AVPType = 163;
@@ -54,6 +58,26 @@ pub fn lookup_all_eap_lower_layer(packet: &Packet) -> Result<Vec<EapLowerLayer>,
     }
     Ok(vec)
 }
+/// Lookup a `eap_lower_layer` value from a packet as `EapLowerLayerValue`.
+///
+/// It returns the first looked up value. If there is no associated value with `eap_lower_layer`, it returns `None`.
+pub fn lookup_eap_lower_layer_value(
+    packet: &Packet,
+) -> Option<Result<EapLowerLayerValue, AVPError>> {
+    packet
+        .lookup(EAP_LOWER_LAYER_TYPE)
+        .map(|v| core::convert::TryFrom::try_from(v.encode_u32()?))
+}
+/// Lookup all of the `eap_lower_layer` values from a packet as `EapLowerLayerValue`.
+pub fn lookup_all_eap_lower_layer_value(
+    packet: &Packet,
+) -> Result<Vec<EapLowerLayerValue>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(EAP_LOWER_LAYER_TYPE) {
+        vec.push(core::convert::TryFrom::try_from(avp.encode_u32()?)?)
+    }
+    Ok(vec)
+}
 
 pub type EapLowerLayer = u32;
 pub const EAP_LOWER_LAYER_WIRED_IEEE_802_1X: EapLowerLayer = 1;
@@ -65,3 +89,77 @@ pub const EAP_LOWER_LAYER_PPP: EapLowerLayer = 6;
 pub const EAP_LOWER_LAYER_PANA_NO_PREAUTH: EapLowerLayer = 7;
 pub const EAP_LOWER_LAYER_GSS_API: EapLowerLayer = 8;
 pub const EAP_LOWER_LAYER_PANA_PREAUTH: EapLowerLayer = 9;
+
+/// The values of `EAP-Lower-Layer` that are defined by the dictionaries, for the exhaustive matches.
+///
+/// `EapLowerLayerValue::try_from()` fails with `AVPError::UnknownValueError` on the other values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum EapLowerLayerValue {
+    /// `Wired-IEEE-802.1X`
+    WiredIEEE8021X = 1,
+    /// `IEEE-802.1X-No-Preauth`
+    Ieee8021XNoPreauth = 2,
+    /// `IEEE-802.1X-Preauth`
+    Ieee8021XPreauth = 3,
+    /// `IEEE-802.16e`
+    Ieee80216E = 4,
+    /// `IKEv2`
+    Ikev2 = 5,
+    /// `PPP`
+    Ppp = 6,
+    /// `PANA-No-Preauth`
+    PanaNoPreauth = 7,
+    /// `GSS-API`
+    GssApi = 8,
+    /// `PANA-Preauth`
+    PanaPreauth = 9,
+}
+
+impl From<EapLowerLayerValue> for u32 {
+    fn from(value: EapLowerLayerValue) -> Self {
+        value as u32
+    }
+}
+
+impl core::convert::TryFrom<u32> for EapLowerLayerValue {
+    type Error = AVPError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(EapLowerLayerValue::WiredIEEE8021X),
+            2 => Ok(EapLowerLayerValue::Ieee8021XNoPreauth),
+            3 => Ok(EapLowerLayerValue::Ieee8021XPreauth),
+            4 => Ok(EapLowerLayerValue::Ieee80216E),
+            5 => Ok(EapLowerLayerValue::Ikev2),
+            6 => Ok(EapLowerLayerValue::Ppp),
+            7 => Ok(EapLowerLayerValue::PanaNoPreauth),
+            8 => Ok(EapLowerLayerValue::GssApi),
+            9 => Ok(EapLowerLayerValue::PanaPreauth),
+            _ => Err(AVPError::UnknownValueError(
+                "EAP-Lower-Layer".to_owned(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[AttributeDefinition::new(
+    "EAP-Lower-Layer",
+    EAP_LOWER_LAYER_TYPE,
+    "integer",
+)];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[
+    ValueDefinition::new("EAP-Lower-Layer", "Wired-IEEE-802.1X", 1),
+    ValueDefinition::new("EAP-Lower-Layer", "IEEE-802.1X-No-Preauth", 2),
+    ValueDefinition::new("EAP-Lower-Layer", "IEEE-802.1X-Preauth", 3),
+    ValueDefinition::new("EAP-Lower-Layer", "IEEE-802.16e", 4),
+    ValueDefinition::new("EAP-Lower-Layer", "IKEv2", 5),
+    ValueDefinition::new("EAP-Lower-Layer", "PPP", 6),
+    ValueDefinition::new("EAP-Lower-Layer", "PANA-No-Preauth", 7),
+    ValueDefinition::new("EAP-Lower-Layer", "GSS-API", 8),
+    ValueDefinition::new("EAP-Lower-Layer", "PANA-Preauth", 9),
+];
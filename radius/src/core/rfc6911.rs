@@ -20,9 +20,13 @@
 //! ATTRIBUTE    Stateful-IPV6-Address-Pool        172    string
 //! ```
 
-use std::net::Ipv6Addr;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::net::Ipv6Addr;
 
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::ipv6::Ipv6Prefix;
 use crate::core::packet::Packet;
 
 pub const FRAMED_IPV6_ADDRESS_TYPE: AVPType = 168;
@@ -103,6 +107,33 @@ pub fn lookup_all_route_ipv6_information(packet: &Packet) -> Result<Vec<Vec<u8>>
     }
     Ok(vec)
 }
+/// Add `route_ipv6_information` ipv6 prefix value with the prefix length to a packet.
+pub fn add_route_ipv6_information_value(packet: &mut Packet, value: &Ipv6Prefix) {
+    packet.add(AVP::from_ipv6_prefix_value(
+        ROUTE_IPV6_INFORMATION_TYPE,
+        value,
+    ));
+}
+/// Lookup a `route_ipv6_information` ipv6 prefix value with the prefix length from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `route_ipv6_information`, it returns `None`.
+pub fn lookup_route_ipv6_information_value(
+    packet: &Packet,
+) -> Option<Result<Ipv6Prefix, AVPError>> {
+    packet
+        .lookup(ROUTE_IPV6_INFORMATION_TYPE)
+        .map(|v| v.encode_ipv6_prefix_value())
+}
+/// Lookup all of the `route_ipv6_information` ipv6 prefix value with the prefix length from a packet.
+pub fn lookup_all_route_ipv6_information_value(
+    packet: &Packet,
+) -> Result<Vec<Ipv6Prefix>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in packet.lookup_all(ROUTE_IPV6_INFORMATION_TYPE) {
+        vec.push(avp.encode_ipv6_prefix_value()?)
+    }
+    Ok(vec)
+}
 
 pub const DELEGATED_IPV6_PREFIX_POOL_TYPE: AVPType = 171;
 /// Delete all of `delegated_ipv6_prefix_pool` values from a packet.
@@ -155,3 +186,30 @@ pub fn lookup_all_stateful_ipv6_address_pool(packet: &Packet) -> Result<Vec<Stri
     }
     Ok(vec)
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("Framed-IPV6-Address", FRAMED_IPV6_ADDRESS_TYPE, "ipv6addr"),
+    AttributeDefinition::new(
+        "DNS-Server-IPV6-Address",
+        DNS_SERVER_IPV6_ADDRESS_TYPE,
+        "ipv6addr",
+    ),
+    AttributeDefinition::new(
+        "Route-IPV6-Information",
+        ROUTE_IPV6_INFORMATION_TYPE,
+        "ipv6prefix",
+    ),
+    AttributeDefinition::new(
+        "Delegated-IPV6-Prefix-Pool",
+        DELEGATED_IPV6_PREFIX_POOL_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "Stateful-IPV6-Address-Pool",
+        STATEFUL_IPV6_ADDRESS_POOL_TYPE,
+        "string",
+    ),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
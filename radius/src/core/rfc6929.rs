@@ -0,0 +1,417 @@
+//! Utility for rfc6929 packet, i.e. the Extended and the Long Extended attributes of RADIUS.
+//!
+//! The attributes of the types 241 to 246 are the containers of the extended space: the first octet of the value is
+//! the Extended-Type, so an attribute of the extended space is identified by the pair of them (e.g. `241.1`).
+//! The Long Extended types (245 and 246) have the flags octet after that, whose More flag fragments a value across
+//! the contiguous attributes, and the Extended-Type 26 is the Extended-Vendor-Specific that carries the Vendor-Id and
+//! the Evs-Type (RFC 6929 section 2.4).
+//!
+//! This module is written by hand, since the dictionaries of the extended space don't fit the generated modules;
+//! `add_extended()` and `lookup_extended()` encode and decode (and reassemble) the values of an `ExtendedType`.
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::packet::Packet;
+
+pub const EXTENDED_ATTRIBUTE_1_TYPE: AVPType = 241;
+pub const EXTENDED_ATTRIBUTE_2_TYPE: AVPType = 242;
+pub const EXTENDED_ATTRIBUTE_3_TYPE: AVPType = 243;
+pub const EXTENDED_ATTRIBUTE_4_TYPE: AVPType = 244;
+pub const EXTENDED_ATTRIBUTE_5_TYPE: AVPType = 245;
+pub const EXTENDED_ATTRIBUTE_6_TYPE: AVPType = 246;
+
+/// The Extended-Type of Extended-Vendor-Specific.
+pub const EXTENDED_VENDOR_SPECIFIC_TYPE: u8 = 26;
+
+/// The More flag of the Long Extended types.
+const MORE_FLAG: u8 = 0x80;
+
+/// The maximum length of the value of an attribute.
+const MAX_VALUE_LENGTH: usize = 253;
+
+/// Returns whether the type is a container of the extended space (241 to 246).
+pub fn is_extended(typ: AVPType) -> bool {
+    (EXTENDED_ATTRIBUTE_1_TYPE..=EXTENDED_ATTRIBUTE_6_TYPE).contains(&typ)
+}
+
+/// Returns whether the type is a Long Extended type (245 and 246).
+pub fn is_long_extended(typ: AVPType) -> bool {
+    typ == EXTENDED_ATTRIBUTE_5_TYPE || typ == EXTENDED_ATTRIBUTE_6_TYPE
+}
+
+/// The type of an attribute of the extended space, i.e. the Type and the Extended-Type (e.g. `241.1`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExtendedType {
+    typ: AVPType,
+    extended_type: u8,
+}
+
+impl ExtendedType {
+    /// A constructor for the Extended-Type of the container type (241 to 246).
+    pub const fn new(typ: AVPType, extended_type: u8) -> Self {
+        ExtendedType { typ, extended_type }
+    }
+
+    pub fn get_type(&self) -> AVPType {
+        self.typ
+    }
+
+    pub fn get_extended_type(&self) -> u8 {
+        self.extended_type
+    }
+}
+
+impl fmt::Display for ExtendedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.typ, self.extended_type)
+    }
+}
+
+/// The Vendor-Id and the Evs-Type of an Extended-Vendor-Specific attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExtendedVendor {
+    vendor_id: u32,
+    evs_type: u8,
+}
+
+impl ExtendedVendor {
+    pub const fn new(vendor_id: u32, evs_type: u8) -> Self {
+        ExtendedVendor {
+            vendor_id,
+            evs_type,
+        }
+    }
+
+    pub fn get_vendor_id(&self) -> u32 {
+        self.vendor_id
+    }
+
+    pub fn get_evs_type(&self) -> u8 {
+        self.evs_type
+    }
+}
+
+/// A decoded attribute of the extended space, whose fragments have been reassembled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedAttribute {
+    typ: ExtendedType,
+    vendor: Option<ExtendedVendor>,
+    value: Vec<u8>,
+}
+
+impl ExtendedAttribute {
+    pub fn get_type(&self) -> ExtendedType {
+        self.typ
+    }
+
+    /// Returns the vendor of an Extended-Vendor-Specific attribute.
+    pub fn get_vendor(&self) -> Option<ExtendedVendor> {
+        self.vendor
+    }
+
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+/// Make the attributes of a value of the extended space; a value of the Long Extended types is fragmented.
+fn encode(
+    typ: ExtendedType,
+    vendor: Option<ExtendedVendor>,
+    value: &[u8],
+) -> Result<Vec<AVP>, AVPError> {
+    if !is_extended(typ.typ) {
+        return Err(AVPError::DecodingError(format!(
+            "type {} is not extended",
+            typ.typ
+        )));
+    }
+
+    let long = is_long_extended(typ.typ);
+    let mut header = vec![typ.extended_type];
+    if long {
+        header.push(0);
+    }
+    if let Some(vendor) = vendor {
+        header.extend(vendor.vendor_id.to_be_bytes());
+        header.push(vendor.evs_type);
+    }
+    let max_fragment_length = MAX_VALUE_LENGTH - header.len();
+
+    if !long {
+        if value.len() > max_fragment_length {
+            return Err(AVPError::InvalidAttributeLengthError(
+                format!("{max_fragment_length} bytes"),
+                value.len(),
+            ));
+        }
+        return Ok(vec![AVP::from_bytes(
+            typ.typ,
+            &[&header[..], value].concat(),
+        )]);
+    }
+
+    // the Vendor-Id and the Evs-Type are repeated in each fragment
+    let mut fragments: Vec<&[u8]> = value.chunks(max_fragment_length).collect();
+    if fragments.is_empty() {
+        fragments.push(&[]);
+    }
+    let last = fragments.len() - 1;
+    Ok(fragments
+        .into_iter()
+        .enumerate()
+        .map(|(i, fragment)| {
+            header[1] = if i == last { 0 } else { MORE_FLAG };
+            AVP::from_bytes(typ.typ, &[&header[..], fragment].concat())
+        })
+        .collect())
+}
+
+/// Decode the attributes of the extended space of a packet, reassembling the fragments of the Long Extended types.
+pub fn decode_extended_attributes(packet: &Packet) -> Result<Vec<ExtendedAttribute>, AVPError> {
+    let mut attributes: Vec<ExtendedAttribute> = Vec::new();
+    // whether the last attribute has the More flag, i.e. the next attribute has to be its fragment
+    let mut more = false;
+
+    for avp in packet.get_attributes() {
+        let typ = avp.get_type();
+        if !is_extended(typ) {
+            if more {
+                return Err(AVPError::DecodingError(
+                    "the fragment of a Long Extended attribute is missing".to_owned(),
+                ));
+            }
+            continue;
+        }
+
+        let value = avp.get_value();
+        let long = is_long_extended(typ);
+        let header_length = if long { 2 } else { 1 };
+        if value.len() < header_length {
+            return Err(AVPError::InvalidAttributeLengthError(
+                format!("at least {header_length} bytes"),
+                value.len(),
+            ));
+        }
+        let extended_type = ExtendedType::new(typ, value[0]);
+        let flags = if long { value[1] } else { 0 };
+        let mut data = &value[header_length..];
+
+        let vendor = if extended_type.extended_type == EXTENDED_VENDOR_SPECIFIC_TYPE {
+            if data.len() < 5 {
+                return Err(AVPError::InvalidAttributeLengthError(
+                    format!("at least {} bytes", header_length + 5),
+                    value.len(),
+                ));
+            }
+            let vendor = ExtendedVendor::new(
+                u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+                data[4],
+            );
+            data = &data[5..];
+            Some(vendor)
+        } else {
+            None
+        };
+
+        if more {
+            let previous = attributes.last_mut().unwrap();
+            if previous.typ != extended_type || previous.vendor != vendor {
+                return Err(AVPError::DecodingError(format!(
+                    "the fragment of {} is missing",
+                    previous.typ
+                )));
+            }
+            previous.value.extend_from_slice(data);
+        } else {
+            attributes.push(ExtendedAttribute {
+                typ: extended_type,
+                vendor,
+                value: data.to_vec(),
+            });
+        }
+        more = flags & MORE_FLAG == MORE_FLAG;
+    }
+
+    if more {
+        return Err(AVPError::DecodingError(
+            "the fragment of a Long Extended attribute is missing".to_owned(),
+        ));
+    }
+    Ok(attributes)
+}
+
+/// Delete all of the values of an `ExtendedType` (including the fragments) from a packet.
+pub fn delete_extended(packet: &mut Packet, typ: ExtendedType) {
+    packet.retain_attributes(|avp| {
+        avp.get_type() != typ.typ || avp.get_value().first() != Some(&typ.extended_type)
+    });
+}
+/// Add a value of an `ExtendedType` to a packet; a value of the Long Extended types is fragmented by the More flag.
+pub fn add_extended(packet: &mut Packet, typ: ExtendedType, value: &[u8]) -> Result<(), AVPError> {
+    packet.extend(encode(typ, None, value)?);
+    Ok(())
+}
+/// Lookup a value of an `ExtendedType` from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with the type, it returns `None`.
+pub fn lookup_extended(packet: &Packet, typ: ExtendedType) -> Option<Result<Vec<u8>, AVPError>> {
+    match lookup_all_extended(packet, typ) {
+        Ok(values) => values.into_iter().next().map(Ok),
+        Err(e) => Some(Err(e)),
+    }
+}
+/// Lookup all of the values of an `ExtendedType` from a packet.
+pub fn lookup_all_extended(packet: &Packet, typ: ExtendedType) -> Result<Vec<Vec<u8>>, AVPError> {
+    Ok(decode_extended_attributes(packet)?
+        .into_iter()
+        .filter(|attribute| attribute.typ == typ && attribute.vendor.is_none())
+        .map(|attribute| attribute.value)
+        .collect())
+}
+
+/// Add an Extended-Vendor-Specific value of the container type (241 to 246) to a packet.
+pub fn add_extended_vendor_specific(
+    packet: &mut Packet,
+    typ: AVPType,
+    vendor: ExtendedVendor,
+    value: &[u8],
+) -> Result<(), AVPError> {
+    packet.extend(encode(
+        ExtendedType::new(typ, EXTENDED_VENDOR_SPECIFIC_TYPE),
+        Some(vendor),
+        value,
+    )?);
+    Ok(())
+}
+/// Lookup an Extended-Vendor-Specific value of the container type and the vendor from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with the vendor, it returns `None`.
+pub fn lookup_extended_vendor_specific(
+    packet: &Packet,
+    typ: AVPType,
+    vendor: ExtendedVendor,
+) -> Option<Result<Vec<u8>, AVPError>> {
+    let attributes = match decode_extended_attributes(packet) {
+        Ok(attributes) => attributes,
+        Err(e) => return Some(Err(e)),
+    };
+    attributes
+        .into_iter()
+        .find(|attribute| attribute.typ.typ == typ && attribute.vendor == Some(vendor))
+        .map(|attribute| Ok(attribute.value))
+}
+
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new(
+        "Extended-Attribute-1",
+        EXTENDED_ATTRIBUTE_1_TYPE,
+        "extended",
+    ),
+    AttributeDefinition::new(
+        "Extended-Attribute-2",
+        EXTENDED_ATTRIBUTE_2_TYPE,
+        "extended",
+    ),
+    AttributeDefinition::new(
+        "Extended-Attribute-3",
+        EXTENDED_ATTRIBUTE_3_TYPE,
+        "extended",
+    ),
+    AttributeDefinition::new(
+        "Extended-Attribute-4",
+        EXTENDED_ATTRIBUTE_4_TYPE,
+        "extended",
+    ),
+    AttributeDefinition::new(
+        "Extended-Attribute-5",
+        EXTENDED_ATTRIBUTE_5_TYPE,
+        "long-extended",
+    ),
+    AttributeDefinition::new(
+        "Extended-Attribute-6",
+        EXTENDED_ATTRIBUTE_6_TYPE,
+        "long-extended",
+    ),
+];
+
+/// The values that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
+
+#[cfg(test)]
+mod tests {
+    use crate::core::avp::AVP;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::core::rfc6929::*;
+
+    #[test]
+    fn test_extended() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        let short = ExtendedType::new(EXTENDED_ATTRIBUTE_1_TYPE, 1);
+        let long = ExtendedType::new(EXTENDED_ATTRIBUTE_5_TYPE, 2);
+        let vendor = ExtendedVendor::new(9, 3);
+        add_extended(&mut packet, short, b"abc").unwrap();
+        add_extended(&mut packet, long, &[0x5a; 600]).unwrap();
+        add_extended_vendor_specific(&mut packet, EXTENDED_ATTRIBUTE_6_TYPE, vendor, &[1; 300])
+            .unwrap();
+        assert!(add_extended(&mut packet, short, &[0; 253]).is_err());
+        assert!(add_extended(&mut packet, ExtendedType::new(26, 1), b"").is_err());
+
+        // 600 bytes are 251 + 251 + 98, and the More flag is set except the last fragment
+        let fragments = packet.lookup_all(EXTENDED_ATTRIBUTE_5_TYPE);
+        assert_eq!(fragments.len(), 3);
+        assert_eq!(fragments[0].get_value()[..2], [2, 0x80]);
+        assert_eq!(fragments[2].get_value().len(), 2 + 98);
+        assert_eq!(fragments[2].get_value()[1], 0);
+        // the Vendor-Id and the Evs-Type are in each fragment
+        let fragments = packet.lookup_all(EXTENDED_ATTRIBUTE_6_TYPE);
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(fragments[1].get_value()[..7], [26, 0, 0, 0, 0, 9, 3]);
+
+        let decoded = Packet::decode(&packet.encode().unwrap(), b"secret").unwrap();
+        assert_eq!(lookup_extended(&decoded, short).unwrap().unwrap(), b"abc");
+        assert_eq!(
+            lookup_extended(&decoded, long).unwrap().unwrap(),
+            vec![0x5a; 600]
+        );
+        assert_eq!(
+            lookup_extended_vendor_specific(&decoded, EXTENDED_ATTRIBUTE_6_TYPE, vendor)
+                .unwrap()
+                .unwrap(),
+            vec![1; 300]
+        );
+        let attributes = decode_extended_attributes(&decoded).unwrap();
+        assert_eq!(attributes.len(), 3);
+        assert_eq!(attributes[2].get_type().to_string(), "246.26");
+        assert_eq!(attributes[2].get_vendor(), Some(vendor));
+
+        delete_extended(&mut packet, long);
+        assert!(lookup_extended(&packet, long).is_none());
+        assert!(lookup_extended(&packet, short).is_some());
+    }
+
+    #[test]
+    fn test_missing_fragment() {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        packet.add(AVP::from_bytes(EXTENDED_ATTRIBUTE_5_TYPE, &[1, 0x80, 0xff]));
+        rfc2865::add_user_name(&mut packet, "alice");
+        assert!(decode_extended_attributes(&packet).is_err());
+
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        packet.add(AVP::from_bytes(EXTENDED_ATTRIBUTE_5_TYPE, &[1, 0x80, 0xff]));
+        assert!(
+            lookup_extended(&packet, ExtendedType::new(EXTENDED_ATTRIBUTE_5_TYPE, 1))
+                .unwrap()
+                .is_err()
+        );
+    }
+}
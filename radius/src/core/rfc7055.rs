@@ -19,7 +19,11 @@
 //! ATTRIBUTE    GSS-Acceptor-Realm-Name            167    string
 //! ```
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const GSS_ACCEPTOR_SERVICE_NAME_TYPE: AVPType = 164;
@@ -125,3 +129,29 @@ pub fn lookup_all_gss_acceptor_realm_name(packet: &Packet) -> Result<Vec<String>
     }
     Ok(vec)
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new(
+        "GSS-Acceptor-Service-Name",
+        GSS_ACCEPTOR_SERVICE_NAME_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "GSS-Acceptor-Host-Name",
+        GSS_ACCEPTOR_HOST_NAME_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "GSS-Acceptor-Service-Specifics",
+        GSS_ACCEPTOR_SERVICE_SPECIFICS_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "GSS-Acceptor-Realm-Name",
+        GSS_ACCEPTOR_REALM_NAME_TYPE,
+        "string",
+    ),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
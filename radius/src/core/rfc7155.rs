@@ -19,7 +19,11 @@
 //! ATTRIBUTE    Originating-Line-Info            94    octets[2]
 //! ```
 
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+
 use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
 use crate::core::packet::Packet;
 
 pub const ORIGINATING_LINE_INFO_TYPE: AVPType = 94;
@@ -54,3 +58,12 @@ pub fn lookup_all_originating_line_info(packet: &Packet) -> Vec<Vec<u8>> {
     }
     vec
 }
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[AttributeDefinition::new(
+    "Originating-Line-Info",
+    ORIGINATING_LINE_INFO_TYPE,
+    "octets",
+)];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
@@ -0,0 +1,645 @@
+//! Utility for rfc7499 packet, i.e. the fragmentation of the RADIUS packets that exceed 4096 bytes.
+//!
+//! Frag-Status (`241.1`) and Proxy-State-Length (`241.2`) are the attributes of the extended space (see `rfc6929`), so
+//! this module is written by hand as well. Beside their accessors, this has the sans-io state machines of the chunking
+//! of RFC 7499 section 4:
+//!
+//! - `RequestFragmenter` splits a request into the chunks; each chunk but the last has Frag-Status of
+//!   More-Data-Pending, and the server asks for the next one by an Access-Challenge of More-Data-Request, whose State
+//!   the next chunk echoes.
+//! - `RequestReassembler` is the server side of that: it answers the chunks and returns the reassembled request.
+//! - `ResponseReassembler` is the client side of the fragmented responses: the server sends the chunks by the
+//!   Access-Challenges of More-Data-Pending, and the client asks for the next one by an Access-Request of
+//!   More-Data-Request.
+//!
+//! `radius::client::Client::send_fragmented_packet()` drives the client side of both. An attribute is never split,
+//! and the attributes that are encrypted by the Request Authenticator (e.g. User-Password) are put in the last chunk,
+//! which has the authenticator of the original request.
+//!
+//! ```
+//! use radius::core::code::Code;
+//! use radius::core::packet::Packet;
+//! use radius::core::rfc2865;
+//! use radius::core::rfc7499::{ReassemblyStep, RequestFragmenter, RequestReassembler};
+//!
+//! let mut request = Packet::new(Code::AccessRequest, b"secret");
+//! rfc2865::add_user_name(&mut request, "alice");
+//! for _ in 0..30 {
+//!     rfc2865::add_class(&mut request, &[0; 200]);
+//! }
+//!
+//! let mut fragmenter = RequestFragmenter::new(&request, 4096).unwrap();
+//! let mut reassembler = RequestReassembler::new();
+//! let mut state = None;
+//! let reassembled = loop {
+//!     let chunk = fragmenter.next_chunk(state.as_deref()).unwrap();
+//!     match reassembler.on_request(&chunk).unwrap() {
+//!         ReassemblyStep::Challenge(challenge) => state = rfc2865::lookup_state(&challenge),
+//!         ReassemblyStep::Complete(reassembled) => break reassembled,
+//!     }
+//! };
+//! assert_eq!(rfc2865::lookup_all_class(&reassembled).len(), 30);
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use rand::RngCore;
+use thiserror::Error;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::code::Code;
+use crate::core::packet::{Packet, RADIUS_PACKET_HEADER_LENGTH};
+use crate::core::random;
+use crate::core::rfc2865;
+use crate::core::rfc2869;
+use crate::core::rfc6929::{self, ExtendedType, EXTENDED_ATTRIBUTE_1_TYPE};
+use crate::error::ErrorKind;
+
+pub const FRAG_STATUS_TYPE: ExtendedType = ExtendedType::new(EXTENDED_ATTRIBUTE_1_TYPE, 1);
+pub const PROXY_STATE_LENGTH_TYPE: ExtendedType = ExtendedType::new(EXTENDED_ATTRIBUTE_1_TYPE, 2);
+
+pub type FragStatus = u32;
+pub const FRAG_STATUS_RESERVED: FragStatus = 0;
+pub const FRAG_STATUS_FRAGMENTATION_SUPPORTED: FragStatus = 1;
+pub const FRAG_STATUS_MORE_DATA_PENDING: FragStatus = 2;
+pub const FRAG_STATUS_MORE_DATA_REQUEST: FragStatus = 3;
+
+/// The length of the State that `RequestReassembler` gives to the chunks.
+const STATE_LENGTH: usize = 16;
+
+/// The encoded length of Frag-Status, i.e. the header, the Extended-Type and the integer.
+const FRAG_STATUS_LENGTH: usize = 2 + 1 + 4;
+
+/// The encoded length of Message-Authenticator.
+const MESSAGE_AUTHENTICATOR_LENGTH: usize = 2 + 16;
+
+/// The maximum encoded length of an attribute.
+const MAX_ATTRIBUTE_LENGTH: usize = 255;
+
+const DEFAULT_MAX_REASSEMBLED_LENGTH: usize = 65535;
+const DEFAULT_MAX_PENDING: usize = 1024;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum FragmentationError {
+    /// An error that is raised when the chunks are too short to carry an attribute besides the repeated ones.
+    #[error("the chunks of {0} bytes are too short")]
+    InvalidChunkLengthError(usize),
+    /// An error that is raised when a chunk of More-Data-Pending or More-Data-Request doesn't have State.
+    #[error("the chunk doesn't have State")]
+    MissingStateError(),
+    /// An error that is raised when the reassembled packet exceeds the limit (the length and the limit).
+    #[error("the reassembled packet of {0} bytes exceeds the limit of {1} bytes")]
+    TooLongError(usize, usize),
+    /// An error that is raised when Frag-Status is malformed.
+    #[error("failed to decode Frag-Status; {0}")]
+    DecodingError(#[from] AVPError),
+}
+
+impl FragmentationError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            FragmentationError::InvalidChunkLengthError(_) => ErrorKind::Configuration,
+            FragmentationError::MissingStateError()
+            | FragmentationError::TooLongError(_, _)
+            | FragmentationError::DecodingError(_) => ErrorKind::Malformed,
+        }
+    }
+}
+
+fn lookup_extended_u32(packet: &Packet, typ: ExtendedType) -> Option<Result<u32, AVPError>> {
+    let value = match rfc6929::lookup_extended(packet, typ)? {
+        Ok(value) => value,
+        Err(e) => return Some(Err(e)),
+    };
+    Some(match <[u8; 4]>::try_from(value.as_slice()) {
+        Ok(bytes) => Ok(u32::from_be_bytes(bytes)),
+        Err(_) => Err(AVPError::InvalidAttributeLengthError(
+            "4 bytes".to_owned(),
+            value.len(),
+        )),
+    })
+}
+
+/// Delete all of Frag-Status values from a packet.
+pub fn delete_frag_status(packet: &mut Packet) {
+    rfc6929::delete_extended(packet, FRAG_STATUS_TYPE);
+}
+/// Add Frag-Status value to a packet.
+pub fn add_frag_status(packet: &mut Packet, value: FragStatus) {
+    // an integer always fits an attribute
+    let _ = rfc6929::add_extended(packet, FRAG_STATUS_TYPE, &value.to_be_bytes());
+}
+/// Lookup a Frag-Status value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with Frag-Status, it returns `None`.
+pub fn lookup_frag_status(packet: &Packet) -> Option<Result<FragStatus, AVPError>> {
+    lookup_extended_u32(packet, FRAG_STATUS_TYPE)
+}
+
+/// Delete all of Proxy-State-Length values from a packet.
+pub fn delete_proxy_state_length(packet: &mut Packet) {
+    rfc6929::delete_extended(packet, PROXY_STATE_LENGTH_TYPE);
+}
+/// Add Proxy-State-Length value to a packet.
+pub fn add_proxy_state_length(packet: &mut Packet, value: u32) {
+    // an integer always fits an attribute
+    let _ = rfc6929::add_extended(packet, PROXY_STATE_LENGTH_TYPE, &value.to_be_bytes());
+}
+/// Lookup a Proxy-State-Length value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with Proxy-State-Length, it returns `None`.
+pub fn lookup_proxy_state_length(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    lookup_extended_u32(packet, PROXY_STATE_LENGTH_TYPE)
+}
+
+fn is_frag_status(avp: &AVP) -> bool {
+    avp.get_type() == FRAG_STATUS_TYPE.get_type()
+        && avp.get_value().first() == Some(&FRAG_STATUS_TYPE.get_extended_type())
+}
+
+/// Returns whether the attribute is encrypted by (or computed of) the Request Authenticator, so it has to be in the
+/// chunk that has the authenticator of the original request.
+fn is_authenticator_bound(typ: AVPType) -> bool {
+    #[cfg(feature = "rfc2868")]
+    if typ == crate::core::rfc2868::TUNNEL_PASSWORD_TYPE {
+        return true;
+    }
+    matches!(
+        typ,
+        rfc2865::USER_PASSWORD_TYPE | rfc2865::CHAP_PASSWORD_TYPE | rfc2865::CHAP_CHALLENGE_TYPE
+    )
+}
+
+fn encoded_length(avps: &[AVP]) -> usize {
+    avps.iter().map(|avp| 2 + avp.get_value().len()).sum()
+}
+
+/// Returns the attributes of a chunk without the ones of the chunking: Frag-Status, Message-Authenticator, and (if
+/// requested) the first State, i.e. the one of the chunking, and the first User-Name, i.e. the repeated one.
+fn chunk_data(packet: &Packet, skip_state: bool, skip_user_name: bool) -> Vec<AVP> {
+    let mut state_skipped = !skip_state;
+    let mut user_name_skipped = !skip_user_name;
+    packet
+        .get_attributes()
+        .iter()
+        .filter(|avp| {
+            if is_frag_status(avp) || avp.get_type() == rfc2869::MESSAGE_AUTHENTICATOR_TYPE {
+                return false;
+            }
+            if !state_skipped && avp.get_type() == rfc2865::STATE_TYPE {
+                state_skipped = true;
+                return false;
+            }
+            if !user_name_skipped && avp.get_type() == rfc2865::USER_NAME_TYPE {
+                user_name_skipped = true;
+                return false;
+            }
+            true
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns the packet of the attributes, with the header of the base packet.
+fn with_attributes(base: &Packet, attributes: Vec<AVP>) -> Packet {
+    let mut packet = base.clone();
+    packet.retain_attributes(|_| false);
+    packet.extend(attributes);
+    packet
+}
+
+/// The client side of the fragmentation of a request (see the module document).
+#[derive(Debug, Clone)]
+pub struct RequestFragmenter {
+    request: Packet,
+    user_name: Option<AVP>,
+    message_authenticator: bool,
+    chunks: VecDeque<Vec<AVP>>,
+}
+
+impl RequestFragmenter {
+    /// Split a request into the chunks of at most `max_packet_length` bytes (usually `4096`).
+    ///
+    /// A request that fits is a chunk as it is.
+    pub fn new(request: &Packet, max_packet_length: usize) -> Result<Self, FragmentationError> {
+        let user_name = request.lookup(rfc2865::USER_NAME_TYPE).cloned();
+        let message_authenticator = rfc2869::lookup_message_authenticator(request).is_some();
+        let mut fragmenter = RequestFragmenter {
+            request: with_attributes(request, Vec::new()),
+            user_name,
+            message_authenticator,
+            chunks: VecDeque::new(),
+        };
+
+        if RADIUS_PACKET_HEADER_LENGTH + encoded_length(request.get_attributes())
+            <= max_packet_length
+        {
+            fragmenter
+                .chunks
+                .push_back(request.get_attributes().to_vec());
+            fragmenter.user_name = None;
+            fragmenter.message_authenticator = false;
+            return Ok(fragmenter);
+        }
+
+        let reserved = RADIUS_PACKET_HEADER_LENGTH
+            + fragmenter
+                .user_name
+                .as_ref()
+                .map_or(0, |avp| 2 + avp.get_value().len())
+            + 2
+            + STATE_LENGTH
+            + FRAG_STATUS_LENGTH
+            + if message_authenticator {
+                MESSAGE_AUTHENTICATOR_LENGTH
+            } else {
+                0
+            };
+        let budget = max_packet_length.saturating_sub(reserved);
+        if budget < MAX_ATTRIBUTE_LENGTH {
+            return Err(FragmentationError::InvalidChunkLengthError(
+                max_packet_length,
+            ));
+        }
+
+        let mut data = chunk_data(request, false, false);
+        if fragmenter.user_name.is_some() {
+            // User-Name is in every chunk
+            let index = data
+                .iter()
+                .position(|avp| avp.get_type() == rfc2865::USER_NAME_TYPE)
+                .unwrap();
+            data.remove(index);
+        }
+        let (bound, data): (Vec<AVP>, Vec<AVP>) = data
+            .into_iter()
+            .partition(|avp| is_authenticator_bound(avp.get_type()));
+
+        let mut chunk = Vec::new();
+        for avp in data {
+            if encoded_length(&chunk) + 2 + avp.get_value().len() > budget {
+                fragmenter.chunks.push_back(core::mem::take(&mut chunk));
+            }
+            chunk.push(avp);
+        }
+        if encoded_length(&chunk) + encoded_length(&bound) > budget {
+            fragmenter.chunks.push_back(core::mem::take(&mut chunk));
+        }
+        chunk.extend(bound);
+        fragmenter.chunks.push_back(chunk);
+        Ok(fragmenter)
+    }
+
+    /// Returns the number of the chunks that haven't been sent yet.
+    pub fn get_remaining_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the next chunk, with the State of the Access-Challenge of More-Data-Request that has asked for it
+    /// (`None` for the first chunk); `None` if all of the chunks have been sent.
+    ///
+    /// The last chunk has the identifier and the authenticator of the request; the others are new packets.
+    pub fn next_chunk(&mut self, state: Option<&[u8]>) -> Option<Packet> {
+        let data = self.chunks.pop_front()?;
+        let last = self.chunks.is_empty();
+        let mut chunk = match last {
+            true => with_attributes(&self.request, Vec::new()),
+            false => Packet::new(self.request.get_code(), self.request.get_secret()),
+        };
+        if let Some(user_name) = &self.user_name {
+            chunk.add(user_name.clone());
+        }
+        if let Some(state) = state {
+            rfc2865::add_state(&mut chunk, state);
+        }
+        if !last {
+            add_frag_status(&mut chunk, FRAG_STATUS_MORE_DATA_PENDING);
+        }
+        chunk.extend(data);
+        if self.message_authenticator {
+            chunk.add_message_authenticator();
+        }
+        Some(chunk)
+    }
+}
+
+/// The step of `RequestReassembler::on_request()`.
+#[derive(Debug, Clone)]
+pub enum ReassemblyStep {
+    /// The Access-Challenge of More-Data-Request to answer the chunk by.
+    Challenge(Packet),
+    /// The reassembled request (or the request that isn't fragmented).
+    Complete(Packet),
+}
+
+/// The server side of the fragmentation of the requests (see the module document).
+///
+/// This keeps the attributes of the chunks by the State that it has given to the challenges; the oldest ones are
+/// dropped beyond `set_max_pending()`.
+#[derive(Debug, Clone)]
+pub struct RequestReassembler {
+    pending: VecDeque<(Vec<u8>, Vec<AVP>)>,
+    max_reassembled_length: usize,
+    max_pending: usize,
+}
+
+impl RequestReassembler {
+    pub fn new() -> Self {
+        RequestReassembler {
+            pending: VecDeque::new(),
+            max_reassembled_length: DEFAULT_MAX_REASSEMBLED_LENGTH,
+            max_pending: DEFAULT_MAX_PENDING,
+        }
+    }
+
+    /// Set the maximum length of the attributes of a reassembled request (default: `65535`).
+    pub fn set_max_reassembled_length(&mut self, max_reassembled_length: usize) {
+        self.max_reassembled_length = max_reassembled_length;
+    }
+
+    /// Set the maximum number of the requests that are being reassembled (default: `1024`).
+    pub fn set_max_pending(&mut self, max_pending: usize) {
+        self.max_pending = max_pending.max(1);
+    }
+
+    /// Returns the number of the requests that are being reassembled.
+    pub fn get_pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Handle a request: a chunk of More-Data-Pending is answered by a challenge, and the last chunk completes the
+    /// reassembled request, which has the header of the last chunk.
+    pub fn on_request(&mut self, request: &Packet) -> Result<ReassemblyStep, FragmentationError> {
+        let more_data_pending =
+            lookup_frag_status(request).transpose()? == Some(FRAG_STATUS_MORE_DATA_PENDING);
+        let pending = rfc2865::lookup_state(request).and_then(|state| {
+            self.pending
+                .iter()
+                .position(|(pending_state, _)| *pending_state == state)
+        });
+        let mut attributes = match pending {
+            Some(index) => self.pending.remove(index).unwrap().1,
+            None if more_data_pending => Vec::new(),
+            None => return Ok(ReassemblyStep::Complete(request.clone())),
+        };
+        attributes.extend(chunk_data(request, pending.is_some(), pending.is_some()));
+        let length = encoded_length(&attributes);
+        if length > self.max_reassembled_length {
+            return Err(FragmentationError::TooLongError(
+                length,
+                self.max_reassembled_length,
+            ));
+        }
+
+        if !more_data_pending {
+            return Ok(ReassemblyStep::Complete(with_attributes(
+                request, attributes,
+            )));
+        }
+
+        let mut state = [0; STATE_LENGTH];
+        random::rng().fill_bytes(&mut state);
+        if self.pending.len() >= self.max_pending {
+            self.pending.pop_front();
+        }
+        self.pending.push_back((state.to_vec(), attributes));
+        let mut challenge = request.make_response_packet(Code::AccessChallenge);
+        rfc2865::add_state(&mut challenge, &state);
+        add_frag_status(&mut challenge, FRAG_STATUS_MORE_DATA_REQUEST);
+        Ok(ReassemblyStep::Challenge(challenge))
+    }
+}
+
+impl Default for RequestReassembler {
+    fn default() -> Self {
+        RequestReassembler::new()
+    }
+}
+
+/// The step of `ResponseReassembler::on_response()`.
+#[derive(Debug, Clone)]
+pub enum ResponseStep {
+    /// The Access-Request of More-Data-Request to ask for the next chunk by.
+    Request(Packet),
+    /// The reassembled response (or the response that isn't fragmented).
+    Complete(Packet),
+}
+
+/// The client side of the fragmentation of the responses (see the module document).
+#[derive(Debug, Clone)]
+pub struct ResponseReassembler {
+    request: Packet,
+    attributes: Vec<AVP>,
+    max_reassembled_length: usize,
+}
+
+impl ResponseReassembler {
+    /// A constructor for the responses to the request.
+    pub fn new(request: &Packet) -> Self {
+        ResponseReassembler {
+            request: request.clone(),
+            attributes: Vec::new(),
+            max_reassembled_length: DEFAULT_MAX_REASSEMBLED_LENGTH,
+        }
+    }
+
+    /// Set the maximum length of the attributes of a reassembled response (default: `65535`).
+    pub fn set_max_reassembled_length(&mut self, max_reassembled_length: usize) {
+        self.max_reassembled_length = max_reassembled_length;
+    }
+
+    /// Handle a response: an Access-Challenge of More-Data-Pending is answered by a request for the next chunk, and
+    /// the other response completes the reassembled response, which has the header of that.
+    pub fn on_response(&mut self, response: &Packet) -> Result<ResponseStep, FragmentationError> {
+        let more_data_pending = response.get_code() == Code::AccessChallenge
+            && lookup_frag_status(response).transpose()? == Some(FRAG_STATUS_MORE_DATA_PENDING);
+        if !more_data_pending {
+            if self.attributes.is_empty() {
+                return Ok(ResponseStep::Complete(response.clone()));
+            }
+            let mut attributes = core::mem::take(&mut self.attributes);
+            attributes.extend(chunk_data(response, false, false));
+            return Ok(ResponseStep::Complete(with_attributes(
+                response, attributes,
+            )));
+        }
+
+        let state =
+            rfc2865::lookup_state(response).ok_or(FragmentationError::MissingStateError())?;
+        self.attributes.extend(chunk_data(response, true, false));
+        let length = encoded_length(&self.attributes);
+        if length > self.max_reassembled_length {
+            return Err(FragmentationError::TooLongError(
+                length,
+                self.max_reassembled_length,
+            ));
+        }
+
+        let mut request = Packet::new(self.request.get_code(), self.request.get_secret());
+        if let Some(user_name) = self.request.lookup(rfc2865::USER_NAME_TYPE) {
+            request.add(user_name.clone());
+        }
+        rfc2865::add_state(&mut request, &state);
+        add_frag_status(&mut request, FRAG_STATUS_MORE_DATA_REQUEST);
+        if rfc2869::lookup_message_authenticator(&self.request).is_some() {
+            request.add_message_authenticator();
+        }
+        Ok(ResponseStep::Request(request))
+    }
+}
+
+/// Returns the State of an Access-Challenge of More-Data-Request, i.e. the server asks for the next chunk of a
+/// request by that; `None` for the other responses.
+pub fn lookup_more_data_request(response: &Packet) -> Option<Vec<u8>> {
+    match (response.get_code(), lookup_frag_status(response)) {
+        (Code::AccessChallenge, Some(Ok(FRAG_STATUS_MORE_DATA_REQUEST))) => {
+            rfc2865::lookup_state(response)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::core::rfc2869;
+    use crate::core::rfc7499::{
+        add_frag_status, lookup_frag_status, lookup_more_data_request, FragmentationError,
+        ReassemblyStep, RequestFragmenter, RequestReassembler, ResponseReassembler, ResponseStep,
+        FRAG_STATUS_MORE_DATA_PENDING, FRAG_STATUS_MORE_DATA_REQUEST,
+    };
+
+    fn large_request() -> Packet {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        for i in 0..50 {
+            rfc2865::add_class(&mut request, &[i; 200]);
+        }
+        rfc2865::add_user_password(&mut request, b"p@ssw0rd").unwrap();
+        request.add_message_authenticator();
+        request
+    }
+
+    #[test]
+    fn test_request_fragmentation() {
+        let request = large_request();
+        let mut fragmenter = RequestFragmenter::new(&request, 4096).unwrap();
+        assert_eq!(fragmenter.get_remaining_chunks(), 3);
+        let mut reassembler = RequestReassembler::new();
+
+        let mut state = None;
+        let mut chunks = 0;
+        let reassembled = loop {
+            let chunk = fragmenter.next_chunk(state.as_deref()).unwrap();
+            chunks += 1;
+            let encoded = chunk.encode().unwrap();
+            assert!(encoded.len() <= 4096);
+            let chunk = Packet::decode(&encoded, b"secret").unwrap();
+            assert_eq!(rfc2865::lookup_user_name(&chunk).unwrap().unwrap(), "alice");
+            assert!(rfc2869::lookup_message_authenticator(&chunk).is_some());
+            match reassembler.on_request(&chunk).unwrap() {
+                ReassemblyStep::Challenge(challenge) => {
+                    assert_eq!(
+                        lookup_frag_status(&chunk),
+                        Some(Ok(FRAG_STATUS_MORE_DATA_PENDING))
+                    );
+                    state = lookup_more_data_request(&challenge);
+                    assert!(state.is_some());
+                }
+                ReassemblyStep::Complete(reassembled) => break reassembled,
+            }
+        };
+        assert_eq!(chunks, 3);
+        assert!(fragmenter.next_chunk(None).is_none());
+        assert_eq!(reassembler.get_pending(), 0);
+
+        assert_eq!(reassembled.get_identifier(), request.get_identifier());
+        let classes: Vec<Vec<u8>> = rfc2865::lookup_all_class(&reassembled);
+        assert_eq!(classes, rfc2865::lookup_all_class(&request));
+        assert_eq!(
+            rfc2865::lookup_user_password(&reassembled)
+                .unwrap()
+                .unwrap(),
+            b"p@ssw0rd"
+        );
+        assert_eq!(
+            rfc2865::lookup_all_user_name(&reassembled).unwrap(),
+            vec!["alice"]
+        );
+        assert_eq!(lookup_frag_status(&reassembled), None);
+
+        // a request that fits is sent as it is
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        let mut fragmenter = RequestFragmenter::new(&request, 4096).unwrap();
+        let chunk = fragmenter.next_chunk(None).unwrap();
+        assert_eq!(chunk.get_attributes(), request.get_attributes());
+        assert!(matches!(
+            reassembler.on_request(&chunk).unwrap(),
+            ReassemblyStep::Complete(_)
+        ));
+
+        assert_eq!(
+            RequestFragmenter::new(&large_request(), 300).err(),
+            Some(FragmentationError::InvalidChunkLengthError(300))
+        );
+        let mut reassembler = RequestReassembler::new();
+        reassembler.set_max_reassembled_length(1000);
+        let mut fragmenter = RequestFragmenter::new(&large_request(), 4096).unwrap();
+        assert!(matches!(
+            reassembler.on_request(&fragmenter.next_chunk(None).unwrap()),
+            Err(FragmentationError::TooLongError(_, 1000))
+        ));
+    }
+
+    #[test]
+    fn test_response_reassembly() {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        let mut reassembler = ResponseReassembler::new(&request);
+
+        // the server sends the first chunk by an Access-Challenge of More-Data-Pending
+        let mut chunk = request.make_response_packet(Code::AccessChallenge);
+        rfc2865::add_state(&mut chunk, b"chunk-1");
+        add_frag_status(&mut chunk, FRAG_STATUS_MORE_DATA_PENDING);
+        rfc2865::add_reply_message(&mut chunk, "first");
+        let next = match reassembler.on_response(&chunk).unwrap() {
+            ResponseStep::Request(next) => next,
+            step => panic!("unexpected step: {step:?}"),
+        };
+        assert_eq!(next.get_code(), Code::AccessRequest);
+        assert_eq!(rfc2865::lookup_state(&next).unwrap(), b"chunk-1");
+        assert_eq!(
+            lookup_frag_status(&next),
+            Some(Ok(FRAG_STATUS_MORE_DATA_REQUEST))
+        );
+        assert_eq!(rfc2865::lookup_user_name(&next).unwrap().unwrap(), "alice");
+
+        let mut last = next.make_response_packet(Code::AccessAccept);
+        rfc2865::add_reply_message(&mut last, "second");
+        let response = match reassembler.on_response(&last).unwrap() {
+            ResponseStep::Complete(response) => response,
+            step => panic!("unexpected step: {step:?}"),
+        };
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(
+            rfc2865::lookup_all_reply_message(&response).unwrap(),
+            vec!["first", "second"]
+        );
+        assert_eq!(rfc2865::lookup_state(&response), None);
+
+        let mut chunk = request.make_response_packet(Code::AccessChallenge);
+        add_frag_status(&mut chunk, FRAG_STATUS_MORE_DATA_PENDING);
+        assert_eq!(
+            reassembler.on_response(&chunk).err(),
+            Some(FragmentationError::MissingStateError())
+        );
+    }
+}
@@ -0,0 +1,539 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use thiserror::Error;
+
+use crate::core::accounting;
+use crate::core::code::Code;
+use crate::core::message_authenticator;
+use crate::core::packet::Packet;
+use crate::core::random;
+use crate::core::rfc2869;
+use crate::error::ErrorKind;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ClientTransactionError {
+    /// An error that is raised when encoding the request has been failed.
+    #[error("failed to encode a RADIUS request; {0}")]
+    EncodingError(String),
+    /// An error that is raised when decoding the response has been failed.
+    #[error("failed to decode a RADIUS response packet; {0}")]
+    DecodingError(String),
+    /// An error that is raised when the datagram is not the response of the request (e.g. a forged one), or the
+    /// response lacks the required Message-Authenticator.
+    #[error("unauthentic RADIUS response; {0}")]
+    UnauthenticResponseError(String),
+}
+
+impl ClientTransactionError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            ClientTransactionError::EncodingError(_) => ErrorKind::Encoding,
+            ClientTransactionError::DecodingError(_) => ErrorKind::Malformed,
+            ClientTransactionError::UnauthenticResponseError(_) => ErrorKind::Authenticity,
+        }
+    }
+}
+
+/// The state of a request from a client.
+///
+/// The adapter sends `get_request_datagram()` first, and then it calls `on_timeout()` when the response doesn't arrive
+/// in time, or `on_response()` when a datagram arrives.
+///
+/// ## Example
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use chrono::Utc;
+/// use radius::core::code::Code;
+/// use radius::core::packet::Packet;
+/// use radius::core::sansio::ClientTransaction;
+///
+/// let request = Packet::new(Code::AccountingRequest, b"secret");
+/// let mut transaction = ClientTransaction::new(&request, true, 1, &Utc::now()).unwrap();
+/// let datagram = transaction.get_request_datagram().to_vec(); // send this by any transport
+///
+/// // no response in time: retransmit the updated datagram
+/// assert!(transaction.on_timeout(Duration::from_secs(3)).unwrap());
+/// // the maximum number of retransmissions has been reached: give up
+/// assert!(!transaction.on_timeout(Duration::from_secs(6)).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientTransaction {
+    request_packet: Packet,
+    request_datagram: Vec<u8>,
+    initial_acct_delay_time: Option<u32>,
+    initial_event_timestamp: Option<DateTime<Utc>>,
+    retransmissions: u32,
+    max_retransmissions: u32,
+    require_message_authenticator: bool,
+}
+
+impl ClientTransaction {
+    /// Prepare a request.
+    ///
+    /// If `add_event_timestamp` is true, Event-Timestamp of `now` is added to the Accounting-Request,
+    /// CoA-Request and Disconnect-Request that don't have that yet (see `on_timeout()` for the retransmissions).
+    pub fn new(
+        request_packet: &Packet,
+        add_event_timestamp: bool,
+        max_retransmissions: u32,
+        now: &DateTime<Utc>,
+    ) -> Result<Self, ClientTransactionError> {
+        let mut request_packet = request_packet.clone();
+        let mut initial_event_timestamp = None;
+        if add_event_timestamp
+            && matches!(
+                request_packet.get_code(),
+                Code::AccountingRequest | Code::CoARequest | Code::DisconnectRequest
+            )
+            && request_packet
+                .lookup(rfc2869::EVENT_TIMESTAMP_TYPE)
+                .is_none()
+        {
+            rfc2869::add_event_timestamp(&mut request_packet, now);
+            if request_packet.get_code() != Code::AccountingRequest {
+                initial_event_timestamp = Some(*now);
+            }
+        }
+        let initial_acct_delay_time = match request_packet.get_code() {
+            Code::AccountingRequest => {
+                match accounting::lookup_acct_delay_time_or_zero(&request_packet) {
+                    Ok(delay_time) => Some(delay_time),
+                    Err(e) => return Err(ClientTransactionError::EncodingError(format!("{e}"))),
+                }
+            }
+            _ => None,
+        };
+        let mut request_datagram = Vec::new();
+        Self::encode(&request_packet, &mut request_datagram)?;
+
+        Ok(ClientTransaction {
+            request_packet,
+            request_datagram,
+            initial_acct_delay_time,
+            initial_event_timestamp,
+            retransmissions: 0,
+            max_retransmissions,
+            require_message_authenticator: false,
+        })
+    }
+
+    /// Require Message-Authenticator against the forgery of the responses (CVE-2024-3596, a.k.a. Blast-RADIUS)
+    /// (default: `false`).
+    ///
+    /// If this is `true`, the request has Message-Authenticator as the first attribute (i.e. the datagram is
+    /// recomputed), and Access-Accept, Access-Reject and Access-Challenge without Message-Authenticator are errors.
+    /// The Message-Authenticator of any response is verified regardless of this.
+    pub fn set_require_message_authenticator(
+        &mut self,
+        require_message_authenticator: bool,
+    ) -> Result<(), ClientTransactionError> {
+        self.require_message_authenticator = require_message_authenticator;
+        if require_message_authenticator {
+            self.request_packet.add_message_authenticator();
+            Self::encode(&self.request_packet, &mut self.request_datagram)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the request packet (i.e. including the attributes that are added by this transaction).
+    pub fn get_request_packet(&self) -> &Packet {
+        &self.request_packet
+    }
+
+    /// Returns the datagram to send.
+    pub fn get_request_datagram(&self) -> &[u8] {
+        &self.request_datagram
+    }
+
+    /// Returns the number of the retransmissions so far.
+    pub fn get_retransmissions(&self) -> u32 {
+        self.retransmissions
+    }
+
+    /// Handle the timeout of the response, and return whether the request has to be retransmitted.
+    ///
+    /// `elapsed` is the time since the first transmission. When an `Accounting-Request` is retransmitted,
+    /// its `Acct-Delay-Time` is updated by that (the Event-Timestamp stays the time of the event), and the
+    /// Event-Timestamp that this transaction has added to a CoA-Request or a Disconnect-Request is refreshed by that
+    /// (RFC 2869, section 5.3); then the datagram (i.e. the Request Authenticator) is recomputed.
+    pub fn on_timeout(&mut self, elapsed: Duration) -> Result<bool, ClientTransactionError> {
+        if self.retransmissions >= self.max_retransmissions {
+            return Ok(false);
+        }
+        self.retransmissions += 1;
+
+        let mut updated = false;
+        if let Some(initial_acct_delay_time) = self.initial_acct_delay_time {
+            accounting::update_acct_delay_time(
+                &mut self.request_packet,
+                initial_acct_delay_time,
+                elapsed,
+            );
+            updated = true;
+        }
+        if let Some(initial_event_timestamp) = self.initial_event_timestamp {
+            let elapsed = chrono::Duration::from_std(elapsed).unwrap_or(chrono::Duration::zero());
+            rfc2869::delete_event_timestamp(&mut self.request_packet);
+            rfc2869::add_event_timestamp(
+                &mut self.request_packet,
+                &(initial_event_timestamp + elapsed),
+            );
+            updated = true;
+        }
+        if updated {
+            Self::encode(&self.request_packet, &mut self.request_datagram)?;
+        }
+        Ok(true)
+    }
+
+    /// Rewrite the request packet of the next retransmission, e.g. by a hook of the application, and recompute the
+    /// datagram (i.e. the Request Authenticator and Message-Authenticator).
+    ///
+    /// The identifier is kept as it is, since a retransmission has to have the same identifier as the former datagram
+    /// (RFC 2865, section 3; RFC 5080, section 2.2.1).
+    pub fn update_request_packet(
+        &mut self,
+        update: impl FnOnce(&mut Packet),
+    ) -> Result<(), ClientTransactionError> {
+        let identifier = self.request_packet.get_identifier();
+        update(&mut self.request_packet);
+        self.request_packet.set_identifier(identifier);
+        Self::encode(&self.request_packet, &mut self.request_datagram)
+    }
+
+    /// Returns whether a received datagram is the response of the request, i.e. it has the identifier of the request
+    /// and the Response Authenticator (and Message-Authenticator if that has) of the secret.
+    ///
+    /// The other datagrams (e.g. the forged ones, or the late responses of the former datagram) have to be discarded.
+    pub fn is_response(&self, datagram: &[u8]) -> bool {
+        Packet::decode(datagram, self.request_packet.get_secret())
+            .is_ok_and(|response| self.is_authentic(&response))
+    }
+
+    /// Verifies the decoded response by `Packet::verify_response_authenticator()` against the request datagram,
+    /// whose Request Authenticator may be computed by the encoding (e.g. of Accounting-Request).
+    fn is_authentic(&self, response: &Packet) -> bool {
+        match Packet::decode_unverified(&self.request_datagram) {
+            Ok(request) => {
+                response.get_identifier() == request.get_identifier()
+                    && response.verify_response_authenticator(&request)
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Decode a received datagram as the response.
+    ///
+    /// The datagram that is not the response (see `is_response()`), and the response that has a Message-Authenticator
+    /// of the wrong secret, are errors.
+    pub fn on_response(&self, datagram: &[u8]) -> Result<Packet, ClientTransactionError> {
+        let secret = self.request_packet.get_secret();
+        let response = Packet::decode(datagram, secret)
+            .map_err(|e| ClientTransactionError::DecodingError(format!("{e}")))?;
+        if !self.is_authentic(&response) {
+            return Err(ClientTransactionError::UnauthenticResponseError(
+                "not the response of the request (the identifier, the Response Authenticator or Message-Authenticator)"
+                    .into(),
+            ));
+        }
+        if self.require_message_authenticator
+            && matches!(
+                response.get_code(),
+                Code::AccessAccept | Code::AccessReject | Code::AccessChallenge
+            )
+            && message_authenticator::find_value_offset(datagram).is_none()
+        {
+            return Err(ClientTransactionError::UnauthenticResponseError(
+                "missing Message-Authenticator".into(),
+            ));
+        }
+        Ok(response)
+    }
+
+    /// Encode the packet into the datagram; the datagram of a retransmission reuses the allocation of the former one.
+    fn encode(packet: &Packet, datagram: &mut Vec<u8>) -> Result<(), ClientTransactionError> {
+        let encoding_error = |e| ClientTransactionError::EncodingError(format!("{e}"));
+        datagram.resize(packet.encoded_len().map_err(encoding_error)?, 0);
+        packet.encode_into(datagram).map_err(encoding_error)?;
+        Ok(())
+    }
+}
+
+/// An allocator of the identifiers of the outstanding requests to a server over the sockets of a client.
+///
+/// Each socket (i.e. each source port) has its own space of 256 identifiers; when all of them are outstanding, the
+/// allocation spills to the next socket, up to the maximum number of the sockets. An identifier stays outstanding
+/// until it is released, so a late response of a request that has been timed out doesn't go to another request.
+/// `client::multiplexed::MultiplexedClient` is the tokio client of the same scheme.
+#[derive(Debug, Clone)]
+pub struct IdentifierAllocator {
+    max_sockets: usize,
+    sockets: Vec<[u128; 2]>,
+    next_identifier: u8,
+    outstanding: usize,
+}
+
+impl IdentifierAllocator {
+    /// A constructor for an allocator over the sockets up to `max_sockets` (at least `1`).
+    pub fn new(max_sockets: usize) -> Self {
+        IdentifierAllocator {
+            max_sockets: max_sockets.max(1),
+            sockets: Vec::new(),
+            next_identifier: random::rng().next_u32() as u8,
+            outstanding: 0,
+        }
+    }
+
+    /// Allocate an identifier and return it with the index of the socket, or `None` if all of the identifiers of
+    /// the maximum number of the sockets are outstanding.
+    ///
+    /// The index of a new socket is the number of the sockets so far, i.e. the caller opens a socket for that.
+    pub fn allocate(&mut self) -> Option<(usize, u8)> {
+        let start = self.next_identifier;
+        let found = self.sockets.iter().enumerate().find_map(|(socket, bits)| {
+            (0..=255u8)
+                .map(|i| start.wrapping_add(i))
+                .find(|identifier| !is_set(bits, *identifier))
+                .map(|identifier| (socket, identifier))
+        });
+        let (socket, identifier) = match found {
+            Some(found) => found,
+            None if self.sockets.len() < self.max_sockets => {
+                self.sockets.push([0; 2]);
+                (self.sockets.len() - 1, start)
+            }
+            None => return None,
+        };
+        let bits = &mut self.sockets[socket];
+        bits[usize::from(identifier >> 7)] |= 1 << (identifier & 0x7f);
+        self.next_identifier = identifier.wrapping_add(1);
+        self.outstanding += 1;
+        Some((socket, identifier))
+    }
+
+    /// Release an identifier of a socket, e.g. when the response has been received or the request has been timed out.
+    pub fn release(&mut self, socket: usize, identifier: u8) {
+        if let Some(bits) = self.sockets.get_mut(socket) {
+            if is_set(bits, identifier) {
+                bits[usize::from(identifier >> 7)] &= !(1 << (identifier & 0x7f));
+                self.outstanding -= 1;
+            }
+        }
+    }
+
+    /// Returns whether an identifier of a socket is outstanding, e.g. to discard the responses of the others.
+    pub fn is_outstanding(&self, socket: usize, identifier: u8) -> bool {
+        self.sockets
+            .get(socket)
+            .is_some_and(|bits| is_set(bits, identifier))
+    }
+
+    /// Returns the number of the outstanding identifiers of all of the sockets.
+    pub fn get_outstanding(&self) -> usize {
+        self.outstanding
+    }
+
+    /// Returns the number of the sockets that the allocation has spilled to.
+    pub fn get_socket_count(&self) -> usize {
+        self.sockets.len()
+    }
+}
+
+fn is_set(bits: &[u128; 2], identifier: u8) -> bool {
+    bits[usize::from(identifier >> 7)] & (1 << (identifier & 0x7f)) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use chrono::Utc;
+
+    use crate::core::code::Code;
+    use crate::core::message_authenticator::verify_request;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::core::rfc2866;
+    use crate::core::rfc2869;
+    use crate::core::sansio::{ClientTransaction, ClientTransactionError, IdentifierAllocator};
+
+    #[test]
+    fn test_accounting_transaction() {
+        let mut request = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut request, rfc2866::ACCT_STATUS_TYPE_START);
+
+        let mut transaction = ClientTransaction::new(&request, true, 1, &Utc::now()).unwrap();
+        assert!(rfc2869::lookup_event_timestamp(transaction.get_request_packet()).is_some());
+        let first_datagram = transaction.get_request_datagram().to_vec();
+        assert!(Packet::is_authentic_request(&first_datagram, b"secret"));
+
+        assert!(transaction.on_timeout(Duration::from_secs(2)).unwrap());
+        assert_eq!(transaction.get_retransmissions(), 1);
+        let retransmitted = Packet::decode(transaction.get_request_datagram(), b"secret").unwrap();
+        assert_eq!(
+            rfc2866::lookup_acct_delay_time(&retransmitted)
+                .unwrap()
+                .unwrap(),
+            2
+        );
+        assert_ne!(
+            transaction.get_request_datagram(),
+            first_datagram.as_slice()
+        );
+        assert!(!transaction.on_timeout(Duration::from_secs(4)).unwrap());
+
+        let request = Packet::decode(transaction.get_request_datagram(), b"secret").unwrap();
+        let response = request
+            .make_response_packet(Code::AccountingResponse)
+            .encode()
+            .unwrap();
+        assert_eq!(
+            transaction.on_response(&response).unwrap().get_code(),
+            Code::AccountingResponse
+        );
+        assert!(transaction.on_response(&[0x05]).is_err());
+        // the late response of the first datagram has the other Request Authenticator
+        let late_response = Packet::decode(&first_datagram, b"secret")
+            .unwrap()
+            .make_response_packet(Code::AccountingResponse)
+            .encode()
+            .unwrap();
+        assert!(matches!(
+            transaction.on_response(&late_response),
+            Err(ClientTransactionError::UnauthenticResponseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_coa_request_transaction() {
+        let now = Utc::now();
+        let request = Packet::new(Code::CoARequest, b"secret");
+        let mut transaction = ClientTransaction::new(&request, true, 1, &now).unwrap();
+        let first_datagram = transaction.get_request_datagram().to_vec();
+
+        // the retransmission has the refreshed Event-Timestamp
+        assert!(transaction.on_timeout(Duration::from_secs(3)).unwrap());
+        let retransmitted = Packet::decode(transaction.get_request_datagram(), b"secret").unwrap();
+        assert_eq!(
+            rfc2869::lookup_all_event_timestamp(&retransmitted)
+                .unwrap()
+                .iter()
+                .map(|t| t.timestamp())
+                .collect::<Vec<_>>(),
+            vec![now.timestamp() + 3]
+        );
+        assert_ne!(
+            transaction.get_request_datagram(),
+            first_datagram.as_slice()
+        );
+
+        // the Event-Timestamp of the caller is kept
+        let mut request = Packet::new(Code::DisconnectRequest, b"secret");
+        rfc2869::add_event_timestamp(&mut request, &now);
+        let mut transaction = ClientTransaction::new(&request, true, 1, &now).unwrap();
+        let first_datagram = transaction.get_request_datagram().to_vec();
+        assert!(transaction.on_timeout(Duration::from_secs(3)).unwrap());
+        assert_eq!(
+            transaction.get_request_datagram(),
+            first_datagram.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_access_request_transaction() {
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let mut transaction = ClientTransaction::new(&request, true, 1, &Utc::now()).unwrap();
+        assert!(rfc2869::lookup_event_timestamp(transaction.get_request_packet()).is_none());
+
+        // the retransmission of Access-Request is identical
+        let first_datagram = transaction.get_request_datagram().to_vec();
+        assert!(transaction.on_timeout(Duration::from_secs(2)).unwrap());
+        assert_eq!(
+            transaction.get_request_datagram(),
+            first_datagram.as_slice()
+        );
+
+        let request = Packet::decode(&first_datagram, b"secret").unwrap();
+        let response = request.make_response_packet(Code::AccessAccept);
+        assert!(transaction.is_response(&response.encode().unwrap()));
+        let mut forged = response.clone();
+        forged.set_secret(b"wrong");
+        assert!(!transaction.is_response(&forged.encode().unwrap()));
+        let mut stray = response.clone();
+        stray.set_identifier(request.get_identifier().wrapping_add(1));
+        assert!(!transaction.is_response(&stray.encode().unwrap()));
+        assert!(!transaction.is_response(&[0x02]));
+
+        // the same verification as verify_response_authenticator()
+        let received = transaction
+            .on_response(&response.encode().unwrap())
+            .unwrap();
+        assert!(received.verify_response_authenticator(&request));
+        for datagram in [forged.encode().unwrap(), stray.encode().unwrap()] {
+            assert!(matches!(
+                transaction.on_response(&datagram),
+                Err(ClientTransactionError::UnauthenticResponseError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_require_message_authenticator() {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        let mut transaction = ClientTransaction::new(&request, true, 0, &Utc::now()).unwrap();
+        transaction.set_require_message_authenticator(true).unwrap();
+        let datagram = transaction.get_request_datagram();
+        // the first attribute
+        assert_eq!(datagram[20], rfc2869::MESSAGE_AUTHENTICATOR_TYPE);
+        assert_eq!(verify_request(datagram, b"secret"), Some(true));
+
+        let request = Packet::decode(datagram, b"secret").unwrap();
+        let response = request.make_response_packet(Code::AccessAccept);
+        assert!(transaction.on_response(&response.encode().unwrap()).is_ok());
+
+        let mut response = request.make_response_packet(Code::AccessAccept);
+        rfc2869::delete_message_authenticator(&mut response);
+        assert!(matches!(
+            transaction.on_response(&response.encode().unwrap()),
+            Err(ClientTransactionError::UnauthenticResponseError(_))
+        ));
+
+        let mut response = Packet::decode(datagram, b"wrong")
+            .unwrap()
+            .make_response_packet(Code::AccessAccept);
+        response.add_message_authenticator();
+        assert!(matches!(
+            transaction.on_response(&response.encode().unwrap()),
+            Err(ClientTransactionError::UnauthenticResponseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_identifier_allocator() {
+        let mut allocator = IdentifierAllocator::new(2);
+        let mut allocated: Vec<_> = (0..512).map(|_| allocator.allocate().unwrap()).collect();
+        assert!(allocator.allocate().is_none());
+        assert_eq!(allocator.get_outstanding(), 512);
+        assert_eq!(allocator.get_socket_count(), 2);
+        allocated.sort_unstable();
+        allocated.dedup();
+        assert_eq!(allocated.len(), 512);
+        assert!(allocated[..256].iter().all(|(socket, _)| *socket == 0));
+
+        allocator.release(1, 7);
+        allocator.release(1, 7);
+        assert!(!allocator.is_outstanding(1, 7));
+        assert_eq!(allocator.get_outstanding(), 511);
+        assert_eq!(allocator.allocate(), Some((1, 7)));
+        assert!(allocator.is_outstanding(1, 7));
+    }
+}
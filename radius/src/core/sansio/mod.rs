@@ -0,0 +1,22 @@
+//! Transport-agnostic (sans-io) protocol logic of the RADIUS client and server.
+//!
+//! The types in this module never touch sockets, timers or an async runtime; they only take the received datagrams
+//! and the current time, and tell what to send. So they can be embedded in any event loop
+//! (e.g. a custom runtime, io_uring or a simulator). `client::Client` and `server::Server` are the tokio adapters of them.
+//!
+//! - `ClientTransaction`: a request from a client, including the retransmissions and the response decoding.
+//! - `IdentifierAllocator`: the identifiers of the outstanding requests over the sockets of a client.
+//! - `RequestDecoder`: the validation and decoding of the requests on a server.
+//! - `UndergoingRequests`: the tracker of the requests that are being handled, to drop the duplicated ones.
+//! - `RateLimiter`: the per-client rate limit of the requests on a server.
+//! - `ResponseCache`: the cache of the responses, to resend them to the retransmissions of the answered requests.
+//! - `AuthenticationCache`: the cache of the responses keyed on the credentials, to answer the identical retries.
+
+mod client;
+mod server;
+
+pub use client::{ClientTransaction, ClientTransactionError, IdentifierAllocator};
+pub use server::{
+    make_status_server_response, status_server_response_code, AuthenticationCache, DecodingLimits,
+    RateLimiter, RequestDecoder, RequestDecodingError, ResponseCache, UndergoingRequests,
+};
@@ -0,0 +1,951 @@
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::net::{IpAddr, SocketAddr};
+use core::time::Duration;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::core::accounting;
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::code::Code;
+use crate::core::message_authenticator;
+use crate::core::packet::{Packet, PacketError, RADIUS_PACKET_HEADER_LENGTH};
+#[cfg(feature = "rfc2548")]
+use crate::core::rfc2548;
+use crate::core::rfc2865;
+#[cfg(feature = "rfc2868")]
+use crate::core::rfc2868;
+use crate::core::rfc2869;
+#[cfg(feature = "rfc3162")]
+use crate::core::rfc3162;
+#[cfg(feature = "rfc2548")]
+use crate::core::vsa;
+use crate::error::ErrorKind;
+
+#[derive(Error, Debug)]
+pub enum RequestDecodingError {
+    /// An error that is raised when the secret for the request is empty.
+    #[error("empty secret returned from secret source; empty secret is prohibited")]
+    EmptySecretError(),
+    /// An error that is raised when the Request Authenticator doesn't match (e.g. bad secret).
+    #[error("packet validation failed; bad secret")]
+    InvalidAuthenticatorError(),
+    /// An error that is raised when the request is not a valid RADIUS packet.
+    #[error("failed to parse given request data to pack into the RADIUS packet; {0}")]
+    DecodingError(#[from] PacketError),
+    /// An error that is raised when Message-Authenticator of the request doesn't match.
+    #[error("packet validation failed; bad Message-Authenticator")]
+    InvalidMessageAuthenticatorError(),
+    /// An error that is raised when a Status-Server (RFC 5997 section 3), or an Access-Request that is required to have
+    /// that (see `RequestDecoder::set_require_message_authenticator()`), doesn't have Message-Authenticator.
+    #[error("packet validation failed; missing Message-Authenticator")]
+    MissingMessageAuthenticatorError(),
+    /// An error that is raised when Event-Timestamp of the request is outside of the allowed window.
+    #[error("packet validation failed; Event-Timestamp is out of the allowed window")]
+    EventTimestampOutOfWindowError(),
+    /// An error that is raised when Event-Timestamp of the request is broken.
+    #[error("packet validation failed; invalid Event-Timestamp: {0}")]
+    InvalidEventTimestampError(AVPError),
+    /// An error that is raised when the datagram exceeds the maximum size of `DecodingLimits`.
+    #[error("packet validation failed; the datagram has {0} bytes, but the maximum is {1} bytes")]
+    PacketTooLargeError(usize, usize),
+    /// An error that is raised when the request has more attributes than the maximum of `DecodingLimits`.
+    #[error("packet validation failed; the request has {0} attributes, but the maximum is {1}")]
+    TooManyAttributesError(usize, usize),
+    /// An error that is raised when an attribute of the request has no value and `DecodingLimits` rejects that.
+    #[error("packet validation failed; the attribute {0} has the zero-length value")]
+    EmptyAttributeValueError(AVPType),
+}
+
+impl RequestDecodingError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            RequestDecodingError::EmptySecretError() => ErrorKind::Configuration,
+            RequestDecodingError::InvalidAuthenticatorError()
+            | RequestDecodingError::InvalidMessageAuthenticatorError()
+            | RequestDecodingError::MissingMessageAuthenticatorError()
+            | RequestDecodingError::EventTimestampOutOfWindowError() => ErrorKind::Authenticity,
+            RequestDecodingError::DecodingError(e) => e.get_kind(),
+            RequestDecodingError::InvalidEventTimestampError(e) => e.get_kind(),
+            RequestDecodingError::PacketTooLargeError(_, _)
+            | RequestDecodingError::TooManyAttributesError(_, _)
+            | RequestDecodingError::EmptyAttributeValueError(_) => ErrorKind::Malformed,
+        }
+    }
+}
+
+/// The limits of the requests that `RequestDecoder` accepts, to discard the garbage traffic early (default: no limits).
+///
+/// The RFCs allow the packets of 4096 bytes and any number of the attributes, so these are stricter than the RFCs;
+/// set them by the traffic of the clients of a server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DecodingLimits {
+    max_packet_size: Option<usize>,
+    max_attributes: Option<usize>,
+    reject_empty_values: bool,
+}
+
+impl DecodingLimits {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the maximum size of the datagrams in bytes (default: `None`, i.e. 4096 bytes of RFC 2865).
+    ///
+    /// This is checked before the authenticity validation, i.e. without the cost of MD5.
+    pub fn set_max_packet_size(&mut self, max_packet_size: Option<usize>) {
+        self.max_packet_size = max_packet_size;
+    }
+
+    /// Set the maximum number of the attributes of a request (default: `None`, i.e. unlimited).
+    pub fn set_max_attributes(&mut self, max_attributes: Option<usize>) {
+        self.max_attributes = max_attributes;
+    }
+
+    /// Set a flag to specify whether to reject the requests that have an attribute of the zero-length value
+    /// (default: `false`); RFC 2865 section 5 doesn't allow those for the text and the string.
+    pub fn set_reject_empty_values(&mut self, reject_empty_values: bool) {
+        self.reject_empty_values = reject_empty_values;
+    }
+
+    pub fn get_max_packet_size(&self) -> Option<usize> {
+        self.max_packet_size
+    }
+
+    pub fn get_max_attributes(&self) -> Option<usize> {
+        self.max_attributes
+    }
+
+    pub fn get_reject_empty_values(&self) -> bool {
+        self.reject_empty_values
+    }
+}
+
+/// The validation and decoding of the requests that a server receives.
+#[derive(Debug, Clone, Default)]
+pub struct RequestDecoder {
+    skip_authenticity_validation: bool,
+    require_message_authenticator: bool,
+    event_timestamp_window: Option<Duration>,
+    limits: DecodingLimits,
+}
+
+impl RequestDecoder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set a flag to specify whether to skip the authenticity validation or not (default: `false`).
+    pub fn set_skip_authenticity_validation(&mut self, skip_authenticity_validation: bool) {
+        self.skip_authenticity_validation = skip_authenticity_validation;
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator of Access-Request against the forgery of
+    /// the responses (CVE-2024-3596, a.k.a. Blast-RADIUS; default: `false`).
+    ///
+    /// A bad Message-Authenticator of any request is an error regardless of this.
+    pub fn set_require_message_authenticator(&mut self, require_message_authenticator: bool) {
+        self.require_message_authenticator = require_message_authenticator;
+    }
+
+    /// Set the allowed clock skew of Event-Timestamp (default: `None`, i.e. no validation).
+    pub fn set_event_timestamp_window(&mut self, event_timestamp_window: Option<Duration>) {
+        self.event_timestamp_window = event_timestamp_window;
+    }
+
+    /// Set the limits of the requests (see `DecodingLimits`).
+    pub fn set_limits(&mut self, limits: DecodingLimits) {
+        self.limits = limits;
+    }
+
+    /// Validate and decode a received datagram with the secret of the remote client.
+    /// The requests that fail here have to be discarded silently.
+    pub fn decode(
+        &self,
+        datagram: &[u8],
+        secret: &[u8],
+        now: &DateTime<Utc>,
+    ) -> Result<Packet, RequestDecodingError> {
+        if secret.is_empty() {
+            return Err(RequestDecodingError::EmptySecretError());
+        }
+        if let Some(max_packet_size) = self.limits.max_packet_size {
+            if datagram.len() > max_packet_size {
+                return Err(RequestDecodingError::PacketTooLargeError(
+                    datagram.len(),
+                    max_packet_size,
+                ));
+            }
+        }
+        // the garbage shorter than the header is malformed rather than unauthentic
+        if datagram.len() < RADIUS_PACKET_HEADER_LENGTH {
+            return Err(RequestDecodingError::DecodingError(
+                PacketError::InsufficientPacketPayloadLengthError(
+                    RADIUS_PACKET_HEADER_LENGTH,
+                    datagram.len(),
+                ),
+            ));
+        }
+
+        if !self.skip_authenticity_validation {
+            if !Packet::is_authentic_request(datagram, secret) {
+                return Err(RequestDecodingError::InvalidAuthenticatorError());
+            }
+            match message_authenticator::verify_request(datagram, secret) {
+                Some(true) => {}
+                Some(false) => return Err(RequestDecodingError::InvalidMessageAuthenticatorError()),
+                None => match Code::from(datagram[0]) {
+                    Code::StatusServer => {
+                        return Err(RequestDecodingError::MissingMessageAuthenticatorError())
+                    }
+                    Code::AccessRequest if self.require_message_authenticator => {
+                        return Err(RequestDecodingError::MissingMessageAuthenticatorError())
+                    }
+                    _ => {}
+                },
+            }
+        }
+
+        let packet = Packet::decode(datagram, secret)?;
+        if let Some(max_attributes) = self.limits.max_attributes {
+            let attributes = packet.get_attributes().len();
+            if attributes > max_attributes {
+                return Err(RequestDecodingError::TooManyAttributesError(
+                    attributes,
+                    max_attributes,
+                ));
+            }
+        }
+        if self.limits.reject_empty_values {
+            if let Some(avp) = packet
+                .get_attributes()
+                .iter()
+                .find(|avp| avp.get_value().is_empty())
+            {
+                return Err(RequestDecodingError::EmptyAttributeValueError(
+                    avp.get_type(),
+                ));
+            }
+        }
+
+        if let Some(event_timestamp_window) = self.event_timestamp_window {
+            match accounting::is_event_timestamp_within_window(&packet, now, event_timestamp_window)
+            {
+                Ok(true) => {}
+                Ok(false) => return Err(RequestDecodingError::EventTimestampOutOfWindowError()),
+                Err(e) => return Err(RequestDecodingError::InvalidEventTimestampError(e)),
+            }
+        }
+
+        Ok(packet)
+    }
+}
+
+/// Returns the code of the response to Status-Server on the port (RFC 5997 section 3),
+/// i.e. Accounting-Response on the accounting ports (`1813` and `1646`), or Access-Accept on the others.
+pub fn status_server_response_code(port: u16) -> Code {
+    match port {
+        1813 | 1646 => Code::AccountingResponse,
+        _ => Code::AccessAccept,
+    }
+}
+
+/// Make the response to a Status-Server (RFC 5997), which has Message-Authenticator.
+pub fn make_status_server_response(request: &Packet, code: Code) -> Packet {
+    let mut response = request.make_response_packet(code);
+    response.add_message_authenticator();
+    response
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+struct RequestKey {
+    ip: String,
+    identifier: u8,
+}
+
+/// A tracker of the requests that are being handled.
+///
+/// The duplicated requests (i.e. the retransmissions from a client while the original one is being handled)
+/// are identified by the remote address and the identifier.
+#[derive(Debug, Default)]
+pub struct UndergoingRequests {
+    keys: BTreeSet<RequestKey>,
+}
+
+impl UndergoingRequests {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Start handling a request and return `true`, or return `false` if the same request is being handled.
+    pub fn begin(&mut self, remote_addr: SocketAddr, identifier: u8) -> bool {
+        self.keys.insert(RequestKey {
+            ip: remote_addr.to_string(),
+            identifier,
+        })
+    }
+
+    /// Finish handling a request.
+    pub fn finish(&mut self, remote_addr: SocketAddr, identifier: u8) {
+        self.keys.remove(&RequestKey {
+            ip: remote_addr.to_string(),
+            identifier,
+        });
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+struct ResponseKey {
+    remote_addr: SocketAddr,
+    identifier: u8,
+    authenticator: Vec<u8>,
+}
+
+impl ResponseKey {
+    fn new(remote_addr: SocketAddr, request: &Packet) -> Self {
+        ResponseKey {
+            remote_addr,
+            identifier: request.get_identifier(),
+            authenticator: request.get_authenticator().clone(),
+        }
+    }
+}
+
+/// A cache of the responses that have been sent, to resend them to the retransmissions of the requests instead of
+/// handling those again (RFC 2865 section 2.5 and RFC 5080 section 2.2.2).
+///
+/// A retransmission is identified by the remote address, the identifier and the Request Authenticator; the responses
+/// are kept for the TTL, and the oldest ones are evicted beyond the maximum number of the entries.
+#[derive(Debug)]
+pub struct ResponseCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: BTreeMap<ResponseKey, (DateTime<Utc>, Vec<u8>)>,
+    expirations: VecDeque<(DateTime<Utc>, ResponseKey)>,
+}
+
+impl ResponseCache {
+    /// The default maximum number of the entries.
+    pub const DEFAULT_MAX_ENTRIES: usize = 65536;
+
+    /// A constructor for a cache that keeps the responses for the TTL.
+    pub fn new(ttl: Duration) -> Self {
+        ResponseCache {
+            ttl,
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+            entries: BTreeMap::new(),
+            expirations: VecDeque::new(),
+        }
+    }
+
+    /// Set the maximum number of the entries (default: `65536`).
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries;
+    }
+
+    pub fn get_ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the number of the cached responses, including the expired ones that have not been evicted yet.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the response datagram to resend if the request is a retransmission of a request that has been answered
+    /// within the TTL.
+    pub fn lookup(
+        &self,
+        remote_addr: SocketAddr,
+        request: &Packet,
+        now: &DateTime<Utc>,
+    ) -> Option<&[u8]> {
+        self.entries
+            .get(&ResponseKey::new(remote_addr, request))
+            .filter(|(expires_at, _)| expires_at > now)
+            .map(|(_, response)| response.as_slice())
+    }
+
+    /// Cache the response datagram that has been sent to a request, and evict the expired (and the oldest beyond the
+    /// maximum number of the entries) ones.
+    pub fn insert(
+        &mut self,
+        remote_addr: SocketAddr,
+        request: &Packet,
+        response: &[u8],
+        now: &DateTime<Utc>,
+    ) {
+        insert_expiring(
+            &mut self.entries,
+            &mut self.expirations,
+            self.ttl,
+            self.max_entries,
+            ResponseKey::new(remote_addr, request),
+            response.to_vec(),
+            now,
+        );
+    }
+}
+
+/// Insert an entry that expires after the TTL, and evict the expired (and the oldest beyond the maximum number of the
+/// entries) ones in the order of the expirations.
+fn insert_expiring<K: Ord + Clone, V>(
+    entries: &mut BTreeMap<K, (DateTime<Utc>, V)>,
+    expirations: &mut VecDeque<(DateTime<Utc>, K)>,
+    ttl: Duration,
+    max_entries: usize,
+    key: K,
+    value: V,
+    now: &DateTime<Utc>,
+) {
+    let expires_at = chrono::Duration::from_std(ttl)
+        .ok()
+        .and_then(|ttl| now.checked_add_signed(ttl))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC);
+    expirations.push_back((expires_at, key.clone()));
+    entries.insert(key, (expires_at, value));
+
+    while let Some((expires_at, _)) = expirations.front() {
+        if expires_at > now && entries.len() <= max_entries {
+            break;
+        }
+        let (expires_at, key) = expirations.pop_front().unwrap();
+        // the entry may have been replaced by a later one of the same key
+        if entries.get(&key).is_some_and(|(e, _)| *e == expires_at) {
+            entries.remove(&key);
+        }
+    }
+}
+
+/// The code and the attributes of a response that `AuthenticationCache` rebuilds for each request.
+type CachedResponse = (Code, Vec<AVP>);
+
+/// A cache of the responses to Access-Request keyed on the credentials of the requests, to answer the identical
+/// authentication retries (e.g. while the backend is down) without handling those again.
+///
+/// Unlike `ResponseCache`, a new request (i.e. another identifier and Request Authenticator) hits the cache: the key
+/// is a digest of the remote IP address, User-Name, the User-Password (decrypted) or the CHAP-Password with its
+/// challenge, NAS-IP-Address, NAS-IPv6-Address and NAS-Identifier, and the response is rebuilt for the request by
+/// `make_response_packet()`. The requests without User-Name or the credentials (e.g. EAP, which is stateful) are not
+/// cached, and only Access-Accept and Access-Reject are cached (Access-Accept is not if `set_negative_only()`).
+/// The responses that have the attributes encrypted by the Request Authenticator (Tunnel-Password and MS-MPPE keys)
+/// are not cached, since they can't be replayed to another request.
+#[derive(Debug)]
+pub struct AuthenticationCache {
+    ttl: Duration,
+    max_entries: usize,
+    negative_only: bool,
+    entries: BTreeMap<[u8; 16], (DateTime<Utc>, CachedResponse)>,
+    expirations: VecDeque<(DateTime<Utc>, [u8; 16])>,
+}
+
+impl AuthenticationCache {
+    /// The default maximum number of the entries.
+    pub const DEFAULT_MAX_ENTRIES: usize = 65536;
+
+    /// A constructor for a cache that keeps the responses for the TTL.
+    pub fn new(ttl: Duration) -> Self {
+        AuthenticationCache {
+            ttl,
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+            negative_only: false,
+            entries: BTreeMap::new(),
+            expirations: VecDeque::new(),
+        }
+    }
+
+    /// Set the maximum number of the entries (default: `65536`).
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries;
+    }
+
+    /// Set a flag to specify whether to cache only Access-Reject, i.e. the negative caching (default: `false`).
+    pub fn set_negative_only(&mut self, negative_only: bool) {
+        self.negative_only = negative_only;
+    }
+
+    pub fn get_ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the number of the cached responses, including the expired ones that have not been evicted yet.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the response to a request, if an identical request has been answered within the TTL.
+    pub fn lookup(
+        &self,
+        remote_addr: SocketAddr,
+        request: &Packet,
+        now: &DateTime<Utc>,
+    ) -> Option<Packet> {
+        let (_, (code, attributes)) = self
+            .entries
+            .get(&Self::key(remote_addr.ip(), request)?)
+            .filter(|(expires_at, _)| expires_at > now)?;
+        let mut response = request.make_response_packet(*code);
+        response.extend(attributes.clone());
+        Some(response)
+    }
+
+    /// Cache the response that has been sent to a request if it is cacheable, and evict the expired (and the oldest
+    /// beyond the maximum number of the entries) ones.
+    pub fn insert(
+        &mut self,
+        remote_addr: SocketAddr,
+        request: &Packet,
+        response: &Packet,
+        now: &DateTime<Utc>,
+    ) {
+        let cacheable = match response.get_code() {
+            Code::AccessAccept => !self.negative_only,
+            Code::AccessReject => true,
+            _ => false,
+        };
+        if !cacheable || response.get_attributes().iter().any(is_salted) {
+            return;
+        }
+        let key = match Self::key(remote_addr.ip(), request) {
+            Some(key) => key,
+            None => return,
+        };
+        // Message-Authenticator and Proxy-State are made for each request by make_response_packet()
+        let attributes = response
+            .get_attributes()
+            .iter()
+            .filter(|avp| {
+                !matches!(
+                    avp.get_type(),
+                    rfc2869::MESSAGE_AUTHENTICATOR_TYPE | rfc2865::PROXY_STATE_TYPE
+                )
+            })
+            .cloned()
+            .collect();
+
+        insert_expiring(
+            &mut self.entries,
+            &mut self.expirations,
+            self.ttl,
+            self.max_entries,
+            key,
+            (response.get_code(), attributes),
+            now,
+        );
+    }
+
+    fn key(remote_ip: IpAddr, request: &Packet) -> Option<[u8; 16]> {
+        if request.get_code() != Code::AccessRequest {
+            return None;
+        }
+        let user_name = request.lookup(rfc2865::USER_NAME_TYPE)?;
+
+        // each field is prefixed by the type and the length, so the concatenation is unambiguous
+        let mut material = Vec::new();
+        let mut push = |typ: AVPType, value: &[u8]| {
+            material.push(typ);
+            material.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            material.extend_from_slice(value);
+        };
+        match remote_ip {
+            IpAddr::V4(ip) => push(0, &ip.octets()),
+            IpAddr::V6(ip) => push(0, &ip.octets()),
+        }
+        push(rfc2865::USER_NAME_TYPE, user_name.get_value());
+        if let Some(password) = rfc2865::lookup_user_password(request) {
+            push(rfc2865::USER_PASSWORD_TYPE, &password.ok()?);
+        } else if let Some(chap_password) = rfc2865::lookup_chap_password(request) {
+            // the CHAP response is bound to the challenge, which is the Request Authenticator without CHAP-Challenge
+            let chap_challenge = rfc2865::lookup_chap_challenge(request)
+                .unwrap_or_else(|| request.get_authenticator().clone());
+            push(rfc2865::CHAP_PASSWORD_TYPE, &chap_password);
+            push(rfc2865::CHAP_CHALLENGE_TYPE, &chap_challenge);
+        } else {
+            return None;
+        }
+        #[cfg(feature = "rfc3162")]
+        let nas_ipv6_address_type = rfc3162::NAS_IPV6_ADDRESS_TYPE;
+        #[cfg(not(feature = "rfc3162"))]
+        let nas_ipv6_address_type = 95;
+        for typ in [
+            rfc2865::NAS_IP_ADDRESS_TYPE,
+            nas_ipv6_address_type,
+            rfc2865::NAS_IDENTIFIER_TYPE,
+        ] {
+            if let Some(avp) = request.lookup(typ) {
+                push(typ, avp.get_value());
+            }
+        }
+        Some(md5::compute(material).0)
+    }
+}
+
+/// Returns whether an attribute is encrypted by the Request Authenticator of the request, i.e. Tunnel-Password,
+/// MS-MPPE-Send-Key or MS-MPPE-Recv-Key.
+#[cfg_attr(
+    not(any(feature = "rfc2548", feature = "rfc2868")),
+    allow(unused_variables)
+)]
+fn is_salted(avp: &AVP) -> bool {
+    #[cfg(feature = "rfc2868")]
+    if avp.get_type() == rfc2868::TUNNEL_PASSWORD_TYPE {
+        return true;
+    }
+    #[cfg(feature = "rfc2548")]
+    if let Ok((rfc2548::VENDOR_MICROSOFT, tlvs)) = vsa::decode_vendor_specific(avp) {
+        return tlvs.iter().any(|tlv| {
+            matches!(
+                tlv.get_type(),
+                rfc2548::MS_MPPE_SEND_KEY_TYPE | rfc2548::MS_MPPE_RECV_KEY_TYPE
+            )
+        });
+    }
+    false
+}
+
+/// A rate limiter of the requests of each client (i.e. the remote IP address), as a token bucket that is refilled by
+/// the packets per second up to the burst.
+///
+/// The buckets that have been refilled up to the burst are evicted when the number of the clients exceeds the limit
+/// (`65536`), so the spoofed source addresses don't grow it unboundedly.
+#[derive(Debug)]
+pub struct RateLimiter {
+    packets_per_second: u32,
+    burst: u32,
+    buckets: BTreeMap<IpAddr, (f64, DateTime<Utc>)>,
+}
+
+impl RateLimiter {
+    const MAX_CLIENTS: usize = 65536;
+
+    /// A constructor for a limiter of the packets per second of each client; the burst is a second of that.
+    pub fn new(packets_per_second: u32) -> Self {
+        RateLimiter {
+            packets_per_second,
+            burst: packets_per_second,
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    /// Set the number of the packets that a client can send at once (default: the packets per second).
+    pub fn set_burst(&mut self, burst: u32) {
+        self.burst = burst;
+    }
+
+    pub fn get_packets_per_second(&self) -> u32 {
+        self.packets_per_second
+    }
+
+    /// Take a token of the client and return `true`, or return `false` if the client has exceeded the rate.
+    pub fn allow(&mut self, ip: IpAddr, now: &DateTime<Utc>) -> bool {
+        let (rate, burst) = (self.rate(), self.capacity());
+        if self.buckets.len() >= Self::MAX_CLIENTS && !self.buckets.contains_key(&ip) {
+            self.buckets.retain(|_, (tokens, updated_at)| {
+                refill(*tokens, updated_at, now, rate, burst) < burst
+            });
+        }
+
+        let (tokens, updated_at) = self.buckets.entry(ip).or_insert((burst, *now));
+        *tokens = refill(*tokens, updated_at, now, rate, burst);
+        if *updated_at < *now {
+            *updated_at = *now;
+        }
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn rate(&self) -> f64 {
+        f64::from(self.packets_per_second)
+    }
+
+    fn capacity(&self) -> f64 {
+        f64::from(self.burst.max(1))
+    }
+}
+
+fn refill(
+    tokens: f64,
+    updated_at: &DateTime<Utc>,
+    now: &DateTime<Utc>,
+    rate: f64,
+    burst: f64,
+) -> f64 {
+    let elapsed = (*now - *updated_at)
+        .to_std()
+        .map(|elapsed| elapsed.as_secs_f64())
+        .unwrap_or(0.0);
+    (tokens + elapsed * rate).min(burst)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::net::SocketAddr;
+    use core::time::Duration;
+
+    use chrono::Utc;
+
+    use crate::core::avp::AVP;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::core::rfc2866;
+    use crate::core::rfc2869;
+    use crate::core::sansio::{
+        AuthenticationCache, DecodingLimits, RateLimiter, RequestDecoder, RequestDecodingError,
+        ResponseCache, UndergoingRequests,
+    };
+
+    #[test]
+    fn test_decode() {
+        let now = Utc::now();
+        let mut request = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut request, rfc2866::ACCT_STATUS_TYPE_START);
+        rfc2869::add_event_timestamp(&mut request, &(now - chrono::Duration::seconds(600)));
+        let datagram = request.encode().unwrap();
+
+        let mut decoder = RequestDecoder::new();
+        assert!(decoder.decode(&datagram, b"secret", &now).is_ok());
+        assert!(matches!(
+            decoder.decode(&datagram, b"", &now),
+            Err(RequestDecodingError::EmptySecretError())
+        ));
+        assert!(matches!(
+            decoder.decode(&datagram, b"wrong", &now),
+            Err(RequestDecodingError::InvalidAuthenticatorError())
+        ));
+
+        decoder.set_event_timestamp_window(Some(Duration::from_secs(300)));
+        assert!(matches!(
+            decoder.decode(&datagram, b"secret", &now),
+            Err(RequestDecodingError::EventTimestampOutOfWindowError())
+        ));
+    }
+
+    #[test]
+    fn test_decode_limits() {
+        let now = Utc::now();
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "user");
+        rfc2865::add_nas_identifier(&mut request, "nas");
+        let datagram = request.encode().unwrap();
+
+        let mut decoder = RequestDecoder::new();
+        let mut limits = DecodingLimits::new();
+        limits.set_max_packet_size(Some(datagram.len() - 1));
+        decoder.set_limits(limits);
+        let e = decoder.decode(&datagram, b"secret", &now).unwrap_err();
+        assert!(
+            matches!(e, RequestDecodingError::PacketTooLargeError(size, _) if size == datagram.len())
+        );
+        assert_eq!(e.get_kind(), crate::error::ErrorKind::Malformed);
+
+        limits.set_max_packet_size(Some(datagram.len()));
+        limits.set_max_attributes(Some(1));
+        decoder.set_limits(limits);
+        assert!(matches!(
+            decoder.decode(&datagram, b"secret", &now),
+            Err(RequestDecodingError::TooManyAttributesError(2, 1))
+        ));
+
+        limits.set_max_attributes(Some(2));
+        limits.set_reject_empty_values(true);
+        decoder.set_limits(limits);
+        assert!(decoder.decode(&datagram, b"secret", &now).is_ok());
+        request.add(AVP::from_bytes(rfc2865::CALLING_STATION_ID_TYPE, &[]));
+        let datagram = request.encode().unwrap();
+        limits.set_max_packet_size(None);
+        limits.set_max_attributes(None);
+        decoder.set_limits(limits);
+        assert!(matches!(
+            decoder.decode(&datagram, b"secret", &now),
+            Err(RequestDecodingError::EmptyAttributeValueError(
+                rfc2865::CALLING_STATION_ID_TYPE
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_decode_message_authenticator() {
+        let now = Utc::now();
+        let decoder = RequestDecoder::new();
+
+        let request = Packet::new(Code::StatusServer, b"secret");
+        assert!(matches!(
+            decoder.decode(&request.encode().unwrap(), b"secret", &now),
+            Err(RequestDecodingError::MissingMessageAuthenticatorError())
+        ));
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        request.add_message_authenticator();
+        let mut datagram = request.encode().unwrap();
+        assert!(decoder.decode(&datagram, b"secret", &now).is_ok());
+        let last = datagram.len() - 1;
+        datagram[last] ^= 0xff;
+        assert!(matches!(
+            decoder.decode(&datagram, b"secret", &now),
+            Err(RequestDecodingError::InvalidMessageAuthenticatorError())
+        ));
+
+        let mut decoder = RequestDecoder::new();
+        decoder.set_require_message_authenticator(true);
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        assert!(matches!(
+            decoder.decode(&request.encode().unwrap(), b"secret", &now),
+            Err(RequestDecodingError::MissingMessageAuthenticatorError())
+        ));
+        let request = Packet::new(Code::AccountingRequest, b"secret");
+        assert!(decoder
+            .decode(&request.encode().unwrap(), b"secret", &now)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_undergoing_requests() {
+        let addr: SocketAddr = "192.0.2.1:1234".parse().unwrap();
+        let mut undergoing_requests = UndergoingRequests::new();
+        assert!(undergoing_requests.begin(addr, 1));
+        assert!(!undergoing_requests.begin(addr, 1));
+        assert!(undergoing_requests.begin(addr, 2));
+
+        undergoing_requests.finish(addr, 1);
+        assert!(undergoing_requests.begin(addr, 1));
+    }
+
+    #[test]
+    fn test_response_cache() {
+        let now = Utc::now();
+        let addr: SocketAddr = "192.0.2.1:1234".parse().unwrap();
+        let other_addr: SocketAddr = "192.0.2.1:1235".parse().unwrap();
+        let request = Packet::new_with_identifier(Code::AccessRequest, b"secret", 1);
+        let mut cache = ResponseCache::new(Duration::from_secs(5));
+        assert!(cache.lookup(addr, &request, &now).is_none());
+
+        cache.insert(addr, &request, b"response", &now);
+        assert_eq!(cache.lookup(addr, &request, &now), Some(&b"response"[..]));
+        assert!(cache.lookup(other_addr, &request, &now).is_none());
+        // a new request that reuses the identifier has another Request Authenticator
+        let new_request = Packet::new_with_identifier(Code::AccessRequest, b"secret", 1);
+        assert!(cache.lookup(addr, &new_request, &now).is_none());
+        let later = now + chrono::Duration::seconds(5);
+        assert!(cache.lookup(addr, &request, &later).is_none());
+
+        cache.insert(other_addr, &request, b"other", &later);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.lookup(addr, &request, &later).is_none());
+
+        cache.set_max_entries(1);
+        cache.insert(addr, &request, b"response", &later);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.lookup(other_addr, &request, &later).is_none());
+        assert!(cache.lookup(addr, &request, &later).is_some());
+    }
+
+    #[test]
+    fn test_authentication_cache() {
+        let now = Utc::now();
+        let addr: SocketAddr = "192.0.2.1:1234".parse().unwrap();
+        let new_request = |password: &[u8]| {
+            let mut request = Packet::new(Code::AccessRequest, b"secret");
+            rfc2865::add_user_name(&mut request, "alice");
+            rfc2865::add_user_password(&mut request, password).unwrap();
+            rfc2865::add_nas_identifier(&mut request, "nas");
+            request
+        };
+        let request = new_request(b"p@ssw0rd");
+        let mut response = request.make_response_packet(Code::AccessAccept);
+        rfc2865::add_reply_message(&mut response, "welcome");
+        let mut cache = AuthenticationCache::new(Duration::from_secs(5));
+        cache.insert(addr, &request, &response, &now);
+        assert_eq!(cache.len(), 1);
+
+        // a retry has another identifier, Request Authenticator and Proxy-State
+        let mut retry = new_request(b"p@ssw0rd");
+        rfc2865::add_proxy_state(&mut retry, b"proxy");
+        let cached = cache.lookup(addr, &retry, &now).unwrap();
+        assert_eq!(cached.get_code(), Code::AccessAccept);
+        assert_eq!(cached.get_identifier(), retry.get_identifier());
+        assert_eq!(
+            rfc2865::lookup_reply_message(&cached).unwrap().unwrap(),
+            "welcome"
+        );
+        assert_eq!(
+            rfc2865::lookup_all_proxy_state(&cached),
+            vec![b"proxy".to_vec()]
+        );
+        assert!(cache
+            .lookup(addr, &new_request(b"password"), &now)
+            .is_none());
+        assert!(cache
+            .lookup("192.0.2.2:1234".parse().unwrap(), &retry, &now)
+            .is_none());
+        let later = now + chrono::Duration::seconds(5);
+        assert!(cache.lookup(addr, &retry, &later).is_none());
+
+        // only Access-Reject is cached by the negative caching, and the requests without the credentials aren't
+        cache.set_negative_only(true);
+        let request = new_request(b"password");
+        cache.insert(
+            addr,
+            &request,
+            &request.make_response_packet(Code::AccessAccept),
+            &now,
+        );
+        assert!(cache.lookup(addr, &request, &now).is_none());
+        cache.insert(
+            addr,
+            &request,
+            &request.make_response_packet(Code::AccessReject),
+            &now,
+        );
+        assert_eq!(
+            cache.lookup(addr, &request, &now).unwrap().get_code(),
+            Code::AccessReject
+        );
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        cache.insert(
+            addr,
+            &request,
+            &request.make_response_packet(Code::AccessReject),
+            &now,
+        );
+        assert!(cache.lookup(addr, &request, &now).is_none());
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let now = Utc::now();
+        let ip = "192.0.2.1".parse().unwrap();
+        let other_ip = "192.0.2.2".parse().unwrap();
+        let mut limiter = RateLimiter::new(10);
+        limiter.set_burst(2);
+        assert!(limiter.allow(ip, &now));
+        assert!(limiter.allow(ip, &now));
+        assert!(!limiter.allow(ip, &now));
+        assert!(limiter.allow(other_ip, &now));
+
+        // a token per 100ms
+        let later = now + chrono::Duration::milliseconds(150);
+        assert!(limiter.allow(ip, &later));
+        assert!(!limiter.allow(ip, &later));
+        let much_later = now + chrono::Duration::seconds(60);
+        assert!(limiter.allow(ip, &much_later));
+        assert!(limiter.allow(ip, &much_later));
+        assert!(!limiter.allow(ip, &much_later));
+    }
+}
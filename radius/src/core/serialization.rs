@@ -0,0 +1,221 @@
+//! The serde support of `Packet`, `AVP` and `Code` (behind the `serde` feature), e.g. for the structured logs, the
+//! queues and the replays in the tests.
+//!
+//! A packet is serialized as the code (the number), the identifier, the authenticator and the attributes, and an AVP
+//! is serialized as the type and the raw value, so the deserialized ones encode the same bytes; the values are the hex
+//! strings (e.g. `0x0102`) in the human-readable formats like JSON, and the bytes in the others like CBOR.
+//!
+//! The shared secret is never serialized, so a deserialized packet has an empty secret; set it by
+//! `Packet::set_secret()` to encode the packet or to decrypt the passwords of it.
+
+use alloc::format;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::core::avp::{AVPType, AVP};
+use crate::core::code::Code;
+use crate::core::debug_bundle::to_hex;
+use crate::core::packet::Packet;
+
+impl Serialize for Code {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for Code {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = u8::deserialize(deserializer)?;
+        Code::try_from(code).map_err(|_| de::Error::custom(format!("unknown code: {code}")))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AVPRepr {
+    #[serde(rename = "type")]
+    typ: AVPType,
+    #[serde(with = "bytes")]
+    value: Vec<u8>,
+}
+
+impl Serialize for AVP {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        AVPRepr {
+            typ: self.get_type(),
+            value: self.get_value().to_vec(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AVP {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = AVPRepr::deserialize(deserializer)?;
+        if repr.value.len() > 253 {
+            return Err(de::Error::invalid_length(
+                repr.value.len(),
+                &"at most 253 bytes",
+            ));
+        }
+        Ok(AVP::from_bytes(repr.typ, &repr.value))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PacketRepr {
+    code: Code,
+    identifier: u8,
+    #[serde(with = "bytes")]
+    authenticator: Vec<u8>,
+    attributes: Vec<AVP>,
+}
+
+impl Serialize for Packet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PacketRepr {
+            code: self.get_code(),
+            identifier: self.get_identifier(),
+            authenticator: self.get_authenticator().clone(),
+            attributes: self.get_attributes().to_vec(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Packet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = PacketRepr::deserialize(deserializer)?;
+        if repr.authenticator.len() != 16 {
+            return Err(de::Error::invalid_length(
+                repr.authenticator.len(),
+                &"16 bytes",
+            ));
+        }
+        let mut packet = Packet::new_with_identifier(repr.code, &[], repr.identifier);
+        packet.set_authenticator(&repr.authenticator);
+        packet.extend(repr.attributes);
+        Ok(packet)
+    }
+}
+
+/// The bytes as a hex string in the human-readable formats, and as the bytes in the others.
+mod bytes {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(bs: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&to_hex(bs))
+        } else {
+            serializer.serialize_bytes(bs)
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BytesVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a hex string (e.g. `0x0102`) or bytes")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            from_hex(v).ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bs = Vec::new();
+            while let Some(b) = seq.next_element()? {
+                bs.push(b);
+            }
+            Ok(bs)
+        }
+    }
+
+    fn from_hex(s: &str) -> Option<Vec<u8>> {
+        let hex = s.strip_prefix("0x")?;
+        if !hex.len().is_multiple_of(2) {
+            return None;
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                hex.get(i..i + 2)
+                    .and_then(|b| u8::from_str_radix(b, 16).ok())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::avp::AVP;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+
+    #[test]
+    fn test_json() {
+        let mut packet = Packet::new_with_identifier(Code::AccessRequest, b"secret", 7);
+        rfc2865::add_user_name(&mut packet, "bob");
+        rfc2865::add_user_password(&mut packet, b"hunter2").unwrap();
+        packet.add(AVP::from_bytes(200, &[0xde, 0xad]));
+
+        let json = serde_json::to_value(&packet).unwrap();
+        assert_eq!(json["code"], 1);
+        assert_eq!(json["identifier"], 7);
+        assert_eq!(json["attributes"][0]["type"], 1);
+        assert_eq!(json["attributes"][0]["value"], "0x626f62");
+        assert_eq!(json["attributes"][2]["value"], "0xdead");
+        assert!(json.get("secret").is_none());
+
+        let mut decoded: Packet = serde_json::from_value(json).unwrap();
+        assert!(decoded.get_secret().is_empty());
+        decoded.set_secret(b"secret");
+        assert_eq!(decoded, packet);
+        assert_eq!(
+            rfc2865::lookup_user_password(&decoded).unwrap().unwrap(),
+            b"hunter2"
+        );
+
+        let avps: Vec<AVP> = serde_json::from_str(r#"[{"type": 1, "value": "0x626f62"}]"#).unwrap();
+        assert_eq!(avps, vec![AVP::from_string(1, "bob")]);
+        assert!(serde_json::from_str::<AVP>(r#"{"type": 1, "value": "626f62"}"#).is_err());
+        assert!(serde_json::from_str::<Code>("6").is_err());
+    }
+
+    #[test]
+    fn test_cbor() {
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, "bob");
+
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&packet, &mut cbor).unwrap();
+        let mut decoded: Packet = ciborium::from_reader(cbor.as_slice()).unwrap();
+        decoded.set_secret(b"secret");
+        assert_eq!(decoded, packet);
+    }
+}
@@ -0,0 +1,259 @@
+//! Utility for the multi-round authentication by the State attribute without a session table on the server.
+//!
+//! The server puts an opaque State value into Access-Challenge, and the NAS sends that back in the following
+//! Access-Request as it is (see also: https://tools.ietf.org/html/rfc2865#section-5.24).
+//! `StateIssuer` generates the value that carries the server's own payload (e.g. the round of the conversation or
+//! the expected OTP slot) with the time of the issue, signed with a server-side key; so it can be verified that
+//! the value in an Access-Request is the one that this server has issued, and the payload is restored from that.
+//!
+//! The payload is signed but not encrypted, so the NAS can read that; don't put a secret into the payload.
+//!
+//! ```
+//! use chrono::{Duration, TimeZone, Utc};
+//! use radius::core::state::StateIssuer;
+//!
+//! let issuer = StateIssuer::new(b"state-key", Some(Duration::minutes(5)));
+//! let issued_at = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+//! let value = issuer.issue(b"round-2", issued_at).unwrap();
+//!
+//! let token = issuer.verify(&value, issued_at + Duration::seconds(30)).unwrap();
+//! assert_eq!(token.get_payload(), b"round-2");
+//! assert!(issuer.verify(&value, issued_at + Duration::minutes(6)).is_err());
+//! ```
+
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use md5_digest::Md5;
+use rand::Rng;
+use thiserror::Error;
+
+#[cfg(feature = "std")]
+use crate::core::deterministic;
+#[cfg(feature = "std")]
+use crate::core::packet::Packet;
+use crate::core::random;
+#[cfg(feature = "std")]
+use crate::core::rfc2865;
+
+const STATE_MAGIC: &[u8] = b"rrs1";
+const ID_LENGTH: usize = 16;
+const TIMESTAMP_LENGTH: usize = 8;
+const MAC_LENGTH: usize = 16;
+const HEADER_LENGTH: usize = STATE_MAGIC.len() + ID_LENGTH + TIMESTAMP_LENGTH;
+/// The maximum length of an attribute value; a State value is a single attribute.
+const MAX_STATE_LENGTH: usize = 253;
+
+/// The maximum length of the payload of a State value.
+pub const MAX_PAYLOAD_LENGTH: usize = MAX_STATE_LENGTH - HEADER_LENGTH - MAC_LENGTH;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum StateError {
+    /// An error that is raised when the payload exceeds `MAX_PAYLOAD_LENGTH`.
+    #[error("the payload of the state value is too long: {0} bytes")]
+    PayloadTooLongError(usize),
+    /// An error that is raised when the State value has not been issued by `StateIssuer`.
+    #[error("the state value has not been issued by this server")]
+    UnknownStateFormatError(),
+    /// An error that is raised when the signature of the State value doesn't match.
+    #[error("the state value has an invalid signature")]
+    InvalidSignatureError(),
+    /// An error that is raised when the State value is older than the TTL of the issuer.
+    #[error("the state value has expired")]
+    ExpiredStateError(),
+}
+
+/// The content of a State value that has been issued by `StateIssuer`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StateToken {
+    id: [u8; ID_LENGTH],
+    issued_at: DateTime<Utc>,
+    payload: Vec<u8>,
+}
+
+impl StateToken {
+    /// Returns the random identifier that is unique per Access-Challenge, e.g. to reject the replays.
+    pub fn get_id(&self) -> &[u8] {
+        &self.id
+    }
+
+    pub fn get_issued_at(&self) -> DateTime<Utc> {
+        self.issued_at
+    }
+
+    pub fn get_payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+/// An issuer of the signed State values.
+pub struct StateIssuer {
+    key: Vec<u8>,
+    ttl: Option<Duration>,
+}
+
+impl StateIssuer {
+    /// A constructor for an issuer with the key to sign the State values, and the TTL of them (`None` never expires).
+    /// The key has to be shared between the servers that may receive the following Access-Requests.
+    pub fn new(key: &[u8], ttl: Option<Duration>) -> Self {
+        StateIssuer {
+            key: key.to_vec(),
+            ttl,
+        }
+    }
+
+    /// HMAC-MD5 of the key, for the signature of a value.
+    fn mac(&self) -> Hmac<Md5> {
+        Hmac::<Md5>::new_from_slice(&self.key).expect("HMAC accepts any key length")
+    }
+
+    /// Generate a new State value of a payload.
+    pub fn issue(&self, payload: &[u8], issued_at: DateTime<Utc>) -> Result<Vec<u8>, StateError> {
+        if payload.len() > MAX_PAYLOAD_LENGTH {
+            return Err(StateError::PayloadTooLongError(payload.len()));
+        }
+        let id: [u8; ID_LENGTH] = random::rng().gen();
+
+        let mut value = Vec::with_capacity(HEADER_LENGTH + payload.len() + MAC_LENGTH);
+        value.extend_from_slice(STATE_MAGIC);
+        value.extend_from_slice(&id);
+        value.extend_from_slice(&issued_at.timestamp().to_be_bytes());
+        value.extend_from_slice(payload);
+        let mut mac = self.mac();
+        mac.update(&value);
+        value.extend_from_slice(&mac.finalize().into_bytes());
+        Ok(value)
+    }
+
+    /// Add a new State value of a payload to a packet (i.e. Access-Challenge); the existing State values are replaced.
+    #[cfg(feature = "std")]
+    pub fn add_state(&self, packet: &mut Packet, payload: &[u8]) -> Result<(), StateError> {
+        let value = self.issue(payload, deterministic::now())?;
+        rfc2865::delete_state(packet);
+        rfc2865::add_state(packet, &value);
+        Ok(())
+    }
+
+    /// Verify a State value at the time, and return its content.
+    pub fn verify(&self, value: &[u8], now: DateTime<Utc>) -> Result<StateToken, StateError> {
+        if value.len() < HEADER_LENGTH + MAC_LENGTH || !value.starts_with(STATE_MAGIC) {
+            return Err(StateError::UnknownStateFormatError());
+        }
+
+        let (signed, mac) = value.split_at(value.len() - MAC_LENGTH);
+        let mut expected_mac = self.mac();
+        expected_mac.update(signed);
+        if expected_mac.verify_slice(mac).is_err() {
+            return Err(StateError::InvalidSignatureError());
+        }
+
+        let id_end = STATE_MAGIC.len() + ID_LENGTH;
+        let timestamp = i64::from_be_bytes(signed[id_end..HEADER_LENGTH].try_into().unwrap());
+        let issued_at = match Utc.timestamp_opt(timestamp, 0).single() {
+            Some(issued_at) => issued_at,
+            None => return Err(StateError::UnknownStateFormatError()),
+        };
+        if self
+            .ttl
+            .is_some_and(|ttl| now.signed_duration_since(issued_at) > ttl)
+        {
+            return Err(StateError::ExpiredStateError());
+        }
+        Ok(StateToken {
+            id: signed[STATE_MAGIC.len()..id_end].try_into().unwrap(),
+            issued_at,
+            payload: signed[HEADER_LENGTH..].to_vec(),
+        })
+    }
+
+    /// Lookup the State value of a packet (i.e. the Access-Request that follows an Access-Challenge) and verify that.
+    ///
+    /// It returns `None` if the packet has no State, i.e. the request begins a new conversation.
+    #[cfg(feature = "std")]
+    pub fn lookup_state(&self, packet: &Packet) -> Option<Result<StateToken, StateError>> {
+        rfc2865::lookup_state(packet).map(|value| self.verify(&value, deterministic::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use crate::core::state::{StateError, StateIssuer, MAX_PAYLOAD_LENGTH};
+
+    #[test]
+    fn test_issue_and_verify() {
+        let issuer = StateIssuer::new(b"state-key", Some(Duration::seconds(60)));
+        let issued_at = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let value = issuer.issue(b"payload", issued_at).unwrap();
+        let token = issuer.verify(&value, issued_at).unwrap();
+        assert_eq!(token.get_issued_at(), issued_at);
+        assert_eq!(token.get_payload(), b"payload");
+
+        let another_value = issuer.issue(b"payload", issued_at).unwrap();
+        assert_ne!(
+            token.get_id(),
+            issuer.verify(&another_value, issued_at).unwrap().get_id()
+        );
+
+        let mut tampered = value.clone();
+        tampered[30] ^= 0x01;
+        assert_eq!(
+            issuer.verify(&tampered, issued_at),
+            Err(StateError::InvalidSignatureError())
+        );
+        assert_eq!(
+            StateIssuer::new(b"another-key", None).verify(&value, issued_at),
+            Err(StateError::InvalidSignatureError())
+        );
+        assert_eq!(
+            issuer.verify(b"foreign-state", issued_at),
+            Err(StateError::UnknownStateFormatError())
+        );
+        assert_eq!(
+            issuer.verify(&value, issued_at + Duration::seconds(61)),
+            Err(StateError::ExpiredStateError())
+        );
+
+        let payload = vec![0; MAX_PAYLOAD_LENGTH];
+        let value = issuer.issue(&payload, issued_at).unwrap();
+        assert_eq!(value.len(), 253);
+        assert_eq!(
+            issuer.verify(&value, issued_at).unwrap().get_payload(),
+            payload
+        );
+        assert_eq!(
+            issuer.issue(&[0; MAX_PAYLOAD_LENGTH + 1], issued_at),
+            Err(StateError::PayloadTooLongError(MAX_PAYLOAD_LENGTH + 1))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lookup_state() {
+        use crate::core::code::Code;
+        use crate::core::packet::Packet;
+        use crate::core::rfc2865;
+
+        let issuer = StateIssuer::new(b"state-key", Some(Duration::seconds(60)));
+        let mut challenge = Packet::new(Code::AccessChallenge, b"secret");
+        rfc2865::add_state(&mut challenge, b"stale");
+        issuer.add_state(&mut challenge, b"round-1").unwrap();
+        assert_eq!(rfc2865::lookup_all_state(&challenge).len(), 1);
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        assert!(issuer.lookup_state(&request).is_none());
+
+        rfc2865::add_state(&mut request, &rfc2865::lookup_state(&challenge).unwrap());
+        assert_eq!(
+            issuer
+                .lookup_state(&request)
+                .unwrap()
+                .unwrap()
+                .get_payload(),
+            b"round-1"
+        );
+    }
+}
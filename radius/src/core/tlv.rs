@@ -0,0 +1,241 @@
+//! The TLV data type (RFC 6929 section 2.3), i.e. the sub-attributes that are nested in the value of an attribute.
+//!
+//! A `Tlv` has the TLV-Type and the TLV-Value, and the value of a TLV can be the TLVs again (`Tlv::new_nested()`).
+//! The wire format doesn't tell whether a TLV-Value is nested, so `decode_tlvs()` decodes one level, and
+//! `Tlv::decode_nested()` decodes the next one as the dictionary of the attribute defines. `TlvBuilder` builds a set
+//! of the TLVs for `AVP::from_tlvs()` (or for `rfc6929::add_extended()`).
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use core::net::Ipv4Addr;
+
+use crate::core::avp::AVPError;
+
+/// The maximum length of a TLV-Value, since the TLV-Length includes the TLV-Type and itself.
+pub const MAX_TLV_VALUE_LENGTH: usize = 253;
+
+/// A TLV, i.e. a sub-attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tlv {
+    typ: u8,
+    value: Vec<u8>,
+}
+
+impl Tlv {
+    /// A constructor for a TLV of the bytes value.
+    pub fn new(typ: u8, value: &[u8]) -> Result<Self, AVPError> {
+        if value.len() > MAX_TLV_VALUE_LENGTH {
+            return Err(AVPError::InvalidAttributeLengthError(
+                format!("{MAX_TLV_VALUE_LENGTH} bytes"),
+                value.len(),
+            ));
+        }
+        Ok(Tlv {
+            typ,
+            value: value.to_vec(),
+        })
+    }
+
+    /// A constructor for a TLV whose value is the nested TLVs.
+    pub fn new_nested(typ: u8, tlvs: &[Tlv]) -> Result<Self, AVPError> {
+        Tlv::new(typ, &encode_tlvs(tlvs)?)
+    }
+
+    pub fn from_string(typ: u8, value: &str) -> Result<Self, AVPError> {
+        Tlv::new(typ, value.as_bytes())
+    }
+
+    pub fn from_u32(typ: u8, value: u32) -> Self {
+        Tlv {
+            typ,
+            value: value.to_be_bytes().to_vec(),
+        }
+    }
+
+    pub fn from_ipv4(typ: u8, value: &Ipv4Addr) -> Self {
+        Tlv {
+            typ,
+            value: value.octets().to_vec(),
+        }
+    }
+
+    pub fn get_type(&self) -> u8 {
+        self.typ
+    }
+
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Decode the TLV-Value as the nested TLVs.
+    pub fn decode_nested(&self) -> Result<Vec<Tlv>, AVPError> {
+        decode_tlvs(&self.value)
+    }
+
+    pub fn encode_string(&self) -> Result<String, AVPError> {
+        String::from_utf8(self.value.clone()).map_err(|e| AVPError::DecodingError(e.to_string()))
+    }
+
+    pub fn encode_u32(&self) -> Result<u32, AVPError> {
+        let bytes: [u8; 4] = self.value.as_slice().try_into().map_err(|_| {
+            AVPError::InvalidAttributeLengthError("4 bytes".to_owned(), self.value.len())
+        })?;
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    pub fn encode_ipv4(&self) -> Result<Ipv4Addr, AVPError> {
+        let bytes: [u8; 4] = self.value.as_slice().try_into().map_err(|_| {
+            AVPError::InvalidAttributeLengthError("4 bytes".to_owned(), self.value.len())
+        })?;
+        Ok(Ipv4Addr::from(bytes))
+    }
+}
+
+/// Encode the TLVs into the bytes of a value.
+pub fn encode_tlvs(tlvs: &[Tlv]) -> Result<Vec<u8>, AVPError> {
+    let mut encoded = Vec::new();
+    for tlv in tlvs {
+        if tlv.value.len() > MAX_TLV_VALUE_LENGTH {
+            return Err(AVPError::InvalidAttributeLengthError(
+                format!("{MAX_TLV_VALUE_LENGTH} bytes"),
+                tlv.value.len(),
+            ));
+        }
+        encoded.push(tlv.typ);
+        encoded.push((tlv.value.len() + 2) as u8);
+        encoded.extend_from_slice(&tlv.value);
+    }
+    Ok(encoded)
+}
+
+/// Decode the bytes of a value into the TLVs (of one level).
+pub fn decode_tlvs(bs: &[u8]) -> Result<Vec<Tlv>, AVPError> {
+    let mut tlvs = Vec::new();
+    let mut rest = bs;
+    while !rest.is_empty() {
+        if rest.len() < 2 {
+            return Err(AVPError::DecodingError("short TLV".to_owned()));
+        }
+        let len = rest[1] as usize;
+        if len < 2 || len > rest.len() {
+            return Err(AVPError::DecodingError(format!(
+                "invalid TLV-Length: {len}"
+            )));
+        }
+        tlvs.push(Tlv {
+            typ: rest[0],
+            value: rest[2..len].to_vec(),
+        });
+        rest = &rest[len..];
+    }
+    Ok(tlvs)
+}
+
+/// A builder of the TLVs of a value.
+///
+/// The error of a TLV (e.g. too long value) is reported by `build()`.
+#[derive(Debug, Default)]
+pub struct TlvBuilder {
+    tlvs: Vec<Tlv>,
+    error: Option<AVPError>,
+}
+
+impl TlvBuilder {
+    pub fn new() -> Self {
+        TlvBuilder::default()
+    }
+
+    fn push(mut self, tlv: Result<Tlv, AVPError>) -> Self {
+        match tlv {
+            Ok(tlv) => self.tlvs.push(tlv),
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+        self
+    }
+
+    /// Add a TLV of the bytes value.
+    pub fn bytes(self, typ: u8, value: &[u8]) -> Self {
+        self.push(Tlv::new(typ, value))
+    }
+
+    /// Add a TLV of the string value.
+    pub fn string(self, typ: u8, value: &str) -> Self {
+        self.push(Tlv::from_string(typ, value))
+    }
+
+    /// Add a TLV of the integer value.
+    pub fn u32(self, typ: u8, value: u32) -> Self {
+        self.push(Ok(Tlv::from_u32(typ, value)))
+    }
+
+    /// Add a TLV of the IPv4 address value.
+    pub fn ipv4(self, typ: u8, value: &Ipv4Addr) -> Self {
+        self.push(Ok(Tlv::from_ipv4(typ, value)))
+    }
+
+    /// Add a TLV whose value is the TLVs of the builder.
+    pub fn nested(self, typ: u8, builder: TlvBuilder) -> Self {
+        let tlv = builder.build().and_then(|tlvs| Tlv::new_nested(typ, &tlvs));
+        self.push(tlv)
+    }
+
+    /// Returns the TLVs, or the first error of them.
+    pub fn build(self) -> Result<Vec<Tlv>, AVPError> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.tlvs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::net::Ipv4Addr;
+
+    use crate::core::avp::AVP;
+    use crate::core::tlv::{decode_tlvs, encode_tlvs, Tlv, TlvBuilder};
+
+    #[test]
+    fn test_tlvs() {
+        let tlvs = TlvBuilder::new()
+            .string(1, "ssid")
+            .nested(
+                2,
+                TlvBuilder::new()
+                    .u32(1, 6)
+                    .ipv4(2, &Ipv4Addr::new(192, 0, 2, 1)),
+            )
+            .build()
+            .unwrap();
+        let encoded = encode_tlvs(&tlvs).unwrap();
+        assert_eq!(
+            encoded,
+            vec![1, 6, b's', b's', b'i', b'd', 2, 14, 1, 6, 0, 0, 0, 6, 2, 6, 192, 0, 2, 1]
+        );
+
+        let avp = AVP::from_tlvs(100, &tlvs).unwrap();
+        let decoded = avp.encode_tlvs().unwrap();
+        assert_eq!(decoded, tlvs);
+        assert_eq!(decoded[0].encode_string().unwrap(), "ssid");
+        let nested = decoded[1].decode_nested().unwrap();
+        assert_eq!(nested[0].encode_u32().unwrap(), 6);
+        assert_eq!(
+            nested[1].encode_ipv4().unwrap(),
+            Ipv4Addr::new(192, 0, 2, 1)
+        );
+
+        assert!(TlvBuilder::new()
+            .bytes(1, &[0; 254])
+            .u32(2, 1)
+            .build()
+            .is_err());
+        assert!(AVP::from_tlvs(100, &[Tlv::new(1, &[0; 252]).unwrap()]).is_err());
+        assert!(decode_tlvs(&[1, 1]).is_err());
+        assert!(decode_tlvs(&[1, 4, 0]).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+//! The sets of the tunnel attributes (RFC 2868).
+//!
+//! The tunnel attributes of the same Tag describe one tunnel, and an Access-Accept can offer several tunnels of
+//! different Tags (e.g. with different Tunnel-Preference). `Tunnel` is such a set, which `add_tunnel()` adds to
+//! a packet by the tagged values of `rfc2868`, and `lookup_tunnels()` groups the tunnel attributes of a packet by
+//! the Tag; the untagged string values belong to the tunnel of the unused Tag (`0x00`).
+
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::core::avp::AVPError;
+use crate::core::packet::Packet;
+use crate::core::rfc2868::{self, TunnelMediumType, TunnelType};
+use crate::core::tag::Tag;
+
+/// A set of the tunnel attributes of a Tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tunnel {
+    tag: Tag,
+    tunnel_type: Option<TunnelType>,
+    medium_type: Option<TunnelMediumType>,
+    client_endpoint: Option<String>,
+    server_endpoint: Option<String>,
+    password: Option<Vec<u8>>,
+    private_group_id: Option<String>,
+    assignment_id: Option<String>,
+    preference: Option<u32>,
+    client_auth_id: Option<String>,
+    server_auth_id: Option<String>,
+}
+
+impl Tunnel {
+    /// A constructor for the empty tunnel of the Tag (`0x01` to `0x1F`, or `Tag::new_unused()`).
+    pub fn new(tag: Tag) -> Self {
+        Tunnel {
+            tag,
+            tunnel_type: None,
+            medium_type: None,
+            client_endpoint: None,
+            server_endpoint: None,
+            password: None,
+            private_group_id: None,
+            assignment_id: None,
+            preference: None,
+            client_auth_id: None,
+            server_auth_id: None,
+        }
+    }
+
+    /// A constructor for the VLAN assignment of IEEE 802.1X (RFC 3580 section 3.31), i.e. Tunnel-Type of VLAN (13),
+    /// Tunnel-Medium-Type of IEEE-802 and Tunnel-Private-Group-Id of the VLAN ID.
+    pub fn new_vlan(tag: Tag, vlan_id: u16) -> Self {
+        let mut tunnel = Tunnel::new(tag);
+        // TUNNEL_TYPE_VLAN of rfc3580
+        tunnel.set_tunnel_type(13);
+        tunnel.set_medium_type(rfc2868::TUNNEL_MEDIUM_TYPE_IEEE_802);
+        tunnel.set_private_group_id(&vlan_id.to_string());
+        tunnel
+    }
+
+    pub fn get_tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    pub fn set_tunnel_type(&mut self, tunnel_type: TunnelType) {
+        self.tunnel_type = Some(tunnel_type);
+    }
+
+    pub fn get_tunnel_type(&self) -> Option<TunnelType> {
+        self.tunnel_type
+    }
+
+    pub fn set_medium_type(&mut self, medium_type: TunnelMediumType) {
+        self.medium_type = Some(medium_type);
+    }
+
+    pub fn get_medium_type(&self) -> Option<TunnelMediumType> {
+        self.medium_type
+    }
+
+    pub fn set_client_endpoint(&mut self, client_endpoint: &str) {
+        self.client_endpoint = Some(client_endpoint.to_owned());
+    }
+
+    pub fn get_client_endpoint(&self) -> Option<&str> {
+        self.client_endpoint.as_deref()
+    }
+
+    pub fn set_server_endpoint(&mut self, server_endpoint: &str) {
+        self.server_endpoint = Some(server_endpoint.to_owned());
+    }
+
+    pub fn get_server_endpoint(&self) -> Option<&str> {
+        self.server_endpoint.as_deref()
+    }
+
+    /// Set the Tunnel-Password, which is encrypted by the secret and the authenticator of the packet of `add_tunnel()`.
+    pub fn set_password(&mut self, password: &[u8]) {
+        self.password = Some(password.to_vec());
+    }
+
+    pub fn get_password(&self) -> Option<&[u8]> {
+        self.password.as_deref()
+    }
+
+    pub fn set_private_group_id(&mut self, private_group_id: &str) {
+        self.private_group_id = Some(private_group_id.to_owned());
+    }
+
+    pub fn get_private_group_id(&self) -> Option<&str> {
+        self.private_group_id.as_deref()
+    }
+
+    pub fn set_assignment_id(&mut self, assignment_id: &str) {
+        self.assignment_id = Some(assignment_id.to_owned());
+    }
+
+    pub fn get_assignment_id(&self) -> Option<&str> {
+        self.assignment_id.as_deref()
+    }
+
+    pub fn set_preference(&mut self, preference: u32) {
+        self.preference = Some(preference);
+    }
+
+    pub fn get_preference(&self) -> Option<u32> {
+        self.preference
+    }
+
+    pub fn set_client_auth_id(&mut self, client_auth_id: &str) {
+        self.client_auth_id = Some(client_auth_id.to_owned());
+    }
+
+    pub fn get_client_auth_id(&self) -> Option<&str> {
+        self.client_auth_id.as_deref()
+    }
+
+    pub fn set_server_auth_id(&mut self, server_auth_id: &str) {
+        self.server_auth_id = Some(server_auth_id.to_owned());
+    }
+
+    pub fn get_server_auth_id(&self) -> Option<&str> {
+        self.server_auth_id.as_deref()
+    }
+}
+
+/// Add the attributes of a tunnel to a packet; the string values of the unused Tag are added without the tag octet.
+pub fn add_tunnel(packet: &mut Packet, tunnel: &Tunnel) -> Result<(), AVPError> {
+    let tag = Some(&tunnel.tag);
+    let string_tag = tag.filter(|tag| tag.is_valid_value());
+
+    if let Some(tunnel_type) = tunnel.tunnel_type {
+        rfc2868::add_tunnel_type(packet, tag, tunnel_type);
+    }
+    if let Some(medium_type) = tunnel.medium_type {
+        rfc2868::add_tunnel_medium_type(packet, tag, medium_type);
+    }
+    if let Some(client_endpoint) = &tunnel.client_endpoint {
+        rfc2868::add_tunnel_client_endpoint(packet, string_tag, client_endpoint);
+    }
+    if let Some(server_endpoint) = &tunnel.server_endpoint {
+        rfc2868::add_tunnel_server_endpoint(packet, string_tag, server_endpoint);
+    }
+    if let Some(password) = &tunnel.password {
+        rfc2868::add_tunnel_password(packet, tag, password)?;
+    }
+    if let Some(private_group_id) = &tunnel.private_group_id {
+        rfc2868::add_tunnel_private_group_id(packet, string_tag, private_group_id);
+    }
+    if let Some(assignment_id) = &tunnel.assignment_id {
+        rfc2868::add_tunnel_assignment_id(packet, string_tag, assignment_id);
+    }
+    if let Some(preference) = tunnel.preference {
+        rfc2868::add_tunnel_preference(packet, tag, preference);
+    }
+    if let Some(client_auth_id) = &tunnel.client_auth_id {
+        rfc2868::add_tunnel_client_auth_id(packet, string_tag, client_auth_id);
+    }
+    if let Some(server_auth_id) = &tunnel.server_auth_id {
+        rfc2868::add_tunnel_server_auth_id(packet, string_tag, server_auth_id);
+    }
+    Ok(())
+}
+
+/// Returns the tunnels of a packet, in the order of the first attribute of each Tag.
+///
+/// Tunnel-Password is decrypted by the secret and the authenticator of the packet, as `rfc2868::lookup_tunnel_password()`.
+pub fn lookup_tunnels(packet: &Packet) -> Result<Vec<Tunnel>, AVPError> {
+    let mut tunnels: Vec<Tunnel> = Vec::new();
+    fn tunnel_of(tunnels: &mut Vec<Tunnel>, tag: Tag) -> &mut Tunnel {
+        match tunnels.iter().position(|tunnel| tunnel.tag == tag) {
+            Some(i) => &mut tunnels[i],
+            None => {
+                tunnels.push(Tunnel::new(tag));
+                tunnels.last_mut().unwrap()
+            }
+        }
+    }
+    let string_tag = |tag: Option<Tag>| tag.unwrap_or_else(Tag::new_unused);
+
+    for avp in packet.get_attributes() {
+        match avp.get_type() {
+            rfc2868::TUNNEL_TYPE_TYPE => {
+                let (v, tag) = avp.encode_tagged_u32()?;
+                tunnel_of(&mut tunnels, tag).tunnel_type = Some(v);
+            }
+            rfc2868::TUNNEL_MEDIUM_TYPE_TYPE => {
+                let (v, tag) = avp.encode_tagged_u32()?;
+                tunnel_of(&mut tunnels, tag).medium_type = Some(v);
+            }
+            rfc2868::TUNNEL_CLIENT_ENDPOINT_TYPE => {
+                let (v, tag) = avp.encode_tagged_string()?;
+                tunnel_of(&mut tunnels, string_tag(tag)).client_endpoint = Some(v);
+            }
+            rfc2868::TUNNEL_SERVER_ENDPOINT_TYPE => {
+                let (v, tag) = avp.encode_tagged_string()?;
+                tunnel_of(&mut tunnels, string_tag(tag)).server_endpoint = Some(v);
+            }
+            rfc2868::TUNNEL_PASSWORD_TYPE => {
+                let (v, tag) =
+                    avp.encode_tunnel_password(packet.get_secret(), packet.get_authenticator())?;
+                tunnel_of(&mut tunnels, tag).password = Some(v);
+            }
+            rfc2868::TUNNEL_PRIVATE_GROUP_ID_TYPE => {
+                let (v, tag) = avp.encode_tagged_string()?;
+                tunnel_of(&mut tunnels, string_tag(tag)).private_group_id = Some(v);
+            }
+            rfc2868::TUNNEL_ASSIGNMENT_ID_TYPE => {
+                let (v, tag) = avp.encode_tagged_string()?;
+                tunnel_of(&mut tunnels, string_tag(tag)).assignment_id = Some(v);
+            }
+            rfc2868::TUNNEL_PREFERENCE_TYPE => {
+                let (v, tag) = avp.encode_tagged_u32()?;
+                tunnel_of(&mut tunnels, tag).preference = Some(v);
+            }
+            rfc2868::TUNNEL_CLIENT_AUTH_ID_TYPE => {
+                let (v, tag) = avp.encode_tagged_string()?;
+                tunnel_of(&mut tunnels, string_tag(tag)).client_auth_id = Some(v);
+            }
+            rfc2868::TUNNEL_SERVER_AUTH_ID_TYPE => {
+                let (v, tag) = avp.encode_tagged_string()?;
+                tunnel_of(&mut tunnels, string_tag(tag)).server_auth_id = Some(v);
+            }
+            _ => {}
+        }
+    }
+    Ok(tunnels)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2868;
+    use crate::core::tag::Tag;
+    use crate::core::tunnel::{add_tunnel, lookup_tunnels, Tunnel};
+
+    #[test]
+    fn test_tunnels() {
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let mut response = request.make_response_packet(Code::AccessAccept);
+
+        let mut l2tp = Tunnel::new(Tag::new(1));
+        l2tp.set_tunnel_type(rfc2868::TUNNEL_TYPE_L2TP);
+        l2tp.set_medium_type(rfc2868::TUNNEL_MEDIUM_TYPE_I_PV_4);
+        l2tp.set_server_endpoint("192.0.2.1");
+        l2tp.set_password(b"tunnel-secret");
+        l2tp.set_preference(1);
+        let vlan = Tunnel::new_vlan(Tag::new_unused(), 100);
+        add_tunnel(&mut response, &l2tp).unwrap();
+        add_tunnel(&mut response, &vlan).unwrap();
+
+        // Tunnel-Type: Type, Length (6), Tag and the value of 3 octets
+        let encoded = response.encode().unwrap();
+        let tunnel_type = &encoded[20..26];
+        assert_eq!(tunnel_type, &[64, 6, 0x01, 0x00, 0x00, 0x03]);
+
+        let mut decoded = Packet::decode(&encoded, b"secret").unwrap();
+        assert_eq!(lookup_tunnels(&response).unwrap(), vec![l2tp, vlan.clone()]);
+        assert_eq!(
+            rfc2868::lookup_tunnel_private_group_id(&decoded)
+                .unwrap()
+                .unwrap(),
+            ("100".to_owned(), None)
+        );
+
+        rfc2868::delete_tunnel_password(&mut decoded);
+        let tunnels = lookup_tunnels(&decoded).unwrap();
+        assert_eq!(tunnels.len(), 2);
+        assert_eq!(tunnels[0].get_tag(), &Tag::new(1));
+        assert_eq!(tunnels[0].get_server_endpoint(), Some("192.0.2.1"));
+        assert_eq!(tunnels[1], vlan);
+    }
+}
@@ -0,0 +1,279 @@
+//! Utility for the Vendor-Specific attribute (type 26) of RFC 2865 section 5.26.
+//!
+//! The value of a Vendor-Specific attribute is the Vendor-Id (4 octets) and the vendor's sub-attributes, each of
+//! them is the Vendor-Type, the Vendor-Length and the value as the format that RFC 2865 recommends (i.e. a TLV).
+//! The functions of this module handle the sub-attributes of a vendor as the AVPs whose type is the Vendor-Type, so
+//! the vendor modules (e.g. `cisco`) are generated the same way as the `rfcXXXX` ones.
+//! `VsaBuilder` packs some sub-attributes into one Vendor-Specific attribute.
+
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use core::net::Ipv4Addr;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::packet::Packet;
+use crate::core::rfc2865;
+use crate::core::tlv::{decode_tlvs, encode_tlvs, Tlv, TlvBuilder};
+
+/// The Vendor-Id, i.e. the SMI Network Management Private Enterprise Code of a vendor.
+pub type VendorId = u32;
+
+/// Make a Vendor-Specific attribute of the vendor that carries a sub-attribute.
+pub fn make_vendor_specific(vendor_id: VendorId, vendor_type: AVPType, value: &[u8]) -> AVP {
+    AVP::from_bytes(
+        rfc2865::VENDOR_SPECIFIC_TYPE,
+        &[
+            &vendor_id.to_be_bytes()[..],
+            &[vendor_type, (value.len() + 2) as u8],
+            value,
+        ]
+        .concat(),
+    )
+}
+
+/// Make a Vendor-Specific attribute of the vendor that carries the sub-attributes.
+pub fn from_vendor_specific(vendor_id: VendorId, tlvs: &[Tlv]) -> Result<AVP, AVPError> {
+    let value = [&vendor_id.to_be_bytes()[..], &encode_tlvs(tlvs)?].concat();
+    if value.len() > 253 {
+        return Err(AVPError::InvalidAttributeLengthError(
+            "253 bytes".to_owned(),
+            value.len(),
+        ));
+    }
+    Ok(AVP::from_bytes(rfc2865::VENDOR_SPECIFIC_TYPE, &value))
+}
+
+/// Decode a Vendor-Specific attribute into the Vendor-Id and the sub-attributes.
+pub fn decode_vendor_specific(avp: &AVP) -> Result<(VendorId, Vec<Tlv>), AVPError> {
+    if avp.get_type() != rfc2865::VENDOR_SPECIFIC_TYPE {
+        return Err(AVPError::DecodingError(
+            "not a Vendor-Specific attribute".to_owned(),
+        ));
+    }
+    let value = avp.get_value();
+    if value.len() < 4 {
+        return Err(AVPError::InvalidAttributeLengthError(
+            "at least 4 bytes".to_owned(),
+            value.len(),
+        ));
+    }
+    let vendor_id = VendorId::from_be_bytes([value[0], value[1], value[2], value[3]]);
+    Ok((vendor_id, decode_tlvs(&value[4..])?))
+}
+
+/// Returns the sub-attributes of the vendor in a Vendor-Specific attribute; a sub-attribute that can't be decoded
+/// and the rest of it are ignored.
+fn vendor_sub_attributes(avp: &AVP, vendor_id: VendorId) -> Vec<(AVPType, &[u8])> {
+    let value = avp.get_value();
+    let mut sub_attributes = Vec::new();
+    if avp.get_type() != rfc2865::VENDOR_SPECIFIC_TYPE
+        || value.len() < 4
+        || value[..4] != vendor_id.to_be_bytes()
+    {
+        return sub_attributes;
+    }
+    let mut rest = &value[4..];
+    while rest.len() >= 2 {
+        let len = rest[1] as usize;
+        if len < 2 || len > rest.len() {
+            break;
+        }
+        sub_attributes.push((rest[0], &rest[2..len]));
+        rest = &rest[len..];
+    }
+    sub_attributes
+}
+
+/// Add a sub-attribute of the vendor to a packet; `avp` has the Vendor-Type as the type.
+pub fn add_vendor_attribute(packet: &mut Packet, vendor_id: VendorId, avp: AVP) {
+    packet.add(make_vendor_specific(
+        vendor_id,
+        avp.get_type(),
+        avp.get_value(),
+    ));
+}
+
+/// Lookup a sub-attribute of the vendor from a packet, as an AVP whose type is the Vendor-Type.
+///
+/// It returns the first looked up value. If there is no associated value, it returns `None`.
+pub fn lookup_vendor_attribute(
+    packet: &Packet,
+    vendor_id: VendorId,
+    vendor_type: AVPType,
+) -> Option<AVP> {
+    lookup_all_vendor_attributes(packet, vendor_id, vendor_type)
+        .into_iter()
+        .next()
+}
+
+/// Lookup all of the sub-attributes of the vendor from a packet, as the AVPs whose type is the Vendor-Type.
+pub fn lookup_all_vendor_attributes(
+    packet: &Packet,
+    vendor_id: VendorId,
+    vendor_type: AVPType,
+) -> Vec<AVP> {
+    packet
+        .lookup_all(rfc2865::VENDOR_SPECIFIC_TYPE)
+        .into_iter()
+        .flat_map(|avp| vendor_sub_attributes(avp, vendor_id))
+        .filter(|(typ, _)| *typ == vendor_type)
+        .map(|(typ, value)| AVP::from_bytes(typ, value))
+        .collect()
+}
+
+/// Delete the Vendor-Specific attributes that carry the sub-attribute of the vendor; the ones of the other vendors
+/// are kept.
+pub fn delete_vendor_attribute(packet: &mut Packet, vendor_id: VendorId, vendor_type: AVPType) {
+    packet.retain_attributes(|avp| {
+        !vendor_sub_attributes(avp, vendor_id)
+            .iter()
+            .any(|(typ, _)| *typ == vendor_type)
+    });
+}
+
+/// A builder of a Vendor-Specific attribute that carries some sub-attributes of a vendor.
+///
+/// The error of a sub-attribute (e.g. too long value) is reported by `build()`.
+#[derive(Debug)]
+pub struct VsaBuilder {
+    vendor_id: VendorId,
+    tlvs: TlvBuilder,
+}
+
+impl VsaBuilder {
+    pub fn new(vendor_id: VendorId) -> Self {
+        VsaBuilder {
+            vendor_id,
+            tlvs: TlvBuilder::new(),
+        }
+    }
+
+    /// Add a sub-attribute of the bytes value.
+    pub fn bytes(mut self, vendor_type: AVPType, value: &[u8]) -> Self {
+        self.tlvs = self.tlvs.bytes(vendor_type, value);
+        self
+    }
+
+    /// Add a sub-attribute of the string value.
+    pub fn string(mut self, vendor_type: AVPType, value: &str) -> Self {
+        self.tlvs = self.tlvs.string(vendor_type, value);
+        self
+    }
+
+    /// Add a sub-attribute of the integer value.
+    pub fn u32(mut self, vendor_type: AVPType, value: u32) -> Self {
+        self.tlvs = self.tlvs.u32(vendor_type, value);
+        self
+    }
+
+    /// Add a sub-attribute of the IPv4 address value.
+    pub fn ipv4(mut self, vendor_type: AVPType, value: &Ipv4Addr) -> Self {
+        self.tlvs = self.tlvs.ipv4(vendor_type, value);
+        self
+    }
+
+    /// Returns the Vendor-Specific attribute, or the first error of the sub-attributes.
+    pub fn build(self) -> Result<AVP, AVPError> {
+        from_vendor_specific(self.vendor_id, &self.tlvs.build()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::avp::AVP;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::core::vsa::{
+        add_vendor_attribute, decode_vendor_specific, delete_vendor_attribute,
+        lookup_all_vendor_attributes, lookup_vendor_attribute, VsaBuilder,
+    };
+
+    #[test]
+    fn test_vendor_attributes() {
+        let mut packet = Packet::new(Code::AccessAccept, b"secret");
+        add_vendor_attribute(&mut packet, 9, AVP::from_string(1, "shell:priv-lvl=15"));
+        add_vendor_attribute(&mut packet, 9, AVP::from_string(1, "ip:addr-pool=p1"));
+        add_vendor_attribute(&mut packet, 14988, AVP::from_string(1, "mikrotik"));
+        rfc2865::add_user_name(&mut packet, "user");
+
+        assert_eq!(
+            packet
+                .lookup(rfc2865::VENDOR_SPECIFIC_TYPE)
+                .unwrap()
+                .get_value(),
+            [&[0, 0, 0, 9, 1, 19][..], b"shell:priv-lvl=15"].concat()
+        );
+        assert_eq!(
+            lookup_vendor_attribute(&packet, 9, 1)
+                .unwrap()
+                .encode_string()
+                .unwrap(),
+            "shell:priv-lvl=15"
+        );
+        assert_eq!(lookup_all_vendor_attributes(&packet, 9, 1).len(), 2);
+        assert!(lookup_vendor_attribute(&packet, 9, 2).is_none());
+
+        delete_vendor_attribute(&mut packet, 9, 1);
+        assert!(lookup_vendor_attribute(&packet, 9, 1).is_none());
+        assert!(lookup_vendor_attribute(&packet, 14988, 1).is_some());
+        assert!(rfc2865::lookup_user_name(&packet).is_some());
+    }
+
+    #[test]
+    fn test_vsa_builder() {
+        let avp = VsaBuilder::new(14823)
+            .string(1, "employee")
+            .u32(2, 100)
+            .build()
+            .unwrap();
+        assert_eq!(
+            avp.get_value(),
+            [
+                &[0, 0, 0x39, 0xe7, 1, 10][..],
+                b"employee",
+                &[2, 6, 0, 0, 0, 100]
+            ]
+            .concat()
+        );
+
+        let (vendor_id, tlvs) = decode_vendor_specific(&avp).unwrap();
+        assert_eq!(vendor_id, 14823);
+        assert_eq!(tlvs[0].encode_string().unwrap(), "employee");
+        assert_eq!(tlvs[1].encode_u32().unwrap(), 100);
+
+        assert!(VsaBuilder::new(9)
+            .bytes(1, &[0; 200])
+            .bytes(2, &[0; 100])
+            .build()
+            .is_err());
+        assert!(decode_vendor_specific(&AVP::from_bytes(26, &[0, 0])).is_err());
+        assert!(decode_vendor_specific(&AVP::from_string(1, "user")).is_err());
+    }
+
+    #[cfg(feature = "cisco")]
+    #[test]
+    fn test_generated_vendor_module() {
+        use crate::core::cisco;
+
+        let mut packet = Packet::new(Code::AccessAccept, b"secret");
+        cisco::add_cisco_av_pair(&mut packet, "shell:priv-lvl=15");
+        cisco::add_cisco_disconnect_cause(&mut packet, cisco::CISCO_DISCONNECT_CAUSE_IDLE_TIMEOUT);
+
+        let decoded = Packet::decode(&packet.encode().unwrap(), b"secret").unwrap();
+        assert_eq!(
+            cisco::lookup_cisco_av_pair(&decoded).unwrap().unwrap(),
+            "shell:priv-lvl=15"
+        );
+        assert_eq!(
+            cisco::lookup_cisco_disconnect_cause(&decoded)
+                .unwrap()
+                .unwrap(),
+            cisco::CISCO_DISCONNECT_CAUSE_IDLE_TIMEOUT
+        );
+
+        cisco::delete_cisco_av_pair(&mut packet);
+        assert!(cisco::lookup_cisco_av_pair(&packet).is_none());
+        assert_eq!(packet.lookup_all(rfc2865::VENDOR_SPECIFIC_TYPE).len(), 1);
+    }
+}
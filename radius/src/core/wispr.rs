@@ -0,0 +1,448 @@
+// Code generated by machine generator; DO NOT EDIT.
+
+//! Utility for the Vendor-Specific attributes of WISPr (Vendor-Id 14122).
+//!
+//! This module handles the packet according to the following definition:
+//! ```text
+//! //! # -*- text -*-
+//! # Copyright (C) 2020 The FreeRADIUS Server project and contributors
+//! # This work is licensed under CC-BY version 4.0 https://creativecommons.org/licenses/by/4.0
+//! # Version $Id$
+//! #
+//! #    The WISPr (Wireless ISP roaming) attributes of the captive portals and the hotspots, from the
+//! #    dictionary.wispr of FreeRADIUS.
+//! #    http://www.wi-fi.org/
+//! #
+//! #    $Id$
+//! #
+//!
+//! VENDOR        WISPr                14122
+//!
+//! BEGIN-VENDOR    WISPr
+//!
+//! ATTRIBUTE    WISPr-Location-ID            1    string
+//! ATTRIBUTE    WISPr-Location-Name            2    string
+//! ATTRIBUTE    WISPr-Logoff-URL            3    string
+//! ATTRIBUTE    WISPr-Redirection-URL            4    string
+//! ATTRIBUTE    WISPr-Bandwidth-Min-Up            5    integer
+//! ATTRIBUTE    WISPr-Bandwidth-Min-Down        6    integer
+//! ATTRIBUTE    WISPr-Bandwidth-Max-Up            7    integer
+//! ATTRIBUTE    WISPr-Bandwidth-Max-Down        8    integer
+//! ATTRIBUTE    WISPr-Session-Terminate-Time        9    string
+//! ATTRIBUTE    WISPr-Session-Terminate-End-Of-Day    10    string
+//! ATTRIBUTE    WISPr-Billing-Class-Of-Service        11    string
+//!
+//! END-VENDOR    WISPr
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::core::avp::{AVPError, AVPType, AVP};
+use crate::core::dictionary::{AttributeDefinition, ValueDefinition};
+use crate::core::packet::Packet;
+use crate::core::vsa::{self, VendorId};
+
+/// The Vendor-Id of WISPr.
+pub const VENDOR_WIS_PR: VendorId = 14122;
+
+pub const WIS_PR_LOCATION_ID_TYPE: AVPType = 1;
+/// Delete all of `wis_pr_location_id` values from a packet.
+pub fn delete_wis_pr_location_id(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_LOCATION_ID_TYPE);
+}
+/// Add `wis_pr_location_id` string value to a packet.
+pub fn add_wis_pr_location_id(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_string(WIS_PR_LOCATION_ID_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_location_id` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_location_id`, it returns `None`.
+pub fn lookup_wis_pr_location_id(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_LOCATION_ID_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `wis_pr_location_id` string value from a packet.
+pub fn lookup_all_wis_pr_location_id(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_WIS_PR, WIS_PR_LOCATION_ID_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_LOCATION_NAME_TYPE: AVPType = 2;
+/// Delete all of `wis_pr_location_name` values from a packet.
+pub fn delete_wis_pr_location_name(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_LOCATION_NAME_TYPE);
+}
+/// Add `wis_pr_location_name` string value to a packet.
+pub fn add_wis_pr_location_name(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_string(WIS_PR_LOCATION_NAME_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_location_name` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_location_name`, it returns `None`.
+pub fn lookup_wis_pr_location_name(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_LOCATION_NAME_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `wis_pr_location_name` string value from a packet.
+pub fn lookup_all_wis_pr_location_name(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_WIS_PR, WIS_PR_LOCATION_NAME_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_LOGOFF_URL_TYPE: AVPType = 3;
+/// Delete all of `wis_pr_logoff_url` values from a packet.
+pub fn delete_wis_pr_logoff_url(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_LOGOFF_URL_TYPE);
+}
+/// Add `wis_pr_logoff_url` string value to a packet.
+pub fn add_wis_pr_logoff_url(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_string(WIS_PR_LOGOFF_URL_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_logoff_url` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_logoff_url`, it returns `None`.
+pub fn lookup_wis_pr_logoff_url(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_LOGOFF_URL_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `wis_pr_logoff_url` string value from a packet.
+pub fn lookup_all_wis_pr_logoff_url(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_WIS_PR, WIS_PR_LOGOFF_URL_TYPE) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_REDIRECTION_URL_TYPE: AVPType = 4;
+/// Delete all of `wis_pr_redirection_url` values from a packet.
+pub fn delete_wis_pr_redirection_url(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_REDIRECTION_URL_TYPE);
+}
+/// Add `wis_pr_redirection_url` string value to a packet.
+pub fn add_wis_pr_redirection_url(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_string(WIS_PR_REDIRECTION_URL_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_redirection_url` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_redirection_url`, it returns `None`.
+pub fn lookup_wis_pr_redirection_url(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_REDIRECTION_URL_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `wis_pr_redirection_url` string value from a packet.
+pub fn lookup_all_wis_pr_redirection_url(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(packet, VENDOR_WIS_PR, WIS_PR_REDIRECTION_URL_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_BANDWIDTH_MIN_UP_TYPE: AVPType = 5;
+/// Delete all of `wis_pr_bandwidth_min_up` values from a packet.
+pub fn delete_wis_pr_bandwidth_min_up(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MIN_UP_TYPE);
+}
+/// Add `wis_pr_bandwidth_min_up` integer value to a packet.
+pub fn add_wis_pr_bandwidth_min_up(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_u32(WIS_PR_BANDWIDTH_MIN_UP_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_bandwidth_min_up` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_bandwidth_min_up`, it returns `None`.
+pub fn lookup_wis_pr_bandwidth_min_up(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MIN_UP_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `wis_pr_bandwidth_min_up` integer value from a packet.
+pub fn lookup_all_wis_pr_bandwidth_min_up(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MIN_UP_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_BANDWIDTH_MIN_DOWN_TYPE: AVPType = 6;
+/// Delete all of `wis_pr_bandwidth_min_down` values from a packet.
+pub fn delete_wis_pr_bandwidth_min_down(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MIN_DOWN_TYPE);
+}
+/// Add `wis_pr_bandwidth_min_down` integer value to a packet.
+pub fn add_wis_pr_bandwidth_min_down(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_u32(WIS_PR_BANDWIDTH_MIN_DOWN_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_bandwidth_min_down` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_bandwidth_min_down`, it returns `None`.
+pub fn lookup_wis_pr_bandwidth_min_down(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MIN_DOWN_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `wis_pr_bandwidth_min_down` integer value from a packet.
+pub fn lookup_all_wis_pr_bandwidth_min_down(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MIN_DOWN_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_BANDWIDTH_MAX_UP_TYPE: AVPType = 7;
+/// Delete all of `wis_pr_bandwidth_max_up` values from a packet.
+pub fn delete_wis_pr_bandwidth_max_up(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MAX_UP_TYPE);
+}
+/// Add `wis_pr_bandwidth_max_up` integer value to a packet.
+pub fn add_wis_pr_bandwidth_max_up(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_u32(WIS_PR_BANDWIDTH_MAX_UP_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_bandwidth_max_up` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_bandwidth_max_up`, it returns `None`.
+pub fn lookup_wis_pr_bandwidth_max_up(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MAX_UP_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `wis_pr_bandwidth_max_up` integer value from a packet.
+pub fn lookup_all_wis_pr_bandwidth_max_up(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MAX_UP_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_BANDWIDTH_MAX_DOWN_TYPE: AVPType = 8;
+/// Delete all of `wis_pr_bandwidth_max_down` values from a packet.
+pub fn delete_wis_pr_bandwidth_max_down(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MAX_DOWN_TYPE);
+}
+/// Add `wis_pr_bandwidth_max_down` integer value to a packet.
+pub fn add_wis_pr_bandwidth_max_down(packet: &mut Packet, value: u32) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_u32(WIS_PR_BANDWIDTH_MAX_DOWN_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_bandwidth_max_down` integer value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_bandwidth_max_down`, it returns `None`.
+pub fn lookup_wis_pr_bandwidth_max_down(packet: &Packet) -> Option<Result<u32, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MAX_DOWN_TYPE)
+        .map(|v| v.encode_u32())
+}
+/// Lookup all of the `wis_pr_bandwidth_max_down` integer value from a packet.
+pub fn lookup_all_wis_pr_bandwidth_max_down(packet: &Packet) -> Result<Vec<u32>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_WIS_PR, WIS_PR_BANDWIDTH_MAX_DOWN_TYPE)
+    {
+        vec.push(avp.encode_u32()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_SESSION_TERMINATE_TIME_TYPE: AVPType = 9;
+/// Delete all of `wis_pr_session_terminate_time` values from a packet.
+pub fn delete_wis_pr_session_terminate_time(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_SESSION_TERMINATE_TIME_TYPE);
+}
+/// Add `wis_pr_session_terminate_time` string value to a packet.
+pub fn add_wis_pr_session_terminate_time(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_string(WIS_PR_SESSION_TERMINATE_TIME_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_session_terminate_time` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_session_terminate_time`, it returns `None`.
+pub fn lookup_wis_pr_session_terminate_time(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_SESSION_TERMINATE_TIME_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `wis_pr_session_terminate_time` string value from a packet.
+pub fn lookup_all_wis_pr_session_terminate_time(packet: &Packet) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in
+        vsa::lookup_all_vendor_attributes(packet, VENDOR_WIS_PR, WIS_PR_SESSION_TERMINATE_TIME_TYPE)
+    {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_SESSION_TERMINATE_END_OF_DAY_TYPE: AVPType = 10;
+/// Delete all of `wis_pr_session_terminate_end_of_day` values from a packet.
+pub fn delete_wis_pr_session_terminate_end_of_day(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        WIS_PR_SESSION_TERMINATE_END_OF_DAY_TYPE,
+    );
+}
+/// Add `wis_pr_session_terminate_end_of_day` string value to a packet.
+pub fn add_wis_pr_session_terminate_end_of_day(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_string(WIS_PR_SESSION_TERMINATE_END_OF_DAY_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_session_terminate_end_of_day` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_session_terminate_end_of_day`, it returns `None`.
+pub fn lookup_wis_pr_session_terminate_end_of_day(
+    packet: &Packet,
+) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        WIS_PR_SESSION_TERMINATE_END_OF_DAY_TYPE,
+    )
+    .map(|v| v.encode_string())
+}
+/// Lookup all of the `wis_pr_session_terminate_end_of_day` string value from a packet.
+pub fn lookup_all_wis_pr_session_terminate_end_of_day(
+    packet: &Packet,
+) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(
+        packet,
+        VENDOR_WIS_PR,
+        WIS_PR_SESSION_TERMINATE_END_OF_DAY_TYPE,
+    ) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+
+pub const WIS_PR_BILLING_CLASS_OF_SERVICE_TYPE: AVPType = 11;
+/// Delete all of `wis_pr_billing_class_of_service` values from a packet.
+pub fn delete_wis_pr_billing_class_of_service(packet: &mut Packet) {
+    vsa::delete_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BILLING_CLASS_OF_SERVICE_TYPE);
+}
+/// Add `wis_pr_billing_class_of_service` string value to a packet.
+pub fn add_wis_pr_billing_class_of_service(packet: &mut Packet, value: &str) {
+    vsa::add_vendor_attribute(
+        packet,
+        VENDOR_WIS_PR,
+        AVP::from_string(WIS_PR_BILLING_CLASS_OF_SERVICE_TYPE, value),
+    );
+}
+/// Lookup a `wis_pr_billing_class_of_service` string value from a packet.
+///
+/// It returns the first looked up value. If there is no associated value with `wis_pr_billing_class_of_service`, it returns `None`.
+pub fn lookup_wis_pr_billing_class_of_service(packet: &Packet) -> Option<Result<String, AVPError>> {
+    vsa::lookup_vendor_attribute(packet, VENDOR_WIS_PR, WIS_PR_BILLING_CLASS_OF_SERVICE_TYPE)
+        .map(|v| v.encode_string())
+}
+/// Lookup all of the `wis_pr_billing_class_of_service` string value from a packet.
+pub fn lookup_all_wis_pr_billing_class_of_service(
+    packet: &Packet,
+) -> Result<Vec<String>, AVPError> {
+    let mut vec = Vec::new();
+    for avp in vsa::lookup_all_vendor_attributes(
+        packet,
+        VENDOR_WIS_PR,
+        WIS_PR_BILLING_CLASS_OF_SERVICE_TYPE,
+    ) {
+        vec.push(avp.encode_string()?)
+    }
+    Ok(vec)
+}
+/// The attributes that are defined by this dictionary.
+pub const ATTRIBUTES: &[AttributeDefinition] = &[
+    AttributeDefinition::new("WISPr-Location-ID", WIS_PR_LOCATION_ID_TYPE, "string"),
+    AttributeDefinition::new("WISPr-Location-Name", WIS_PR_LOCATION_NAME_TYPE, "string"),
+    AttributeDefinition::new("WISPr-Logoff-URL", WIS_PR_LOGOFF_URL_TYPE, "string"),
+    AttributeDefinition::new(
+        "WISPr-Redirection-URL",
+        WIS_PR_REDIRECTION_URL_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "WISPr-Bandwidth-Min-Up",
+        WIS_PR_BANDWIDTH_MIN_UP_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "WISPr-Bandwidth-Min-Down",
+        WIS_PR_BANDWIDTH_MIN_DOWN_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "WISPr-Bandwidth-Max-Up",
+        WIS_PR_BANDWIDTH_MAX_UP_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "WISPr-Bandwidth-Max-Down",
+        WIS_PR_BANDWIDTH_MAX_DOWN_TYPE,
+        "integer",
+    ),
+    AttributeDefinition::new(
+        "WISPr-Session-Terminate-Time",
+        WIS_PR_SESSION_TERMINATE_TIME_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "WISPr-Session-Terminate-End-Of-Day",
+        WIS_PR_SESSION_TERMINATE_END_OF_DAY_TYPE,
+        "string",
+    ),
+    AttributeDefinition::new(
+        "WISPr-Billing-Class-Of-Service",
+        WIS_PR_BILLING_CLASS_OF_SERVICE_TYPE,
+        "string",
+    ),
+];
+
+/// The named values of the attributes that are defined by this dictionary.
+pub const VALUES: &[ValueDefinition] = &[];
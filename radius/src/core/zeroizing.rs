@@ -0,0 +1,132 @@
+//! The variants of the lookups of the passwords and the keys that wipe the values from the memory on drop.
+//!
+//! With the `zeroize` feature, `Packet` wipes its shared secret when it's dropped (and when `set_secret()` replaces
+//! that), and the decryption of User-Password, Tunnel-Password and MS-MPPE-Send/Recv-Key wipes its intermediate
+//! buffers. The values that the `lookup_*()` functions of the dictionary modules return are the plain `Vec<u8>`s,
+//! which the callers have to wipe by themselves; the functions of this module return them as `Zeroizing<Vec<u8>>`
+//! instead, which does that on drop.
+//!
+//! ```
+//! use radius::core::code::Code;
+//! use radius::core::packet::Packet;
+//! use radius::core::{rfc2865, zeroizing};
+//!
+//! let mut request = Packet::new(Code::AccessRequest, b"secret");
+//! rfc2865::add_user_password(&mut request, b"p@ssw0rd").unwrap();
+//!
+//! let password = zeroizing::lookup_user_password(&request).unwrap().unwrap();
+//! assert_eq!(password.as_slice(), b"p@ssw0rd");
+//! // the password is wiped here
+//! ```
+
+use alloc::vec::Vec;
+
+pub use zeroize::Zeroizing;
+
+use crate::core::avp::AVPError;
+use crate::core::packet::Packet;
+#[cfg(feature = "rfc2548")]
+use crate::core::rfc2548;
+use crate::core::rfc2865;
+#[cfg(feature = "rfc2868")]
+use crate::core::rfc2868;
+#[cfg(feature = "rfc2868")]
+use crate::core::tag::Tag;
+
+/// Lookup the first User-Password of a request (see `rfc2865::lookup_user_password()`).
+pub fn lookup_user_password(packet: &Packet) -> Option<Result<Zeroizing<Vec<u8>>, AVPError>> {
+    rfc2865::lookup_user_password(packet).map(|password| password.map(Zeroizing::new))
+}
+
+/// A Tunnel-Password and its tag.
+#[cfg(feature = "rfc2868")]
+pub type TunnelPassword = (Zeroizing<Vec<u8>>, Tag);
+
+/// Lookup the first Tunnel-Password of a packet (see `rfc2868::lookup_tunnel_password()`).
+#[cfg(feature = "rfc2868")]
+pub fn lookup_tunnel_password(packet: &Packet) -> Option<Result<TunnelPassword, AVPError>> {
+    rfc2868::lookup_tunnel_password(packet)
+        .map(|password| password.map(|(password, tag)| (Zeroizing::new(password), tag)))
+}
+
+/// Lookup the first MS-MPPE-Send-Key of a response by the authenticator of the request
+/// (see `rfc2548::lookup_ms_mppe_send_key()`).
+#[cfg(feature = "rfc2548")]
+pub fn lookup_ms_mppe_send_key(
+    packet: &Packet,
+    request_authenticator: &[u8],
+) -> Option<Result<Zeroizing<Vec<u8>>, AVPError>> {
+    rfc2548::lookup_ms_mppe_send_key(packet, request_authenticator)
+        .map(|key| key.map(Zeroizing::new))
+}
+
+/// Lookup the first MS-MPPE-Recv-Key of a response by the authenticator of the request
+/// (see `rfc2548::lookup_ms_mppe_recv_key()`).
+#[cfg(feature = "rfc2548")]
+pub fn lookup_ms_mppe_recv_key(
+    packet: &Packet,
+    request_authenticator: &[u8],
+) -> Option<Result<Zeroizing<Vec<u8>>, AVPError>> {
+    rfc2548::lookup_ms_mppe_recv_key(packet, request_authenticator)
+        .map(|key| key.map(Zeroizing::new))
+}
+
+/// Returns a copy of the shared secret of a packet, e.g. to hand it to another packet.
+pub fn get_secret(packet: &Packet) -> Zeroizing<Vec<u8>> {
+    Zeroizing::new(packet.get_secret().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2865, zeroizing};
+
+    #[test]
+    fn test_lookup() {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_password(&mut request, b"p@ssw0rd").unwrap();
+        assert_eq!(
+            zeroizing::lookup_user_password(&request)
+                .unwrap()
+                .unwrap()
+                .as_slice(),
+            b"p@ssw0rd"
+        );
+        assert_eq!(zeroizing::get_secret(&request).as_slice(), b"secret");
+
+        #[cfg(feature = "rfc2868")]
+        {
+            let mut response = request.make_response_packet(Code::AccessAccept);
+            crate::core::rfc2868::add_tunnel_password(&mut response, None, b"tunnel").unwrap();
+            let (password, _) = zeroizing::lookup_tunnel_password(&response)
+                .unwrap()
+                .unwrap();
+            assert_eq!(password.as_slice(), b"tunnel");
+        }
+
+        #[cfg(feature = "rfc2548")]
+        {
+            let mut response = request.make_response_packet(Code::AccessAccept);
+            crate::core::rfc2548::add_ms_mppe_send_key(&mut response, &[1; 16]).unwrap();
+            crate::core::rfc2548::add_ms_mppe_recv_key(&mut response, &[2; 16]).unwrap();
+            assert_eq!(
+                zeroizing::lookup_ms_mppe_send_key(&response, request.get_authenticator())
+                    .unwrap()
+                    .unwrap()
+                    .as_slice(),
+                [1; 16]
+            );
+            assert_eq!(
+                zeroizing::lookup_ms_mppe_recv_key(&response, request.get_authenticator())
+                    .unwrap()
+                    .unwrap()
+                    .as_slice(),
+                [2; 16]
+            );
+        }
+
+        request.set_secret(b"another");
+        assert_eq!(request.get_secret(), b"another");
+    }
+}
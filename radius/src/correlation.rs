@@ -0,0 +1,207 @@
+//! Correlation of the upstream requests with the downstream requests that have caused them.
+//!
+//! `Server` (and `TransportServer`) give each received request a random `CorrelationId`, and run the handler in the scope
+//! of that; the requests that the handler sends by `Client` or `MultiplexedClient` (e.g. through `AccountingProxy`)
+//! pick the id of the scope up, so they need no extra parameter:
+//!
+//! - the debug logs of the retransmissions and the forwarding failures have `(correlation: <id>)` at the end;
+//! - with the `tracing` feature, the handler runs in a `radius.server.request` span and the client requests run in
+//!   `radius.client.request` spans, both of which have the id as the `correlation_id` field.
+//!
+//! The spans also have the address of the peer (`peer`) and the code of the request (`code`); the server span gets the
+//! `identifier` after the decoding. Their events: a request has been decoded or dropped, the outcome of a request and
+//! its latency, a retransmission, and the response (or the error) of a client request and its latency. No attribute is
+//! traced, so the secrets and the passwords never appear in the traces.
+//!
+//! The metrics (`radius::metrics`) are aggregated and don't have the ids, not to explode the number of the series.
+//!
+//! The scope doesn't cross `tokio::spawn()`; run the spawned future in `scope(current()...)` to keep the id.
+
+use std::fmt;
+use std::future::Future;
+use std::net::SocketAddr;
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+use rand::RngCore;
+
+use crate::client::ClientError;
+use crate::core::code::Code;
+use crate::core::packet::Packet;
+use crate::core::random;
+
+tokio::task_local! {
+    static CURRENT: CorrelationId;
+}
+
+/// An identifier that ties the upstream requests to the downstream request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CorrelationId(u64);
+
+impl CorrelationId {
+    /// Returns a correlation id of the value (e.g. the one that an upper layer has assigned).
+    pub fn new(id: u64) -> Self {
+        CorrelationId(id)
+    }
+
+    /// Returns a random correlation id.
+    pub fn generate() -> Self {
+        CorrelationId(random::rng().next_u64())
+    }
+
+    /// Returns the value of the id.
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// Returns the correlation id of the current scope, if any.
+pub fn current() -> Option<CorrelationId> {
+    CURRENT.try_with(|id| *id).ok()
+}
+
+/// Run the future in the scope of the correlation id.
+pub async fn scope<F: Future>(id: CorrelationId, future: F) -> F::Output {
+    CURRENT.scope(id, future).await
+}
+
+/// The suffix of the log messages that has the correlation id of the current scope, if any.
+pub(crate) struct LogSuffix(Option<CorrelationId>);
+
+pub(crate) fn log_suffix() -> LogSuffix {
+    LogSuffix(current())
+}
+
+impl fmt::Display for LogSuffix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(id) => write!(f, " (correlation: {id})"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Run the handling of a downstream request from the peer in the scope of a new correlation id.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) async fn serve<F: Future>(remote_addr: SocketAddr, future: F) -> F::Output {
+    let id = CorrelationId::generate();
+    #[cfg(feature = "tracing")]
+    let future = tracing::Instrument::instrument(
+        future,
+        tracing::info_span!(
+            "radius.server.request",
+            correlation_id = %id,
+            peer = %remote_addr,
+            code = tracing::field::Empty,
+            identifier = tracing::field::Empty,
+        ),
+    );
+    scope(id, future).await
+}
+
+/// Record the code and the identifier of the decoded request into the span of `serve()`.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn trace_decoded(packet: &Packet) {
+    #[cfg(feature = "tracing")]
+    {
+        let span = tracing::Span::current();
+        span.record("code", packet.get_code().string());
+        span.record("identifier", packet.get_identifier());
+        tracing::debug!("decoded a request");
+    }
+}
+
+/// Trace a datagram that has been dropped because it couldn't be decoded or validated.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn trace_decoding_failure(error: &dyn fmt::Display) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(error = %error, "dropped a request");
+}
+
+/// Trace the outcome of a downstream request and its latency.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn trace_outcome(outcome: &dyn fmt::Debug, latency: std::time::Duration) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(outcome = ?outcome, latency = ?latency, "finished a request");
+}
+
+/// Trace a retransmission of an upstream request.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn trace_retransmission(retransmissions: u32) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(retransmissions, "retransmitting a request");
+}
+
+/// Run an upstream request of the code to the peer, in the span that has the correlation id of the current scope with
+/// the `tracing` feature; the response (or the error) is traced with the latency.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) async fn request<F>(remote_addr: &SocketAddr, code: Code, future: F) -> F::Output
+where
+    F: Future<Output = Result<Packet, ClientError>>,
+{
+    #[cfg(feature = "tracing")]
+    {
+        let span = tracing::info_span!(
+            "radius.client.request",
+            correlation_id = current().map(tracing::field::display),
+            peer = %remote_addr,
+            code = code.string(),
+        );
+        let started_at = Instant::now();
+        let result = tracing::Instrument::instrument(future, span.clone()).await;
+        let latency = started_at.elapsed();
+        span.in_scope(|| match &result {
+            Ok(response) => tracing::debug!(
+                code = response.get_code().string(),
+                identifier = response.get_identifier(),
+                latency = ?latency,
+                "received a response"
+            ),
+            Err(e) => tracing::debug!(error = %e, latency = ?latency, "failed a request"),
+        });
+        result
+    }
+    #[cfg(not(feature = "tracing"))]
+    future.await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::correlation::{self, CorrelationId};
+
+    #[tokio::test]
+    async fn test_scope() {
+        assert_eq!(correlation::current(), None);
+        assert_eq!(correlation::log_suffix().to_string(), "");
+
+        let id = CorrelationId::new(0xbeef);
+        correlation::scope(id, async move {
+            assert_eq!(correlation::current(), Some(id));
+            assert_eq!(
+                correlation::log_suffix().to_string(),
+                " (correlation: 000000000000beef)"
+            );
+        })
+        .await;
+
+        let remote_addr = "127.0.0.1:1812".parse().unwrap();
+        correlation::serve(remote_addr, async {
+            let id = correlation::current().unwrap();
+            correlation::request(&remote_addr, Code::AccessRequest, async move {
+                assert_eq!(correlation::current(), Some(id));
+                Ok(Packet::new(Code::AccessAccept, b"secret"))
+            })
+            .await
+            .unwrap();
+        })
+        .await;
+    }
+}
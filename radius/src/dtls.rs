@@ -0,0 +1,186 @@
+//! RADIUS over DTLS (RFC 7360).
+//!
+//! RADIUS/DTLS carries each RADIUS packet in a DTLS record over UDP (on port 2083), and all of the packets have the fixed
+//! shared secret `radius/dtls` (`DTLS_SECRET`); the peers are authenticated by the certificates instead.
+//!
+//! `DtlsTransport` is a `Transport` (see `radius::transport`) on top of an established `DtlsConnection` of the DTLS
+//! library, so the client (`GenericClient::send_packet_over()`) and the server (`TransportServer` with
+//! `DtlsSecretProvider`) speak RADIUS/DTLS with the same API as the plain UDP. The handshake, the verification of
+//! the certificates and the demultiplexing of the sessions on the server socket are up to the DTLS library;
+//! give the verified identity of the peer (e.g. the subject of the certificate) to `DtlsTransport::new()`.
+//!
+//! A DTLS implementation of this crate (like `radius::radsec::RadSecConnector` of RadSec on rustls) is descoped, since
+//! rustls doesn't support DTLS; see the roadmap of the README.
+
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use async_trait::async_trait;
+
+use crate::server::{SecretProvider, SecretProviderError};
+use crate::transport::{Peer, Transport};
+
+/// The shared secret of all of the RADIUS/DTLS packets.
+pub const DTLS_SECRET: &[u8] = b"radius/dtls";
+/// The port of RADIUS/DTLS.
+pub const DEFAULT_DTLS_PORT: u16 = 2083;
+
+/// An established DTLS session with a peer, i.e. the application data records of the DTLS library.
+#[async_trait]
+pub trait DtlsConnection: Send + Sync {
+    /// Send a record of the application data.
+    async fn send(&self, data: &[u8]) -> io::Result<usize>;
+    /// Receive a record of the application data, and return the number of the bytes.
+    async fn recv(&self, buf: &mut [u8]) -> io::Result<usize>;
+}
+
+/// A RADIUS/DTLS session as a transport.
+pub struct DtlsTransport<C: DtlsConnection> {
+    connection: C,
+    peer: Peer,
+    local_addr: SocketAddr,
+}
+
+impl<C: DtlsConnection> DtlsTransport<C> {
+    /// A constructor for a transport of the session with the peer, which has been authenticated by the DTLS handshake.
+    pub fn new(connection: C, peer: Peer) -> Self {
+        let local_addr = match peer.get_addr() {
+            SocketAddr::V4(_) => SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0),
+            SocketAddr::V6(_) => SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0),
+        };
+        DtlsTransport {
+            connection,
+            peer,
+            local_addr,
+        }
+    }
+
+    /// Set the local address of the session (default: the unspecified address), e.g. for `Request::get_local_addr()`.
+    pub fn set_local_addr(&mut self, local_addr: SocketAddr) {
+        self.local_addr = local_addr;
+    }
+
+    /// Returns the peer of the session.
+    pub fn get_peer(&self) -> &Peer {
+        &self.peer
+    }
+
+    /// Returns the DTLS session.
+    pub fn get_connection(&self) -> &C {
+        &self.connection
+    }
+}
+
+#[async_trait]
+impl<C: DtlsConnection> Transport for DtlsTransport<C> {
+    async fn send_to(&self, datagram: &[u8], peer: &Peer) -> io::Result<()> {
+        if peer.get_addr() != self.peer.get_addr() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrNotAvailable,
+                format!("the session is not with {}", peer.get_addr()),
+            ));
+        }
+        self.connection.send(datagram).await?;
+        Ok(())
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, Peer)> {
+        let len = self.connection.recv(buf).await?;
+        Ok((len, self.peer.clone()))
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        Ok(self.local_addr)
+    }
+}
+
+/// The secret provider of the RADIUS/DTLS server, i.e. `DTLS_SECRET` for any peer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DtlsSecretProvider;
+
+impl SecretProvider for DtlsSecretProvider {
+    fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        Ok(DTLS_SECRET.to_vec())
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use std::io;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::dtls::{DtlsConnection, DtlsSecretProvider, DtlsTransport, DTLS_SECRET};
+    use crate::runtime::{GenericClient, TokioRuntime};
+    use crate::server::transport::{TransportRequestHandler, TransportServer};
+    use crate::transport::{channel, ChannelTransport, Peer, Transport};
+
+    /// A DTLS session without the encryption, on top of the in-memory channel.
+    struct PlainConnection(ChannelTransport, SocketAddr);
+
+    #[async_trait]
+    impl DtlsConnection for PlainConnection {
+        async fn send(&self, data: &[u8]) -> io::Result<usize> {
+            self.0.send_to(data, &Peer::new(self.1)).await?;
+            Ok(data.len())
+        }
+
+        async fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+            Ok(self.0.recv_from(buf).await?.0)
+        }
+    }
+
+    type ServerTransport = DtlsTransport<PlainConnection>;
+
+    struct AcceptingHandler {}
+
+    #[async_trait]
+    impl TransportRequestHandler<ServerTransport, (), io::Error> for AcceptingHandler {
+        async fn handle_radius_request(
+            &self,
+            transport: &ServerTransport,
+            peer: &Peer,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let response = req.get_packet().make_response_packet(Code::AccessAccept);
+            transport.send_to(&response.encode().unwrap(), peer).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let client_addr: SocketAddr = "192.0.2.1:50000".parse().unwrap();
+        let server_addr: SocketAddr = "192.0.2.2:2083".parse().unwrap();
+        let (client_channel, server_channel) = channel(client_addr, server_addr);
+
+        let server = TransportServer::new(
+            DtlsTransport::new(
+                PlainConnection(server_channel, client_addr),
+                Peer::with_identity(client_addr, "CN=nas.example.org"),
+            ),
+            AcceptingHandler {},
+            DtlsSecretProvider,
+        );
+        tokio::spawn(async move { server.run(std::future::pending::<()>()).await });
+
+        let client_transport = DtlsTransport::new(
+            PlainConnection(client_channel, server_addr),
+            Peer::new(server_addr),
+        );
+        let client = GenericClient::new(TokioRuntime, Some(Duration::from_secs(3)));
+        let response = client
+            .send_packet_over(
+                &client_transport,
+                &Peer::new(server_addr),
+                &Packet::new(Code::AccessRequest, DTLS_SECRET),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+    }
+}
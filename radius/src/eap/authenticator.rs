@@ -0,0 +1,477 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+
+use crate::core::code::Code;
+use crate::core::packet::Packet;
+use crate::core::{deterministic, random, rfc2865, rfc2869};
+use crate::eap::{
+    identity, EapCode, EapError, EapMethod, EapPacket, EapStep, EAP_TYPE_IDENTITY, EAP_TYPE_NAK,
+};
+
+/// The default lifetime of a conversation from the last Access-Challenge.
+pub const DEFAULT_CONVERSATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+const STATE_LENGTH: usize = 16;
+
+/// A conversation in progress, which is identified by the State of the Access-Challenge.
+struct Conversation {
+    identity: String,
+    method: usize,
+    /// the identifier of the outstanding EAP-Request
+    identifier: u8,
+    state: Vec<u8>,
+    expires_at: DateTime<Utc>,
+}
+
+/// The EAP authenticator (i.e. the server) that runs the conversations of the EAP methods.
+pub struct EapAuthenticator {
+    methods: Vec<Box<dyn EapMethod>>,
+    conversations: Mutex<HashMap<Vec<u8>, Conversation>>,
+    conversation_timeout: Duration,
+}
+
+impl Default for EapAuthenticator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EapAuthenticator {
+    /// A constructor for an authenticator without the methods.
+    pub fn new() -> Self {
+        EapAuthenticator {
+            methods: vec![],
+            conversations: Mutex::new(HashMap::new()),
+            conversation_timeout: DEFAULT_CONVERSATION_TIMEOUT,
+        }
+    }
+
+    /// Add a method; the first one is proposed to the peer, and the others are available by the Nak of the peer.
+    pub fn add_method<M: EapMethod>(&mut self, method: M) {
+        self.methods.push(Box::new(method));
+    }
+
+    /// Set the lifetime of a conversation from the last Access-Challenge (default: 60 seconds).
+    pub fn set_conversation_timeout(&mut self, conversation_timeout: Duration) {
+        self.conversation_timeout = conversation_timeout;
+    }
+
+    /// Returns the number of the conversations in progress.
+    pub fn get_conversations_count(&self) -> usize {
+        self.conversations.lock().unwrap().len()
+    }
+
+    /// Make the response to an Access-Request of EAP: an Access-Challenge of the next EAP-Request,
+    /// an Access-Accept of EAP-Success or an Access-Reject of EAP-Failure.
+    ///
+    /// EAP-Start (an empty EAP-Message, RFC 3579 section 2.1) is answered by EAP-Request/Identity.
+    /// The requests that fail here have to be discarded silently.
+    pub async fn handle(&self, request: &Packet) -> Result<Packet, EapError> {
+        let now = deterministic::now();
+        self.conversations
+            .lock()
+            .unwrap()
+            .retain(|_, conversation| conversation.expires_at > now);
+
+        let eap_message =
+            rfc2869::lookup_eap_message(request).ok_or(EapError::MissingEapMessageError())?;
+        if rfc2869::lookup_message_authenticator(request).is_none() {
+            // RFC 3579 section 3.2
+            return Err(EapError::UnexpectedPacketError(
+                "EAP-Message without Message-Authenticator".to_owned(),
+            ));
+        }
+        if eap_message.is_empty() {
+            let mut identifier = [0; 1];
+            random::rng().fill_bytes(&mut identifier);
+            let mut response = request.make_response_packet(Code::AccessChallenge);
+            response.add_eap_message(&identity::make_request(identifier[0]).encode());
+            return Ok(response);
+        }
+
+        let eap_response = EapPacket::decode(&eap_message)?;
+        if eap_response.get_code() != EapCode::Response {
+            return Err(EapError::UnexpectedPacketError(format!(
+                "EAP {:?} from the peer",
+                eap_response.get_code()
+            )));
+        }
+
+        if eap_response.get_type() == Some(EAP_TYPE_IDENTITY) {
+            let identity = identity::parse_response(&eap_response)?;
+            if self.methods.is_empty() {
+                return Ok(Self::finish(request, &eap_response, EapStep::Failure));
+            }
+            return self.start(request, &eap_response, identity, 0).await;
+        }
+
+        let key = rfc2865::lookup_state(request).ok_or_else(|| {
+            EapError::UnexpectedPacketError("EAP-Response without State".to_owned())
+        })?;
+        let mut conversation = self
+            .conversations
+            .lock()
+            .unwrap()
+            .remove(&key)
+            .ok_or_else(|| EapError::UnexpectedPacketError("unknown State".to_owned()))?;
+        if eap_response.get_identifier() != conversation.identifier {
+            let identifier = eap_response.get_identifier();
+            self.conversations.lock().unwrap().insert(key, conversation);
+            return Err(EapError::UnexpectedPacketError(format!(
+                "unexpected EAP identifier: {identifier}"
+            )));
+        }
+
+        let method = &self.methods[conversation.method];
+        match eap_response.get_type() {
+            Some(EAP_TYPE_NAK) => {
+                // the peer desires the other methods (RFC 3748 section 5.3.1)
+                let desired = eap_response.get_type_data();
+                match self.methods.iter().enumerate().position(|(i, method)| {
+                    i != conversation.method && desired.contains(&method.get_type())
+                }) {
+                    Some(i) => {
+                        self.start(request, &eap_response, conversation.identity, i)
+                            .await
+                    }
+                    None => Ok(Self::finish(request, &eap_response, EapStep::Failure)),
+                }
+            }
+            Some(typ) if typ == method.get_type() => {
+                let step = method
+                    .process(
+                        &conversation.identity,
+                        &mut conversation.state,
+                        &eap_response,
+                    )
+                    .await?;
+                self.respond(request, &eap_response, conversation, step)
+            }
+            _ => Ok(Self::finish(request, &eap_response, EapStep::Failure)),
+        }
+    }
+
+    async fn start(
+        &self,
+        request: &Packet,
+        eap_response: &EapPacket,
+        identity: String,
+        method: usize,
+    ) -> Result<Packet, EapError> {
+        let identifier = eap_response.get_identifier().wrapping_add(1);
+        let mut state = vec![];
+        let step = self.methods[method]
+            .start(&identity, identifier, &mut state)
+            .await?;
+        let conversation = Conversation {
+            identity,
+            method,
+            identifier,
+            state,
+            expires_at: deterministic::now(),
+        };
+        self.respond(request, eap_response, conversation, step)
+    }
+
+    fn respond(
+        &self,
+        request: &Packet,
+        eap_response: &EapPacket,
+        mut conversation: Conversation,
+        step: EapStep,
+    ) -> Result<Packet, EapError> {
+        let type_data = match step {
+            EapStep::Continue(type_data) => type_data,
+            EapStep::Success => {
+                let mut response = Self::finish(request, eap_response, EapStep::Success);
+                self.methods[conversation.method]
+                    .add_success_attributes(&conversation.state, &mut response)?;
+                return Ok(response);
+            }
+            step => return Ok(Self::finish(request, eap_response, step)),
+        };
+
+        conversation.identifier = eap_response.get_identifier().wrapping_add(1);
+        conversation.expires_at = deterministic::now()
+            + chrono::Duration::from_std(self.conversation_timeout)
+                .unwrap_or(chrono::Duration::MAX);
+        let eap_request = EapPacket::new_request(
+            conversation.identifier,
+            self.methods[conversation.method].get_type(),
+            &type_data,
+        );
+
+        let mut key = vec![0; STATE_LENGTH];
+        random::rng().fill_bytes(&mut key);
+        let mut response = request.make_response_packet(Code::AccessChallenge);
+        rfc2865::add_state(&mut response, &key);
+        response.add_eap_message(&eap_request.encode());
+        self.conversations.lock().unwrap().insert(key, conversation);
+        Ok(response)
+    }
+
+    fn finish(request: &Packet, eap_response: &EapPacket, step: EapStep) -> Packet {
+        let (code, eap_packet) = match step {
+            EapStep::Success => (
+                Code::AccessAccept,
+                EapPacket::success(eap_response.get_identifier()),
+            ),
+            _ => (
+                Code::AccessReject,
+                EapPacket::failure(eap_response.get_identifier()),
+            ),
+        };
+        let mut response = request.make_response_packet(code);
+        response.add_eap_message(&eap_packet.encode());
+        response
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod handler {
+    use std::io;
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+
+    use crate::core::request::Request;
+    use crate::eap::EapAuthenticator;
+    use crate::server::RequestHandler;
+
+    /// A `RequestHandler` that answers the Access-Requests of EAP by an `EapAuthenticator`.
+    pub struct EapRequestHandler {
+        authenticator: EapAuthenticator,
+    }
+
+    impl EapRequestHandler {
+        pub fn new(authenticator: EapAuthenticator) -> Self {
+            EapRequestHandler { authenticator }
+        }
+
+        pub fn get_authenticator(&self) -> &EapAuthenticator {
+            &self.authenticator
+        }
+    }
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for EapRequestHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            request: &Request,
+        ) -> Result<(), io::Error> {
+            let response = match self.authenticator.handle(request.get_packet()).await {
+                Ok(response) => response,
+                Err(e) => {
+                    info!(
+                        "EAP request from {} is discarded; {}",
+                        request.get_remote_addr(),
+                        e
+                    );
+                    return Ok(());
+                }
+            };
+            let encoded = response
+                .encode()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            conn.send_to(&encoded, request.get_remote_addr()).await?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub use handler::EapRequestHandler;
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use crate::core::avp::AVP;
+    use crate::core::code::Code;
+    use crate::core::mschap::{
+        generate_authenticator_response, generate_mppe_keys, generate_nt_response,
+    };
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2548, rfc2865, rfc2869};
+    use crate::eap::{
+        md5, EapAuthenticator, EapCode, EapCredentialProvider, EapError, EapPacket, Md5Method,
+        MsChapV2Method, EAP_TYPE_IDENTITY, EAP_TYPE_MD5_CHALLENGE, EAP_TYPE_MSCHAPV2, EAP_TYPE_NAK,
+    };
+
+    struct MyUsers {}
+
+    #[async_trait]
+    impl EapCredentialProvider for MyUsers {
+        async fn fetch_password(&self, identity: &str) -> Result<Option<String>, EapError> {
+            Ok((identity == "alice").then(|| "password".to_owned()))
+        }
+    }
+
+    fn make_request(eap_response: &EapPacket, state: Option<Vec<u8>>) -> Packet {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        if let Some(state) = state {
+            rfc2865::add_state(&mut request, &state);
+        }
+        request.add_eap_message(&eap_response.encode());
+        request
+    }
+
+    fn eap_request_of(response: &Packet) -> EapPacket {
+        EapPacket::decode(&response.get_eap_message()).unwrap()
+    }
+
+    fn make_authenticator() -> EapAuthenticator {
+        let mut authenticator = EapAuthenticator::new();
+        authenticator.add_method(Md5Method::new(MyUsers {}));
+        authenticator.add_method(MsChapV2Method::new(MyUsers {}));
+        authenticator
+    }
+
+    async fn md5_conversation(authenticator: &EapAuthenticator, identity: &str) -> Packet {
+        let mut start = Packet::new(Code::AccessRequest, b"secret");
+        // EAP-Start
+        start.add(AVP::from_bytes(rfc2869::EAP_MESSAGE_TYPE, b""));
+        start.add_message_authenticator();
+        let response = authenticator.handle(&start).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessChallenge);
+        let identity_request = eap_request_of(&response);
+        assert_eq!(identity_request.get_type(), Some(EAP_TYPE_IDENTITY));
+
+        let identity_response = EapPacket::new_response(
+            identity_request.get_identifier(),
+            EAP_TYPE_IDENTITY,
+            identity.as_bytes(),
+        );
+        let response = authenticator
+            .handle(&make_request(&identity_response, None))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessChallenge);
+        let md5_request = eap_request_of(&response);
+        assert_eq!(md5_request.get_type(), Some(EAP_TYPE_MD5_CHALLENGE));
+        let challenge = &md5_request.get_type_data()[1..];
+
+        let value = md5::compute_response(md5_request.get_identifier(), b"password", challenge);
+        let md5_response = EapPacket::new_response(
+            md5_request.get_identifier(),
+            EAP_TYPE_MD5_CHALLENGE,
+            &[&[16], &value[..]].concat(),
+        );
+        authenticator
+            .handle(&make_request(
+                &md5_response,
+                rfc2865::lookup_state(&response),
+            ))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_md5_conversation() {
+        let authenticator = make_authenticator();
+
+        let response = md5_conversation(&authenticator, "alice").await;
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(eap_request_of(&response).get_code(), EapCode::Success);
+        assert_eq!(authenticator.get_conversations_count(), 0);
+
+        let response = md5_conversation(&authenticator, "bob").await;
+        assert_eq!(response.get_code(), Code::AccessReject);
+        assert_eq!(eap_request_of(&response).get_code(), EapCode::Failure);
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        request.add(AVP::from_bytes(rfc2869::EAP_MESSAGE_TYPE, b""));
+        assert!(authenticator.handle(&request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mschapv2_conversation() {
+        let authenticator = make_authenticator();
+
+        let identity_response = EapPacket::new_response(1, EAP_TYPE_IDENTITY, b"alice");
+        let response = authenticator
+            .handle(&make_request(&identity_response, None))
+            .await
+            .unwrap();
+        let md5_request = eap_request_of(&response);
+
+        // Nak to MSCHAPv2
+        let nak = EapPacket::new_response(
+            md5_request.get_identifier(),
+            EAP_TYPE_NAK,
+            &[EAP_TYPE_MSCHAPV2],
+        );
+        let response = authenticator
+            .handle(&make_request(&nak, rfc2865::lookup_state(&response)))
+            .await
+            .unwrap();
+        let challenge_request = eap_request_of(&response);
+        assert_eq!(challenge_request.get_type(), Some(EAP_TYPE_MSCHAPV2));
+        let type_data = challenge_request.get_type_data();
+        let ms_chapv2_id = type_data[1];
+        let authenticator_challenge = type_data[5..21].to_vec();
+
+        let peer_challenge = [0x21; 16];
+        let nt_response = generate_nt_response(
+            &authenticator_challenge,
+            &peer_challenge,
+            "alice",
+            "password",
+        );
+        let value = [&peer_challenge[..], &[0; 8], &nt_response[..], &[0]].concat();
+        let body = [&[49], &value[..], b"alice"].concat();
+        let len = ((4 + body.len()) as u16).to_be_bytes();
+        let challenge_response = EapPacket::new_response(
+            challenge_request.get_identifier(),
+            EAP_TYPE_MSCHAPV2,
+            &[&[2, ms_chapv2_id, len[0], len[1]], &body[..]].concat(),
+        );
+        let response = authenticator
+            .handle(&make_request(
+                &challenge_response,
+                rfc2865::lookup_state(&response),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessChallenge);
+        let success_request = eap_request_of(&response);
+        let expected = generate_authenticator_response(
+            "password",
+            &nt_response,
+            &peer_challenge,
+            &authenticator_challenge,
+            "alice",
+        );
+        assert_eq!(success_request.get_type_data()[0], 3);
+        assert!(
+            String::from_utf8_lossy(&success_request.get_type_data()[4..]).starts_with(&expected)
+        );
+
+        let success_response =
+            EapPacket::new_response(success_request.get_identifier(), EAP_TYPE_MSCHAPV2, &[3]);
+        let response = authenticator
+            .handle(&make_request(
+                &success_response,
+                rfc2865::lookup_state(&response),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(
+            eap_request_of(&response),
+            EapPacket::success(success_request.get_identifier())
+        );
+        let (send_key, _) = generate_mppe_keys("password", &nt_response);
+        assert_eq!(
+            rfc2548::lookup_ms_mppe_send_key(&response, response.get_authenticator())
+                .unwrap()
+                .unwrap(),
+            send_key
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! EAP-Identity (RFC 3748 section 5.1).
+
+use crate::eap::{EapCode, EapError, EapPacket, EAP_TYPE_IDENTITY};
+
+/// Make an EAP-Request/Identity, which asks the peer for the identity.
+pub fn make_request(identifier: u8) -> EapPacket {
+    EapPacket::new_request(identifier, EAP_TYPE_IDENTITY, b"")
+}
+
+/// Returns the identity of an EAP-Response/Identity (e.g. `alice@example.com`).
+pub fn parse_response(response: &EapPacket) -> Result<String, EapError> {
+    if response.get_code() != EapCode::Response || response.get_type() != Some(EAP_TYPE_IDENTITY) {
+        return Err(EapError::UnexpectedPacketError(
+            "not an EAP-Response/Identity".to_owned(),
+        ));
+    }
+    String::from_utf8(response.get_type_data().to_vec())
+        .map_err(|e| EapError::DecodingError(format!("invalid identity; {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eap::identity::{make_request, parse_response};
+    use crate::eap::{EapPacket, EAP_TYPE_IDENTITY};
+
+    #[test]
+    fn test_identity() {
+        assert_eq!(make_request(3).get_type(), Some(EAP_TYPE_IDENTITY));
+        assert!(parse_response(&make_request(3)).is_err());
+        assert_eq!(
+            parse_response(&EapPacket::new_response(3, EAP_TYPE_IDENTITY, b"alice")).unwrap(),
+            "alice"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! EAP-MD5 (RFC 3748 section 5.4), i.e. CHAP (RFC 1994) in EAP.
+
+use async_trait::async_trait;
+use rand::RngCore;
+
+use crate::core::{constant_time, random};
+use crate::eap::{
+    EapCredentialProvider, EapError, EapMethod, EapPacket, EapStep, EapType, EAP_TYPE_MD5_CHALLENGE,
+};
+
+const CHALLENGE_LENGTH: usize = 16;
+
+/// EAP-MD5 with the cleartext passwords of the credential provider.
+pub struct Md5Method<P: EapCredentialProvider> {
+    credential_provider: P,
+}
+
+impl<P: EapCredentialProvider> Md5Method<P> {
+    pub fn new(credential_provider: P) -> Self {
+        Md5Method {
+            credential_provider,
+        }
+    }
+}
+
+/// Returns the Value of the EAP-MD5 Response, i.e. MD5 of the identifier, the password and the challenge.
+pub fn compute_response(identifier: u8, password: &[u8], challenge: &[u8]) -> [u8; 16] {
+    ::md5::compute([&[identifier], password, challenge].concat()).0
+}
+
+#[async_trait]
+impl<P: EapCredentialProvider> EapMethod for Md5Method<P> {
+    fn get_type(&self) -> EapType {
+        EAP_TYPE_MD5_CHALLENGE
+    }
+
+    async fn start(
+        &self,
+        _identity: &str,
+        _identifier: u8,
+        state: &mut Vec<u8>,
+    ) -> Result<EapStep, EapError> {
+        let mut challenge = [0; CHALLENGE_LENGTH];
+        random::rng().fill_bytes(&mut challenge);
+        *state = challenge.to_vec();
+        Ok(EapStep::Continue(
+            [&[CHALLENGE_LENGTH as u8], &challenge[..]].concat(),
+        ))
+    }
+
+    async fn process(
+        &self,
+        identity: &str,
+        state: &mut Vec<u8>,
+        response: &EapPacket,
+    ) -> Result<EapStep, EapError> {
+        let type_data = response.get_type_data();
+        let value = match type_data.split_first() {
+            Some((&value_size, rest)) if rest.len() >= value_size as usize => {
+                &rest[..value_size as usize]
+            }
+            _ => {
+                return Err(EapError::DecodingError(
+                    "invalid EAP-MD5 Value-Size".to_owned(),
+                ))
+            }
+        };
+
+        let password = match self.credential_provider.fetch_password(identity).await? {
+            Some(password) => password,
+            None => return Ok(EapStep::Failure),
+        };
+        let expected = compute_response(response.get_identifier(), password.as_bytes(), state);
+        match constant_time::eq(value, &expected) {
+            true => Ok(EapStep::Success),
+            false => Ok(EapStep::Failure),
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! EAP (RFC 3748) over RADIUS (RFC 3579).
+//!
+//! `EapPacket` is the framing of EAP (Code, Identifier, Length and Type), which RADIUS carries in the EAP-Message
+//! attributes (see `Packet::add_eap_message()`). An `EapMethod` is an authentication method of EAP on the server side,
+//! i.e. `Md5Method` (EAP-MD5) and `MsChapV2Method` (EAP-MSCHAPv2) with the passwords of an `EapCredentialProvider`,
+//! and `TlsMethod` (EAP-TLS) on rustls with the `eap-tls` feature; EAP-Identity is handled by `EapAuthenticator`
+//! itself.
+//!
+//! `EapAuthenticator` runs the multi-round conversations: it answers an Access-Request of an EAP-Response by
+//! an Access-Challenge of the next EAP-Request of the method (with the State of the conversation), and finally by
+//! an Access-Accept of EAP-Success or an Access-Reject of EAP-Failure. `EapRequestHandler` is a `RequestHandler`
+//! of `Server` by that.
+//!
+//! ```no_run
+//! use async_trait::async_trait;
+//!
+//! use radius::eap::{EapAuthenticator, EapCredentialProvider, EapError, EapRequestHandler, Md5Method};
+//! use radius::server::Server;
+//! # use std::net::SocketAddr;
+//! # use radius::server::{SecretProvider, SecretProviderError};
+//! # struct MySecretProvider {}
+//! # impl SecretProvider for MySecretProvider {
+//! #     fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+//! #         Ok(b"secret".to_vec())
+//! #     }
+//! # }
+//!
+//! struct MyUsers {}
+//!
+//! #[async_trait]
+//! impl EapCredentialProvider for MyUsers {
+//!     async fn fetch_password(&self, identity: &str) -> Result<Option<String>, EapError> {
+//!         Ok((identity == "alice").then(|| "password".to_owned()))
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mut authenticator = EapAuthenticator::new();
+//! authenticator.add_method(Md5Method::new(MyUsers {}));
+//! let mut server = Server::listen("0.0.0.0", 1812, EapRequestHandler::new(authenticator), MySecretProvider {})
+//!     .await
+//!     .unwrap();
+//! server.run(tokio::signal::ctrl_c()).await.unwrap();
+//! # }
+//! ```
+
+mod authenticator;
+pub mod identity;
+pub mod md5;
+pub mod mschapv2;
+#[cfg(feature = "eap-tls")]
+pub mod tls;
+
+#[cfg(feature = "tokio")]
+pub use authenticator::EapRequestHandler;
+pub use authenticator::{EapAuthenticator, DEFAULT_CONVERSATION_TIMEOUT};
+pub use md5::Md5Method;
+pub use mschapv2::MsChapV2Method;
+#[cfg(feature = "eap-tls")]
+pub use tls::TlsMethod;
+
+use std::convert::TryFrom;
+
+use async_trait::async_trait;
+use num_enum::TryFromPrimitive;
+use thiserror::Error;
+
+use crate::core::packet::Packet;
+use crate::error::ErrorKind;
+
+/// The length of the header of EAP (Code, Identifier and Length).
+pub const EAP_HEADER_LENGTH: usize = 4;
+
+/// The Type of an EAP method.
+pub type EapType = u8;
+
+pub const EAP_TYPE_IDENTITY: EapType = 1;
+pub const EAP_TYPE_NOTIFICATION: EapType = 2;
+pub const EAP_TYPE_NAK: EapType = 3;
+pub const EAP_TYPE_MD5_CHALLENGE: EapType = 4;
+pub const EAP_TYPE_TLS: EapType = 13;
+pub const EAP_TYPE_MSCHAPV2: EapType = 26;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, TryFromPrimitive)]
+#[repr(u8)]
+pub enum EapCode {
+    Request = 1,
+    Response = 2,
+    Success = 3,
+    Failure = 4,
+}
+
+#[derive(Error, Debug)]
+pub enum EapError {
+    /// An error that is raised when an EAP packet is malformed.
+    #[error("failed to decode an EAP packet; {0}")]
+    DecodingError(String),
+    /// An error that is raised when the Access-Request doesn't have EAP-Message.
+    #[error("the request doesn't have EAP-Message")]
+    MissingEapMessageError(),
+    /// An error that is raised when an EAP packet is not expected in the conversation (e.g. the unknown State).
+    #[error("unexpected EAP packet; {0}")]
+    UnexpectedPacketError(String),
+    /// An error that is raised when the attributes of the response can't be encoded (e.g. the MPPE keys without
+    /// the secret).
+    #[error("failed to encode the response; {0}")]
+    EncodingError(String),
+    /// An error that is raised when fetching the credential of the peer has been failed.
+    #[error("failed to fetch the credential; {0}")]
+    CredentialError(String),
+}
+
+impl EapError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            EapError::DecodingError(_)
+            | EapError::MissingEapMessageError()
+            | EapError::UnexpectedPacketError(_) => ErrorKind::Malformed,
+            EapError::EncodingError(_) => ErrorKind::Encoding,
+            EapError::CredentialError(_) => ErrorKind::Storage,
+        }
+    }
+}
+
+/// An EAP packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EapPacket {
+    code: EapCode,
+    identifier: u8,
+    /// the Type and the Type-Data of a Request or a Response
+    data: Vec<u8>,
+}
+
+impl EapPacket {
+    /// A constructor for an EAP-Request of the Type.
+    pub fn new_request(identifier: u8, typ: EapType, type_data: &[u8]) -> Self {
+        EapPacket {
+            code: EapCode::Request,
+            identifier,
+            data: [&[typ], type_data].concat(),
+        }
+    }
+
+    /// A constructor for an EAP-Response of the Type.
+    pub fn new_response(identifier: u8, typ: EapType, type_data: &[u8]) -> Self {
+        EapPacket {
+            code: EapCode::Response,
+            identifier,
+            data: [&[typ], type_data].concat(),
+        }
+    }
+
+    /// A constructor for an EAP-Success; the identifier is the one of the last Response.
+    pub fn success(identifier: u8) -> Self {
+        EapPacket {
+            code: EapCode::Success,
+            identifier,
+            data: vec![],
+        }
+    }
+
+    /// A constructor for an EAP-Failure; the identifier is the one of the last Response.
+    pub fn failure(identifier: u8) -> Self {
+        EapPacket {
+            code: EapCode::Failure,
+            identifier,
+            data: vec![],
+        }
+    }
+
+    pub fn get_code(&self) -> EapCode {
+        self.code
+    }
+
+    pub fn get_identifier(&self) -> u8 {
+        self.identifier
+    }
+
+    /// Returns the Type of a Request or a Response.
+    pub fn get_type(&self) -> Option<EapType> {
+        self.data.first().copied()
+    }
+
+    /// Returns the Type-Data of a Request or a Response.
+    pub fn get_type_data(&self) -> &[u8] {
+        self.data.get(1..).unwrap_or_default()
+    }
+
+    /// Decode an EAP packet (e.g. `Packet::get_eap_message()`); the bytes after the Length field are ignored.
+    pub fn decode(bs: &[u8]) -> Result<Self, EapError> {
+        if bs.len() < EAP_HEADER_LENGTH {
+            return Err(EapError::DecodingError(format!(
+                "insufficient EAP packet length: {}",
+                bs.len()
+            )));
+        }
+        let code = EapCode::try_from(bs[0])
+            .map_err(|_| EapError::DecodingError(format!("unknown EAP code: {}", bs[0])))?;
+        let len = u16::from_be_bytes([bs[2], bs[3]]) as usize;
+        let min_len = match code {
+            EapCode::Request | EapCode::Response => EAP_HEADER_LENGTH + 1,
+            EapCode::Success | EapCode::Failure => EAP_HEADER_LENGTH,
+        };
+        if len < min_len || len > bs.len() {
+            return Err(EapError::DecodingError(format!(
+                "invalid EAP packet length: {len}"
+            )));
+        }
+        Ok(EapPacket {
+            code,
+            identifier: bs[1],
+            data: bs[EAP_HEADER_LENGTH..len].to_vec(),
+        })
+    }
+
+    /// Encode the EAP packet into bytes, i.e. the value of the EAP-Message attributes.
+    pub fn encode(&self) -> Vec<u8> {
+        let len = (EAP_HEADER_LENGTH + self.data.len()) as u16;
+        [
+            &[self.code as u8, self.identifier],
+            &len.to_be_bytes()[..],
+            &self.data,
+        ]
+        .concat()
+    }
+}
+
+/// The outcome of a round of an `EapMethod`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EapStep {
+    /// Send the next EAP-Request of the method with the Type-Data.
+    Continue(Vec<u8>),
+    /// The peer has been authenticated; this is answered by EAP-Success.
+    Success,
+    /// The peer has failed the authentication; this is answered by EAP-Failure.
+    Failure,
+}
+
+/// An authentication method of EAP on the server side.
+///
+/// The state of a conversation is kept by `EapAuthenticator` as the opaque bytes of the method (e.g. the challenge),
+/// so a method itself is shared by all of the conversations.
+#[async_trait]
+pub trait EapMethod: 'static + Send + Sync {
+    /// Returns the Type of the method (e.g. `EAP_TYPE_MD5_CHALLENGE`).
+    fn get_type(&self) -> EapType;
+
+    /// Start a conversation with the peer of the identity; the identifier is the one of the first EAP-Request.
+    async fn start(
+        &self,
+        identity: &str,
+        identifier: u8,
+        state: &mut Vec<u8>,
+    ) -> Result<EapStep, EapError>;
+
+    /// Process an EAP-Response of the method in the conversation.
+    async fn process(
+        &self,
+        identity: &str,
+        state: &mut Vec<u8>,
+        response: &EapPacket,
+    ) -> Result<EapStep, EapError>;
+
+    /// Add the attributes of the method to the Access-Accept of EAP-Success (e.g. the MPPE keys); the default adds
+    /// nothing.
+    fn add_success_attributes(
+        &self,
+        _state: &[u8],
+        _response: &mut Packet,
+    ) -> Result<(), EapError> {
+        Ok(())
+    }
+}
+
+/// A source of the credentials of the peers for the EAP methods.
+#[async_trait]
+pub trait EapCredentialProvider: 'static + Send + Sync {
+    /// Returns the cleartext password of the identity, or `None` if the identity is unknown.
+    async fn fetch_password(&self, identity: &str) -> Result<Option<String>, EapError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eap::{EapCode, EapPacket, EAP_TYPE_IDENTITY};
+
+    #[test]
+    fn test_eap_packet() {
+        let request = EapPacket::new_request(1, EAP_TYPE_IDENTITY, b"");
+        assert_eq!(request.encode(), vec![0x01, 0x01, 0x00, 0x05, 0x01]);
+
+        let response = EapPacket::decode(&[
+            0x02, 0x01, 0x00, 0x0a, 0x01, b'a', b'l', b'i', b'c', b'e', 0xff,
+        ])
+        .unwrap();
+        assert_eq!(response.get_code(), EapCode::Response);
+        assert_eq!(response.get_type(), Some(EAP_TYPE_IDENTITY));
+        assert_eq!(response.get_type_data(), b"alice");
+
+        let success = EapPacket::decode(&EapPacket::success(2).encode()).unwrap();
+        assert_eq!(success.get_type(), None);
+        assert!(success.get_type_data().is_empty());
+
+        assert!(EapPacket::decode(&[0x02, 0x01, 0x00]).is_err());
+        assert!(EapPacket::decode(&[0x05, 0x01, 0x00, 0x04]).is_err());
+        assert!(EapPacket::decode(&[0x02, 0x01, 0x00, 0x04]).is_err());
+        assert!(EapPacket::decode(&[0x02, 0x01, 0x00, 0x08, 0x01]).is_err());
+    }
+}
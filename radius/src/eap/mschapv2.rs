@@ -0,0 +1,181 @@
+//! EAP-MSCHAPv2 (draft-kamath-pppext-eap-mschapv2), i.e. MS-CHAPv2 (RFC 2759) in EAP.
+//!
+//! The conversation is a Challenge, a Success-Request (with the authenticator response of RFC 2759) or
+//! a Failure-Request (`E=691`), and the acknowledgement of the peer. The Access-Accept carries the MPPE keys
+//! (RFC 3079) in MS-MPPE-Send-Key and MS-MPPE-Recv-Key.
+
+use async_trait::async_trait;
+use rand::RngCore;
+
+use crate::core::mschap::{
+    generate_authenticator_response, generate_mppe_keys, generate_nt_response, to_hex,
+};
+use crate::core::packet::Packet;
+use crate::core::{constant_time, random, rfc2548};
+use crate::eap::{
+    EapCredentialProvider, EapError, EapMethod, EapPacket, EapStep, EapType, EAP_TYPE_MSCHAPV2,
+};
+
+const OPCODE_CHALLENGE: u8 = 1;
+const OPCODE_RESPONSE: u8 = 2;
+const OPCODE_SUCCESS: u8 = 3;
+const OPCODE_FAILURE: u8 = 4;
+
+const CHALLENGE_LENGTH: usize = 16;
+const RESPONSE_LENGTH: usize = 49;
+
+/// The phases of the conversation, the first byte of the state; the state is the phase, the MS-CHAPv2-ID and
+/// the challenge, and the MPPE keys of the phase of the success.
+const PHASE_CHALLENGE: u8 = 0;
+const PHASE_SUCCESS: u8 = 1;
+const PHASE_FAILURE: u8 = 2;
+
+/// EAP-MSCHAPv2 with the cleartext passwords of the credential provider.
+pub struct MsChapV2Method<P: EapCredentialProvider> {
+    credential_provider: P,
+    server_name: String,
+}
+
+impl<P: EapCredentialProvider> MsChapV2Method<P> {
+    pub fn new(credential_provider: P) -> Self {
+        MsChapV2Method {
+            credential_provider,
+            server_name: "radius".to_owned(),
+        }
+    }
+
+    /// Set the Name of the Challenge (default: `radius`).
+    pub fn set_server_name(&mut self, server_name: &str) {
+        self.server_name = server_name.to_owned();
+    }
+}
+
+/// Make the Type-Data of an MS-CHAPv2 packet of the server, whose MS-Length covers from the OpCode to the end.
+fn make_type_data(opcode: u8, ms_chapv2_id: u8, body: &[u8]) -> Vec<u8> {
+    let len = (4 + body.len()) as u16;
+    [&[opcode, ms_chapv2_id], &len.to_be_bytes()[..], body].concat()
+}
+
+#[async_trait]
+impl<P: EapCredentialProvider> EapMethod for MsChapV2Method<P> {
+    fn get_type(&self) -> EapType {
+        EAP_TYPE_MSCHAPV2
+    }
+
+    async fn start(
+        &self,
+        _identity: &str,
+        identifier: u8,
+        state: &mut Vec<u8>,
+    ) -> Result<EapStep, EapError> {
+        let mut challenge = [0; CHALLENGE_LENGTH];
+        random::rng().fill_bytes(&mut challenge);
+        *state = [&[PHASE_CHALLENGE, identifier], &challenge[..]].concat();
+
+        let body = [
+            &[CHALLENGE_LENGTH as u8],
+            &challenge[..],
+            self.server_name.as_bytes(),
+        ]
+        .concat();
+        Ok(EapStep::Continue(make_type_data(
+            OPCODE_CHALLENGE,
+            identifier,
+            &body,
+        )))
+    }
+
+    async fn process(
+        &self,
+        identity: &str,
+        state: &mut Vec<u8>,
+        response: &EapPacket,
+    ) -> Result<EapStep, EapError> {
+        let type_data = response.get_type_data();
+        let opcode = type_data.first().copied();
+        match state[0] {
+            PHASE_SUCCESS if opcode == Some(OPCODE_SUCCESS) => return Ok(EapStep::Success),
+            PHASE_SUCCESS | PHASE_FAILURE => return Ok(EapStep::Failure),
+            _ => {}
+        }
+
+        let ms_chapv2_id = state[1];
+        if opcode != Some(OPCODE_RESPONSE)
+            || type_data.len() < 5 + RESPONSE_LENGTH
+            || type_data[4] as usize != RESPONSE_LENGTH
+        {
+            return Err(EapError::DecodingError(
+                "invalid EAP-MSCHAPv2 Response".to_owned(),
+            ));
+        }
+        if type_data[1] != ms_chapv2_id {
+            return Err(EapError::UnexpectedPacketError(format!(
+                "unexpected MS-CHAPv2-ID: {}",
+                type_data[1]
+            )));
+        }
+        let value = &type_data[5..5 + RESPONSE_LENGTH];
+        let peer_challenge = &value[..16];
+        let nt_response = &value[24..48];
+        let name = String::from_utf8_lossy(&type_data[5 + RESPONSE_LENGTH..]).into_owned();
+        // the domain is not a part of the user name of the hash (RFC 2759 section 4)
+        let user_name = name.rsplit('\\').next().unwrap_or_default();
+        let authenticator_challenge = state[2..].to_vec();
+
+        let password = self.credential_provider.fetch_password(identity).await?;
+        let authenticator_response = password.and_then(|password| {
+            let expected = generate_nt_response(
+                &authenticator_challenge,
+                peer_challenge,
+                user_name,
+                &password,
+            );
+            constant_time::eq(&expected, nt_response).then(|| {
+                let authenticator_response = generate_authenticator_response(
+                    &password,
+                    nt_response,
+                    peer_challenge,
+                    &authenticator_challenge,
+                    user_name,
+                );
+                (
+                    authenticator_response,
+                    generate_mppe_keys(&password, nt_response),
+                )
+            })
+        });
+
+        match authenticator_response {
+            Some((authenticator_response, (send_key, recv_key))) => {
+                state[0] = PHASE_SUCCESS;
+                state.extend(send_key);
+                state.extend(recv_key);
+                let message = format!("{authenticator_response} M=success");
+                Ok(EapStep::Continue(make_type_data(
+                    OPCODE_SUCCESS,
+                    ms_chapv2_id,
+                    message.as_bytes(),
+                )))
+            }
+            None => {
+                state[0] = PHASE_FAILURE;
+                let message = format!(
+                    "E=691 R=0 C={} V=3 M=Authentication failed",
+                    to_hex(&authenticator_challenge)
+                );
+                Ok(EapStep::Continue(make_type_data(
+                    OPCODE_FAILURE,
+                    ms_chapv2_id,
+                    message.as_bytes(),
+                )))
+            }
+        }
+    }
+
+    fn add_success_attributes(&self, state: &[u8], response: &mut Packet) -> Result<(), EapError> {
+        let keys = &state[2 + CHALLENGE_LENGTH..];
+        rfc2548::add_ms_mppe_send_key(response, &keys[..16])
+            .and_then(|_| rfc2548::add_ms_mppe_recv_key(response, &keys[16..]))
+            .map_err(|e| EapError::EncodingError(e.to_string()))
+    }
+}
@@ -0,0 +1,510 @@
+//! EAP-TLS (RFC 5216, and RFC 9190 of TLS 1.3) on rustls.
+//!
+//! The TLS records are carried in the Type-Data of EAP-TLS, which are fragmented by the L (Length included) and
+//! M (More fragments) flags, and each fragment is acknowledged by an empty message of the other side. The peer is
+//! authenticated by its certificate, i.e. by the client certificate verifier of the `ServerConfig` (e.g.
+//! `WebPkiClientVerifier`); the Access-Accept carries the MSK (RFC 5216 section 2.3) in MS-MPPE-Recv-Key and
+//! MS-MPPE-Send-Key.
+//!
+//! A TLS session doesn't fit in the state of a conversation, so the method keeps the sessions in progress itself,
+//! by the random handles that are the states.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use rustls::{ProtocolVersion, ServerConfig, ServerConnection};
+
+use crate::core::packet::Packet;
+use crate::core::{deterministic, random, rfc2548};
+use crate::eap::{
+    EapError, EapMethod, EapPacket, EapStep, EapType, DEFAULT_CONVERSATION_TIMEOUT, EAP_TYPE_TLS,
+};
+
+const FLAG_LENGTH_INCLUDED: u8 = 0x80;
+const FLAG_MORE_FRAGMENTS: u8 = 0x40;
+const FLAG_START: u8 = 0x20;
+
+/// The default maximum length of the TLS data in an EAP-TLS Request.
+pub const DEFAULT_FRAGMENT_SIZE: usize = 1024;
+
+/// The maximum length of a TLS message of the peer, i.e. of its fragments in total.
+const MAX_MESSAGE_LENGTH: usize = 64 * 1024;
+
+const HANDLE_LENGTH: usize = 16;
+/// The length of the Key_Material of RFC 5216 (the MSK and the EMSK), and of the MSK.
+const KEY_MATERIAL_LENGTH: usize = 128;
+const MSK_LENGTH: usize = 64;
+
+/// The phases of the conversation, the first byte of the state; the state is the phase and the handle of
+/// the session, or the MSK of the phase of the success.
+const PHASE_HANDSHAKE: u8 = 0;
+const PHASE_SUCCESS: u8 = 1;
+
+/// A TLS session in progress.
+struct Session {
+    connection: ServerConnection,
+    /// the fragments of the TLS message of the peer so far
+    incoming: Vec<u8>,
+    /// the TLS message to the peer, and the length of it that has been sent
+    outgoing: Vec<u8>,
+    sent: usize,
+    /// whether the handshake has failed (the alert may be still being sent)
+    failed: bool,
+    expires_at: DateTime<Utc>,
+}
+
+/// EAP-TLS with the certificates and the client certificate verifier of a rustls `ServerConfig`.
+pub struct TlsMethod {
+    config: Arc<ServerConfig>,
+    sessions: Mutex<HashMap<Vec<u8>, Session>>,
+    session_timeout: Duration,
+    fragment_size: usize,
+}
+
+impl TlsMethod {
+    pub fn new(config: Arc<ServerConfig>) -> Self {
+        TlsMethod {
+            config,
+            sessions: Mutex::new(HashMap::new()),
+            session_timeout: DEFAULT_CONVERSATION_TIMEOUT,
+            fragment_size: DEFAULT_FRAGMENT_SIZE,
+        }
+    }
+
+    /// Set the lifetime of a TLS session from the last EAP-TLS Request (default: 60 seconds), which should be
+    /// the conversation timeout of the `EapAuthenticator`.
+    pub fn set_session_timeout(&mut self, session_timeout: Duration) {
+        self.session_timeout = session_timeout;
+    }
+
+    /// Set the maximum length of the TLS data in an EAP-TLS Request (default: `DEFAULT_FRAGMENT_SIZE`), which
+    /// has to fit in the Framed-MTU of the NAS.
+    pub fn set_fragment_size(&mut self, fragment_size: usize) {
+        self.fragment_size = fragment_size.max(1);
+    }
+
+    /// Returns the number of the TLS sessions in progress.
+    pub fn get_sessions_count(&self) -> usize {
+        self.sessions.lock().unwrap().len()
+    }
+
+    fn expires_at(&self) -> DateTime<Utc> {
+        deterministic::now()
+            + chrono::Duration::from_std(self.session_timeout).unwrap_or(chrono::Duration::MAX)
+    }
+
+    /// Process the Type-Data of an EAP-TLS Response; returns the Type-Data of the next Request, or `None` at the end
+    /// of the handshake.
+    fn advance(
+        &self,
+        session: &mut Session,
+        type_data: &[u8],
+    ) -> Result<Option<Vec<u8>>, EapError> {
+        let flags = *type_data
+            .first()
+            .ok_or_else(|| EapError::DecodingError("empty EAP-TLS Response".to_owned()))?;
+        let data = if flags & FLAG_LENGTH_INCLUDED != 0 {
+            type_data.get(5..).ok_or_else(|| {
+                EapError::DecodingError("insufficient EAP-TLS Message Length".to_owned())
+            })?
+        } else {
+            &type_data[1..]
+        };
+
+        if data.is_empty() && flags & FLAG_MORE_FRAGMENTS == 0 {
+            // the acknowledgement of a fragment
+            if session.sent < session.outgoing.len() {
+                return Ok(Some(self.next_fragment(session)));
+            }
+            if session.connection.is_handshaking() {
+                session.failed = true;
+            }
+            return Ok(None);
+        }
+        if session.sent < session.outgoing.len() {
+            return Err(EapError::UnexpectedPacketError(
+                "EAP-TLS data instead of the acknowledgement".to_owned(),
+            ));
+        }
+
+        if session.incoming.len() + data.len() > MAX_MESSAGE_LENGTH {
+            return Err(EapError::DecodingError(
+                "too long EAP-TLS message".to_owned(),
+            ));
+        }
+        session.incoming.extend_from_slice(data);
+        if flags & FLAG_MORE_FRAGMENTS != 0 {
+            return Ok(Some(vec![0]));
+        }
+
+        let incoming = std::mem::take(&mut session.incoming);
+        let mut rd = &incoming[..];
+        while !rd.is_empty() {
+            let result = session
+                .connection
+                .read_tls(&mut rd)
+                .map_err(|e| e.to_string())
+                .and_then(|_| {
+                    session
+                        .connection
+                        .process_new_packets()
+                        .map_err(|e| e.to_string())
+                });
+            if let Err(e) = result {
+                info!("EAP-TLS handshake has failed; {}", e);
+                session.failed = true;
+                break;
+            }
+        }
+        if !session.failed
+            && !session.connection.is_handshaking()
+            && session.connection.protocol_version() == Some(ProtocolVersion::TLSv1_3)
+        {
+            // the protected success indication (RFC 9190 section 2.5)
+            session
+                .connection
+                .writer()
+                .write_all(&[0])
+                .map_err(|e| EapError::EncodingError(e.to_string()))?;
+        }
+
+        session.outgoing.clear();
+        session.sent = 0;
+        while session.connection.wants_write() {
+            session
+                .connection
+                .write_tls(&mut session.outgoing)
+                .map_err(|e| EapError::EncodingError(e.to_string()))?;
+        }
+        if session.outgoing.is_empty() {
+            if session.connection.is_handshaking() {
+                session.failed = true;
+            }
+            return Ok(None);
+        }
+        Ok(Some(self.next_fragment(session)))
+    }
+
+    /// Returns the Type-Data of the next fragment of the TLS message to the peer.
+    fn next_fragment(&self, session: &mut Session) -> Vec<u8> {
+        let remaining = &session.outgoing[session.sent..];
+        let len = remaining.len().min(self.fragment_size);
+        let mut type_data = if len == remaining.len() {
+            vec![0]
+        } else if session.sent == 0 {
+            let total = (session.outgoing.len() as u32).to_be_bytes();
+            [&[FLAG_LENGTH_INCLUDED | FLAG_MORE_FRAGMENTS], &total[..]].concat()
+        } else {
+            vec![FLAG_MORE_FRAGMENTS]
+        };
+        type_data.extend_from_slice(&remaining[..len]);
+        session.sent += len;
+        type_data
+    }
+}
+
+/// Returns the MSK of the TLS session (RFC 5216 section 2.3, and RFC 9190 section 2.3 of TLS 1.3).
+fn export_msk(connection: &ServerConnection) -> Result<Vec<u8>, EapError> {
+    let key_material = match connection.protocol_version() {
+        Some(ProtocolVersion::TLSv1_3) => connection.export_keying_material(
+            [0; KEY_MATERIAL_LENGTH],
+            b"EXPORTER_EAP_TLS_Key_Material",
+            Some(&[EAP_TYPE_TLS]),
+        ),
+        _ => connection.export_keying_material(
+            [0; KEY_MATERIAL_LENGTH],
+            b"client EAP encryption",
+            None,
+        ),
+    }
+    .map_err(|e| EapError::EncodingError(e.to_string()))?;
+    Ok(key_material[..MSK_LENGTH].to_vec())
+}
+
+#[async_trait]
+impl EapMethod for TlsMethod {
+    fn get_type(&self) -> EapType {
+        EAP_TYPE_TLS
+    }
+
+    async fn start(
+        &self,
+        _identity: &str,
+        _identifier: u8,
+        state: &mut Vec<u8>,
+    ) -> Result<EapStep, EapError> {
+        let connection = ServerConnection::new(Arc::clone(&self.config))
+            .map_err(|e| EapError::EncodingError(e.to_string()))?;
+        let mut handle = vec![0; HANDLE_LENGTH];
+        random::rng().fill_bytes(&mut handle);
+
+        let now = deterministic::now();
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions.insert(
+            handle.clone(),
+            Session {
+                connection,
+                incoming: vec![],
+                outgoing: vec![],
+                sent: 0,
+                failed: false,
+                expires_at: self.expires_at(),
+            },
+        );
+        *state = [&[PHASE_HANDSHAKE], &handle[..]].concat();
+        Ok(EapStep::Continue(vec![FLAG_START]))
+    }
+
+    async fn process(
+        &self,
+        _identity: &str,
+        state: &mut Vec<u8>,
+        response: &EapPacket,
+    ) -> Result<EapStep, EapError> {
+        if state[0] != PHASE_HANDSHAKE {
+            return Ok(EapStep::Failure);
+        }
+        let handle = state[1..].to_vec();
+        let mut sessions = self.sessions.lock().unwrap();
+        let mut session = sessions
+            .remove(&handle)
+            .ok_or_else(|| EapError::UnexpectedPacketError("unknown EAP-TLS session".to_owned()))?;
+        match self.advance(&mut session, response.get_type_data())? {
+            Some(type_data) => {
+                session.expires_at = self.expires_at();
+                sessions.insert(handle, session);
+                Ok(EapStep::Continue(type_data))
+            }
+            None if session.failed => Ok(EapStep::Failure),
+            None => {
+                let msk = export_msk(&session.connection)?;
+                *state = [&[PHASE_SUCCESS], &msk[..]].concat();
+                Ok(EapStep::Success)
+            }
+        }
+    }
+
+    fn add_success_attributes(&self, state: &[u8], response: &mut Packet) -> Result<(), EapError> {
+        let msk = &state[1..];
+        rfc2548::add_ms_mppe_recv_key(response, &msk[..32])
+            .and_then(|_| rfc2548::add_ms_mppe_send_key(response, &msk[32..]))
+            .map_err(|e| EapError::EncodingError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, KeyPair};
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName};
+    use rustls::server::WebPkiClientVerifier;
+    use rustls::{
+        ClientConfig, ClientConnection, ProtocolVersion, RootCertStore, ServerConfig,
+        SupportedProtocolVersion,
+    };
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2548, rfc2865};
+    use crate::eap::{EapAuthenticator, EapPacket, TlsMethod, EAP_TYPE_IDENTITY, EAP_TYPE_TLS};
+
+    const FRAGMENT_SIZE: usize = 300;
+
+    /// A peer of EAP-TLS on a rustls client, which fragments its TLS messages as well.
+    struct TlsPeer {
+        connection: ClientConnection,
+        incoming: Vec<u8>,
+        outgoing: Vec<u8>,
+        sent: usize,
+    }
+
+    impl TlsPeer {
+        /// Returns the Type-Data of the EAP-TLS Response to the Type-Data of an EAP-TLS Request.
+        fn respond(&mut self, type_data: &[u8]) -> Vec<u8> {
+            let flags = type_data[0];
+            let data = if flags & 0x80 != 0 {
+                &type_data[5..]
+            } else {
+                &type_data[1..]
+            };
+            if flags & 0x20 == 0 {
+                if data.is_empty() && self.sent < self.outgoing.len() {
+                    return self.next_fragment();
+                }
+                self.incoming.extend_from_slice(data);
+                if flags & 0x40 != 0 {
+                    return vec![0];
+                }
+                let incoming = std::mem::take(&mut self.incoming);
+                let mut rd = &incoming[..];
+                while !rd.is_empty() {
+                    self.connection.read_tls(&mut rd).unwrap();
+                    if self.connection.process_new_packets().is_err() {
+                        break;
+                    }
+                }
+            }
+
+            self.outgoing.clear();
+            self.sent = 0;
+            while self.connection.wants_write() {
+                self.connection.write_tls(&mut self.outgoing).unwrap();
+            }
+            if self.outgoing.is_empty() {
+                vec![0]
+            } else {
+                self.next_fragment()
+            }
+        }
+
+        fn next_fragment(&mut self) -> Vec<u8> {
+            let remaining = &self.outgoing[self.sent..];
+            let len = remaining.len().min(FRAGMENT_SIZE);
+            let flags = if len == remaining.len() { 0 } else { 0x40 };
+            self.sent += len;
+            [&[flags], &remaining[..len]].concat()
+        }
+    }
+
+    fn issue(
+        name: &str,
+        ca: &rcgen::Certificate,
+        ca_key: &KeyPair,
+    ) -> (CertificateDer<'static>, PrivateKeyDer<'static>) {
+        let mut params = CertificateParams::new(vec![name.to_owned()]).unwrap();
+        params.distinguished_name = rcgen::DistinguishedName::new();
+        params.distinguished_name.push(DnType::CommonName, name);
+        let key = KeyPair::generate().unwrap();
+        let certificate = params.signed_by(&key, ca, ca_key).unwrap();
+        (
+            certificate.der().clone(),
+            PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key.serialize_der())),
+        )
+    }
+
+    /// Runs a conversation of EAP-TLS; returns the final response and the MSK of the peer.
+    async fn conversation(
+        version: &'static SupportedProtocolVersion,
+        with_client_certificate: bool,
+    ) -> (Packet, Option<Vec<u8>>) {
+        let mut params = CertificateParams::new(Vec::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "radius test CA");
+        let ca_key = KeyPair::generate().unwrap();
+        let ca = params.self_signed(&ca_key).unwrap();
+        let mut roots = RootCertStore::empty();
+        roots.add(ca.der().clone()).unwrap();
+        let roots = Arc::new(roots);
+
+        let (server_certificate, server_key) = issue("radius.example.org", &ca, &ca_key);
+        let server_config = ServerConfig::builder_with_protocol_versions(&[version])
+            .with_client_cert_verifier(
+                WebPkiClientVerifier::builder(roots.clone())
+                    .build()
+                    .unwrap(),
+            )
+            .with_single_cert(vec![server_certificate], server_key)
+            .unwrap();
+        let mut method = TlsMethod::new(Arc::new(server_config));
+        method.set_fragment_size(FRAGMENT_SIZE);
+        let mut authenticator = EapAuthenticator::new();
+        authenticator.add_method(method);
+
+        let client_config =
+            ClientConfig::builder_with_protocol_versions(&[version]).with_root_certificates(roots);
+        let client_config = if with_client_certificate {
+            let (client_certificate, client_key) = issue("alice", &ca, &ca_key);
+            client_config
+                .with_client_auth_cert(vec![client_certificate], client_key)
+                .unwrap()
+        } else {
+            client_config.with_no_client_auth()
+        };
+        let mut peer = TlsPeer {
+            connection: ClientConnection::new(
+                Arc::new(client_config),
+                ServerName::try_from("radius.example.org").unwrap(),
+            )
+            .unwrap(),
+            incoming: vec![],
+            outgoing: vec![],
+            sent: 0,
+        };
+
+        let mut eap_response = EapPacket::new_response(1, EAP_TYPE_IDENTITY, b"alice");
+        let mut state: Option<Vec<u8>> = None;
+        let mut fragmented = false;
+        for _ in 0..32 {
+            let mut request = Packet::new(Code::AccessRequest, b"secret");
+            if let Some(state) = &state {
+                rfc2865::add_state(&mut request, state);
+            }
+            request.add_eap_message(&eap_response.encode());
+            request.add_message_authenticator();
+            let response = authenticator.handle(&request).await.unwrap();
+            if response.get_code() != Code::AccessChallenge {
+                let msk = match peer.connection.protocol_version() {
+                    Some(ProtocolVersion::TLSv1_3) => peer.connection.export_keying_material(
+                        [0; 128],
+                        b"EXPORTER_EAP_TLS_Key_Material",
+                        Some(&[EAP_TYPE_TLS]),
+                    ),
+                    _ => peer.connection.export_keying_material(
+                        [0; 128],
+                        b"client EAP encryption",
+                        None,
+                    ),
+                }
+                .ok()
+                .map(|msk| msk[..64].to_vec());
+                assert!(fragmented);
+                return (response, msk);
+            }
+
+            let eap_request = EapPacket::decode(&response.get_eap_message()).unwrap();
+            assert_eq!(eap_request.get_type(), Some(EAP_TYPE_TLS));
+            assert!(eap_request.get_type_data().len() <= 5 + FRAGMENT_SIZE);
+            fragmented |= eap_request.get_type_data()[0] & 0x40 != 0;
+            eap_response = EapPacket::new_response(
+                eap_request.get_identifier(),
+                EAP_TYPE_TLS,
+                &peer.respond(eap_request.get_type_data()),
+            );
+            state = rfc2865::lookup_state(&response);
+        }
+        panic!("the conversation has not been finished");
+    }
+
+    #[tokio::test]
+    async fn test_conversation() {
+        for version in [&rustls::version::TLS12, &rustls::version::TLS13] {
+            let (response, msk) = conversation(version, true).await;
+            assert_eq!(response.get_code(), Code::AccessAccept);
+            let msk = msk.unwrap();
+            assert_eq!(
+                rfc2548::lookup_ms_mppe_recv_key(&response, response.get_authenticator())
+                    .unwrap()
+                    .unwrap(),
+                msk[..32]
+            );
+            assert_eq!(
+                rfc2548::lookup_ms_mppe_send_key(&response, response.get_authenticator())
+                    .unwrap()
+                    .unwrap(),
+                msk[32..]
+            );
+
+            let (response, _) = conversation(version, false).await;
+            assert_eq!(response.get_code(), Code::AccessReject);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Machine-readable classification of the errors of the crate.
+//!
+//! Every error type of the client and the server paths has `get_kind()`, which returns an `ErrorKind`; branch on that
+//! (or on its stable `code()`) instead of matching the error messages, and ask `get_disposition()` whether the operation
+//! is worth retrying.
+//!
+//! ```
+//! # #[cfg(feature = "tokio")]
+//! # fn main() {
+//! use std::time::Duration;
+//!
+//! use radius::client::{ClientError, TimeoutDetails};
+//! use radius::error::{Disposition, ErrorKind};
+//!
+//! let e = ClientError::SocketTimeoutError(TimeoutDetails::new(Duration::from_secs(3), 1, Duration::from_secs(3)));
+//! assert_eq!(e.get_kind(), ErrorKind::Timeout);
+//! assert_eq!(e.get_kind().code(), "timeout");
+//! assert_eq!(e.get_kind().get_disposition(), Disposition::Retry);
+//! # }
+//! # #[cfg(not(feature = "tokio"))]
+//! # fn main() {}
+//! ```
+
+use core::fmt;
+
+/// The kind of an error; the set may grow, but the existing kinds and their codes don't change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// No response (or no connection) in time.
+    Timeout,
+    /// A failure of the socket or the network, e.g. binding, sending or receiving.
+    Io,
+    /// The packet or the attribute couldn't be encoded, e.g. a too long value.
+    Encoding,
+    /// The received bytes are not a valid packet or attribute.
+    Malformed,
+    /// The authenticator or the Event-Timestamp of the packet is not valid, e.g. by a wrong shared secret.
+    Authenticity,
+    /// The peer has responded, but not as expected (e.g. a NAK).
+    UnexpectedResponse,
+    /// The configuration is wrong, e.g. an unknown client or an empty secret.
+    Configuration,
+    /// A failure of the storage, e.g. the spool file or the session store.
+    Storage,
+    /// The other errors.
+    Other,
+}
+
+/// What an application may do with an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Disposition {
+    /// The error can be transient; the same operation may succeed later.
+    Retry,
+    /// Retrying the same operation fails again.
+    Fail,
+    /// Retrying the same operation fails again, and the operator has to fix something (e.g. the secrets).
+    Alert,
+}
+
+impl ErrorKind {
+    /// Returns the stable code of the kind (e.g. `timeout`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::Io => "io",
+            ErrorKind::Encoding => "encoding",
+            ErrorKind::Malformed => "malformed",
+            ErrorKind::Authenticity => "authenticity",
+            ErrorKind::UnexpectedResponse => "unexpected_response",
+            ErrorKind::Configuration => "configuration",
+            ErrorKind::Storage => "storage",
+            ErrorKind::Other => "other",
+        }
+    }
+
+    /// Returns what an application may do with the errors of this kind.
+    pub fn get_disposition(&self) -> Disposition {
+        match self {
+            ErrorKind::Timeout | ErrorKind::Io | ErrorKind::Storage => Disposition::Retry,
+            ErrorKind::Authenticity | ErrorKind::Configuration => Disposition::Alert,
+            ErrorKind::Encoding
+            | ErrorKind::Malformed
+            | ErrorKind::UnexpectedResponse
+            | ErrorKind::Other => Disposition::Fail,
+        }
+    }
+
+    /// Returns whether the error can be transient, i.e. `get_disposition()` is `Retry`.
+    pub fn is_retryable(&self) -> bool {
+        self.get_disposition() == Disposition::Retry
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::avp::AVPError;
+    use crate::core::packet::PacketError;
+    use crate::core::sansio::RequestDecodingError;
+    use crate::error::{Disposition, ErrorKind};
+
+    #[test]
+    fn test_kinds() {
+        let e = RequestDecodingError::InvalidAuthenticatorError();
+        assert_eq!(e.get_kind(), ErrorKind::Authenticity);
+        assert_eq!(e.get_kind().get_disposition(), Disposition::Alert);
+
+        let e = RequestDecodingError::DecodingError(PacketError::DecodingError("".to_owned()));
+        assert_eq!(e.get_kind(), ErrorKind::Malformed);
+        assert!(!e.get_kind().is_retryable());
+
+        let e = RequestDecodingError::InvalidEventTimestampError(
+            AVPError::InvalidAttributeLengthError("4 bytes".to_owned(), 3),
+        );
+        assert_eq!(e.get_kind(), ErrorKind::Malformed);
+
+        assert_eq!(
+            AVPError::UserPasswordPlainTextMaximumLengthExceededError(129).get_kind(),
+            ErrorKind::Encoding
+        );
+        assert!(ErrorKind::Io.is_retryable());
+        assert_eq!(
+            ErrorKind::UnexpectedResponse.to_string(),
+            "unexpected_response"
+        );
+    }
+}
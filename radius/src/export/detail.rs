@@ -0,0 +1,345 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use thiserror::Error;
+
+use crate::core::dictionary;
+use crate::export::AccountingRecord;
+
+const HEADER_FORMAT: &str = "%a %b %e %H:%M:%S %Y";
+const DATE_FORMAT: &str = "%b %e %Y %H:%M:%S";
+
+const TIMESTAMP: &str = "Timestamp";
+const PACKET_SRC_IP_ADDRESS: &str = "Packet-Src-IP-Address";
+const PACKET_SRC_IPV6_ADDRESS: &str = "Packet-Src-IPv6-Address";
+const PACKET_SRC_PORT: &str = "Packet-Src-Port";
+const CLIENT_IP_ADDRESS: &str = "Client-IP-Address";
+
+#[derive(Error, Debug)]
+pub enum DetailError {
+    /// An error that is raised when reading a detail file has been failed.
+    #[error("failed to read the detail file: {0}")]
+    FailedReadingError(String),
+    /// An error that is raised when a line of a detail file is malformed.
+    #[error("invalid detail entry at line {0}: {1}")]
+    InvalidEntryError(usize, String),
+}
+
+impl AccountingRecord {
+    /// Serializes the record into an entry of the FreeRADIUS "detail" file (with a trailing line break).
+    ///
+    /// The header and the dates are written in UTC. The integer values are written by their dictionary names
+    /// (e.g. `Acct-Status-Type = Start`) if those exist, and the NAS address and the timestamp are written
+    /// as `Packet-Src-IP-Address` (or `Packet-Src-IPv6-Address`) and `Timestamp` like FreeRADIUS does.
+    /// An empty line has to follow the entry to separate that from the next one.
+    pub fn to_detail(&self) -> String {
+        let mut detail = self.timestamp.format(HEADER_FORMAT).to_string();
+        detail.push('\n');
+        for (name, value) in &self.attributes {
+            let value = match dictionary::lookup_attribute_by_name(name).map(|a| a.get_data_type())
+            {
+                Some("integer") => value
+                    .parse::<u32>()
+                    .ok()
+                    .and_then(|v| dictionary::lookup_value(name, v))
+                    .map(|v| v.get_name().to_owned())
+                    .unwrap_or_else(|| value.clone()),
+                Some("date") => match DateTime::parse_from_rfc3339(value) {
+                    Ok(dt) => quote(&format!(
+                        "{} UTC",
+                        dt.with_timezone(&Utc).format(DATE_FORMAT)
+                    )),
+                    Err(_) => value.clone(),
+                },
+                Some("string") => quote(value),
+                _ => value.clone(),
+            };
+            detail.push_str(&format!("\t{name} = {value}\n"));
+        }
+
+        let src_name = match self.nas_address.ip() {
+            IpAddr::V4(_) => PACKET_SRC_IP_ADDRESS,
+            IpAddr::V6(_) => PACKET_SRC_IPV6_ADDRESS,
+        };
+        detail.push_str(&format!("\t{} = {}\n", src_name, self.nas_address.ip()));
+        detail.push_str(&format!(
+            "\t{} = {}\n",
+            PACKET_SRC_PORT,
+            self.nas_address.port()
+        ));
+        detail.push_str(&format!(
+            "\t{} = {}\n",
+            TIMESTAMP,
+            self.timestamp.timestamp()
+        ));
+        detail
+    }
+}
+
+/// A reader of the FreeRADIUS "detail" file that yields the entries as `AccountingRecord`s.
+///
+/// The values are converted into the representation of `AccountingRecord` (e.g. `Start` of `Acct-Status-Type` is `1`,
+/// and the dates are RFC 3339), so the records can be written to any `AccountingSink`.
+/// The NAS address and the timestamp are taken from `Packet-Src-IP-Address` (or `Client-IP-Address`) and `Timestamp`;
+/// if the entry doesn't have `Timestamp`, the header is used as UTC.
+pub struct DetailReader<R: BufRead> {
+    reader: R,
+    line_number: usize,
+}
+
+impl DetailReader<BufReader<File>> {
+    /// Open a detail file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, DetailError> {
+        let file = File::open(path).map_err(|e| DetailError::FailedReadingError(e.to_string()))?;
+        Ok(Self::new(BufReader::new(file)))
+    }
+}
+
+impl<R: BufRead> DetailReader<R> {
+    pub fn new(reader: R) -> Self {
+        DetailReader {
+            reader,
+            line_number: 0,
+        }
+    }
+
+    fn read_line(&mut self) -> Result<Option<String>, DetailError> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                self.line_number += 1;
+                Ok(Some(line.trim_end_matches(['\r', '\n']).to_owned()))
+            }
+            Err(e) => Err(DetailError::FailedReadingError(e.to_string())),
+        }
+    }
+
+    fn read_entry(&mut self) -> Result<Option<AccountingRecord>, DetailError> {
+        let header = loop {
+            match self.read_line()? {
+                None => return Ok(None),
+                Some(line) if line.trim().is_empty() => continue,
+                Some(line) => break line,
+            }
+        };
+        let header_line_number = self.line_number;
+        if header.starts_with(char::is_whitespace) {
+            return Err(DetailError::InvalidEntryError(
+                header_line_number,
+                "an entry has to start with a date".to_owned(),
+            ));
+        }
+
+        let mut timestamp = None;
+        let mut src_ip = None;
+        let mut src_port = 0;
+        let mut attributes = vec![];
+        while let Some(line) = self.read_line()? {
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line.split_once('=').ok_or_else(|| {
+                DetailError::InvalidEntryError(self.line_number, format!("no value: {line}"))
+            })?;
+            let name = name.trim();
+            let value = unquote(value.trim());
+            let invalid = || {
+                DetailError::InvalidEntryError(
+                    self.line_number,
+                    format!("invalid value of {name}: {value}"),
+                )
+            };
+            match name {
+                TIMESTAMP => {
+                    let ts = value.parse::<i64>().map_err(|_| invalid())?;
+                    timestamp = Some(Utc.timestamp_opt(ts, 0).single().ok_or_else(invalid)?);
+                }
+                PACKET_SRC_IP_ADDRESS | PACKET_SRC_IPV6_ADDRESS | CLIENT_IP_ADDRESS => {
+                    src_ip = Some(value.parse::<IpAddr>().map_err(|_| invalid())?);
+                }
+                PACKET_SRC_PORT => src_port = value.parse::<u16>().map_err(|_| invalid())?,
+                _ => attributes.push((name.to_owned(), to_record_value(name, value))),
+            }
+        }
+
+        let timestamp = match timestamp {
+            Some(timestamp) => timestamp,
+            None => NaiveDateTime::parse_from_str(header.trim(), HEADER_FORMAT)
+                .map_err(|e| {
+                    DetailError::InvalidEntryError(
+                        header_line_number,
+                        format!("invalid date {header}: {e}"),
+                    )
+                })?
+                .and_utc(),
+        };
+        let nas_address = SocketAddr::new(
+            src_ip.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+            src_port,
+        );
+        Ok(Some(AccountingRecord {
+            timestamp,
+            nas_address,
+            attributes,
+        }))
+    }
+}
+
+impl<R: BufRead> Iterator for DetailReader<R> {
+    type Item = Result<AccountingRecord, DetailError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_entry().transpose()
+    }
+}
+
+fn to_record_value(name: &str, value: String) -> String {
+    match dictionary::lookup_attribute_by_name(name).map(|a| a.get_data_type()) {
+        Some("integer") if value.parse::<u32>().is_err() => {
+            match dictionary::lookup_value_by_name(name, &value) {
+                Some(v) => v.get_value().to_string(),
+                None => value,
+            }
+        }
+        Some("date") => {
+            // e.g. `Jan  1 2024 00:00:00 UTC`; the time zone is assumed to be UTC
+            let without_zone = value.rsplit_once(' ').map_or(value.as_str(), |(dt, _)| dt);
+            match NaiveDateTime::parse_from_str(without_zone, DATE_FORMAT) {
+                Ok(dt) => dt.and_utc().to_rfc3339_opts(SecondsFormat::Secs, true),
+                Err(_) => value,
+            }
+        }
+        _ => value,
+    }
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn unquote(value: &str) -> String {
+    let inner = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => inner,
+        None => return value.to_owned(),
+    };
+    let mut unquoted = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unquoted.push('\n'),
+            Some('r') => unquoted.push('\r'),
+            Some('t') => unquoted.push('\t'),
+            Some(c) => unquoted.push(c),
+            None => unquoted.push('\\'),
+        }
+    }
+    unquoted
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2865, rfc2866, rfc2869};
+    use crate::export::{AccountingRecord, DetailError, DetailReader};
+
+    #[test]
+    fn test_write_and_read_detail() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut packet, rfc2866::ACCT_STATUS_TYPE_START);
+        rfc2866::add_acct_session_id(&mut packet, "session \"1\"");
+        rfc2865::add_user_name(&mut packet, "bob");
+        rfc2865::add_nas_ip_address(&mut packet, &Ipv4Addr::new(192, 0, 2, 1));
+        rfc2869::add_event_timestamp(&mut packet, &timestamp);
+        let record = AccountingRecord::from_packet(
+            &packet,
+            "192.0.2.1:50000".parse::<SocketAddr>().unwrap(),
+            timestamp,
+        );
+
+        let detail = record.to_detail();
+        assert_eq!(
+            detail,
+            "Tue Jan  2 03:04:05 2024\n\
+             \tAcct-Status-Type = Start\n\
+             \tAcct-Session-Id = \"session \\\"1\\\"\"\n\
+             \tUser-Name = \"bob\"\n\
+             \tNAS-IP-Address = 192.0.2.1\n\
+             \tEvent-Timestamp = \"Jan  2 2024 03:04:05 UTC\"\n\
+             \tPacket-Src-IP-Address = 192.0.2.1\n\
+             \tPacket-Src-Port = 50000\n\
+             \tTimestamp = 1704164645\n"
+        );
+
+        let content = format!("{detail}\n{detail}\n");
+        let records = DetailReader::new(content.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records, vec![record.clone(), record]);
+    }
+
+    #[test]
+    fn test_read_freeradius_detail() {
+        let content = "Mon Jan  1 00:00:00 2024\n\
+                       \tAcct-Status-Type = Stop\n\
+                       \tUser-Name = \"alice\"\n\
+                       \tAcct-Session-Time = 3600\n\
+                       \tAcct-Terminate-Cause = User-Request\n\
+                       \tClient-IP-Address = 192.0.2.10\n\
+                       \tAcct-Unique-Session-Id = \"3f0c\"\n\
+                       \n";
+        let mut reader = DetailReader::new(content.as_bytes());
+
+        let record = reader.next().unwrap().unwrap();
+        assert_eq!(
+            record.get_timestamp(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            record.get_nas_address(),
+            "192.0.2.10:0".parse::<SocketAddr>().unwrap()
+        );
+        assert_eq!(record.get_u32("Acct-Status-Type"), Some(2));
+        assert_eq!(record.get_u32("Acct-Terminate-Cause"), Some(1));
+        assert_eq!(record.get("User-Name"), Some("alice"));
+        assert_eq!(record.get("Acct-Unique-Session-Id"), Some("3f0c"));
+        assert!(reader.next().is_none());
+
+        let mut reader = DetailReader::new("Not a date\n\tUser-Name = \"a\"\n".as_bytes());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(DetailError::InvalidEntryError(1, _)))
+        ));
+        let mut reader = DetailReader::new("Mon Jan  1 00:00:00 2024\n\tUser-Name\n".as_bytes());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(DetailError::InvalidEntryError(2, _)))
+        ));
+    }
+}
@@ -0,0 +1,363 @@
+//! Exporting the handled accounting packets as structured records.
+//!
+//! An `AccountingRecord` is a flattened view of an Accounting-Request packet;
+//! each attribute is represented by its dictionary name and a human-readable value.
+//! `AccountingExporter` writes such records to a rotating file as JSON Lines, CSV or the FreeRADIUS "detail" format,
+//! and `DetailReader` reads the records from a detail file that is written by this or FreeRADIUS.
+//!
+//! `AccountingSink` is the common interface of the record destinations; the SQL implementation
+//! is available with the `sql` feature.
+
+mod detail;
+mod file;
+#[cfg(feature = "sql")]
+pub mod sql;
+
+pub use detail::{DetailError, DetailReader};
+pub use file::{AccountingExporter, ExportFormat};
+
+use std::net::SocketAddr;
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use thiserror::Error;
+
+use crate::core::avp::{AVPType, AVP};
+use crate::core::deterministic;
+use crate::core::packet::Packet;
+use crate::core::request::Request;
+use crate::core::{rfc2865, rfc2866, rfc2869};
+
+/// The attributes that are exported by default in CSV format.
+pub const DEFAULT_CSV_COLUMNS: &[&str] = &[
+    "Acct-Status-Type",
+    "Acct-Session-Id",
+    "User-Name",
+    "NAS-IP-Address",
+    "NAS-Identifier",
+    "NAS-Port",
+    "Framed-IP-Address",
+    "Calling-Station-Id",
+    "Called-Station-Id",
+    "Acct-Session-Time",
+    "Acct-Input-Octets",
+    "Acct-Output-Octets",
+    "Acct-Input-Gigawords",
+    "Acct-Output-Gigawords",
+    "Acct-Input-Packets",
+    "Acct-Output-Packets",
+    "Acct-Terminate-Cause",
+    "Event-Timestamp",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ValueKind {
+    String,
+    Integer,
+    IpAddr,
+    Date,
+    Octets,
+}
+
+#[rustfmt::skip]
+const ATTRIBUTE_DEFINITIONS: &[(AVPType, &str, ValueKind)] = &[
+    (rfc2865::USER_NAME_TYPE, "User-Name", ValueKind::String),
+    (rfc2865::NAS_IP_ADDRESS_TYPE, "NAS-IP-Address", ValueKind::IpAddr),
+    (rfc2865::NAS_PORT_TYPE, "NAS-Port", ValueKind::Integer),
+    (rfc2865::SERVICE_TYPE_TYPE, "Service-Type", ValueKind::Integer),
+    (rfc2865::FRAMED_PROTOCOL_TYPE, "Framed-Protocol", ValueKind::Integer),
+    (rfc2865::FRAMED_IP_ADDRESS_TYPE, "Framed-IP-Address", ValueKind::IpAddr),
+    (rfc2865::FRAMED_IP_NETMASK_TYPE, "Framed-IP-Netmask", ValueKind::IpAddr),
+    (rfc2865::FILTER_ID_TYPE, "Filter-Id", ValueKind::String),
+    (rfc2865::FRAMED_MTU_TYPE, "Framed-MTU", ValueKind::Integer),
+    (rfc2865::CLASS_TYPE, "Class", ValueKind::Octets),
+    (rfc2865::SESSION_TIMEOUT_TYPE, "Session-Timeout", ValueKind::Integer),
+    (rfc2865::IDLE_TIMEOUT_TYPE, "Idle-Timeout", ValueKind::Integer),
+    (rfc2865::CALLED_STATION_ID_TYPE, "Called-Station-Id", ValueKind::String),
+    (rfc2865::CALLING_STATION_ID_TYPE, "Calling-Station-Id", ValueKind::String),
+    (rfc2865::NAS_IDENTIFIER_TYPE, "NAS-Identifier", ValueKind::String),
+    (rfc2865::PROXY_STATE_TYPE, "Proxy-State", ValueKind::Octets),
+    (rfc2865::NAS_PORT_TYPE_TYPE, "NAS-Port-Type", ValueKind::Integer),
+    (rfc2866::ACCT_STATUS_TYPE_TYPE, "Acct-Status-Type", ValueKind::Integer),
+    (rfc2866::ACCT_DELAY_TIME_TYPE, "Acct-Delay-Time", ValueKind::Integer),
+    (rfc2866::ACCT_INPUT_OCTETS_TYPE, "Acct-Input-Octets", ValueKind::Integer),
+    (rfc2866::ACCT_OUTPUT_OCTETS_TYPE, "Acct-Output-Octets", ValueKind::Integer),
+    (rfc2866::ACCT_SESSION_ID_TYPE, "Acct-Session-Id", ValueKind::String),
+    (rfc2866::ACCT_AUTHENTIC_TYPE, "Acct-Authentic", ValueKind::Integer),
+    (rfc2866::ACCT_SESSION_TIME_TYPE, "Acct-Session-Time", ValueKind::Integer),
+    (rfc2866::ACCT_INPUT_PACKETS_TYPE, "Acct-Input-Packets", ValueKind::Integer),
+    (rfc2866::ACCT_OUTPUT_PACKETS_TYPE, "Acct-Output-Packets", ValueKind::Integer),
+    (rfc2866::ACCT_TERMINATE_CAUSE_TYPE, "Acct-Terminate-Cause", ValueKind::Integer),
+    (rfc2866::ACCT_MULTI_SESSION_ID_TYPE, "Acct-Multi-Session-Id", ValueKind::String),
+    (rfc2866::ACCT_LINK_COUNT_TYPE, "Acct-Link-Count", ValueKind::Integer),
+    (rfc2869::ACCT_INPUT_GIGAWORDS_TYPE, "Acct-Input-Gigawords", ValueKind::Integer),
+    (rfc2869::ACCT_OUTPUT_GIGAWORDS_TYPE, "Acct-Output-Gigawords", ValueKind::Integer),
+    (rfc2869::EVENT_TIMESTAMP_TYPE, "Event-Timestamp", ValueKind::Date),
+    (rfc2869::CONNECT_INFO_TYPE, "Connect-Info", ValueKind::String),
+    (rfc2869::ACCT_INTERIM_INTERVAL_TYPE, "Acct-Interim-Interval", ValueKind::Integer),
+    (rfc2869::NAS_PORT_ID_TYPE, "NAS-Port-Id", ValueKind::String),
+];
+
+#[derive(Error, Debug)]
+pub enum AccountingSinkError {
+    /// An error that is raised when it fails to write a record to a file.
+    #[error("failed to write an accounting record: {0}")]
+    FailedWritingRecordError(String),
+    /// An error that is raised when the database returns an error.
+    #[error("database error: {0}")]
+    DatabaseError(String),
+    /// An error that is raised when the record lacks an attribute that is required by the sink.
+    #[error("accounting record doesn't have the required attribute: {0}")]
+    MissingRequiredAttributeError(String),
+}
+
+/// AccountingSink is a destination of the accounting records.
+#[async_trait]
+pub trait AccountingSink: 'static + Sync + Send {
+    /// This method has to persist the given record.
+    async fn write_record(&self, record: &AccountingRecord) -> Result<(), AccountingSinkError>;
+}
+
+/// A structured representation of an accounting packet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountingRecord {
+    timestamp: DateTime<Utc>,
+    nas_address: SocketAddr,
+    attributes: Vec<(String, String)>,
+}
+
+impl AccountingRecord {
+    /// Make a record from a handled request. The timestamp of the record is the current time.
+    pub fn from_request(request: &Request) -> Self {
+        Self::from_packet(
+            request.get_packet(),
+            request.get_remote_addr(),
+            deterministic::now(),
+        )
+    }
+
+    /// Make a record from a packet that has come from `nas_address`.
+    ///
+    /// Attributes are named by the accounting related dictionaries; the other attributes are named as `Attr-{type}`
+    /// and their values are exported as hex strings.
+    pub fn from_packet(packet: &Packet, nas_address: SocketAddr, timestamp: DateTime<Utc>) -> Self {
+        AccountingRecord {
+            timestamp,
+            nas_address,
+            attributes: packet
+                .get_attributes()
+                .iter()
+                .filter(|avp| avp.typ != rfc2865::USER_PASSWORD_TYPE)
+                .map(name_and_format_value)
+                .collect(),
+        }
+    }
+
+    pub fn get_timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    pub fn get_nas_address(&self) -> SocketAddr {
+        self.nas_address
+    }
+
+    /// Returns the named attributes in the order of the original packet.
+    pub fn get_attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+
+    /// Returns the first value of the attribute that matches with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the first value of the attribute that matches with the given name as an integer.
+    pub fn get_u32(&self, name: &str) -> Option<u32> {
+        self.get(name).and_then(|v| v.parse().ok())
+    }
+
+    /// Serializes the record into a JSON object (without trailing line break).
+    ///
+    /// An attribute that appears more than once is serialized as an array of the values.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        write_json_string(&mut json, "timestamp");
+        json.push(':');
+        write_json_string(
+            &mut json,
+            &self.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+        );
+        json.push(',');
+        write_json_string(&mut json, "nas_address");
+        json.push(':');
+        write_json_string(&mut json, &self.nas_address.to_string());
+
+        let mut names: Vec<&str> = Vec::new();
+        for (name, _) in &self.attributes {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        for name in names {
+            let values = self
+                .attributes
+                .iter()
+                .filter(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+                .collect::<Vec<&str>>();
+            json.push(',');
+            write_json_string(&mut json, name);
+            json.push(':');
+            if values.len() == 1 {
+                write_json_string(&mut json, values[0]);
+            } else {
+                json.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    write_json_string(&mut json, value);
+                }
+                json.push(']');
+            }
+        }
+        json.push('}');
+        json
+    }
+
+    /// Serializes the record into a CSV row (without trailing line break) according to the given columns.
+    ///
+    /// The first two fields are always the timestamp and the NAS address. A missing attribute makes an empty field.
+    pub fn to_csv_row(&self, columns: &[String]) -> String {
+        let mut fields = vec![
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.nas_address.to_string(),
+        ];
+        fields.extend(
+            columns
+                .iter()
+                .map(|column| self.get(column).unwrap_or_default().to_owned()),
+        );
+        fields
+            .iter()
+            .map(|field| escape_csv_field(field))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+}
+
+pub(crate) fn csv_header(columns: &[String]) -> String {
+    ["timestamp".to_owned(), "nas_address".to_owned()]
+        .iter()
+        .chain(columns.iter())
+        .map(|field| escape_csv_field(field))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+fn name_and_format_value(avp: &AVP) -> (String, String) {
+    match ATTRIBUTE_DEFINITIONS
+        .iter()
+        .find(|(typ, _, _)| *typ == avp.typ)
+    {
+        Some((_, name, kind)) => {
+            let value = match kind {
+                ValueKind::String => avp.encode_string().ok(),
+                ValueKind::Integer => avp.encode_u32().ok().map(|v| v.to_string()),
+                ValueKind::IpAddr => avp.encode_ipv4().ok().map(|v| v.to_string()),
+                ValueKind::Date => avp
+                    .encode_date()
+                    .ok()
+                    .map(|v| v.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                ValueKind::Octets => None,
+            };
+            (
+                (*name).to_owned(),
+                value.unwrap_or_else(|| to_hex(&avp.value)),
+            )
+        }
+        None => (format!("Attr-{}", avp.typ), to_hex(&avp.value)),
+    }
+}
+
+fn to_hex(bs: &[u8]) -> String {
+    let hex = bs.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    format!("0x{hex}")
+}
+
+pub(crate) fn write_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2865, rfc2866};
+    use crate::export::AccountingRecord;
+
+    fn make_record() -> AccountingRecord {
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut packet, rfc2866::ACCT_STATUS_TYPE_STOP);
+        rfc2866::add_acct_session_id(&mut packet, "session,\"1\"");
+        rfc2865::add_user_name(&mut packet, "bob");
+        rfc2865::add_nas_ip_address(&mut packet, &Ipv4Addr::new(192, 0, 2, 1));
+        rfc2865::add_class(&mut packet, b"c1");
+        rfc2865::add_class(&mut packet, b"c2");
+        packet.add(crate::core::avp::AVP::from_bytes(200, &[0xde, 0xad]));
+
+        AccountingRecord::from_packet(
+            &packet,
+            "192.0.2.1:1813".parse::<SocketAddr>().unwrap(),
+            Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            make_record().to_json(),
+            r#"{"timestamp":"2020-09-13T12:26:40Z","nas_address":"192.0.2.1:1813","Acct-Status-Type":"2","Acct-Session-Id":"session,\"1\"","User-Name":"bob","NAS-IP-Address":"192.0.2.1","Class":["0x6331","0x6332"],"Attr-200":"0xdead"}"#
+        );
+    }
+
+    #[test]
+    fn test_to_csv_row() {
+        let columns = vec![
+            "User-Name".to_owned(),
+            "Acct-Session-Id".to_owned(),
+            "Acct-Session-Time".to_owned(),
+        ];
+        assert_eq!(
+            make_record().to_csv_row(&columns),
+            r#"2020-09-13T12:26:40Z,192.0.2.1:1813,bob,"session,""1""","#
+        );
+    }
+}
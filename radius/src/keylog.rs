@@ -0,0 +1,90 @@
+//! The key log of the TLS secrets in the `SSLKEYLOGFILE` format (NSS key log), to decrypt the RadSec sessions in
+//! Wireshark during the troubleshooting.
+//!
+//! `KeyLogFile` is a `rustls::KeyLog`, so it's opt-in by `key_log` of the rustls `ClientConfig` and `ServerConfig`
+//! of the RadSec client and server:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use radius::keylog::KeyLogFile;
+//!
+//! # fn configure(config: &mut rustls::ClientConfig) -> std::io::Result<()> {
+//! if let Some(key_log) = KeyLogFile::from_env()? {
+//!     config.key_log = Arc::new(key_log);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Debug};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use rustls::KeyLog;
+
+/// A writer of the TLS secrets in the `SSLKEYLOGFILE` format.
+///
+/// Anyone who can read the file can decrypt the sessions, so enable this only for the troubleshooting.
+pub struct KeyLogFile {
+    file: Mutex<File>,
+}
+
+impl KeyLogFile {
+    /// Open the file to append the secrets to.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(KeyLogFile {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Open the file of the `SSLKEYLOGFILE` environment variable, or returns `None` if the variable is not set.
+    pub fn from_env() -> io::Result<Option<Self>> {
+        match std::env::var_os("SSLKEYLOGFILE") {
+            Some(path) if !path.is_empty() => Self::open(path).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Debug for KeyLogFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyLogFile").finish_non_exhaustive()
+    }
+}
+
+impl KeyLog for KeyLogFile {
+    /// Append a secret of a session (e.g. `CLIENT_HANDSHAKE_TRAFFIC_SECRET`) to the file.
+    fn log(&self, label: &str, client_random: &[u8], secret: &[u8]) {
+        let line = format!("{} {} {}\n", label, to_hex(client_random), to_hex(secret));
+        if let Err(e) = self.file.lock().unwrap().write_all(line.as_bytes()) {
+            warn!("failed to write a TLS secret to the key log file; {}", e);
+        }
+    }
+}
+
+fn to_hex(bs: &[u8]) -> String {
+    bs.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rustls::KeyLog;
+
+    use crate::keylog::KeyLogFile;
+
+    #[test]
+    fn test_key_log_file() {
+        let path = std::env::temp_dir().join(format!("radius-keylog-{}", std::process::id()));
+        let key_log = KeyLogFile::open(&path).unwrap();
+        key_log.log("CLIENT_RANDOM", &[0x01, 0xab], &[0xff]);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "CLIENT_RANDOM 01ab ff\n"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,6 +1,57 @@
-#[macro_use]
+//! An async/await native implementation of the RADIUS server and client.
+//!
+//! The packet codec (`radius::core`) supports `no_std` with `alloc`: disable the default features
+//! (`std` and `tokio`) to use only that. The other modules require `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+#[cfg_attr(feature = "std", macro_use)]
 extern crate log;
 
+#[cfg(feature = "admin")]
+pub mod admin;
+#[cfg(all(feature = "batch-io", target_os = "linux"))]
+pub mod batch;
+#[cfg(feature = "tokio")]
+mod buffer_pool;
+#[cfg(feature = "pcap")]
+pub mod capture;
+#[cfg(feature = "std")]
 pub mod client;
+#[cfg(feature = "config")]
+pub mod config;
 pub mod core;
+#[cfg(feature = "tokio")]
+pub mod correlation;
+#[cfg(feature = "dtls")]
+pub mod dtls;
+#[cfg(feature = "eap")]
+pub mod eap;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod export;
+#[cfg(feature = "keylog")]
+pub mod keylog;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "policy")]
+pub mod policy;
+#[cfg(feature = "tokio")]
+pub mod proxy;
+#[cfg(feature = "radsec")]
+pub mod radsec;
+#[cfg(feature = "std")]
+pub mod runtime;
+#[cfg(feature = "std")]
 pub mod server;
+#[cfg(feature = "std")]
+pub mod sessions;
+#[cfg(feature = "tokio")]
+pub mod tasks;
+#[cfg(feature = "tcp")]
+pub mod tcp;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod transport;
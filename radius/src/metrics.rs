@@ -0,0 +1,921 @@
+//! Prometheus metrics of the client and the server (available with the `metrics` feature).
+//!
+//! `Metrics` is a registry of the counters, the gauges and the histograms of the RADIUS traffic; share it among the
+//! servers and the clients by `set_metrics()`, and serve `render()` (the Prometheus text exposition format) to the scraper
+//! by `MetricsEndpoint`, or by `GET /metrics` of `radius::admin`.
+//!
+//! | name                                          | type      | labels               |
+//! |-----------------------------------------------|-----------|----------------------|
+//! | `<namespace>_server_requests_total`           | counter   | `code`               |
+//! | `<namespace>_server_outcomes_total`           | counter   | `outcome`            |
+//! | `<namespace>_server_malformed_total`          | counter   |                      |
+//! | `<namespace>_server_drops_total`              | counter   | `client`, `reason`   |
+//! | `<namespace>_server_response_cache_hits_total`| counter   |                      |
+//! | `<namespace>_server_request_duration_seconds` | histogram |                      |
+//! | `<namespace>_server_in_flight`                | gauge     |                      |
+//! | `<namespace>_server_buffer_pool_idle`         | gauge     |                      |
+//! | `<namespace>_client_requests_total`           | counter   | `code`               |
+//! | `<namespace>_client_responses_total`          | counter   | `code`               |
+//! | `<namespace>_client_errors_total`             | counter   | `error`              |
+//! | `<namespace>_client_retransmissions_total`    | counter   |                      |
+//! | `<namespace>_client_request_duration_seconds` | histogram |                      |
+//! | `<namespace>_tasks_spawned_total`             | counter   | `task`               |
+//! | `<namespace>_tasks_active`                    | gauge     | `task`               |
+//!
+//! The constant labels of `add_label()` are put on every series, e.g. to tell the instances apart.
+//! The drops are counted per the address of the client, so the series are as many as the clients that have been dropped.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use radius::metrics::{Metrics, MetricsEndpoint};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mut metrics = Metrics::new("radius");
+//! metrics.add_label("instance", "nas-1");
+//! let metrics = Arc::new(metrics);
+//! // server.set_metrics(metrics.clone()); client.set_metrics(metrics.clone());
+//! assert!(metrics.render().contains("radius_server_in_flight{instance=\"nas-1\"} 0\n"));
+//!
+//! let endpoint = MetricsEndpoint::listen("127.0.0.1", 0, metrics).await.unwrap();
+//! tokio::spawn(async move { endpoint.run(std::future::pending::<()>()).await });
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+
+use crate::client::ClientError;
+use crate::core::code::Code;
+use crate::server::{DropReason, RequestOutcome};
+use crate::tasks;
+
+/// The default upper bounds of the buckets of the duration histograms, in seconds.
+pub const DEFAULT_DURATION_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+const SERVER_OUTCOMES: [&str; 4] = ["handled", "handler_error", "dropped", "duplicated"];
+const CLIENT_ERRORS: [&str; 9] = [
+    "socket_timeout",
+    "connection_timeout",
+    "total_timeout",
+    "io",
+    "encoding",
+    "decoding",
+    "no_servers",
+    "unauthentic_response",
+    "cancelled",
+];
+
+/// The hooks of the events of the RADIUS clients and servers, to record them into any metrics system (e.g. StatsD)
+/// by `set_metrics()` of the clients and the servers.
+///
+/// Every method has an empty default, so an implementation overrides only the events that it needs; `Metrics` is the
+/// Prometheus implementation. The hooks are called on the hot paths, so they shouldn't block.
+#[allow(unused_variables)]
+pub trait MetricsHook: Send + Sync {
+    /// A server has decoded a request of the code.
+    fn on_server_request(&self, code: Code) {}
+
+    /// A server has finished a received datagram with the outcome, in `duration` since it has been received
+    /// (i.e. the latency of the handler for the handled ones).
+    fn on_server_outcome(&self, outcome: RequestOutcome, duration: Duration) {}
+
+    /// A server has dropped a datagram that is malformed or exceeds the decoding limits; the outcome is `Dropped`.
+    fn on_server_malformed(&self) {}
+
+    /// A server has dropped a datagram of the client for the reason; the outcome is `Dropped`.
+    fn on_server_drop(&self, client: IpAddr, reason: DropReason) {}
+
+    /// A server has answered a retransmission by the cached response (see `Server::set_response_cache_ttl()`);
+    /// the outcome is `Duplicated`. An identical authentication retry that is answered by the cached response
+    /// (see `Server::set_authentication_cache()`) is counted as well, and the outcome is `Handled`.
+    fn on_server_response_cache_hit(&self) {}
+
+    /// The number of the requests that a server is processing has changed by `delta`.
+    fn on_server_in_flight(&self, delta: i64) {}
+
+    /// The number of the idle receive buffers of a server has changed by `delta`.
+    fn on_server_buffer_pool_idle(&self, delta: i64) {}
+
+    /// A client has started a request of the code (once per request, regardless of the retransmissions).
+    fn on_client_request(&self, code: Code) {}
+
+    /// A client has retransmitted a request on the timeout.
+    fn on_client_retransmission(&self) {}
+
+    /// A client has received the response of the code, in `duration` since the request has started.
+    fn on_client_response(&self, code: Code, duration: Duration) {}
+
+    /// A request of a client has failed (e.g. by the timeout), in `duration` since it has started.
+    fn on_client_error(&self, error: &ClientError, duration: Duration) {}
+}
+
+impl std::fmt::Debug for dyn MetricsHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MetricsHook")
+    }
+}
+
+/// A registry of the metrics of the RADIUS clients and servers.
+#[derive(Debug)]
+pub struct Metrics {
+    namespace: String,
+    labels: Vec<(String, String)>,
+    server_requests: CodeCounter,
+    server_outcomes: [AtomicU64; SERVER_OUTCOMES.len()],
+    server_malformed: AtomicU64,
+    server_drops: Mutex<BTreeMap<(IpAddr, DropReason), u64>>,
+    server_response_cache_hits: AtomicU64,
+    server_durations: Histogram,
+    server_in_flight: AtomicI64,
+    server_buffer_pool_idle: AtomicI64,
+    client_requests: CodeCounter,
+    client_responses: CodeCounter,
+    client_errors: [AtomicU64; CLIENT_ERRORS.len()],
+    client_retransmissions: AtomicU64,
+    client_durations: Histogram,
+}
+
+impl Metrics {
+    /// Make a registry; the names of the metrics are prefixed by `namespace` and `_` (e.g. `radius`).
+    pub fn new(namespace: &str) -> Self {
+        Metrics {
+            namespace: namespace.to_owned(),
+            labels: Vec::new(),
+            server_requests: CodeCounter::default(),
+            server_outcomes: Default::default(),
+            server_malformed: AtomicU64::new(0),
+            server_drops: Mutex::new(BTreeMap::new()),
+            server_response_cache_hits: AtomicU64::new(0),
+            server_durations: Histogram::new(DEFAULT_DURATION_BUCKETS),
+            server_in_flight: AtomicI64::new(0),
+            server_buffer_pool_idle: AtomicI64::new(0),
+            client_requests: CodeCounter::default(),
+            client_responses: CodeCounter::default(),
+            client_errors: Default::default(),
+            client_retransmissions: AtomicU64::new(0),
+            client_durations: Histogram::new(DEFAULT_DURATION_BUCKETS),
+        }
+    }
+
+    /// Add a constant label that is put on every series.
+    pub fn add_label(&mut self, name: &str, value: &str) {
+        self.labels.push((name.to_owned(), value.to_owned()));
+    }
+
+    /// Set the upper bounds of the buckets of the duration histograms in seconds (default: `DEFAULT_DURATION_BUCKETS`).
+    ///
+    /// This resets the histograms.
+    pub fn set_duration_buckets(&mut self, buckets: &[f64]) {
+        self.server_durations = Histogram::new(buckets);
+        self.client_durations = Histogram::new(buckets);
+    }
+
+    /// Returns the metrics in the Prometheus text exposition format (version 0.0.4).
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        self.header(
+            &mut out,
+            "server_requests_total",
+            "counter",
+            "The number of the decoded requests by the code.",
+        );
+        self.render_codes(&mut out, "server_requests_total", &self.server_requests);
+
+        self.header(
+            &mut out,
+            "server_outcomes_total",
+            "counter",
+            "The number of the received datagrams by the outcome.",
+        );
+        for (outcome, counter) in SERVER_OUTCOMES.iter().zip(&self.server_outcomes) {
+            self.sample(
+                &mut out,
+                "server_outcomes_total",
+                &[("outcome", outcome)],
+                counter.load(Ordering::Relaxed),
+            );
+        }
+
+        self.header(
+            &mut out,
+            "server_malformed_total",
+            "counter",
+            "The number of the dropped datagrams that are malformed or exceed the limits.",
+        );
+        self.sample(
+            &mut out,
+            "server_malformed_total",
+            &[],
+            self.server_malformed.load(Ordering::Relaxed),
+        );
+
+        self.header(
+            &mut out,
+            "server_drops_total",
+            "counter",
+            "The number of the dropped datagrams by the client and the reason.",
+        );
+        for ((client, reason), value) in self.server_drops.lock().unwrap().iter() {
+            self.sample(
+                &mut out,
+                "server_drops_total",
+                &[("client", &client.to_string()), ("reason", reason.as_str())],
+                value,
+            );
+        }
+
+        self.header(
+            &mut out,
+            "server_response_cache_hits_total",
+            "counter",
+            "The number of the retransmissions that have been answered by the cached responses.",
+        );
+        self.sample(
+            &mut out,
+            "server_response_cache_hits_total",
+            &[],
+            self.server_response_cache_hits.load(Ordering::Relaxed),
+        );
+
+        self.header(
+            &mut out,
+            "server_request_duration_seconds",
+            "histogram",
+            "The duration of processing the requests.",
+        );
+        self.render_histogram(
+            &mut out,
+            "server_request_duration_seconds",
+            &self.server_durations,
+        );
+
+        self.header(
+            &mut out,
+            "server_in_flight",
+            "gauge",
+            "The number of the requests that are being processed.",
+        );
+        self.sample(
+            &mut out,
+            "server_in_flight",
+            &[],
+            self.server_in_flight.load(Ordering::Relaxed),
+        );
+
+        self.header(
+            &mut out,
+            "server_buffer_pool_idle",
+            "gauge",
+            "The number of the idle receive buffers in the pools.",
+        );
+        self.sample(
+            &mut out,
+            "server_buffer_pool_idle",
+            &[],
+            self.server_buffer_pool_idle.load(Ordering::Relaxed),
+        );
+
+        self.header(
+            &mut out,
+            "client_requests_total",
+            "counter",
+            "The number of the requests that have been sent by the code.",
+        );
+        self.render_codes(&mut out, "client_requests_total", &self.client_requests);
+
+        self.header(
+            &mut out,
+            "client_responses_total",
+            "counter",
+            "The number of the responses that have been received by the code.",
+        );
+        self.render_codes(&mut out, "client_responses_total", &self.client_responses);
+
+        self.header(
+            &mut out,
+            "client_errors_total",
+            "counter",
+            "The number of the requests that have failed by the error.",
+        );
+        for (error, counter) in CLIENT_ERRORS.iter().zip(&self.client_errors) {
+            self.sample(
+                &mut out,
+                "client_errors_total",
+                &[("error", error)],
+                counter.load(Ordering::Relaxed),
+            );
+        }
+
+        self.header(
+            &mut out,
+            "client_retransmissions_total",
+            "counter",
+            "The number of the retransmissions on socket timeout.",
+        );
+        self.sample(
+            &mut out,
+            "client_retransmissions_total",
+            &[],
+            self.client_retransmissions.load(Ordering::Relaxed),
+        );
+
+        self.header(
+            &mut out,
+            "client_request_duration_seconds",
+            "histogram",
+            "The duration of the requests including the retransmissions.",
+        );
+        self.render_histogram(
+            &mut out,
+            "client_request_duration_seconds",
+            &self.client_durations,
+        );
+
+        // the tasks are counted in the process (see `radius::tasks`), so they are the same for every registry
+        let task_counts = tasks::get_task_counts();
+        self.header(
+            &mut out,
+            "tasks_spawned_total",
+            "counter",
+            "The number of the tasks that the crate has spawned by the name.",
+        );
+        for count in &task_counts {
+            self.sample(
+                &mut out,
+                "tasks_spawned_total",
+                &[("task", count.get_name())],
+                count.get_spawned(),
+            );
+        }
+        self.header(
+            &mut out,
+            "tasks_active",
+            "gauge",
+            "The number of the tasks of the crate that are alive by the name.",
+        );
+        for count in &task_counts {
+            self.sample(
+                &mut out,
+                "tasks_active",
+                &[("task", count.get_name())],
+                count.get_active(),
+            );
+        }
+
+        out
+    }
+
+    fn header(&self, out: &mut String, name: &str, typ: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {}_{} {}", self.namespace, name, help);
+        let _ = writeln!(out, "# TYPE {}_{} {}", self.namespace, name, typ);
+    }
+
+    fn sample(
+        &self,
+        out: &mut String,
+        name: &str,
+        labels: &[(&str, &str)],
+        value: impl std::fmt::Display,
+    ) {
+        let _ = write!(out, "{}_{}", self.namespace, name);
+        let mut labels = self
+            .labels
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .chain(labels.iter().copied())
+            .peekable();
+        if labels.peek().is_some() {
+            out.push('{');
+            for (i, (name, value)) in labels.enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(name);
+                out.push_str("=\"");
+                for c in value.chars() {
+                    match c {
+                        '\\' => out.push_str("\\\\"),
+                        '"' => out.push_str("\\\""),
+                        '\n' => out.push_str("\\n"),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+            }
+            out.push('}');
+        }
+        let _ = writeln!(out, " {value}");
+    }
+
+    fn render_codes(&self, out: &mut String, name: &str, counter: &CodeCounter) {
+        for (code, value) in counter.0.iter().enumerate() {
+            let value = value.load(Ordering::Relaxed);
+            if value > 0 {
+                self.sample(
+                    out,
+                    name,
+                    &[("code", Code::from(code as u8).string())],
+                    value,
+                );
+            }
+        }
+    }
+
+    fn render_histogram(&self, out: &mut String, name: &str, histogram: &Histogram) {
+        let bucket_name = format!("{name}_bucket");
+        let mut cumulative = 0;
+        for (bound, count) in histogram.bounds.iter().zip(&histogram.counts) {
+            cumulative += count.load(Ordering::Relaxed);
+            self.sample(out, &bucket_name, &[("le", &bound.to_string())], cumulative);
+        }
+        let count = histogram.count.load(Ordering::Relaxed);
+        self.sample(out, &bucket_name, &[("le", "+Inf")], count);
+        let sum = histogram.sum_nanos.load(Ordering::Relaxed) as f64 / 1e9;
+        self.sample(out, &format!("{name}_sum"), &[], sum);
+        self.sample(out, &format!("{name}_count"), &[], count);
+    }
+}
+
+impl MetricsHook for Metrics {
+    fn on_server_request(&self, code: Code) {
+        self.server_requests.inc(code);
+    }
+
+    fn on_server_outcome(&self, outcome: RequestOutcome, duration: Duration) {
+        self.server_outcomes[outcome as usize].fetch_add(1, Ordering::Relaxed);
+        if outcome != RequestOutcome::Dropped {
+            self.server_durations.observe(duration);
+        }
+    }
+
+    fn on_server_malformed(&self) {
+        self.server_malformed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_server_drop(&self, client: IpAddr, reason: DropReason) {
+        *self
+            .server_drops
+            .lock()
+            .unwrap()
+            .entry((client, reason))
+            .or_default() += 1;
+    }
+
+    fn on_server_response_cache_hit(&self) {
+        self.server_response_cache_hits
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_server_in_flight(&self, delta: i64) {
+        self.server_in_flight.fetch_add(delta, Ordering::Relaxed);
+    }
+
+    fn on_server_buffer_pool_idle(&self, delta: i64) {
+        self.server_buffer_pool_idle
+            .fetch_add(delta, Ordering::Relaxed);
+    }
+
+    fn on_client_request(&self, code: Code) {
+        self.client_requests.inc(code);
+    }
+
+    fn on_client_retransmission(&self) {
+        self.client_retransmissions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_client_response(&self, code: Code, duration: Duration) {
+        self.client_responses.inc(code);
+        self.client_durations.observe(duration);
+    }
+
+    fn on_client_error(&self, error: &ClientError, _duration: Duration) {
+        let error = match error {
+            ClientError::SocketTimeoutError(_) => 0,
+            ClientError::ConnectionTimeoutError(_) => 1,
+            ClientError::TotalTimeoutError(_) => 2,
+            ClientError::FailedUdpSocketBindingError(_)
+            | ClientError::FailedEstablishingUdpConnectionError(_, _)
+            | ClientError::FailedSendingRadiusPacketError(_, _)
+            | ClientError::FailedReceivingResponseError(_, _)
+            | ClientError::FailedResolvingHostError(_, _) => 3,
+            ClientError::FailedRadiusPacketEncodingError(_) => 4,
+            ClientError::FailedDecodingRadiusResponseError(_) => 5,
+            ClientError::NoServersError() => 6,
+            ClientError::UnauthenticResponseError(_) => 7,
+            ClientError::CancelledError() => 8,
+        };
+        self.client_errors[error].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+const MAX_REQUEST_LENGTH: usize = 8192;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A minimal HTTP endpoint that serves `Metrics::render()` by `GET /metrics` to the Prometheus scraper.
+///
+/// This is for the servers without `radius::admin`; the other paths are `404 Not Found`.
+pub struct MetricsEndpoint {
+    listener: TcpListener,
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsEndpoint {
+    /// Listen on the host and the port (e.g. `0.0.0.0` and `9812`) to serve the metrics.
+    pub async fn listen(host: &str, port: u16, metrics: Arc<Metrics>) -> Result<Self, io::Error> {
+        Ok(MetricsEndpoint {
+            listener: TcpListener::bind((host, port)).await?,
+            metrics,
+        })
+    }
+
+    /// Returns the listening address.
+    pub fn get_listen_address(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Starts serving the metrics; the requests are handled one by one.
+    ///
+    /// ## Parameters
+    ///
+    /// - `shutdown_trigger`: an implementation of the `Future` to interrupt to shutdown the endpoint
+    pub async fn run(&self, shutdown_trigger: impl Future) -> Result<(), io::Error> {
+        tokio::select! {
+            res = self.run_loop() => {
+                res
+            }
+            _ = shutdown_trigger => {
+                info!("metrics endpoint is shutting down");
+                Ok(())
+            }
+        }
+    }
+
+    async fn run_loop(&self) -> Result<(), io::Error> {
+        loop {
+            let (stream, remote_addr) = self.listener.accept().await?;
+            if let Err(e) = self.handle_connection(stream).await {
+                debug!(
+                    "failed to handle a metrics request from {}; {}",
+                    remote_addr, e
+                );
+            }
+        }
+    }
+
+    async fn handle_connection(&self, mut stream: TcpStream) -> Result<(), io::Error> {
+        let (status, body) = match timeout(READ_TIMEOUT, read_request_line(&mut stream)).await {
+            Ok(Ok(request_line)) => {
+                let mut items = request_line.split(' ');
+                match (
+                    items.next(),
+                    items.next().map(|path| path.split('?').next()),
+                ) {
+                    (Some("GET"), Some(Some("/metrics"))) => ("200 OK", self.metrics.render()),
+                    (_, Some(Some("/metrics"))) => {
+                        ("405 Method Not Allowed", "method not allowed\n".to_owned())
+                    }
+                    _ => ("404 Not Found", "not found\n".to_owned()),
+                }
+            }
+            Ok(Err(e)) => return Err(e),
+            Err(_) => ("408 Request Timeout", "request timeout\n".to_owned()),
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
+/// Read the head of an HTTP request, and return the request line of that.
+async fn read_request_line(stream: &mut TcpStream) -> Result<String, io::Error> {
+    let mut buf = Vec::new();
+    let mut chunk = [0; 1024];
+    loop {
+        let size = stream.read(&mut chunk).await?;
+        if size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed",
+            ));
+        }
+        buf.extend_from_slice(&chunk[..size]);
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&buf[..end]);
+            return Ok(head.split("\r\n").next().unwrap_or_default().to_owned());
+        }
+        if buf.len() > MAX_REQUEST_LENGTH {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request is too large",
+            ));
+        }
+    }
+}
+
+/// Call the hook of the result of a request of a client, i.e. `on_client_response()` or `on_client_error()`.
+pub(crate) fn observe_client_result<T>(
+    hook: &dyn MetricsHook,
+    result: &Result<T, ClientError>,
+    response_code: impl FnOnce(&T) -> Code,
+    duration: Duration,
+) {
+    match result {
+        Ok(response) => hook.on_client_response(response_code(response), duration),
+        Err(e) => hook.on_client_error(e, duration),
+    }
+}
+
+/// The counters by the packet code.
+#[derive(Debug)]
+struct CodeCounter(Box<[AtomicU64]>);
+
+impl Default for CodeCounter {
+    fn default() -> Self {
+        CodeCounter((0..=u8::MAX).map(|_| AtomicU64::new(0)).collect())
+    }
+}
+
+impl CodeCounter {
+    fn inc(&self, code: Code) {
+        self.0[code as usize].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug)]
+struct Histogram {
+    bounds: Vec<f64>,
+    counts: Vec<AtomicU64>,
+    count: AtomicU64,
+    sum_nanos: AtomicU64,
+}
+
+impl Histogram {
+    fn new(bounds: &[f64]) -> Self {
+        let mut bounds = bounds.to_vec();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        Histogram {
+            counts: bounds.iter().map(|_| AtomicU64::new(0)).collect(),
+            bounds,
+            count: AtomicU64::new(0),
+            sum_nanos: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        if let Some(i) = self.bounds.iter().position(|bound| seconds <= *bound) {
+            self.counts[i].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+
+    use crate::client::{Client, ClientError, TimeoutDetails};
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::metrics::{Metrics, MetricsEndpoint, MetricsHook};
+    use crate::server::{
+        DropReason, RequestHandler, RequestOutcome, SecretProvider, SecretProviderError, Server,
+    };
+
+    struct AcceptingHandler {}
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for AcceptingHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let response = req.get_packet().make_response_packet(Code::AccessAccept);
+            conn.send_to(&response.encode().unwrap(), req.get_remote_addr())
+                .await?;
+            Ok(())
+        }
+    }
+
+    struct StaticSecretProvider {}
+
+    impl SecretProvider for StaticSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let mut metrics = Metrics::new("radius");
+        metrics.add_label("instance", "a\"b");
+        metrics.set_duration_buckets(&[0.1, 0.01]);
+
+        metrics.on_server_request(Code::AccessRequest);
+        metrics.on_server_request(Code::AccessRequest);
+        metrics.on_server_outcome(RequestOutcome::Handled, Duration::from_millis(5));
+        metrics.on_server_outcome(RequestOutcome::Handled, Duration::from_millis(50));
+        metrics.on_server_outcome(RequestOutcome::Dropped, Duration::from_millis(1));
+        metrics.on_server_malformed();
+        metrics.on_server_drop("192.0.2.1".parse().unwrap(), DropReason::RateLimited);
+        metrics.on_server_drop("192.0.2.1".parse().unwrap(), DropReason::RateLimited);
+        metrics.on_server_response_cache_hit();
+        metrics.on_server_in_flight(3);
+        metrics.on_server_in_flight(-1);
+        metrics.on_client_request(Code::AccountingRequest);
+        metrics.on_client_retransmission();
+        metrics.on_client_response(Code::AccountingResponse, Duration::from_secs(1));
+        metrics.on_client_error(
+            &ClientError::SocketTimeoutError(TimeoutDetails::new(
+                Duration::from_secs(3),
+                1,
+                Duration::from_secs(3),
+            )),
+            Duration::from_secs(3),
+        );
+
+        let rendered = metrics.render();
+        for line in [
+            "# TYPE radius_server_requests_total counter\n",
+            "radius_server_requests_total{instance=\"a\\\"b\",code=\"Access-Request\"} 2\n",
+            "radius_server_outcomes_total{instance=\"a\\\"b\",outcome=\"handled\"} 2\n",
+            "radius_server_outcomes_total{instance=\"a\\\"b\",outcome=\"dropped\"} 1\n",
+            "radius_server_malformed_total{instance=\"a\\\"b\"} 1\n",
+            "radius_server_drops_total{instance=\"a\\\"b\",client=\"192.0.2.1\",reason=\"rate_limited\"} 2\n",
+            "radius_server_response_cache_hits_total{instance=\"a\\\"b\"} 1\n",
+            "radius_server_request_duration_seconds_bucket{instance=\"a\\\"b\",le=\"0.01\"} 1\n",
+            "radius_server_request_duration_seconds_bucket{instance=\"a\\\"b\",le=\"0.1\"} 2\n",
+            "radius_server_request_duration_seconds_bucket{instance=\"a\\\"b\",le=\"+Inf\"} 2\n",
+            "radius_server_request_duration_seconds_sum{instance=\"a\\\"b\"} 0.055\n",
+            "radius_server_request_duration_seconds_count{instance=\"a\\\"b\"} 2\n",
+            "radius_server_in_flight{instance=\"a\\\"b\"} 2\n",
+            "radius_client_requests_total{instance=\"a\\\"b\",code=\"Accounting-Request\"} 1\n",
+            "radius_client_responses_total{instance=\"a\\\"b\",code=\"Accounting-Response\"} 1\n",
+            "radius_client_errors_total{instance=\"a\\\"b\",error=\"socket_timeout\"} 1\n",
+            "radius_client_retransmissions_total{instance=\"a\\\"b\"} 1\n",
+            "radius_client_request_duration_seconds_bucket{instance=\"a\\\"b\",le=\"+Inf\"} 1\n",
+        ] {
+            assert!(rendered.contains(line), "{line} is not in {rendered}");
+        }
+        assert!(!rendered.contains("code=\"Access-Accept\""));
+    }
+
+    #[tokio::test]
+    async fn test_server_and_client() {
+        let metrics = Arc::new(Metrics::new("radius"));
+        let mut server =
+            Server::listen("127.0.0.1", 0, AcceptingHandler {}, StaticSecretProvider {})
+                .await
+                .unwrap();
+        server.set_metrics(metrics.clone());
+        let addr = server.get_listen_address().unwrap();
+        tokio::spawn(async move { server.run(std::future::pending::<()>()).await });
+
+        let mut client = Client::new(None, Some(Duration::from_secs(3)));
+        client.set_metrics(metrics.clone());
+        for _ in 0..3 {
+            let response = client
+                .send_packet(&addr, &Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+        // the server records the outcome after the handler has sent the response
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let rendered = metrics.render();
+        for line in [
+            "radius_server_requests_total{code=\"Access-Request\"} 3\n",
+            "radius_server_outcomes_total{outcome=\"handled\"} 3\n",
+            "radius_server_request_duration_seconds_count 3\n",
+            "radius_server_in_flight 0\n",
+            "radius_server_buffer_pool_idle 1\n",
+            "radius_client_requests_total{code=\"Access-Request\"} 3\n",
+            "radius_client_responses_total{code=\"Access-Accept\"} 3\n",
+            "radius_client_request_duration_seconds_count 3\n",
+            "radius_tasks_active{task=\"radius.server.request\"} ",
+        ] {
+            assert!(rendered.contains(line), "{line} is not in {rendered}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpStream;
+
+        let metrics = Arc::new(Metrics::new("radius"));
+        metrics.on_server_request(Code::AccessRequest);
+        let endpoint = MetricsEndpoint::listen("127.0.0.1", 0, metrics)
+            .await
+            .unwrap();
+        let addr = endpoint.get_listen_address().unwrap();
+        tokio::spawn(async move { endpoint.run(std::future::pending::<()>()).await });
+
+        let get = |path: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+        let response = get("/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("radius_server_requests_total{code=\"Access-Request\"} 1\n"));
+        assert!(get("/").await.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    #[derive(Default)]
+    struct CountingHook {
+        server_outcomes: AtomicU64,
+        client_responses: AtomicU64,
+        client_errors: AtomicU64,
+    }
+
+    impl MetricsHook for CountingHook {
+        fn on_server_outcome(&self, outcome: RequestOutcome, _duration: Duration) {
+            assert_eq!(outcome, RequestOutcome::Handled);
+            self.server_outcomes.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_client_response(&self, code: Code, _duration: Duration) {
+            assert_eq!(code, Code::AccessAccept);
+            self.client_responses.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_client_error(&self, error: &ClientError, _duration: Duration) {
+            assert!(matches!(error, ClientError::SocketTimeoutError(_)));
+            self.client_errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_hook() {
+        let hook = Arc::new(CountingHook::default());
+        let mut server =
+            Server::listen("127.0.0.1", 0, AcceptingHandler {}, StaticSecretProvider {})
+                .await
+                .unwrap();
+        server.set_metrics(hook.clone());
+        let addr = server.get_listen_address().unwrap();
+        tokio::spawn(async move { server.run(std::future::pending::<()>()).await });
+
+        let mut client = Client::new(None, Some(Duration::from_millis(200)));
+        client.set_metrics(hook.clone());
+        client
+            .send_packet(&addr, &Packet::new(Code::AccessRequest, b"secret"))
+            .await
+            .unwrap();
+        // nobody listens on this
+        let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        assert!(client
+            .send_packet(
+                &silent.local_addr().unwrap(),
+                &Packet::new(Code::AccessRequest, b"secret")
+            )
+            .await
+            .is_err());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(hook.server_outcomes.load(Ordering::Relaxed), 1);
+        assert_eq!(hook.client_responses.load(Ordering::Relaxed), 1);
+        assert_eq!(hook.client_errors.load(Ordering::Relaxed), 1);
+    }
+}
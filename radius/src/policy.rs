@@ -0,0 +1,769 @@
+//! Declarative rules of the responses, so a basic AAA server can be stood up without writing a request handler.
+//!
+//! A policy is a list of the rules in a format like the `users` file of FreeRADIUS: the first line of a rule has the
+//! conditions on the attributes of the request (or `DEFAULT`, which matches any request), and the indented lines
+//! below that have the attributes of the reply. The first rule whose conditions all match answers the request; it is
+//! accepted unless the rule has `Auth-Type := Reject`. The attributes are named and the values are written in the
+//! notation of the dictionary (see `radius::core::dictionary::Dictionary`), i.e. the built-in one by default.
+//!
+//! ```text
+//! # the operators are `==`, `!=`, `=*` (present) and `!*` (absent)
+//! User-Name == "alice", User-Password == "p@ssw0rd"
+//!     Reply-Message = "welcome, alice"
+//!     Session-Timeout = 3600
+//!
+//! Packet-Type == Accounting-Request
+//!
+//! User-Name == "mallory"
+//!     Auth-Type := Reject
+//!     Reply-Message = "go away"
+//!
+//! DEFAULT Auth-Type := Reject
+//! ```
+//!
+//! `Packet-Type` is the code of the request. An Access-Request is answered by Access-Accept or Access-Reject, an
+//! Accounting-Request by Accounting-Response, and a CoA-Request and a Disconnect-Request by their ACK or NAK.
+//! `PolicyHandler` is a `RequestHandler` that answers the requests by a policy; a handler of its own can call
+//! `Policy::evaluate()` and handle only the requests that no rule matches (e.g. for the hard parts like EAP).
+//!
+//! This is available with the `policy` feature.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+#[cfg(feature = "tokio")]
+use async_trait::async_trait;
+use thiserror::Error;
+#[cfg(feature = "tokio")]
+use tokio::net::UdpSocket;
+
+use crate::core::code::Code;
+use crate::core::dictionary::{Dictionary, DictionaryError};
+use crate::core::packet::Packet;
+#[cfg(feature = "tokio")]
+use crate::core::request::Request;
+use crate::error::ErrorKind;
+#[cfg(feature = "tokio")]
+use crate::server::{send_response, RequestHandler};
+
+/// The pseudo-attribute of the conditions on the code of the request.
+const PACKET_TYPE: &str = "Packet-Type";
+/// The pseudo-attribute of the decision of a rule, i.e. `Auth-Type := Accept` or `Auth-Type := Reject`.
+const AUTH_TYPE: &str = "Auth-Type";
+/// The first line of the rule that matches any request.
+const DEFAULT_RULE: &str = "DEFAULT";
+/// The codes of the requests that `Packet-Type` can be compared with.
+const REQUEST_CODES: &[Code] = &[
+    Code::AccessRequest,
+    Code::AccountingRequest,
+    Code::StatusServer,
+    Code::DisconnectRequest,
+    Code::CoARequest,
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PolicyError {
+    /// An error that is raised when reading a policy file has been failed.
+    #[error("failed to read the policy file: {0}")]
+    FailedReadingError(String),
+    /// An error that is raised when a line of a policy is malformed.
+    #[error("invalid rule at {0} line {1}: {2}")]
+    InvalidRuleError(String, usize, String),
+    /// An error that is raised when the matched rule has no response for the code of the request, e.g. the
+    /// rejection of an Accounting-Request.
+    #[error("no {1} response to {0}")]
+    UnsupportedCodeError(String, String),
+    /// An error that is raised when no rule matches the request (see `PolicyHandler`).
+    #[error("no rule matches the request")]
+    NoMatchingRuleError(),
+    /// An error that is raised when sending a response has been failed.
+    #[error("failed to send the response: {0}")]
+    FailedSendingResponseError(String),
+    /// An error of the attributes of the request or of the reply, see `DictionaryError`.
+    #[error(transparent)]
+    DictionaryError(#[from] DictionaryError),
+}
+
+impl PolicyError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            PolicyError::FailedReadingError(_) | PolicyError::FailedSendingResponseError(_) => {
+                ErrorKind::Io
+            }
+            PolicyError::InvalidRuleError(_, _, _) => ErrorKind::Configuration,
+            PolicyError::UnsupportedCodeError(_, _) | PolicyError::NoMatchingRuleError() => {
+                ErrorKind::Other
+            }
+            PolicyError::DictionaryError(e) => e.get_kind(),
+        }
+    }
+}
+
+/// An operator of a `Condition`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// `==`: one of the values of the attribute is the value.
+    Equal,
+    /// `!=`: none of the values of the attribute is the value (i.e. it matches the request without the attribute).
+    NotEqual,
+    /// `=*`: the request has the attribute.
+    Present,
+    /// `!*`: the request doesn't have the attribute.
+    Absent,
+}
+
+/// A condition of a `Rule` on an attribute of the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    name: String,
+    operator: Operator,
+    value: String,
+}
+
+impl Condition {
+    /// Returns the name of the attribute, or `Packet-Type`.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_operator(&self) -> Operator {
+        self.operator
+    }
+
+    /// Returns the value in the notation of the dictionary (e.g. `Framed-User` for `2` of Service-Type), or an empty
+    /// string for `Present` and `Absent`.
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+
+    fn matches(&self, dictionary: &Dictionary, request: &Packet) -> Result<bool, PolicyError> {
+        let values = match self.name.as_str() {
+            PACKET_TYPE => vec![request.get_code().string().to_owned()],
+            name => dictionary.lookup_all(request, name)?,
+        };
+        let has_value = || values.contains(&self.value);
+        Ok(match self.operator {
+            Operator::Equal => has_value(),
+            Operator::NotEqual => !has_value(),
+            Operator::Present => !values.is_empty(),
+            Operator::Absent => values.is_empty(),
+        })
+    }
+}
+
+/// A rule of a `Policy`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    line: usize,
+    conditions: Vec<Condition>,
+    reject: bool,
+    reply_attributes: Vec<(String, String)>,
+}
+
+impl Rule {
+    /// Returns the line number of the first line of the rule.
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the conditions, which are empty for `DEFAULT`.
+    pub fn get_conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+
+    /// Returns whether the rule rejects the request (`Auth-Type := Reject`).
+    pub fn is_reject(&self) -> bool {
+        self.reject
+    }
+
+    /// Returns the names and the values of the attributes of the reply, in the order of the rule.
+    pub fn get_reply_attributes(&self) -> &[(String, String)] {
+        &self.reply_attributes
+    }
+
+    /// Returns whether the request matches all of the conditions.
+    pub fn matches(&self, dictionary: &Dictionary, request: &Packet) -> Result<bool, PolicyError> {
+        for condition in &self.conditions {
+            if !condition.matches(dictionary, request)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns the response of the rule to the request, with the reply attributes.
+    pub fn make_response(
+        &self,
+        dictionary: &Dictionary,
+        request: &Packet,
+    ) -> Result<Packet, PolicyError> {
+        let code = match (request.get_code(), self.reject) {
+            (Code::AccessRequest, false) => Code::AccessAccept,
+            (Code::AccessRequest, true) => Code::AccessReject,
+            (Code::AccountingRequest, false) => Code::AccountingResponse,
+            (Code::CoARequest, false) => Code::CoAACK,
+            (Code::CoARequest, true) => Code::CoANAK,
+            (Code::DisconnectRequest, false) => Code::DisconnectACK,
+            (Code::DisconnectRequest, true) => Code::DisconnectNAK,
+            (code, reject) => {
+                return Err(PolicyError::UnsupportedCodeError(
+                    code.string().to_owned(),
+                    if reject { "reject" } else { "accept" }.to_owned(),
+                ))
+            }
+        };
+        let mut response = request.make_response_packet(code);
+        for (name, value) in &self.reply_attributes {
+            dictionary.add(&mut response, name, value)?;
+        }
+        Ok(response)
+    }
+}
+
+/// An item of a line of a policy, i.e. `Name <operator> value`.
+enum Item {
+    Condition(Condition),
+    AuthType(bool),
+    Reply(String, String),
+}
+
+/// Returns the byte offsets of the character in a line that are out of the double quotes.
+fn find_unquoted(line: &str, target: char) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == target && !quoted => offsets.push(i),
+            _ => {}
+        }
+    }
+    offsets
+}
+
+/// Split a line by the commas that are out of the double quotes, and skip the empty items (e.g. of a trailing comma).
+fn split_items(line: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    for offset in find_unquoted(line, ',')
+        .into_iter()
+        .chain(std::iter::once(line.len()))
+    {
+        let item = line[start..offset].trim();
+        if !item.is_empty() {
+            items.push(item);
+        }
+        start = offset + 1;
+    }
+    items
+}
+
+/// Returns the value of a bare word or of a double-quoted string (with `\"` and `\\`).
+fn unquote(value: &str) -> Result<String, String> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        return match value.contains(char::is_whitespace) {
+            true => Err(format!("unquoted value with spaces: {value}")),
+            false => Ok(value.to_owned()),
+        };
+    };
+    let mut unquoted = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(c @ ('"' | '\\')) => unquoted.push(c),
+                _ => return Err(format!("invalid escape in {value}")),
+            },
+            '"' if chars.as_str().is_empty() => return Ok(unquoted),
+            '"' => return Err(format!("trailing characters after {value}")),
+            c => unquoted.push(c),
+        }
+    }
+    Err(format!("unterminated string: {value}"))
+}
+
+/// A list of the rules that answers the requests (see `radius::policy`).
+#[derive(Debug, Clone)]
+pub struct Policy {
+    dictionary: Dictionary,
+    rules: Vec<Rule>,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy::new()
+    }
+}
+
+impl Policy {
+    /// Returns a policy without the rules, of the built-in dictionary.
+    pub fn new() -> Self {
+        Policy::with_dictionary(Dictionary::built_in())
+    }
+
+    /// Returns a policy without the rules, of the dictionary, e.g. the one that has been loaded from the dictionary
+    /// files of FreeRADIUS for the attributes that the crate doesn't define.
+    pub fn with_dictionary(dictionary: Dictionary) -> Self {
+        Policy {
+            dictionary,
+            rules: vec![],
+        }
+    }
+
+    /// Load a policy file of the built-in dictionary.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, PolicyError> {
+        let mut policy = Policy::new();
+        policy.load_file(path)?;
+        Ok(policy)
+    }
+
+    /// Add the rules of a policy file after the existing ones.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), PolicyError> {
+        let path = path.as_ref();
+        let source = path.display().to_string();
+        let content = fs::read_to_string(path)
+            .map_err(|e| PolicyError::FailedReadingError(format!("{source}: {e}")))?;
+        self.parse(&content, &source)
+    }
+
+    /// Add the rules of the content of a policy file after the existing ones.
+    pub fn parse_str(&mut self, content: &str) -> Result<(), PolicyError> {
+        self.parse(content, "<string>")
+    }
+
+    pub fn get_dictionary(&self) -> &Dictionary {
+        &self.dictionary
+    }
+
+    pub fn get_rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Returns the first rule that the request matches, or `None` if no rule matches.
+    pub fn lookup_rule(&self, request: &Packet) -> Result<Option<&Rule>, PolicyError> {
+        for rule in &self.rules {
+            if rule.matches(&self.dictionary, request)? {
+                return Ok(Some(rule));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the response of the first rule that the request matches, or `None` if no rule matches.
+    pub fn evaluate(&self, request: &Packet) -> Result<Option<Packet>, PolicyError> {
+        self.lookup_rule(request)?
+            .map(|rule| rule.make_response(&self.dictionary, request))
+            .transpose()
+    }
+
+    fn parse(&mut self, content: &str, source: &str) -> Result<(), PolicyError> {
+        let mut rules = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            let invalid =
+                |message: String| PolicyError::InvalidRuleError(source.to_owned(), i + 1, message);
+            let line = match find_unquoted(line, '#').first() {
+                Some(&offset) => &line[..offset],
+                None => line,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let indented = line.starts_with(char::is_whitespace);
+            let line = line.trim();
+            if !indented {
+                let items = match line.strip_prefix(DEFAULT_RULE) {
+                    Some(rest) if rest.is_empty() || rest.starts_with(char::is_whitespace) => rest,
+                    _ => line,
+                };
+                let mut rule = Rule {
+                    line: i + 1,
+                    conditions: vec![],
+                    reject: false,
+                    reply_attributes: vec![],
+                };
+                for item in split_items(items) {
+                    match self.parse_item(item).map_err(invalid)? {
+                        Item::Condition(condition) => rule.conditions.push(condition),
+                        Item::AuthType(reject) => rule.reject = reject,
+                        Item::Reply(name, _) => {
+                            return Err(invalid(format!(
+                                "reply attribute {name} on the first line of a rule"
+                            )))
+                        }
+                    }
+                }
+                rules.push(rule);
+                continue;
+            }
+
+            let Some(rule) = rules.last_mut() else {
+                return Err(invalid("indented line before any rule".to_owned()));
+            };
+            for item in split_items(line) {
+                match self.parse_item(item).map_err(invalid)? {
+                    Item::Condition(condition) => {
+                        return Err(invalid(format!(
+                            "condition on {} in the reply of a rule",
+                            condition.name
+                        )))
+                    }
+                    Item::AuthType(reject) => rule.reject = reject,
+                    Item::Reply(name, value) => rule.reply_attributes.push((name, value)),
+                }
+            }
+        }
+        self.rules.extend(rules);
+        Ok(())
+    }
+
+    fn parse_item(&self, item: &str) -> Result<Item, String> {
+        let name_end = item
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '!' | ':'))
+            .unwrap_or(item.len());
+        let (name, rest) = item.split_at(name_end);
+        let rest = rest.trim_start();
+        let (operator, value) = ["==", "!=", "=*", "!*", ":=", "="]
+            .iter()
+            .find_map(|operator| {
+                rest.strip_prefix(operator)
+                    .map(|value| (*operator, value.trim()))
+            })
+            .ok_or_else(|| format!("no operator in {item:?}"))?;
+        if name.is_empty() {
+            return Err(format!("no attribute name in {item:?}"));
+        }
+        let value = unquote(value)?;
+
+        if name.eq_ignore_ascii_case(AUTH_TYPE) {
+            return match operator {
+                ":=" if value.eq_ignore_ascii_case("Accept") => Ok(Item::AuthType(false)),
+                ":=" if value.eq_ignore_ascii_case("Reject") => Ok(Item::AuthType(true)),
+                ":=" => Err(format!("{AUTH_TYPE} must be Accept or Reject: {value}")),
+                _ => Err(format!("{AUTH_TYPE} must be set by :=")),
+            };
+        }
+        if name.eq_ignore_ascii_case(PACKET_TYPE) {
+            let code = REQUEST_CODES
+                .iter()
+                .find(|code| code.string().eq_ignore_ascii_case(&value));
+            return match (operator, code) {
+                ("==" | "!=", Some(code)) => Ok(Item::Condition(Condition {
+                    name: PACKET_TYPE.to_owned(),
+                    operator: if operator == "==" {
+                        Operator::Equal
+                    } else {
+                        Operator::NotEqual
+                    },
+                    value: code.string().to_owned(),
+                })),
+                ("==" | "!=", None) => Err(format!("unknown {PACKET_TYPE}: {value}")),
+                _ => Err(format!("{PACKET_TYPE} must be compared by == or !=")),
+            };
+        }
+
+        let attribute = self
+            .dictionary
+            .lookup_attribute_by_name(name)
+            .ok_or_else(|| format!("unknown attribute: {name}"))?;
+        let name = attribute.get_name().to_owned();
+        // the passwords can't be parsed without a packet, so they are compared and added as they are
+        let canonical = |value: &str| match attribute.get_encrypt() {
+            Some(_) => Ok(value.to_owned()),
+            None => self
+                .dictionary
+                .parse_value(&name, value)
+                .and_then(|avp| self.dictionary.format_value(&avp))
+                .map_err(|e| e.to_string()),
+        };
+        let operator = match operator {
+            // the reply keeps the value as it is (e.g. the tag of `Tunnel-Type = 1:VLAN`), since that is added by the
+            // dictionary
+            "=" => {
+                canonical(&value)?;
+                return Ok(Item::Reply(name.clone(), value));
+            }
+            "==" => Operator::Equal,
+            "!=" => Operator::NotEqual,
+            "=*" => Operator::Present,
+            "!*" => Operator::Absent,
+            _ => return Err(format!("{operator} is only for {AUTH_TYPE}")),
+        };
+        let value = match operator {
+            Operator::Equal | Operator::NotEqual => canonical(&value)?,
+            Operator::Present | Operator::Absent => String::new(),
+        };
+        Ok(Item::Condition(Condition {
+            name,
+            operator,
+            value,
+        }))
+    }
+}
+
+impl FromStr for Policy {
+    type Err = PolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = Policy::new();
+        policy.parse_str(s)?;
+        Ok(policy)
+    }
+}
+
+/// A `RequestHandler` that answers the requests by a `Policy`.
+///
+/// The requests that no rule matches are `PolicyError::NoMatchingRuleError`, i.e. they are not answered; a policy
+/// that ends with `DEFAULT Auth-Type := Reject` answers all of the Access-Requests.
+#[cfg(feature = "tokio")]
+pub struct PolicyHandler {
+    policy: Policy,
+}
+
+#[cfg(feature = "tokio")]
+impl PolicyHandler {
+    pub fn new(policy: Policy) -> Self {
+        PolicyHandler { policy }
+    }
+
+    pub fn get_policy(&self) -> &Policy {
+        &self.policy
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[async_trait]
+impl RequestHandler<(), PolicyError> for PolicyHandler {
+    async fn handle_radius_request(
+        &self,
+        conn: &UdpSocket,
+        request: &Request,
+    ) -> Result<(), PolicyError> {
+        let response = self
+            .policy
+            .evaluate(request.get_packet())?
+            .ok_or(PolicyError::NoMatchingRuleError())?;
+        send_response(conn, request, &response)
+            .await
+            .map_err(|e| PolicyError::FailedSendingResponseError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "tokio")]
+    use std::net::SocketAddr;
+    #[cfg(feature = "tokio")]
+    use std::time::Duration;
+
+    #[cfg(feature = "tokio")]
+    use crate::client::Client;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2865, rfc2866};
+    use crate::error::ErrorKind;
+    #[cfg(feature = "tokio")]
+    use crate::policy::PolicyHandler;
+    use crate::policy::{Operator, Policy, PolicyError};
+    #[cfg(feature = "tokio")]
+    use crate::server::{SecretProvider, SecretProviderError, Server};
+
+    const POLICY: &str = r#"
+# the staff
+User-Name == "alice", User-Password == "p@ssw0rd" # a comment
+    Reply-Message = "welcome, \"alice\"",
+    Session-Timeout = 3600
+    Service-Type = 2
+
+User-Name == "mallory"
+    Auth-Type := Reject
+    Reply-Message = "go away"
+
+Packet-Type == Accounting-Request, Acct-Status-Type != Interim-Update
+
+DEFAULT Auth-Type := Reject
+"#;
+
+    fn access_request(user_name: &str, password: &[u8]) -> Packet {
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, user_name);
+        rfc2865::add_user_password(&mut request, password).unwrap();
+        request
+    }
+
+    #[test]
+    fn test_parse() {
+        let policy: Policy = POLICY.parse().unwrap();
+        let rules = policy.get_rules();
+        assert_eq!(rules.len(), 4);
+        assert_eq!(rules[0].get_line(), 3);
+        assert!(!rules[0].is_reject());
+        assert_eq!(rules[0].get_conditions().len(), 2);
+        assert_eq!(rules[0].get_conditions()[0].get_name(), "User-Name");
+        assert_eq!(rules[0].get_conditions()[0].get_operator(), Operator::Equal);
+        assert_eq!(rules[0].get_conditions()[0].get_value(), "alice");
+        assert_eq!(
+            rules[0].get_reply_attributes(),
+            &[
+                ("Reply-Message".to_owned(), "welcome, \"alice\"".to_owned()),
+                ("Session-Timeout".to_owned(), "3600".to_owned()),
+                ("Service-Type".to_owned(), "2".to_owned()),
+            ]
+        );
+        assert!(rules[1].is_reject());
+        // the values of the conditions are in the notation of the dictionary
+        assert_eq!(
+            rules[2].get_conditions()[0].get_value(),
+            "Accounting-Request"
+        );
+        assert_eq!(
+            rules[2].get_conditions()[1].get_operator(),
+            Operator::NotEqual
+        );
+        // Alive is the first name of the value 3 in the dictionary, i.e. the one that the requests are formatted in
+        assert_eq!(rules[2].get_conditions()[1].get_value(), "Alive");
+        assert!(rules[3].get_conditions().is_empty());
+        assert!(rules[3].is_reject());
+
+        for (content, line) in [
+            ("    Reply-Message = \"orphan\"", 1),
+            ("DEFAULT\nNo-Such-Attribute == 1", 2),
+            ("Session-Timeout == forever", 1),
+            ("User-Name == \"unterminated", 1),
+            ("User-Name \"alice\"", 1),
+            ("DEFAULT Auth-Type := Maybe", 1),
+            ("DEFAULT Reply-Message = \"first line\"", 1),
+            ("DEFAULT\n    User-Name == \"alice\"", 2),
+            ("Packet-Type == Access-Accept", 1),
+        ] {
+            match content.parse::<Policy>() {
+                Err(PolicyError::InvalidRuleError(source, l, _)) => {
+                    assert_eq!(source, "<string>");
+                    assert_eq!(l, line, "{content}");
+                }
+                result => panic!("unexpected result of {content:?}: {result:?}"),
+            }
+        }
+        let e = "DEFAULT = 1".parse::<Policy>().unwrap_err();
+        assert_eq!(e.get_kind(), ErrorKind::Configuration);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let policy: Policy = POLICY.parse().unwrap();
+        let dictionary = policy.get_dictionary();
+
+        let request = access_request("alice", b"p@ssw0rd");
+        let response = policy.evaluate(&request).unwrap().unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(response.get_identifier(), request.get_identifier());
+        assert_eq!(
+            rfc2865::lookup_reply_message(&response).unwrap().unwrap(),
+            "welcome, \"alice\""
+        );
+        assert_eq!(
+            rfc2865::lookup_session_timeout(&response).unwrap().unwrap(),
+            3600
+        );
+        assert_eq!(
+            dictionary
+                .lookup(&response, "Service-Type")
+                .unwrap()
+                .unwrap(),
+            "Framed-User"
+        );
+
+        // the wrong password falls through to DEFAULT
+        let request = access_request("alice", b"wrong");
+        let rule = policy.lookup_rule(&request).unwrap().unwrap();
+        assert_eq!(rule.get_line(), 14);
+        let response = policy.evaluate(&request).unwrap().unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+        assert!(response.get_attributes().is_empty());
+
+        let request = access_request("mallory", b"p@ssw0rd");
+        let response = policy.evaluate(&request).unwrap().unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+        assert_eq!(
+            rfc2865::lookup_reply_message(&response).unwrap().unwrap(),
+            "go away"
+        );
+
+        let mut request = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut request, rfc2866::ACCT_STATUS_TYPE_START);
+        let response = policy.evaluate(&request).unwrap().unwrap();
+        assert_eq!(response.get_code(), Code::AccountingResponse);
+        // an Interim-Update falls through to DEFAULT, which can't reject an Accounting-Request
+        let mut request = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut request, rfc2866::ACCT_STATUS_TYPE_INTERIM_UPDATE);
+        let e = policy.evaluate(&request).unwrap_err();
+        assert_eq!(
+            e,
+            PolicyError::UnsupportedCodeError("Accounting-Request".to_owned(), "reject".to_owned())
+        );
+
+        let policy: Policy = "User-Name =* ANY\nUser-Name !* ANY\n    Auth-Type := Reject"
+            .parse()
+            .unwrap();
+        let request = access_request("anyone", b"");
+        assert_eq!(
+            policy.evaluate(&request).unwrap().unwrap().get_code(),
+            Code::AccessAccept
+        );
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        assert_eq!(
+            policy.evaluate(&request).unwrap().unwrap().get_code(),
+            Code::AccessReject
+        );
+        let request = Packet::new(Code::CoARequest, b"secret");
+        assert_eq!(
+            policy.evaluate(&request).unwrap().unwrap().get_code(),
+            Code::CoANAK
+        );
+
+        let policy: Policy = "User-Name == \"bob\"".parse().unwrap();
+        let request = access_request("alice", b"p@ssw0rd");
+        assert!(policy.evaluate(&request).unwrap().is_none());
+    }
+
+    #[cfg(feature = "tokio")]
+    struct StaticSecretProvider {}
+
+    #[cfg(feature = "tokio")]
+    impl SecretProvider for StaticSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_policy_handler() {
+        let handler = PolicyHandler::new(POLICY.parse().unwrap());
+        assert_eq!(handler.get_policy().get_rules().len(), 4);
+        let servers = Server::builder(handler, StaticSecretProvider {})
+            .listener("127.0.0.1", 0)
+            .build()
+            .await
+            .unwrap();
+        let addr = servers[0].get_listen_address().unwrap();
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let client = Client::new(None, Some(Duration::from_secs(1)));
+        let response = client
+            .send_packet(&addr, &access_request("alice", b"p@ssw0rd"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        let response = client
+            .send_packet(&addr, &access_request("bob", b"p@ssw0rd"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+    }
+}
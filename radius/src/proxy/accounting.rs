@@ -0,0 +1,393 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::client::{Client, ClientError};
+use crate::core::accounting;
+use crate::core::code::Code;
+use crate::core::packet::Packet;
+use crate::core::request::Request;
+use crate::core::rfc2869;
+use crate::correlation;
+use crate::error::ErrorKind;
+
+#[derive(Error, Debug)]
+pub enum AccountingProxyError {
+    /// An error that is raised when it fails to read or write the spool file.
+    #[error("failed to access the spool file: {0}")]
+    SpoolError(#[from] io::Error),
+    /// An error that is raised when the spool file has a corrupted entry.
+    #[error("corrupted spool entry: {0}")]
+    CorruptedSpoolEntryError(String),
+    /// An error that is raised when it fails to forward a request to the upstream
+    /// (only if the immediate acknowledgement is disabled).
+    #[error("failed to forward the request to the upstream: {0}")]
+    ForwardingError(#[from] ClientError),
+    /// An error that is raised when the upstream returns a packet that is not Accounting-Response.
+    #[error("unexpected response from the upstream: {0}")]
+    UnexpectedResponseError(String),
+}
+
+impl AccountingProxyError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            AccountingProxyError::SpoolError(_)
+            | AccountingProxyError::CorruptedSpoolEntryError(_) => ErrorKind::Storage,
+            AccountingProxyError::ForwardingError(e) => e.get_kind(),
+            AccountingProxyError::UnexpectedResponseError(_) => ErrorKind::UnexpectedResponse,
+        }
+    }
+
+    /// Returns whether the request may succeed later (see `ClientError::is_retryable()`).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            AccountingProxyError::ForwardingError(e) => e.is_retryable(),
+            e => e.get_kind().is_retryable(),
+        }
+    }
+}
+
+/// A proxy that forwards Accounting-Request packets to an upstream server with store-and-forward buffering.
+///
+/// When the upstream doesn't respond, the request is appended to the spool file and the NAS is acknowledged
+/// immediately (this is the common proxy behavior; it can be disabled by `set_acknowledge_immediately()`).
+/// The spooled requests are replayed in the received order by `replay()`, and their Acct-Delay-Time is
+/// increased by the time that they have been spooled.
+///
+/// While the spool has pending requests, new requests are appended to the spool instead of being forwarded
+/// directly so that the upstream receives them in order.
+pub struct AccountingProxy {
+    client: Client,
+    upstream_addr: SocketAddr,
+    upstream_secret: Vec<u8>,
+    spool_path: PathBuf,
+    acknowledge_immediately: bool,
+    spool_lock: Mutex<()>,
+}
+
+impl AccountingProxy {
+    /// A constructor for a proxy.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A client to send the requests to the upstream. The socket timeout of this client decides
+    ///   how long it waits for the upstream before spooling a request.
+    /// * `upstream_addr` - The address of the upstream accounting server.
+    /// * `upstream_secret` - The shared-secret between this proxy and the upstream.
+    /// * `spool_path` - The path of the spool file. The pending requests of the previous run are replayed as well.
+    pub fn new<P: AsRef<Path>>(
+        client: Client,
+        upstream_addr: SocketAddr,
+        upstream_secret: &[u8],
+        spool_path: P,
+    ) -> Self {
+        AccountingProxy {
+            client,
+            upstream_addr,
+            upstream_secret: upstream_secret.to_vec(),
+            spool_path: spool_path.as_ref().to_path_buf(),
+            acknowledge_immediately: true,
+            spool_lock: Mutex::new(()),
+        }
+    }
+
+    /// Set a flag to specify whether to acknowledge the NAS when the request is spooled (default: `true`).
+    ///
+    /// If this is `false`, `forward()` returns an error without spooling when the upstream doesn't respond,
+    /// so it is up to the NAS to retransmit the request.
+    pub fn set_acknowledge_immediately(&mut self, acknowledge_immediately: bool) {
+        self.acknowledge_immediately = acknowledge_immediately;
+    }
+
+    /// Forward an Accounting-Request to the upstream and return an Accounting-Response for the NAS.
+    pub async fn forward(&self, request: &Request) -> Result<Packet, AccountingProxyError> {
+        let request_packet = request.get_packet();
+        let upstream_packet = self.make_upstream_packet(request_packet);
+
+        if self.acknowledge_immediately && self.has_pending_requests().await? {
+            self.spool(&upstream_packet).await?;
+        } else {
+            match self.send(&upstream_packet).await {
+                Ok(_) => {}
+                Err(AccountingProxyError::ForwardingError(e)) if self.acknowledge_immediately => {
+                    info!(
+                        "failed to forward an accounting request to {}, spooling it; {}{}",
+                        self.upstream_addr,
+                        e,
+                        correlation::log_suffix()
+                    );
+                    self.spool(&upstream_packet).await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(request_packet.make_response_packet(Code::AccountingResponse))
+    }
+
+    /// Replay the spooled requests in order, and return the number of the requests that have been forwarded.
+    ///
+    /// This stops at the first request that the upstream doesn't respond to; the rest of them are kept in the spool.
+    pub async fn replay(&self) -> Result<usize, AccountingProxyError> {
+        let _guard = self.spool_lock.lock().await;
+
+        let entries = match fs::read_to_string(&self.spool_path) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        let entries: Vec<&str> = entries.lines().filter(|line| !line.is_empty()).collect();
+
+        let mut forwarded = 0;
+        for entry in &entries {
+            let packet = self.decode_spool_entry(entry)?;
+            match self.send(&packet).await {
+                Ok(_) => forwarded += 1,
+                Err(AccountingProxyError::ForwardingError(e)) => {
+                    debug!(
+                        "upstream {} is still unavailable; {}",
+                        self.upstream_addr, e
+                    );
+                    break;
+                }
+                Err(AccountingProxyError::UnexpectedResponseError(e)) => {
+                    // the upstream is alive but it doesn't accept this request; drop it not to block the others
+                    warn!("dropping a spooled accounting request; {}", e);
+                    forwarded += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        if forwarded > 0 {
+            let rest = &entries[forwarded..];
+            if rest.is_empty() {
+                fs::remove_file(&self.spool_path)?;
+            } else {
+                let mut tmp_path = self.spool_path.clone().into_os_string();
+                tmp_path.push(".tmp");
+                fs::write(&tmp_path, format!("{}\n", rest.join("\n")))?;
+                fs::rename(&tmp_path, &self.spool_path)?;
+            }
+        }
+        Ok(forwarded)
+    }
+
+    /// Replay the spooled requests repeatedly at the given interval. This never returns.
+    pub async fn run_replay_loop(&self, interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+            if let Err(e) = self.replay().await {
+                error!("failed to replay the spooled accounting requests; {}", e);
+            }
+        }
+    }
+
+    /// Returns whether the spool has requests that are not forwarded yet.
+    pub async fn has_pending_requests(&self) -> Result<bool, AccountingProxyError> {
+        let _guard = self.spool_lock.lock().await;
+        match fs::metadata(&self.spool_path) {
+            Ok(metadata) => Ok(metadata.len() > 0),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn make_upstream_packet(&self, request_packet: &Packet) -> Packet {
+        let mut packet = Packet::new(request_packet.get_code(), &self.upstream_secret);
+        packet.extend(
+            request_packet
+                .get_attributes()
+                .iter()
+                .filter(|avp| avp.typ != rfc2869::MESSAGE_AUTHENTICATOR_TYPE)
+                .cloned()
+                .collect(),
+        );
+        packet
+    }
+
+    async fn send(&self, packet: &Packet) -> Result<(), AccountingProxyError> {
+        let response = self.client.send_packet(&self.upstream_addr, packet).await?;
+        match response.get_code() {
+            Code::AccountingResponse => Ok(()),
+            code => Err(AccountingProxyError::UnexpectedResponseError(
+                code.string().to_owned(),
+            )),
+        }
+    }
+
+    async fn spool(&self, packet: &Packet) -> Result<(), AccountingProxyError> {
+        let encoded = packet
+            .encode()
+            .map_err(|e| AccountingProxyError::CorruptedSpoolEntryError(e.to_string()))?;
+        let spooled_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let _guard = self.spool_lock.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.spool_path)?;
+        file.write_all(format!("{} {}\n", spooled_at, to_hex(&encoded)).as_bytes())?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    fn decode_spool_entry(&self, entry: &str) -> Result<Packet, AccountingProxyError> {
+        let corrupted = || AccountingProxyError::CorruptedSpoolEntryError(entry.to_owned());
+
+        let (spooled_at, encoded) = entry.split_once(' ').ok_or_else(corrupted)?;
+        let spooled_at: u64 = spooled_at.parse().map_err(|_| corrupted())?;
+        let encoded = from_hex(encoded).ok_or_else(corrupted)?;
+        let mut packet =
+            Packet::decode(&encoded, &self.upstream_secret).map_err(|_| corrupted())?;
+
+        let initial_delay_time =
+            accounting::lookup_acct_delay_time_or_zero(&packet).map_err(|_| corrupted())?;
+        let spooled_for = (UNIX_EPOCH + Duration::from_secs(spooled_at))
+            .elapsed()
+            .unwrap_or_default();
+        accounting::update_acct_delay_time(&mut packet, initial_delay_time, spooled_for);
+        Ok(packet)
+    }
+}
+
+fn to_hex(bs: &[u8]) -> String {
+    bs.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::client::{Client, ClientError};
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::{accounting, rfc2866};
+    use crate::proxy::{AccountingProxy, AccountingProxyError};
+
+    fn make_request(session_id: &str) -> Request {
+        let mut packet = Packet::new(Code::AccountingRequest, b"nas-secret");
+        rfc2866::add_acct_status_type(&mut packet, rfc2866::ACCT_STATUS_TYPE_START);
+        rfc2866::add_acct_session_id(&mut packet, session_id);
+        Request::new(
+            "127.0.0.1:1813".parse::<SocketAddr>().unwrap(),
+            "127.0.0.1:50000".parse::<SocketAddr>().unwrap(),
+            packet,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_store_and_forward() {
+        let dir = std::env::temp_dir().join(format!("radius-proxy-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let spool_path = dir.join("acct.spool");
+
+        // the upstream doesn't respond at first
+        let upstream = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let upstream_addr = upstream.local_addr().unwrap();
+
+        let proxy = AccountingProxy::new(
+            Client::new(None, Some(Duration::from_millis(100))),
+            upstream_addr,
+            b"upstream-secret",
+            &spool_path,
+        );
+
+        for session_id in ["s1", "s2"] {
+            let response = proxy.forward(&make_request(session_id)).await.unwrap();
+            assert_eq!(response.get_code(), Code::AccountingResponse);
+        }
+        assert!(proxy.has_pending_requests().await.unwrap());
+
+        // drain the requests that have been received while it was "down"
+        let mut buf = vec![0; 4096];
+        while tokio::time::timeout(Duration::from_millis(10), upstream.recv_from(&mut buf))
+            .await
+            .is_ok()
+        {}
+
+        let upstream_task = tokio::spawn(async move {
+            let mut session_ids = vec![];
+            while session_ids.len() < 2 {
+                let (len, remote_addr) = upstream.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"upstream-secret").unwrap();
+                assert!(accounting::lookup_acct_delay_time_or_zero(&request).is_ok());
+                session_ids.push(rfc2866::lookup_acct_session_id(&request).unwrap().unwrap());
+                let response = request.make_response_packet(Code::AccountingResponse);
+                upstream
+                    .send_to(&response.encode().unwrap(), remote_addr)
+                    .await
+                    .unwrap();
+            }
+            session_ids
+        });
+
+        assert_eq!(proxy.replay().await.unwrap(), 2);
+        assert_eq!(upstream_task.await.unwrap(), vec!["s1", "s2"]);
+        assert!(!proxy.has_pending_requests().await.unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_forged_upstream_response() {
+        let dir = std::env::temp_dir().join(format!("radius-proxy-forged-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // the upstream only responds by a forged Accounting-Response (of the wrong secret)
+        let upstream = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let upstream_addr = upstream.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            let (len, from) = upstream.recv_from(&mut buf).await.unwrap();
+            let forged = Packet::decode(&buf[..len], b"wrong")
+                .unwrap()
+                .make_response_packet(Code::AccountingResponse);
+            upstream
+                .send_to(&forged.encode().unwrap(), from)
+                .await
+                .unwrap();
+        });
+
+        let mut proxy = AccountingProxy::new(
+            Client::new(None, Some(Duration::from_millis(300))),
+            upstream_addr,
+            b"upstream-secret",
+            dir.join("acct.spool"),
+        );
+        proxy.set_acknowledge_immediately(false);
+        // the forged response is dropped, so the request is timed out
+        assert!(matches!(
+            proxy.forward(&make_request("s1")).await,
+            Err(AccountingProxyError::ForwardingError(
+                ClientError::SocketTimeoutError(_)
+            ))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,434 @@
+use std::io;
+
+use async_trait::async_trait;
+use rand::Rng;
+use thiserror::Error;
+use tokio::net::UdpSocket;
+
+use crate::client::failover::FailoverClient;
+use crate::client::ClientError;
+use crate::core::avp::AVPError;
+#[cfg(feature = "rfc2548")]
+use crate::core::avp::AVP;
+use crate::core::code::Code;
+use crate::core::packet::Packet;
+use crate::core::random;
+use crate::core::request::Request;
+#[cfg(feature = "rfc2548")]
+use crate::core::rfc2548;
+#[cfg(feature = "rfc2548")]
+use crate::core::vsa;
+use crate::core::{rfc2865, rfc2869};
+use crate::correlation;
+use crate::error::ErrorKind;
+use crate::server::{send_response, RequestHandler};
+
+#[derive(Error, Debug)]
+pub enum RequestProxyError {
+    /// An error that is raised when it fails to forward a request to the all upstreams.
+    #[error("failed to forward the request to the upstream: {0}")]
+    ForwardingError(#[from] ClientError),
+    /// An error that is raised when an encrypted attribute can't be re-encrypted for the upstream or the NAS.
+    #[error("failed to re-encrypt the attribute: {0}")]
+    ReencryptionError(#[from] AVPError),
+    /// An error that is raised when the response of the upstream doesn't have the Proxy-State of this proxy.
+    #[error("the response of the upstream doesn't have the Proxy-State of the proxy")]
+    MissingProxyStateError(),
+    /// An error that is raised when the upstream returns a packet that doesn't respond to the request.
+    #[error("unexpected response from the upstream: {0}")]
+    UnexpectedResponseError(String),
+    /// An error that is raised when it fails to send the response to the NAS.
+    #[error("failed to respond to the NAS: {0}")]
+    RespondingError(#[from] io::Error),
+}
+
+impl RequestProxyError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            RequestProxyError::ForwardingError(e) => e.get_kind(),
+            RequestProxyError::ReencryptionError(e) => e.get_kind(),
+            RequestProxyError::MissingProxyStateError()
+            | RequestProxyError::UnexpectedResponseError(_) => ErrorKind::UnexpectedResponse,
+            RequestProxyError::RespondingError(_) => ErrorKind::Io,
+        }
+    }
+
+    /// Returns whether the request may succeed later (see `ClientError::is_retryable()`).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RequestProxyError::ForwardingError(e) => e.is_retryable(),
+            e => e.get_kind().is_retryable(),
+        }
+    }
+}
+
+/// A proxy that forwards the requests of the NASes to the upstream servers and returns their responses (RFC 2865
+/// section 2.3).
+///
+/// Each forwarded request is a new packet of the upstream, i.e. it has the identifier and the Request Authenticator of
+/// its own, the attributes of the request with User-Password re-encrypted by the upstream secret, and a Proxy-State of
+/// this proxy at the end. CHAP-Password of a request without CHAP-Challenge gets the Request Authenticator of the NAS
+/// as CHAP-Challenge, since the CHAP challenge is that. The requests are sent to the upstreams by `FailoverClient`, so
+/// a dead upstream fails over to the next one.
+///
+/// The response to the NAS has the code and the attributes of the response of the upstream without the Proxy-State of
+/// this proxy; the response without that is an error, since that is not a response to this proxy. With the `rfc2548`
+/// feature, MS-MPPE-Send-Key and MS-MPPE-Recv-Key are re-encrypted for the NAS; the other encrypted attributes of the
+/// responses (e.g. Tunnel-Password) are passed as they are.
+///
+/// This is a `RequestHandler`, so a server proxies the requests by that; or `forward()` proxies a request in a handler
+/// (e.g. for a realm).
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use radius::client::Client;
+/// use radius::client::failover::FailoverClient;
+/// use radius::proxy::RequestProxy;
+///
+/// let proxy = RequestProxy::new(
+///     FailoverClient::new(
+///         Client::new(Some(Duration::from_secs(1)), Some(Duration::from_secs(3))),
+///         &["198.51.100.1:1812".parse().unwrap(), "198.51.100.2:1812".parse().unwrap()],
+///     ),
+///     b"upstream-secret",
+/// );
+/// ```
+pub struct RequestProxy {
+    upstream: FailoverClient,
+    upstream_secret: Vec<u8>,
+}
+
+impl RequestProxy {
+    /// A constructor for a proxy to the upstreams of the shared-secret.
+    pub fn new(upstream: FailoverClient, upstream_secret: &[u8]) -> Self {
+        RequestProxy {
+            upstream,
+            upstream_secret: upstream_secret.to_vec(),
+        }
+    }
+
+    /// Returns the client of the upstreams, e.g. to see the states of them.
+    pub fn get_upstream(&self) -> &FailoverClient {
+        &self.upstream
+    }
+
+    /// Forward a request to the upstreams and return the response for the NAS.
+    pub async fn forward(&self, request: &Request) -> Result<Packet, RequestProxyError> {
+        let request_packet = request.get_packet();
+        let proxy_state: [u8; 16] = random::rng().gen();
+        let upstream_request = self.make_upstream_request(request_packet, &proxy_state)?;
+
+        let upstream_response = self.upstream.send_packet(&upstream_request).await?;
+        if !is_response_code(request_packet.get_code(), upstream_response.get_code()) {
+            return Err(RequestProxyError::UnexpectedResponseError(
+                upstream_response.get_code().string().to_owned(),
+            ));
+        }
+        if !rfc2865::lookup_all_proxy_state(&upstream_response).contains(&proxy_state.to_vec()) {
+            return Err(RequestProxyError::MissingProxyStateError());
+        }
+
+        let response = make_response(
+            request_packet,
+            &upstream_request,
+            &upstream_response,
+            &proxy_state,
+        )?;
+        debug!(
+            "proxied {} from {} as {}{}",
+            request_packet.get_code().string(),
+            request.get_remote_addr(),
+            response.get_code().string(),
+            correlation::log_suffix()
+        );
+        Ok(response)
+    }
+
+    fn make_upstream_request(
+        &self,
+        request_packet: &Packet,
+        proxy_state: &[u8],
+    ) -> Result<Packet, RequestProxyError> {
+        let mut packet = Packet::new(request_packet.get_code(), &self.upstream_secret);
+        for avp in request_packet.get_attributes() {
+            match avp.get_type() {
+                rfc2869::MESSAGE_AUTHENTICATOR_TYPE | rfc2865::USER_PASSWORD_TYPE => {}
+                _ => packet.add(avp.clone()),
+            }
+        }
+        if let Some(password) = rfc2865::lookup_user_password(request_packet) {
+            rfc2865::add_user_password(&mut packet, &password?)?;
+        }
+        if rfc2865::lookup_chap_password(request_packet).is_some()
+            && rfc2865::lookup_chap_challenge(request_packet).is_none()
+        {
+            rfc2865::add_chap_challenge(&mut packet, request_packet.get_authenticator());
+        }
+        rfc2865::add_proxy_state(&mut packet, proxy_state);
+        if request_packet.get_code() == Code::AccessRequest
+            || rfc2869::lookup_message_authenticator(request_packet).is_some()
+        {
+            packet.add_message_authenticator();
+        }
+        Ok(packet)
+    }
+}
+
+fn is_response_code(request_code: Code, response_code: Code) -> bool {
+    match request_code {
+        Code::AccessRequest => matches!(
+            response_code,
+            Code::AccessAccept | Code::AccessReject | Code::AccessChallenge
+        ),
+        Code::AccountingRequest => response_code == Code::AccountingResponse,
+        Code::DisconnectRequest => {
+            matches!(response_code, Code::DisconnectACK | Code::DisconnectNAK)
+        }
+        Code::CoARequest => matches!(response_code, Code::CoAACK | Code::CoANAK),
+        _ => false,
+    }
+}
+
+#[cfg_attr(not(feature = "rfc2548"), allow(unused_variables))]
+fn make_response(
+    request_packet: &Packet,
+    upstream_request: &Packet,
+    upstream_response: &Packet,
+    proxy_state: &[u8],
+) -> Result<Packet, RequestProxyError> {
+    // this has Message-Authenticator if the request has that; the Proxy-State attributes are taken from the upstream
+    // response, which echoes those of the request
+    let mut response =
+        request_packet.make_response_packet_without_proxy_state(upstream_response.get_code());
+    let mut own_proxy_state_removed = false;
+    for avp in upstream_response.get_attributes() {
+        match avp.get_type() {
+            rfc2869::MESSAGE_AUTHENTICATOR_TYPE => {}
+            rfc2865::PROXY_STATE_TYPE
+                if avp.get_value() == proxy_state && !own_proxy_state_removed =>
+            {
+                own_proxy_state_removed = true;
+            }
+            #[cfg(feature = "rfc2548")]
+            rfc2865::VENDOR_SPECIFIC_TYPE if is_mppe_key(avp) => {}
+            _ => response.add(avp.clone()),
+        }
+    }
+
+    #[cfg(feature = "rfc2548")]
+    {
+        let upstream_authenticator = upstream_request.get_authenticator();
+        if let Some(key) =
+            rfc2548::lookup_ms_mppe_send_key(upstream_response, upstream_authenticator)
+        {
+            rfc2548::add_ms_mppe_send_key(&mut response, &key?)?;
+        }
+        if let Some(key) =
+            rfc2548::lookup_ms_mppe_recv_key(upstream_response, upstream_authenticator)
+        {
+            rfc2548::add_ms_mppe_recv_key(&mut response, &key?)?;
+        }
+    }
+    Ok(response)
+}
+
+/// Returns whether a Vendor-Specific attribute carries MS-MPPE-Send-Key or MS-MPPE-Recv-Key, which are encrypted by
+/// the Request Authenticator.
+#[cfg(feature = "rfc2548")]
+fn is_mppe_key(avp: &AVP) -> bool {
+    match vsa::decode_vendor_specific(avp) {
+        Ok((rfc2548::VENDOR_MICROSOFT, tlvs)) => tlvs.iter().any(|tlv| {
+            matches!(
+                tlv.get_type(),
+                rfc2548::MS_MPPE_SEND_KEY_TYPE | rfc2548::MS_MPPE_RECV_KEY_TYPE
+            )
+        }),
+        _ => false,
+    }
+}
+
+#[async_trait]
+impl RequestHandler<(), RequestProxyError> for RequestProxy {
+    async fn handle_radius_request(
+        &self,
+        conn: &UdpSocket,
+        request: &Request,
+    ) -> Result<(), RequestProxyError> {
+        let response = self.forward(request).await?;
+        send_response(conn, request, &response).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+
+    use crate::client::failover::FailoverClient;
+    use crate::client::Client;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::rfc2865;
+    use crate::proxy::RequestProxy;
+    use crate::server::{
+        send_response, RequestHandler, SecretProvider, SecretProviderError, Server,
+    };
+
+    struct UpstreamHandler {}
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for UpstreamHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let request = req.get_packet();
+            let password = rfc2865::lookup_user_password(request).unwrap().unwrap();
+            let code = if password == b"hunter2" {
+                Code::AccessAccept
+            } else {
+                Code::AccessReject
+            };
+            let mut response = request.make_response_packet(code);
+            rfc2865::add_reply_message(&mut response, "hello");
+            #[cfg(feature = "rfc2548")]
+            crate::core::rfc2548::add_ms_mppe_send_key(&mut response, &[7; 32]).unwrap();
+            send_response(conn, req, &response).await
+        }
+    }
+
+    struct StaticSecretProvider {
+        secret: &'static [u8],
+    }
+
+    impl SecretProvider for StaticSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(self.secret.to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_proxy() {
+        let mut upstream = Server::listen(
+            "127.0.0.1",
+            0,
+            UpstreamHandler {},
+            StaticSecretProvider {
+                secret: b"upstream-secret",
+            },
+        )
+        .await
+        .unwrap();
+        let upstream_addr = upstream.get_listen_address().unwrap();
+        tokio::spawn(async move { upstream.run(std::future::pending::<()>()).await });
+
+        // the first upstream doesn't respond, so the proxy fails over to the second one
+        let dead_upstream = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut client = Client::new(None, Some(Duration::from_millis(300)));
+        client.set_max_retransmissions(0);
+        let proxy = RequestProxy::new(
+            FailoverClient::new(
+                client,
+                &[dead_upstream.local_addr().unwrap(), upstream_addr],
+            ),
+            b"upstream-secret",
+        );
+        let mut proxy_server = Server::listen(
+            "127.0.0.1",
+            0,
+            proxy,
+            StaticSecretProvider {
+                secret: b"nas-secret",
+            },
+        )
+        .await
+        .unwrap();
+        let proxy_addr = proxy_server.get_listen_address().unwrap();
+        tokio::spawn(async move { proxy_server.run(std::future::pending::<()>()).await });
+
+        let nas = Client::new(None, Some(Duration::from_secs(3)));
+        for (password, code) in [
+            (&b"hunter2"[..], Code::AccessAccept),
+            (&b"wrong"[..], Code::AccessReject),
+        ] {
+            let mut request = Packet::new(Code::AccessRequest, b"nas-secret");
+            rfc2865::add_user_name(&mut request, "alice");
+            rfc2865::add_user_password(&mut request, password).unwrap();
+            rfc2865::add_proxy_state(&mut request, b"nas");
+            let response = nas.send_packet(&proxy_addr, &request).await.unwrap();
+
+            assert_eq!(response.get_code(), code);
+            assert_eq!(response.get_identifier(), request.get_identifier());
+            assert_eq!(
+                rfc2865::lookup_reply_message(&response).unwrap().unwrap(),
+                "hello"
+            );
+            // the Proxy-State of the proxy is stripped, and the one of the NAS is kept
+            assert_eq!(
+                rfc2865::lookup_all_proxy_state(&response),
+                vec![b"nas".to_vec()]
+            );
+            #[cfg(feature = "rfc2548")]
+            assert_eq!(
+                crate::core::rfc2548::lookup_ms_mppe_send_key(
+                    &response,
+                    request.get_authenticator()
+                )
+                .unwrap()
+                .unwrap(),
+                vec![7; 32]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forged_upstream_response() {
+        let upstream = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let upstream_addr = upstream.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            let (len, from) = upstream.recv_from(&mut buf).await.unwrap();
+            // a forged Access-Accept (of the wrong secret) precedes the genuine Access-Reject
+            let forged = Packet::decode(&buf[..len], b"wrong")
+                .unwrap()
+                .make_response_packet(Code::AccessAccept);
+            let request = Packet::decode(&buf[..len], b"upstream-secret").unwrap();
+            let response = request.make_response_packet(Code::AccessReject);
+            for packet in [forged, response] {
+                upstream
+                    .send_to(&packet.encode().unwrap(), from)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let proxy = RequestProxy::new(
+            FailoverClient::new(
+                Client::new(None, Some(Duration::from_secs(3))),
+                &[upstream_addr],
+            ),
+            b"upstream-secret",
+        );
+        let mut packet = Packet::new(Code::AccessRequest, b"nas-secret");
+        rfc2865::add_user_name(&mut packet, "alice");
+        rfc2865::add_user_password(&mut packet, b"hunter2").unwrap();
+        let request = Request::new(
+            "127.0.0.1:1812".parse().unwrap(),
+            "127.0.0.1:50000".parse().unwrap(),
+            packet,
+        );
+        let response = proxy.forward(&request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+    }
+}
@@ -0,0 +1,12 @@
+//! Proxying the RADIUS requests to the upstream servers.
+//!
+//! `RequestProxy` forwards the requests to the upstream servers with the Proxy-State of its own, and returns the
+//! responses of them to the NASes; it fails over among the upstreams.
+//! `AccountingProxy` forwards the Accounting-Request packets to an upstream accounting server;
+//! while the upstream is unavailable, the packets are buffered in a spool file and replayed in order later.
+
+mod accounting;
+mod forwarding;
+
+pub use accounting::{AccountingProxy, AccountingProxyError};
+pub use forwarding::{RequestProxy, RequestProxyError};
@@ -0,0 +1,59 @@
+//! RadSec, i.e. RADIUS over TLS (RFC 6614).
+//!
+//! RadSec carries the RADIUS packets over a TLS connection (on TCP port 2083), delimited by the Length field of
+//! the RADIUS header as RADIUS/TCP (see `radius::tcp`), and all of the packets have the fixed shared secret `radsec`
+//! (`RADSEC_SECRET`); the peers are authenticated by the certificates instead.
+//!
+//! `RadSecStream` is a `Transport` (see `radius::transport`) on top of any stream of the TLS library, so the client
+//! (`GenericClient::send_packet_over()`) and the server (`TransportServer` with `RadSecSecretProvider`) speak RadSec
+//! with it. The TLS handshake and the verification of the certificates (e.g. the CA of the federation) are up to the
+//! TLS library; give the verified identity of the peer (e.g. the subject of the certificate) to `RadSecStream::new()`.
+//!
+//! With the `radsec-rustls` feature, `RadSecConnector` and `RadSecAcceptor` do the handshake by tokio-rustls and make
+//! the streams whose peers have the subject of the verified certificate as the identity, and `RadSecServer` accepts
+//! the connections and serves the requests on each of them by a `TransportServer`, as `radius::tcp::TcpServer` does.
+//!
+//! ```ignore
+//! // a client with the rustls config that has the CA of the server and the certificate of the client
+//! let connector = RadSecConnector::new(Arc::new(client_config));
+//! let stream = connector.connect(server_addr, "radius.example.org".try_into()?).await?;
+//! let client = GenericClient::new(TokioRuntime, Some(Duration::from_secs(10)));
+//! let response = client
+//!     .send_packet_over(&stream, stream.get_peer(), &Packet::new(Code::AccessRequest, RADSEC_SECRET))
+//!     .await?;
+//! ```
+//!
+//! The TLS secrets of the sessions are written in the `SSLKEYLOGFILE` format to decrypt them in Wireshark by
+//! `set_key_log()` of `RadSecConnector`, `RadSecAcceptor` and `RadSecServer` (opt-in), e.g. with
+//! `radius::keylog::KeyLogFile::from_env()`.
+
+use std::net::SocketAddr;
+
+use crate::server::{SecretProvider, SecretProviderError};
+use crate::tcp::StreamTransport;
+pub use crate::tcp::{read_packet, write_packet};
+
+#[cfg(feature = "radsec-rustls")]
+mod tls;
+#[cfg(feature = "radsec-rustls")]
+pub use tls::{
+    RadSecAcceptor, RadSecClientStream, RadSecConnector, RadSecServer, RadSecServerStream,
+};
+
+/// The shared secret of all of the RadSec packets.
+pub const RADSEC_SECRET: &[u8] = b"radsec";
+/// The port of RadSec.
+pub const DEFAULT_RADSEC_PORT: u16 = 2083;
+
+/// A RadSec connection as a transport, on top of a TLS stream.
+pub type RadSecStream<S> = StreamTransport<S>;
+
+/// The secret provider of the RadSec server, i.e. `RADSEC_SECRET` for any peer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RadSecSecretProvider;
+
+impl SecretProvider for RadSecSecretProvider {
+    fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        Ok(RADSEC_SECRET.to_vec())
+    }
+}
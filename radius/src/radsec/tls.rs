@@ -0,0 +1,351 @@
+//! The client and the server of RadSec on tokio-rustls.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use rustls::pki_types::{CertificateDer, ServerName};
+use rustls::{ClientConfig, KeyLog, ServerConfig};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+use crate::core::packet::MAX_PACKET_LENGTH;
+use crate::radsec::{RadSecSecretProvider, RadSecStream};
+use crate::server::transport::{TransportRequestHandler, TransportServer};
+use crate::tasks;
+use crate::transport::Peer;
+
+/// A RadSec connection to a server by `RadSecConnector`.
+pub type RadSecClientStream = RadSecStream<tokio_rustls::client::TlsStream<TcpStream>>;
+/// A RadSec connection from a client by `RadSecAcceptor`.
+pub type RadSecServerStream = RadSecStream<tokio_rustls::server::TlsStream<TcpStream>>;
+
+/// Returns the peer of the address with the subject (e.g. `CN=nas.example.org`) of the end-entity certificate of the
+/// chain as the identity, or without the identity if the peer has not presented a certificate.
+fn peer_of_certificates(addr: SocketAddr, certificates: Option<&[CertificateDer<'_>]>) -> Peer {
+    let subject = certificates
+        .and_then(|certificates| certificates.first())
+        .and_then(|certificate| x509_parser::parse_x509_certificate(certificate).ok())
+        .map(|(_, certificate)| certificate.subject().to_string());
+    match subject {
+        Some(subject) => Peer::with_identity(addr, &subject),
+        None => Peer::new(addr),
+    }
+}
+
+/// A connector of the RadSec client on tokio-rustls.
+///
+/// This is available with the `radsec-rustls` feature.
+#[derive(Clone)]
+pub struct RadSecConnector {
+    config: Arc<ClientConfig>,
+}
+
+impl RadSecConnector {
+    /// A constructor for a connector of the rustls config, which has the CA of the servers to verify them, and the
+    /// certificate of the client for the mutual authentication.
+    pub fn new(config: Arc<ClientConfig>) -> Self {
+        RadSecConnector { config }
+    }
+
+    /// Set the key log of the TLS secrets of the connections, e.g. `KeyLogFile::from_env()` (default: none).
+    pub fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) {
+        Arc::make_mut(&mut self.config).key_log = key_log;
+    }
+
+    /// Connect to the RadSec server, and verify its certificate for the server name.
+    ///
+    /// The peer of the stream has the subject of the certificate of the server as the identity.
+    pub async fn connect(
+        &self,
+        remote_addr: SocketAddr,
+        server_name: ServerName<'static>,
+    ) -> io::Result<RadSecClientStream> {
+        let stream = TcpStream::connect(remote_addr).await?;
+        let local_addr = stream.local_addr()?;
+        let stream = TlsConnector::from(self.config.clone())
+            .connect(server_name, stream)
+            .await?;
+        let peer = peer_of_certificates(remote_addr, stream.get_ref().1.peer_certificates());
+        let mut transport = RadSecStream::new(stream, peer);
+        transport.set_local_addr(local_addr);
+        Ok(transport)
+    }
+}
+
+/// An acceptor of the RadSec server on tokio-rustls.
+///
+/// This is available with the `radsec-rustls` feature.
+#[derive(Clone)]
+pub struct RadSecAcceptor {
+    config: Arc<ServerConfig>,
+}
+
+impl RadSecAcceptor {
+    /// A constructor for an acceptor of the rustls config, which has the certificate of the server, and the verifier
+    /// of the certificates of the clients (e.g. `WebPkiClientVerifier` of the CA of the federation).
+    pub fn new(config: Arc<ServerConfig>) -> Self {
+        RadSecAcceptor { config }
+    }
+
+    /// Set the key log of the TLS secrets of the connections, e.g. `KeyLogFile::from_env()` (default: none).
+    pub fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) {
+        Arc::make_mut(&mut self.config).key_log = key_log;
+    }
+
+    /// Do the handshake on an accepted TCP connection.
+    ///
+    /// The peer of the stream has the subject of the verified certificate of the client as the identity, if the
+    /// client has presented one.
+    pub async fn accept(&self, stream: TcpStream) -> io::Result<RadSecServerStream> {
+        let remote_addr = stream.peer_addr()?;
+        let local_addr = stream.local_addr()?;
+        let stream = TlsAcceptor::from(self.config.clone())
+            .accept(stream)
+            .await?;
+        let peer = peer_of_certificates(remote_addr, stream.get_ref().1.peer_certificates());
+        let mut transport = RadSecStream::new(stream, peer);
+        transport.set_local_addr(local_addr);
+        Ok(transport)
+    }
+}
+
+/// A RadSec server on tokio-rustls, that serves the requests on each of the connections.
+///
+/// This is available with the `radsec-rustls` feature.
+pub struct RadSecServer<X, E, T>
+where
+    E: Debug,
+    T: TransportRequestHandler<RadSecServerStream, X, E>,
+{
+    listener: TcpListener,
+    acceptor: RadSecAcceptor,
+    request_handler_arc: Arc<T>,
+    _phantom_return_type: PhantomData<X>,
+    _phantom_error_type: PhantomData<E>,
+}
+
+impl<X, E, T> RadSecServer<X, E, T>
+where
+    X: Send + Sync + 'static,
+    E: Debug + Send + Sync + 'static,
+    T: TransportRequestHandler<RadSecServerStream, X, E>,
+{
+    /// Starts TCP listening for the RadSec server (e.g. on `0.0.0.0` and `DEFAULT_RADSEC_PORT`) with the rustls
+    /// config of `RadSecAcceptor::new()`.
+    pub async fn listen(
+        host: &str,
+        port: u16,
+        config: Arc<ServerConfig>,
+        request_handler: T,
+    ) -> Result<Self, io::Error> {
+        let listener = TcpListener::bind(format!("{host}:{port}")).await?;
+        Ok(RadSecServer {
+            listener,
+            acceptor: RadSecAcceptor::new(config),
+            request_handler_arc: Arc::new(request_handler),
+            _phantom_return_type: Default::default(),
+            _phantom_error_type: Default::default(),
+        })
+    }
+
+    /// Set the key log of the TLS secrets of the connections (see `RadSecAcceptor::set_key_log()`).
+    pub fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) {
+        self.acceptor.set_key_log(key_log);
+    }
+
+    /// Returns the listening address.
+    pub fn get_listen_address(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Starts accepting the connections and handling the RADIUS requests on them.
+    ///
+    /// This returns when accepting has failed, or when `shutdown_trigger` has been resolved.
+    pub async fn run(&self, shutdown_trigger: impl Future) -> Result<(), io::Error> {
+        tokio::select! {
+            res = self.accept_loop() => {
+                res
+            }
+            _ = shutdown_trigger => {
+                info!("server is shutting down");
+                Ok(())
+            }
+        }
+    }
+
+    async fn accept_loop(&self) -> Result<(), io::Error> {
+        loop {
+            let (stream, remote_addr) = self.listener.accept().await?;
+            let acceptor = self.acceptor.clone();
+            let request_handler_arc = self.request_handler_arc.clone();
+            // the handshake is done in the task, so a slow client doesn't block accepting the others
+            tasks::spawn(&tasks::RADSEC_CONNECTION, async move {
+                let transport = match acceptor.accept(stream).await {
+                    Ok(transport) => transport,
+                    Err(e) => {
+                        info!("failed the TLS handshake with {}; {}", remote_addr, e);
+                        return;
+                    }
+                };
+                let mut server =
+                    TransportServer::new(transport, request_handler_arc, RadSecSecretProvider);
+                server.set_buffer_size(MAX_PACKET_LENGTH);
+                if let Err(e) = server.run(std::future::pending::<()>()).await {
+                    debug!("connection from {} has been closed; {}", remote_addr, e);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, KeyPair};
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+    use rustls::server::WebPkiClientVerifier;
+    use rustls::{ClientConfig, RootCertStore, ServerConfig};
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::keylog::KeyLogFile;
+    use crate::radsec::tls::{RadSecConnector, RadSecServer, RadSecServerStream};
+    use crate::radsec::RADSEC_SECRET;
+    use crate::runtime::{GenericClient, TokioRuntime};
+    use crate::server::transport::TransportRequestHandler;
+    use crate::transport::{Peer, Transport};
+
+    struct AcceptingHandler {}
+
+    #[async_trait]
+    impl TransportRequestHandler<RadSecServerStream, (), io::Error> for AcceptingHandler {
+        async fn handle_radius_request(
+            &self,
+            transport: &RadSecServerStream,
+            peer: &Peer,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            assert_eq!(peer.get_identity(), Some("CN=nas.example.org"));
+            let response = req.get_packet().make_response_packet(Code::AccessAccept);
+            transport.send_to(&response.encode().unwrap(), peer).await
+        }
+    }
+
+    /// Returns the certificate of the CA, and the chains and the keys of the server and the client that it has issued.
+    fn certificates() -> (
+        CertificateDer<'static>,
+        (CertificateDer<'static>, PrivateKeyDer<'static>),
+        (CertificateDer<'static>, PrivateKeyDer<'static>),
+    ) {
+        let mut params = CertificateParams::new(Vec::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "radius test CA");
+        let ca_key = KeyPair::generate().unwrap();
+        let ca = params.self_signed(&ca_key).unwrap();
+
+        let issue = |name: &str| {
+            let mut params = CertificateParams::new(vec![name.to_owned()]).unwrap();
+            params.distinguished_name = rcgen::DistinguishedName::new();
+            params.distinguished_name.push(DnType::CommonName, name);
+            let key = KeyPair::generate().unwrap();
+            let certificate = params.signed_by(&key, &ca, &ca_key).unwrap();
+            (
+                certificate.der().clone(),
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key.serialize_der())),
+            )
+        };
+        (
+            ca.der().clone(),
+            issue("localhost"),
+            issue("nas.example.org"),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let (ca, (server_certificate, server_key), (client_certificate, client_key)) =
+            certificates();
+        let mut roots = RootCertStore::empty();
+        roots.add(ca).unwrap();
+        let roots = Arc::new(roots);
+
+        let server_config = ServerConfig::builder()
+            .with_client_cert_verifier(
+                WebPkiClientVerifier::builder(roots.clone())
+                    .build()
+                    .unwrap(),
+            )
+            .with_single_cert(vec![server_certificate], server_key)
+            .unwrap();
+        let server =
+            RadSecServer::listen("127.0.0.1", 0, Arc::new(server_config), AcceptingHandler {})
+                .await
+                .unwrap();
+        let server_addr = server.get_listen_address().unwrap();
+        tokio::spawn(async move { server.run(std::future::pending::<()>()).await });
+
+        let client_config = ClientConfig::builder()
+            .with_root_certificates(roots.clone())
+            .with_client_auth_cert(vec![client_certificate], client_key)
+            .unwrap();
+        let key_log_path =
+            std::env::temp_dir().join(format!("radius-radsec-keylog-{}", std::process::id()));
+        let mut connector = RadSecConnector::new(Arc::new(client_config));
+        connector.set_key_log(Arc::new(KeyLogFile::open(&key_log_path).unwrap()));
+        let stream = connector
+            .connect(server_addr, "localhost".try_into().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(stream.get_peer().get_identity(), Some("CN=localhost"));
+        let key_log = std::fs::read_to_string(&key_log_path).unwrap();
+        assert!(
+            key_log.contains("CLIENT_HANDSHAKE_TRAFFIC_SECRET "),
+            "{key_log}"
+        );
+        std::fs::remove_file(&key_log_path).unwrap();
+
+        let client = GenericClient::new(TokioRuntime, Some(Duration::from_secs(3)));
+        for _ in 0..2 {
+            let response = client
+                .send_packet_over(
+                    &stream,
+                    stream.get_peer(),
+                    &Packet::new(Code::AccessRequest, RADSEC_SECRET),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+
+        // the server rejects a client without a certificate; in TLS 1.3 that is after the handshake of the client
+        let client_config = ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let rejected = match RadSecConnector::new(Arc::new(client_config))
+            .connect(server_addr, "localhost".try_into().unwrap())
+            .await
+        {
+            Ok(stream) => client
+                .send_packet_over(
+                    &stream,
+                    stream.get_peer(),
+                    &Packet::new(Code::AccessRequest, RADSEC_SECRET),
+                )
+                .await
+                .is_err(),
+            Err(_) => true,
+        };
+        assert!(rejected);
+    }
+}
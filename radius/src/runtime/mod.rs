@@ -0,0 +1,342 @@
+//! Runtime-agnostic RADIUS client and server.
+//!
+//! `Client` and `Server` are built on tokio (the `tokio` feature, enabled by default). For the other async runtimes,
+//! `GenericClient` sends the requests through a minimal socket abstraction: `Runtime` binds an `AsyncUdpSocket`
+//! and sleeps. The adapters are provided for tokio (`TokioRuntime`, with the `tokio` feature) and
+//! smol/async-std (`SmolRuntime`, with the `smol` feature); the other runtimes can implement the traits by themselves.
+//!
+//! `GenericServer` serves the requests on a socket of a runtime that can spawn the tasks as well (`Spawn`); a server
+//! for the other event loops can be composed of the sans-io components in `radius::core::sansio`.
+//!
+//! `GenericClient::send_packet_over()` sends a request through a custom `Transport` (see `radius::transport`)
+//! instead of a UDP socket of the runtime.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "tokio")]
+//! # #[tokio::main]
+//! # async fn main() {
+//! use std::time::Duration;
+//!
+//! use radius::core::code::Code;
+//! use radius::core::packet::Packet;
+//! use radius::runtime::{GenericClient, TokioRuntime};
+//!
+//! let client = GenericClient::new(TokioRuntime, Some(Duration::from_secs(3)));
+//! let request = Packet::new(Code::AccessRequest, b"secret");
+//! let response = client
+//!     .send_packet(&"127.0.0.1:1812".parse().unwrap(), &request)
+//!     .await;
+//! # }
+//! # #[cfg(not(feature = "tokio"))]
+//! # fn main() {}
+//! ```
+
+mod server;
+#[cfg(feature = "smol")]
+mod smol_runtime;
+#[cfg(feature = "tokio")]
+mod tokio_runtime;
+
+pub use server::{GenericRequestHandler, GenericServer};
+#[cfg(feature = "smol")]
+pub use smol_runtime::SmolRuntime;
+#[cfg(feature = "tokio")]
+pub use tokio_runtime::TokioRuntime;
+
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::client::{local_bind_addr, ClientError, Deadline, TimeoutDetails};
+use crate::core::deterministic;
+use crate::core::packet::Packet;
+use crate::core::sansio::ClientTransaction;
+use crate::transport::{Peer, Transport};
+
+/// An unconnected UDP socket of an async runtime.
+#[async_trait]
+pub trait AsyncUdpSocket: Send + Sync {
+    /// Send a datagram to the target, and return the number of the bytes that have been sent.
+    async fn send_to(&self, buf: &[u8], target: SocketAddr) -> io::Result<usize>;
+    /// Receive a datagram, and return the number of the bytes and the source address.
+    async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)>;
+    /// Returns the local address of the socket (default: the `Unsupported` error).
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the local address of the socket is not available",
+        ))
+    }
+}
+
+/// The facilities of an async runtime that the RADIUS client needs.
+#[async_trait]
+pub trait Runtime: 'static + Send + Sync {
+    type Socket: AsyncUdpSocket;
+
+    /// Bind a UDP socket to the local address.
+    async fn bind(&self, local_addr: SocketAddr) -> io::Result<Self::Socket>;
+    /// Wait until the duration has elapsed.
+    async fn sleep(&self, duration: Duration);
+}
+
+/// The spawning of the tasks of an async runtime that `GenericServer` needs.
+pub trait Spawn: Runtime {
+    /// Run the future on a task in the background.
+    fn spawn(&self, future: Pin<Box<dyn Future<Output = ()> + Send + 'static>>);
+}
+
+/// Run the future with the timeout by the sleep of the runtime; it returns `None` if the timeout has exceeded.
+pub async fn timeout<R: Runtime + ?Sized, F: Future>(
+    runtime: &R,
+    duration: Duration,
+    future: F,
+) -> Option<F::Output> {
+    let mut future = Box::pin(future);
+    let mut sleep = runtime.sleep(duration);
+    std::future::poll_fn(move |cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        match Pin::new(&mut sleep).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    })
+    .await
+}
+
+/// A RADIUS client on top of any async runtime.
+///
+/// This behaves like `radius::client::Client`, but the socket is not connected;
+/// the datagrams that come from the other addresses than the destination are ignored.
+pub struct GenericClient<R: Runtime> {
+    runtime: R,
+    receive_timeout: Option<Duration>,
+    total_timeout: Option<Duration>,
+    max_retransmissions: u32,
+    add_event_timestamp: bool,
+    require_message_authenticator: bool,
+    local_addr: Option<SocketAddr>,
+}
+
+impl<R: Runtime> GenericClient<R> {
+    const MAX_DATAGRAM_SIZE: usize = 65507;
+
+    /// A constructor for a client.
+    ///
+    /// # Arguments
+    ///
+    /// * `runtime` - An adapter of the async runtime.
+    /// * `receive_timeout` - A duration of receive timeout of each attempt. If the response is not returned in time, it retransmits the request
+    ///   (see `set_max_retransmissions()`) or the `SocketTimeoutError` occurs. If this value is `None`, it never timed-out.
+    ///
+    /// The overall deadline of a request is set by `set_total_timeout()`.
+    pub fn new(runtime: R, receive_timeout: Option<Duration>) -> Self {
+        GenericClient {
+            runtime,
+            receive_timeout,
+            total_timeout: None,
+            max_retransmissions: 0,
+            add_event_timestamp: true,
+            require_message_authenticator: false,
+            local_addr: None,
+        }
+    }
+
+    /// Set the receive timeout of each attempt (see `new()`).
+    pub fn set_receive_timeout(&mut self, receive_timeout: Option<Duration>) {
+        self.receive_timeout = receive_timeout;
+    }
+
+    /// Set the total timeout of a request, i.e. the deadline of all the attempts (default: `None`, i.e. no deadline).
+    ///
+    /// See `Client::set_total_timeout()`.
+    pub fn set_total_timeout(&mut self, total_timeout: Option<Duration>) {
+        self.total_timeout = total_timeout;
+    }
+
+    /// Set the maximum number of retransmissions on socket timeout (default: `0`, i.e. no retransmission).
+    pub fn set_max_retransmissions(&mut self, max_retransmissions: u32) {
+        self.max_retransmissions = max_retransmissions;
+    }
+
+    /// Set a flag to specify whether to add Event-Timestamp to the Accounting-Request, CoA-Request and Disconnect-Request
+    /// that don't have that yet (default: `true`).
+    pub fn set_add_event_timestamp(&mut self, add_event_timestamp: bool) {
+        self.add_event_timestamp = add_event_timestamp;
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator against the forgery of the responses (default: `false`).
+    ///
+    /// See `Client::set_require_message_authenticator()`.
+    pub fn set_require_message_authenticator(&mut self, require_message_authenticator: bool) {
+        self.require_message_authenticator = require_message_authenticator;
+    }
+
+    /// Set the local address to bind the sockets to (default: `None`, i.e. `0.0.0.0:0` or `[::]:0` by the destination).
+    ///
+    /// See `Client::set_local_addr()`.
+    pub fn set_local_addr(&mut self, local_addr: Option<SocketAddr>) {
+        self.local_addr = local_addr;
+    }
+
+    /// This method sends a packet to the destination.
+    ///
+    /// This method retransmits the packet only when the socket timeout has exceeded and the retransmission is enabled by `set_max_retransmissions()`.
+    pub async fn send_packet(
+        &self,
+        remote_addr: &SocketAddr,
+        request_packet: &Packet,
+    ) -> Result<Packet, ClientError> {
+        let deadline = Deadline::new(self.total_timeout);
+        let local_addr = local_bind_addr(self.local_addr, remote_addr);
+
+        let conn = match self.runtime.bind(local_addr).await {
+            Ok(conn) => conn,
+            Err(e) => return Err(ClientError::FailedUdpSocketBindingError(e)),
+        };
+
+        self.exchange(
+            &SocketTransport(&conn),
+            &Peer::new(*remote_addr),
+            request_packet,
+            deadline,
+        )
+        .await
+    }
+
+    /// This method sends a packet to the peer through the transport, and receives the response from that.
+    ///
+    /// The datagrams from the other peers (by the address) are ignored; the timeouts and the retransmission are the same as `send_packet()`.
+    pub async fn send_packet_over<T: Transport + ?Sized>(
+        &self,
+        transport: &T,
+        peer: &Peer,
+        request_packet: &Packet,
+    ) -> Result<Packet, ClientError> {
+        let deadline = Deadline::new(self.total_timeout);
+        self.exchange(transport, peer, request_packet, deadline)
+            .await
+    }
+
+    async fn exchange<T: Transport + ?Sized>(
+        &self,
+        transport: &T,
+        peer: &Peer,
+        request_packet: &Packet,
+        deadline: Deadline,
+    ) -> Result<Packet, ClientError> {
+        let remote_addr = &peer.get_addr();
+        let mut transaction = ClientTransaction::new(
+            request_packet,
+            self.add_event_timestamp,
+            self.max_retransmissions,
+            &deterministic::now(),
+        )?;
+        transaction.set_require_message_authenticator(self.require_message_authenticator)?;
+        let first_sent_at = Instant::now();
+
+        let response = loop {
+            let response = match deadline.cap(self.receive_timeout) {
+                Some(receive_timeout) => {
+                    timeout(
+                        &self.runtime,
+                        receive_timeout,
+                        Self::request(transport, &transaction, peer),
+                    )
+                    .await
+                }
+                None => Some(Self::request(transport, &transaction, peer).await),
+            };
+            if let Some(response) = response {
+                break response;
+            }
+
+            let attempts = transaction.get_retransmissions() + 1;
+            if deadline.is_exceeded() {
+                return Err(deadline.exceeded(attempts));
+            }
+            if !transaction.on_timeout(first_sent_at.elapsed())? {
+                return Err(ClientError::SocketTimeoutError(TimeoutDetails::new(
+                    self.receive_timeout.unwrap_or_default(),
+                    attempts,
+                    deadline.elapsed(),
+                )));
+            }
+            debug!(
+                "retransmitting a request to {} ({}/{})",
+                remote_addr,
+                transaction.get_retransmissions(),
+                self.max_retransmissions
+            );
+        }?;
+
+        Ok(transaction.on_response(&response)?)
+    }
+
+    async fn request<T: Transport + ?Sized>(
+        transport: &T,
+        transaction: &ClientTransaction,
+        peer: &Peer,
+    ) -> Result<Vec<u8>, ClientError> {
+        let remote_addr = peer.get_addr();
+        if let Err(e) = transport
+            .send_to(transaction.get_request_datagram(), peer)
+            .await
+        {
+            return Err(ClientError::FailedSendingRadiusPacketError(
+                remote_addr.to_string(),
+                e,
+            ));
+        }
+
+        let mut buf = vec![0; Self::MAX_DATAGRAM_SIZE];
+        loop {
+            match transport.recv_from(&mut buf).await {
+                Ok((_, from)) if from.get_addr() != remote_addr => debug!(
+                    "ignoring a datagram from unexpected address {}",
+                    from.get_addr()
+                ),
+                Ok((len, _)) if !transaction.is_response(&buf[..len]) => debug!(
+                    "ignoring a datagram from {} that is not the response",
+                    remote_addr
+                ),
+                Ok((len, _)) => return Ok(buf[..len].to_vec()),
+                Err(e) => {
+                    return Err(ClientError::FailedReceivingResponseError(
+                        remote_addr.to_string(),
+                        e,
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// A socket of the runtime as a transport for a request.
+struct SocketTransport<'a, S: AsyncUdpSocket>(&'a S);
+
+#[async_trait]
+impl<S: AsyncUdpSocket> Transport for SocketTransport<'_, S> {
+    async fn send_to(&self, datagram: &[u8], peer: &Peer) -> io::Result<()> {
+        self.0.send_to(datagram, peer.get_addr()).await?;
+        Ok(())
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, Peer)> {
+        let (len, addr) = self.0.recv_from(buf).await?;
+        Ok((len, Peer::new(addr)))
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.local_addr()
+    }
+}
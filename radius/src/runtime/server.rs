@@ -0,0 +1,351 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::pin::pin;
+use std::sync::{Arc, RwLock};
+use std::task::Poll;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::core::code::Code;
+use crate::core::deterministic;
+use crate::core::request::Request;
+use crate::core::sansio::{
+    make_status_server_response, status_server_response_code, DecodingLimits, RequestDecoder,
+    RequestDecodingError, UndergoingRequests,
+};
+use crate::error::ErrorKind;
+use crate::runtime::{AsyncUdpSocket, Spawn};
+use crate::server::SecretProvider;
+
+const DEFAULT_BUFFER_SIZE: usize = 1500;
+
+/// A handler for the RADIUS requests of `GenericServer`.
+#[async_trait]
+pub trait GenericRequestHandler<S: AsyncUdpSocket, T, E>: 'static + Sync + Send {
+    /// This method has to implement the core feature of the server application what you need.
+    ///
+    /// # Arguments
+    ///
+    /// * conn - The socket of the server; send a response to `req.get_remote_addr()` through this.
+    /// * req - This is a request object that comes from the remote requester.
+    async fn handle_radius_request(&self, conn: &S, req: &Request) -> Result<T, E>;
+}
+
+#[async_trait]
+impl<S, T, E, H> GenericRequestHandler<S, T, E> for Arc<H>
+where
+    S: AsyncUdpSocket,
+    H: GenericRequestHandler<S, T, E>,
+{
+    async fn handle_radius_request(&self, conn: &S, req: &Request) -> Result<T, E> {
+        (**self).handle_radius_request(conn, req).await
+    }
+}
+
+/// A RADIUS server on top of any async runtime that can spawn the tasks (see `Spawn`).
+///
+/// This behaves like `radius::server::Server`: each request is handled on a task of its own, after the validation,
+/// the Status-Server response and the duplicate suppression.
+pub struct GenericServer<R, X, E, T, U>
+where
+    R: Spawn,
+    E: Debug,
+    T: GenericRequestHandler<R::Socket, X, E>,
+    U: SecretProvider,
+{
+    runtime: R,
+    conn_arc: Arc<R::Socket>,
+    local_addr: SocketAddr,
+    request_decoder: RequestDecoder,
+    buf_size: usize,
+    status_server_response_code: Option<Code>,
+    request_handler_arc: Arc<T>,
+    secret_provider_arc: Arc<U>,
+    undergoing_requests_lock_arc: Arc<RwLock<UndergoingRequests>>,
+    _phantom_return_type: PhantomData<X>,
+    _phantom_error_type: PhantomData<E>,
+}
+
+impl<R, X, E, T, U> GenericServer<R, X, E, T, U>
+where
+    R: Spawn,
+    E: Debug,
+    T: GenericRequestHandler<R::Socket, X, E>,
+    U: SecretProvider,
+{
+    /// Bind a socket of the runtime to the local address, and return a server on that.
+    pub async fn listen(
+        runtime: R,
+        local_addr: SocketAddr,
+        request_handler: T,
+        secret_provider: U,
+    ) -> Result<Self, io::Error> {
+        let conn = runtime.bind(local_addr).await?;
+        let local_addr = conn.local_addr().unwrap_or(local_addr);
+        Ok(GenericServer {
+            runtime,
+            conn_arc: Arc::new(conn),
+            local_addr,
+            request_decoder: RequestDecoder::new(),
+            buf_size: DEFAULT_BUFFER_SIZE,
+            status_server_response_code: Some(status_server_response_code(local_addr.port())),
+            request_handler_arc: Arc::new(request_handler),
+            secret_provider_arc: Arc::new(secret_provider),
+            undergoing_requests_lock_arc: Arc::new(RwLock::new(UndergoingRequests::new())),
+            _phantom_return_type: Default::default(),
+            _phantom_error_type: Default::default(),
+        })
+    }
+
+    /// Set a buffer size for receiving the request payload (default: `1500`).
+    pub fn set_buffer_size(&mut self, buf_size: usize) {
+        self.buf_size = buf_size;
+    }
+
+    /// Set a flag to specify whether to skip the authenticity validation or not (default: `false`).
+    pub fn set_skip_authenticity_validation(&mut self, skip_authenticity_validation: bool) {
+        self.request_decoder
+            .set_skip_authenticity_validation(skip_authenticity_validation);
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator of Access-Request
+    /// (see `Server::set_require_message_authenticator()`).
+    pub fn set_require_message_authenticator(&mut self, require_message_authenticator: bool) {
+        self.request_decoder
+            .set_require_message_authenticator(require_message_authenticator);
+    }
+
+    /// Set the allowed clock skew of Event-Timestamp (see `Server::set_event_timestamp_window()`).
+    pub fn set_event_timestamp_window(&mut self, event_timestamp_window: Option<Duration>) {
+        self.request_decoder
+            .set_event_timestamp_window(event_timestamp_window);
+    }
+
+    /// Set the limits of the requests (see `Server::set_decoding_limits()`).
+    pub fn set_decoding_limits(&mut self, decoding_limits: DecodingLimits) {
+        self.request_decoder.set_limits(decoding_limits);
+    }
+
+    /// Set the code of the response to Status-Server (see `Server::set_status_server_response_code()`).
+    pub fn set_status_server_response_code(&mut self, status_server_response_code: Option<Code>) {
+        self.status_server_response_code = status_server_response_code;
+    }
+
+    /// Returns the local address of the server; the port is the bound one if the given port was `0`
+    /// (and the socket of the runtime tells that, see `AsyncUdpSocket::local_addr()`).
+    pub fn get_local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Starts the RADIUS requests handling.
+    ///
+    /// This returns when receiving from the socket has failed, or when `shutdown_trigger` has been resolved.
+    pub async fn run(&self, shutdown_trigger: impl Future) -> Result<(), io::Error> {
+        let mut run_loop = pin!(self.run_loop());
+        let mut shutdown_trigger = pin!(shutdown_trigger);
+        std::future::poll_fn(|cx| {
+            if let Poll::Ready(res) = run_loop.as_mut().poll(cx) {
+                return Poll::Ready(res);
+            }
+            match shutdown_trigger.as_mut().poll(cx) {
+                Poll::Ready(_) => {
+                    info!("server is shutting down");
+                    Poll::Ready(Ok(()))
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    async fn run_loop(&self) -> Result<(), io::Error> {
+        loop {
+            let mut request_data = vec![0; self.buf_size];
+            let (size, remote_addr) = self.conn_arc.recv_from(&mut request_data).await?;
+            request_data.truncate(size);
+
+            let conn = self.conn_arc.clone();
+            let local_addr = self.local_addr;
+            let request_handler = self.request_handler_arc.clone();
+            let secret_provider = self.secret_provider_arc.clone();
+            let undergoing_requests_lock = self.undergoing_requests_lock_arc.clone();
+            let request_decoder = self.request_decoder.clone();
+            let status_server_response_code = self.status_server_response_code;
+
+            self.runtime.spawn(Box::pin(async move {
+                Self::process_request(
+                    &conn,
+                    &request_data,
+                    local_addr,
+                    remote_addr,
+                    &undergoing_requests_lock,
+                    &request_handler,
+                    &secret_provider,
+                    &request_decoder,
+                    status_server_response_code,
+                )
+                .await;
+            }));
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn process_request(
+        conn: &R::Socket,
+        request_data: &[u8],
+        local_addr: SocketAddr,
+        remote_addr: SocketAddr,
+        undergoing_requests_lock: &RwLock<UndergoingRequests>,
+        request_handler: &T,
+        secret_provider: &U,
+        request_decoder: &RequestDecoder,
+        status_server_response_code: Option<Code>,
+    ) {
+        let secret: Vec<u8> = match secret_provider.fetch_secret(remote_addr) {
+            Ok(secret) => secret,
+            Err(e) => {
+                error!(
+                    "failed to fetch secret binary vector from the secret provider; {}",
+                    e
+                );
+                return;
+            }
+        };
+
+        let packet = match request_decoder.decode(request_data, &secret, &deterministic::now()) {
+            Ok(packet) => packet,
+            Err(e @ RequestDecodingError::EmptySecretError()) => {
+                error!("{}", e);
+                return;
+            }
+            Err(e) if e.get_kind() == ErrorKind::Malformed => {
+                debug!("{}; failed request data => {:?}", e, request_data);
+                return;
+            }
+            Err(e) => {
+                info!("{}", e);
+                return;
+            }
+        };
+
+        if let (Code::StatusServer, Some(code)) = (packet.get_code(), status_server_response_code) {
+            let response = make_status_server_response(&packet, code);
+            match response.encode() {
+                Ok(response_data) => {
+                    if let Err(e) = conn.send_to(&response_data, remote_addr).await {
+                        error!("failed to respond to Status-Server; {}", e);
+                    }
+                }
+                Err(e) => error!("failed to encode the response to Status-Server; {}", e),
+            }
+            return;
+        }
+
+        let identifier = packet.get_identifier();
+        if !undergoing_requests_lock
+            .write()
+            .unwrap()
+            .begin(remote_addr, identifier)
+        {
+            return;
+        }
+
+        if let Err(e) = request_handler
+            .handle_radius_request(conn, &Request::new(local_addr, remote_addr, packet))
+            .await
+        {
+            error!("{:?}", e);
+        }
+
+        undergoing_requests_lock
+            .write()
+            .unwrap()
+            .finish(remote_addr, identifier);
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use std::io;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::rfc2865;
+    use crate::runtime::{GenericClient, GenericRequestHandler, GenericServer, TokioRuntime};
+    use crate::server::{SecretProvider, SecretProviderError};
+
+    struct EchoingHandler {}
+
+    #[async_trait]
+    impl GenericRequestHandler<UdpSocket, (), io::Error> for EchoingHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let mut response = req.get_packet().make_response_packet(Code::AccessAccept);
+            let user_name = rfc2865::lookup_user_name(req.get_packet())
+                .unwrap()
+                .unwrap();
+            rfc2865::add_reply_message(&mut response, &user_name);
+            conn.send_to(&response.encode().unwrap(), req.get_remote_addr())
+                .await?;
+            Ok(())
+        }
+    }
+
+    struct StaticSecretProvider {}
+
+    impl SecretProvider for StaticSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generic_server() {
+        let server = GenericServer::listen(
+            TokioRuntime,
+            "127.0.0.1:0".parse().unwrap(),
+            EchoingHandler {},
+            StaticSecretProvider {},
+        )
+        .await
+        .unwrap();
+        let server_addr = server.get_local_addr();
+        assert_ne!(server_addr.port(), 0);
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let handle = tokio::spawn(async move { server.run(shutdown_rx).await });
+
+        let client = GenericClient::new(TokioRuntime, Some(Duration::from_secs(3)));
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "alice");
+        let response = client.send_packet(&server_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(
+            rfc2865::lookup_reply_message(&response).unwrap().unwrap(),
+            "alice"
+        );
+
+        let mut status_server = Packet::new(Code::StatusServer, b"secret");
+        status_server.add_message_authenticator();
+        let response = client
+            .send_packet(&server_addr, &status_server)
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+
+        shutdown_tx.send(()).unwrap();
+        handle.await.unwrap().unwrap();
+    }
+}
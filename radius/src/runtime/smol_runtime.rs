@@ -0,0 +1,127 @@
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use smol::net::UdpSocket;
+
+use crate::runtime::{AsyncUdpSocket, Runtime, Spawn};
+
+/// The adapter of smol; this works with async-std as well, since they share the reactor (async-io).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmolRuntime;
+
+#[async_trait]
+impl AsyncUdpSocket for UdpSocket {
+    async fn send_to(&self, buf: &[u8], target: SocketAddr) -> io::Result<usize> {
+        UdpSocket::send_to(self, buf, target).await
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        UdpSocket::recv_from(self, buf).await
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        UdpSocket::local_addr(self)
+    }
+}
+
+#[async_trait]
+impl Runtime for SmolRuntime {
+    type Socket = UdpSocket;
+
+    async fn bind(&self, local_addr: SocketAddr) -> io::Result<Self::Socket> {
+        UdpSocket::bind(local_addr).await
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        smol::Timer::after(duration).await;
+    }
+}
+
+impl Spawn for SmolRuntime {
+    fn spawn(&self, future: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        smol::spawn(future).detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::{SocketAddr, UdpSocket};
+    use std::thread;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::runtime::{GenericClient, GenericRequestHandler, GenericServer, SmolRuntime};
+    use crate::server::{SecretProvider, SecretProviderError};
+
+    #[test]
+    fn test_send_packet() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let server_addr = server.local_addr().unwrap();
+        thread::spawn(move || {
+            let mut buf = vec![0; 4096];
+            let (len, addr) = server.recv_from(&mut buf).unwrap();
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            let response = request.make_response_packet(Code::AccountingResponse);
+            server.send_to(&response.encode().unwrap(), addr).unwrap();
+        });
+
+        let client = GenericClient::new(SmolRuntime, Some(Duration::from_secs(3)));
+        let request = Packet::new(Code::AccountingRequest, b"secret");
+        let response = smol::block_on(client.send_packet(&server_addr, &request)).unwrap();
+        assert_eq!(response.get_code(), Code::AccountingResponse);
+    }
+
+    struct AcceptingHandler {}
+
+    #[async_trait]
+    impl GenericRequestHandler<smol::net::UdpSocket, (), io::Error> for AcceptingHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &smol::net::UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let response = req.get_packet().make_response_packet(Code::AccessAccept);
+            conn.send_to(&response.encode().unwrap(), req.get_remote_addr())
+                .await?;
+            Ok(())
+        }
+    }
+
+    struct StaticSecretProvider {}
+
+    impl SecretProvider for StaticSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[test]
+    fn test_generic_server() {
+        smol::block_on(async {
+            let server = GenericServer::listen(
+                SmolRuntime,
+                "127.0.0.1:0".parse().unwrap(),
+                AcceptingHandler {},
+                StaticSecretProvider {},
+            )
+            .await
+            .unwrap();
+            let server_addr = server.get_local_addr();
+            smol::spawn(async move { server.run(std::future::pending::<()>()).await }).detach();
+
+            let client = GenericClient::new(SmolRuntime, Some(Duration::from_secs(3)));
+            let request = Packet::new(Code::AccessRequest, b"secret");
+            let response = client.send_packet(&server_addr, &request).await.unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        });
+    }
+}
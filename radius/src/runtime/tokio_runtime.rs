@@ -0,0 +1,116 @@
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::net::UdpSocket;
+
+use crate::runtime::{AsyncUdpSocket, Runtime, Spawn};
+
+/// The adapter of tokio.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioRuntime;
+
+#[async_trait]
+impl AsyncUdpSocket for UdpSocket {
+    async fn send_to(&self, buf: &[u8], target: SocketAddr) -> io::Result<usize> {
+        UdpSocket::send_to(self, buf, target).await
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        UdpSocket::recv_from(self, buf).await
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        UdpSocket::local_addr(self)
+    }
+}
+
+#[async_trait]
+impl Runtime for TokioRuntime {
+    type Socket = UdpSocket;
+
+    async fn bind(&self, local_addr: SocketAddr) -> io::Result<Self::Socket> {
+        UdpSocket::bind(local_addr).await
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await
+    }
+}
+
+impl Spawn for TokioRuntime {
+    fn spawn(&self, future: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        tokio::spawn(future);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::client::ClientError;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::runtime::{GenericClient, TokioRuntime};
+
+    #[tokio::test]
+    async fn test_send_packet() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            // ignore the first transmission to make the client retransmit
+            server.recv_from(&mut buf).await.unwrap();
+            let (len, addr) = server.recv_from(&mut buf).await.unwrap();
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            let response = request.make_response_packet(Code::AccessAccept);
+            server
+                .send_to(&response.encode().unwrap(), addr)
+                .await
+                .unwrap();
+        });
+
+        let mut client = GenericClient::new(TokioRuntime, Some(Duration::from_millis(200)));
+        client.set_max_retransmissions(1);
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let response = client.send_packet(&server_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+
+        let response = client.send_packet(&server_addr, &request).await;
+        assert!(matches!(response, Err(ClientError::SocketTimeoutError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_forged_responses() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 4096];
+            let (len, addr) = server.recv_from(&mut buf).await.unwrap();
+            // an Access-Accept of the wrong secret and that of the other identifier precede the genuine Access-Reject
+            let forged = Packet::decode(&buf[..len], b"wrong")
+                .unwrap()
+                .make_response_packet(Code::AccessAccept);
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            let mut stray = request.make_response_packet(Code::AccessAccept);
+            stray.set_identifier(request.get_identifier().wrapping_add(1));
+            let response = request.make_response_packet(Code::AccessReject);
+            for packet in [forged, stray, response] {
+                server
+                    .send_to(&packet.encode().unwrap(), addr)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let client = GenericClient::new(TokioRuntime, Some(Duration::from_secs(3)));
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let response = client.send_packet(&server_addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+    }
+}
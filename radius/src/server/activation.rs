@@ -0,0 +1,123 @@
+//! The socket activation of systemd (`sd_listen_fds(3)`) and the other service managers of the same protocol.
+//!
+//! The service manager binds the sockets of the `.socket` unit (e.g. `ListenDatagram=1812`) and passes them to the
+//! process as the file descriptors from 3 with `LISTEN_PID` and `LISTEN_FDS`; so the server can listen on the
+//! privileged ports without running as root, and the datagrams that arrive while it restarts are kept in the socket.
+//! `listen_fds()` takes the UDP ones of them, and `ServerBuilder::sockets()` makes a server per socket.
+//!
+//! ```no_run
+//! # use std::io;
+//! # use std::net::SocketAddr;
+//! # use async_trait::async_trait;
+//! # use tokio::net::UdpSocket;
+//! # use radius::core::request::Request;
+//! # use radius::server::{RequestHandler, SecretProvider, SecretProviderError, Server};
+//! # struct MyRequestHandler {}
+//! # #[async_trait]
+//! # impl RequestHandler<(), io::Error> for MyRequestHandler {
+//! #     async fn handle_radius_request(&self, _conn: &UdpSocket, _req: &Request) -> Result<(), io::Error> { Ok(()) }
+//! # }
+//! # struct MySecretProvider {}
+//! # impl SecretProvider for MySecretProvider {
+//! #     fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> { Ok(b"secret".to_vec()) }
+//! # }
+//! # async fn f() -> Result<(), io::Error> {
+//! use radius::server::activation;
+//!
+//! let servers = Server::builder(MyRequestHandler {}, MySecretProvider {})
+//!     .sockets(activation::listen_fds()?)
+//!     .build()
+//!     .await?;
+//! Server::run_all(&servers, tokio::signal::ctrl_c()).await
+//! # }
+//! ```
+
+use std::env;
+use std::io;
+use std::net::UdpSocket;
+use std::os::unix::io::{FromRawFd, IntoRawFd, RawFd};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use socket2::{Socket, Type};
+
+/// The first file descriptor of the passed sockets.
+const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// Whether the passed file descriptors have been taken; they are owned by the sockets of the first `listen_fds()`.
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Returns the number of the file descriptors that have been passed to this process (0 if none, or if they are for
+/// another process, e.g. the parent of this one).
+fn parse_listen_fds(
+    listen_pid: Option<&str>,
+    listen_fds: Option<&str>,
+    pid: u32,
+) -> io::Result<usize> {
+    let (listen_pid, listen_fds) = match (listen_pid, listen_fds) {
+        (Some(listen_pid), Some(listen_fds)) => (listen_pid, listen_fds),
+        _ => return Ok(0),
+    };
+    let invalid = |name: &str, value: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid {name}: {value}"),
+        )
+    };
+    if listen_pid
+        .parse::<u32>()
+        .map_err(|_| invalid("LISTEN_PID", listen_pid))?
+        != pid
+    {
+        return Ok(0);
+    }
+    listen_fds
+        .parse()
+        .map_err(|_| invalid("LISTEN_FDS", listen_fds))
+}
+
+/// Take the UDP sockets that the service manager has passed to this process, in the order of the `.socket` unit.
+///
+/// The passed file descriptors of the other types (e.g. the TCP listeners of RadSec) are left open as they are. This
+/// returns an empty list if the process has not been activated by the sockets, and on the second and later calls,
+/// since the first one takes the ownership of the file descriptors. The environment variables are kept, so the
+/// child processes have to ignore them (they do by `LISTEN_PID`).
+pub fn listen_fds() -> io::Result<Vec<UdpSocket>> {
+    let count = parse_listen_fds(
+        env::var("LISTEN_PID").ok().as_deref(),
+        env::var("LISTEN_FDS").ok().as_deref(),
+        process::id(),
+    )?;
+    if count == 0 || TAKEN.swap(true, Ordering::SeqCst) {
+        return Ok(vec![]);
+    }
+
+    let mut sockets = Vec::new();
+    for fd in SD_LISTEN_FDS_START..SD_LISTEN_FDS_START + count as RawFd {
+        // SAFETY: the service manager has passed the file descriptor to this process, and `TAKEN` makes this the only
+        // owner of that
+        let socket = unsafe { Socket::from_raw_fd(fd) };
+        if !matches!(socket.r#type(), Ok(socket_type) if socket_type == Type::DGRAM) {
+            let _ = socket.into_raw_fd();
+            continue;
+        }
+        socket.set_cloexec(true)?;
+        sockets.push(socket.into());
+    }
+    Ok(sockets)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::activation::parse_listen_fds;
+
+    #[test]
+    fn test_parse_listen_fds() {
+        assert_eq!(parse_listen_fds(Some("42"), Some("2"), 42).unwrap(), 2);
+        assert_eq!(parse_listen_fds(Some("41"), Some("2"), 42).unwrap(), 0);
+        assert_eq!(parse_listen_fds(None, Some("2"), 42).unwrap(), 0);
+        assert_eq!(parse_listen_fds(Some("42"), None, 42).unwrap(), 0);
+        assert!(parse_listen_fds(Some("x"), Some("2"), 42).is_err());
+        assert!(parse_listen_fds(Some("42"), Some("-1"), 42).is_err());
+    }
+}
@@ -0,0 +1,958 @@
+//! A builder that composes the listeners, the handler, the secret provider and the limits into servers.
+//!
+//! The servers of the listeners share the handler, so a dual-stack server on the authentication and the accounting
+//! ports is a builder of four listeners; the IPv6 sockets are IPv6-only, so `0.0.0.0` and `::` of the same port don't
+//! conflict.
+//!
+//! ```no_run
+//! # use std::io;
+//! # use std::net::SocketAddr;
+//! # use async_trait::async_trait;
+//! # use tokio::net::UdpSocket;
+//! # use radius::core::request::Request;
+//! # use radius::server::{RequestHandler, SecretProvider, SecretProviderError, Server};
+//! # struct MyRequestHandler {}
+//! # #[async_trait]
+//! # impl RequestHandler<(), io::Error> for MyRequestHandler {
+//! #     async fn handle_radius_request(&self, _conn: &UdpSocket, _req: &Request) -> Result<(), io::Error> { Ok(()) }
+//! # }
+//! # struct MySecretProvider {}
+//! # impl SecretProvider for MySecretProvider {
+//! #     fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> { Ok(b"secret".to_vec()) }
+//! # }
+//! # async fn f() -> Result<(), io::Error> {
+//! let servers = Server::builder(MyRequestHandler {}, MySecretProvider {})
+//!     .listener("0.0.0.0", 1812)
+//!     .listener("::", 1812)
+//!     .listener("0.0.0.0", 1813)
+//!     .listener("::", 1813)
+//!     .buffer_size(4096)
+//!     .build()
+//!     .await?;
+//! Server::run_all(&servers, tokio::signal::ctrl_c()).await
+//! # }
+//! ```
+
+use std::fmt::Debug;
+use std::io;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::code::Code;
+use crate::core::sansio::{AuthenticationCache, DecodingLimits};
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsHook;
+use crate::server::cidr::{AllowedClients, IpNetwork};
+use crate::server::{Concurrency, RequestHandler, SecretProvider, Server};
+
+/// A builder of `Server`s (see `Server::builder()`).
+///
+/// Every option has the default of the corresponding setter of `Server`, so the options that are not set keep working
+/// as `Server::listen()` does; the options are applied to the all listeners, which share the handler and the secret provider.
+pub struct ServerBuilder<X, E: Debug, T: RequestHandler<X, E>, U: SecretProvider> {
+    listeners: Vec<(String, u16)>,
+    sockets: Vec<std::net::UdpSocket>,
+    request_handler_arc: Arc<T>,
+    secret_provider_arc: Arc<U>,
+    buf_size: Option<usize>,
+    buffer_pool_size: Option<usize>,
+    #[cfg(all(feature = "batch-io", target_os = "linux"))]
+    batch_size: Option<usize>,
+    #[cfg(target_os = "linux")]
+    reuse_port_sockets: Option<usize>,
+    skip_authenticity_validation: Option<bool>,
+    require_message_authenticator: Option<bool>,
+    event_timestamp_window: Option<Duration>,
+    decoding_limits: Option<DecodingLimits>,
+    status_server_response_code: Option<Option<Code>>,
+    response_cache_ttl: Option<Duration>,
+    authentication_cache_ttl: Option<Duration>,
+    concurrency: Option<Concurrency>,
+    max_in_flight: Option<usize>,
+    client_rate_limit: Option<u32>,
+    allowed_clients: Option<Arc<AllowedClients>>,
+    log_packets: Option<bool>,
+    fast_path: Option<bool>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<dyn MetricsHook>>,
+    _phantom_return_type: PhantomData<X>,
+    _phantom_error_type: PhantomData<E>,
+}
+
+impl<X, E: Debug, T: RequestHandler<X, E>, U: SecretProvider> ServerBuilder<X, E, T, U> {
+    /// Returns a builder that has no listeners.
+    pub fn new(request_handler: T, secret_provider: U) -> Self {
+        ServerBuilder {
+            listeners: vec![],
+            sockets: vec![],
+            request_handler_arc: Arc::new(request_handler),
+            secret_provider_arc: Arc::new(secret_provider),
+            buf_size: None,
+            buffer_pool_size: None,
+            #[cfg(all(feature = "batch-io", target_os = "linux"))]
+            batch_size: None,
+            #[cfg(target_os = "linux")]
+            reuse_port_sockets: None,
+            skip_authenticity_validation: None,
+            require_message_authenticator: None,
+            event_timestamp_window: None,
+            decoding_limits: None,
+            status_server_response_code: None,
+            response_cache_ttl: None,
+            authentication_cache_ttl: None,
+            concurrency: None,
+            max_in_flight: None,
+            client_rate_limit: None,
+            allowed_clients: None,
+            log_packets: None,
+            fast_path: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            _phantom_return_type: Default::default(),
+            _phantom_error_type: Default::default(),
+        }
+    }
+
+    /// Add a UDP listener (e.g. `0.0.0.0` and `1812`, or `::` and `1812`); `build()` makes a server per listener.
+    pub fn listener(mut self, host: &str, port: u16) -> Self {
+        self.listeners.push((host.to_owned(), port));
+        self
+    }
+
+    /// Add a UDP socket that has been bound by the caller (see `Server::from_socket()`); `build()` makes a server per
+    /// socket as well.
+    pub fn socket(mut self, socket: std::net::UdpSocket) -> Self {
+        self.sockets.push(socket);
+        self
+    }
+
+    /// Add the UDP sockets that have been bound by the caller, e.g. the ones of `radius::server::activation::listen_fds()`.
+    pub fn sockets(mut self, sockets: impl IntoIterator<Item = std::net::UdpSocket>) -> Self {
+        self.sockets.extend(sockets);
+        self
+    }
+
+    /// Set a buffer size for receiving the request payload (see `Server::set_buffer_size()`).
+    pub fn buffer_size(mut self, buf_size: usize) -> Self {
+        self.buf_size = Some(buf_size);
+        self
+    }
+
+    /// Set the maximum number of the idle receive buffers to keep for reuse (see `Server::set_buffer_pool_size()`).
+    pub fn buffer_pool_size(mut self, buffer_pool_size: usize) -> Self {
+        self.buffer_pool_size = Some(buffer_pool_size);
+        self
+    }
+
+    /// Set the maximum number of the datagrams to receive by one `recvmmsg(2)` (see `Server::set_batch_size()`).
+    #[cfg(all(feature = "batch-io", target_os = "linux"))]
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Open the number of the sockets with `SO_REUSEPORT` on each listener (default: `1`, i.e. without that), e.g. one
+    /// per core by `std::thread::available_parallelism()`.
+    ///
+    /// `build()` makes a server per socket, and the kernel load-balances the datagrams among the sockets by the source
+    /// address and port, so the retransmissions of a NAS reach the same server (and its response cache). This is
+    /// available on Linux.
+    #[cfg(target_os = "linux")]
+    pub fn reuse_port(mut self, sockets: usize) -> Self {
+        self.reuse_port_sockets = Some(sockets);
+        self
+    }
+
+    /// Set a flag to specify whether to skip the authenticity validation or not (see `Server::set_skip_authenticity_validation()`).
+    pub fn skip_authenticity_validation(mut self, skip_authenticity_validation: bool) -> Self {
+        self.skip_authenticity_validation = Some(skip_authenticity_validation);
+        self
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator of Access-Request
+    /// (see `Server::set_require_message_authenticator()`).
+    pub fn require_message_authenticator(mut self, require_message_authenticator: bool) -> Self {
+        self.require_message_authenticator = Some(require_message_authenticator);
+        self
+    }
+
+    /// Set the allowed clock skew of Event-Timestamp (see `Server::set_event_timestamp_window()`).
+    pub fn event_timestamp_window(mut self, event_timestamp_window: Duration) -> Self {
+        self.event_timestamp_window = Some(event_timestamp_window);
+        self
+    }
+
+    /// Set the limits of the requests (see `Server::set_decoding_limits()`).
+    pub fn decoding_limits(mut self, decoding_limits: DecodingLimits) -> Self {
+        self.decoding_limits = Some(decoding_limits);
+        self
+    }
+
+    /// Set the code of the response to Status-Server (see `Server::set_status_server_response_code()`).
+    pub fn status_server_response_code(
+        mut self,
+        status_server_response_code: Option<Code>,
+    ) -> Self {
+        self.status_server_response_code = Some(status_server_response_code);
+        self
+    }
+
+    /// Set the TTL of the cached responses to resend to the retransmissions (see `Server::set_response_cache_ttl()`).
+    pub fn response_cache_ttl(mut self, response_cache_ttl: Duration) -> Self {
+        self.response_cache_ttl = Some(response_cache_ttl);
+        self
+    }
+
+    /// Set the TTL of the cached responses to answer the identical authentication retries
+    /// (see `Server::set_authentication_cache()`); each server has its own cache.
+    pub fn authentication_cache_ttl(mut self, authentication_cache_ttl: Duration) -> Self {
+        self.authentication_cache_ttl = Some(authentication_cache_ttl);
+        self
+    }
+
+    /// Set how each server runs the handlers of the requests (see `Server::set_concurrency()`); a worker pool is
+    /// per server.
+    pub fn concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Set the maximum number of the requests that each server handles concurrently (see `Server::set_max_in_flight()`).
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = Some(max_in_flight);
+        self
+    }
+
+    /// Set the maximum number of the packets per second from each client (see `Server::set_client_rate_limit()`).
+    pub fn client_rate_limit(mut self, packets_per_second: u32) -> Self {
+        self.client_rate_limit = Some(packets_per_second);
+        self
+    }
+
+    /// Set the allow-list of the source networks of the clients, which the servers share
+    /// (see `Server::set_allowed_clients()`).
+    pub fn allowed_clients(mut self, networks: Vec<IpNetwork>) -> Self {
+        self.allowed_clients = Some(Arc::new(AllowedClients::new(networks)));
+        self
+    }
+
+    /// Set whether to log the requests and the responses (see `Server::set_log_packets()`).
+    pub fn log_packets(mut self, log_packets: bool) -> Self {
+        self.log_packets = Some(log_packets);
+        self
+    }
+
+    /// Set whether to pre-validate the header of each datagram in the receive loop (see `Server::set_fast_path()`).
+    pub fn fast_path(mut self, fast_path: bool) -> Self {
+        self.fast_path = Some(fast_path);
+        self
+    }
+
+    /// Set the metrics to record the requests of the servers into (see `Server::set_metrics()`).
+    #[cfg(feature = "metrics")]
+    pub fn metrics(mut self, metrics: Arc<dyn MetricsHook>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Starts UDP listening on the all listeners, in the order of `listener()` (and the sockets of `reuse_port()` of
+    /// each listener), and then on the sockets of `socket()` (`reuse_port()` doesn't apply to them).
+    ///
+    /// This fails with `InvalidInput` if there are no listeners nor sockets; run the servers by `Server::run_all()`.
+    pub async fn build(mut self) -> Result<Vec<Server<X, E, T, U>>, io::Error> {
+        if self.listeners.is_empty() && self.sockets.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the server has no listeners",
+            ));
+        }
+
+        #[cfg(target_os = "linux")]
+        let sockets = self.reuse_port_sockets.unwrap_or(1).max(1);
+        #[cfg(not(target_os = "linux"))]
+        let sockets = 1;
+
+        let mut servers = Vec::with_capacity(self.listeners.len() * sockets + self.sockets.len());
+        for (host, port) in &self.listeners {
+            let mut port = *port;
+            for _ in 0..sockets {
+                let mut server = Server::bind(
+                    host,
+                    port,
+                    self.request_handler_arc.clone(),
+                    self.secret_provider_arc.clone(),
+                    sockets > 1,
+                )
+                .await
+                .map_err(|e| {
+                    io::Error::new(e.kind(), format!("failed to listen on {host}:{port}: {e}"))
+                })?;
+                // the other sockets of an ephemeral port bind to the port of the first one
+                port = server.get_listen_address()?.port();
+                self.apply_options(&mut server);
+                servers.push(server);
+            }
+        }
+        for socket in std::mem::take(&mut self.sockets) {
+            let mut server = Server::from_std_socket(
+                socket,
+                self.request_handler_arc.clone(),
+                self.secret_provider_arc.clone(),
+            )?;
+            self.apply_options(&mut server);
+            servers.push(server);
+        }
+        Ok(servers)
+    }
+
+    fn apply_options(&self, server: &mut Server<X, E, T, U>) {
+        if let Some(buf_size) = self.buf_size {
+            server.set_buffer_size(buf_size);
+        }
+        if let Some(buffer_pool_size) = self.buffer_pool_size {
+            server.set_buffer_pool_size(buffer_pool_size);
+        }
+        #[cfg(all(feature = "batch-io", target_os = "linux"))]
+        if let Some(batch_size) = self.batch_size {
+            server.set_batch_size(batch_size);
+        }
+        if let Some(skip_authenticity_validation) = self.skip_authenticity_validation {
+            server.set_skip_authenticity_validation(skip_authenticity_validation);
+        }
+        if let Some(require_message_authenticator) = self.require_message_authenticator {
+            server.set_require_message_authenticator(require_message_authenticator);
+        }
+        if self.event_timestamp_window.is_some() {
+            server.set_event_timestamp_window(self.event_timestamp_window);
+        }
+        if let Some(decoding_limits) = self.decoding_limits {
+            server.set_decoding_limits(decoding_limits);
+        }
+        if let Some(status_server_response_code) = self.status_server_response_code {
+            server.set_status_server_response_code(status_server_response_code);
+        }
+        if self.response_cache_ttl.is_some() {
+            server.set_response_cache_ttl(self.response_cache_ttl);
+        }
+        if let Some(authentication_cache_ttl) = self.authentication_cache_ttl {
+            server
+                .set_authentication_cache(Some(AuthenticationCache::new(authentication_cache_ttl)));
+        }
+        if let Some(concurrency) = self.concurrency {
+            server.set_concurrency(concurrency);
+        }
+        if self.max_in_flight.is_some() {
+            server.set_max_in_flight(self.max_in_flight);
+        }
+        if self.client_rate_limit.is_some() {
+            server.set_client_rate_limit(self.client_rate_limit);
+        }
+        if self.allowed_clients.is_some() {
+            server.set_allowed_clients(self.allowed_clients.clone());
+        }
+        if let Some(log_packets) = self.log_packets {
+            server.set_log_packets(log_packets);
+        }
+        if let Some(fast_path) = self.fast_path {
+            server.set_fast_path(fast_path);
+        }
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            server.set_metrics(metrics.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::{IpAddr, SocketAddr};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+
+    use crate::client::{Client, ClientError};
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::rfc2865;
+    use crate::core::rfc2869;
+    use crate::core::sansio::DecodingLimits;
+    use crate::server::{
+        send_response, Concurrency, RequestHandler, SecretProvider, SecretProviderError, Server,
+    };
+    use crate::tasks::get_task_counts;
+
+    struct AcceptingHandler {}
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for AcceptingHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let response = req.get_packet().make_response_packet(Code::AccessAccept);
+            conn.send_to(&response.encode().unwrap(), req.get_remote_addr())
+                .await?;
+            Ok(())
+        }
+    }
+
+    struct StaticSecretProvider {}
+
+    impl SecretProvider for StaticSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_build() {
+        let err = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+            .build()
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let servers = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+            .listener("127.0.0.1", 0)
+            .listener("127.0.0.1", 0)
+            .buffer_size(4096)
+            .event_timestamp_window(Duration::from_secs(300))
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(servers.len(), 2);
+        assert!(Arc::ptr_eq(
+            &servers[0].get_secret_provider(),
+            &servers[1].get_secret_provider()
+        ));
+        let addrs: Vec<_> = servers
+            .iter()
+            .map(|server| server.get_listen_address().unwrap())
+            .collect();
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let client = Client::new(None, Some(Duration::from_secs(3)));
+        for addr in addrs {
+            let response = client
+                .send_packet(&addr, &Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_server() {
+        let mut servers = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+            .listener("127.0.0.1", 0)
+            .build()
+            .await
+            .unwrap();
+        servers.extend(
+            Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+                .listener("127.0.0.1", 0)
+                .status_server_response_code(Some(Code::AccountingResponse))
+                .build()
+                .await
+                .unwrap(),
+        );
+        let addrs: Vec<_> = servers
+            .iter()
+            .map(|server| server.get_listen_address().unwrap())
+            .collect();
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let client = Client::new(None, Some(Duration::from_millis(500)));
+        let response = client.probe(&addrs[0], b"secret").await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert!(rfc2869::lookup_message_authenticator(&response).is_some());
+        let response = client.probe(&addrs[1], b"secret").await.unwrap();
+        assert_eq!(response.get_code(), Code::AccountingResponse);
+
+        // Status-Server without Message-Authenticator is discarded
+        let err = client
+            .send_packet(&addrs[0], &Packet::new(Code::StatusServer, b"secret"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClientError::SocketTimeoutError(_)));
+    }
+
+    struct CountingHandler {
+        count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for CountingHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let count = self.count.fetch_add(1, Ordering::SeqCst) + 1;
+            let mut response = req.get_packet().make_response_packet(Code::AccessAccept);
+            rfc2869::add_acct_interim_interval(&mut response, count as u32);
+            send_response(conn, req, &response).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_response_cache() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let servers = Server::builder(
+            CountingHandler {
+                count: count.clone(),
+            },
+            StaticSecretProvider {},
+        )
+        .listener("127.0.0.1", 0)
+        .response_cache_ttl(Duration::from_secs(30))
+        .build()
+        .await
+        .unwrap();
+        let addr = servers[0].get_listen_address().unwrap();
+        let control = servers[0].get_control();
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let mut responses = Vec::new();
+        for request in [
+            &request,
+            &request,
+            &Packet::new(Code::AccessRequest, b"secret"),
+        ] {
+            conn.send_to(&request.encode().unwrap(), addr)
+                .await
+                .unwrap();
+            let mut buf = vec![0; 4096];
+            let len = tokio::time::timeout(Duration::from_secs(3), conn.recv(&mut buf))
+                .await
+                .unwrap()
+                .unwrap();
+            responses.push(Packet::decode(&buf[..len], b"secret").unwrap());
+        }
+
+        // the retransmission is answered by the cached response without the handler
+        assert_eq!(responses[0], responses[1]);
+        assert_eq!(
+            rfc2869::lookup_acct_interim_interval(&responses[2])
+                .unwrap()
+                .unwrap(),
+            2
+        );
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        assert_eq!(control.get_stats().get_duplicated(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_authentication_cache() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let servers = Server::builder(
+            CountingHandler {
+                count: count.clone(),
+            },
+            StaticSecretProvider {},
+        )
+        .listener("127.0.0.1", 0)
+        .authentication_cache_ttl(Duration::from_secs(30))
+        .build()
+        .await
+        .unwrap();
+        let addr = servers[0].get_listen_address().unwrap();
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let client = Client::new(None, Some(Duration::from_secs(3)));
+        let mut responses = Vec::new();
+        for password in [b"p@ssw0rd", b"p@ssw0rd", b"password"] {
+            let mut request = Packet::new(Code::AccessRequest, b"secret");
+            rfc2865::add_user_name(&mut request, "alice");
+            rfc2865::add_user_password(&mut request, password).unwrap();
+            responses.push(client.send_packet(&addr, &request).await.unwrap());
+        }
+
+        // the identical retry is answered by the cached response without the handler
+        let intervals: Vec<u32> = responses
+            .iter()
+            .map(|response| {
+                rfc2869::lookup_acct_interim_interval(response)
+                    .unwrap()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(intervals, vec![1, 1, 2]);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency() {
+        for concurrency in [
+            Concurrency::WorkerPool {
+                workers: 2,
+                queue_size: 4,
+            },
+            Concurrency::Inline,
+        ] {
+            let count = Arc::new(AtomicUsize::new(0));
+            let servers = Server::builder(
+                CountingHandler {
+                    count: count.clone(),
+                },
+                StaticSecretProvider {},
+            )
+            .listener("127.0.0.1", 0)
+            .concurrency(concurrency)
+            .build()
+            .await
+            .unwrap();
+            let addr = servers[0].get_listen_address().unwrap();
+            let server = tokio::spawn(async move {
+                Server::run_all(&servers, std::future::pending::<()>()).await
+            });
+
+            let client = Client::new(None, Some(Duration::from_secs(3)));
+            for _ in 0..3 {
+                let response = client
+                    .send_packet(&addr, &Packet::new(Code::AccessRequest, b"secret"))
+                    .await
+                    .unwrap();
+                assert_eq!(response.get_code(), Code::AccessAccept);
+            }
+            assert_eq!(count.load(Ordering::SeqCst), 3);
+            server.abort();
+        }
+
+        let workers = get_task_counts()
+            .into_iter()
+            .find(|count| count.get_name() == "radius.server.worker")
+            .unwrap();
+        assert!(workers.get_spawned() >= 2);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_reuse_port() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let servers = Server::builder(
+            CountingHandler {
+                count: count.clone(),
+            },
+            StaticSecretProvider {},
+        )
+        .listener("127.0.0.1", 0)
+        .reuse_port(3)
+        .build()
+        .await
+        .unwrap();
+        assert_eq!(servers.len(), 3);
+        let addr = servers[0].get_listen_address().unwrap();
+        for server in &servers {
+            assert_eq!(server.get_listen_address().unwrap(), addr);
+        }
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        // each client has its own source port, which the kernel hashes to one of the sockets
+        for _ in 0..6 {
+            let client = Client::new(None, Some(Duration::from_secs(3)));
+            let response = client
+                .send_packet(&addr, &Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 6);
+    }
+
+    #[tokio::test]
+    async fn test_limits() {
+        let servers = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+            .listener("127.0.0.1", 0)
+            .max_in_flight(1)
+            .client_rate_limit(2)
+            .build()
+            .await
+            .unwrap();
+        let addr = servers[0].get_listen_address().unwrap();
+        let control = servers[0].get_control();
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        for _ in 0..3 {
+            let request = Packet::new(Code::AccessRequest, b"secret");
+            conn.send_to(&request.encode().unwrap(), addr)
+                .await
+                .unwrap();
+        }
+        let mut buf = vec![0; 4096];
+        for _ in 0..2 {
+            tokio::time::timeout(Duration::from_secs(3), conn.recv(&mut buf))
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        // the third one exceeds the burst of the client
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), conn.recv(&mut buf))
+                .await
+                .is_err()
+        );
+        let stats = control.get_stats();
+        assert_eq!(stats.get_handled(), 2);
+        assert_eq!(stats.get_dropped(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sockets() {
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = socket.local_addr().unwrap();
+        let servers = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+            .listener("127.0.0.1", 0)
+            .socket(socket)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[1].get_listen_address().unwrap(), addr);
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        conn.send_to(&request.encode().unwrap(), addr)
+            .await
+            .unwrap();
+        let mut buf = vec![0; 4096];
+        let len = tokio::time::timeout(Duration::from_secs(1), conn.recv(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Packet::decode(&buf[..len], b"secret").unwrap().get_code(),
+            Code::AccessAccept
+        );
+
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let server =
+            Server::from_socket(socket, AcceptingHandler {}, StaticSecretProvider {}).unwrap();
+        assert!(server.get_listen_address().is_ok());
+    }
+
+    struct SlowHandler {}
+
+    #[async_trait]
+    impl RequestHandler<(), io::Error> for SlowHandler {
+        async fn handle_radius_request(
+            &self,
+            conn: &UdpSocket,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let response = req.get_packet().make_response_packet(Code::AccessAccept);
+            send_response(conn, req, &response).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_with_drain() {
+        for (drain_timeout, drained) in [(Duration::from_secs(3), true), (Duration::ZERO, false)] {
+            let mut servers = Server::builder(SlowHandler {}, StaticSecretProvider {})
+                .listener("127.0.0.1", 0)
+                .build()
+                .await
+                .unwrap();
+            let mut server = servers.pop().unwrap();
+            let addr = server.get_listen_address().unwrap();
+            let control = server.get_control();
+            let (shutdown, shutdown_trigger) = tokio::sync::oneshot::channel::<()>();
+            let server = tokio::spawn(async move {
+                server
+                    .run_with_drain(shutdown_trigger, drain_timeout)
+                    .await
+                    .unwrap();
+                server.get_control().get_stats().get_in_flight()
+            });
+
+            let request = tokio::spawn(async move {
+                Client::new(None, Some(Duration::from_secs(1)))
+                    .send_packet(&addr, &Packet::new(Code::AccessRequest, b"secret"))
+                    .await
+            });
+            while control.get_stats().get_in_flight() == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            shutdown.send(()).unwrap();
+
+            if drained {
+                assert_eq!(server.await.unwrap(), 0);
+                let response = request.await.unwrap().unwrap();
+                assert_eq!(response.get_code(), Code::AccessAccept);
+            } else {
+                // the handler is left behind rather than aborted
+                assert_eq!(server.await.unwrap(), 1);
+            }
+            assert!(control.is_draining());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_allowed_clients() {
+        let servers = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+            .listener("127.0.0.1", 0)
+            .listener("127.0.0.1", 0)
+            .allowed_clients(vec!["192.0.2.0/24".parse().unwrap()])
+            .build()
+            .await
+            .unwrap();
+        let addrs: Vec<_> = servers
+            .iter()
+            .map(|server| server.get_listen_address().unwrap())
+            .collect();
+        let control = servers[0].get_control();
+        let allowed_clients = servers[0].get_allowed_clients().unwrap();
+        assert!(Arc::ptr_eq(
+            &allowed_clients,
+            &servers[1].get_allowed_clients().unwrap()
+        ));
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut buf = vec![0; 4096];
+        for addr in addrs {
+            let request = Packet::new(Code::AccessRequest, b"secret");
+            conn.send_to(&request.encode().unwrap(), addr)
+                .await
+                .unwrap();
+            assert!(
+                tokio::time::timeout(Duration::from_millis(200), conn.recv(&mut buf))
+                    .await
+                    .is_err()
+            );
+        }
+        assert_eq!(control.get_stats().get_dropped(), 1);
+        assert_eq!(allowed_clients.get_total_drops(), 2);
+        assert_eq!(
+            allowed_clients.get_drops()[&"127.0.0.1".parse::<IpAddr>().unwrap()],
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decoding_limits() {
+        let mut decoding_limits = DecodingLimits::new();
+        decoding_limits.set_max_attributes(Some(1));
+        let servers = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+            .listener("127.0.0.1", 0)
+            .decoding_limits(decoding_limits)
+            .build()
+            .await
+            .unwrap();
+        let addr = servers[0].get_listen_address().unwrap();
+        let control = servers[0].get_control();
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut request, "first");
+        rfc2865::add_user_name(&mut request, "second");
+        // the garbage and the request of too many attributes are dropped silently
+        conn.send_to(&[0xff; 3], addr).await.unwrap();
+        conn.send_to(&request.encode().unwrap(), addr)
+            .await
+            .unwrap();
+        let mut buf = vec![0; 4096];
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), conn.recv(&mut buf))
+                .await
+                .is_err()
+        );
+        let stats = control.get_stats();
+        assert_eq!(stats.get_dropped(), 2);
+        assert_eq!(stats.get_malformed(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fast_path() {
+        let servers = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+            .listener("127.0.0.1", 0)
+            .concurrency(Concurrency::Inline)
+            .fast_path(true)
+            .build()
+            .await
+            .unwrap();
+        let addr = servers[0].get_listen_address().unwrap();
+        let control = servers[0].get_control();
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        let mut truncated = request.encode().unwrap();
+        truncated[3] += 1;
+        // the garbage, a stray response and the request of the Length beyond the datagram are dropped before the
+        // secret is fetched
+        conn.send_to(&[0xff; 3], addr).await.unwrap();
+        conn.send_to(
+            &request
+                .make_response_packet(Code::AccessAccept)
+                .encode()
+                .unwrap(),
+            addr,
+        )
+        .await
+        .unwrap();
+        conn.send_to(&truncated, addr).await.unwrap();
+        conn.send_to(&request.encode().unwrap(), addr)
+            .await
+            .unwrap();
+        let mut buf = vec![0; 4096];
+        let len = tokio::time::timeout(Duration::from_secs(1), conn.recv(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        let response = Packet::decode(&buf[..len], b"secret").unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(response.get_identifier(), request.get_identifier());
+        let stats = control.get_stats();
+        assert_eq!(stats.get_malformed(), 3);
+        assert_eq!(stats.get_dropped(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_dual_stack() {
+        let mut servers = Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+            .listener("0.0.0.0", 0)
+            .build()
+            .await
+            .unwrap();
+        let port = servers[0].get_listen_address().unwrap().port();
+        servers.extend(
+            Server::builder(AcceptingHandler {}, StaticSecretProvider {})
+                .listener("::", port)
+                .listener("[::1]", 0)
+                .build()
+                .await
+                .unwrap(),
+        );
+        let v6_port = servers[2].get_listen_address().unwrap().port();
+        tokio::spawn(async move { Server::run_all(&servers, std::future::pending::<()>()).await });
+
+        let client = Client::new(None, Some(Duration::from_secs(3)));
+        for addr in [
+            format!("127.0.0.1:{port}"),
+            format!("[::1]:{port}"),
+            format!("[::1]:{v6_port}"),
+        ] {
+            let addr: SocketAddr = addr.parse().unwrap();
+            let response = client
+                .send_packet(&addr, &Packet::new(Code::AccessRequest, b"secret"))
+                .await
+                .unwrap();
+            assert_eq!(response.get_code(), Code::AccessAccept);
+        }
+    }
+}
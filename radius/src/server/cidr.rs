@@ -0,0 +1,361 @@
+//! The shared secrets of the clients by the source networks (CIDR), e.g. for the NAS fleets that have the different
+//! secrets per site.
+//!
+//! ```
+//! use radius::server::cidr::CidrSecretProvider;
+//!
+//! let mut secret_provider = CidrSecretProvider::new();
+//! secret_provider.insert("192.0.2.0/24".parse().unwrap(), b"site-secret");
+//! secret_provider.insert("192.0.2.10".parse().unwrap(), b"nas-secret");
+//! secret_provider.insert("2001:db8::/32".parse().unwrap(), b"v6-secret");
+//! ```
+//!
+//! `AllowedClients` is the allow-list of the source networks of a server (see `Server::set_allowed_clients()`).
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use thiserror::Error;
+
+use crate::error::ErrorKind;
+use crate::server::{SecretProvider, SecretProviderError};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CidrError {
+    /// An error that is raised when a network is neither an IP address nor a network in CIDR notation.
+    #[error("invalid network: {0}")]
+    InvalidNetworkError(String),
+}
+
+impl CidrError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            CidrError::InvalidNetworkError(_) => ErrorKind::Configuration,
+        }
+    }
+}
+
+/// An IP network, i.e. an address and a prefix length (e.g. `192.0.2.0/24`); an address without the prefix length
+/// is the network of the single address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    /// A constructor for a network; the prefix length must not exceed the width of the address.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, CidrError> {
+        let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+        if prefix_len > max_prefix_len {
+            return Err(CidrError::InvalidNetworkError(format!(
+                "{addr}/{prefix_len}"
+            )));
+        }
+        Ok(IpNetwork { addr, prefix_len })
+    }
+
+    pub fn get_addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    pub fn get_prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns whether the network contains the address; an IPv4-mapped IPv6 address (e.g. of a dual-stack socket)
+    /// is the IPv4 address.
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        fn masked(bits: u128, width: u32, prefix_len: u8) -> u128 {
+            match u32::from(prefix_len) {
+                0 => 0,
+                len => bits >> (width - len),
+            }
+        }
+        match (self.addr, to_canonical(addr)) {
+            (IpAddr::V4(network), IpAddr::V4(addr)) => {
+                masked(u32::from(network).into(), 32, self.prefix_len)
+                    == masked(u32::from(addr).into(), 32, self.prefix_len)
+            }
+            (IpAddr::V6(network), IpAddr::V6(addr)) => {
+                masked(u128::from(network), 128, self.prefix_len)
+                    == masked(u128::from(addr), 128, self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = CidrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CidrError::InvalidNetworkError(s.to_owned());
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len.parse::<u8>().map_err(|_| invalid())?,
+            None if addr.is_ipv4() => 32,
+            None => 128,
+        };
+        IpNetwork::new(addr, prefix_len).map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+fn to_canonical(addr: &IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V6(addr) => addr
+            .to_ipv4_mapped()
+            .map(IpAddr::V4)
+            .unwrap_or(IpAddr::V6(*addr)),
+        addr => *addr,
+    }
+}
+
+/// A `SecretProvider` of the secrets by the source networks.
+///
+/// When the networks overlap, the longest prefix wins; the requests from the other addresses are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct CidrSecretProvider {
+    networks: Vec<(IpNetwork, Vec<u8>)>,
+}
+
+impl CidrSecretProvider {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the secret of a network; the secret of the same network is replaced.
+    pub fn insert(&mut self, network: IpNetwork, secret: &[u8]) {
+        self.remove(&network);
+        let index = self
+            .networks
+            .partition_point(|(n, _)| n.prefix_len >= network.prefix_len);
+        self.networks.insert(index, (network, secret.to_vec()));
+    }
+
+    /// Remove the secret of a network, and return it if there was.
+    pub fn remove(&mut self, network: &IpNetwork) -> Option<Vec<u8>> {
+        let index = self.networks.iter().position(|(n, _)| n == network)?;
+        Some(self.networks.remove(index).1)
+    }
+
+    /// Returns the secret of the longest network that contains the address.
+    pub fn lookup(&self, addr: &IpAddr) -> Option<&[u8]> {
+        self.networks
+            .iter()
+            .find(|(network, _)| network.contains(addr))
+            .map(|(_, secret)| secret.as_slice())
+    }
+
+    /// Returns the networks in the order of the lookup, i.e. from the longest prefix.
+    pub fn get_networks(&self) -> Vec<IpNetwork> {
+        self.networks.iter().map(|(network, _)| *network).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.networks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+}
+
+impl FromIterator<(IpNetwork, Vec<u8>)> for CidrSecretProvider {
+    fn from_iter<I: IntoIterator<Item = (IpNetwork, Vec<u8>)>>(iter: I) -> Self {
+        let mut secret_provider = CidrSecretProvider::new();
+        for (network, secret) in iter {
+            secret_provider.insert(network, &secret);
+        }
+        secret_provider
+    }
+}
+
+impl SecretProvider for CidrSecretProvider {
+    fn fetch_secret(&self, remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        self.lookup(&remote_addr.ip())
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| {
+                SecretProviderError::FailedFetchingError(format!("unknown client: {remote_addr}"))
+            })
+    }
+}
+
+/// The maximum number of the source addresses that `AllowedClients` counts the drops of; the drops from the other
+/// addresses are counted only in total, so the spoofed sources can't exhaust the memory.
+const MAX_DROP_SOURCES: usize = 4096;
+
+/// An allow-list of the source networks of the clients, which counts the dropped datagrams by the source address.
+///
+/// A server drops the datagrams from the other addresses before it decodes them (see `Server::set_allowed_clients()`).
+#[derive(Debug, Default)]
+pub struct AllowedClients {
+    networks: Vec<IpNetwork>,
+    drops: Mutex<Drops>,
+}
+
+#[derive(Debug, Default)]
+struct Drops {
+    by_source: HashMap<IpAddr, u64>,
+    total: u64,
+}
+
+impl AllowedClients {
+    /// A constructor for an allow-list of the networks; an empty list allows no client.
+    pub fn new(networks: Vec<IpNetwork>) -> Self {
+        AllowedClients {
+            networks,
+            drops: Mutex::new(Drops::default()),
+        }
+    }
+
+    pub fn get_networks(&self) -> &[IpNetwork] {
+        &self.networks
+    }
+
+    /// Returns whether any of the networks contains the address.
+    pub fn is_allowed(&self, addr: &IpAddr) -> bool {
+        self.networks.iter().any(|network| network.contains(addr))
+    }
+
+    /// Returns whether the address is allowed, and counts the drop of that otherwise (e.g. for a custom transport).
+    pub fn check(&self, addr: &IpAddr) -> bool {
+        if self.is_allowed(addr) {
+            return true;
+        }
+        let mut drops = self.drops.lock().unwrap();
+        drops.total += 1;
+        let addr = to_canonical(addr);
+        if drops.by_source.len() < MAX_DROP_SOURCES || drops.by_source.contains_key(&addr) {
+            *drops.by_source.entry(addr).or_insert(0) += 1;
+        }
+        false
+    }
+
+    /// Returns the snapshot of the numbers of the dropped datagrams by the source address (up to 4096 addresses).
+    pub fn get_drops(&self) -> HashMap<IpAddr, u64> {
+        self.drops.lock().unwrap().by_source.clone()
+    }
+
+    /// Returns the total number of the dropped datagrams, including the ones of the addresses beyond `get_drops()`.
+    pub fn get_total_drops(&self) -> u64 {
+        self.drops.lock().unwrap().total
+    }
+}
+
+impl FromIterator<IpNetwork> for AllowedClients {
+    fn from_iter<I: IntoIterator<Item = IpNetwork>>(iter: I) -> Self {
+        AllowedClients::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, SocketAddr};
+
+    use crate::server::cidr::{AllowedClients, CidrError, CidrSecretProvider, IpNetwork};
+    use crate::server::SecretProvider;
+
+    #[test]
+    fn test_ip_network() {
+        let network: IpNetwork = "192.0.2.0/24".parse().unwrap();
+        assert!(network.contains(&"192.0.2.200".parse().unwrap()));
+        assert!(network.contains(&"::ffff:192.0.2.1".parse().unwrap()));
+        assert!(!network.contains(&"192.0.3.1".parse().unwrap()));
+        assert_eq!(network.to_string(), "192.0.2.0/24");
+
+        let network: IpNetwork = "2001:db8::1".parse().unwrap();
+        assert_eq!(network.get_prefix_len(), 128);
+        assert!(!network.contains(&"2001:db8::2".parse().unwrap()));
+        let addr: IpAddr = "198.51.100.1".parse().unwrap();
+        assert!("0.0.0.0/0".parse::<IpNetwork>().unwrap().contains(&addr));
+
+        for invalid in ["192.0.2.0/33", "192.0.2.0/x", "example.com", "::/129"] {
+            assert_eq!(
+                invalid.parse::<IpNetwork>(),
+                Err(CidrError::InvalidNetworkError(invalid.to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_cidr_secret_provider() {
+        let mut secret_provider: CidrSecretProvider = [
+            ("0.0.0.0/0".parse().unwrap(), b"default".to_vec()),
+            ("192.0.2.10".parse().unwrap(), b"nas".to_vec()),
+            ("192.0.2.0/24".parse().unwrap(), b"site".to_vec()),
+        ]
+        .into_iter()
+        .collect();
+        let fetch = |secret_provider: &CidrSecretProvider, addr: &str| {
+            secret_provider
+                .fetch_secret(addr.parse::<SocketAddr>().unwrap())
+                .ok()
+        };
+
+        assert_eq!(
+            fetch(&secret_provider, "192.0.2.10:1234"),
+            Some(b"nas".to_vec())
+        );
+        assert_eq!(
+            fetch(&secret_provider, "192.0.2.11:1234"),
+            Some(b"site".to_vec())
+        );
+        assert_eq!(
+            fetch(&secret_provider, "198.51.100.1:1234"),
+            Some(b"default".to_vec())
+        );
+        assert_eq!(fetch(&secret_provider, "[2001:db8::1]:1234"), None);
+
+        secret_provider.insert("192.0.2.0/24".parse().unwrap(), b"new-site");
+        assert_eq!(secret_provider.len(), 3);
+        assert_eq!(
+            fetch(&secret_provider, "[::ffff:192.0.2.11]:1234"),
+            Some(b"new-site".to_vec())
+        );
+        assert_eq!(
+            secret_provider.remove(&"192.0.2.10/32".parse().unwrap()),
+            Some(b"nas".to_vec())
+        );
+        assert_eq!(
+            fetch(&secret_provider, "192.0.2.10:1234"),
+            Some(b"new-site".to_vec())
+        );
+    }
+    #[test]
+    fn test_allowed_clients() {
+        let allowed_clients: AllowedClients = ["192.0.2.0/24", "2001:db8::/32"]
+            .into_iter()
+            .map(|network| network.parse().unwrap())
+            .collect();
+        let addr = |addr: &str| addr.parse::<IpAddr>().unwrap();
+
+        assert!(allowed_clients.check(&addr("192.0.2.10")));
+        assert!(allowed_clients.check(&addr("2001:db8::1")));
+        assert!(!allowed_clients.check(&addr("198.51.100.1")));
+        assert!(!allowed_clients.check(&addr("::ffff:198.51.100.1")));
+        assert!(!allowed_clients.check(&addr("2001:db9::1")));
+        assert_eq!(allowed_clients.get_networks().len(), 2);
+        assert_eq!(allowed_clients.get_total_drops(), 3);
+        let drops = allowed_clients.get_drops();
+        assert_eq!(drops.len(), 2);
+        assert_eq!(drops[&addr("198.51.100.1")], 2);
+        assert_eq!(drops[&addr("2001:db9::1")], 1);
+
+        assert!(!AllowedClients::new(vec![]).is_allowed(&addr("192.0.2.10")));
+    }
+}
@@ -0,0 +1,268 @@
+//! A Dynamic Authorization Server (RFC 5176), i.e. the NAS side of Disconnect-Request and CoA-Request.
+//!
+//! `DynamicAuthorizationRequestHandler` is a `RequestHandler` of `Server` that dispatches the Disconnect-Requests
+//! and the CoA-Requests to a `DynamicAuthorizationHandler`, and sends the ACK or the NAK of its decision.
+//! The requests are validated against the shared secret by `Server` as any other request; the other codes are
+//! silently discarded.
+//!
+//! ```no_run
+//! use async_trait::async_trait;
+//!
+//! use radius::core::request::Request;
+//! use radius::core::rfc3576;
+//! use radius::server::dynamic_authorization::{
+//!     listen, DynamicAuthorizationDecision, DynamicAuthorizationHandler,
+//! };
+//! # use std::net::SocketAddr;
+//! # use radius::server::{SecretProvider, SecretProviderError};
+//! # struct MySecretProvider {}
+//! # impl SecretProvider for MySecretProvider {
+//! #     fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+//! #         Ok(b"secret".to_vec())
+//! #     }
+//! # }
+//!
+//! struct MyNas {}
+//!
+//! #[async_trait]
+//! impl DynamicAuthorizationHandler for MyNas {
+//!     async fn disconnect(&self, request: &Request) -> DynamicAuthorizationDecision {
+//!         DynamicAuthorizationDecision::Ack
+//!     }
+//!
+//!     async fn change_authorization(&self, request: &Request) -> DynamicAuthorizationDecision {
+//!         DynamicAuthorizationDecision::Nak(rfc3576::ERROR_CAUSE_UNSUPPORTED_SERVICE)
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mut server = listen("0.0.0.0", MyNas {}, MySecretProvider {}).await.unwrap();
+//! server.run(tokio::signal::ctrl_c()).await.unwrap();
+//! # }
+//! ```
+
+use std::io;
+
+use async_trait::async_trait;
+use tokio::net::UdpSocket;
+
+use crate::core::code::Code;
+use crate::core::packet::Packet;
+use crate::core::request::Request;
+use crate::core::rfc3576;
+use crate::server::{send_response, RequestHandler, SecretProvider, Server};
+use crate::sessions::DEFAULT_DYNAMIC_AUTHORIZATION_PORT;
+
+/// The decision of a `DynamicAuthorizationHandler` for a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicAuthorizationDecision {
+    /// The request has been done; this is responded by Disconnect-ACK or CoA-ACK.
+    Ack,
+    /// The request has been refused by the Error-Cause (e.g. `rfc3576::ERROR_CAUSE_SESSION_CONTEXT_NOT_FOUND`);
+    /// this is responded by Disconnect-NAK or CoA-NAK.
+    Nak(rfc3576::ErrorCause),
+}
+
+/// A handler for the Disconnect-Requests and the CoA-Requests to the NAS.
+#[async_trait]
+pub trait DynamicAuthorizationHandler: 'static + Sync + Send {
+    /// Disconnect the session that the request identifies (e.g. by User-Name, Acct-Session-Id or Framed-IP-Address).
+    async fn disconnect(&self, request: &Request) -> DynamicAuthorizationDecision;
+
+    /// Change the authorization of the session that the request identifies by the other attributes of the request.
+    async fn change_authorization(&self, request: &Request) -> DynamicAuthorizationDecision;
+}
+
+/// A `RequestHandler` that dispatches the Disconnect-Requests and the CoA-Requests to a `DynamicAuthorizationHandler`.
+pub struct DynamicAuthorizationRequestHandler<H: DynamicAuthorizationHandler> {
+    handler: H,
+}
+
+impl<H: DynamicAuthorizationHandler> DynamicAuthorizationRequestHandler<H> {
+    pub fn new(handler: H) -> Self {
+        DynamicAuthorizationRequestHandler { handler }
+    }
+}
+
+/// A Dynamic Authorization Server.
+pub type DynamicAuthorizationServer<H, U> =
+    Server<(), io::Error, DynamicAuthorizationRequestHandler<H>, U>;
+
+/// Starts UDP listening for the Dynamic Authorization Server on the port `3799` of the host (e.g. `0.0.0.0`).
+///
+/// Use `Server::listen()` with `DynamicAuthorizationRequestHandler` to listen on the other port.
+pub async fn listen<H: DynamicAuthorizationHandler, U: SecretProvider>(
+    host: &str,
+    handler: H,
+    secret_provider: U,
+) -> Result<DynamicAuthorizationServer<H, U>, io::Error> {
+    Server::listen(
+        host,
+        DEFAULT_DYNAMIC_AUTHORIZATION_PORT,
+        DynamicAuthorizationRequestHandler::new(handler),
+        secret_provider,
+    )
+    .await
+}
+
+/// Make the ACK or the NAK of a Disconnect-Request or CoA-Request by the decision.
+///
+/// The response has the Error-Cause of a NAK, and the Proxy-State attributes of the request in the same order.
+///
+/// see also: https://tools.ietf.org/html/rfc5176#section-3.5
+pub fn make_response_packet(request: &Packet, decision: DynamicAuthorizationDecision) -> Packet {
+    let ack = decision == DynamicAuthorizationDecision::Ack;
+    let code = match (request.get_code(), ack) {
+        (Code::DisconnectRequest, true) => Code::DisconnectACK,
+        (Code::DisconnectRequest, false) => Code::DisconnectNAK,
+        (_, true) => Code::CoAACK,
+        (_, false) => Code::CoANAK,
+    };
+
+    let mut response = request.make_response_packet(code);
+    if let DynamicAuthorizationDecision::Nak(error_cause) = decision {
+        rfc3576::add_error_cause(&mut response, error_cause);
+    }
+    response
+}
+
+#[async_trait]
+impl<H: DynamicAuthorizationHandler> RequestHandler<(), io::Error>
+    for DynamicAuthorizationRequestHandler<H>
+{
+    async fn handle_radius_request(
+        &self,
+        conn: &UdpSocket,
+        request: &Request,
+    ) -> Result<(), io::Error> {
+        let packet = request.get_packet();
+        let decision = match packet.get_code() {
+            Code::DisconnectRequest => self.handler.disconnect(request).await,
+            Code::CoARequest => self.handler.change_authorization(request).await,
+            code => {
+                info!(
+                    "{} from {} is not a dynamic authorization request; discarded",
+                    code.string(),
+                    request.get_remote_addr()
+                );
+                return Ok(());
+            }
+        };
+
+        let response = make_response_packet(packet, decision);
+        send_response(conn, request, &response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use crate::client::Client;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::{rfc2865, rfc3576};
+    use crate::server::dynamic_authorization::{
+        make_response_packet, DynamicAuthorizationDecision, DynamicAuthorizationHandler,
+        DynamicAuthorizationRequestHandler,
+    };
+    use crate::server::{SecretProvider, SecretProviderError, Server};
+    use crate::sessions::DynamicAuthorizationClient;
+
+    struct MyNas {}
+
+    #[async_trait]
+    impl DynamicAuthorizationHandler for MyNas {
+        async fn disconnect(&self, request: &Request) -> DynamicAuthorizationDecision {
+            match rfc2865::lookup_user_name(request.get_packet()) {
+                Some(Ok(user_name)) if user_name == "bob" => DynamicAuthorizationDecision::Ack,
+                _ => DynamicAuthorizationDecision::Nak(
+                    rfc3576::ERROR_CAUSE_SESSION_CONTEXT_NOT_FOUND,
+                ),
+            }
+        }
+
+        async fn change_authorization(&self, _request: &Request) -> DynamicAuthorizationDecision {
+            DynamicAuthorizationDecision::Nak(rfc3576::ERROR_CAUSE_UNSUPPORTED_SERVICE)
+        }
+    }
+
+    struct MySecretProvider {}
+
+    impl SecretProvider for MySecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_authorization_server() {
+        let mut server = Server::listen(
+            "127.0.0.1",
+            0,
+            DynamicAuthorizationRequestHandler::new(MyNas {}),
+            MySecretProvider {},
+        )
+        .await
+        .unwrap();
+        let server_addr = server.get_listen_address().unwrap();
+        tokio::spawn(async move { server.run(std::future::pending::<()>()).await });
+
+        let mut client = DynamicAuthorizationClient::new(
+            Client::new(None, Some(Duration::from_secs(1))),
+            MySecretProvider {},
+        );
+        client.set_port(server_addr.port());
+
+        let response = client
+            .disconnect_nas(server_addr.ip(), |packet| {
+                rfc2865::add_user_name(packet, "bob");
+            })
+            .await
+            .unwrap();
+        assert!(response.is_ack());
+
+        let response = client
+            .disconnect_nas(server_addr.ip(), |packet| {
+                rfc2865::add_user_name(packet, "alice");
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            response.get_error_cause(),
+            Some(rfc3576::ERROR_CAUSE_SESSION_CONTEXT_NOT_FOUND)
+        );
+
+        let response = client
+            .change_authorization_nas(server_addr.ip(), |packet| {
+                rfc2865::add_user_name(packet, "bob");
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.get_packet().get_code(), Code::CoANAK);
+        assert_eq!(
+            response.get_error_cause(),
+            Some(rfc3576::ERROR_CAUSE_UNSUPPORTED_SERVICE)
+        );
+    }
+
+    #[test]
+    fn test_make_response_packet() {
+        let mut request = Packet::new(Code::CoARequest, b"secret");
+        rfc2865::add_proxy_state(&mut request, b"first");
+        rfc2865::add_proxy_state(&mut request, b"second");
+
+        let response = make_response_packet(&request, DynamicAuthorizationDecision::Ack);
+        assert_eq!(response.get_code(), Code::CoAACK);
+        assert!(rfc3576::lookup_error_cause(&response).is_none());
+        assert_eq!(
+            rfc2865::lookup_all_proxy_state(&response),
+            vec![b"first".to_vec(), b"second".to_vec()]
+        );
+    }
+}
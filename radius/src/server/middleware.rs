@@ -0,0 +1,306 @@
+//! The middlewares of the request handlers, i.e. the cross-cutting concerns (e.g. logging, rate limiting, rewriting
+//! the attributes and metrics) that are composed around a `RequestHandler` without modifying it.
+//!
+//! A `Middleware` handles a request with the rest of the chain (`Next`): it can do something before and after
+//! `next.run()`, pass another request (e.g. the rewritten one) to it, or return without calling it (e.g. to discard
+//! the request). `MiddlewareChain` is a `RequestHandler` of the middlewares and the handler, so the servers take it as
+//! it is.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::io;
+//!
+//! use async_trait::async_trait;
+//! use tokio::net::UdpSocket;
+//!
+//! use radius::core::request::Request;
+//! use radius::server::middleware::{Middleware, MiddlewareChain, Next};
+//! use radius::server::RequestHandler;
+//!
+//! struct MyRequestHandler {}
+//!
+//! #[async_trait]
+//! impl RequestHandler<(), io::Error> for MyRequestHandler {
+//!     async fn handle_radius_request(&self, _conn: &UdpSocket, _req: &Request) -> Result<(), io::Error> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! struct Logging {}
+//!
+//! #[async_trait]
+//! impl Middleware<(), io::Error> for Logging {
+//!     async fn handle(
+//!         &self,
+//!         conn: &UdpSocket,
+//!         request: &Request,
+//!         next: Next<'_, (), io::Error>,
+//!     ) -> Result<(), io::Error> {
+//!         println!("{} from {}", request.get_packet().get_code().string(), request.get_remote_addr());
+//!         let result = next.run(conn, request).await;
+//!         println!("handled: {}", result.is_ok());
+//!         result
+//!     }
+//! }
+//!
+//! let handler = MiddlewareChain::new(MyRequestHandler {}).middleware(Logging {});
+//! ```
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::net::UdpSocket;
+
+use crate::core::request::Request;
+use crate::server::RequestHandler;
+
+/// A middleware of the request handler.
+#[async_trait]
+pub trait Middleware<T, E>: 'static + Sync + Send {
+    /// Handle a request; `next.run()` passes the request to the next middleware, or to the handler at the end of
+    /// the chain.
+    async fn handle(
+        &self,
+        conn: &UdpSocket,
+        request: &Request,
+        next: Next<'_, T, E>,
+    ) -> Result<T, E>;
+}
+
+#[async_trait]
+impl<T, E, M: Middleware<T, E>> Middleware<T, E> for Arc<M>
+where
+    T: 'static,
+    E: 'static,
+{
+    async fn handle(
+        &self,
+        conn: &UdpSocket,
+        request: &Request,
+        next: Next<'_, T, E>,
+    ) -> Result<T, E> {
+        (**self).handle(conn, request, next).await
+    }
+}
+
+/// The rest of a middleware chain.
+pub struct Next<'a, T, E> {
+    middlewares: &'a [Box<dyn Middleware<T, E>>],
+    handler: &'a dyn RequestHandler<T, E>,
+}
+
+impl<T: 'static, E: 'static> Next<'_, T, E> {
+    /// Pass a request to the rest of the chain.
+    pub async fn run(self, conn: &UdpSocket, request: &Request) -> Result<T, E> {
+        match self.middlewares.split_first() {
+            Some((middleware, middlewares)) => {
+                let next = Next {
+                    middlewares,
+                    handler: self.handler,
+                };
+                middleware.handle(conn, request, next).await
+            }
+            None => self.handler.handle_radius_request(conn, request).await,
+        }
+    }
+}
+
+/// A `RequestHandler` that passes the requests through the middlewares to the handler.
+///
+/// The middlewares run in the added order, i.e. the first one is the outermost.
+pub struct MiddlewareChain<T, E, H: RequestHandler<T, E>> {
+    middlewares: Vec<Box<dyn Middleware<T, E>>>,
+    handler: H,
+}
+
+impl<T, E, H: RequestHandler<T, E>> MiddlewareChain<T, E, H> {
+    /// A constructor for a chain of the handler without the middlewares.
+    pub fn new(handler: H) -> Self {
+        MiddlewareChain {
+            middlewares: Vec::new(),
+            handler,
+        }
+    }
+
+    /// Add a middleware inside the ones that have been added.
+    pub fn middleware<M: Middleware<T, E>>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Box::new(middleware));
+        self
+    }
+
+    /// Returns the handler at the end of the chain.
+    pub fn get_handler(&self) -> &H {
+        &self.handler
+    }
+}
+
+#[async_trait]
+impl<T, E, H> RequestHandler<T, E> for MiddlewareChain<T, E, H>
+where
+    T: 'static,
+    E: 'static,
+    H: RequestHandler<T, E>,
+{
+    async fn handle_radius_request(&self, conn: &UdpSocket, request: &Request) -> Result<T, E> {
+        Next {
+            middlewares: &self.middlewares,
+            handler: &self.handler,
+        }
+        .run(conn, request)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::rfc2865;
+    use crate::server::middleware::{Middleware, MiddlewareChain, Next};
+    use crate::server::RequestHandler;
+
+    struct UserNameHandler {
+        user_names: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl RequestHandler<&'static str, String> for UserNameHandler {
+        async fn handle_radius_request(
+            &self,
+            _conn: &UdpSocket,
+            request: &Request,
+        ) -> Result<&'static str, String> {
+            let user_name = rfc2865::lookup_user_name(request.get_packet())
+                .unwrap()
+                .unwrap();
+            self.user_names.lock().unwrap().push(user_name);
+            Ok("handled")
+        }
+    }
+
+    /// Records the order of the middlewares.
+    struct Trace {
+        name: &'static str,
+        trace: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Middleware<&'static str, String> for Trace {
+        async fn handle(
+            &self,
+            conn: &UdpSocket,
+            request: &Request,
+            next: Next<'_, &'static str, String>,
+        ) -> Result<&'static str, String> {
+            self.trace.lock().unwrap().push(format!("{} in", self.name));
+            let result = next.run(conn, request).await;
+            self.trace
+                .lock()
+                .unwrap()
+                .push(format!("{} out", self.name));
+            result
+        }
+    }
+
+    /// Lowercases User-Name.
+    struct Rewrite {}
+
+    #[async_trait]
+    impl Middleware<&'static str, String> for Rewrite {
+        async fn handle(
+            &self,
+            conn: &UdpSocket,
+            request: &Request,
+            next: Next<'_, &'static str, String>,
+        ) -> Result<&'static str, String> {
+            let mut packet = request.get_packet().clone();
+            let user_name = rfc2865::lookup_user_name(&packet).unwrap().unwrap();
+            rfc2865::delete_user_name(&mut packet);
+            rfc2865::add_user_name(&mut packet, &user_name.to_lowercase());
+            let request = Request::new(request.get_local_addr(), request.get_remote_addr(), packet);
+            next.run(conn, &request).await
+        }
+    }
+
+    /// Rejects the requests after the limit.
+    struct Limit {
+        remaining: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Middleware<&'static str, String> for Limit {
+        async fn handle(
+            &self,
+            conn: &UdpSocket,
+            request: &Request,
+            next: Next<'_, &'static str, String>,
+        ) -> Result<&'static str, String> {
+            let allowed = self
+                .remaining
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if !allowed {
+                return Err("rate limited".to_owned());
+            }
+            next.run(conn, request).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_chain() {
+        let trace = Arc::new(Mutex::new(Vec::new()));
+        let chain = MiddlewareChain::new(UserNameHandler {
+            user_names: Mutex::new(Vec::new()),
+        })
+        .middleware(Trace {
+            name: "outer",
+            trace: trace.clone(),
+        })
+        .middleware(Arc::new(Limit {
+            remaining: AtomicUsize::new(1),
+        }))
+        .middleware(Trace {
+            name: "inner",
+            trace: trace.clone(),
+        })
+        .middleware(Rewrite {});
+
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = conn.local_addr().unwrap();
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, "Alice");
+        let request = Request::new(addr, addr, packet);
+
+        assert_eq!(
+            chain.handle_radius_request(&conn, &request).await,
+            Ok("handled")
+        );
+        assert_eq!(
+            chain.handle_radius_request(&conn, &request).await,
+            Err("rate limited".to_owned())
+        );
+        assert_eq!(
+            *chain.get_handler().user_names.lock().unwrap(),
+            vec!["alice".to_owned()]
+        );
+        assert_eq!(
+            *trace.lock().unwrap(),
+            vec![
+                "outer in",
+                "inner in",
+                "inner out",
+                "outer out",
+                "outer in",
+                "outer out"
+            ]
+        );
+    }
+}
@@ -0,0 +1,1322 @@
+//! RADIUS server implementation.
+//!
+//! `Server` and `RequestHandler` are available with the `tokio` feature (enabled by default).
+
+#[cfg(all(feature = "tokio", unix))]
+pub mod activation;
+#[cfg(feature = "tokio")]
+pub mod builder;
+pub mod cidr;
+#[cfg(all(feature = "tokio", feature = "rfc3576"))]
+pub mod dynamic_authorization;
+#[cfg(feature = "tokio")]
+pub mod middleware;
+pub mod reload;
+#[cfg(feature = "tokio")]
+pub mod transport;
+
+#[cfg(feature = "tokio")]
+use async_trait::async_trait;
+#[cfg(feature = "tokio")]
+use std::borrow::Borrow;
+#[cfg(feature = "tokio")]
+use std::cell::RefCell;
+#[cfg(feature = "tokio")]
+use std::future::Future;
+#[cfg(feature = "tokio")]
+use std::io;
+use std::net::SocketAddr;
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+#[cfg(feature = "tokio")]
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "tokio")]
+use std::sync::{Mutex, RwLock};
+#[cfg(feature = "tokio")]
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+#[cfg(feature = "tokio")]
+use tokio::net::UdpSocket;
+#[cfg(feature = "tokio")]
+use tokio::sync::{mpsc, Notify, OwnedSemaphorePermit, Semaphore};
+
+#[cfg(all(feature = "batch-io", target_os = "linux"))]
+use crate::batch;
+#[cfg(feature = "tokio")]
+use crate::buffer_pool::{BufferPool, PooledBuffer};
+#[cfg(feature = "tokio")]
+use crate::core::code::Code;
+#[cfg(feature = "tokio")]
+use crate::core::deterministic;
+#[cfg(feature = "tokio")]
+use crate::core::packet::{Packet, MAX_PACKET_LENGTH, RADIUS_PACKET_HEADER_LENGTH};
+#[cfg(feature = "tokio")]
+use crate::core::request::Request;
+#[cfg(feature = "tokio")]
+use crate::core::sansio::{
+    make_status_server_response, status_server_response_code, AuthenticationCache, DecodingLimits,
+    RateLimiter, RequestDecoder, RequestDecodingError, ResponseCache, UndergoingRequests,
+};
+#[cfg(feature = "tokio")]
+use crate::correlation;
+#[cfg(feature = "tokio")]
+use crate::server::builder::ServerBuilder;
+#[cfg(feature = "tokio")]
+use crate::server::cidr::AllowedClients;
+#[cfg(feature = "tokio")]
+use crate::tasks;
+#[cfg(feature = "tokio")]
+use std::fmt::Debug;
+#[cfg(feature = "tokio")]
+use std::marker::PhantomData;
+#[cfg(feature = "config")]
+use std::path::Path;
+#[cfg(feature = "tokio")]
+use std::task::Poll;
+
+#[cfg(feature = "config")]
+use crate::config::{ClientsSecretProvider, ConfigError, ServerConfig};
+use crate::error::ErrorKind;
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsHook;
+
+#[cfg(feature = "tokio")]
+const DEFAULT_BUFFER_SIZE: usize = 1500;
+#[cfg(feature = "tokio")]
+const DEFAULT_BUFFER_POOL_SIZE: usize = 1024;
+#[cfg(all(feature = "batch-io", target_os = "linux"))]
+const DEFAULT_BATCH_SIZE: usize = 1;
+#[cfg(feature = "tokio")]
+const DEFAULT_SKIP_AUTHENTICITY_VALIDATION: bool = false;
+
+#[cfg(feature = "tokio")]
+tokio::task_local! {
+    /// The response datagram that the handler has sent by `send_response()` in the scope of a request.
+    static SENT_RESPONSE: RefCell<Option<Vec<u8>>>;
+}
+
+/// Send a response to a request from the handler.
+///
+/// This is the same as `conn.send_to()` of the encoded response, and the server caches the responses that are sent
+/// by this for the retransmissions of the requests (see `Server::set_response_cache_ttl()`).
+#[cfg(feature = "tokio")]
+pub async fn send_response(
+    conn: &UdpSocket,
+    request: &Request,
+    response: &Packet,
+) -> Result<(), io::Error> {
+    let encoded = response
+        .encode()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    conn.send_to(&encoded, request.get_remote_addr()).await?;
+    let _ = SENT_RESPONSE.try_with(|sent_response| *sent_response.borrow_mut() = Some(encoded));
+    Ok(())
+}
+
+/// Returns whether a datagram has the header of a request, i.e. the code of a request and the Length within the
+/// datagram (see `Server::set_fast_path()`).
+#[cfg(feature = "tokio")]
+fn is_request_header(datagram: &[u8]) -> bool {
+    if datagram.len() < RADIUS_PACKET_HEADER_LENGTH {
+        return false;
+    }
+    let length = u16::from_be_bytes([datagram[2], datagram[3]]) as usize;
+    (RADIUS_PACKET_HEADER_LENGTH..=datagram.len().min(MAX_PACKET_LENGTH)).contains(&length)
+        && matches!(
+            Code::from(datagram[0]),
+            Code::AccessRequest
+                | Code::AccountingRequest
+                | Code::StatusServer
+                | Code::DisconnectRequest
+                | Code::CoARequest
+        )
+}
+
+/// How a server runs the handlers of the requests (see `Server::set_concurrency()`).
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Concurrency {
+    /// Spawn a task per request (the default); `Server::set_max_in_flight()` bounds the number of the tasks.
+    #[default]
+    TaskPerRequest,
+    /// Queue the requests to the fixed number of the worker tasks, which handle the requests one by one; the server
+    /// stops receiving while the queue of the size is full.
+    WorkerPool { workers: usize, queue_size: usize },
+    /// Handle each request in the receive loop without spawning a task, e.g. for a handler that responds without
+    /// waiting for anything; the server doesn't receive the next datagram until the handler returns.
+    Inline,
+}
+
+/// A request that is queued to the workers of `Concurrency::WorkerPool`.
+#[cfg(feature = "tokio")]
+type Job = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A basic implementation of the RADIUS server.
+///
+/// ## Example Usage
+/// - https://github.com/moznion/radius-rs/blob/HEAD/examples/server.rs
+#[cfg(feature = "tokio")]
+pub struct Server<X, E: Debug, T: RequestHandler<X, E>, U: SecretProvider> {
+    request_decoder: RequestDecoder,
+    buf_size: usize,
+    buffer_pool_size: usize,
+    #[cfg(all(feature = "batch-io", target_os = "linux"))]
+    batch_size: usize,
+    status_server_response_code: Option<Code>,
+    concurrency: Concurrency,
+    conn_arc: Arc<UdpSocket>,
+    request_handler_arc: Arc<T>,
+    secret_provider_arc: Arc<U>,
+    undergoing_requests_lock_arc: Arc<RwLock<UndergoingRequests>>,
+    response_cache_lock_arc: Option<Arc<RwLock<ResponseCache>>>,
+    authentication_cache_lock_arc: Option<Arc<RwLock<AuthenticationCache>>>,
+    in_flight_semaphore_arc: Option<Arc<Semaphore>>,
+    client_rate_limiter_lock: Option<Mutex<RateLimiter>>,
+    allowed_clients_arc: Option<Arc<AllowedClients>>,
+    log_packets: bool,
+    fast_path: bool,
+    control_arc: Arc<ServerControl>,
+    _phantom_return_type: PhantomData<X>,
+    _phantom_error_type: PhantomData<E>,
+}
+
+#[cfg(feature = "tokio")]
+impl<X, E: Debug, T: RequestHandler<X, E>, U: SecretProvider> Server<X, E, T, U> {
+    // NOTE: why it separates between `listen()` and `run()`.
+    // Initially it uses a channel that given through the `run()` parameter to notify when a server becomes ready,
+    // but that doesn't work because it never run the procedure until `await` called.
+    // This means if it calls `await`, it blocks the procedure so it cannot consume a channel simultaneously.
+    // Thus, it separates bootstrap sequence between `listen()` and `run()`.
+    // `listen()`: Start UDP listening. After this function call is finished, the RADIUS server is ready.
+    // `run()`: Start a loop to handle the RADIUS requests.
+
+    /// Starts UDP listening for the RADIUS server.
+    /// After this function call is finished, the RADIUS server becomes ready to handle the requests;
+    /// then it calls `run()` method for a `Server` instance that returned by this function,
+    /// it starts RADIUS request handling.
+    ///
+    /// ## Parameters
+    ///
+    /// - `host` - a host to listen (e.g. `0.0.0.0`)
+    /// - `port` - a port number to listen (e.g. `1812`)
+    /// - `request_handler` - a request handler for the RADIUS requests.
+    /// - `secret_provider` - a provider for shared-secret value.
+    pub async fn listen(
+        host: &str,
+        port: u16,
+        request_handler: T,
+        secret_provider: U,
+    ) -> Result<Self, io::Error> {
+        Self::bind(
+            host,
+            port,
+            Arc::new(request_handler),
+            Arc::new(secret_provider),
+            false,
+        )
+        .await
+    }
+
+    /// Returns a server on a UDP socket that has been bound by the caller, e.g. a socket of the systemd socket
+    /// activation (see `radius::server::activation`), or one that a privileged parent process has bound to a
+    /// privileged port.
+    ///
+    /// This has to be called in the tokio runtime, as `tokio::net::UdpSocket::from_std()`.
+    pub fn from_socket(
+        conn: std::net::UdpSocket,
+        request_handler: T,
+        secret_provider: U,
+    ) -> Result<Self, io::Error> {
+        Self::from_std_socket(conn, Arc::new(request_handler), Arc::new(secret_provider))
+    }
+
+    /// Returns a builder that composes the listeners, the handler, the secret provider and the limits into servers.
+    ///
+    /// Prefer this instead of `listen()` and the setters when the server has more than one listener or option.
+    pub fn builder(request_handler: T, secret_provider: U) -> ServerBuilder<X, E, T, U> {
+        ServerBuilder::new(request_handler, secret_provider)
+    }
+
+    async fn bind(
+        host: &str,
+        port: u16,
+        request_handler_arc: Arc<T>,
+        secret_provider_arc: Arc<U>,
+        reuse_port: bool,
+    ) -> Result<Self, io::Error> {
+        let conn = bind_udp_socket(host, port, reuse_port).await?;
+        Ok(Self::with_socket(
+            conn,
+            request_handler_arc,
+            secret_provider_arc,
+        ))
+    }
+
+    fn from_std_socket(
+        conn: std::net::UdpSocket,
+        request_handler_arc: Arc<T>,
+        secret_provider_arc: Arc<U>,
+    ) -> Result<Self, io::Error> {
+        conn.set_nonblocking(true)?;
+        Ok(Self::with_socket(
+            UdpSocket::from_std(conn)?,
+            request_handler_arc,
+            secret_provider_arc,
+        ))
+    }
+
+    fn with_socket(
+        conn: UdpSocket,
+        request_handler_arc: Arc<T>,
+        secret_provider_arc: Arc<U>,
+    ) -> Self {
+        let undergoing_requests_lock_arc = Arc::new(RwLock::new(UndergoingRequests::new()));
+        let port = conn.local_addr().map_or(0, |addr| addr.port());
+        let conn_arc = Arc::new(conn);
+
+        let mut request_decoder = RequestDecoder::new();
+        request_decoder.set_skip_authenticity_validation(DEFAULT_SKIP_AUTHENTICITY_VALIDATION);
+
+        Server {
+            request_decoder,
+            buf_size: DEFAULT_BUFFER_SIZE,
+            buffer_pool_size: DEFAULT_BUFFER_POOL_SIZE,
+            #[cfg(all(feature = "batch-io", target_os = "linux"))]
+            batch_size: DEFAULT_BATCH_SIZE,
+            status_server_response_code: Some(status_server_response_code(port)),
+            concurrency: Concurrency::default(),
+            conn_arc,
+            request_handler_arc,
+            secret_provider_arc,
+            undergoing_requests_lock_arc,
+            response_cache_lock_arc: None,
+            authentication_cache_lock_arc: None,
+            in_flight_semaphore_arc: None,
+            client_rate_limiter_lock: None,
+            allowed_clients_arc: None,
+            log_packets: false,
+            fast_path: false,
+            control_arc: Arc::new(ServerControl::default()),
+            _phantom_return_type: Default::default(),
+            _phantom_error_type: Default::default(),
+        }
+    }
+
+    /// Starts the RADIUS requests handling.
+    ///
+    /// ## Parameters
+    ///
+    /// - `shutdown_trigger`: an implementation of the `Future` to interrupt to shutdown the RADIUS server (e.g. `signal::ctrl_c()`)
+    pub async fn run(&mut self, shutdown_trigger: impl Future) -> Result<(), io::Error> {
+        tokio::select! {
+            res = self.run_loop() => {
+                res
+            }
+            _ = shutdown_trigger => {
+                info!("server is shutting down");
+                Ok(())
+            }
+        }
+    }
+
+    /// Starts the RADIUS requests handling of the all servers (e.g. the servers that are made by `from_config()`).
+    ///
+    /// This returns when one of the servers has failed, or when `shutdown_trigger` has been resolved.
+    pub async fn run_all(servers: &[Self], shutdown_trigger: impl Future) -> Result<(), io::Error> {
+        let mut run_loops: Vec<_> = servers
+            .iter()
+            .map(|server| Box::pin(server.run_loop()))
+            .collect();
+        let run_loops = std::future::poll_fn(move |cx| {
+            for run_loop in run_loops.iter_mut() {
+                if let Poll::Ready(res) = run_loop.as_mut().poll(cx) {
+                    return Poll::Ready(res);
+                }
+            }
+            Poll::Pending
+        });
+
+        tokio::select! {
+            res = run_loops => {
+                res
+            }
+            _ = shutdown_trigger => {
+                info!("server is shutting down");
+                Ok(())
+            }
+        }
+    }
+
+    /// Starts the RADIUS requests handling, and shuts down gracefully.
+    ///
+    /// When `shutdown_trigger` has been resolved, the server starts draining (see `ServerControl::drain()`): it drops the
+    /// new requests so that the NAS retransmits them to another server, while it keeps handling the requests in flight.
+    /// This returns when they have been done, or when `drain_timeout` has elapsed; the handlers still running then are
+    /// left behind. The server remains draining, so `resume()` it to run that again.
+    pub async fn run_with_drain(
+        &mut self,
+        shutdown_trigger: impl Future,
+        drain_timeout: Duration,
+    ) -> Result<(), io::Error> {
+        let controls = vec![self.control_arc.clone()];
+        self.run(Self::drain_after(shutdown_trigger, controls, drain_timeout))
+            .await
+    }
+
+    /// Starts the RADIUS requests handling of the all servers, and shuts them down gracefully (see `run_with_drain()`).
+    pub async fn run_all_with_drain(
+        servers: &[Self],
+        shutdown_trigger: impl Future,
+        drain_timeout: Duration,
+    ) -> Result<(), io::Error> {
+        let controls = servers
+            .iter()
+            .map(|server| server.control_arc.clone())
+            .collect();
+        Self::run_all(
+            servers,
+            Self::drain_after(shutdown_trigger, controls, drain_timeout),
+        )
+        .await
+    }
+
+    async fn drain_after(
+        shutdown_trigger: impl Future,
+        controls: Vec<Arc<ServerControl>>,
+        drain_timeout: Duration,
+    ) {
+        shutdown_trigger.await;
+        info!("server is draining the requests in flight");
+        controls.iter().for_each(|control| control.drain());
+        let idle = async {
+            for control in &controls {
+                control.wait_idle().await;
+            }
+        };
+        if tokio::time::timeout(drain_timeout, idle).await.is_err() {
+            let in_flight: u64 = controls
+                .iter()
+                .map(|control| control.get_stats().get_in_flight())
+                .sum();
+            warn!(
+                "{} request(s) in flight haven't been done in {:?}",
+                in_flight, drain_timeout
+            );
+        }
+    }
+
+    /// Set a buffer size for receiving the request payload (default: `1500`).
+    pub fn set_buffer_size(&mut self, buf_size: usize) {
+        self.buf_size = buf_size;
+    }
+
+    /// Set the maximum number of the idle receive buffers to keep for reuse (default: `1024`).
+    ///
+    /// Each datagram is received into a buffer from the pool, and the buffer goes back to the pool after the request has been handled;
+    /// so the receive loop doesn't allocate per datagram while the requests in flight don't exceed this number.
+    pub fn set_buffer_pool_size(&mut self, buffer_pool_size: usize) {
+        self.buffer_pool_size = buffer_pool_size;
+    }
+
+    /// Set the maximum number of the datagrams to receive by one `recvmmsg(2)` (default: `1`, i.e. `recv_from()` per datagram).
+    ///
+    /// This is available on Linux with the `batch-io` feature; see `radius::batch`.
+    #[cfg(all(feature = "batch-io", target_os = "linux"))]
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = batch_size;
+    }
+
+    /// Set a flag to specify whether to skip the authenticity validation or not (default: `false`).
+    pub fn set_skip_authenticity_validation(&mut self, skip_authenticity_validation: bool) {
+        self.request_decoder
+            .set_skip_authenticity_validation(skip_authenticity_validation);
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator of Access-Request against the forgery of
+    /// the responses (CVE-2024-3596, a.k.a. Blast-RADIUS; default: `false`).
+    ///
+    /// If this is `true`, the server discards the Access-Requests that don't have Message-Authenticator.
+    /// The requests that have a bad Message-Authenticator are discarded regardless of this, and the responses to
+    /// the requests that have that have that as well (see `Packet::make_response_packet()`).
+    pub fn set_require_message_authenticator(&mut self, require_message_authenticator: bool) {
+        self.request_decoder
+            .set_require_message_authenticator(require_message_authenticator);
+    }
+
+    /// Set the allowed clock skew of Event-Timestamp (default: `None`, i.e. no validation).
+    ///
+    /// If this is set, the server discards the requests that have Event-Timestamp outside of the window around the current time.
+    /// The requests that don't have Event-Timestamp are handled as usual.
+    pub fn set_event_timestamp_window(&mut self, event_timestamp_window: Option<Duration>) {
+        self.request_decoder
+            .set_event_timestamp_window(event_timestamp_window);
+    }
+
+    /// Set the limits of the requests against the garbage traffic (default: no limits; see `DecodingLimits`).
+    ///
+    /// The server drops the requests that exceed the limits, as well as the malformed ones, silently; those are
+    /// counted as `malformed` of the stats instead of the logs per datagram.
+    pub fn set_decoding_limits(&mut self, decoding_limits: DecodingLimits) {
+        self.request_decoder.set_limits(decoding_limits);
+    }
+
+    /// Set the code of the response that the server sends to Status-Server by itself (RFC 5997)
+    /// (default: Accounting-Response on the ports `1813` and `1646`, or Access-Accept on the others).
+    ///
+    /// The response has Message-Authenticator, and the Status-Server without (or with a bad) Message-Authenticator is
+    /// discarded unless the authenticity validation is skipped. If this is `None`, Status-Server is given to the handler.
+    pub fn set_status_server_response_code(&mut self, status_server_response_code: Option<Code>) {
+        self.status_server_response_code = status_server_response_code;
+    }
+
+    /// Set the TTL of the cached responses to resend to the retransmissions of the requests (default: `None`, i.e. no cache).
+    ///
+    /// If this is set, the server resends the cached response to a retransmission (i.e. the same remote address,
+    /// identifier and Request Authenticator; RFC 2865 section 2.5) instead of handling it again, and counts it as
+    /// a duplicated request. The responses that the handler sends by `send_response()` are cached.
+    pub fn set_response_cache_ttl(&mut self, response_cache_ttl: Option<Duration>) {
+        self.response_cache_lock_arc = response_cache_ttl.map(|response_cache_ttl| {
+            Arc::new(RwLock::new(ResponseCache::new(response_cache_ttl)))
+        });
+    }
+
+    /// Set the cache of the responses to Access-Request keyed on the credentials (default: `None`, i.e. no cache).
+    ///
+    /// If this is set, the server answers an identical authentication retry (i.e. a new request of the same client,
+    /// User-Name, password and NAS; see `AuthenticationCache`) by the cached response without the handler, e.g. to
+    /// keep answering the NASes while the backend of the handler is down. The responses that the handler sends by
+    /// `send_response()` are cached.
+    pub fn set_authentication_cache(&mut self, authentication_cache: Option<AuthenticationCache>) {
+        self.authentication_cache_lock_arc = authentication_cache
+            .map(|authentication_cache| Arc::new(RwLock::new(authentication_cache)));
+    }
+
+    /// Set how the server runs the handlers of the requests (default: `Concurrency::TaskPerRequest`).
+    ///
+    /// The maximum number of the requests in flight (see `set_max_in_flight()`) is applied to all of them.
+    pub fn set_concurrency(&mut self, concurrency: Concurrency) {
+        self.concurrency = concurrency;
+    }
+
+    /// Set the maximum number of the requests that are handled concurrently (default: `None`, i.e. unlimited).
+    ///
+    /// If this is set, the server stops receiving while the handlers of the maximum number of the requests are running,
+    /// so the excess requests wait in the receive buffer of the socket (and the kernel drops them when that is full)
+    /// instead of spawning the tasks unboundedly.
+    pub fn set_max_in_flight(&mut self, max_in_flight: Option<usize>) {
+        self.in_flight_semaphore_arc =
+            max_in_flight.map(|max_in_flight| Arc::new(Semaphore::new(max_in_flight)));
+    }
+
+    /// Set the maximum number of the packets per second from each client IP address (default: `None`, i.e. unlimited).
+    ///
+    /// The excess packets are dropped before they are decoded; a client can send a second of the packets at once
+    /// (see `radius::core::sansio::RateLimiter`).
+    pub fn set_client_rate_limit(&mut self, packets_per_second: Option<u32>) {
+        self.client_rate_limiter_lock = packets_per_second
+            .map(|packets_per_second| Mutex::new(RateLimiter::new(packets_per_second)));
+    }
+
+    /// Set the allow-list of the source networks of the clients (default: `None`, i.e. any client).
+    ///
+    /// The datagrams from the other addresses are dropped before they are decoded, even before the rate limit;
+    /// the allow-list counts them by the source address, and can be shared by the servers (e.g. of `ServerBuilder`).
+    pub fn set_allowed_clients(&mut self, allowed_clients: Option<Arc<AllowedClients>>) {
+        self.allowed_clients_arc = allowed_clients;
+    }
+
+    /// Set whether to log the requests and the responses at the debug level (default: `false`).
+    ///
+    /// The packets are logged by `Packet::redacted_debug()`, i.e. the attributes by the names with the passwords and
+    /// the keys masked; the responses are the ones that the handler has sent by `send_response()`.
+    pub fn set_log_packets(&mut self, log_packets: bool) {
+        self.log_packets = log_packets;
+    }
+
+    /// Set whether to pre-validate the header of each datagram in the receive loop (default: `false`).
+    ///
+    /// The datagrams that can't be a request (shorter than the header, the Length that is out of the datagram, or the
+    /// code of a response) are dropped as `malformed` before a task is spawned and the secret is fetched for them,
+    /// so the garbage traffic (e.g. a flood of the stray responses) costs the least. Combined with
+    /// `Concurrency::Inline` and the receive buffer pool, this is the fast path for the handlers that respond without
+    /// waiting for anything (see the `server` benchmark).
+    pub fn set_fast_path(&mut self, fast_path: bool) {
+        self.fast_path = fast_path;
+    }
+
+    /// Returns the allow-list of the source networks, e.g. to read the numbers of the drops.
+    pub fn get_allowed_clients(&self) -> Option<Arc<AllowedClients>> {
+        self.allowed_clients_arc.clone()
+    }
+
+    /// Returns the listening address.
+    pub fn get_listen_address(&self) -> io::Result<SocketAddr> {
+        self.conn_arc.local_addr()
+    }
+
+    /// Returns the counters and the drain switch of the server.
+    pub fn get_control(&self) -> Arc<ServerControl> {
+        self.control_arc.clone()
+    }
+
+    /// Set the metrics to record the requests of the server into, e.g. the Prometheus `Metrics` or an own `MetricsHook`
+    /// (see `radius::metrics`).
+    ///
+    /// This is available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn set_metrics(&mut self, metrics: Arc<dyn MetricsHook>) {
+        *self.control_arc.metrics.write().unwrap() = Some(metrics);
+    }
+
+    /// Returns the secret provider of the server (e.g. to reload the secrets of `ClientsSecretProvider`, or to replace
+    /// the provider of `radius::server::reload::ReloadableSecretProvider`).
+    pub fn get_secret_provider(&self) -> Arc<U> {
+        self.secret_provider_arc.clone()
+    }
+
+    async fn run_loop(&self) -> Result<(), io::Error> {
+        #[cfg(not(feature = "metrics"))]
+        let buffer_pool = BufferPool::new(self.buf_size, self.buffer_pool_size);
+        #[cfg(feature = "metrics")]
+        let buffer_pool = BufferPool::with_metrics(
+            self.buf_size,
+            self.buffer_pool_size,
+            self.control_arc.get_metrics(),
+        );
+        // the workers finish the queued requests and exit when this is dropped, i.e. when the server stops
+        let worker_queue = self.start_workers();
+        let local_addr = self.conn_arc.local_addr()?;
+
+        loop {
+            #[cfg(all(feature = "batch-io", target_os = "linux"))]
+            if self.batch_size > 1 {
+                let mut bufs: Vec<_> = (0..self.batch_size).map(|_| buffer_pool.take()).collect();
+                let received = {
+                    let mut slices: Vec<&mut [u8]> =
+                        bufs.iter_mut().map(|buf| buf.as_mut_buffer()).collect();
+                    batch::recv_batch(&self.conn_arc, &mut slices).await?
+                };
+                for (mut request_data, (size, remote_addr)) in bufs.into_iter().zip(received) {
+                    request_data.set_len(size);
+                    let permit = self.acquire_in_flight_permit().await;
+                    self.dispatch(
+                        request_data,
+                        local_addr,
+                        remote_addr,
+                        permit,
+                        worker_queue.as_ref(),
+                    )
+                    .await;
+                }
+                continue;
+            }
+
+            let permit = self.acquire_in_flight_permit().await;
+            let mut request_data = buffer_pool.take();
+            let (size, remote_addr) = self
+                .conn_arc
+                .recv_from(request_data.as_mut_buffer())
+                .await?;
+            request_data.set_len(size);
+            self.dispatch(
+                request_data,
+                local_addr,
+                remote_addr,
+                permit,
+                worker_queue.as_ref(),
+            )
+            .await;
+        }
+    }
+
+    fn start_workers(&self) -> Option<mpsc::Sender<Job>> {
+        let (workers, queue_size) = match self.concurrency {
+            Concurrency::WorkerPool {
+                workers,
+                queue_size,
+            } => (workers.max(1), queue_size.max(1)),
+            Concurrency::TaskPerRequest | Concurrency::Inline => return None,
+        };
+        let (sender, receiver) = mpsc::channel::<Job>(queue_size);
+        let receiver = Arc::new(tokio::sync::Mutex::new(receiver));
+        for _ in 0..workers {
+            let receiver = receiver.clone();
+            tasks::spawn(&tasks::SERVER_WORKER, async move {
+                loop {
+                    let job = receiver.lock().await.recv().await;
+                    match job {
+                        Some(job) => job.await,
+                        None => break,
+                    }
+                }
+            });
+        }
+        Some(sender)
+    }
+
+    async fn acquire_in_flight_permit(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.in_flight_semaphore_arc {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("the semaphore of the requests in flight is never closed"),
+            ),
+            None => None,
+        }
+    }
+
+    async fn dispatch(
+        &self,
+        request_data: PooledBuffer,
+        local_addr: SocketAddr,
+        remote_addr: SocketAddr,
+        permit: Option<OwnedSemaphorePermit>,
+        worker_queue: Option<&mpsc::Sender<Job>>,
+    ) {
+        let started_at = Instant::now();
+        self.control_arc.begin();
+        if self.control_arc.is_draining() {
+            self.control_arc
+                .observe_drop(remote_addr, DropReason::Draining);
+            self.control_arc
+                .record(RequestOutcome::Dropped, started_at.elapsed());
+            return;
+        }
+        if self.fast_path && !is_request_header(&request_data) {
+            self.control_arc.record_malformed();
+            self.control_arc
+                .observe_drop(remote_addr, DropReason::Malformed);
+            self.control_arc
+                .record(RequestOutcome::Dropped, started_at.elapsed());
+            return;
+        }
+        if let Some(allowed_clients) = &self.allowed_clients_arc {
+            if !allowed_clients.check(&remote_addr.ip()) {
+                debug!("{} is not an allowed client; dropped", remote_addr);
+                self.control_arc
+                    .observe_drop(remote_addr, DropReason::NotAllowed);
+                self.control_arc
+                    .record(RequestOutcome::Dropped, started_at.elapsed());
+                return;
+            }
+        }
+        if let Some(client_rate_limiter_lock) = &self.client_rate_limiter_lock {
+            if !client_rate_limiter_lock
+                .lock()
+                .unwrap()
+                .allow(remote_addr.ip(), &deterministic::now())
+            {
+                debug!("{} has exceeded the rate limit; dropped", remote_addr);
+                self.control_arc
+                    .observe_drop(remote_addr, DropReason::RateLimited);
+                self.control_arc
+                    .record(RequestOutcome::Dropped, started_at.elapsed());
+                return;
+            }
+        }
+
+        let conn = self.conn_arc.clone();
+
+        let request_handler = self.request_handler_arc.clone();
+        let secret_provider = self.secret_provider_arc.clone();
+        let undergoing_requests_lock = self.undergoing_requests_lock_arc.clone();
+        let response_cache_lock = self.response_cache_lock_arc.clone();
+        let authentication_cache_lock = self.authentication_cache_lock_arc.clone();
+        let request_decoder = self.request_decoder.clone();
+        let status_server_response_code = self.status_server_response_code;
+        let log_packets = self.log_packets;
+        let control = self.control_arc.clone();
+
+        let job = correlation::serve(remote_addr, async move {
+            let outcome = Self::process_request(
+                conn,
+                &request_data,
+                local_addr,
+                remote_addr,
+                undergoing_requests_lock,
+                response_cache_lock,
+                authentication_cache_lock,
+                request_handler,
+                secret_provider,
+                &request_decoder,
+                status_server_response_code,
+                log_packets,
+                &control,
+            )
+            .await;
+            correlation::trace_outcome(&outcome, started_at.elapsed());
+            control.record(outcome, started_at.elapsed());
+            drop(permit);
+        });
+        match (self.concurrency, worker_queue) {
+            (_, Some(worker_queue)) => {
+                // the workers never exit while the queue is open, but handle the request here rather than losing it
+                if let Err(mpsc::error::SendError(job)) = worker_queue.send(Box::pin(job)).await {
+                    job.await;
+                }
+            }
+            (Concurrency::Inline, None) => job.await,
+            (_, None) => {
+                tasks::spawn(&tasks::SERVER_REQUEST, job);
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn process_request(
+        conn: Arc<UdpSocket>,
+        request_data: &[u8],
+        local_addr: SocketAddr,
+        remote_addr: SocketAddr,
+        undergoing_requests_lock: Arc<RwLock<UndergoingRequests>>,
+        response_cache_lock: Option<Arc<RwLock<ResponseCache>>>,
+        authentication_cache_lock: Option<Arc<RwLock<AuthenticationCache>>>,
+        request_handler: Arc<T>,
+        secret_provider: Arc<U>,
+        request_decoder: &RequestDecoder,
+        status_server_response_code: Option<Code>,
+        log_packets: bool,
+        control: &ServerControl,
+    ) -> RequestOutcome {
+        let secret: Vec<u8> = match secret_provider.fetch_secret(remote_addr) {
+            Ok(secret) => secret,
+            Err(e) => {
+                error!(
+                    "failed to fetch secret binary vector from the secret provider; {}",
+                    e
+                );
+                control.observe_drop(remote_addr, DropReason::UnknownClient);
+                return RequestOutcome::Dropped;
+            }
+        };
+
+        let packet = match request_decoder.decode(request_data, &secret, &deterministic::now()) {
+            Ok(packet) => packet,
+            Err(e @ RequestDecodingError::EmptySecretError()) => {
+                error!("{}", e);
+                // TODO error handler support?
+                control.observe_drop(remote_addr, DropReason::UnknownClient);
+                return RequestOutcome::Dropped;
+            }
+            Err(e) if e.get_kind() == ErrorKind::Malformed => {
+                debug!("{}; failed request data => {:?}", e, request_data);
+                correlation::trace_decoding_failure(&e);
+                control.record_malformed();
+                control.observe_drop(remote_addr, DropReason::Malformed);
+                return RequestOutcome::Dropped;
+            }
+            Err(e) => {
+                info!("{}", e);
+                correlation::trace_decoding_failure(&e);
+                control.observe_drop(remote_addr, DropReason::Invalid);
+                return RequestOutcome::Dropped;
+            }
+        };
+        correlation::trace_decoded(&packet);
+        if log_packets {
+            debug!("received {} from {}", packet.redacted_debug(), remote_addr);
+        }
+
+        control.observe_request(packet.get_code());
+
+        if let (Code::StatusServer, Some(code)) = (packet.get_code(), status_server_response_code) {
+            let response = make_status_server_response(&packet, code);
+            return match response.encode() {
+                Ok(response_data) => match conn.send_to(&response_data, remote_addr).await {
+                    Ok(_) => RequestOutcome::Handled,
+                    Err(e) => {
+                        error!("failed to respond to Status-Server; {}", e);
+                        RequestOutcome::HandlerError
+                    }
+                },
+                Err(e) => {
+                    error!("failed to encode the response to Status-Server; {}", e);
+                    RequestOutcome::HandlerError
+                }
+            };
+        }
+
+        if let Some(response_cache_lock) = &response_cache_lock {
+            let cached_response = response_cache_lock
+                .read()
+                .unwrap()
+                .lookup(remote_addr, &packet, &deterministic::now())
+                .map(<[u8]>::to_vec);
+            if let Some(response_data) = cached_response {
+                control.observe_response_cache_hit();
+                if let Err(e) = conn.send_to(&response_data, remote_addr).await {
+                    error!("failed to resend the cached response; {}", e);
+                }
+                return RequestOutcome::Duplicated;
+            }
+        }
+
+        if let Some(authentication_cache_lock) = &authentication_cache_lock {
+            let cached_response = authentication_cache_lock.read().unwrap().lookup(
+                remote_addr,
+                &packet,
+                &deterministic::now(),
+            );
+            if let Some(response) = cached_response {
+                control.observe_response_cache_hit();
+                return match response.encode() {
+                    Ok(response_data) => {
+                        if let Err(e) = conn.send_to(&response_data, remote_addr).await {
+                            error!("failed to send the cached response; {}", e);
+                            return RequestOutcome::HandlerError;
+                        }
+                        if let Some(response_cache_lock) = &response_cache_lock {
+                            response_cache_lock.write().unwrap().insert(
+                                remote_addr,
+                                &packet,
+                                &response_data,
+                                &deterministic::now(),
+                            );
+                        }
+                        RequestOutcome::Handled
+                    }
+                    Err(e) => {
+                        error!("failed to encode the cached response; {}", e);
+                        RequestOutcome::HandlerError
+                    }
+                };
+            }
+        }
+
+        let identifier = packet.get_identifier();
+        if !undergoing_requests_lock
+            .write()
+            .unwrap()
+            .begin(remote_addr, identifier)
+        {
+            return RequestOutcome::Duplicated;
+        }
+
+        let request = Request::new(local_addr, remote_addr, packet);
+        let (result, sent_response) = SENT_RESPONSE
+            .scope(RefCell::new(None), async {
+                let result = request_handler
+                    .handle_radius_request(conn.borrow(), &request)
+                    .await;
+                (result, SENT_RESPONSE.with(RefCell::take))
+            })
+            .await;
+        let outcome = match result {
+            Ok(_) => RequestOutcome::Handled,
+            Err(e) => {
+                println!("{e:?}");
+                RequestOutcome::HandlerError
+            }
+        };
+        if let (true, Some(sent_response)) = (log_packets, &sent_response) {
+            if let Ok(response) = Packet::decode(sent_response, &secret) {
+                debug!("sent {} to {}", response.redacted_debug(), remote_addr);
+            }
+        }
+
+        if let (Some(response_cache_lock), Some(sent_response)) =
+            (&response_cache_lock, &sent_response)
+        {
+            response_cache_lock.write().unwrap().insert(
+                remote_addr,
+                request.get_packet(),
+                sent_response,
+                &deterministic::now(),
+            );
+        }
+        if let (Some(authentication_cache_lock), Some(sent_response)) =
+            (&authentication_cache_lock, &sent_response)
+        {
+            if let Ok(response) = Packet::decode(sent_response, &secret) {
+                authentication_cache_lock.write().unwrap().insert(
+                    remote_addr,
+                    request.get_packet(),
+                    &response,
+                    &deterministic::now(),
+                );
+            }
+        }
+
+        undergoing_requests_lock
+            .write()
+            .unwrap()
+            .finish(remote_addr, identifier);
+        outcome
+    }
+}
+
+/// Bind a UDP socket on a host (a name, an IPv4 address or an IPv6 address with or without the brackets) and a port.
+///
+/// An IPv6 socket is IPv6-only, so the servers can listen on both of `0.0.0.0` and `::` of the same port.
+/// With `reuse_port`, the socket has `SO_REUSEPORT` (Linux only), so the other sockets of the option can bind to
+/// the same port, and the kernel load-balances the datagrams among them.
+#[cfg(feature = "tokio")]
+async fn bind_udp_socket(host: &str, port: u16, reuse_port: bool) -> Result<UdpSocket, io::Error> {
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    let mut last_err = None;
+    for address in tokio::net::lookup_host((host, port)).await? {
+        match bind_udp_address(address, reuse_port) {
+            Ok(conn) => return Ok(conn),
+            Err(e) => last_err = Some(e),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("could not resolve to any address: {host}"),
+        )
+    }))
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+fn bind_udp_address(address: SocketAddr, reuse_port: bool) -> Result<UdpSocket, io::Error> {
+    use socket2::{Domain, Protocol, Socket, Type};
+
+    let socket = Socket::new(
+        Domain::for_address(address),
+        Type::DGRAM,
+        Some(Protocol::UDP),
+    )?;
+    if address.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+    #[cfg(target_os = "linux")]
+    if reuse_port {
+        socket.set_reuse_port(true)?;
+    }
+    socket.set_nonblocking(true)?;
+    socket.bind(&address.into())?;
+    UdpSocket::from_std(socket.into())
+}
+
+/// The outcome of a datagram that a server has received (see `ServerStats` and `radius::metrics::MetricsHook`).
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    /// The handler has handled the request successfully, or the server has answered Status-Server.
+    Handled,
+    /// The handler has returned an error.
+    HandlerError,
+    /// The datagram has been dropped, e.g. by the unknown clients, the invalid packets, the rate limit or draining.
+    Dropped,
+    /// The retransmitted request has been ignored or answered by the cached response.
+    Duplicated,
+}
+
+/// The reason why a server has dropped a datagram (see `radius::metrics::MetricsHook::on_server_drop()`).
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DropReason {
+    /// The server is draining (see `ServerControl::drain()`).
+    Draining,
+    /// The source address isn't in the allow-list (see `Server::set_allowed_clients()`).
+    NotAllowed,
+    /// The client has exceeded the rate limit (see `Server::set_client_rate_limit()`).
+    RateLimited,
+    /// The secret provider doesn't have the secret of the client.
+    UnknownClient,
+    /// The datagram is malformed or exceeds the decoding limits.
+    Malformed,
+    /// The packet has failed the validation, e.g. the authenticity or Message-Authenticator.
+    Invalid,
+}
+
+#[cfg(feature = "tokio")]
+impl DropReason {
+    /// Returns the name of the reason in `snake_case` (e.g. `rate_limited`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DropReason::Draining => "draining",
+            DropReason::NotAllowed => "not_allowed",
+            DropReason::RateLimited => "rate_limited",
+            DropReason::UnknownClient => "unknown_client",
+            DropReason::Malformed => "malformed",
+            DropReason::Invalid => "invalid",
+        }
+    }
+}
+
+/// The counters and the drain switch of a server (e.g. for the admin API of `radius::admin`).
+#[cfg(feature = "tokio")]
+#[derive(Debug, Default)]
+pub struct ServerControl {
+    #[cfg(feature = "metrics")]
+    metrics: RwLock<Option<Arc<dyn MetricsHook>>>,
+    received: AtomicU64,
+    handled: AtomicU64,
+    handler_errors: AtomicU64,
+    dropped: AtomicU64,
+    malformed: AtomicU64,
+    duplicated: AtomicU64,
+    in_flight: AtomicU64,
+    idle: Notify,
+    draining: AtomicBool,
+}
+
+#[cfg(feature = "tokio")]
+impl ServerControl {
+    /// Returns the snapshot of the counters.
+    pub fn get_stats(&self) -> ServerStats {
+        ServerStats {
+            received: self.received.load(Ordering::Relaxed),
+            handled: self.handled.load(Ordering::Relaxed),
+            handler_errors: self.handler_errors.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            malformed: self.malformed.load(Ordering::Relaxed),
+            duplicated: self.duplicated.load(Ordering::Relaxed),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Start draining the server; it drops the new requests while it keeps handling the requests in flight.
+    ///
+    /// It is done when `in_flight` of the stats becomes `0`; then the server can be shut down without losing any request.
+    pub fn drain(&self) {
+        self.draining.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop draining the server, i.e. it handles the new requests again.
+    pub fn resume(&self) {
+        self.draining.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    /// Wait until there are no requests in flight, e.g. after `drain()`.
+    pub async fn wait_idle(&self) {
+        loop {
+            // this is notified by the requests that are done after this has been made, so none of them is missed
+            let idle = self.idle.notified();
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn get_metrics(&self) -> Option<Arc<dyn MetricsHook>> {
+        self.metrics.read().unwrap().clone()
+    }
+
+    fn begin(&self) {
+        self.received.fetch_add(1, Ordering::Relaxed);
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.get_metrics() {
+            metrics.on_server_in_flight(1);
+        }
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn observe_request(&self, code: Code) {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.get_metrics() {
+            metrics.on_server_request(code);
+        }
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn observe_drop(&self, remote_addr: SocketAddr, reason: DropReason) {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.get_metrics() {
+            metrics.on_server_drop(remote_addr.ip(), reason);
+        }
+    }
+
+    fn observe_response_cache_hit(&self) {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.get_metrics() {
+            metrics.on_server_response_cache_hit();
+        }
+    }
+
+    /// Count a malformed request (e.g. by `DecodingLimits`), which is recorded as dropped as well.
+    pub(crate) fn record_malformed(&self) {
+        self.malformed.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.get_metrics() {
+            metrics.on_server_malformed();
+        }
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn record(&self, outcome: RequestOutcome, duration: Duration) {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.get_metrics() {
+            metrics.on_server_outcome(outcome, duration);
+            metrics.on_server_in_flight(-1);
+        }
+        let counter = match outcome {
+            RequestOutcome::Handled => &self.handled,
+            RequestOutcome::HandlerError => &self.handler_errors,
+            RequestOutcome::Dropped => &self.dropped,
+            RequestOutcome::Duplicated => &self.duplicated,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        if self.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+/// A snapshot of the counters of a server.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ServerStats {
+    received: u64,
+    handled: u64,
+    handler_errors: u64,
+    dropped: u64,
+    malformed: u64,
+    duplicated: u64,
+    in_flight: u64,
+}
+
+#[cfg(feature = "tokio")]
+impl ServerStats {
+    /// Returns the number of the received datagrams.
+    pub fn get_received(&self) -> u64 {
+        self.received
+    }
+
+    /// Returns the number of the requests that the handler has handled successfully.
+    pub fn get_handled(&self) -> u64 {
+        self.handled
+    }
+
+    /// Returns the number of the requests that the handler has returned an error.
+    pub fn get_handler_errors(&self) -> u64 {
+        self.handler_errors
+    }
+
+    /// Returns the number of the datagrams that have been dropped, e.g. by the unknown clients, the invalid packets,
+    /// the rate limit or draining.
+    pub fn get_dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Returns the number of the dropped datagrams that are malformed or exceed the limits of the decoding
+    /// (see `Server::set_decoding_limits()`).
+    pub fn get_malformed(&self) -> u64 {
+        self.malformed
+    }
+
+    /// Returns the number of the retransmitted requests that have been ignored while the original one is in flight,
+    /// or answered by the cached response (see `Server::set_response_cache_ttl()`).
+    pub fn get_duplicated(&self) -> u64 {
+        self.duplicated
+    }
+
+    /// Returns the number of the requests that are being processed.
+    pub fn get_in_flight(&self) -> u64 {
+        self.in_flight
+    }
+}
+
+#[cfg(feature = "config")]
+impl<X, E: Debug, T: RequestHandler<X, E>> Server<X, E, T, ClientsSecretProvider> {
+    /// Starts UDP listening on the all listeners of a configuration file (see `radius::config`).
+    ///
+    /// The secrets are served by the configured clients, and the policy toggles are applied to the servers.
+    /// Run them by `Server::run_all()`.
+    pub async fn from_config<P: AsRef<Path>>(
+        path: P,
+        request_handler: T,
+    ) -> Result<Vec<Self>, ConfigError> {
+        let config = ServerConfig::load(path)?;
+        Self::listen_with_config(&config, request_handler).await
+    }
+
+    /// Starts UDP listening on the all listeners of a configuration.
+    pub async fn listen_with_config(
+        config: &ServerConfig,
+        request_handler: T,
+    ) -> Result<Vec<Self>, ConfigError> {
+        let request_handler_arc = Arc::new(request_handler);
+        let secret_provider_arc = Arc::new(config.secret_provider()?);
+        let policy = config.get_policy();
+
+        let mut servers = Vec::with_capacity(config.get_listeners().len());
+        for listener in config.get_listeners() {
+            let mut server = Self::bind(
+                listener.get_host(),
+                listener.get_port(),
+                request_handler_arc.clone(),
+                secret_provider_arc.clone(),
+                false,
+            )
+            .await
+            .map_err(|e| {
+                ConfigError::FailedListeningError(
+                    format!("{}:{}", listener.get_host(), listener.get_port()),
+                    e.to_string(),
+                )
+            })?;
+            if let Some(buf_size) = policy.get_buffer_size() {
+                server.set_buffer_size(buf_size);
+            }
+            server.set_skip_authenticity_validation(policy.is_skip_authenticity_validation());
+            server.set_require_message_authenticator(policy.is_require_message_authenticator());
+            server.set_event_timestamp_window(policy.get_event_timestamp_window());
+            servers.push(server);
+        }
+        Ok(servers)
+    }
+}
+
+/// RequestHandler is a handler for the received RADIUS request.
+#[cfg(feature = "tokio")]
+#[async_trait]
+pub trait RequestHandler<T, E>: 'static + Sync + Send {
+    /// This method has to implement the core feature of the server application what you need.
+    ///
+    /// # Arguments
+    ///
+    /// * conn - This connection is associated with the remote requester. In the most situations,
+    ///          you have to send a response through this connection object.
+    /// * request - This is a request object that comes from the remote requester.
+    async fn handle_radius_request(&self, conn: &UdpSocket, request: &Request) -> Result<T, E>;
+}
+
+#[derive(Error, Debug)]
+pub enum SecretProviderError {
+    /// An error that represents a failure to fetch a secret value from the provider.
+    #[error("failed to fetch a secret value: {0}")]
+    FailedFetchingError(String),
+    /// An error that represents a generic (i.e. unclassified) error that occurs on the secret value provider.
+    #[error("unexpected error: {0}")]
+    GenericError(String),
+}
+
+impl SecretProviderError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            SecretProviderError::FailedFetchingError(_) => ErrorKind::Configuration,
+            SecretProviderError::GenericError(_) => ErrorKind::Other,
+        }
+    }
+}
+
+/// SecretProvider is a provider for secret value.
+pub trait SecretProvider: 'static + Sync + Send {
+    /// This method has to implement the generator of the shared-secret value to verify the request.
+    fn fetch_secret(&self, remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError>;
+}
+
+impl<U: SecretProvider> SecretProvider for Arc<U> {
+    fn fetch_secret(&self, remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        (**self).fetch_secret(remote_addr)
+    }
+}
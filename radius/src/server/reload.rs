@@ -0,0 +1,136 @@
+//! A secret provider that can be swapped at runtime.
+//!
+//! `ReloadableSecretProvider` wraps any `SecretProvider` (e.g. `CidrSecretProvider`); `replace()` swaps the wrapped one
+//! while the server keeps listening. A request that has fetched its secret before the swap is handled with that, so
+//! adding or removing a NAS doesn't drop the requests in flight.
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use radius::server::cidr::CidrSecretProvider;
+//! use radius::server::reload::ReloadableSecretProvider;
+//!
+//! let mut clients = CidrSecretProvider::new();
+//! clients.insert("192.0.2.0/24".parse().unwrap(), b"secret");
+//! let secret_provider = Arc::new(ReloadableSecretProvider::new(clients));
+//! // Server::listen(host, port, handler, secret_provider.clone())
+//!
+//! // later, e.g. on SIGHUP
+//! let mut clients = (*secret_provider.get()).clone();
+//! clients.insert("198.51.100.1".parse().unwrap(), b"new-nas");
+//! secret_provider.replace(clients);
+//! ```
+
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+
+use crate::server::{SecretProvider, SecretProviderError};
+
+/// A `SecretProvider` that delegates to the wrapped provider, which can be replaced at runtime.
+#[derive(Debug, Default)]
+pub struct ReloadableSecretProvider<U: SecretProvider> {
+    current: RwLock<Arc<U>>,
+}
+
+impl<U: SecretProvider> ReloadableSecretProvider<U> {
+    pub fn new(secret_provider: U) -> Self {
+        ReloadableSecretProvider {
+            current: RwLock::new(Arc::new(secret_provider)),
+        }
+    }
+
+    /// Returns the current provider.
+    pub fn get(&self) -> Arc<U> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Replace the provider; the requests after this are served by the new one. It returns the former provider.
+    pub fn replace(&self, secret_provider: U) -> Arc<U> {
+        std::mem::replace(
+            &mut *self.current.write().unwrap(),
+            Arc::new(secret_provider),
+        )
+    }
+}
+
+impl<U: SecretProvider> SecretProvider for ReloadableSecretProvider<U> {
+    fn fetch_secret(&self, remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        // the provider is cloned out of the lock, so a slow provider doesn't block the replacement
+        let secret_provider = self.get();
+        secret_provider.fetch_secret(remote_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use crate::server::cidr::CidrSecretProvider;
+    use crate::server::reload::ReloadableSecretProvider;
+    use crate::server::SecretProvider;
+
+    #[test]
+    fn test_replace() {
+        let nas: SocketAddr = "192.0.2.1:50000".parse().unwrap();
+        let secret_provider = ReloadableSecretProvider::new(CidrSecretProvider::new());
+        assert!(secret_provider.fetch_secret(nas).is_err());
+
+        let mut clients = (*secret_provider.get()).clone();
+        clients.insert("192.0.2.0/24".parse().unwrap(), b"secret");
+        let former = secret_provider.replace(clients);
+        assert!(former.is_empty());
+        assert_eq!(secret_provider.fetch_secret(nas).unwrap(), b"secret");
+        assert_eq!(secret_provider.get().len(), 1);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_reload_without_restart() {
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        use async_trait::async_trait;
+        use tokio::net::UdpSocket;
+
+        use crate::client::Client;
+        use crate::core::code::Code;
+        use crate::core::packet::Packet;
+        use crate::core::request::Request;
+        use crate::server::{RequestHandler, Server};
+
+        struct AcceptingHandler {}
+
+        #[async_trait]
+        impl RequestHandler<(), std::io::Error> for AcceptingHandler {
+            async fn handle_radius_request(
+                &self,
+                conn: &UdpSocket,
+                req: &Request,
+            ) -> Result<(), std::io::Error> {
+                let response = req.get_packet().make_response_packet(Code::AccessAccept);
+                conn.send_to(&response.encode().unwrap(), req.get_remote_addr())
+                    .await?;
+                Ok(())
+            }
+        }
+
+        let secret_provider = Arc::new(ReloadableSecretProvider::new(CidrSecretProvider::new()));
+        let mut server =
+            Server::listen("127.0.0.1", 0, AcceptingHandler {}, secret_provider.clone())
+                .await
+                .unwrap();
+        let addr = server.get_listen_address().unwrap();
+        tokio::spawn(async move { server.run(std::future::pending::<()>()).await });
+
+        let client = Client::new(None, Some(Duration::from_millis(200)));
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        // the unknown client is dropped
+        assert!(client.send_packet(&addr, &request).await.is_err());
+
+        let mut clients = CidrSecretProvider::new();
+        clients.insert("127.0.0.1".parse().unwrap(), b"secret");
+        secret_provider.replace(clients);
+        let response = client.send_packet(&addr, &request).await.unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+    }
+}
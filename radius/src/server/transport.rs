@@ -0,0 +1,395 @@
+//! A RADIUS server on top of a custom transport (see `radius::transport`).
+//!
+//! `TransportServer` decodes the requests that come from a `Transport` and dispatches them to a `TransportRequestHandler`,
+//! which sends the responses through the same transport. The validation, the duplicate suppression and the counters
+//! are the same as `Server`.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::marker::PhantomData;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::core::code::Code;
+use crate::core::deterministic;
+use crate::core::request::Request;
+use crate::core::sansio::{
+    make_status_server_response, status_server_response_code, DecodingLimits, RequestDecoder,
+    RequestDecodingError, UndergoingRequests,
+};
+use crate::correlation;
+use crate::error::ErrorKind;
+use crate::server::{DropReason, RequestOutcome, SecretProvider, ServerControl};
+use crate::tasks;
+use crate::transport::{Peer, Transport};
+
+const DEFAULT_BUFFER_SIZE: usize = 1500;
+
+/// A handler for the RADIUS requests that come from a transport.
+#[async_trait]
+pub trait TransportRequestHandler<Tr: Transport, T, E>: 'static + Sync + Send {
+    /// This method has to implement the core feature of the server application what you need.
+    ///
+    /// # Arguments
+    ///
+    /// * transport - The transport that the request has come from; send a response to the peer through this.
+    /// * peer - The peer that has sent the request, with the identity if the transport has one.
+    /// * request - This is a request object that comes from the remote requester.
+    async fn handle_radius_request(
+        &self,
+        transport: &Tr,
+        peer: &Peer,
+        request: &Request,
+    ) -> Result<T, E>;
+}
+
+#[async_trait]
+impl<Tr, T, E, H> TransportRequestHandler<Tr, T, E> for Arc<H>
+where
+    Tr: Transport,
+    H: TransportRequestHandler<Tr, T, E>,
+{
+    async fn handle_radius_request(
+        &self,
+        transport: &Tr,
+        peer: &Peer,
+        request: &Request,
+    ) -> Result<T, E> {
+        (**self)
+            .handle_radius_request(transport, peer, request)
+            .await
+    }
+}
+
+/// A RADIUS server that receives the requests from a transport.
+pub struct TransportServer<Tr, X, E, T, U>
+where
+    Tr: Transport + 'static,
+    E: Debug,
+    T: TransportRequestHandler<Tr, X, E>,
+    U: SecretProvider,
+{
+    transport_arc: Arc<Tr>,
+    request_decoder: RequestDecoder,
+    buf_size: usize,
+    status_server_response_code: Option<Code>,
+    request_handler_arc: Arc<T>,
+    secret_provider_arc: Arc<U>,
+    undergoing_requests_lock_arc: Arc<RwLock<UndergoingRequests>>,
+    control_arc: Arc<ServerControl>,
+    _phantom_return_type: PhantomData<X>,
+    _phantom_error_type: PhantomData<E>,
+}
+
+impl<Tr, X, E, T, U> TransportServer<Tr, X, E, T, U>
+where
+    Tr: Transport + 'static,
+    E: Debug,
+    T: TransportRequestHandler<Tr, X, E>,
+    U: SecretProvider,
+{
+    /// A constructor for a server on the transport.
+    pub fn new(transport: Tr, request_handler: T, secret_provider: U) -> Self {
+        let port = transport.local_addr().map(|addr| addr.port()).unwrap_or(0);
+        TransportServer {
+            transport_arc: Arc::new(transport),
+            request_decoder: RequestDecoder::new(),
+            buf_size: DEFAULT_BUFFER_SIZE,
+            status_server_response_code: Some(status_server_response_code(port)),
+            request_handler_arc: Arc::new(request_handler),
+            secret_provider_arc: Arc::new(secret_provider),
+            undergoing_requests_lock_arc: Arc::new(RwLock::new(UndergoingRequests::new())),
+            control_arc: Arc::new(ServerControl::default()),
+            _phantom_return_type: Default::default(),
+            _phantom_error_type: Default::default(),
+        }
+    }
+
+    /// Set a buffer size for receiving the request payload (default: `1500`).
+    pub fn set_buffer_size(&mut self, buf_size: usize) {
+        self.buf_size = buf_size;
+    }
+
+    /// Set a flag to specify whether to skip the authenticity validation or not (default: `false`).
+    pub fn set_skip_authenticity_validation(&mut self, skip_authenticity_validation: bool) {
+        self.request_decoder
+            .set_skip_authenticity_validation(skip_authenticity_validation);
+    }
+
+    /// Set a flag to specify whether to require Message-Authenticator of Access-Request
+    /// (see `Server::set_require_message_authenticator()`).
+    pub fn set_require_message_authenticator(&mut self, require_message_authenticator: bool) {
+        self.request_decoder
+            .set_require_message_authenticator(require_message_authenticator);
+    }
+
+    /// Set the allowed clock skew of Event-Timestamp (see `Server::set_event_timestamp_window()`).
+    pub fn set_event_timestamp_window(&mut self, event_timestamp_window: Option<Duration>) {
+        self.request_decoder
+            .set_event_timestamp_window(event_timestamp_window);
+    }
+
+    /// Set the limits of the requests (see `Server::set_decoding_limits()`).
+    pub fn set_decoding_limits(&mut self, decoding_limits: DecodingLimits) {
+        self.request_decoder.set_limits(decoding_limits);
+    }
+
+    /// Set the code of the response to Status-Server (see `Server::set_status_server_response_code()`).
+    pub fn set_status_server_response_code(&mut self, status_server_response_code: Option<Code>) {
+        self.status_server_response_code = status_server_response_code;
+    }
+
+    /// Returns the transport of the server.
+    pub fn get_transport(&self) -> Arc<Tr> {
+        self.transport_arc.clone()
+    }
+
+    /// Returns the counters and the drain switch of the server.
+    pub fn get_control(&self) -> Arc<ServerControl> {
+        self.control_arc.clone()
+    }
+
+    /// Starts the RADIUS requests handling.
+    ///
+    /// This returns when receiving from the transport has failed, or when `shutdown_trigger` has been resolved.
+    pub async fn run(&self, shutdown_trigger: impl Future) -> Result<(), io::Error> {
+        tokio::select! {
+            res = self.run_loop() => {
+                res
+            }
+            _ = shutdown_trigger => {
+                info!("server is shutting down");
+                Ok(())
+            }
+        }
+    }
+
+    async fn run_loop(&self) -> Result<(), io::Error> {
+        let local_addr = self.transport_arc.local_addr()?;
+        loop {
+            let mut request_data = vec![0; self.buf_size];
+            let (size, peer) = self.transport_arc.recv_from(&mut request_data).await?;
+            request_data.truncate(size);
+
+            let started_at = Instant::now();
+            self.control_arc.begin();
+            if self.control_arc.is_draining() {
+                self.control_arc
+                    .observe_drop(peer.get_addr(), DropReason::Draining);
+                self.control_arc
+                    .record(RequestOutcome::Dropped, started_at.elapsed());
+                continue;
+            }
+
+            let transport = self.transport_arc.clone();
+            let request_handler = self.request_handler_arc.clone();
+            let secret_provider = self.secret_provider_arc.clone();
+            let undergoing_requests_lock = self.undergoing_requests_lock_arc.clone();
+            let request_decoder = self.request_decoder.clone();
+            let status_server_response_code = self.status_server_response_code;
+            let control = self.control_arc.clone();
+
+            tasks::spawn(
+                &tasks::SERVER_REQUEST,
+                correlation::serve(peer.get_addr(), async move {
+                    let outcome = Self::process_request(
+                        &transport,
+                        &request_data,
+                        local_addr,
+                        &peer,
+                        &undergoing_requests_lock,
+                        &request_handler,
+                        &secret_provider,
+                        &request_decoder,
+                        status_server_response_code,
+                        &control,
+                    )
+                    .await;
+                    correlation::trace_outcome(&outcome, started_at.elapsed());
+                    control.record(outcome, started_at.elapsed());
+                }),
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn process_request(
+        transport: &Tr,
+        request_data: &[u8],
+        local_addr: std::net::SocketAddr,
+        peer: &Peer,
+        undergoing_requests_lock: &RwLock<UndergoingRequests>,
+        request_handler: &T,
+        secret_provider: &U,
+        request_decoder: &RequestDecoder,
+        status_server_response_code: Option<Code>,
+        control: &ServerControl,
+    ) -> RequestOutcome {
+        let remote_addr = peer.get_addr();
+        let secret: Vec<u8> = match secret_provider.fetch_secret(remote_addr) {
+            Ok(secret) => secret,
+            Err(e) => {
+                error!(
+                    "failed to fetch secret binary vector from the secret provider; {}",
+                    e
+                );
+                control.observe_drop(remote_addr, DropReason::UnknownClient);
+                return RequestOutcome::Dropped;
+            }
+        };
+
+        let packet = match request_decoder.decode(request_data, &secret, &deterministic::now()) {
+            Ok(packet) => packet,
+            Err(e @ RequestDecodingError::EmptySecretError()) => {
+                error!("{}", e);
+                control.observe_drop(remote_addr, DropReason::UnknownClient);
+                return RequestOutcome::Dropped;
+            }
+            Err(e) if e.get_kind() == ErrorKind::Malformed => {
+                debug!("{}; failed request data => {:?}", e, request_data);
+                correlation::trace_decoding_failure(&e);
+                control.record_malformed();
+                control.observe_drop(remote_addr, DropReason::Malformed);
+                return RequestOutcome::Dropped;
+            }
+            Err(e) => {
+                info!("{}", e);
+                correlation::trace_decoding_failure(&e);
+                control.observe_drop(remote_addr, DropReason::Invalid);
+                return RequestOutcome::Dropped;
+            }
+        };
+        correlation::trace_decoded(&packet);
+
+        control.observe_request(packet.get_code());
+
+        if let (Code::StatusServer, Some(code)) = (packet.get_code(), status_server_response_code) {
+            let response = make_status_server_response(&packet, code);
+            return match response.encode() {
+                Ok(response_data) => match transport.send_to(&response_data, peer).await {
+                    Ok(_) => RequestOutcome::Handled,
+                    Err(e) => {
+                        error!("failed to respond to Status-Server; {}", e);
+                        RequestOutcome::HandlerError
+                    }
+                },
+                Err(e) => {
+                    error!("failed to encode the response to Status-Server; {}", e);
+                    RequestOutcome::HandlerError
+                }
+            };
+        }
+
+        let identifier = packet.get_identifier();
+        if !undergoing_requests_lock
+            .write()
+            .unwrap()
+            .begin(remote_addr, identifier)
+        {
+            return RequestOutcome::Duplicated;
+        }
+
+        let outcome = match request_handler
+            .handle_radius_request(
+                transport,
+                peer,
+                &Request::new(local_addr, remote_addr, packet),
+            )
+            .await
+        {
+            Ok(_) => RequestOutcome::Handled,
+            Err(e) => {
+                error!("{:?}", e);
+                RequestOutcome::HandlerError
+            }
+        };
+
+        undergoing_requests_lock
+            .write()
+            .unwrap()
+            .finish(remote_addr, identifier);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::rfc2865;
+    use crate::correlation;
+    use crate::runtime::{GenericClient, TokioRuntime};
+    use crate::server::transport::{TransportRequestHandler, TransportServer};
+    use crate::server::{SecretProvider, SecretProviderError};
+    use crate::transport::{channel, ChannelTransport, Peer, Transport};
+
+    struct EchoingHandler {}
+
+    #[async_trait]
+    impl TransportRequestHandler<ChannelTransport, (), io::Error> for EchoingHandler {
+        async fn handle_radius_request(
+            &self,
+            transport: &ChannelTransport,
+            peer: &Peer,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            assert!(correlation::current().is_some());
+            let mut response = req.get_packet().make_response_packet(Code::AccessAccept);
+            rfc2865::add_reply_message(&mut response, req.get_local_addr().to_string().as_str());
+            transport.send_to(&response.encode().unwrap(), peer).await
+        }
+    }
+
+    struct StaticSecretProvider {}
+
+    impl SecretProvider for StaticSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_channel_round_trip() {
+        let client_addr: SocketAddr = "192.0.2.1:50000".parse().unwrap();
+        let server_addr: SocketAddr = "192.0.2.2:1812".parse().unwrap();
+        let (client_transport, server_transport) = channel(client_addr, server_addr);
+
+        let server = Arc::new(TransportServer::new(
+            server_transport,
+            EchoingHandler {},
+            StaticSecretProvider {},
+        ));
+        let control = server.get_control();
+        {
+            let server = server.clone();
+            tokio::spawn(async move { server.run(std::future::pending::<()>()).await });
+        }
+
+        let client = GenericClient::new(TokioRuntime, Some(Duration::from_secs(3)));
+        let response = client
+            .send_packet_over(
+                &client_transport,
+                &Peer::new(server_addr),
+                &Packet::new(Code::AccessRequest, b"secret"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(
+            rfc2865::lookup_reply_message(&response).unwrap().unwrap(),
+            "192.0.2.2:1812"
+        );
+        assert_eq!(control.get_stats().get_received(), 1);
+    }
+}
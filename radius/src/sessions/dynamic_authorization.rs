@@ -0,0 +1,383 @@
+use std::net::{IpAddr, SocketAddr};
+
+use thiserror::Error;
+
+use crate::client::{Client, ClientError};
+use crate::core::code::Code;
+use crate::core::packet::Packet;
+use crate::core::{rfc2865, rfc2866, rfc3576};
+use crate::error::ErrorKind;
+use crate::server::{SecretProvider, SecretProviderError};
+use crate::sessions::Session;
+
+/// The default port number of the Dynamic Authorization Server on the NAS.
+///
+/// see also: https://tools.ietf.org/html/rfc5176#section-3
+pub const DEFAULT_DYNAMIC_AUTHORIZATION_PORT: u16 = 3799;
+
+#[derive(Error, Debug)]
+pub enum DynamicAuthorizationError {
+    /// An error that is raised when it fails to fetch the shared-secret of the NAS.
+    #[error("failed to fetch the secret of the NAS: {0}")]
+    FailedFetchingSecretError(#[from] SecretProviderError),
+    /// An error that is raised when it fails to exchange the packets with the NAS.
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    /// An error that is raised when the NAS returns a packet that is neither ACK nor NAK for the request.
+    #[error("unexpected response from the NAS: {0}")]
+    UnexpectedResponseError(String),
+}
+
+impl DynamicAuthorizationError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            DynamicAuthorizationError::FailedFetchingSecretError(e) => e.get_kind(),
+            DynamicAuthorizationError::ClientError(e) => e.get_kind(),
+            DynamicAuthorizationError::UnexpectedResponseError(_) => ErrorKind::UnexpectedResponse,
+        }
+    }
+
+    /// Returns whether the request may succeed later (see `ClientError::is_retryable()`).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DynamicAuthorizationError::ClientError(e) => e.is_retryable(),
+            e => e.get_kind().is_retryable(),
+        }
+    }
+}
+
+/// The response of a Disconnect-Request or CoA-Request.
+#[derive(Debug)]
+pub enum DynamicAuthorizationResponse {
+    /// The NAS has acknowledged the request (i.e. Disconnect-ACK or CoA-ACK).
+    Ack(Packet),
+    /// The NAS has refused the request (i.e. Disconnect-NAK or CoA-NAK);
+    /// this contains the Error-Cause of the response if the NAS has given that.
+    Nak(Option<rfc3576::ErrorCause>, Packet),
+}
+
+impl DynamicAuthorizationResponse {
+    pub fn is_ack(&self) -> bool {
+        matches!(self, DynamicAuthorizationResponse::Ack(_))
+    }
+
+    pub fn get_packet(&self) -> &Packet {
+        match self {
+            DynamicAuthorizationResponse::Ack(packet) => packet,
+            DynamicAuthorizationResponse::Nak(_, packet) => packet,
+        }
+    }
+
+    /// Returns the Error-Cause of the response, if any (e.g. `rfc3576::ERROR_CAUSE_SESSION_CONTEXT_NOT_FOUND`).
+    ///
+    /// An ACK can also have an Error-Cause (e.g. `rfc3576::ERROR_CAUSE_RESIDUAL_CONTEXT_REMOVED`).
+    pub fn get_error_cause(&self) -> Option<rfc3576::ErrorCause> {
+        match self {
+            DynamicAuthorizationResponse::Ack(packet) => {
+                rfc3576::lookup_error_cause(packet).and_then(Result::ok)
+            }
+            DynamicAuthorizationResponse::Nak(error_cause, _) => *error_cause,
+        }
+    }
+}
+
+/// Interpret the response of a Disconnect-Request or CoA-Request (e.g. the one that has been sent by another client).
+///
+/// The response has to be the ACK or the NAK of the request code.
+pub fn interpret_response(
+    request_code: Code,
+    response: Packet,
+) -> Result<DynamicAuthorizationResponse, DynamicAuthorizationError> {
+    match (request_code, response.get_code()) {
+        (Code::DisconnectRequest, Code::DisconnectACK) | (Code::CoARequest, Code::CoAACK) => {
+            Ok(DynamicAuthorizationResponse::Ack(response))
+        }
+        (Code::DisconnectRequest, Code::DisconnectNAK) | (Code::CoARequest, Code::CoANAK) => {
+            let error_cause = rfc3576::lookup_error_cause(&response)
+                .transpose()
+                .unwrap_or_default();
+            Ok(DynamicAuthorizationResponse::Nak(error_cause, response))
+        }
+        (_, response_code) => Err(DynamicAuthorizationError::UnexpectedResponseError(format!(
+            "{} for {}",
+            response_code.string(),
+            request_code.string()
+        ))),
+    }
+}
+
+/// Make a Disconnect-Request or CoA-Request packet that identifies the session.
+///
+/// This has the NAS identification attributes (NAS-IP-Address and NAS-Identifier) that have been reported by the NAS,
+/// and the session identification attributes (User-Name and Acct-Session-Id).
+///
+/// see also: https://tools.ietf.org/html/rfc5176#section-3
+pub fn make_session_request_packet(session: &Session, code: Code, secret: &[u8]) -> Packet {
+    let mut packet = Packet::new(code, secret);
+    if let Some(nas_ip_address) = session.get_nas_ip_address() {
+        rfc2865::add_nas_ip_address(&mut packet, &nas_ip_address);
+    }
+    if let Some(nas_identifier) = session.get_nas_identifier() {
+        rfc2865::add_nas_identifier(&mut packet, nas_identifier);
+    }
+    if let Some(user_name) = session.get_user_name() {
+        rfc2865::add_user_name(&mut packet, user_name);
+    }
+    rfc2866::add_acct_session_id(&mut packet, session.get_session_id());
+    packet
+}
+
+/// A client to send Disconnect-Request and CoA-Request to the NAS that owns a session.
+///
+/// The request is sent to the address that the accounting packets of the session have come from,
+/// and it is signed by the secret that `secret_provider` returns for that address.
+pub struct DynamicAuthorizationClient<U: SecretProvider> {
+    client: Client,
+    secret_provider: U,
+    port: u16,
+}
+
+impl<U: SecretProvider> DynamicAuthorizationClient<U> {
+    pub fn new(client: Client, secret_provider: U) -> Self {
+        DynamicAuthorizationClient {
+            client,
+            secret_provider,
+            port: DEFAULT_DYNAMIC_AUTHORIZATION_PORT,
+        }
+    }
+
+    /// Set the port number of the Dynamic Authorization Server on the NAS (default: `3799`).
+    pub fn set_port(&mut self, port: u16) {
+        self.port = port;
+    }
+
+    /// Send a Disconnect-Request for the session.
+    pub async fn disconnect(
+        &self,
+        session: &Session,
+    ) -> Result<DynamicAuthorizationResponse, DynamicAuthorizationError> {
+        self.send(session, Code::DisconnectRequest, |_| {}).await
+    }
+
+    /// Send a CoA-Request for the session. `add_attributes` can add the attributes to change into the request packet.
+    pub async fn change_authorization<F: FnOnce(&mut Packet)>(
+        &self,
+        session: &Session,
+        add_attributes: F,
+    ) -> Result<DynamicAuthorizationResponse, DynamicAuthorizationError> {
+        self.send(session, Code::CoARequest, add_attributes).await
+    }
+
+    /// Send a Disconnect-Request to the NAS without a tracked session.
+    /// `add_attributes` has to add the attributes that identify the session (e.g. User-Name, Acct-Session-Id or
+    /// Framed-IP-Address) into the request packet.
+    pub async fn disconnect_nas<F: FnOnce(&mut Packet)>(
+        &self,
+        nas_ip: IpAddr,
+        add_attributes: F,
+    ) -> Result<DynamicAuthorizationResponse, DynamicAuthorizationError> {
+        self.send_nas(nas_ip, Code::DisconnectRequest, add_attributes)
+            .await
+    }
+
+    /// Send a CoA-Request to the NAS without a tracked session.
+    /// `add_attributes` has to add the attributes that identify the session and the attributes to change
+    /// into the request packet.
+    pub async fn change_authorization_nas<F: FnOnce(&mut Packet)>(
+        &self,
+        nas_ip: IpAddr,
+        add_attributes: F,
+    ) -> Result<DynamicAuthorizationResponse, DynamicAuthorizationError> {
+        self.send_nas(nas_ip, Code::CoARequest, add_attributes)
+            .await
+    }
+
+    async fn send<F: FnOnce(&mut Packet)>(
+        &self,
+        session: &Session,
+        code: Code,
+        add_attributes: F,
+    ) -> Result<DynamicAuthorizationResponse, DynamicAuthorizationError> {
+        let remote_addr = SocketAddr::new(session.get_key().get_nas_ip(), self.port);
+        let secret = self.secret_provider.fetch_secret(remote_addr)?;
+
+        let mut packet = make_session_request_packet(session, code, &secret);
+        add_attributes(&mut packet);
+
+        let response = self.client.send_packet(&remote_addr, &packet).await?;
+        interpret_response(code, response)
+    }
+
+    async fn send_nas<F: FnOnce(&mut Packet)>(
+        &self,
+        nas_ip: IpAddr,
+        code: Code,
+        add_attributes: F,
+    ) -> Result<DynamicAuthorizationResponse, DynamicAuthorizationError> {
+        let remote_addr = SocketAddr::new(nas_ip, self.port);
+        let secret = self.secret_provider.fetch_secret(remote_addr)?;
+
+        let mut packet = Packet::new(code, &secret);
+        add_attributes(&mut packet);
+
+        let response = self.client.send_packet(&remote_addr, &packet).await?;
+        interpret_response(code, response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use tokio::net::UdpSocket;
+
+    use crate::client::Client;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2865, rfc2866, rfc3576};
+    use crate::server::{SecretProvider, SecretProviderError};
+    use crate::sessions::{
+        interpret_response, DynamicAuthorizationClient, DynamicAuthorizationError,
+        DynamicAuthorizationResponse, Session,
+    };
+
+    struct MySecretProvider {}
+
+    impl SecretProvider for MySecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_and_change_authorization() {
+        let nas = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let nas_port = nas.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 1500];
+            loop {
+                let (len, remote_addr) = nas.recv_from(&mut buf).await.unwrap();
+                let request = Packet::decode(&buf[..len], b"secret").unwrap();
+                assert_eq!(
+                    rfc2866::lookup_acct_session_id(&request).unwrap().unwrap(),
+                    "s1"
+                );
+                assert_eq!(rfc2865::lookup_user_name(&request).unwrap().unwrap(), "bob");
+                let response = match request.get_code() {
+                    Code::DisconnectRequest => request.make_response_packet(Code::DisconnectACK),
+                    _ => {
+                        let mut response = request.make_response_packet(Code::CoANAK);
+                        rfc3576::add_error_cause(
+                            &mut response,
+                            rfc3576::ERROR_CAUSE_UNSUPPORTED_ATTRIBUTE,
+                        );
+                        response
+                    }
+                };
+                nas.send_to(&response.encode().unwrap(), remote_addr)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_session_id(&mut packet, "s1");
+        rfc2865::add_user_name(&mut packet, "bob");
+        let session = Session::from_packet(
+            &packet,
+            "127.0.0.1:50000".parse::<SocketAddr>().unwrap(),
+            Utc::now(),
+        )
+        .unwrap()
+        .unwrap();
+
+        let mut client = DynamicAuthorizationClient::new(
+            Client::new(None, Some(Duration::from_secs(1))),
+            MySecretProvider {},
+        );
+        client.set_port(nas_port);
+
+        let response = client.disconnect(&session).await.unwrap();
+        assert!(response.is_ack());
+
+        let response = client
+            .change_authorization(&session, |packet| {
+                rfc2865::add_session_timeout(packet, 3600);
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            response,
+            DynamicAuthorizationResponse::Nak(Some(rfc3576::ERROR_CAUSE_UNSUPPORTED_ATTRIBUTE), _)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_nas() {
+        let nas = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let nas_addr = nas.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 1500];
+            let (len, remote_addr) = nas.recv_from(&mut buf).await.unwrap();
+            let request = Packet::decode(&buf[..len], b"secret").unwrap();
+            assert_eq!(request.get_code(), Code::DisconnectRequest);
+            assert_eq!(
+                rfc2865::lookup_framed_ip_address(&request)
+                    .unwrap()
+                    .unwrap(),
+                "192.0.2.10".parse::<std::net::Ipv4Addr>().unwrap()
+            );
+            let mut response = request.make_response_packet(Code::DisconnectNAK);
+            rfc3576::add_error_cause(
+                &mut response,
+                rfc3576::ERROR_CAUSE_SESSION_CONTEXT_NOT_FOUND,
+            );
+            nas.send_to(&response.encode().unwrap(), remote_addr)
+                .await
+                .unwrap();
+        });
+
+        let mut client = DynamicAuthorizationClient::new(
+            Client::new(None, Some(Duration::from_secs(1))),
+            MySecretProvider {},
+        );
+        client.set_port(nas_addr.port());
+        let response = client
+            .disconnect_nas(nas_addr.ip(), |packet| {
+                rfc2865::add_framed_ip_address(packet, &"192.0.2.10".parse().unwrap());
+            })
+            .await
+            .unwrap();
+        assert!(!response.is_ack());
+        assert_eq!(
+            response.get_error_cause(),
+            Some(rfc3576::ERROR_CAUSE_SESSION_CONTEXT_NOT_FOUND)
+        );
+    }
+
+    #[test]
+    fn test_interpret_response() {
+        let request = Packet::new(Code::CoARequest, b"secret");
+        let mut ack = request.make_response_packet(Code::CoAACK);
+        rfc3576::add_error_cause(&mut ack, rfc3576::ERROR_CAUSE_RESIDUAL_CONTEXT_REMOVED);
+        let response = interpret_response(Code::CoARequest, ack).unwrap();
+        assert!(response.is_ack());
+        assert_eq!(
+            response.get_error_cause(),
+            Some(rfc3576::ERROR_CAUSE_RESIDUAL_CONTEXT_REMOVED)
+        );
+
+        let err = interpret_response(
+            Code::CoARequest,
+            request.make_response_packet(Code::DisconnectACK),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            DynamicAuthorizationError::UnexpectedResponseError(_)
+        ));
+    }
+}
@@ -0,0 +1,489 @@
+//! Tracking the live sessions on the NASes.
+//!
+//! A `Session` is identified by the NAS address and Acct-Session-Id (`SessionKey`), and it is maintained
+//! in a `SessionStore` according to the Accounting-Request packets (`track_accounting_request()`):
+//! Start, Interim-Update and Stop of the sessions, and Accounting-On/Off that clear all the sessions of a NAS.
+//! `InMemorySessionStore` is an implementation of the store for a single server process.
+//!
+//! On a server, `NasStatusMiddleware` closes the sessions of a NAS on its Accounting-On/Off (e.g. after a reboot) in
+//! front of the handler, and passes the closed ones to a `NasStatusHandler`.
+//!
+//! `SessionTimer` sweeps the sessions that have exceeded their Session-Timeout or Idle-Timeout.
+//! `SimultaneousUsePolicy` consults the store to enforce the limit of the concurrent sessions per user,
+//! and `DynamicAuthorizationClient` sends Disconnect-Request and CoA-Request (RFC 5176) for a session.
+
+#[cfg(all(feature = "tokio", feature = "rfc3576"))]
+mod dynamic_authorization;
+#[cfg(feature = "tokio")]
+mod nas_status;
+mod simultaneous_use;
+#[cfg(feature = "tokio")]
+mod timer;
+
+#[cfg(all(feature = "tokio", feature = "rfc3576"))]
+pub use dynamic_authorization::{
+    interpret_response, make_session_request_packet, DynamicAuthorizationClient,
+    DynamicAuthorizationError, DynamicAuthorizationResponse, DEFAULT_DYNAMIC_AUTHORIZATION_PORT,
+};
+#[cfg(feature = "tokio")]
+pub use nas_status::{NasStatus, NasStatusHandler, NasStatusMiddleware};
+pub use simultaneous_use::{SimultaneousUseAction, SimultaneousUseDecision, SimultaneousUsePolicy};
+#[cfg(feature = "tokio")]
+pub use timer::{SessionExpiry, SessionTimer};
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::core::avp::AVPError;
+use crate::core::deterministic;
+use crate::core::packet::Packet;
+use crate::core::request::Request;
+use crate::core::{rfc2865, rfc2866};
+use crate::error::ErrorKind;
+
+/// The identity of a session; it is unique per NAS.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionKey {
+    nas_ip: IpAddr,
+    session_id: String,
+}
+
+impl SessionKey {
+    pub fn new(nas_ip: IpAddr, session_id: &str) -> Self {
+        SessionKey {
+            nas_ip,
+            session_id: session_id.to_owned(),
+        }
+    }
+
+    pub fn get_nas_ip(&self) -> IpAddr {
+        self.nas_ip
+    }
+
+    pub fn get_session_id(&self) -> &str {
+        &self.session_id
+    }
+}
+
+/// A live session on a NAS.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    key: SessionKey,
+    nas_address: SocketAddr,
+    user_name: Option<String>,
+    nas_ip_address: Option<Ipv4Addr>,
+    nas_identifier: Option<String>,
+    nas_port: Option<u32>,
+    framed_ip_address: Option<Ipv4Addr>,
+    calling_station_id: Option<String>,
+    started_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    session_timeout: Option<Duration>,
+    idle_timeout: Option<Duration>,
+}
+
+impl Session {
+    /// Make a session from an Accounting-Request packet that has come from `nas_address`.
+    ///
+    /// It returns `None` if the packet doesn't have Acct-Session-Id.
+    pub fn from_packet(
+        packet: &Packet,
+        nas_address: SocketAddr,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Self>, AVPError> {
+        let session_id = match rfc2866::lookup_acct_session_id(packet) {
+            Some(session_id) => session_id?,
+            None => return Ok(None),
+        };
+
+        Ok(Some(Session {
+            key: SessionKey::new(nas_address.ip(), &session_id),
+            nas_address,
+            user_name: rfc2865::lookup_user_name(packet).transpose()?,
+            nas_ip_address: rfc2865::lookup_nas_ip_address(packet).transpose()?,
+            nas_identifier: rfc2865::lookup_nas_identifier(packet).transpose()?,
+            nas_port: rfc2865::lookup_nas_port(packet).transpose()?,
+            framed_ip_address: rfc2865::lookup_framed_ip_address(packet).transpose()?,
+            calling_station_id: rfc2865::lookup_calling_station_id(packet).transpose()?,
+            started_at: now,
+            updated_at: now,
+            session_timeout: None,
+            idle_timeout: None,
+        }))
+    }
+
+    pub fn get_key(&self) -> &SessionKey {
+        &self.key
+    }
+
+    /// Returns the source address of the accounting packets of the session.
+    pub fn get_nas_address(&self) -> SocketAddr {
+        self.nas_address
+    }
+
+    pub fn get_session_id(&self) -> &str {
+        self.key.get_session_id()
+    }
+
+    pub fn get_user_name(&self) -> Option<&str> {
+        self.user_name.as_deref()
+    }
+
+    pub fn get_nas_ip_address(&self) -> Option<Ipv4Addr> {
+        self.nas_ip_address
+    }
+
+    pub fn get_nas_identifier(&self) -> Option<&str> {
+        self.nas_identifier.as_deref()
+    }
+
+    pub fn get_nas_port(&self) -> Option<u32> {
+        self.nas_port
+    }
+
+    pub fn get_framed_ip_address(&self) -> Option<Ipv4Addr> {
+        self.framed_ip_address
+    }
+
+    pub fn get_calling_station_id(&self) -> Option<&str> {
+        self.calling_station_id.as_deref()
+    }
+
+    pub fn get_started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// Returns the time of the last accounting packet of the session.
+    pub fn get_updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    pub fn get_session_timeout(&self) -> Option<Duration> {
+        self.session_timeout
+    }
+
+    /// Set the maximum lifetime of the session (i.e. Session-Timeout).
+    pub fn set_session_timeout(&mut self, session_timeout: Option<Duration>) {
+        self.session_timeout = session_timeout;
+    }
+
+    pub fn get_idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    /// Set the maximum duration of the session without any accounting packet (i.e. Idle-Timeout).
+    pub fn set_idle_timeout(&mut self, idle_timeout: Option<Duration>) {
+        self.idle_timeout = idle_timeout;
+    }
+
+    /// Set the timeouts according to Session-Timeout and Idle-Timeout of a packet (e.g. the Access-Accept of the session).
+    /// The timeouts that the packet doesn't have are kept as they are.
+    pub fn set_timeouts_from_packet(&mut self, packet: &Packet) -> Result<(), AVPError> {
+        if let Some(session_timeout) = rfc2865::lookup_session_timeout(packet) {
+            self.session_timeout = Some(Duration::from_secs(u64::from(session_timeout?)));
+        }
+        if let Some(idle_timeout) = rfc2865::lookup_idle_timeout(packet) {
+            self.idle_timeout = Some(Duration::from_secs(u64::from(idle_timeout?)));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SessionStoreError {
+    /// An error that is raised when the session attributes in the packet are malformed.
+    #[error("failed to read the session attributes: {0}")]
+    InvalidSessionAttributeError(#[from] AVPError),
+    /// An error that represents a generic (i.e. unclassified) error that occurs on the session store.
+    #[error("unexpected error: {0}")]
+    GenericError(String),
+}
+
+impl SessionStoreError {
+    /// Returns the machine-readable kind of the error (see `radius::error`).
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            SessionStoreError::InvalidSessionAttributeError(e) => e.get_kind(),
+            SessionStoreError::GenericError(_) => ErrorKind::Storage,
+        }
+    }
+}
+
+/// SessionStore is a storage of the live sessions.
+pub trait SessionStore: 'static + Sync + Send {
+    /// This method has to insert the session, or replace the session that has the same key.
+    fn upsert(&self, session: Session) -> Result<(), SessionStoreError>;
+
+    /// This method has to remove the session and return that if it exists.
+    fn remove(&self, key: &SessionKey) -> Result<Option<Session>, SessionStoreError>;
+
+    fn get(&self, key: &SessionKey) -> Result<Option<Session>, SessionStoreError>;
+
+    /// This method has to return all of the sessions of the user.
+    fn find_by_user_name(&self, user_name: &str) -> Result<Vec<Session>, SessionStoreError>;
+
+    /// This method has to return all of the sessions in the store.
+    fn list(&self) -> Result<Vec<Session>, SessionStoreError>;
+
+    /// This method has to return all of the sessions of the NAS.
+    ///
+    /// The default implementation filters `list()`.
+    fn find_by_nas_ip(&self, nas_ip: IpAddr) -> Result<Vec<Session>, SessionStoreError> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|session| session.get_key().get_nas_ip() == nas_ip)
+            .collect())
+    }
+
+    /// This method has to return all of the sessions that have the Framed-IP-Address.
+    ///
+    /// The default implementation filters `list()`.
+    fn find_by_framed_ip_address(
+        &self,
+        framed_ip_address: Ipv4Addr,
+    ) -> Result<Vec<Session>, SessionStoreError> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|session| session.get_framed_ip_address() == Some(framed_ip_address))
+            .collect())
+    }
+
+    /// This method has to remove all of the sessions of the NAS (e.g. on its Accounting-On/Off) and return those.
+    ///
+    /// The default implementation removes each of `find_by_nas_ip()`.
+    fn remove_by_nas_ip(&self, nas_ip: IpAddr) -> Result<Vec<Session>, SessionStoreError> {
+        let mut removed = Vec::new();
+        for session in self.find_by_nas_ip(nas_ip)? {
+            if let Some(session) = self.remove(session.get_key())? {
+                removed.push(session);
+            }
+        }
+        Ok(removed)
+    }
+
+    /// This method has to return the number of the sessions in the store.
+    ///
+    /// The default implementation counts `list()`.
+    fn count(&self) -> Result<usize, SessionStoreError> {
+        Ok(self.list()?.len())
+    }
+}
+
+/// Update the sessions in the store according to an Accounting-Request.
+///
+/// `Start` and `Interim-Update` register the session (the start time and the timeouts are kept on updates)
+/// and `Stop` removes that. `Accounting-On` and `Accounting-Off` remove all the sessions of the NAS
+/// (i.e. the source address of the request), since the NAS has (re)started or is going to stop.
+/// The other packets are ignored.
+///
+/// Session-Timeout and Idle-Timeout in the Accounting-Request are applied to the session as well.
+pub fn track_accounting_request<S: SessionStore + ?Sized>(
+    store: &S,
+    request: &Request,
+) -> Result<(), SessionStoreError> {
+    let packet = request.get_packet();
+    let status_type = match rfc2866::lookup_acct_status_type(packet) {
+        Some(status_type) => status_type?,
+        None => return Ok(()),
+    };
+    if let rfc2866::ACCT_STATUS_TYPE_ACCOUNTING_ON | rfc2866::ACCT_STATUS_TYPE_ACCOUNTING_OFF =
+        status_type
+    {
+        return store
+            .remove_by_nas_ip(request.get_remote_addr().ip())
+            .map(|_| ());
+    }
+
+    let mut session =
+        match Session::from_packet(packet, request.get_remote_addr(), deterministic::now())? {
+            Some(session) => session,
+            None => return Ok(()),
+        };
+
+    match status_type {
+        rfc2866::ACCT_STATUS_TYPE_START => {
+            session.set_timeouts_from_packet(packet)?;
+            store.upsert(session)
+        }
+        rfc2866::ACCT_STATUS_TYPE_INTERIM_UPDATE => {
+            if let Some(existing) = store.get(session.get_key())? {
+                session.started_at = existing.started_at;
+                session.session_timeout = existing.session_timeout;
+                session.idle_timeout = existing.idle_timeout;
+            }
+            session.set_timeouts_from_packet(packet)?;
+            store.upsert(session)
+        }
+        rfc2866::ACCT_STATUS_TYPE_STOP => store.remove(session.get_key()).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
+/// A `SessionStore` implementation that keeps the sessions on memory.
+#[derive(Default)]
+pub struct InMemorySessionStore {
+    sessions_lock: RwLock<HashMap<SessionKey, Session>>,
+}
+
+impl InMemorySessionStore {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl SessionStore for InMemorySessionStore {
+    fn upsert(&self, session: Session) -> Result<(), SessionStoreError> {
+        let mut sessions = self.sessions_lock.write().unwrap();
+        sessions.insert(session.get_key().clone(), session);
+        Ok(())
+    }
+
+    fn remove(&self, key: &SessionKey) -> Result<Option<Session>, SessionStoreError> {
+        let mut sessions = self.sessions_lock.write().unwrap();
+        Ok(sessions.remove(key))
+    }
+
+    fn get(&self, key: &SessionKey) -> Result<Option<Session>, SessionStoreError> {
+        let sessions = self.sessions_lock.read().unwrap();
+        Ok(sessions.get(key).cloned())
+    }
+
+    fn find_by_user_name(&self, user_name: &str) -> Result<Vec<Session>, SessionStoreError> {
+        let sessions = self.sessions_lock.read().unwrap();
+        Ok(sessions
+            .values()
+            .filter(|session| session.get_user_name() == Some(user_name))
+            .cloned()
+            .collect())
+    }
+
+    fn list(&self) -> Result<Vec<Session>, SessionStoreError> {
+        let sessions = self.sessions_lock.read().unwrap();
+        Ok(sessions.values().cloned().collect())
+    }
+
+    fn remove_by_nas_ip(&self, nas_ip: IpAddr) -> Result<Vec<Session>, SessionStoreError> {
+        let mut sessions = self.sessions_lock.write().unwrap();
+        let keys: Vec<SessionKey> = sessions
+            .keys()
+            .filter(|key| key.get_nas_ip() == nas_ip)
+            .cloned()
+            .collect();
+        Ok(keys.iter().filter_map(|key| sessions.remove(key)).collect())
+    }
+
+    fn count(&self) -> Result<usize, SessionStoreError> {
+        Ok(self.sessions_lock.read().unwrap().len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::{rfc2865, rfc2866};
+    use crate::sessions::{
+        track_accounting_request, InMemorySessionStore, SessionKey, SessionStore,
+    };
+
+    fn make_request(status_type: rfc2866::AcctStatusType, session_id: &str) -> Request {
+        make_nas_request(status_type, session_id, "192.0.2.1:50000")
+    }
+
+    fn make_nas_request(
+        status_type: rfc2866::AcctStatusType,
+        session_id: &str,
+        nas_address: &str,
+    ) -> Request {
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut packet, status_type);
+        if !session_id.is_empty() {
+            rfc2866::add_acct_session_id(&mut packet, session_id);
+            rfc2865::add_user_name(&mut packet, "bob");
+        }
+        Request::new(
+            "192.0.2.254:1813".parse::<SocketAddr>().unwrap(),
+            nas_address.parse::<SocketAddr>().unwrap(),
+            packet,
+        )
+    }
+
+    #[test]
+    fn test_track_accounting_request() {
+        let store = InMemorySessionStore::new();
+        let key = SessionKey::new("192.0.2.1".parse().unwrap(), "s1");
+
+        track_accounting_request(&store, &make_request(rfc2866::ACCT_STATUS_TYPE_START, "s1"))
+            .unwrap();
+        track_accounting_request(&store, &make_request(rfc2866::ACCT_STATUS_TYPE_START, "s2"))
+            .unwrap();
+        let started_at = store.get(&key).unwrap().unwrap().get_started_at();
+        assert_eq!(store.find_by_user_name("bob").unwrap().len(), 2);
+
+        track_accounting_request(
+            &store,
+            &make_request(rfc2866::ACCT_STATUS_TYPE_INTERIM_UPDATE, "s1"),
+        )
+        .unwrap();
+        let session = store.get(&key).unwrap().unwrap();
+        assert_eq!(session.get_started_at(), started_at);
+        assert_eq!(session.get_user_name(), Some("bob"));
+
+        track_accounting_request(&store, &make_request(rfc2866::ACCT_STATUS_TYPE_STOP, "s1"))
+            .unwrap();
+        assert!(store.get(&key).unwrap().is_none());
+        assert_eq!(store.find_by_user_name("bob").unwrap().len(), 1);
+        assert!(store.find_by_user_name("alice").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_accounting_on_off() {
+        let store = InMemorySessionStore::new();
+        let nas1 = "192.0.2.1:50000";
+        let nas2 = "192.0.2.2:50000";
+        for (session_id, nas_address) in [("s1", nas1), ("s2", nas1), ("s3", nas2)] {
+            track_accounting_request(
+                &store,
+                &make_nas_request(rfc2866::ACCT_STATUS_TYPE_START, session_id, nas_address),
+            )
+            .unwrap();
+        }
+        assert_eq!(store.count().unwrap(), 3);
+        assert_eq!(
+            store
+                .find_by_nas_ip("192.0.2.1".parse().unwrap())
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // the NAS has rebooted
+        track_accounting_request(
+            &store,
+            &make_nas_request(rfc2866::ACCT_STATUS_TYPE_ACCOUNTING_ON, "", nas1),
+        )
+        .unwrap();
+        assert_eq!(store.count().unwrap(), 1);
+        assert!(store
+            .find_by_nas_ip("192.0.2.1".parse().unwrap())
+            .unwrap()
+            .is_empty());
+
+        track_accounting_request(
+            &store,
+            &make_nas_request(rfc2866::ACCT_STATUS_TYPE_ACCOUNTING_OFF, "", nas2),
+        )
+        .unwrap();
+        assert_eq!(store.count().unwrap(), 0);
+    }
+}
@@ -0,0 +1,212 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::net::UdpSocket;
+
+use crate::core::code::Code;
+use crate::core::packet::Packet;
+use crate::core::request::Request;
+use crate::core::rfc2866;
+use crate::server::middleware::{Middleware, Next};
+use crate::sessions::{Session, SessionStore};
+
+/// The state of a NAS that its Accounting-Request of Accounting-On or Accounting-Off tells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NasStatus {
+    /// The NAS has (re)started, e.g. after a reboot; the sessions that it had before are gone.
+    AccountingOn,
+    /// The NAS is going to stop, e.g. for a maintenance; it ends all the sessions.
+    AccountingOff,
+}
+
+impl NasStatus {
+    /// Returns the status of an Accounting-Request of Accounting-On or Accounting-Off, or `None` for the other packets
+    /// (e.g. Start and Stop of a session).
+    pub fn from_packet(packet: &Packet) -> Option<NasStatus> {
+        if packet.get_code() != Code::AccountingRequest {
+            return None;
+        }
+        match rfc2866::lookup_acct_status_type(packet)?.ok()? {
+            rfc2866::ACCT_STATUS_TYPE_ACCOUNTING_ON => Some(NasStatus::AccountingOn),
+            rfc2866::ACCT_STATUS_TYPE_ACCOUNTING_OFF => Some(NasStatus::AccountingOff),
+            _ => None,
+        }
+    }
+}
+
+/// A callback of Accounting-On/Off of the NASes.
+#[async_trait]
+pub trait NasStatusHandler: 'static + Sync + Send {
+    /// This method is called with the sessions of the NAS that have been removed from the store, e.g. to write the
+    /// Stop records of them, or to release their Framed-IP-Addresses.
+    async fn on_nas_status(
+        &self,
+        request: &Request,
+        status: NasStatus,
+        closed_sessions: Vec<Session>,
+    );
+}
+
+/// A `Middleware` that closes the sessions of a NAS on its Accounting-On/Off.
+///
+/// The sessions of the source address of the request are removed from the store (see
+/// `SessionStore::remove_by_nas_ip()`) and passed to the `NasStatusHandler`, then the request goes on to the rest of the
+/// chain as usual, which responds to it with Accounting-Response. If the store fails, the error is logged and the
+/// request goes on as well, since the NAS would keep retransmitting Accounting-On without the response.
+pub struct NasStatusMiddleware<S: SessionStore, H: NasStatusHandler> {
+    store: Arc<S>,
+    handler: H,
+}
+
+impl<S: SessionStore, H: NasStatusHandler> NasStatusMiddleware<S, H> {
+    pub fn new(store: Arc<S>, handler: H) -> Self {
+        NasStatusMiddleware { store, handler }
+    }
+}
+
+#[async_trait]
+impl<T, E, S, H> Middleware<T, E> for NasStatusMiddleware<S, H>
+where
+    T: 'static,
+    E: 'static,
+    S: SessionStore,
+    H: NasStatusHandler,
+{
+    async fn handle(
+        &self,
+        conn: &UdpSocket,
+        request: &Request,
+        next: Next<'_, T, E>,
+    ) -> Result<T, E> {
+        if let Some(status) = NasStatus::from_packet(request.get_packet()) {
+            let nas_ip = request.get_remote_addr().ip();
+            match self.store.remove_by_nas_ip(nas_ip) {
+                Ok(closed_sessions) => {
+                    info!(
+                        "{:?} of {}: closed {} session(s)",
+                        status,
+                        nas_ip,
+                        closed_sessions.len()
+                    );
+                    self.handler
+                        .on_nas_status(request, status, closed_sessions)
+                        .await;
+                }
+                Err(e) => warn!(
+                    "failed to close the sessions of {} on {:?}; {}",
+                    nas_ip, status, e
+                ),
+            }
+        }
+        next.run(conn, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use tokio::net::UdpSocket;
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::rfc2866;
+    use crate::server::middleware::MiddlewareChain;
+    use crate::server::RequestHandler;
+    use crate::sessions::{
+        track_accounting_request, InMemorySessionStore, NasStatus, NasStatusHandler,
+        NasStatusMiddleware, Session, SessionStore,
+    };
+
+    struct TrackingHandler {
+        store: Arc<InMemorySessionStore>,
+    }
+
+    #[async_trait]
+    impl RequestHandler<(), String> for TrackingHandler {
+        async fn handle_radius_request(
+            &self,
+            _conn: &UdpSocket,
+            request: &Request,
+        ) -> Result<(), String> {
+            track_accounting_request(self.store.as_ref(), request).map_err(|e| e.to_string())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingNasStatusHandler {
+        closed: Mutex<Vec<(NasStatus, Vec<String>)>>,
+    }
+
+    #[async_trait]
+    impl NasStatusHandler for Arc<RecordingNasStatusHandler> {
+        async fn on_nas_status(
+            &self,
+            _request: &Request,
+            status: NasStatus,
+            closed_sessions: Vec<Session>,
+        ) {
+            let mut session_ids: Vec<_> = closed_sessions
+                .iter()
+                .map(|session| session.get_session_id().to_owned())
+                .collect();
+            session_ids.sort();
+            self.closed.lock().unwrap().push((status, session_ids));
+        }
+    }
+
+    fn make_request(status_type: rfc2866::AcctStatusType, session_id: &str, nas: &str) -> Request {
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_status_type(&mut packet, status_type);
+        if !session_id.is_empty() {
+            rfc2866::add_acct_session_id(&mut packet, session_id);
+        }
+        Request::new(
+            "192.0.2.254:1813".parse::<SocketAddr>().unwrap(),
+            nas.parse::<SocketAddr>().unwrap(),
+            packet,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_nas_status_middleware() {
+        let store = Arc::new(InMemorySessionStore::new());
+        let recorder = Arc::new(RecordingNasStatusHandler::default());
+        let chain = MiddlewareChain::new(TrackingHandler {
+            store: store.clone(),
+        })
+        .middleware(NasStatusMiddleware::new(store.clone(), recorder.clone()));
+        let conn = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+        let nas1 = "192.0.2.1:50000";
+        let nas2 = "192.0.2.2:50000";
+        for (session_id, nas) in [("s1", nas1), ("s2", nas1), ("s3", nas2)] {
+            let request = make_request(rfc2866::ACCT_STATUS_TYPE_START, session_id, nas);
+            chain.handle_radius_request(&conn, &request).await.unwrap();
+        }
+        assert_eq!(store.count().unwrap(), 3);
+        assert!(recorder.closed.lock().unwrap().is_empty());
+
+        let request = make_request(rfc2866::ACCT_STATUS_TYPE_ACCOUNTING_ON, "", nas1);
+        chain.handle_radius_request(&conn, &request).await.unwrap();
+        let request = make_request(rfc2866::ACCT_STATUS_TYPE_ACCOUNTING_OFF, "", nas2);
+        chain.handle_radius_request(&conn, &request).await.unwrap();
+        assert_eq!(store.count().unwrap(), 0);
+        assert_eq!(
+            *recorder.closed.lock().unwrap(),
+            vec![
+                (
+                    NasStatus::AccountingOn,
+                    vec!["s1".to_owned(), "s2".to_owned()]
+                ),
+                (NasStatus::AccountingOff, vec!["s3".to_owned()]),
+            ]
+        );
+
+        let request = Packet::new(Code::AccessRequest, b"secret");
+        assert_eq!(NasStatus::from_packet(&request), None);
+    }
+}
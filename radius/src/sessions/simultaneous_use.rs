@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use crate::core::request::Request;
+use crate::core::rfc2865;
+use crate::sessions::{Session, SessionStore, SessionStoreError};
+
+/// The action to take when a user exceeds the limit of the concurrent sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimultaneousUseAction {
+    /// Reject the new Access-Request.
+    Reject,
+    /// Accept the new Access-Request and disconnect the oldest sessions to keep the limit.
+    DisconnectOldest,
+}
+
+/// The result of `SimultaneousUsePolicy::check()`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimultaneousUseDecision {
+    /// The user doesn't exceed the limit.
+    Allow,
+    /// The request should be rejected; this contains the live sessions of the user.
+    Reject(Vec<Session>),
+    /// The request can be accepted after the contained sessions are disconnected (e.g. by Disconnect-Request).
+    Disconnect(Vec<Session>),
+}
+
+impl SimultaneousUseDecision {
+    /// Returns whether the request should be accepted or not.
+    pub fn is_allowed(&self) -> bool {
+        !matches!(self, SimultaneousUseDecision::Reject(_))
+    }
+}
+
+/// A policy to enforce the limit of the concurrent sessions per user (a.k.a. Simultaneous-Use).
+///
+/// This is supposed to be consulted in the Access-Request handling, and the sessions are counted by a `SessionStore`
+/// that is maintained by the accounting handling (see also `track_accounting_request()`).
+pub struct SimultaneousUsePolicy<S: SessionStore> {
+    store: Arc<S>,
+    max_sessions: usize,
+    action: SimultaneousUseAction,
+}
+
+impl<S: SessionStore> SimultaneousUsePolicy<S> {
+    /// A constructor for a policy that allows up to `max_sessions` concurrent sessions per user.
+    pub fn new(store: Arc<S>, max_sessions: usize) -> Self {
+        SimultaneousUsePolicy {
+            store,
+            max_sessions,
+            action: SimultaneousUseAction::Reject,
+        }
+    }
+
+    /// Set the action to take when a user exceeds the limit (default: `SimultaneousUseAction::Reject`).
+    pub fn set_action(&mut self, action: SimultaneousUseAction) {
+        self.action = action;
+    }
+
+    /// Check the Access-Request against the limit. A request that doesn't have User-Name is always allowed.
+    pub fn check(&self, request: &Request) -> Result<SimultaneousUseDecision, SessionStoreError> {
+        match rfc2865::lookup_user_name(request.get_packet()) {
+            Some(user_name) => self.check_user(&user_name?, self.max_sessions),
+            None => Ok(SimultaneousUseDecision::Allow),
+        }
+    }
+
+    /// Check the user against the given limit instead of the configured one
+    /// (e.g. a per-user limit that comes from a user database).
+    ///
+    /// If the action is `SimultaneousUseAction::DisconnectOldest`, the decision contains the oldest sessions
+    /// so that the number of the remaining sessions plus the new one doesn't exceed the limit.
+    /// The request is rejected in any case when the limit is `0`.
+    pub fn check_user(
+        &self,
+        user_name: &str,
+        max_sessions: usize,
+    ) -> Result<SimultaneousUseDecision, SessionStoreError> {
+        let mut sessions = self.store.find_by_user_name(user_name)?;
+        if sessions.len() < max_sessions {
+            return Ok(SimultaneousUseDecision::Allow);
+        }
+
+        if self.action == SimultaneousUseAction::Reject || max_sessions == 0 {
+            return Ok(SimultaneousUseDecision::Reject(sessions));
+        }
+
+        sessions.sort_by_key(|session| session.get_started_at());
+        sessions.truncate(sessions.len() - max_sessions + 1);
+        Ok(SimultaneousUseDecision::Disconnect(sessions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+
+    use chrono::{Duration, TimeZone, Utc};
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::core::{rfc2865, rfc2866};
+    use crate::sessions::{
+        InMemorySessionStore, Session, SessionStore, SimultaneousUseAction,
+        SimultaneousUseDecision, SimultaneousUsePolicy,
+    };
+
+    fn make_session(session_id: &str, started_seconds_ago: i64) -> Session {
+        let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+        rfc2866::add_acct_session_id(&mut packet, session_id);
+        rfc2865::add_user_name(&mut packet, "bob");
+        Session::from_packet(
+            &packet,
+            "192.0.2.1:50000".parse::<SocketAddr>().unwrap(),
+            Utc.timestamp_opt(1_600_000_000, 0).unwrap() - Duration::seconds(started_seconds_ago),
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    fn make_access_request(user_name: &str) -> Request {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, user_name);
+        Request::new(
+            "192.0.2.254:1812".parse::<SocketAddr>().unwrap(),
+            "192.0.2.1:50000".parse::<SocketAddr>().unwrap(),
+            packet,
+        )
+    }
+
+    #[test]
+    fn test_check() {
+        let store = Arc::new(InMemorySessionStore::new());
+        store.upsert(make_session("s1", 30)).unwrap();
+        store.upsert(make_session("s2", 20)).unwrap();
+        store.upsert(make_session("s3", 10)).unwrap();
+
+        let mut policy = SimultaneousUsePolicy::new(store, 3);
+        assert_eq!(
+            policy.check(&make_access_request("alice")).unwrap(),
+            SimultaneousUseDecision::Allow
+        );
+
+        let decision = policy.check(&make_access_request("bob")).unwrap();
+        assert!(!decision.is_allowed());
+        assert!(
+            matches!(decision, SimultaneousUseDecision::Reject(sessions) if sessions.len() == 3)
+        );
+
+        policy.set_action(SimultaneousUseAction::DisconnectOldest);
+        let decision = policy.check_user("bob", 2).unwrap();
+        assert!(decision.is_allowed());
+        match decision {
+            SimultaneousUseDecision::Disconnect(sessions) => {
+                let session_ids: Vec<&str> = sessions.iter().map(|s| s.get_session_id()).collect();
+                assert_eq!(session_ids, vec!["s1", "s2"]);
+            }
+            _ => panic!("unexpected decision"),
+        }
+
+        assert!(!policy.check_user("bob", 0).unwrap().is_allowed());
+    }
+}
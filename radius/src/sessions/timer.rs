@@ -0,0 +1,182 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::core::deterministic;
+use crate::sessions::{Session, SessionStore, SessionStoreError};
+
+/// The reason why a session has been expired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionExpiry {
+    /// The session has exceeded its Session-Timeout since the start.
+    SessionTimeout,
+    /// The session hasn't had any accounting packet (e.g. Interim-Update) within its Idle-Timeout.
+    IdleTimeout,
+}
+
+/// A timer that finds the sessions that have exceeded their Session-Timeout or Idle-Timeout.
+///
+/// The expired sessions are removed from the store by default, and they are passed to the callback of `run()`
+/// so that the application can clean up or force-disconnect them (e.g. with `DynamicAuthorizationClient`).
+pub struct SessionTimer<S: SessionStore> {
+    store: Arc<S>,
+    default_idle_timeout: Option<Duration>,
+    remove_expired_sessions: bool,
+}
+
+impl<S: SessionStore> SessionTimer<S> {
+    pub fn new(store: Arc<S>) -> Self {
+        SessionTimer {
+            store,
+            default_idle_timeout: None,
+            remove_expired_sessions: true,
+        }
+    }
+
+    /// Set the idle timeout for the sessions that don't have their own Idle-Timeout (default: `None`, i.e. no idle timeout).
+    ///
+    /// This is useful to clean up the sessions whose Stop has been lost; in that case this should be long enough compared with
+    /// the interim interval of the NAS.
+    pub fn set_default_idle_timeout(&mut self, default_idle_timeout: Option<Duration>) {
+        self.default_idle_timeout = default_idle_timeout;
+    }
+
+    /// Set a flag to specify whether to remove the expired sessions from the store or not (default: `true`).
+    pub fn set_remove_expired_sessions(&mut self, remove_expired_sessions: bool) {
+        self.remove_expired_sessions = remove_expired_sessions;
+    }
+
+    /// Returns the reason if the session has been expired at `now`. Session-Timeout takes precedence over Idle-Timeout.
+    pub fn check_session(&self, session: &Session, now: DateTime<Utc>) -> Option<SessionExpiry> {
+        let exceeds =
+            |since: DateTime<Utc>, timeout: Duration| match chrono::Duration::from_std(timeout) {
+                Ok(timeout) => now - since > timeout,
+                Err(_) => false,
+            };
+
+        if let Some(session_timeout) = session.get_session_timeout() {
+            if exceeds(session.get_started_at(), session_timeout) {
+                return Some(SessionExpiry::SessionTimeout);
+            }
+        }
+        if let Some(idle_timeout) = session.get_idle_timeout().or(self.default_idle_timeout) {
+            if exceeds(session.get_updated_at(), idle_timeout) {
+                return Some(SessionExpiry::IdleTimeout);
+            }
+        }
+        None
+    }
+
+    /// Find the sessions that have been expired at `now` (and remove them if it is enabled).
+    pub fn sweep(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<(Session, SessionExpiry)>, SessionStoreError> {
+        let mut expired = vec![];
+        for session in self.store.list()? {
+            if let Some(expiry) = self.check_session(&session, now) {
+                if self.remove_expired_sessions {
+                    self.store.remove(session.get_key())?;
+                }
+                expired.push((session, expiry));
+            }
+        }
+        Ok(expired)
+    }
+
+    /// Sweep the sessions repeatedly at the given interval and call `on_expired` for each expired session.
+    /// This never returns.
+    pub async fn run<F, Fut>(&self, interval: Duration, on_expired: F)
+    where
+        F: Fn(Session, SessionExpiry) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        loop {
+            tokio::time::sleep(interval).await;
+            match self.sweep(deterministic::now()) {
+                Ok(expired) => {
+                    for (session, expiry) in expired {
+                        debug!(
+                            "session {} on {} has been expired; {:?}",
+                            session.get_session_id(),
+                            session.get_key().get_nas_ip(),
+                            expiry
+                        );
+                        on_expired(session, expiry).await;
+                    }
+                }
+                Err(e) => error!("failed to sweep the expired sessions; {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::{rfc2865, rfc2866};
+    use crate::sessions::{
+        InMemorySessionStore, Session, SessionExpiry, SessionStore, SessionTimer,
+    };
+
+    #[test]
+    fn test_sweep() {
+        let started_at = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let make_session = |session_id: &str, session_timeout: Option<u32>| {
+            let mut packet = Packet::new(Code::AccountingRequest, b"secret");
+            rfc2866::add_acct_session_id(&mut packet, session_id);
+            if let Some(session_timeout) = session_timeout {
+                rfc2865::add_session_timeout(&mut packet, session_timeout);
+            }
+            let mut session = Session::from_packet(
+                &packet,
+                "192.0.2.1:50000".parse::<SocketAddr>().unwrap(),
+                started_at,
+            )
+            .unwrap()
+            .unwrap();
+            session.set_timeouts_from_packet(&packet).unwrap();
+            session
+        };
+
+        let store = Arc::new(InMemorySessionStore::new());
+        store.upsert(make_session("s1", Some(60))).unwrap();
+        store.upsert(make_session("s2", Some(3600))).unwrap();
+        store.upsert(make_session("s3", None)).unwrap();
+
+        let mut timer = SessionTimer::new(store.clone());
+        timer.set_default_idle_timeout(Some(Duration::from_secs(600)));
+
+        let expired = timer
+            .sweep(started_at + chrono::Duration::seconds(61))
+            .unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].0.get_session_id(), "s1");
+        assert_eq!(expired[0].1, SessionExpiry::SessionTimeout);
+
+        let mut expired = timer
+            .sweep(started_at + chrono::Duration::seconds(601))
+            .unwrap();
+        expired.sort_by(|a, b| a.0.get_session_id().cmp(b.0.get_session_id()));
+        assert_eq!(
+            expired
+                .iter()
+                .map(|(session, expiry)| (session.get_session_id(), *expiry))
+                .collect::<Vec<_>>(),
+            vec![
+                ("s2", SessionExpiry::IdleTimeout),
+                ("s3", SessionExpiry::IdleTimeout)
+            ]
+        );
+        assert!(store.list().unwrap().is_empty());
+    }
+}
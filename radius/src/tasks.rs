@@ -0,0 +1,181 @@
+//! The tasks that the crate spawns, and their counts.
+//!
+//! Every task of the crate (e.g. the request workers of the server and the receive loops of the multiplexed client) is
+//! spawned under a stable name, and `get_task_counts()` returns how many of them have been spawned and are alive.
+//!
+//! With the `tracing` feature, each task runs in a `radius.task` span that has the name as the `task` field; when it is
+//! built with `--cfg tokio_unstable` as well (as tokio-console requires), the names are also given to the tokio tasks.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tokio::task::JoinHandle;
+
+/// A kind of the tasks, i.e. a place in the crate that spawns them.
+pub(crate) struct TaskKind {
+    name: &'static str,
+    spawned: AtomicU64,
+    active: AtomicU64,
+}
+
+impl TaskKind {
+    const fn new(name: &'static str) -> Self {
+        TaskKind {
+            name,
+            spawned: AtomicU64::new(0),
+            active: AtomicU64::new(0),
+        }
+    }
+}
+
+/// The worker of a request of `Server`.
+pub(crate) static SERVER_REQUEST: TaskKind = TaskKind::new("radius.server.request");
+/// A worker of the pool of `Server` (see `Concurrency::WorkerPool`).
+pub(crate) static SERVER_WORKER: TaskKind = TaskKind::new("radius.server.worker");
+/// The receive loop of a socket of `MultiplexedClient`.
+pub(crate) static MULTIPLEXED_CLIENT_RECEIVER: TaskKind =
+    TaskKind::new("radius.client.multiplexed.receiver");
+/// A request that `Replayer` is sending.
+#[cfg(feature = "pcap")]
+pub(crate) static REPLAY_SEND: TaskKind = TaskKind::new("radius.capture.replay.send");
+/// The server of a connection of `TcpServer`.
+#[cfg(feature = "tcp")]
+pub(crate) static TCP_CONNECTION: TaskKind = TaskKind::new("radius.tcp.connection");
+/// The server of a connection of `RadSecServer`.
+#[cfg(feature = "radsec-rustls")]
+pub(crate) static RADSEC_CONNECTION: TaskKind = TaskKind::new("radius.radsec.connection");
+/// A connection of `AdminServer`.
+#[cfg(feature = "admin")]
+pub(crate) static ADMIN_CONNECTION: TaskKind = TaskKind::new("radius.admin.connection");
+/// The server of `MockServer`.
+#[cfg(feature = "testing")]
+pub(crate) static MOCK_SERVER: TaskKind = TaskKind::new("radius.testing.mock_server");
+
+fn kinds() -> Vec<&'static TaskKind> {
+    vec![
+        &SERVER_REQUEST,
+        &SERVER_WORKER,
+        &MULTIPLEXED_CLIENT_RECEIVER,
+        #[cfg(feature = "pcap")]
+        &REPLAY_SEND,
+        #[cfg(feature = "tcp")]
+        &TCP_CONNECTION,
+        #[cfg(feature = "radsec-rustls")]
+        &RADSEC_CONNECTION,
+        #[cfg(feature = "admin")]
+        &ADMIN_CONNECTION,
+        #[cfg(feature = "testing")]
+        &MOCK_SERVER,
+    ]
+}
+
+/// The counts of the tasks of a kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskCount {
+    name: &'static str,
+    spawned: u64,
+    active: u64,
+}
+
+impl TaskCount {
+    /// Returns the name of the tasks (e.g. `radius.server.request`).
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the number of the tasks that have been spawned.
+    pub fn get_spawned(&self) -> u64 {
+        self.spawned
+    }
+
+    /// Returns the number of the tasks that are alive, i.e. they have been neither finished nor aborted.
+    pub fn get_active(&self) -> u64 {
+        self.active
+    }
+}
+
+/// Returns the counts of the tasks of the crate by the kind, in the process.
+pub fn get_task_counts() -> Vec<TaskCount> {
+    kinds()
+        .into_iter()
+        .map(|kind| TaskCount {
+            name: kind.name,
+            spawned: kind.spawned.load(Ordering::Relaxed),
+            active: kind.active.load(Ordering::Relaxed),
+        })
+        .collect()
+}
+
+/// Spawn a task of the kind on the current tokio runtime.
+pub(crate) fn spawn<F>(kind: &'static TaskKind, future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    kind.spawned.fetch_add(1, Ordering::Relaxed);
+    kind.active.fetch_add(1, Ordering::Relaxed);
+    let active = Active(kind);
+    let future = async move {
+        let _active = active;
+        future.await
+    };
+
+    #[cfg(feature = "tracing")]
+    let future = tracing::Instrument::instrument(
+        future,
+        tracing::info_span!("radius.task", task = kind.name),
+    );
+
+    #[cfg(all(tokio_unstable, feature = "tracing"))]
+    return tokio::task::Builder::new()
+        .name(kind.name)
+        .spawn(future)
+        .expect("failed to spawn a task");
+    #[cfg(not(all(tokio_unstable, feature = "tracing")))]
+    tokio::spawn(future)
+}
+
+/// Counts the task as active until it is dropped, i.e. until the task has finished or has been aborted.
+struct Active(&'static TaskKind);
+
+impl Drop for Active {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::tasks::{get_task_counts, spawn, TaskKind};
+
+    static TEST_TASK: TaskKind = TaskKind::new("radius.test");
+
+    #[tokio::test]
+    async fn test_counts() {
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let task = spawn(&TEST_TASK, async move {
+            let _ = rx.await;
+        });
+        let aborted = spawn(&TEST_TASK, std::future::pending::<()>());
+        assert_eq!(TEST_TASK.spawned.load(Ordering::Relaxed), 2);
+        assert_eq!(TEST_TASK.active.load(Ordering::Relaxed), 2);
+
+        tx.send(()).unwrap();
+        task.await.unwrap();
+        assert_eq!(TEST_TASK.active.load(Ordering::Relaxed), 1);
+
+        aborted.abort();
+        assert!(aborted.await.unwrap_err().is_cancelled());
+        assert_eq!(TEST_TASK.active.load(Ordering::Relaxed), 0);
+        assert_eq!(TEST_TASK.spawned.load(Ordering::Relaxed), 2);
+
+        let names: Vec<_> = get_task_counts()
+            .iter()
+            .map(|count| count.get_name())
+            .collect();
+        assert!(names.contains(&"radius.server.request"));
+        assert!(names.contains(&"radius.client.multiplexed.receiver"));
+    }
+}
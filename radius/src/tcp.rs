@@ -0,0 +1,318 @@
+//! RADIUS over TCP (RFC 6613).
+//!
+//! RADIUS/TCP carries the RADIUS packets over a TCP connection, delimited by the Length field of the RADIUS header,
+//! with the same shared secrets as the UDP.
+//!
+//! `StreamTransport` is a `Transport` (see `radius::transport`) on top of a connection (`TcpTransport` on
+//! a `TcpStream`), so the client (`GenericClient::send_packet_over()`) speaks RADIUS/TCP with the same API as the UDP;
+//! `TcpServer` accepts the connections and serves the requests on each of them by a `TransportServer`.
+//!
+//! The connection delivers the packets reliably, so set the retransmissions of the client to `0` (the default),
+//! as RFC 6613 requires.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::marker::PhantomData;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::core::packet::{MAX_PACKET_LENGTH, RADIUS_PACKET_HEADER_LENGTH};
+use crate::server::transport::{TransportRequestHandler, TransportServer};
+use crate::server::SecretProvider;
+use crate::tasks;
+use crate::transport::{Peer, Transport};
+
+/// Read a RADIUS packet from the stream, by the Length field of the header.
+///
+/// This returns `None` if the stream has been closed between the packets.
+pub async fn read_packet<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let mut header = [0; 4];
+    match reader.read_exact(&mut header).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    let len = u16::from_be_bytes([header[2], header[3]]) as usize;
+    if !(RADIUS_PACKET_HEADER_LENGTH..=MAX_PACKET_LENGTH).contains(&len) {
+        // the stream can't be delimited anymore
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid RADIUS packet length: {len}"),
+        ));
+    }
+    let mut packet = vec![0; len];
+    packet[..4].copy_from_slice(&header);
+    reader.read_exact(&mut packet[4..]).await?;
+    Ok(Some(packet))
+}
+
+/// Write an encoded RADIUS packet to the stream.
+pub async fn write_packet<W: AsyncWrite + Unpin>(writer: &mut W, packet: &[u8]) -> io::Result<()> {
+    writer.write_all(packet).await?;
+    writer.flush().await
+}
+
+/// A connection of the RADIUS packets as a transport, on top of a stream.
+pub struct StreamTransport<S> {
+    reader: tokio::sync::Mutex<ReadHalf<S>>,
+    writer: tokio::sync::Mutex<WriteHalf<S>>,
+    peer: Peer,
+    local_addr: SocketAddr,
+}
+
+/// A RADIUS/TCP connection.
+pub type TcpTransport = StreamTransport<TcpStream>;
+
+impl<S: AsyncRead + AsyncWrite + Send> StreamTransport<S> {
+    /// A constructor for a connection to the peer.
+    pub fn new(stream: S, peer: Peer) -> Self {
+        let local_addr = match peer.get_addr() {
+            SocketAddr::V4(_) => SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0),
+            SocketAddr::V6(_) => SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0),
+        };
+        let (reader, writer) = tokio::io::split(stream);
+        StreamTransport {
+            reader: tokio::sync::Mutex::new(reader),
+            writer: tokio::sync::Mutex::new(writer),
+            peer,
+            local_addr,
+        }
+    }
+
+    /// Set the local address of the connection (default: the unspecified address), e.g. for `Request::get_local_addr()`.
+    pub fn set_local_addr(&mut self, local_addr: SocketAddr) {
+        self.local_addr = local_addr;
+    }
+
+    /// Returns the peer of the connection.
+    pub fn get_peer(&self) -> &Peer {
+        &self.peer
+    }
+}
+
+impl StreamTransport<TcpStream> {
+    /// Connect to the RADIUS/TCP server.
+    pub async fn connect(remote_addr: SocketAddr) -> io::Result<Self> {
+        Self::from_tcp_stream(TcpStream::connect(remote_addr).await?)
+    }
+
+    /// A constructor for a connection of the TCP stream, with the addresses of that.
+    pub fn from_tcp_stream(stream: TcpStream) -> io::Result<Self> {
+        let peer = Peer::new(stream.peer_addr()?);
+        let local_addr = stream.local_addr()?;
+        let mut transport = Self::new(stream, peer);
+        transport.set_local_addr(local_addr);
+        Ok(transport)
+    }
+}
+
+#[async_trait]
+impl<S: AsyncRead + AsyncWrite + Send> Transport for StreamTransport<S> {
+    async fn send_to(&self, datagram: &[u8], peer: &Peer) -> io::Result<()> {
+        if peer.get_addr() != self.peer.get_addr() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrNotAvailable,
+                format!("the connection is not to {}", peer.get_addr()),
+            ));
+        }
+        write_packet(&mut *self.writer.lock().await, datagram).await
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, Peer)> {
+        let packet = read_packet(&mut *self.reader.lock().await)
+            .await?
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "the connection has been closed",
+                )
+            })?;
+        let len = packet.len().min(buf.len());
+        buf[..len].copy_from_slice(&packet[..len]);
+        Ok((len, self.peer.clone()))
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        Ok(self.local_addr)
+    }
+}
+
+/// A RADIUS/TCP server that serves the requests on each accepted connection.
+///
+/// The handler and the secret provider are shared by the connections.
+pub struct TcpServer<X, E, T, U>
+where
+    E: Debug,
+    T: TransportRequestHandler<TcpTransport, X, E>,
+    U: SecretProvider,
+{
+    listener: TcpListener,
+    request_handler_arc: Arc<T>,
+    secret_provider_arc: Arc<U>,
+    _phantom_return_type: PhantomData<X>,
+    _phantom_error_type: PhantomData<E>,
+}
+
+impl<X, E, T, U> TcpServer<X, E, T, U>
+where
+    X: Send + Sync + 'static,
+    E: Debug + Send + Sync + 'static,
+    T: TransportRequestHandler<TcpTransport, X, E>,
+    U: SecretProvider,
+{
+    /// Starts TCP listening for the RADIUS server (e.g. on `0.0.0.0` and `1812`).
+    pub async fn listen(
+        host: &str,
+        port: u16,
+        request_handler: T,
+        secret_provider: U,
+    ) -> Result<Self, io::Error> {
+        let listener = TcpListener::bind(format!("{host}:{port}")).await?;
+        Ok(TcpServer {
+            listener,
+            request_handler_arc: Arc::new(request_handler),
+            secret_provider_arc: Arc::new(secret_provider),
+            _phantom_return_type: Default::default(),
+            _phantom_error_type: Default::default(),
+        })
+    }
+
+    /// Returns the listening address.
+    pub fn get_listen_address(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Starts accepting the connections and handling the RADIUS requests on them.
+    ///
+    /// This returns when accepting has failed, or when `shutdown_trigger` has been resolved.
+    pub async fn run(&self, shutdown_trigger: impl Future) -> Result<(), io::Error> {
+        tokio::select! {
+            res = self.accept_loop() => {
+                res
+            }
+            _ = shutdown_trigger => {
+                info!("server is shutting down");
+                Ok(())
+            }
+        }
+    }
+
+    async fn accept_loop(&self) -> Result<(), io::Error> {
+        loop {
+            let (stream, remote_addr) = self.listener.accept().await?;
+            let transport = match TcpTransport::from_tcp_stream(stream) {
+                Ok(transport) => transport,
+                Err(e) => {
+                    info!(
+                        "failed to set up the connection from {}; {}",
+                        remote_addr, e
+                    );
+                    continue;
+                }
+            };
+
+            let mut server = TransportServer::new(
+                transport,
+                self.request_handler_arc.clone(),
+                self.secret_provider_arc.clone(),
+            );
+            server.set_buffer_size(MAX_PACKET_LENGTH);
+            tasks::spawn(&tasks::TCP_CONNECTION, async move {
+                if let Err(e) = server.run(std::future::pending::<()>()).await {
+                    debug!("connection from {} has been closed; {}", remote_addr, e);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::request::Request;
+    use crate::runtime::{GenericClient, TokioRuntime};
+    use crate::server::transport::TransportRequestHandler;
+    use crate::server::{SecretProvider, SecretProviderError};
+    use crate::tcp::{read_packet, TcpServer, TcpTransport};
+    use crate::transport::{Peer, Transport};
+
+    struct AcceptingHandler {}
+
+    #[async_trait]
+    impl TransportRequestHandler<TcpTransport, (), io::Error> for AcceptingHandler {
+        async fn handle_radius_request(
+            &self,
+            transport: &TcpTransport,
+            peer: &Peer,
+            req: &Request,
+        ) -> Result<(), io::Error> {
+            let response = req.get_packet().make_response_packet(Code::AccessAccept);
+            transport.send_to(&response.encode().unwrap(), peer).await
+        }
+    }
+
+    struct StaticSecretProvider {}
+
+    impl SecretProvider for StaticSecretProvider {
+        fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+            Ok(b"secret".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tcp_round_trip() {
+        let server =
+            TcpServer::listen("127.0.0.1", 0, AcceptingHandler {}, StaticSecretProvider {})
+                .await
+                .unwrap();
+        let server_addr = server.get_listen_address().unwrap();
+        tokio::spawn(async move { server.run(std::future::pending::<()>()).await });
+
+        let client = GenericClient::new(TokioRuntime, Some(Duration::from_secs(3)));
+        // each connection has its own server
+        for _ in 0..2 {
+            let transport = TcpTransport::connect(server_addr).await.unwrap();
+            for _ in 0..2 {
+                let response = client
+                    .send_packet_over(
+                        &transport,
+                        &Peer::new(server_addr),
+                        &Packet::new(Code::AccessRequest, b"secret"),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.get_code(), Code::AccessAccept);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_packet() {
+        let packet = Packet::new(Code::AccessRequest, b"secret")
+            .encode()
+            .unwrap();
+        let mut stream = [packet.clone(), packet.clone()].concat();
+        stream.extend_from_slice(&[1, 2, 0, 3]);
+        let mut reader = &stream[..];
+        assert_eq!(read_packet(&mut reader).await.unwrap().unwrap(), packet);
+        assert_eq!(read_packet(&mut reader).await.unwrap().unwrap(), packet);
+        assert_eq!(
+            read_packet(&mut reader).await.unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(read_packet(&mut &b""[..]).await.unwrap(), None);
+    }
+}
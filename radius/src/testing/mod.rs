@@ -0,0 +1,421 @@
+//! Utilities to test RADIUS clients against a scripted server (available with the `testing` feature).
+//!
+//! `MockResponder` is a `RequestHandler` that responds according to the `MockRule`s:
+//! each rule matches the requests by predicates, and responds with the canned attributes or a templated packet,
+//! responds after a delay, or deliberately doesn't respond (e.g. to test the retransmission).
+//! `MockServer` runs a `Server` with a responder on a local ephemeral port.
+//!
+//! `strategies` has the [proptest](https://docs.rs/proptest) strategies of valid packets and attributes
+//! to property-test the handlers and the rewrites; `Packet`, `AVP` and `Code` implement `Arbitrary` as well.
+//!
+//! ## Example
+//!
+//! ```
+//! use radius::core::avp::AVP;
+//! use radius::core::code::Code;
+//! use radius::core::rfc2865;
+//! use radius::testing::{MockResponder, MockRule, MockServer};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let responder = MockResponder::new();
+//! responder.add_rule(
+//!     MockRule::respond(Code::AccessAccept)
+//!         .when_user_name("bob")
+//!         .with_attributes(vec![AVP::from_u32(rfc2865::SESSION_TIMEOUT_TYPE, 3600)]),
+//! );
+//! responder.add_rule(MockRule::respond(Code::AccessAccept).when_user_name("admin"));
+//! responder.add_rule(
+//!     MockRule::respond(Code::AccessReject)
+//!         .with_template(|_request, response| rfc2865::add_reply_message(response, "denied")),
+//! );
+//! let server = MockServer::start(responder.clone(), b"secret").await.unwrap();
+//! // send the requests to `server.get_address()`, and then inspect `responder.get_received_requests()`
+//! # }
+//! ```
+
+pub mod strategies;
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::net::UdpSocket;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+use crate::core::avp::{AVPType, AVP};
+use crate::core::chap;
+use crate::core::code::Code;
+use crate::core::packet::Packet;
+use crate::core::request::Request;
+use crate::core::rfc2865;
+use crate::server::{RequestHandler, SecretProvider, SecretProviderError, Server};
+use crate::tasks;
+
+type Predicate = Box<dyn Fn(&Packet) -> bool + Send + Sync>;
+type Template = Box<dyn Fn(&Packet, &mut Packet) + Send + Sync>;
+
+/// A rule of `MockResponder`; the rule that matches a request first is applied.
+pub struct MockRule {
+    response_code: Option<Code>,
+    predicates: Vec<Predicate>,
+    attributes: Vec<AVP>,
+    template: Option<Template>,
+    delay: Option<Duration>,
+    remaining_times: Option<usize>,
+}
+
+impl MockRule {
+    /// A rule that responds with the given code (e.g. `Code::AccessAccept`).
+    pub fn respond(code: Code) -> Self {
+        Self::new(Some(code))
+    }
+
+    /// A rule that doesn't respond to the matched requests.
+    pub fn silence() -> Self {
+        Self::new(None)
+    }
+
+    fn new(response_code: Option<Code>) -> Self {
+        MockRule {
+            response_code,
+            predicates: vec![],
+            attributes: vec![],
+            template: None,
+            delay: None,
+            remaining_times: None,
+        }
+    }
+
+    /// Add a predicate for the requests; a rule matches a request only if all of the predicates are satisfied.
+    /// A rule without predicates matches any request.
+    pub fn when<F: Fn(&Packet) -> bool + Send + Sync + 'static>(mut self, predicate: F) -> Self {
+        self.predicates.push(Box::new(predicate));
+        self
+    }
+
+    /// Add a predicate that the request has the given code.
+    pub fn when_code(self, code: Code) -> Self {
+        self.when(move |packet| packet.get_code() == code)
+    }
+
+    /// Add a predicate that the request has an attribute of the given type and the raw value.
+    pub fn when_attribute(self, typ: AVPType, value: &[u8]) -> Self {
+        let value = value.to_vec();
+        self.when(move |packet| {
+            packet
+                .lookup_all(typ)
+                .iter()
+                .any(|avp| avp.value[..] == value[..])
+        })
+    }
+
+    /// Add a predicate that the request has the given User-Name.
+    pub fn when_user_name(self, user_name: &str) -> Self {
+        let user_name = user_name.to_owned();
+        self.when(move |packet| {
+            matches!(rfc2865::lookup_user_name(packet), Some(Ok(v)) if v == user_name)
+        })
+    }
+
+    /// Add a predicate that the request has the given User-Password (i.e. decrypted with the shared-secret).
+    pub fn when_user_password(self, password: &[u8]) -> Self {
+        let password = password.to_vec();
+        self.when(move |packet| {
+            matches!(rfc2865::lookup_user_password(packet), Some(Ok(v)) if v == password)
+        })
+    }
+
+    /// Add a predicate that the request has the CHAP-Password of the given password (see `radius::core::chap`).
+    pub fn when_chap_password(self, password: &[u8]) -> Self {
+        let password = password.to_vec();
+        self.when(move |packet| {
+            matches!(
+                chap::verify_chap_password(packet, &password),
+                Some(Ok(true))
+            )
+        })
+    }
+
+    /// Add the canned attributes to the response; they are added before the template is applied.
+    pub fn with_attributes(mut self, attributes: Vec<AVP>) -> Self {
+        self.attributes.extend(attributes);
+        self
+    }
+
+    /// Set a template of the response; it is called with the request and the response to fill the attributes.
+    pub fn with_template<F: Fn(&Packet, &mut Packet) + Send + Sync + 'static>(
+        mut self,
+        template: F,
+    ) -> Self {
+        self.template = Some(Box::new(template));
+        self
+    }
+
+    /// Set a delay before the response.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Limit the number of the requests that this rule is applied to; after that, the rule is skipped.
+    pub fn times(mut self, times: usize) -> Self {
+        self.remaining_times = Some(times);
+        self
+    }
+
+    fn matches(&self, packet: &Packet) -> bool {
+        self.remaining_times != Some(0) && self.predicates.iter().all(|p| p(packet))
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    rules: Vec<MockRule>,
+    received_requests: Vec<Packet>,
+}
+
+/// A `RequestHandler` that responds to the requests according to the rules.
+///
+/// The requests that no rule matches are not responded. The responder can be cloned to inspect
+/// the received requests after it is passed to the server.
+#[derive(Clone, Default)]
+pub struct MockResponder {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockResponder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a rule; the rules are evaluated in the order of the addition.
+    pub fn add_rule(&self, rule: MockRule) {
+        self.state.lock().unwrap().rules.push(rule);
+    }
+
+    /// Returns the requests that have been received, in the order of the arrival.
+    pub fn get_received_requests(&self) -> Vec<Packet> {
+        self.state.lock().unwrap().received_requests.clone()
+    }
+
+    /// Make the response to a request according to the rules, and return that with the delay.
+    /// It returns `None` if the request shouldn't be responded.
+    pub fn make_response(&self, request_packet: &Packet) -> Option<(Packet, Option<Duration>)> {
+        let mut state = self.state.lock().unwrap();
+        state.received_requests.push(request_packet.clone());
+
+        let rule = state
+            .rules
+            .iter_mut()
+            .find(|rule| rule.matches(request_packet))?;
+        if let Some(remaining_times) = rule.remaining_times.as_mut() {
+            *remaining_times -= 1;
+        }
+
+        let code = rule.response_code?;
+        let mut response = request_packet.make_response_packet(code);
+        response.extend(rule.attributes.clone());
+        if let Some(template) = &rule.template {
+            template(request_packet, &mut response);
+        }
+        Some((response, rule.delay))
+    }
+}
+
+#[async_trait]
+impl RequestHandler<(), io::Error> for MockResponder {
+    async fn handle_radius_request(
+        &self,
+        conn: &UdpSocket,
+        request: &Request,
+    ) -> Result<(), io::Error> {
+        let (response, delay) = match self.make_response(request.get_packet()) {
+            Some(response) => response,
+            None => return Ok(()),
+        };
+        if let Some(delay) = delay {
+            sleep(delay).await;
+        }
+
+        let encoded = response
+            .encode()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        conn.send_to(&encoded, request.get_remote_addr()).await?;
+        Ok(())
+    }
+}
+
+struct FixedSecretProvider {
+    secret: Vec<u8>,
+}
+
+impl SecretProvider for FixedSecretProvider {
+    fn fetch_secret(&self, _remote_addr: SocketAddr) -> Result<Vec<u8>, SecretProviderError> {
+        Ok(self.secret.clone())
+    }
+}
+
+/// A RADIUS server with a `MockResponder` that listens on an ephemeral port of localhost.
+/// The server stops when this is dropped.
+pub struct MockServer {
+    address: SocketAddr,
+    shutdown_sender: Option<oneshot::Sender<()>>,
+    server_proc: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Start a server with the responder and the shared-secret.
+    pub async fn start(responder: MockResponder, secret: &[u8]) -> Result<Self, io::Error> {
+        let mut server = Server::listen(
+            "127.0.0.1",
+            0,
+            responder,
+            FixedSecretProvider {
+                secret: secret.to_vec(),
+            },
+        )
+        .await?;
+        let address = server.get_listen_address()?;
+
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+        let server_proc = tasks::spawn(&tasks::MOCK_SERVER, async move {
+            if let Err(e) = server.run(shutdown_receiver).await {
+                error!("mock server has stopped unexpectedly: {}", e);
+            }
+        });
+
+        Ok(MockServer {
+            address,
+            shutdown_sender: Some(shutdown_sender),
+            server_proc: Some(server_proc),
+        })
+    }
+
+    pub fn get_address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Stop the server and wait for that.
+    pub async fn shutdown(mut self) {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+        if let Some(server_proc) = self.server_proc.take() {
+            let _ = server_proc.await;
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::client::Client;
+    use crate::core::avp::AVP;
+    use crate::core::chap;
+    use crate::core::code::Code;
+    use crate::core::packet::Packet;
+    use crate::core::rfc2865;
+    use crate::testing::{MockResponder, MockRule, MockServer};
+
+    fn make_request(user_name: &str, password: &[u8]) -> Packet {
+        let mut packet = Packet::new(Code::AccessRequest, b"secret");
+        rfc2865::add_user_name(&mut packet, user_name);
+        rfc2865::add_user_password(&mut packet, password).unwrap();
+        packet
+    }
+
+    #[tokio::test]
+    async fn test_mock_server() {
+        let responder = MockResponder::new();
+        responder.add_rule(MockRule::silence().when_user_name("flaky").times(1));
+        responder.add_rule(
+            MockRule::respond(Code::AccessAccept)
+                .when_user_name("bob")
+                .with_attributes(vec![
+                    AVP::from_u32(rfc2865::SESSION_TIMEOUT_TYPE, 3600),
+                    AVP::from_string(rfc2865::FILTER_ID_TYPE, "staff"),
+                ]),
+        );
+        responder.add_rule(
+            MockRule::respond(Code::AccessAccept)
+                .when_user_password(b"p@ssw0rd")
+                .with_template(|request, response| {
+                    let user_name = rfc2865::lookup_user_name(request).unwrap().unwrap();
+                    rfc2865::add_reply_message(response, &format!("hello {user_name}"));
+                }),
+        );
+        responder.add_rule(MockRule::respond(Code::AccessAccept).when_chap_password(b"chap"));
+        responder.add_rule(
+            MockRule::respond(Code::AccessReject)
+                .when_code(Code::AccessRequest)
+                .with_delay(Duration::from_millis(10)),
+        );
+        let server = MockServer::start(responder.clone(), b"secret")
+            .await
+            .unwrap();
+
+        let mut client = Client::new(None, Some(Duration::from_millis(300)));
+        let response = client
+            .send_packet(&server.get_address(), &make_request("admin", b"p@ssw0rd"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(
+            rfc2865::lookup_reply_message(&response).unwrap().unwrap(),
+            "hello admin"
+        );
+
+        let response = client
+            .send_packet(&server.get_address(), &make_request("admin", b"wrong"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessReject);
+
+        let mut request = Packet::new(Code::AccessRequest, b"secret");
+        chap::add_chap_password(&mut request, 1, b"chap", None);
+        let response = client
+            .send_packet(&server.get_address(), &request)
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+
+        // the first transmission is ignored, so it succeeds with the retransmission
+        client.set_max_retransmissions(1);
+        let response = client
+            .send_packet(&server.get_address(), &make_request("flaky", b"p@ssw0rd"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+
+        let response = client
+            .send_packet(&server.get_address(), &make_request("bob", b"any"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_code(), Code::AccessAccept);
+        assert_eq!(
+            rfc2865::lookup_session_timeout(&response).unwrap().unwrap(),
+            3600
+        );
+        assert_eq!(
+            rfc2865::lookup_filter_id(&response).unwrap().unwrap(),
+            "staff"
+        );
+
+        assert_eq!(responder.get_received_requests().len(), 6);
+        server.shutdown().await;
+    }
+}
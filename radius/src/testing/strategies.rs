@@ -0,0 +1,255 @@
+//! [proptest](https://docs.rs/proptest) strategies of valid packets and attributes.
+//!
+//! The attributes are generated according to the data types of the built-in dictionary (`radius::core::dictionary`),
+//! e.g. the enumerated values of `integer` attributes, so the handlers see realistic inputs;
+//! `avp()` generates the attributes of any type and any value instead.
+//! The generated packets always fit in the maximum packet length.
+//!
+//! ## Example
+//!
+//! ```
+//! use proptest::test_runner::TestRunner;
+//! use radius::core::packet::Packet;
+//! use radius::testing::strategies;
+//!
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&strategies::request_packet(), |packet| {
+//!         let decoded = Packet::decode(&packet.encode().unwrap(), packet.get_secret()).unwrap();
+//!         assert_eq!(decoded.get_attributes(), packet.get_attributes());
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::core::attributes::Attributes;
+use crate::core::avp::AVP;
+use crate::core::code::Code;
+use crate::core::dictionary::{self, AttributeDefinition};
+use crate::core::packet::Packet;
+use crate::core::rfc2869;
+
+/// The maximum number of the attributes of a generated packet; this keeps the packets within the maximum length.
+pub const MAX_ATTRIBUTES: usize = 15;
+
+const MAX_VALUE_LENGTH: usize = 253;
+const REQUEST_CODES: &[Code] = &[
+    Code::AccessRequest,
+    Code::AccountingRequest,
+    Code::StatusServer,
+    Code::DisconnectRequest,
+    Code::CoARequest,
+];
+const RESPONSE_CODES: &[Code] = &[
+    Code::AccessAccept,
+    Code::AccessReject,
+    Code::AccessChallenge,
+    Code::AccountingResponse,
+    Code::DisconnectACK,
+    Code::DisconnectNAK,
+    Code::CoAACK,
+    Code::CoANAK,
+];
+
+/// Generates the codes of the requests and the responses (i.e. not `Invalid` and `Reserved`).
+pub fn code() -> impl Strategy<Value = Code> {
+    select([REQUEST_CODES, RESPONSE_CODES].concat())
+}
+
+/// Generates the codes of the requests.
+pub fn request_code() -> impl Strategy<Value = Code> {
+    select(REQUEST_CODES)
+}
+
+/// Generates the non-empty shared secrets.
+pub fn secret() -> impl Strategy<Value = Vec<u8>> {
+    vec(any::<u8>(), 1..=64)
+}
+
+/// Generates the attributes of any type (except `0`) and any value.
+pub fn avp() -> impl Strategy<Value = AVP> {
+    (1..=u8::MAX, vec(any::<u8>(), 0..=MAX_VALUE_LENGTH))
+        .prop_map(|(typ, value)| AVP::from_bytes(typ, &value))
+}
+
+/// Generates the attributes of the built-in dictionary with the values of their data types.
+///
+/// Message-Authenticator is not generated, since the encoding of a packet computes that.
+pub fn dictionary_avp() -> impl Strategy<Value = AVP> {
+    let definitions = dictionary::attributes()
+        .filter(|definition| definition.get_type() != rfc2869::MESSAGE_AUTHENTICATOR_TYPE)
+        .collect::<Vec<_>>();
+    select(definitions).prop_flat_map(|definition| {
+        attribute_value(definition)
+            .prop_map(move |value| AVP::from_bytes(definition.get_type(), &value))
+    })
+}
+
+/// Generates the encoded values of an attribute according to its data type.
+pub fn attribute_value(definition: &'static AttributeDefinition) -> BoxedStrategy<Vec<u8>> {
+    match definition.get_data_type() {
+        "string" => "\\PC{0,32}".prop_map(String::into_bytes).boxed(),
+        "integer" => {
+            let values: Vec<u32> = dictionary::values()
+                .filter(|v| v.get_attribute_name() == definition.get_name())
+                .map(|v| v.get_value())
+                .collect();
+            let value = if values.is_empty() {
+                any::<u32>().boxed()
+            } else {
+                prop_oneof![3 => select(values), 1 => any::<u32>()].boxed()
+            };
+            value.prop_map(|v| v.to_be_bytes().to_vec()).boxed()
+        }
+        "short" => any::<u16>().prop_map(|v| v.to_be_bytes().to_vec()).boxed(),
+        "integer64" => any::<u64>().prop_map(|v| v.to_be_bytes().to_vec()).boxed(),
+        "date" => any::<u32>().prop_map(|v| v.to_be_bytes().to_vec()).boxed(),
+        "ipaddr" => any::<[u8; 4]>().prop_map(|v| v.to_vec()).boxed(),
+        "ipv6addr" => any::<[u8; 16]>().prop_map(|v| v.to_vec()).boxed(),
+        "ifid" => any::<[u8; 8]>().prop_map(|v| v.to_vec()).boxed(),
+        "ipv6prefix" => (0..=128u8, any::<[u8; 16]>())
+            .prop_map(|(prefix_len, addr)| {
+                let len = usize::from(prefix_len).div_ceil(8);
+                let mut value = vec![0, prefix_len];
+                value.extend_from_slice(&addr[..len]);
+                // the bits outside of the prefix have to be zero
+                if prefix_len % 8 != 0 {
+                    value[len + 1] &= 0xff << (8 - prefix_len % 8);
+                }
+                value
+            })
+            .boxed(),
+        // the Extended-Type, (the flags of the last fragment,) and the value
+        "extended" => (any::<u8>(), vec(any::<u8>(), 0..=MAX_VALUE_LENGTH - 1))
+            .prop_map(|(extended_type, data)| [&[extended_type], &data[..]].concat())
+            .boxed(),
+        "long-extended" => (any::<u8>(), vec(any::<u8>(), 0..=MAX_VALUE_LENGTH - 2))
+            .prop_map(|(extended_type, data)| [&[extended_type, 0], &data[..]].concat())
+            .boxed(),
+        "vsa" => (1..=u32::MAX >> 8, 1..=u8::MAX, vec(any::<u8>(), 0..=240))
+            .prop_map(|(vendor_id, vendor_type, data)| {
+                let mut value = vendor_id.to_be_bytes().to_vec();
+                value.push(vendor_type);
+                value.push(data.len() as u8 + 2);
+                value.extend(data);
+                value
+            })
+            .boxed(),
+        _ => vec(any::<u8>(), 0..=MAX_VALUE_LENGTH).boxed(),
+    }
+}
+
+/// Generates the packets of the given codes that have the given attributes.
+///
+/// The identifier, the authenticator and the secret are random.
+pub fn packet_with(
+    code: impl Strategy<Value = Code>,
+    attribute: impl Strategy<Value = AVP>,
+) -> impl Strategy<Value = Packet> {
+    (
+        code,
+        any::<u8>(),
+        any::<[u8; 16]>(),
+        secret(),
+        vec(attribute, 0..=MAX_ATTRIBUTES),
+    )
+        .prop_map(|(code, identifier, authenticator, secret, attributes)| {
+            let attributes = Attributes(attributes).encode().unwrap();
+            let mut datagram = vec![code as u8, identifier];
+            datagram.extend(((20 + attributes.len()) as u16).to_be_bytes());
+            datagram.extend(authenticator);
+            datagram.extend(attributes);
+            Packet::decode(&datagram, &secret).unwrap()
+        })
+}
+
+/// Generates the packets of any code with the attributes of the built-in dictionary.
+pub fn packet() -> impl Strategy<Value = Packet> {
+    packet_with(code(), dictionary_avp())
+}
+
+/// Generates the request packets with the attributes of the built-in dictionary.
+pub fn request_packet() -> impl Strategy<Value = Packet> {
+    packet_with(request_code(), dictionary_avp())
+}
+
+impl Arbitrary for Code {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Code>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        code().boxed()
+    }
+}
+
+impl Arbitrary for AVP {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<AVP>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        dictionary_avp().boxed()
+    }
+}
+
+impl Arbitrary for Packet {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Packet>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        packet().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::core::code::Code;
+    use crate::core::dictionary;
+    use crate::core::packet::Packet;
+    use crate::testing::strategies;
+
+    proptest! {
+        #[test]
+        fn test_packet_round_trip(packet in any::<Packet>()) {
+            let datagram = packet.encode().unwrap();
+            let decoded = Packet::decode(&datagram, packet.get_secret()).unwrap();
+            prop_assert_eq!(decoded.get_code(), packet.get_code());
+            prop_assert_eq!(decoded.get_identifier(), packet.get_identifier());
+            prop_assert_eq!(decoded.get_attributes(), packet.get_attributes());
+            if packet.get_code() == Code::AccessRequest {
+                prop_assert_eq!(decoded, packet);
+            }
+        }
+
+        #[test]
+        fn test_dictionary_avp(avp in strategies::dictionary_avp()) {
+            let definition = dictionary::lookup_attribute_by_type(avp.get_type()).unwrap();
+            let value = avp.get_value();
+            match definition.get_data_type() {
+                "integer" | "date" => prop_assert_eq!(value.len(), 4),
+                "ipaddr" => prop_assert!(avp.encode_ipv4().is_ok()),
+                "ipv6prefix" => prop_assert!(avp.encode_ipv6_prefix().is_ok()),
+                "string" => prop_assert!(avp.encode_string().is_ok()),
+                _ => prop_assert!(value.len() <= 253),
+            }
+        }
+
+        #[test]
+        fn test_request_packet(packet in strategies::request_packet()) {
+            prop_assert!(matches!(
+                packet.get_code(),
+                Code::AccessRequest
+                    | Code::AccountingRequest
+                    | Code::StatusServer
+                    | Code::DisconnectRequest
+                    | Code::CoARequest
+            ));
+        }
+    }
+}
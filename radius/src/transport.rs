@@ -0,0 +1,172 @@
+//! Pluggable datagram transports of the client and the server.
+//!
+//! `Transport` sends and receives the encoded RADIUS packets with the identity of the peer, so the RADIUS logic can run
+//! over anything that carries datagrams (e.g. QUIC, a userspace network stack or in-memory channels for simulation)
+//! as well as UDP. `GenericClient::send_packet_over()` (see `radius::runtime`) sends the requests through a transport,
+//! and `TransportServer` (see `radius::server::transport`) serves the requests that come from a transport.
+//!
+//! The tokio `UdpSocket` is a transport (with the `tokio` feature), and `channel()` makes a connected pair of
+//! the in-memory transports.
+
+use std::io;
+use std::net::SocketAddr;
+
+use async_trait::async_trait;
+
+/// The remote end of a datagram.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Peer {
+    addr: SocketAddr,
+    identity: Option<String>,
+}
+
+impl Peer {
+    /// Returns a peer that is identified only by the address.
+    pub fn new(addr: SocketAddr) -> Self {
+        Peer {
+            addr,
+            identity: None,
+        }
+    }
+
+    /// Returns a peer that has the identity that the transport has authenticated (e.g. the subject of a certificate).
+    pub fn with_identity(addr: SocketAddr, identity: &str) -> Self {
+        Peer {
+            addr,
+            identity: Some(identity.to_owned()),
+        }
+    }
+
+    /// Returns the address of the peer; the server looks the shared secret up by this (see `SecretProvider`).
+    pub fn get_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the identity of the peer, if the transport has one.
+    pub fn get_identity(&self) -> Option<&str> {
+        self.identity.as_deref()
+    }
+}
+
+/// A transport of the RADIUS datagrams, i.e. the encoded packets.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Send a datagram to the peer.
+    async fn send_to(&self, datagram: &[u8], peer: &Peer) -> io::Result<()>;
+    /// Receive a datagram, and return the number of the bytes and the peer that has sent it.
+    async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, Peer)>;
+    /// Returns the local address of the transport.
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+}
+
+#[cfg(feature = "tokio")]
+#[async_trait]
+impl Transport for tokio::net::UdpSocket {
+    async fn send_to(&self, datagram: &[u8], peer: &Peer) -> io::Result<()> {
+        tokio::net::UdpSocket::send_to(self, datagram, peer.get_addr()).await?;
+        Ok(())
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, Peer)> {
+        let (len, addr) = tokio::net::UdpSocket::recv_from(self, buf).await?;
+        Ok((len, Peer::new(addr)))
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        tokio::net::UdpSocket::local_addr(self)
+    }
+}
+
+/// An end of the in-memory transports that are made by `channel()`.
+///
+/// Like UDP, the datagrams to the other addresses than the other end are discarded, and a datagram that is longer than
+/// the receive buffer is truncated.
+#[cfg(feature = "tokio")]
+pub struct ChannelTransport {
+    local_addr: SocketAddr,
+    remote_addr: SocketAddr,
+    sender: tokio::sync::mpsc::UnboundedSender<Vec<u8>>,
+    receiver: tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>>,
+}
+
+/// Returns a connected pair of the in-memory transports, which have the addresses `a` and `b`.
+#[cfg(feature = "tokio")]
+pub fn channel(a: SocketAddr, b: SocketAddr) -> (ChannelTransport, ChannelTransport) {
+    let (a_sender, b_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let (b_sender, a_receiver) = tokio::sync::mpsc::unbounded_channel();
+    (
+        ChannelTransport {
+            local_addr: a,
+            remote_addr: b,
+            sender: a_sender,
+            receiver: tokio::sync::Mutex::new(a_receiver),
+        },
+        ChannelTransport {
+            local_addr: b,
+            remote_addr: a,
+            sender: b_sender,
+            receiver: tokio::sync::Mutex::new(b_receiver),
+        },
+    )
+}
+
+#[cfg(feature = "tokio")]
+#[async_trait]
+impl Transport for ChannelTransport {
+    async fn send_to(&self, datagram: &[u8], peer: &Peer) -> io::Result<()> {
+        if peer.get_addr() != self.remote_addr {
+            debug!(
+                "discarding a datagram to unknown address {}",
+                peer.get_addr()
+            );
+            return Ok(());
+        }
+        self.sender.send(datagram.to_vec()).map_err(|_| {
+            io::Error::new(io::ErrorKind::BrokenPipe, "the other end has been dropped")
+        })
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, Peer)> {
+        let datagram = self.receiver.lock().await.recv().await.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::BrokenPipe, "the other end has been dropped")
+        })?;
+        let len = datagram.len().min(buf.len());
+        buf[..len].copy_from_slice(&datagram[..len]);
+        Ok((len, Peer::new(self.remote_addr)))
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        Ok(self.local_addr)
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use crate::transport::{channel, Peer, Transport};
+
+    #[tokio::test]
+    async fn test_channel() {
+        let (a, b) = channel(
+            "127.0.0.1:1".parse().unwrap(),
+            "127.0.0.1:2".parse().unwrap(),
+        );
+        a.send_to(b"unknown", &Peer::new("127.0.0.1:3".parse().unwrap()))
+            .await
+            .unwrap();
+        a.send_to(b"hello", &Peer::new("127.0.0.1:2".parse().unwrap()))
+            .await
+            .unwrap();
+
+        let mut buf = [0; 4];
+        let (len, peer) = b.recv_from(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], b"hell");
+        assert_eq!(peer, Peer::new("127.0.0.1:1".parse().unwrap()));
+        assert_eq!(b.local_addr().unwrap(), "127.0.0.1:2".parse().unwrap());
+
+        drop(a);
+        assert_eq!(
+            b.recv_from(&mut buf).await.unwrap_err().kind(),
+            std::io::ErrorKind::BrokenPipe
+        );
+    }
+}